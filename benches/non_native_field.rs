@@ -0,0 +1,151 @@
+#![allow(
+    dead_code, // OK for benches
+    incomplete_features,
+)]
+#![feature(allocator_api)]
+#![feature(generic_const_exprs)]
+
+// Compares synthesis of non-native field arithmetic over 16 and 32 bit limbs.
+// Number of rows used is printed once per configuration, and synthesis time is measured
+
+use std::sync::Arc;
+
+use boojum::config::DevCSConfig;
+use boojum::cs::cs_builder::new_builder;
+use boojum::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+use boojum::cs::gates::*;
+use boojum::cs::traits::cs::ConstraintSystem;
+use boojum::cs::traits::gate::GatePlacementStrategy;
+use boojum::cs::{CSGeometry, LookupParameters};
+use boojum::dag::CircuitResolverOpts;
+use boojum::field::goldilocks::GoldilocksField;
+use boojum::gadgets::non_native_field::implementations::*;
+use boojum::gadgets::non_native_field::traits::NonNativeField;
+use boojum::gadgets::tables::range_check_16_bits::{
+    create_range_check_16_bits_table, RangeCheck16BitsTable,
+};
+use boojum::pairing::ff::PrimeField;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+type F = GoldilocksField;
+
+// number of multiply-add steps in the synthesized chain
+const CHAIN_LENGTH: usize = 16;
+
+type BN254Fq = boojum::pairing::bn256::Fq;
+type Secp256k1Fq = boojum::gadgets::curves::secp256k1::Fq;
+
+// Synthesizes x = x * b + a repeated `CHAIN_LENGTH` times and returns number of rows used
+fn synthesize<T: PrimeField, NN: NonNativeField<F, T>>(params: &Arc<NN::Params>) -> usize {
+    let geometry = CSGeometry {
+        num_columns_under_copy_permutation: 60,
+        num_witness_columns: 0,
+        num_constant_columns: 4,
+        max_allowed_constraint_degree: 4,
+    };
+
+    let builder_impl =
+        CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20);
+    let builder = new_builder::<_, F>(builder_impl);
+
+    let builder = builder.allow_lookup(
+        LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+            width: 1,
+            num_repetitions: 10,
+            share_table_id: true,
+        },
+    );
+
+    let builder = ConstantsAllocatorGate::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = ReductionGate::<F, 4>::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = DotProductGate::<4>::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = UIntXAddGate::<16>::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder =
+        SelectionGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+    let builder =
+        NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+    let mut owned_cs = builder.build(CircuitResolverOpts::new(1 << 22));
+
+    let table = create_range_check_16_bits_table();
+    owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+    let cs = &mut owned_cs;
+
+    let a_value = T::from_str("1234567890123456789").unwrap();
+    let mut b_value = a_value;
+    b_value.square();
+
+    let mut a = NN::allocate_checked(cs, a_value, params);
+    let mut b = NN::allocate_checked(cs, b_value, params);
+
+    let mut x = a.clone();
+    for _ in 0..CHAIN_LENGTH {
+        let mut product = x.mul(cs, &mut b);
+        x = product.add(cs, &mut a);
+    }
+    x.normalize(cs);
+
+    cs.next_available_row()
+}
+
+fn bench_field<T: PrimeField, NN: NonNativeField<F, T>>(
+    c: &mut Criterion,
+    name: &str,
+    params: NN::Params,
+) {
+    let params = Arc::new(params);
+    let rows = synthesize::<T, NN>(&params);
+    println!(
+        "{}: {} rows for {} multiply-add steps",
+        name, rows, CHAIN_LENGTH
+    );
+
+    c.bench_function(name, |b| b.iter(|| synthesize::<T, NN>(&params)));
+}
+
+fn criterion_benchmark_non_native_field(c: &mut Criterion) {
+    bench_field::<BN254Fq, NonNativeFieldOverU16<F, BN254Fq, 17>>(
+        c,
+        "BN254 base field over u16 limbs",
+        NonNativeFieldOverU16Params::create(),
+    );
+    bench_field::<BN254Fq, NonNativeFieldOverU32<F, BN254Fq, 8>>(
+        c,
+        "BN254 base field over u32 limbs",
+        NonNativeFieldOverU32Params::create(),
+    );
+    bench_field::<Secp256k1Fq, NonNativeFieldOverU16<F, Secp256k1Fq, 17>>(
+        c,
+        "secp256k1 base field over u16 limbs",
+        NonNativeFieldOverU16Params::create(),
+    );
+    bench_field::<Secp256k1Fq, NonNativeFieldOverU32<F, Secp256k1Fq, 8>>(
+        c,
+        "secp256k1 base field over u32 limbs",
+        NonNativeFieldOverU32Params::create(),
+    );
+}
+
+criterion_group!(
+    name = non_native_field;
+    config = Criterion::default().sample_size(10);
+    targets = criterion_benchmark_non_native_field
+);
+criterion_main!(non_native_field);
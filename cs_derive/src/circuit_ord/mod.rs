@@ -0,0 +1,105 @@
+use proc_macro2::Span;
+use proc_macro_error::abort_call_site;
+use quote::quote;
+use syn::{
+    parse_macro_input, punctuated::Punctuated, token::Comma, DeriveInput, GenericParam, Generics,
+    Type, WhereClause,
+};
+
+use crate::utils::*;
+
+const BOUND_ATTR_NAME: &'static str = "CSCircuitOrdBound";
+
+pub(crate) fn derive_circuit_ord(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let derived_input = parse_macro_input!(input as DeriveInput);
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        attrs,
+        ..
+    } = derived_input.clone();
+
+    let extra_bound = if let Some(bound) = fetch_attr_from_list(BOUND_ATTR_NAME, &attrs) {
+        let bound = syn::parse_str::<WhereClause>(&bound).expect("must parse bound as WhereClause");
+
+        Some(bound)
+    } else {
+        None
+    };
+
+    let bound = merge_where_clauses(generics.where_clause.clone(), extra_bound);
+
+    let mut field_idents = vec![];
+    match data {
+        syn::Data::Struct(ref struct_data) => match struct_data.fields {
+            syn::Fields::Named(ref named_fields) => {
+                for field in named_fields.named.iter() {
+                    let field_ident = field.ident.clone().expect("should have a field elem ident");
+                    match field.ty {
+                        Type::Array(_) | Type::Path(_) => {}
+                        _ => abort_call_site!("only array and path types are allowed"),
+                    };
+                    field_idents.push(field_ident);
+                }
+            }
+            _ => abort_call_site!("only named fields are allowed!"),
+        },
+        _ => abort_call_site!("only struct types are allowed!"),
+    }
+
+    // fields are compared lexicographically in the order of declaration, so we start
+    // from the last one: a < b if it's smaller in the current field, or the current
+    // fields are equal and the rest is smaller
+    let last_field = match field_idents.pop() {
+        Some(field_ident) => field_ident,
+        None => abort_call_site!("at least one field is required"),
+    };
+    let mut field_comparisons = quote! {
+        let result = CircuitOrd::<F>::less_than(cs, &a.#last_field, &b.#last_field);
+    };
+    for field_ident in field_idents.iter().rev() {
+        let field_comparison = quote! {
+            let less = CircuitOrd::<F>::less_than(cs, &a.#field_ident, &b.#field_ident);
+            let equal = CircuitEq::<F>::equals(cs, &a.#field_ident, &b.#field_ident);
+            let rest_is_less = equal.and(cs, result);
+            let result = less.or(cs, rest_is_less);
+        };
+        field_comparisons.extend(field_comparison);
+    }
+
+    let comma = Comma(Span::call_site());
+
+    let field_generic_param = syn::parse_str::<GenericParam>(&"F: SmallField").unwrap();
+    let has_engine_param = has_proper_small_field_parameter(&generics.params, &field_generic_param);
+    if has_engine_param == false {
+        panic!("Expected to have `F: SmallField` somewhere in bounds");
+    }
+
+    // add CS to func generic params
+    let mut function_generic_params = Punctuated::new();
+    let cs_generic_param = syn::parse_str::<GenericParam>(&"CS: ConstraintSystem<F>").unwrap();
+    function_generic_params.push(cs_generic_param.clone());
+    function_generic_params.push_punct(comma.clone());
+
+    let type_params_of_allocated_struct = get_type_params_from_generics(&generics, &comma);
+
+    let function_generics = Generics {
+        lt_token: Some(syn::token::Lt(Span::call_site())),
+        params: function_generic_params,
+        gt_token: Some(syn::token::Gt(Span::call_site())),
+        where_clause: None,
+    };
+
+    let expanded = quote! {
+        impl #generics CircuitOrd<F> for #ident<#type_params_of_allocated_struct> #bound {
+            fn less_than #function_generics(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+                #field_comparisons
+
+                result
+            }
+        }
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
@@ -3,6 +3,8 @@
 use proc_macro::TokenStream;
 
 mod allocatable;
+mod circuit_eq;
+mod circuit_ord;
 mod selectable;
 pub(crate) mod utils;
 mod var_length_encodable;
@@ -31,3 +33,15 @@ pub fn derive_witness_hook(input: TokenStream) -> TokenStream {
 pub fn derive_var_length_encodable(input: TokenStream) -> TokenStream {
     self::var_length_encodable::derive_var_length_encodable(input)
 }
+
+#[proc_macro_derive(CSCircuitEq, attributes(CSCircuitEqBound))]
+#[proc_macro_error::proc_macro_error]
+pub fn derive_circuit_eq(input: TokenStream) -> TokenStream {
+    self::circuit_eq::derive_circuit_eq(input)
+}
+
+#[proc_macro_derive(CSCircuitOrd, attributes(CSCircuitOrdBound))]
+#[proc_macro_error::proc_macro_error]
+pub fn derive_circuit_ord(input: TokenStream) -> TokenStream {
+    self::circuit_ord::derive_circuit_ord(input)
+}
@@ -1,9 +1,11 @@
 use crate::algebraic_props::matrix_parameters::MatrixParameters;
 use crate::cs::traits::cs::DstBuffer;
+use crate::field::babybear::BabyBearField;
 use crate::field::goldilocks::GoldilocksField;
 use crate::field::PrimeField;
 use crate::implementations::poseidon2;
 use crate::implementations::poseidon2::Poseidon2Goldilocks;
+use crate::implementations::poseidon2_babybear::{self, Poseidon2BabyBear};
 use crate::implementations::poseidon_goldilocks_params;
 use derivative::*;
 
@@ -126,3 +128,41 @@ impl Poseidon2Parameters<GoldilocksField, 8, 12, 4> for Poseidon2Goldilocks {
         }
     }
 }
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Poseidon2BabyBearExternalMatrix;
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Poseidon2BabyBearInnerMatrix;
+
+impl MatrixParameters<BabyBearField, 16> for Poseidon2BabyBearExternalMatrix {
+    const COEFFS: [[BabyBearField; 16]; 16] = poseidon2_babybear::params::EXTERNAL_MDS_MATRIX;
+}
+
+impl MatrixParameters<BabyBearField, 16> for Poseidon2BabyBearInnerMatrix {
+    const COEFFS: [[BabyBearField; 16]; 16] = poseidon2_babybear::params::INNER_ROUNDS_MATRIX;
+}
+
+impl Poseidon2Parameters<BabyBearField, 8, 16, 8> for Poseidon2BabyBear {
+    const NUM_ROUNDS: usize = poseidon2_babybear::params::TOTAL_NUM_ROUNDS;
+    const NUM_PARTIAL_ROUNDS: usize = poseidon2_babybear::params::NUM_PARTIAL_ROUNDS;
+    const NUM_FULL_ROUNDS: usize = poseidon2_babybear::params::NUM_FULL_ROUNDS_TOTAL;
+    const HALF_NUM_FULL_ROUNDS: usize = poseidon2_babybear::params::HALF_NUM_FULL_ROUNDS;
+    const FULL_NUM_ROUNDS: usize = poseidon2_babybear::params::TOTAL_NUM_ROUNDS;
+    const NONLINEARITY_DEGREE: usize = poseidon2_babybear::params::NONLINEARITY_DEGREE;
+
+    type ExternalMatrixParams = Poseidon2BabyBearExternalMatrix;
+    type InternalMatrixParams = Poseidon2BabyBearInnerMatrix;
+
+    #[inline]
+    fn full_round_constants() -> &'static [[BabyBearField; 16]] {
+        &poseidon2_babybear::params::FULL_ROUND_CONSTANTS[..]
+    }
+
+    #[inline]
+    fn inner_round_constants() -> &'static [BabyBearField] {
+        &poseidon2_babybear::params::PARTIAL_ROUND_CONSTANTS[..]
+    }
+}
@@ -14,6 +14,10 @@ pub trait CSWitnessEvaluationConfig:
 
 pub trait CSDebugConfig: 'static + Send + Sync + Clone + Copy + std::fmt::Debug {
     const PERFORM_RUNTIME_ASSERTS: bool = true;
+    /// Record source locations (and named scopes) where variables are allocated and gates are placed
+    const TRACK_CALL_SITES: bool = false;
+    /// Account resources (rows, gates, variables, lookups) used within every named namespace
+    const TRACK_NAMESPACES: bool = false;
 }
 
 pub trait CSSetupConfig: 'static + Send + Sync + Clone + Copy + std::fmt::Debug {
@@ -53,6 +57,7 @@ pub struct DoPerformRuntimeAsserts;
 
 impl CSDebugConfig for DoPerformRuntimeAsserts {
     const PERFORM_RUNTIME_ASSERTS: bool = true;
+    const TRACK_NAMESPACES: bool = true;
 }
 
 #[derive(Derivative)]
@@ -63,6 +68,16 @@ impl CSDebugConfig for DontPerformRuntimeAsserts {
     const PERFORM_RUNTIME_ASSERTS: bool = false;
 }
 
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+pub struct DoPerformRuntimeAssertsAndTrackCallSites;
+
+impl CSDebugConfig for DoPerformRuntimeAssertsAndTrackCallSites {
+    const PERFORM_RUNTIME_ASSERTS: bool = true;
+    const TRACK_CALL_SITES: bool = true;
+    const TRACK_NAMESPACES: bool = true;
+}
+
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug)]
 pub struct DoKeepSetup;
@@ -98,6 +113,19 @@ impl CSConfig for DevCSConfig {
     type ResolverConfig = Resolver<DoPerformRuntimeAsserts>;
 }
 
+/// Same as `DevCSConfig`, but also records call sites of every variable and gate,
+/// so unsatisfied constraints and gate statistics can be attributed to source lines
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+pub struct DevCSConfigWithCallSites;
+
+impl CSConfig for DevCSConfigWithCallSites {
+    type WitnessConfig = DoEvaluateWitenss;
+    type DebugConfig = DoPerformRuntimeAssertsAndTrackCallSites;
+    type SetupConfig = DoKeepSetup;
+    type ResolverConfig = Resolver<DoPerformRuntimeAsserts>;
+}
+
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug)]
 pub struct ProvingCSConfig;
@@ -4,6 +4,8 @@ use crate::config::*;
 
 use crate::cs::gates::lookup_marker::LookupFormalGate;
 use crate::cs::gates::LookupTooling;
+use crate::cs::implementations::call_sites::CallSitesTracker;
+use crate::cs::implementations::namespaces::NamespacesTracker;
 use crate::cs::implementations::reference_cs::INITIAL_LOOKUP_TABLE_ID_VALUE;
 use crate::dag::DefaultCircuitResolver;
 use crate::{
@@ -455,6 +457,8 @@ impl<
             evaluation_data_over_general_purpose_columns,
             evaluation_data_over_specialized_columns,
             specialized_gates_rough_stats: HashMap::with_capacity(16),
+            call_sites: CallSitesTracker::default(),
+            namespaces: NamespacesTracker::default(),
             gates_application_sets,
             copy_permutation_data,
             witness_placement_data,
@@ -469,6 +473,7 @@ impl<
             lookup_multiplicities: Vec::with_capacity(8),
             table_ids_as_variables: Vec::with_capacity(32),
             public_inputs: Vec::with_capacity(8),
+            blinding_rows: 0..0,
             max_trace_len,
             static_toolbox: builder.toolbox,
             row_cleanups,
@@ -21,17 +21,21 @@ use crate::{
     field::{FieldExtension, SmallField},
 };
 
-pub struct CsVerifierBuilder<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
+pub struct CsVerifierBuilder<
+    F: SmallField,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+> {
     pub parameters: CSGeometry,
     pub lookup_parameters: LookupParameters,
 
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT, N>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT, N>>,
     pub(crate) general_purpose_evaluators_comparison_functions:
         HashMap<TypeId, Vec<(GateBatchEvaluationComparisonFunction, usize)>>,
 
@@ -40,7 +44,9 @@ pub struct CsVerifierBuilder<F: SmallField, EXT: FieldExtension<2, BaseField = F
     pub(crate) total_num_constants_for_specialized_columns: usize,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> CsVerifierBuilder<F, EXT> {
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    CsVerifierBuilder<F, EXT, N>
+{
     pub fn new_from_parameters(parameters: CSGeometry) -> Self {
         Self {
             parameters,
@@ -60,10 +66,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> CsVerifierBuilder<F,
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
-    CsBuilderImpl<F, CsVerifierBuilder<F, EXT>> for CsVerifierBuilder<F, EXT>
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    CsBuilderImpl<F, CsVerifierBuilder<F, EXT, N>> for CsVerifierBuilder<F, EXT, N>
 {
-    type Final<GC: GateConfigurationHolder<F>, TB: StaticToolboxHolder> = Verifier<F, EXT>;
+    type Final<GC: GateConfigurationHolder<F>, TB: StaticToolboxHolder> = Verifier<F, EXT, N>;
 
     type BuildParams<'a> = ();
 
@@ -406,7 +412,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
     ) -> Self::Final<GC, TB> {
         let this = builder.implementation;
 
-        let new = VerifierProxy::<F, EXT, GC, TB> {
+        let new = VerifierProxy::<F, EXT, GC, TB, N> {
             parameters: this.parameters,
             lookup_parameters: this.lookup_parameters,
 
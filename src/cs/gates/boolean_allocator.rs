@@ -153,6 +153,7 @@ impl BooleanConstraintGate {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -202,6 +203,7 @@ impl BooleanConstraintGate {
         }
     }
 
+    #[track_caller]
     pub fn alloc_boolean_from_witness<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         witness_value: bool,
@@ -221,6 +223,7 @@ impl BooleanConstraintGate {
         new_var
     }
 
+    #[track_caller]
     pub fn enforce_boolean<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         variable: Variable,
@@ -170,6 +170,7 @@ impl BoundedBooleanConstraintGate {
         builder.allow_gate(placement_strategy, params, None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -202,6 +203,7 @@ impl BoundedBooleanConstraintGate {
         }
     }
 
+    #[track_caller]
     pub fn alloc_boolean_from_witness<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         witness_value: bool,
@@ -222,6 +224,7 @@ impl BoundedBooleanConstraintGate {
         new_var
     }
 
+    #[track_caller]
     pub fn enforce_boolean<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         variable: Variable,
@@ -171,6 +171,7 @@ impl<F: SmallField> BoundedConstantsAllocatorGate<F> {
         }
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -215,6 +216,7 @@ impl<F: SmallField> BoundedConstantsAllocatorGate<F> {
         // cs.place_variable(self.variable_with_constant_value, row, offset);
     }
 
+    #[track_caller]
     pub fn allocate_constant<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         _constant_to_add: F,
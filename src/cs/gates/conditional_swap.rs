@@ -193,6 +193,7 @@ impl<const N: usize> ConditionalSwapGate<N> {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn conditionally_swap<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: &[Variable; N],
@@ -249,6 +250,7 @@ impl<const N: usize> ConditionalSwapGate<N> {
         (output_variables_a, output_variables_b)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -190,6 +190,7 @@ impl<F: SmallField> ConstantsAllocatorGate<F> {
             )
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -249,6 +250,7 @@ impl<F: SmallField> ConstantsAllocatorGate<F> {
         }
     }
 
+    #[track_caller]
     pub fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant_to_add: F) -> Variable {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -113,6 +113,7 @@ impl<F: SmallField, P: field::traits::field_like::PrimeFieldLike> Gate<F, P>
 
     type GateNewVariables = ();
     type GateValueEvaluationResult = ();
+    #[track_caller]
     fn add<CS: ConstraintSystem<F, EVALUATE_WITNESS, P>, const EVALUATE_WITNESS: bool>(
         self,
         cs: &mut CS,
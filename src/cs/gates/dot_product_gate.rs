@@ -185,6 +185,7 @@ where
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn compute_dot_product<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         terms: [(Variable, Variable); N],
@@ -229,6 +230,7 @@ where
         output_variable
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
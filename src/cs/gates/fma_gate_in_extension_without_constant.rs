@@ -3,19 +3,60 @@ use crate::field::{ExtensionField, FieldExtension};
 
 use super::*;
 
-// A simple gate of c0 * A * B + c1 * C -> D in the extension field
+// A simple gate of c0 * A * B + c1 * C -> D in the extension field. Extension is binomial
+// of degree N, so coefficients are reduced by the non-residue
 
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct FmaGateInExtensionWithoutConstantConstraintEvaluator<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
 > {
     _marker: std::marker::PhantomData<(F, EXT)>,
 }
 
-impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluator<F>
-    for FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT>
+impl<F: PrimeField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT, N>
+{
+    // A, B, C and D
+    const PRINCIPAL_WIDTH: usize = 4 * N;
+    // coefficients of the quadratic and linear terms
+    const NUM_CONSTANTS: usize = 2 * N;
+}
+
+// schoolbook multiplication in the binomial extension
+#[inline(always)]
+fn mul_in_extension<
+    F: PrimeField,
+    P: field::traits::field_like::PrimeFieldLike<Base = F>,
+    const N: usize,
+>(
+    a: &[P; N],
+    b: &[P; N],
+    non_residue: &P,
+    ctx: &mut P::Context,
+) -> [P; N] {
+    let mut result = [(); N].map(|_| P::zero(ctx));
+    let mut to_reduce = [(); N].map(|_| P::zero(ctx));
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            if i + j < N {
+                P::mul_and_accumulate_into(&mut result[i + j], a, b, ctx);
+            } else {
+                P::mul_and_accumulate_into(&mut to_reduce[i + j - N], a, b, ctx);
+            }
+        }
+    }
+    for (dst, src) in result.iter_mut().zip(to_reduce.iter()) {
+        P::mul_and_accumulate_into(dst, src, non_residue, ctx);
+    }
+
+    result
+}
+
+impl<F: PrimeField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    GateConstraintEvaluator<F> for FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT, N>
 {
     type UniqueParameterizationParams = ();
 
@@ -31,15 +72,22 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
 
     #[inline]
     fn type_name() -> std::borrow::Cow<'static, str> {
-        Cow::Borrowed(UNIQUE_IDENTIFIER)
+        if N == 2 {
+            Cow::Borrowed(UNIQUE_IDENTIFIER)
+        } else {
+            Cow::Owned(format!(
+                "c0 * A * B + c1 * C -> D in extension of degree {}",
+                N
+            ))
+        }
     }
 
     #[inline]
     fn instance_width(&self) -> GatePrincipalInstanceWidth {
         GatePrincipalInstanceWidth {
-            num_variables: 8,
+            num_variables: Self::PRINCIPAL_WIDTH,
             num_witnesses: 0,
-            num_constants: 4,
+            num_constants: Self::NUM_CONSTANTS,
         }
     }
 
@@ -47,7 +95,7 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
     fn gate_purpose() -> GatePurpose {
         GatePurpose::Evaluatable {
             max_constraint_degree: 3,
-            num_quotient_terms: 2,
+            num_quotient_terms: N,
         }
     }
 
@@ -55,7 +103,7 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
     fn placement_type(&self) -> GatePlacementType {
         GatePlacementType::MultipleOnRow {
             per_chunk_offset: PerChunkOffset {
-                variables_offset: PRINCIPAL_WIDTH,
+                variables_offset: Self::PRINCIPAL_WIDTH,
                 witnesses_offset: 0,
                 constants_offset: 0,
             },
@@ -64,16 +112,16 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
 
     #[inline]
     fn num_repetitions_in_geometry(&self, geometry: &CSGeometry) -> usize {
-        debug_assert!(geometry.num_columns_under_copy_permutation >= PRINCIPAL_WIDTH);
+        debug_assert!(geometry.num_columns_under_copy_permutation >= Self::PRINCIPAL_WIDTH);
 
-        geometry.num_columns_under_copy_permutation / PRINCIPAL_WIDTH
+        geometry.num_columns_under_copy_permutation / Self::PRINCIPAL_WIDTH
     }
 
     #[inline]
     fn num_required_constants_in_geometry(&self, geometry: &CSGeometry) -> usize {
-        debug_assert!(geometry.num_constant_columns >= 4);
+        debug_assert!(geometry.num_constant_columns >= Self::NUM_CONSTANTS);
 
-        4
+        Self::NUM_CONSTANTS
     }
 
     type GlobalConstants<P: field::traits::field_like::PrimeFieldLike<Base = F>> = [P; 1];
@@ -88,7 +136,8 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
         [non_residue]
     }
 
-    type RowSharedConstants<P: field::traits::field_like::PrimeFieldLike<Base = F>> = [P; 4];
+    // coefficients of the quadratic term, followed by the ones of the linear term
+    type RowSharedConstants<P: field::traits::field_like::PrimeFieldLike<Base = F>> = [[P; N]; 2];
 
     #[inline(always)]
     fn load_row_shared_constants<
@@ -99,18 +148,10 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
         trace_source: &S,
         _ctx: &mut P::Context,
     ) -> Self::RowSharedConstants<P> {
-        let quadratic_term_coeff_c0 = trace_source.get_constant_value(0);
-        let quadratic_term_coeff_c1 = trace_source.get_constant_value(1);
-
-        let linear_term_coeff_c0 = trace_source.get_constant_value(2);
-        let linear_term_coeff_c1 = trace_source.get_constant_value(3);
-
-        [
-            quadratic_term_coeff_c0,
-            quadratic_term_coeff_c1,
-            linear_term_coeff_c0,
-            linear_term_coeff_c1,
-        ]
+        let quadratic_term_coeff = std::array::from_fn(|i| trace_source.get_constant_value(i));
+        let linear_term_coeff = std::array::from_fn(|i| trace_source.get_constant_value(N + i));
+
+        [quadratic_term_coeff, linear_term_coeff]
     }
 
     #[inline(always)]
@@ -126,77 +167,27 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
         global_constants: &Self::GlobalConstants<P>,
         ctx: &mut P::Context,
     ) {
-        let a_c0 = trace_source.get_variable_value(0);
-        let a_c1 = trace_source.get_variable_value(1);
-        let b_c0 = trace_source.get_variable_value(2);
-        let b_c1 = trace_source.get_variable_value(3);
-        let c_c0 = trace_source.get_variable_value(4);
-        let c_c1 = trace_source.get_variable_value(5);
-        let d_c0 = trace_source.get_variable_value(6);
-        let d_c1 = trace_source.get_variable_value(7);
-
-        let [quadratic_term_coeff_c0, quadratic_term_coeff_c1, linear_term_coeff_c0, linear_term_coeff_c1] =
-            shared_constants;
-
-        let [non_residue] = global_constants;
-
-        // linear part is easy
-
-        // c0 * c0
-        let mut linear_c0 = c_c0;
-        linear_c0.mul_assign(linear_term_coeff_c0, ctx);
-
-        // c1 * c1 * non-res
-        let mut t = c_c1;
-        t.mul_assign(linear_term_coeff_c1, ctx);
-        P::mul_and_accumulate_into(&mut linear_c0, &t, non_residue, ctx);
-
-        // c0 * c1
-        let mut linear_c1 = c_c0;
-        linear_c1.mul_assign(linear_term_coeff_c1, ctx);
-        // c1 * c0
-        P::mul_and_accumulate_into(&mut linear_c1, &c_c1, linear_term_coeff_c0, ctx);
-
-        // compute first term of c0 * A * B
+        let a: [P; N] = std::array::from_fn(|i| trace_source.get_variable_value(i));
+        let b: [P; N] = std::array::from_fn(|i| trace_source.get_variable_value(N + i));
+        let c: [P; N] = std::array::from_fn(|i| trace_source.get_variable_value(2 * N + i));
+        let d: [P; N] = std::array::from_fn(|i| trace_source.get_variable_value(3 * N + i));
 
-        let mut inner_c0 = a_c0;
-        inner_c0.mul_assign(&b_c0, ctx);
+        let [quadratic_term_coeff, linear_term_coeff] = shared_constants;
 
-        let mut t = a_c1;
-        t.mul_assign(&b_c1, ctx);
-        P::mul_and_accumulate_into(&mut inner_c0, &t, non_residue, ctx);
-
-        let mut inner_c1 = a_c0;
-        inner_c1.mul_assign(&b_c1, ctx);
-        P::mul_and_accumulate_into(&mut inner_c1, &a_c1, &b_c0, ctx);
-
-        // and use those to make next term
-
-        let mut final_c0 = inner_c0;
-        final_c0.mul_assign(quadratic_term_coeff_c0, ctx);
-
-        let mut t = inner_c1;
-        t.mul_assign(quadratic_term_coeff_c1, ctx);
-        P::mul_and_accumulate_into(&mut final_c0, &t, non_residue, ctx);
-
-        let mut final_c1 = inner_c0;
-        final_c1.mul_assign(quadratic_term_coeff_c1, ctx);
-        P::mul_and_accumulate_into(&mut final_c1, &inner_c1, quadratic_term_coeff_c0, ctx);
-
-        // add linear part
+        let [non_residue] = global_constants;
 
-        final_c0.add_assign(&linear_c0, ctx);
-        final_c1.add_assign(&linear_c1, ctx);
+        let linear = mul_in_extension(&c, linear_term_coeff, non_residue, ctx);
+        let inner = mul_in_extension(&a, &b, non_residue, ctx);
+        let quadratic = mul_in_extension(&inner, quadratic_term_coeff, non_residue, ctx);
 
         // output contributions
 
-        let mut contribution = final_c0;
-        contribution.sub_assign(&d_c0, ctx);
-        destination.push_evaluation_result(contribution, ctx);
-
-        let mut contribution = final_c1;
-        contribution.sub_assign(&d_c1, ctx);
-        destination.push_evaluation_result(contribution, ctx);
+        for ((quadratic, linear), d) in quadratic.iter().zip(linear.iter()).zip(d.iter()) {
+            let mut contribution = *quadratic;
+            contribution.add_assign(linear, ctx);
+            contribution.sub_assign(d, ctx);
+            destination.push_evaluation_result(contribution, ctx);
+        }
     }
 }
 
@@ -204,42 +195,46 @@ impl<F: PrimeField, EXT: FieldExtension<2, BaseField = F>> GateConstraintEvaluat
 #[derivative(Clone, Copy, Debug, PartialEq(bound = ""), Eq(bound = ""), Hash)]
 pub struct FmaGateInExtensionWithoutConstantParams<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
 > {
-    pub coeff_for_quadtaric_part: ExtensionField<F, 2, EXT>,
-    pub linear_term_coeff: ExtensionField<F, 2, EXT>,
+    pub coeff_for_quadtaric_part: ExtensionField<F, N, EXT>,
+    pub linear_term_coeff: ExtensionField<F, N, EXT>,
 }
 
 #[derive(Derivative)]
 #[derivative(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct FmaGateInExtensionWithoutConstant<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
-    pub params: FmaGateInExtensionWithoutConstantParams<F, EXT>,
-    pub quadratic_part: ([Variable; 2], [Variable; 2]),
-    pub linear_part: [Variable; 2],
-    pub rhs_part: [Variable; 2],
+pub struct FmaGateInExtensionWithoutConstant<
+    F: SmallField,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+> {
+    pub params: FmaGateInExtensionWithoutConstantParams<F, EXT, N>,
+    pub quadratic_part: ([Variable; N], [Variable; N]),
+    pub linear_part: [Variable; N],
+    pub rhs_part: [Variable; N],
 }
 
 const UNIQUE_IDENTIFIER: &str = "c0 * A * B + c1 * C -> D in quadratic extension";
-const PRINCIPAL_WIDTH: usize = 8;
 
 // HashMap coefficients into row index to know vacant places
-type FmaInExtensionGateTooling<F, EXT> = (
+type FmaInExtensionGateTooling<F, EXT, const N: usize> = (
     usize,
-    HashMap<FmaGateInExtensionWithoutConstantParams<F, EXT>, (usize, usize)>,
+    HashMap<FmaGateInExtensionWithoutConstantParams<F, EXT, N>, (usize, usize)>,
 );
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Gate<F>
-    for FmaGateInExtensionWithoutConstant<F, EXT>
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>> Gate<F>
+    for FmaGateInExtensionWithoutConstant<F, EXT, N>
 {
     #[inline(always)]
     fn check_compatible_with_cs<CS: ConstraintSystem<F>>(&self, cs: &CS) -> bool {
         let geometry = cs.get_params();
         geometry.max_allowed_constraint_degree >= 3
-            && geometry.num_columns_under_copy_permutation >= 8
-            && geometry.num_constant_columns >= 4
+            && geometry.num_columns_under_copy_permutation >= FmaGateInExtensionWithoutConstantConstraintEvaluator::<F, EXT, N>::PRINCIPAL_WIDTH
+            && geometry.num_constant_columns >= FmaGateInExtensionWithoutConstantConstraintEvaluator::<F, EXT, N>::NUM_CONSTANTS
     }
 
-    type Evaluator = FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT>;
+    type Evaluator = FmaGateInExtensionWithoutConstantConstraintEvaluator<F, EXT, N>;
 
     #[inline]
     fn evaluator(&self) -> Self::Evaluator {
@@ -249,8 +244,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Gate<F>
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
-    FmaGateInExtensionWithoutConstant<F, EXT>
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    FmaGateInExtensionWithoutConstant<F, EXT, N>
 {
     pub fn configure_builder<
         GC: GateConfigurationHolder<F>,
@@ -264,7 +259,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         TImpl,
         F,
         (
-            GateTypeEntry<F, Self, FmaInExtensionGateTooling<F, EXT>>,
+            GateTypeEntry<F, Self, FmaInExtensionGateTooling<F, EXT, N>>,
             GC,
         ),
         TB,
@@ -272,6 +267,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         builder.allow_gate(placement_strategy, (), (0, HashMap::with_capacity(16)))
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -279,27 +275,27 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
             return;
         }
 
+        // A, B, C and D are placed one after another
         let all_variables = [
-            self.quadratic_part.0[0],
-            self.quadratic_part.0[1],
-            self.quadratic_part.1[0],
-            self.quadratic_part.1[1],
-            self.linear_part[0],
-            self.linear_part[1],
-            self.rhs_part[0],
-            self.rhs_part[1],
+            self.quadratic_part.0,
+            self.quadratic_part.1,
+            self.linear_part,
+            self.rhs_part,
         ];
+        for variables in all_variables.iter() {
+            assert_no_placeholder_variables(variables);
+        }
 
         match cs.get_gate_placement_strategy::<Self>() {
             GatePlacementStrategy::UseGeneralPurposeColumns => {
                 let offered_row_idx = cs.next_available_row();
                 let capacity_per_row = self.capacity_per_row(&*cs);
                 let tooling: &mut HashMap<
-                    FmaGateInExtensionWithoutConstantParams<F, EXT>,
+                    FmaGateInExtensionWithoutConstantParams<F, EXT, N>,
                     (usize, usize),
                 > = &mut cs
                     .get_gates_config_mut()
-                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT>>()
+                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT, N>>()
                     .expect("gate must be allowed")
                     .1;
                 let (row, num_instances_already_placed) =
@@ -307,22 +303,16 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                 drop(tooling);
 
                 // now we can use methods of CS to inform it of low level operations
-                let offset = num_instances_already_placed * PRINCIPAL_WIDTH;
+                let offset = num_instances_already_placed * FmaGateInExtensionWithoutConstantConstraintEvaluator::<F, EXT, N>::PRINCIPAL_WIDTH;
                 if offered_row_idx == row {
                     cs.place_gate(&self, row);
+                    // this gate used same constants per row only, so those are placed once
+                    cs.place_constants(&self.params.coeff_for_quadtaric_part.coeffs, row, 0);
+                    cs.place_constants(&self.params.linear_term_coeff.coeffs, row, N);
+                }
+                for (idx, variables) in all_variables.iter().enumerate() {
+                    cs.place_multiple_variables_into_row(variables, row, offset + idx * N);
                 }
-                cs.place_constants(
-                    &[
-                        self.params.coeff_for_quadtaric_part.coeffs[0],
-                        self.params.coeff_for_quadtaric_part.coeffs[1],
-                        self.params.linear_term_coeff.coeffs[0],
-                        self.params.linear_term_coeff.coeffs[1],
-                    ],
-                    row,
-                    0,
-                ); // this gate used same constants per row only
-                assert_no_placeholder_variables(&all_variables);
-                cs.place_multiple_variables_into_row(&all_variables, row, offset);
             }
             GatePlacementStrategy::UseSpecializedColumns {
                 num_repetitions,
@@ -330,9 +320,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
             } => {
                 // gate knows how to place itself
                 let capacity_per_row = num_repetitions;
-                let t: &mut FmaInExtensionGateTooling<F, EXT> = cs
+                let t: &mut FmaInExtensionGateTooling<F, EXT, N> = cs
                     .get_gates_config_mut()
-                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT>>()
+                    .get_aux_data_mut::<Self, FmaInExtensionGateTooling<F, EXT, N>>()
                     .expect("gate must be allowed");
 
                 let (next_available_row, tooling) = (&mut t.0, &mut t.1);
@@ -343,38 +333,42 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                     capacity_per_row,
                 );
                 cs.place_gate_specialized(&self, num_instances_already_placed, row);
-                cs.place_constants_specialized::<Self, 4>(
-                    &[
-                        self.params.coeff_for_quadtaric_part.coeffs[0],
-                        self.params.coeff_for_quadtaric_part.coeffs[1],
-                        self.params.linear_term_coeff.coeffs[0],
-                        self.params.linear_term_coeff.coeffs[1],
-                    ],
+                // this gate used same constants per row only
+                cs.place_constants_specialized::<Self, N>(
+                    &self.params.coeff_for_quadtaric_part.coeffs,
                     num_instances_already_placed,
                     row,
                     0,
-                ); // this gate used same constants per row only
-                assert_no_placeholder_variables(&all_variables);
-                cs.place_multiple_variables_into_row_specialized::<Self, 8>(
-                    &all_variables,
+                );
+                cs.place_constants_specialized::<Self, N>(
+                    &self.params.linear_term_coeff.coeffs,
                     num_instances_already_placed,
                     row,
-                    0,
+                    N,
                 );
+                for (idx, variables) in all_variables.iter().enumerate() {
+                    cs.place_multiple_variables_into_row_specialized::<Self, N>(
+                        variables,
+                        num_instances_already_placed,
+                        row,
+                        idx * N,
+                    );
+                }
             }
         }
     }
 
+    #[track_caller]
     pub fn compute_fma_in_extension<CS: ConstraintSystem<F>>(
         cs: &mut CS,
-        coeff_for_quadtaric_part: ExtensionField<F, 2, EXT>,
-        ab: ([Variable; 2], [Variable; 2]),
-        linear_term_coeff: ExtensionField<F, 2, EXT>,
-        c: [Variable; 2],
-    ) -> [Variable; 2] {
+        coeff_for_quadtaric_part: ExtensionField<F, N, EXT>,
+        ab: ([Variable; N], [Variable; N]),
+        linear_term_coeff: ExtensionField<F, N, EXT>,
+        c: [Variable; N],
+    ) -> [Variable; N] {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
-        let output_variables = cs.alloc_multiple_variables_without_values::<2>();
+        let output_variables = cs.alloc_multiple_variables_without_values::<N>();
 
         let params = FmaGateInExtensionWithoutConstantParams {
             coeff_for_quadtaric_part,
@@ -382,11 +376,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         };
 
         if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
-            let value_fn = move |inputs: [F; 6]| {
-                let [a_c0, a_c1, b_c0, b_c1, c_c0, c_c1] = inputs;
-                let a = ExtensionField::<F, 2, EXT>::from_coeff_in_base([a_c0, a_c1]);
-                let b = ExtensionField::<F, 2, EXT>::from_coeff_in_base([b_c0, b_c1]);
-                let c = ExtensionField::<F, 2, EXT>::from_coeff_in_base([c_c0, c_c1]);
+            let value_fn = move |inputs: &[F], buffer: &mut DstBuffer<'_, '_, F>| {
+                debug_assert_eq!(inputs.len(), 3 * N);
+                let [a, b, c] = [0, 1, 2].map(|idx| {
+                    ExtensionField::<F, N, EXT>::from_coeff_in_base(std::array::from_fn(|i| {
+                        inputs[idx * N + i]
+                    }))
+                });
 
                 let mut result = params.coeff_for_quadtaric_part;
                 use crate::field::traits::field::Field;
@@ -397,13 +393,17 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
 
                 result.add_assign(&tmp);
 
-                result.into_coeffs_in_base()
+                buffer.extend(result.into_coeffs_in_base());
             };
 
-            let dependencies =
-                Place::from_variables([ab.0[0], ab.0[1], ab.1[0], ab.1[1], c[0], c[1]]);
+            let dependencies: Vec<Place> =
+                ab.0.iter()
+                    .chain(ab.1.iter())
+                    .chain(c.iter())
+                    .map(|el| Place::from_variable(*el))
+                    .collect();
 
-            cs.set_values_with_dependencies(
+            cs.set_values_with_dependencies_vararg(
                 &dependencies,
                 &Place::from_variables(output_variables),
                 value_fn,
@@ -424,12 +424,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         output_variables
     }
 
+    #[track_caller]
     pub fn create_inversion_constraint<CS: ConstraintSystem<F>>(
         cs: &mut CS,
-        variable_to_inverse: [Variable; 2],
+        variable_to_inverse: [Variable; N],
         zero_variable: Variable,
         one_variable: Variable, // we need constant `1` (from any other consideration) as RHS
-    ) -> [Variable; 2] {
+    ) -> [Variable; N] {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
         use crate::field::traits::field::Field;
@@ -443,11 +444,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
         // the only thing that we needed was to create a variable with some index.
         // When we are interested in proving only we are not interested in placement of such variable,
         // and instead only need index and value
-        let output_variables = cs.alloc_multiple_variables_without_values::<2>();
+        let output_variables = cs.alloc_multiple_variables_without_values::<N>();
 
         if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
-            let value_fn = move |inputs: [F; 2]| {
-                let value = ExtensionField::<F, 2, EXT>::from_coeff_in_base(inputs);
+            let value_fn = move |inputs: [F; N]| {
+                let value = ExtensionField::<F, N, EXT>::from_coeff_in_base(inputs);
                 let inverse = value.inverse().unwrap();
 
                 inverse.into_coeffs_in_base()
@@ -467,7 +468,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                 params,
                 quadratic_part: (variable_to_inverse, output_variables),
                 linear_part: variable_to_inverse, // not important
-                rhs_part: [one_variable, zero_variable],
+                rhs_part: std::array::from_fn(
+                    |i| if i == 0 { one_variable } else { zero_variable },
+                ),
             };
             this.add_to_cs(cs);
         }
@@ -193,6 +193,7 @@ impl<F: SmallField> FmaGateInBaseFieldWithoutConstant<F> {
         builder.allow_gate(placement_strategy, (), (0, HashMap::with_capacity(16)))
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -276,6 +277,7 @@ impl<F: SmallField> FmaGateInBaseFieldWithoutConstant<F> {
         }
     }
 
+    #[track_caller]
     pub fn compute_fma<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         coeff_for_quadtaric_part: F,
@@ -329,6 +331,7 @@ impl<F: SmallField> FmaGateInBaseFieldWithoutConstant<F> {
         output_variable
     }
 
+    #[track_caller]
     pub fn create_inversion_constraint<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         variable_to_inverse: Variable,
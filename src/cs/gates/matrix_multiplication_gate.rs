@@ -170,6 +170,7 @@ impl<F: SmallField, const N: usize, PAR: MatrixParameters<F, N>>
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -209,6 +210,7 @@ impl<F: SmallField, const N: usize, PAR: MatrixParameters<F, N>>
         }
     }
 
+    #[track_caller]
     pub fn compute_multiplication<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         input: [Variable; N],
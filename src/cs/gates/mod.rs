@@ -11,6 +11,8 @@ use crate::cs::traits::gate::GatePlacementStrategy;
 use crate::field::PrimeField;
 use std::borrow::Cow;
 
+#[cfg(test)]
+pub(crate) mod testing_cs;
 pub mod testing_tools;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -54,11 +56,13 @@ impl<F: SmallField, CS: ConstraintSystem<F>> BooleanAllocatableCS<F> for CS {
 
 // Trait to allocate variables that are literal constants
 pub trait ConstantAllocatableCS<F: SmallField> {
+    #[track_caller]
     fn allocate_constant(&mut self, constant: F) -> Variable;
 }
 
 // default extension implementation
 impl<F: SmallField, CS: ConstraintSystem<F>> ConstantAllocatableCS<F> for CS {
+    #[track_caller]
     fn allocate_constant(&mut self, constant: F) -> Variable {
         if self.gate_is_allowed::<ConstantsAllocatorGate<F>>() {
             ConstantsAllocatorGate::allocate_constant(self, constant)
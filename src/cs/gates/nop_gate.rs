@@ -130,6 +130,7 @@ impl NopGate {
         builder.allow_gate(placement_strategy, (), ())
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         if <CS::Config as CSConfig>::SetupConfig::KEEP_SETUP == false {
             return;
@@ -176,6 +176,7 @@ impl<const N: usize> ParallelSelectionGate<N> {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn select<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: &[Variable; N],
@@ -224,6 +225,7 @@ impl<const N: usize> ParallelSelectionGate<N> {
         output_variables
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -527,6 +527,7 @@ where
         (max_instances, (copiable_vars_per_copy, in_witness_per_copy))
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS, temporary_places: Vec<Place>) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -740,6 +741,7 @@ where
         }
     }
 
+    #[track_caller]
     pub fn compute_round_function<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         elements_to_absorb: [Variable; AW],
@@ -810,6 +812,7 @@ where
         output_variables
     }
 
+    #[track_caller]
     pub fn enforce_round_function<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         initial_state: [Variable; SW],
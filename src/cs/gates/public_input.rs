@@ -48,6 +48,7 @@ impl PublicInputGate {
 
     // If we allocate public input separately from the moment of knowing it's value
     // we can use this helper function to copy witness. NOTE: caller must ensure equality constraint by himself!
+    #[track_caller]
     pub fn assign_witness_value<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         from_variable: Variable,
@@ -64,6 +65,7 @@ impl PublicInputGate {
         )
     }
 
+    #[track_caller]
     pub fn reserve_public_input_location<F: SmallField, CS: ConstraintSystem<F>>(cs: &mut CS) {
         if <CS::Config as CSConfig>::SetupConfig::KEEP_SETUP == false {
             return;
@@ -101,6 +103,7 @@ impl PublicInputGate {
         }
     }
 
+    #[track_caller]
     pub fn use_reserved_public_input_location<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         var: Variable,
@@ -127,6 +130,7 @@ impl PublicInputGate {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         if <CS::Config as CSConfig>::SetupConfig::KEEP_SETUP == false {
             return;
@@ -151,6 +151,7 @@ impl<const N: usize> QuadraticCombinationGate<N> {
     }
 
     // evaluation of witness must be done by the caller
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -183,6 +183,7 @@ impl<F: SmallField, const N: usize> ReductionByPowersGate<F, N> {
         }
     }
 
+    #[track_caller]
     pub fn reduce_terms<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         reduction_constant: F,
@@ -225,6 +226,7 @@ impl<F: SmallField, const N: usize> ReductionByPowersGate<F, N> {
         output_variable
     }
 
+    #[track_caller]
     pub fn decompose_into_limbs<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         limb_size: F,
@@ -271,6 +273,7 @@ impl<F: SmallField, const N: usize> ReductionByPowersGate<F, N> {
         output_variables
     }
 
+    #[track_caller]
     pub fn decompose_into_limbs_limited<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         limb_size: F,
@@ -324,6 +327,7 @@ impl<F: SmallField, const N: usize> ReductionByPowersGate<F, N> {
         output_variables
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
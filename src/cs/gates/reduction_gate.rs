@@ -184,6 +184,7 @@ impl<F: SmallField, const N: usize> ReductionGate<F, N> {
         builder.allow_gate(placement_strategy, (), (0, HashMap::new()))
     }
 
+    #[track_caller]
     pub fn reduce_terms<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         reduction_constants: [F; N],
@@ -224,6 +225,7 @@ impl<F: SmallField, const N: usize> ReductionGate<F, N> {
         output_variable
     }
 
+    #[track_caller]
     pub fn decompose_into_limbs<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         limb_size: F,
@@ -278,6 +280,7 @@ impl<F: SmallField, const N: usize> ReductionGate<F, N> {
         output_variables
     }
 
+    #[track_caller]
     pub fn decompose_into_limbs_limited<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         limb_size: F,
@@ -343,6 +346,7 @@ impl<F: SmallField, const N: usize> ReductionGate<F, N> {
         output_variables
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
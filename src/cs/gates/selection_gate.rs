@@ -169,6 +169,7 @@ impl SelectionGate {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn select<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -213,6 +214,7 @@ impl SelectionGate {
         output_variable
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -262,6 +262,7 @@ impl<F: SmallField, const N: usize> SimpleNonlinearityGate<F, N> {
         builder.allow_gate(placement_strategy, (), (0, HashMap::with_capacity(16)))
     }
 
+    #[track_caller]
     pub fn add_to_cs<CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -334,6 +335,7 @@ impl<F: SmallField, const N: usize> SimpleNonlinearityGate<F, N> {
         }
     }
 
+    #[track_caller]
     pub fn apply_nonlinearity<CS: ConstraintSystem<F>>(
         cs: &mut CS,
         x: Variable,
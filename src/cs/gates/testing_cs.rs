@@ -0,0 +1,119 @@
+use super::*;
+use crate::cs::cs_builder::{new_builder, CsBuilder, CsBuilderImpl};
+use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+use crate::dag::CircuitResolverOpts;
+use crate::gadgets::tables::range_check_16_bits::{
+    create_range_check_16_bits_table, RangeCheck16BitsTable,
+};
+
+// widths of UIntXAddGate that a gadget test needs, e.g. `(UIntXAddGate<32>, UIntXAddGate<16>)`
+pub(crate) trait UIntXAddWidths<F: SmallField> {
+    fn configure_builder<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder>;
+}
+
+impl<F: SmallField, const WIDTH: usize> UIntXAddWidths<F> for UIntXAddGate<WIDTH> {
+    fn configure_builder<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        UIntXAddGate::<WIDTH>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        )
+    }
+}
+
+impl<F: SmallField, A: UIntXAddWidths<F>, B: UIntXAddWidths<F>> UIntXAddWidths<F> for (A, B) {
+    fn configure_builder<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        let builder = A::configure_builder(builder);
+
+        B::configure_builder(builder)
+    }
+}
+
+// CS for gadget tests: general purpose gates over 60 columns, lookups of the given
+// width, and the 16 bit range check table. Other tables are added by the caller
+pub(crate) fn create_test_cs<F: SmallField, W: UIntXAddWidths<F>>(
+    max_trace_len: usize,
+    lookup_width: usize,
+    lookup_num_repetitions: usize,
+    max_variables: usize,
+) -> CSReferenceImplementation<
+    F,
+    F,
+    DevCSConfig,
+    impl GateConfigurationHolder<F>,
+    impl StaticToolboxHolder,
+> {
+    let geometry = CSGeometry {
+        num_columns_under_copy_permutation: 60,
+        num_witness_columns: 0,
+        num_constant_columns: 4,
+        max_allowed_constraint_degree: 4,
+    };
+
+    let builder_impl =
+        CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, max_trace_len);
+    let builder = new_builder::<_, F>(builder_impl);
+
+    let builder = builder.allow_lookup(
+        crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+            width: lookup_width,
+            num_repetitions: lookup_num_repetitions,
+            share_table_id: true,
+        },
+    );
+    let builder = ConstantsAllocatorGate::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = BooleanConstraintGate::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = ReductionGate::<F, 4>::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = DotProductGate::<4>::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = W::configure_builder(builder);
+    let builder =
+        SelectionGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+    let builder = ZeroCheckGate::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+        false,
+    );
+    let builder =
+        NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+    let mut owned_cs = builder.build(CircuitResolverOpts::new(max_variables));
+
+    let table = create_range_check_16_bits_table();
+    owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+
+    owned_cs
+}
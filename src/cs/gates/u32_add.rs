@@ -158,6 +158,7 @@ impl<F: SmallField> Gate<F> for U32AddGate {
 }
 
 impl U32AddGate {
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -211,6 +212,7 @@ impl U32AddGate {
         }
     }
 
+    #[track_caller]
     pub fn perform_addition<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -272,6 +274,7 @@ impl U32AddGate {
     // a - b + 2^N * borrow_out - borrow_in = c -> a + 2^N * borrow_out = b + c + borrow_in
     // can be re-arranged into the same relation
     // Caller is responsible to range-check the output variable
+    #[track_caller]
     pub fn perform_subtraction<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -342,6 +342,7 @@ impl U8x4FMAGate {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -447,6 +448,7 @@ impl U8x4FMAGate {
         }
     }
 
+    #[track_caller]
     pub fn perform_fma<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a_decomposition: [Variable; 4],
@@ -166,6 +166,7 @@ impl U32SubGate {
         }
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -219,6 +220,7 @@ impl U32SubGate {
         }
     }
 
+    #[track_caller]
     pub fn perform_subtraction<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
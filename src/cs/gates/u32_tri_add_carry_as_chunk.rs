@@ -222,6 +222,7 @@ impl U32TriAddCarryAsChunkGate {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(F::CAPACITY_BITS >= 34);
         debug_assert!(cs.gate_is_allowed::<Self>());
@@ -310,6 +311,7 @@ impl U32TriAddCarryAsChunkGate {
         }
     }
 
+    #[track_caller]
     pub fn perform_addition<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: [Variable; 4],
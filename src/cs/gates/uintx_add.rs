@@ -183,6 +183,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
         builder.allow_gate(placement_strategy, (), None)
     }
 
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(F::CAPACITY_BITS >= WIDTH + 1);
         debug_assert!(WIDTH == 8 || WIDTH == 16 || WIDTH == 32);
@@ -245,6 +246,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
         }
     }
 
+    #[track_caller]
     pub fn perform_addition<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -302,6 +304,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
         (output_variables[0], output_variables[1])
     }
 
+    #[track_caller]
     pub fn perform_addition_no_carry<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -366,6 +369,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
     // a - b + 2^N * borrow_out - borrow_in = c -> a + 2^N * borrow_out = b + c + borrow_in
     // can be re-arranged into the same relation
     // Caller is responsible to range-check the output variable
+    #[track_caller]
     pub fn perform_subtraction<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -426,6 +430,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
         (output_variables[0], output_variables[1])
     }
 
+    #[track_caller]
     pub fn perform_subtraction_no_borrow<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -488,6 +493,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
         output_variable
     }
 
+    #[track_caller]
     pub fn enforce_add_relation_compute_carry<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -546,6 +552,7 @@ impl<const WIDTH: usize> UIntXAddGate<WIDTH> {
         output_variable
     }
 
+    #[track_caller]
     pub fn perform_subtraction_with_expected_borrow_out<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         a: Variable,
@@ -407,6 +407,7 @@ pub struct ZeroCheckFinalizationHint {
 }
 
 impl ZeroCheckGate {
+    #[track_caller]
     pub fn add_to_cs<F: SmallField, CS: ConstraintSystem<F>>(self, cs: &mut CS) {
         debug_assert!(cs.gate_is_allowed::<Self>());
 
@@ -514,6 +515,7 @@ impl ZeroCheckGate {
         builder.allow_gate(placement_strategy, use_witness_for_inversion, None)
     }
 
+    #[track_caller]
     pub fn check_if_zero<F: SmallField, CS: ConstraintSystem<F>>(
         cs: &mut CS,
         var_to_check: Variable,
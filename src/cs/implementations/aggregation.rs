@@ -0,0 +1,887 @@
+use super::fri::do_fri;
+use super::fri::QuerySource;
+use super::polynomial::lde::{ArcGenericLdeStorage, GenericLdeStorage};
+use super::polynomial::{BitreversedLagrangeForm, GenericPolynomial};
+use super::polynomial_storage::{SetupBaseStorage, SetupStorage};
+use super::pow::PoWRunner;
+use super::proof::{
+    AggregatedCircuitProof, AggregatedProof, AggregatedSingleRoundQueries, OracleQuery,
+    SharedOraclesCaps, SharedOraclesQueries,
+};
+use super::prover::{
+    compute_fri_schedule_for_config, query_fri_oracles, run_pow, u64_from_lsb_first_bits,
+    ProofConfig, ProverStage, ProverState,
+};
+use super::reference_cs::CSReferenceAssembly;
+use super::transcript::{BoolsBuffer, Transcript};
+use super::utils::{chunk_columns_mut, materialize_x_poly_as_arc_lde};
+use super::verifier::{
+    verify_oracle_query, CircuitOpenings, FriVerificationContext, OracleType, VerificationError,
+    VerificationKey, Verifier,
+};
+use super::witness::WitnessSet;
+use super::*;
+
+use crate::config::CSConfig;
+use crate::cs::oracle::merkle_tree::{MerkleProofSource, MerkleTreeWithCap};
+use crate::cs::oracle::TreeHasher;
+use crate::field::ExtensionField;
+use crate::field::Field;
+use crate::field::FieldExtension;
+use std::alloc::Global;
+
+// Aggregation of several circuits, possibly of different sizes, under a single FRI.
+//
+// Up to the DEEP openings every circuit is proven with its own transcript that starts
+// from its setup cap, but circuits with the same trace length are committed in lockstep:
+// witness, second stage and quotient oracles of all of them are committed by one tree per
+// stage, whose leafs are concatenations of the leafs of every circuit, and every circuit
+// draws the challenges of the next stage after the cap of the shared tree. So a query opens
+// three shared paths per group of circuits of the same size, and a setup path per circuit,
+// as setup trees are fixed by the verification keys of every circuit.
+//
+// All the oracles are committed over the same LDE domain: a circuit with a trace of n_i rows
+// is extended by a factor of L * N / n_i, where N is the largest trace length and L is the LDE
+// factor of the proof. Oracles are enumerated in bitreversed order, so the same leaf index
+// corresponds to the same point for every circuit, and one query opens all of them.
+//
+// Then a single transcript absorbs all the setup caps, commitments and openings, and draws
+// challenges to combine base FRI oracles D_i of degree < n_i into
+// C(x) = sum_i (lambda_i + mu_i * x^{N - n_i}) * D_i(x) of degree < N (mu_i is only drawn for
+// circuits smaller than N), so lower degree oracles are lifted to the degree of the largest one.
+// FRI is done once over C(x).
+
+/// LDE factor that setup of a circuit with `trace_len` rows must be created with
+/// to be aggregated with circuits of up to `max_trace_len` rows. It makes the LDE
+/// domain of all the circuits the same
+pub fn lde_factor_for_aggregation(
+    fri_lde_factor: usize,
+    max_trace_len: usize,
+    trace_len: usize,
+) -> usize {
+    assert!(fri_lde_factor.is_power_of_two());
+    assert!(max_trace_len.is_power_of_two());
+    assert!(trace_len.is_power_of_two());
+    assert!(trace_len <= max_trace_len);
+
+    fri_lde_factor * (max_trace_len / trace_len)
+}
+
+/// Circuit with everything needed to prove it. Setup and VK must be created
+/// with the LDE factor from `lde_factor_for_aggregation`
+pub struct CircuitForAggregation<
+    'a,
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    CFG: CSConfig,
+    H: TreeHasher<F>,
+> {
+    pub assembly: &'a CSReferenceAssembly<F, P, CFG>,
+    pub witness_set: WitnessSet<F>,
+    pub setup_base: &'a SetupBaseStorage<F, P>,
+    pub setup: &'a SetupStorage<F, P>,
+    pub setup_tree: &'a MerkleTreeWithCap<F, H>,
+    pub vk: &'a VerificationKey<F, H>,
+}
+
+// circuits with the same trace length, in the order of their first circuits
+fn group_by_trace_len(trace_lens: &[usize]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(usize, Vec<usize>)> = vec![];
+    for (circuit_idx, trace_len) in trace_lens.iter().enumerate() {
+        match groups.iter_mut().find(|(len, _)| len == trace_len) {
+            Some((_, group)) => group.push(circuit_idx),
+            None => groups.push((*trace_len, vec![circuit_idx])),
+        }
+    }
+
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+fn commit_shared_oracles_into_transcript<
+    F: SmallField,
+    H: TreeHasher<F>,
+    TR: Transcript<F, CompatibleCap = H::Output>,
+>(
+    transcript: &mut TR,
+    caps: &SharedOraclesCaps<F, H>,
+) {
+    transcript.witness_merkle_tree_cap(&caps.witness_oracle_cap);
+    transcript.witness_merkle_tree_cap(&caps.stage_2_oracle_cap);
+    transcript.witness_merkle_tree_cap(&caps.quotient_oracle_cap);
+}
+
+fn commit_circuit_into_transcript<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    H: TreeHasher<F>,
+    TR: Transcript<F, CompatibleCap = H::Output>,
+>(
+    transcript: &mut TR,
+    vk: &VerificationKey<F, H>,
+    circuit: &AggregatedCircuitProof<F, EXT, N>,
+) {
+    transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+    transcript.witness_field_elements(&circuit.public_inputs);
+
+    for set in circuit
+        .values_at_z
+        .iter()
+        .chain(circuit.values_at_z_omega.iter())
+        .chain(circuit.values_at_0.iter())
+    {
+        transcript.witness_field_elements(set.as_coeffs_in_base());
+    }
+}
+
+// Commits to the oracles of the last completed stage of all the circuits in the group
+// with one tree, and absorbs its cap into the transcript of every circuit
+fn commit_shared_oracle<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    TR: Transcript<F>,
+    H: TreeHasher<F, Output = TR::CompatibleCap>,
+>(
+    states: &mut [ProverState<F, P, EXT, TR, H, N>],
+    group: &[usize],
+    cap_size: usize,
+    worker: &Worker,
+) -> MerkleTreeWithCap<F, H> {
+    let columns: Vec<_> = group
+        .iter()
+        .flat_map(|circuit_idx| states[*circuit_idx].uncommitted_oracle_columns())
+        .collect();
+    let tree = MerkleTreeWithCap::<F, H>::construct(columns, cap_size, worker);
+
+    let cap = tree.get_cap();
+    for circuit_idx in group.iter() {
+        states[*circuit_idx].commit_shared_oracle(&cap);
+    }
+
+    tree
+}
+
+// Leafs of a shared tree, that are concatenations of the leafs of the same
+// oracle of every circuit in the group
+struct SharedLeafSource<
+    'a,
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    TR: Transcript<F>,
+    H: TreeHasher<F>,
+    const N: usize,
+> {
+    states: &'a [&'a ProverState<F, P, EXT, TR, H, N>],
+    oracle: OracleType,
+}
+
+impl<
+        'a,
+        F: SmallField,
+        P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F>,
+    > QuerySource<F> for SharedLeafSource<'a, F, P, EXT, TR, H, N>
+{
+    fn get_elements(
+        &self,
+        _lde_factor: usize,
+        coset_idx: usize,
+        domain_size: usize,
+        inner_idx: usize,
+        num_elements: usize,
+        dst: &mut Vec<F>,
+    ) {
+        assert_eq!(num_elements, 1);
+        for state in self.states.iter() {
+            state.get_oracle_leaf_elements(self.oracle, domain_size, coset_idx, inner_idx, dst);
+        }
+    }
+}
+
+// lambda_i and mu_i for every circuit, mu_i is zero for circuits of the largest size
+fn draw_combination_challenges<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    TR: Transcript<F>,
+>(
+    transcript: &mut TR,
+    trace_lens: &[usize],
+    max_trace_len: usize,
+) -> Vec<[ExtensionField<F, N, EXT>; 2]> {
+    let mut result = Vec::with_capacity(trace_lens.len());
+    for trace_len in trace_lens.iter() {
+        let lambda = transcript.get_multiple_challenges_fixed::<N>();
+        let lambda = ExtensionField::<F, N, EXT>::from_coeff_in_base(lambda);
+        let mu = if *trace_len != max_trace_len {
+            let mu = transcript.get_multiple_challenges_fixed::<N>();
+            ExtensionField::<F, N, EXT>::from_coeff_in_base(mu)
+        } else {
+            ExtensionField::<F, N, EXT>::ZERO
+        };
+
+        result.push([lambda, mu]);
+    }
+
+    result
+}
+
+/// Proves all the circuits with a single FRI. `proof_config` is the config for the
+/// largest circuit, and the same transcript parameters are used for every transcript
+pub fn prove_aggregated<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    CFG: CSConfig,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    TR: Transcript<F>,
+    H: TreeHasher<F, Output = TR::CompatibleCap>,
+    POW: PoWRunner,
+>(
+    worker: &Worker,
+    circuits: Vec<CircuitForAggregation<'_, F, P, CFG, H>>,
+    proof_config: ProofConfig,
+    transcript_params: TR::TransciptParameters,
+) -> AggregatedProof<F, H, EXT, N> {
+    assert!(!circuits.is_empty(), "there must be at least one circuit");
+    assert!(
+        proof_config.zero_knowledge == false,
+        "zero-knowledge mode is not supported for aggregated proofs"
+    );
+
+    profile_fn!(prove_aggregated);
+
+    let fri_lde_factor = proof_config.fri_lde_factor;
+    let trace_lens: Vec<_> = circuits
+        .iter()
+        .map(|el| el.assembly.max_trace_len)
+        .collect();
+    let max_trace_len = *trace_lens.iter().max().unwrap();
+    let lde_domain_size = max_trace_len * fri_lde_factor;
+    let groups = group_by_trace_len(&trace_lens);
+
+    // start every circuit, witness oracles are committed below together with
+    // the other circuits of the same size
+    let mut setups = Vec::with_capacity(circuits.len());
+    let mut states = Vec::with_capacity(circuits.len());
+    for circuit in circuits.into_iter() {
+        let CircuitForAggregation {
+            assembly,
+            witness_set,
+            setup_base,
+            setup,
+            setup_tree,
+            vk,
+        } = circuit;
+
+        let circuit_lde_factor =
+            lde_factor_for_aggregation(fri_lde_factor, max_trace_len, assembly.max_trace_len);
+        assert_eq!(
+            vk.fixed_parameters.fri_lde_factor, circuit_lde_factor,
+            "setup must be created with LDE factor {} for aggregation",
+            circuit_lde_factor
+        );
+
+        let circuit_proof_config = ProofConfig {
+            fri_lde_factor: circuit_lde_factor,
+            ..proof_config.clone()
+        };
+
+        let state = assembly.compute_witness::<N, EXT, TR, H>(
+            worker,
+            witness_set,
+            setup_base,
+            vk,
+            circuit_proof_config,
+            transcript_params.clone(),
+        );
+
+        setups.push((assembly, setup_base, setup, setup_tree, vk));
+        states.push(state);
+    }
+
+    // commit every group of circuits in lockstep up to the DEEP openings
+    let cap_size = proof_config.merkle_tree_cap_size;
+    let mut shared_trees = Vec::with_capacity(groups.len());
+    for group in groups.iter() {
+        let witness_tree = commit_shared_oracle(&mut states, group, cap_size, worker);
+
+        for circuit_idx in group.iter().copied() {
+            let (assembly, setup_base, ..) = setups[circuit_idx];
+            assembly.compute_second_stage(worker, &mut states[circuit_idx], setup_base);
+        }
+        let stage_2_tree = commit_shared_oracle(&mut states, group, cap_size, worker);
+
+        for circuit_idx in group.iter().copied() {
+            let (assembly, setup_base, setup, ..) = setups[circuit_idx];
+            assembly.compute_quotient(worker, &mut states[circuit_idx], setup_base, setup);
+        }
+        let quotient_tree = commit_shared_oracle(&mut states, group, cap_size, worker);
+
+        for circuit_idx in group.iter().copied() {
+            let (assembly, setup_base, setup, setup_tree, _) = setups[circuit_idx];
+            let state = &mut states[circuit_idx];
+            assembly.advance_proof_impl::<N, EXT, TR, H, POW>(
+                worker, state, setup_base, setup, setup_tree, None,
+            );
+            assert_eq!(state.stage(), ProverStage::DeepOpeningsComputed);
+        }
+
+        shared_trees.push([witness_tree, stage_2_tree, quotient_tree]);
+    }
+
+    let circuits_proofs: Vec<_> = states
+        .iter_mut()
+        .map(|state| AggregatedCircuitProof::<F, EXT, N> {
+            public_inputs: state.public_inputs_values.clone(),
+            values_at_z: std::mem::take(&mut state.values_at_z),
+            values_at_z_omega: std::mem::take(&mut state.values_at_z_omega),
+            values_at_0: std::mem::take(&mut state.values_at_0),
+        })
+        .collect();
+
+    let shared_oracles_caps: Vec<_> = shared_trees
+        .iter()
+        .map(
+            |[witness_tree, stage_2_tree, quotient_tree]| SharedOraclesCaps::<F, H> {
+                witness_oracle_cap: witness_tree.get_cap(),
+                stage_2_oracle_cap: stage_2_tree.get_cap(),
+                quotient_oracle_cap: quotient_tree.get_cap(),
+            },
+        )
+        .collect();
+
+    let mut transcript = TR::new(transcript_params);
+    for caps in shared_oracles_caps.iter() {
+        commit_shared_oracles_into_transcript(&mut transcript, caps);
+    }
+    for ((.., vk), circuit) in setups.iter().zip(circuits_proofs.iter()) {
+        commit_circuit_into_transcript(&mut transcript, *vk, circuit);
+    }
+
+    let combination_challenges =
+        draw_combination_challenges::<F, N, EXT, TR>(&mut transcript, &trace_lens, max_trace_len);
+
+    // combine base FRI oracles over the full LDE domain, in the order of oracle leafs
+    let mut combined: [Vec<F>; N] = std::array::from_fn(|_| vec![F::ZERO; lde_domain_size]);
+    let x_poly = materialize_x_poly_as_arc_lde::<F, F, Global, Global>(
+        max_trace_len,
+        fri_lde_factor,
+        F::multiplicative_generator(),
+        worker,
+        &mut (),
+    );
+    let x_poly: Vec<F> = x_poly
+        .storage
+        .iter()
+        .flat_map(|el| el.storage.iter().copied())
+        .collect();
+
+    for ((trace_len, state), [lambda, mu]) in trace_lens
+        .iter()
+        .zip(states.iter())
+        .zip(combination_challenges.iter())
+    {
+        let sources: [Vec<&[F]>; N] = state
+            .fri_base_oracle_sources
+            .as_ref()
+            .expect("DEEP openings must be computed")
+            .each_ref()
+            .map(|source| {
+                source
+                    .storage
+                    .iter()
+                    .map(|el| P::slice_into_base_slice(&el.storage))
+                    .collect()
+            });
+        let degree_shift = (max_trace_len - *trace_len) as u64;
+        let log_trace_len = trace_len.trailing_zeros();
+
+        worker.scope(lde_domain_size, |scope, chunk_size| {
+            for (chunk_idx, (mut dst, x_chunk)) in
+                chunk_columns_mut(combined.each_mut().map(|el| &mut el[..]), chunk_size)
+                    .zip(x_poly.chunks(chunk_size))
+                    .enumerate()
+            {
+                let sources = &sources;
+                scope.spawn(move |_| {
+                    let mut idx = chunk_idx * chunk_size;
+                    for (i, x) in x_chunk.iter().enumerate() {
+                        let coset_idx = idx >> log_trace_len;
+                        let inner_idx = idx & (*trace_len - 1);
+                        let mut value = ExtensionField::<F, N, EXT>::from_coeff_in_base(
+                            sources.each_ref().map(|el| el[coset_idx][inner_idx]),
+                        );
+
+                        let mut coeff = *lambda;
+                        if degree_shift != 0 {
+                            let mut tmp = *mu;
+                            tmp.mul_assign_by_base(&x.pow_u64(degree_shift));
+                            coeff.add_assign(&tmp);
+                        }
+                        value.mul_assign(&coeff);
+
+                        for (dst, coeff) in dst.iter_mut().zip(value.into_coeffs_in_base()) {
+                            dst[i].add_assign(&coeff);
+                        }
+
+                        idx += 1;
+                    }
+                });
+            }
+        });
+    }
+
+    let into_lde_storage = |flat: Vec<F>| {
+        let storage = flat
+            .chunks(max_trace_len)
+            .map(|el| {
+                let mut coset = crate::utils::allocate_in_with_alignment_of::<F, P, Global>(
+                    max_trace_len,
+                    Global,
+                );
+                coset.extend_from_slice(el);
+                GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(
+                    P::vec_from_base_vec(coset),
+                )
+            })
+            .collect();
+
+        ArcGenericLdeStorage::from_owned(GenericLdeStorage { storage })
+    };
+    let base_fri_source = combined.map(into_lde_storage);
+
+    let (
+        new_pow_bits,                 // updated POW bits if needed
+        num_queries,                  // num queries
+        interpolation_log2s_schedule, // folding schedule
+        final_expected_degree,
+    ) = compute_fri_schedule_for_config(
+        &proof_config,
+        fri_lde_factor.trailing_zeros(),
+        max_trace_len.trailing_zeros(),
+    )
+    .unwrap_or_else(|err| panic!("{}", err));
+    assert!(new_pow_bits <= proof_config.pow_bits);
+
+    let mut ctx = P::Context::placeholder();
+    let fri_data = do_fri::<F, P, N, EXT, TR, H, Global, Global>(
+        base_fri_source.clone(),
+        &mut transcript,
+        interpolation_log2s_schedule.clone(),
+        fri_lde_factor,
+        proof_config.merkle_tree_cap_size,
+        worker,
+        &mut ctx,
+    );
+
+    for monomials in fri_data.monomial_forms.iter() {
+        assert_eq!(monomials.len(), final_expected_degree);
+    }
+
+    let pow_challenge = run_pow::<F, TR, POW>(&mut transcript, new_pow_bits, worker);
+
+    let mut proof = AggregatedProof::<F, H, EXT, N> {
+        proof_config,
+        circuits: circuits_proofs,
+        shared_oracles_caps,
+        final_fri_monomials: fri_data.monomial_forms.clone(),
+        fri_base_oracle_cap: fri_data.base_oracle.get_cap(),
+        fri_intermediate_oracles_caps: fri_data
+            .intermediate_oracles
+            .iter()
+            .map(|el| el.get_cap())
+            .collect(),
+        queries_per_fri_repetition: Vec::with_capacity(num_queries),
+        pow_challenge,
+        _marker: std::marker::PhantomData,
+    };
+
+    let groups_states: Vec<Vec<_>> = groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .map(|circuit_idx| &states[*circuit_idx])
+                .collect()
+        })
+        .collect();
+
+    let max_needed_bits = lde_domain_size.trailing_zeros() as usize;
+    let mut bools_buffer = BoolsBuffer {
+        available: vec![],
+        max_needed: max_needed_bits,
+    };
+
+    for _query_idx in 0..num_queries {
+        let query_index_lsb_first_bits = bools_buffer.get_bits(&mut transcript, max_needed_bits);
+        // index of the leaf in any of the base oracles, that is coset index followed by inner index
+        let leaf_idx = u64_from_lsb_first_bits(&query_index_lsb_first_bits) as usize;
+
+        let shared_oracles_queries = groups
+            .iter()
+            .zip(groups_states.iter())
+            .zip(shared_trees.iter())
+            .map(|((group, group_states), trees)| {
+                let trace_len = trace_lens[group[0]];
+                let query = |tree: &MerkleTreeWithCap<F, H>, oracle: OracleType| {
+                    OracleQuery::construct(
+                        tree,
+                        &SharedLeafSource {
+                            states: group_states,
+                            oracle,
+                        },
+                        fri_lde_factor,
+                        leaf_idx >> trace_len.trailing_zeros(),
+                        trace_len,
+                        leaf_idx & (trace_len - 1),
+                        1,
+                    )
+                };
+
+                SharedOraclesQueries {
+                    witness_query: query(&trees[0], OracleType::Witness),
+                    stage_2_query: query(&trees[1], OracleType::Stage2),
+                    quotient_query: query(&trees[2], OracleType::Quotient),
+                }
+            })
+            .collect();
+
+        let setup_queries = setups
+            .iter()
+            .zip(states.iter())
+            .map(|((assembly, _, setup, setup_tree, _), state)| {
+                let trace_len = assembly.max_trace_len;
+                OracleQuery::construct(
+                    *setup_tree,
+                    *setup,
+                    state.proof_config().fri_lde_factor,
+                    leaf_idx >> trace_len.trailing_zeros(),
+                    trace_len,
+                    leaf_idx & (trace_len - 1),
+                    1,
+                )
+            })
+            .collect();
+
+        let fri_queries: Vec<OracleQuery<F, H>> = query_fri_oracles(
+            &fri_data,
+            &base_fri_source,
+            &interpolation_log2s_schedule,
+            fri_lde_factor,
+            leaf_idx >> max_trace_len.trailing_zeros(),
+            max_trace_len,
+            leaf_idx & (max_trace_len - 1),
+        );
+
+        proof
+            .queries_per_fri_repetition
+            .push(AggregatedSingleRoundQueries {
+                shared_oracles_queries,
+                setup_queries,
+                fri_queries,
+            });
+    }
+
+    proof
+}
+
+/// Verifier for proofs from `prove_aggregated`. It holds verifiers and VKs of
+/// the circuits in the same order as those were aggregated
+pub struct AggregatedVerifier<
+    F: SmallField,
+    EXT: FieldExtension<N, BaseField = F>,
+    H: TreeHasher<F>,
+    const N: usize = 2,
+> {
+    circuits: Vec<(Verifier<F, EXT, N>, VerificationKey<F, H>)>,
+}
+
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>, H: TreeHasher<F>>
+    AggregatedVerifier<F, EXT, H, N>
+{
+    pub fn new(circuits: Vec<(Verifier<F, EXT, N>, VerificationKey<F, H>)>) -> Self {
+        assert!(!circuits.is_empty(), "there must be at least one circuit");
+
+        Self { circuits }
+    }
+
+    pub fn verify<TR: Transcript<F, CompatibleCap = H::Output>, POW: PoWRunner>(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        proof: &AggregatedProof<F, H, EXT, N>,
+    ) -> bool {
+        match self.verify_detailed::<TR, POW>(transcript_params, proof) {
+            Ok(()) => true,
+            Err(error) => {
+                log!("{}", error);
+                false
+            }
+        }
+    }
+
+    /// Same as `verify`, but reports the first failed check instead of only a validity flag.
+    /// Failures of checks that are specific to one of the circuits are reported as
+    /// `VerificationError::Circuit`, and failures of shared oracles are reported as is
+    pub fn verify_detailed<TR: Transcript<F, CompatibleCap = H::Output>, POW: PoWRunner>(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        proof: &AggregatedProof<F, H, EXT, N>,
+    ) -> Result<(), VerificationError> {
+        if proof.circuits.len() != self.circuits.len() {
+            return Err(VerificationError::InvalidNumberOfCircuits {
+                expected: self.circuits.len(),
+                got: proof.circuits.len(),
+            });
+        }
+
+        let trace_lens: Vec<_> = self
+            .circuits
+            .iter()
+            .map(|(_, vk)| vk.fixed_parameters.domain_size as usize)
+            .collect();
+        let max_trace_len = *trace_lens.iter().max().unwrap();
+        let groups = group_by_trace_len(&trace_lens);
+
+        // LDE factors of the circuits are derived from the one of the proof, so it must be
+        // checked before use. Setup of the largest circuit is created with exactly this factor
+        let largest_circuit_idx = trace_lens
+            .iter()
+            .position(|el| *el == max_trace_len)
+            .unwrap();
+        let largest_circuit_vk = &self.circuits[largest_circuit_idx].1;
+        if largest_circuit_vk.fixed_parameters.fri_lde_factor != proof.proof_config.fri_lde_factor {
+            return Err(VerificationError::FriLdeFactorMismatch {
+                vk: largest_circuit_vk.fixed_parameters.fri_lde_factor,
+                proof: proof.proof_config.fri_lde_factor,
+            });
+        }
+
+        if proof.shared_oracles_caps.len() != groups.len() {
+            return Err(VerificationError::InvalidNumberOfSharedOracles {
+                expected: groups.len(),
+                got: proof.shared_oracles_caps.len(),
+            });
+        }
+
+        let mut circuits_caps = vec![None; self.circuits.len()];
+        for (group, caps) in groups.iter().zip(proof.shared_oracles_caps.iter()) {
+            for circuit_idx in group.iter() {
+                circuits_caps[*circuit_idx] = Some(caps);
+            }
+        }
+
+        let mut openings = Vec::with_capacity(self.circuits.len());
+        for (circuit_idx, (((verifier, vk), circuit), caps)) in self
+            .circuits
+            .iter()
+            .zip(proof.circuits.iter())
+            .zip(circuits_caps.into_iter())
+            .enumerate()
+        {
+            let in_circuit = |error| VerificationError::Circuit {
+                circuit_idx,
+                error: Box::new(error),
+            };
+
+            verifier.check_verification_key(vk).map_err(in_circuit)?;
+
+            let caps = caps.expect("every circuit belongs to a group");
+            let circuit_openings = CircuitOpenings::<F, H, EXT, N> {
+                merkle_tree_cap_size: proof.proof_config.merkle_tree_cap_size,
+                fri_lde_factor: lde_factor_for_aggregation(
+                    proof.proof_config.fri_lde_factor,
+                    max_trace_len,
+                    vk.fixed_parameters.domain_size as usize,
+                ),
+                public_inputs: &circuit.public_inputs,
+                witness_oracle_cap: &caps.witness_oracle_cap,
+                stage_2_oracle_cap: &caps.stage_2_oracle_cap,
+                quotient_oracle_cap: &caps.quotient_oracle_cap,
+                values_at_z: &circuit.values_at_z,
+                values_at_z_omega: &circuit.values_at_z_omega,
+                values_at_0: &circuit.values_at_0,
+                salt_size: proof.proof_config.salt_size(),
+                num_masking_polys: proof.proof_config.num_masking_polys(),
+            };
+
+            let mut transcript = TR::new(transcript_params.clone());
+            transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+            let openings_at_z = verifier
+                .verify_openings_at_z(&mut transcript, vk, &circuit_openings)
+                .map_err(in_circuit)?;
+
+            openings.push((circuit_openings, openings_at_z));
+        }
+
+        let mut transcript = TR::new(transcript_params);
+        for caps in proof.shared_oracles_caps.iter() {
+            commit_shared_oracles_into_transcript(&mut transcript, caps);
+        }
+        for ((_, vk), circuit) in self.circuits.iter().zip(proof.circuits.iter()) {
+            commit_circuit_into_transcript(&mut transcript, vk, circuit);
+        }
+
+        let combination_challenges = draw_combination_challenges::<F, N, EXT, TR>(
+            &mut transcript,
+            &trace_lens,
+            max_trace_len,
+        );
+
+        let mut fri = FriVerificationContext::<F, EXT, H, N>::new::<TR, POW>(
+            &mut transcript,
+            &proof.proof_config,
+            max_trace_len as u64,
+            &proof.fri_base_oracle_cap,
+            &proof.fri_intermediate_oracles_caps,
+            &proof.final_fri_monomials,
+            proof.pow_challenge,
+        )?;
+
+        if fri.num_queries != proof.queries_per_fri_repetition.len() {
+            return Err(VerificationError::InvalidNumberOfQueries {
+                expected: fri.num_queries,
+                got: proof.queries_per_fri_repetition.len(),
+            });
+        }
+
+        // base oracles of all the circuits have the same depth, as those are over the same domain
+        let base_oracle_depth = self.circuits[0].1.fixed_parameters.base_oracles_depth();
+        let leaf_sizes: Vec<_> = self
+            .circuits
+            .iter()
+            .zip(openings.iter())
+            .map(|((verifier, vk), (circuit_openings, _))| {
+                verifier.committed_leaf_sizes(vk, circuit_openings)
+            })
+            .collect();
+
+        for (query_idx, queries) in proof.queries_per_fri_repetition.iter().enumerate() {
+            if queries.shared_oracles_queries.len() != groups.len() {
+                return Err(VerificationError::InvalidNumberOfSharedOracles {
+                    expected: groups.len(),
+                    got: queries.shared_oracles_queries.len(),
+                });
+            }
+            if queries.setup_queries.len() != self.circuits.len() {
+                return Err(VerificationError::InvalidNumberOfCircuits {
+                    expected: self.circuits.len(),
+                    got: queries.setup_queries.len(),
+                });
+            }
+
+            let query_point = fri.draw_query_point(&mut transcript);
+
+            let mut simulated_ext_element = ExtensionField::<F, N, EXT>::ZERO;
+            for ((group, caps), shared_queries) in groups
+                .iter()
+                .zip(proof.shared_oracles_caps.iter())
+                .zip(queries.shared_oracles_queries.iter())
+            {
+                let shared_leafs = [
+                    (
+                        &shared_queries.witness_query,
+                        &caps.witness_oracle_cap,
+                        OracleType::Witness,
+                    ),
+                    (
+                        &shared_queries.stage_2_query,
+                        &caps.stage_2_oracle_cap,
+                        OracleType::Stage2,
+                    ),
+                    (
+                        &shared_queries.quotient_query,
+                        &caps.quotient_oracle_cap,
+                        OracleType::Quotient,
+                    ),
+                ];
+                for (oracle_idx, (query, cap, oracle)) in shared_leafs.iter().enumerate() {
+                    let leaf_size = group
+                        .iter()
+                        .map(|circuit_idx| leaf_sizes[*circuit_idx][oracle_idx])
+                        .sum();
+                    verify_oracle_query(
+                        query,
+                        cap,
+                        leaf_size,
+                        base_oracle_depth,
+                        query_idx,
+                        *oracle,
+                        query_point.base_tree_idx,
+                    )?;
+                }
+
+                // split shared leafs into the leafs of every circuit
+                let mut offsets = [0; 3];
+                for circuit_idx in group.iter().copied() {
+                    let (verifier, vk) = &self.circuits[circuit_idx];
+                    let (circuit_openings, openings_at_z) = &openings[circuit_idx];
+                    let [witness_leaf, stage_2_leaf, quotient_leaf] =
+                        std::array::from_fn(|oracle_idx| {
+                            let start = offsets[oracle_idx];
+                            let end = start + leaf_sizes[circuit_idx][oracle_idx];
+                            offsets[oracle_idx] = end;
+
+                            &shared_leafs[oracle_idx].0.leaf_elements[start..end]
+                        });
+
+                    let setup_query = &queries.setup_queries[circuit_idx];
+                    verify_oracle_query(
+                        setup_query,
+                        &vk.setup_merkle_tree_cap,
+                        verifier.setup_leaf_size(&vk.fixed_parameters),
+                        base_oracle_depth,
+                        query_idx,
+                        OracleType::Setup,
+                        query_point.base_tree_idx,
+                    )
+                    .map_err(|error| VerificationError::Circuit {
+                        circuit_idx,
+                        error: Box::new(error),
+                    })?;
+
+                    let mut value = verifier.evaluate_base_oracles_at_query(
+                        vk,
+                        circuit_openings,
+                        openings_at_z,
+                        witness_leaf,
+                        stage_2_leaf,
+                        quotient_leaf,
+                        &setup_query.leaf_elements,
+                        &query_point,
+                    );
+
+                    let [lambda, mu] = combination_challenges[circuit_idx];
+                    let degree_shift = (max_trace_len as u64) - vk.fixed_parameters.domain_size;
+                    let mut coeff = lambda;
+                    if degree_shift != 0 {
+                        let mut tmp = mu;
+                        tmp.mul_assign_by_base(
+                            &query_point
+                                .domain_element_for_quotiening
+                                .pow_u64(degree_shift),
+                        );
+                        coeff.add_assign(&tmp);
+                    }
+                    value.mul_assign(&coeff);
+                    simulated_ext_element.add_assign(&value);
+                }
+            }
+
+            fri.verify_query(
+                query_idx,
+                &query_point,
+                simulated_ext_element,
+                &queries.fri_queries,
+            )?;
+        }
+
+        Ok(())
+    }
+}
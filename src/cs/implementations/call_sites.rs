@@ -0,0 +1,122 @@
+use super::*;
+use std::any::TypeId;
+use std::panic::Location;
+use std::sync::Arc;
+
+/// Source location that allocated a variable or placed a gate, together with the
+/// named scope that was active at that moment
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CallSite {
+    pub location: &'static Location<'static>,
+    pub scope: Option<Arc<str>>,
+}
+
+impl std::fmt::Display for CallSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.scope.as_ref() {
+            Some(scope) => write!(f, "{} in {}", self.location, scope),
+            None => write!(f, "{}", self.location),
+        }
+    }
+}
+
+/// Storage of call sites, only populated if `CSDebugConfig::TRACK_CALL_SITES` is set
+#[derive(Clone, Debug, Default)]
+pub struct CallSitesTracker {
+    scope_stack: Vec<String>,
+    current_scope: Option<Arc<str>>,
+    /// Call site of every place (variable or witness), indexed by place index
+    pub places: Vec<Option<CallSite>>,
+    /// Call site of the gate that took the row over general purpose columns. For gates
+    /// that are placed multiple times per row it's the call site of the first instance
+    pub general_purpose_rows: Vec<Option<CallSite>>,
+    /// Call sites of gates over specialized columns, by gate type, row and repetition
+    pub specialized_instances: HashMap<(TypeId, usize, usize), CallSite>,
+}
+
+impl CallSitesTracker {
+    pub fn push_scope(&mut self, name: &str) {
+        self.scope_stack.push(name.to_owned());
+        self.current_scope = Some(Arc::from(self.scope_stack.join("/")));
+    }
+
+    pub fn pop_scope(&mut self) {
+        assert!(
+            self.scope_stack.pop().is_some(),
+            "trying to pop a scope, but none was pushed"
+        );
+        self.current_scope = if self.scope_stack.is_empty() {
+            None
+        } else {
+            Some(Arc::from(self.scope_stack.join("/")))
+        };
+    }
+
+    #[inline]
+    pub fn current_call_site(&self, location: &'static Location<'static>) -> CallSite {
+        CallSite {
+            location,
+            scope: self.current_scope.clone(),
+        }
+    }
+
+    pub fn record_places(
+        &mut self,
+        first_place_idx: u64,
+        num_places: usize,
+        location: &'static Location<'static>,
+    ) {
+        let first_place_idx = first_place_idx as usize;
+        if self.places.len() < first_place_idx {
+            // places may be allocated before tracking has started, e.g. by the CS builder
+            self.places.resize(first_place_idx, None);
+        }
+        let site = self.current_call_site(location);
+        self.places
+            .extend(std::iter::repeat(Some(site)).take(num_places));
+    }
+
+    pub fn record_general_purpose_row(&mut self, row: usize, location: &'static Location<'static>) {
+        if self.general_purpose_rows.len() <= row {
+            self.general_purpose_rows.resize(row + 1, None);
+        }
+        if self.general_purpose_rows[row].is_none() {
+            self.general_purpose_rows[row] = Some(self.current_call_site(location));
+        }
+    }
+
+    pub fn record_specialized_instance(
+        &mut self,
+        gate_type_id: TypeId,
+        repetition: usize,
+        row: usize,
+        location: &'static Location<'static>,
+    ) {
+        let site = self.current_call_site(location);
+        self.specialized_instances
+            .insert((gate_type_id, row, repetition), site);
+    }
+
+    #[inline]
+    pub fn place_call_site(&self, place: Place) -> Option<&CallSite> {
+        self.places.get(place.raw_ix()).and_then(|el| el.as_ref())
+    }
+
+    #[inline]
+    pub fn general_purpose_row_call_site(&self, row: usize) -> Option<&CallSite> {
+        self.general_purpose_rows
+            .get(row)
+            .and_then(|el| el.as_ref())
+    }
+
+    #[inline]
+    pub fn specialized_instance_call_site(
+        &self,
+        gate_type_id: TypeId,
+        repetition: usize,
+        row: usize,
+    ) -> Option<&CallSite> {
+        self.specialized_instances
+            .get(&(gate_type_id, row, repetition))
+    }
+}
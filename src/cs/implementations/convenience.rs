@@ -4,7 +4,7 @@ use std::alloc::Global;
 
 use super::polynomial_storage::{SetupBaseStorage, SetupStorage};
 use super::proof::Proof;
-use super::verifier::VerificationKey;
+use super::verifier::{VerificationError, VerificationKey};
 use super::witness::WitnessVec;
 use crate::cs::cs_builder_verifier::CsVerifierBuilder;
 use crate::cs::oracle::merkle_tree::MerkleTreeWithCap;
@@ -19,6 +19,7 @@ use super::pow::*;
 
 use crate::config::*;
 use crate::cs::implementations::hints::*;
+use crate::cs::implementations::out_of_core::OutOfCoreConfig;
 use crate::cs::implementations::prover::ProofConfig;
 use crate::cs::implementations::reference_cs::*;
 use crate::cs::oracle::TreeHasher;
@@ -32,7 +33,8 @@ impl<
     > CSReferenceAssembly<F, P, CFG, A>
 {
     pub fn prove_one_shot<
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -41,7 +43,7 @@ impl<
         worker: &Worker,
         proof_config: ProofConfig,
         transcript_params: TR::TransciptParameters,
-    ) -> (Proof<F, H, EXT>, VerificationKey<F, H>) {
+    ) -> (Proof<F, H, EXT, N>, VerificationKey<F, H>) {
         assert!(
             CFG::SetupConfig::KEEP_SETUP,
             "CS is not configured to keep setup to know variables placement"
@@ -65,7 +67,7 @@ impl<
         );
         let witness_set = self.take_witness(worker);
 
-        let proof = self.prove_cpu_basic::<EXT, TR, H, POW>(
+        let proof = self.prove_cpu_basic::<N, EXT, TR, H, POW>(
             worker,
             witness_set,
             &setup_base,
@@ -79,6 +81,57 @@ impl<
         (proof, vk)
     }
 
+    pub fn prove_one_shot_out_of_core<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        mut self,
+        worker: &Worker,
+        proof_config: ProofConfig,
+        transcript_params: TR::TransciptParameters,
+        out_of_core: &OutOfCoreConfig,
+    ) -> (Proof<F, H, EXT, N>, VerificationKey<F, H>) {
+        assert!(
+            CFG::SetupConfig::KEEP_SETUP,
+            "CS is not configured to keep setup to know variables placement"
+        );
+
+        assert!(
+            CFG::WitnessConfig::EVALUATE_WITNESS,
+            "CS is not configured to have witness available"
+        );
+
+        assert!(proof_config.fri_lde_factor.is_power_of_two());
+
+        let mut ctx = P::Context::placeholder();
+
+        let setup_base = self.create_base_setup(worker, &mut ctx);
+        let (setup, vk, setup_tree) = self.materialize_setup_storage_and_vk::<H>(
+            proof_config.fri_lde_factor,
+            proof_config.merkle_tree_cap_size,
+            worker,
+            &mut ctx,
+        );
+        let witness_set = self.take_witness(worker);
+
+        let proof = self.prove_cpu_out_of_core::<N, EXT, TR, H, POW>(
+            worker,
+            witness_set,
+            &setup_base,
+            &setup,
+            &setup_tree,
+            &vk,
+            proof_config,
+            transcript_params,
+            out_of_core,
+        );
+
+        (proof, vk)
+    }
+
     pub fn prepare_base_setup_with_precomputations_and_vk<
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
@@ -117,7 +170,8 @@ impl<
     }
 
     pub fn prove_from_precomputations<
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -132,7 +186,7 @@ impl<
         wits_hint: &DenseWitnessCopyHint,
         transcript_params: TR::TransciptParameters,
         worker: &Worker,
-    ) -> Proof<F, H, EXT> {
+    ) -> Proof<F, H, EXT, N> {
         assert!(
             CFG::WitnessConfig::EVALUATE_WITNESS,
             "CS is not configured to have witness available"
@@ -142,7 +196,7 @@ impl<
 
         let witness_set = self.take_witness_using_hints(worker, vars_hint, wits_hint);
 
-        let proof = self.prove_cpu_basic::<EXT, TR, H, POW>(
+        let proof = self.prove_cpu_basic::<N, EXT, TR, H, POW>(
             worker,
             witness_set,
             setup_base,
@@ -158,7 +212,8 @@ impl<
 
     /// Intended to be used mainly with assembly produced by `into_assembly_for_repeated_proving`
     pub fn prove_from_witness_vec_and_precomputations<
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -174,13 +229,13 @@ impl<
         wits_hint: &DenseWitnessCopyHint,
         transcript_params: TR::TransciptParameters,
         worker: &Worker,
-    ) -> Proof<F, H, EXT> {
+    ) -> Proof<F, H, EXT, N> {
         assert!(proof_config.fri_lde_factor.is_power_of_two());
 
         let witness_set =
             self.witness_set_from_witness_vec(witness_vector, vars_hint, wits_hint, worker);
 
-        let proof = self.prove_cpu_basic::<EXT, TR, H, POW>(
+        let proof = self.prove_cpu_basic::<N, EXT, TR, H, POW>(
             worker,
             witness_set,
             setup_base,
@@ -198,18 +253,19 @@ impl<
 pub fn verify_circuit<
     F: SmallField,
     C: Circuit<F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     TR: Transcript<F>,
     H: TreeHasher<F, Output = TR::CompatibleCap>,
     POW: PoWRunner,
 >(
     circuit: &C,
-    proof: &Proof<F, H, EXT>,
+    proof: &Proof<F, H, EXT, N>,
     vk: &VerificationKey<F, H>,
     transcript_params: TR::TransciptParameters,
 ) -> bool {
     let builder_impl =
-        CsVerifierBuilder::<F, EXT>::new_from_parameters(vk.fixed_parameters.parameters);
+        CsVerifierBuilder::<F, EXT, N>::new_from_parameters(vk.fixed_parameters.parameters);
     use crate::cs::cs_builder::new_builder;
 
     let builder = new_builder::<_, F>(builder_impl);
@@ -218,3 +274,29 @@ pub fn verify_circuit<
     let verifier = builder.build(());
     verifier.verify::<H, TR, POW>(transcript_params, vk, proof)
 }
+
+pub fn verify_circuit_batch<
+    F: SmallField,
+    C: Circuit<F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    TR: Transcript<F>,
+    H: TreeHasher<F, Output = TR::CompatibleCap>,
+    POW: PoWRunner,
+>(
+    circuit: &C,
+    proofs: &[Proof<F, H, EXT, N>],
+    vk: &VerificationKey<F, H>,
+    transcript_params: TR::TransciptParameters,
+    worker: &Worker,
+) -> Vec<Result<(), VerificationError>> {
+    let builder_impl =
+        CsVerifierBuilder::<F, EXT, N>::new_from_parameters(vk.fixed_parameters.parameters);
+    use crate::cs::cs_builder::new_builder;
+
+    let builder = new_builder::<_, F>(builder_impl);
+    let builder = circuit.configure_builder(builder);
+
+    let verifier = builder.build(());
+    verifier.verify_batch::<H, TR, POW>(transcript_params, vk, proofs, worker)
+}
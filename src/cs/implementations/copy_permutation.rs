@@ -114,7 +114,8 @@ pub(crate) fn pointwise_rational<
 pub(crate) fn pointwise_rational_in_extension<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
@@ -122,10 +123,9 @@ pub(crate) fn pointwise_rational_in_extension<
     precomputed_x_poly: std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>,
     sigma_polys: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
     non_residues: Vec<F, B>,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
-    basis_c0: &mut GenericPolynomial<F, LagrangeForm, P, A>,
-    basis_c1: &mut GenericPolynomial<F, LagrangeForm, P, A>,
+    beta: ExtensionField<F, N, EXT>,
+    gamma: ExtensionField<F, N, EXT>,
+    basis: &mut [GenericPolynomial<F, LagrangeForm, P, A>; N],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
@@ -134,13 +134,12 @@ pub(crate) fn pointwise_rational_in_extension<
     assert_eq!(copy_permutation_columns.len(), sigma_polys.len());
     assert_eq!(copy_permutation_columns.len(), non_residues.len());
     let typical_size = copy_permutation_columns[0].storage.len();
-    assert_eq!(basis_c0.storage.len(), typical_size);
-    assert_eq!(basis_c1.storage.len(), typical_size);
+    for el in basis.iter() {
+        assert_eq!(el.storage.len(), typical_size);
+    }
 
-    let beta_c0 = P::constant(beta.coeffs[0], ctx);
-    let beta_c1 = P::constant(beta.coeffs[1], ctx);
-    let gamma_c0 = P::constant(gamma.coeffs[0], ctx);
-    let gamma_c1 = P::constant(gamma.coeffs[1], ctx);
+    let beta = beta.coeffs.map(|el| P::constant(el, ctx));
+    let gamma = gamma.coeffs.map(|el| P::constant(el, ctx));
 
     for ((witness_poly, sigma_poly), non_residue) in copy_permutation_columns
         .iter()
@@ -149,12 +148,13 @@ pub(crate) fn pointwise_rational_in_extension<
     {
         let non_residue = P::constant(*non_residue, ctx);
         worker.scope(typical_size, |scope, chunk_size| {
-            for ((((w, sigma), x_poly), dst_c0), dst_c1) in
-                (witness_poly.storage.chunks(chunk_size))
-                    .zip(sigma_poly.storage.chunks(chunk_size))
-                    .zip(precomputed_x_poly.storage.chunks(chunk_size))
-                    .zip(basis_c0.storage.chunks_mut(chunk_size))
-                    .zip(basis_c1.storage.chunks_mut(chunk_size))
+            for (((w, sigma), x_poly), mut dst) in (witness_poly.storage.chunks(chunk_size))
+                .zip(sigma_poly.storage.chunks(chunk_size))
+                .zip(precomputed_x_poly.storage.chunks(chunk_size))
+                .zip(chunk_columns_mut(
+                    basis.each_mut().map(|el| &mut el.storage[..]),
+                    chunk_size,
+                ))
             {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
@@ -163,12 +163,8 @@ pub(crate) fn pointwise_rational_in_extension<
                     let mut buffer_for_inverses =
                         Vec::with_capacity_in(chunk_size * P::SIZE_FACTOR, A::default());
 
-                    for ((((w, sigma), x_poly), dst_c0), dst_c1) in w
-                        .iter()
-                        .zip(sigma.iter())
-                        .zip(x_poly.iter())
-                        .zip(dst_c0.iter_mut())
-                        .zip(dst_c1.iter_mut())
+                    for (idx, ((w, sigma), x_poly)) in
+                        w.iter().zip(sigma.iter()).zip(x_poly.iter()).enumerate()
                     {
                         // numerator is w + beta * non_res * x + gamma
 
@@ -177,69 +173,61 @@ pub(crate) fn pointwise_rational_in_extension<
                         let mut numerator_common = non_residue;
                         numerator_common.mul_assign(x_poly, &mut ctx);
 
-                        // c0 and c1 parts
-                        let mut numerator_c0 = numerator_common;
-                        numerator_c0.mul_assign(&beta_c0, &mut ctx);
-                        numerator_c0
-                            .add_assign(w, &mut ctx)
-                            .add_assign(&gamma_c0, &mut ctx);
-
-                        let mut numerator_c1 = numerator_common;
-                        numerator_c1.mul_assign(&beta_c1, &mut ctx);
-                        numerator_c1.add_assign(&gamma_c1, &mut ctx);
+                        // and only the first coefficient gets w
+                        let mut numerator = [numerator_common; N];
+                        for (i, dst) in numerator.iter_mut().enumerate() {
+                            dst.mul_assign(&beta[i], &mut ctx);
+                            if i == 0 {
+                                dst.add_assign(w, &mut ctx);
+                            }
+                            dst.add_assign(&gamma[i], &mut ctx);
+                        }
 
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            dst_c0,
-                            dst_c1,
-                            &numerator_c0,
-                            &numerator_c1,
-                            &mut ctx,
+                        let mut value: [P; N] = std::array::from_fn(|i| dst[i][idx]);
+                        mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                            &mut value, &numerator, &mut ctx,
                         );
+                        for (dst, src) in dst.iter_mut().zip(value.into_iter()) {
+                            dst[idx] = src;
+                        }
 
                         // denominator is w + beta * sigma(x) + gamma
                         // we do the same
 
-                        // c0 and c1 parts
-                        let mut denominator_c0 = *sigma;
-                        denominator_c0.mul_assign(&beta_c0, &mut ctx);
-                        denominator_c0
-                            .add_assign(w, &mut ctx)
-                            .add_assign(&gamma_c0, &mut ctx);
-
-                        let mut denominator_c1 = *sigma;
-                        denominator_c1.mul_assign(&beta_c1, &mut ctx);
-                        denominator_c1.add_assign(&gamma_c1, &mut ctx);
+                        let mut denominator = [*sigma; N];
+                        for (i, dst) in denominator.iter_mut().enumerate() {
+                            dst.mul_assign(&beta[i], &mut ctx);
+                            if i == 0 {
+                                dst.add_assign(w, &mut ctx);
+                            }
+                            dst.add_assign(&gamma[i], &mut ctx);
+                        }
 
-                        for (c0, c1) in denominator_c0
-                            .as_base_elements()
-                            .iter()
-                            .zip(denominator_c1.as_base_elements().iter())
-                        {
+                        for j in 0..P::SIZE_FACTOR {
                             let denominator =
-                                ExtensionField::<F, 2, EXT>::from_coeff_in_base([*c0, *c1]);
+                                ExtensionField::<F, N, EXT>::from_coeff_in_base(
+                                    std::array::from_fn(|i| denominator[i].as_base_elements()[j]),
+                                );
                             buffer_den.push(denominator);
                         }
                     }
 
                     batch_inverse(&buffer_den, &mut buffer_for_inverses);
-                    assert_eq!(dst_c0.len() * P::SIZE_FACTOR, buffer_for_inverses.len());
-                    assert_eq!(dst_c1.len() * P::SIZE_FACTOR, buffer_for_inverses.len());
+                    for el in dst.iter() {
+                        assert_eq!(el.len() * P::SIZE_FACTOR, buffer_for_inverses.len());
+                    }
 
                     // mul as scalars
-                    let dst_c0_base = P::slice_into_base_slice_mut(dst_c0);
-                    let dst_c1_base = P::slice_into_base_slice_mut(dst_c1);
+                    let mut dst_base = dst.map(|el| P::slice_into_base_slice_mut(el));
 
-                    for ((dst_c0, dst_c1), src) in dst_c0_base
-                        .iter_mut()
-                        .zip(dst_c1_base.iter_mut())
-                        .zip(buffer_for_inverses.drain(..))
-                    {
-                        let mut dst =
-                            ExtensionField::<F, 2, EXT>::from_coeff_in_base([*dst_c0, *dst_c1]);
-                        crate::field::Field::mul_assign(&mut dst, &src);
-                        let [c0, c1] = dst.into_coeffs_in_base();
-                        *dst_c0 = c0;
-                        *dst_c1 = c1;
+                    for (idx, src) in buffer_for_inverses.drain(..).enumerate() {
+                        let mut value = ExtensionField::<F, N, EXT>::from_coeff_in_base(
+                            std::array::from_fn(|i| dst_base[i][idx]),
+                        );
+                        crate::field::Field::mul_assign(&mut value, &src);
+                        for (dst, src) in dst_base.iter_mut().zip(value.coeffs.into_iter()) {
+                            dst[idx] = src;
+                        }
                     }
                 });
             }
@@ -298,65 +286,60 @@ pub(crate) fn pointwise_product_into<
 pub(crate) fn pointwise_product_in_extension<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; 2]],
+    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; N]],
     worker: &Worker,
     ctx: &mut P::Context,
-) -> [GenericPolynomial<F, LagrangeForm, P, A>; 2] {
+) -> [GenericPolynomial<F, LagrangeForm, P, A>; N] {
     if inputs.len() == 1 {
         return inputs[0].clone();
     }
 
-    let [mut result_c0, mut result_c1] = inputs[0].clone();
+    let mut result = inputs[0].clone();
 
-    pointwise_product_in_extension_into::<F, P, EXT, A>(
-        &inputs[1..],
-        &mut result_c0,
-        &mut result_c1,
-        worker,
-        ctx,
-    );
+    pointwise_product_in_extension_into::<F, P, N, EXT, A>(&inputs[1..], &mut result, worker, ctx);
 
-    [result_c0, result_c1]
+    result
 }
 
 pub(crate) fn pointwise_product_in_extension_into<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; 2]],
-    into_c0: &mut GenericPolynomial<F, LagrangeForm, P, A>,
-    into_c1: &mut GenericPolynomial<F, LagrangeForm, P, A>,
+    inputs: &[[GenericPolynomial<F, LagrangeForm, P, A>; N]],
+    into: &mut [GenericPolynomial<F, LagrangeForm, P, A>; N],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
-    let typical_size = into_c0.storage.len(); // we need raw length in counts of P
+    let typical_size = into[0].storage.len(); // we need raw length in counts of P
 
     for source in inputs.iter() {
-        let [src_c0, src_c1] = source;
         worker.scope(typical_size, |scope, chunk_size| {
-            for (((dst_c0, dst_c1), src_c0), src_c1) in into_c0
-                .storage
-                .chunks_mut(chunk_size)
-                .zip(into_c1.storage.chunks_mut(chunk_size))
-                .zip(src_c0.storage.chunks(chunk_size))
-                .zip(src_c1.storage.chunks(chunk_size))
-            {
+            for (mut dst, src) in chunk_columns_mut(
+                into.each_mut().map(|el| &mut el.storage[..]),
+                chunk_size,
+            )
+            .zip(chunk_columns(
+                source.each_ref().map(|el| &el.storage[..]),
+                chunk_size,
+            )) {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
-                    for (((dst_c0, dst_c1), src_c0), src_c1) in dst_c0
-                        .iter_mut()
-                        .zip(dst_c1.iter_mut())
-                        .zip(src_c0.iter())
-                        .zip(src_c1.iter())
-                    {
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            dst_c0, dst_c1, src_c0, src_c1, &mut ctx,
+                    for idx in 0..dst[0].len() {
+                        let mut value: [P; N] = std::array::from_fn(|i| dst[i][idx]);
+                        let other: [P; N] = std::array::from_fn(|i| src[i][idx]);
+                        mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                            &mut value, &other, &mut ctx,
                         );
+                        for (dst, el) in dst.iter_mut().zip(value.into_iter()) {
+                            dst[idx] = el;
+                        }
                     }
                 });
             }
@@ -424,41 +407,42 @@ pub(crate) fn shifted_grand_product<F: PrimeField, A: GoodAllocator>(
 
 pub(crate) fn shifted_grand_product_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    input_c0: Vec<F, A>,
-    input_c1: Vec<F, A>,
+    input: [Vec<F, A>; N],
     worker: &Worker,
-) -> [Vec<F, A>; 2] {
-    let mut input_c0 = input_c0;
-    let mut input_c1 = input_c1;
-    let work_size = input_c0.len();
+) -> [Vec<F, A>; N] {
+    let mut input = input;
+    let work_size = input[0].len();
     assert!(work_size.is_power_of_two());
     let chunk_size = worker.get_chunk_size(work_size);
     let num_chunks = Worker::compute_num_chunks(work_size, chunk_size);
 
     let mut partial_subproducts = {
         use crate::field::traits::field::Field;
-        vec![ExtensionField::<F, 2, EXT>::ZERO; num_chunks]
+        vec![ExtensionField::<F, N, EXT>::ZERO; num_chunks]
     };
 
     worker.scope(work_size, |scope, chunk_size| {
-        for ((chunk_c0, chunk_c1), acc) in input_c0
-            .chunks_mut(chunk_size)
-            .zip(input_c1.chunks_mut(chunk_size))
-            .zip(partial_subproducts.iter_mut())
+        for (mut chunk, acc) in
+            chunk_columns_mut(input.each_mut().map(|el| &mut el[..]), chunk_size)
+                .zip(partial_subproducts.iter_mut())
         {
             scope.spawn(move |_| {
                 let mut product = {
                     use crate::field::Field;
-                    ExtensionField::<F, 2, EXT>::ONE
+                    ExtensionField::<F, N, EXT>::ONE
                 };
-                for (dst_c0, dst_c1) in chunk_c0.iter_mut().zip(chunk_c1.iter_mut()) {
-                    let tmp = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*dst_c0, *dst_c1]);
+                for idx in 0..chunk[0].len() {
+                    let tmp = ExtensionField::<F, N, EXT>::from_coeff_in_base(
+                        std::array::from_fn(|i| chunk[i][idx]),
+                    );
 
-                    *dst_c0 = product.coeffs[0];
-                    *dst_c1 = product.coeffs[1];
+                    for (dst, src) in chunk.iter_mut().zip(product.coeffs.iter()) {
+                        dst[idx] = *src;
+                    }
 
                     crate::field::Field::mul_assign(&mut product, &tmp);
                 }
@@ -471,7 +455,7 @@ pub(crate) fn shifted_grand_product_in_extension<
     // multiply partial products
     let one = {
         use crate::field::Field;
-        ExtensionField::<F, 2, EXT>::ONE
+        ExtensionField::<F, N, EXT>::ONE
     };
     let mut current = one;
 
@@ -487,26 +471,26 @@ pub(crate) fn shifted_grand_product_in_extension<
     // second pass
     worker.scope(work_size, |scope, chunk_size| {
         // no multiplication for 1st chunk
-        for ((chunk_c0, chunk_c1), acc) in input_c0
-            .chunks_mut(chunk_size)
-            .skip(1)
-            .zip(input_c1.chunks_mut(chunk_size).skip(1))
-            .zip(partial_subproducts.iter())
+        for (mut chunk, acc) in
+            chunk_columns_mut(input.each_mut().map(|el| &mut el[..]), chunk_size)
+                .skip(1)
+                .zip(partial_subproducts.iter())
         {
             scope.spawn(move |_| {
-                for (dst_c0, dst_c1) in chunk_c0.iter_mut().zip(chunk_c1.iter_mut()) {
-                    let mut dst =
-                        ExtensionField::<F, 2, EXT>::from_coeff_in_base([*dst_c0, *dst_c1]);
+                for idx in 0..chunk[0].len() {
+                    let mut dst = ExtensionField::<F, N, EXT>::from_coeff_in_base(
+                        std::array::from_fn(|i| chunk[i][idx]),
+                    );
                     crate::field::Field::mul_assign(&mut dst, acc);
-                    let [c0, c1] = dst.into_coeffs_in_base();
-                    *dst_c0 = c0;
-                    *dst_c1 = c1;
+                    for (dst, src) in chunk.iter_mut().zip(dst.coeffs.into_iter()) {
+                        dst[idx] = src;
+                    }
                 }
             });
         }
     });
 
-    [input_c0, input_c1]
+    input
 }
 
 pub fn non_residues_for_copy_permutation<F: PrimeField, B: GoodAllocator>(
@@ -649,21 +633,22 @@ use crate::field::FieldExtension;
 pub(crate) fn compute_partial_products_in_extension<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
     all_copy_permutation_columns: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
     precomputed_x_poly: std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>,
     all_sigma_polys: Vec<std::sync::Arc<GenericPolynomial<F, LagrangeForm, P, A>>, B>,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
+    beta: ExtensionField<F, N, EXT>,
+    gamma: ExtensionField<F, N, EXT>,
     worker: &Worker,
     max_degree: usize,
     ctx: &mut P::Context,
 ) -> (
-    [GenericPolynomial<F, LagrangeForm, P, A>; 2], // Z(x)
-    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; 2], B>, // partial products
+    [GenericPolynomial<F, LagrangeForm, P, A>; N], // Z(x)
+    Vec<[GenericPolynomial<F, LagrangeForm, P, A>; N], B>, // partial products
 ) {
     assert!(all_copy_permutation_columns.len() > 0);
     assert_eq!(all_copy_permutation_columns.len(), all_sigma_polys.len());
@@ -692,15 +677,18 @@ pub(crate) fn compute_partial_products_in_extension<
         let sigma_polys_chunk = sigma_polys_chunk.to_vec_in(B::default());
         let non_residues_chunk = non_residues_chunk.to_vec_in(B::default());
 
-        let basis_c0 =
-            initialize_in_with_alignment_of::<F, P, _>(F::ONE, domain_size, A::default());
-        let basis_c1 =
-            initialize_in_with_alignment_of::<F, P, _>(F::ZERO, domain_size, A::default());
-        let basis_c0 = P::vec_from_base_vec(basis_c0);
-        let basis_c1 = P::vec_from_base_vec(basis_c1);
-
-        let mut partial_elementwise_product_c0 = GenericPolynomial::from_storage(basis_c0);
-        let mut partial_elementwise_product_c1 = GenericPolynomial::from_storage(basis_c1);
+        // start from 1 in extension
+        let mut partial_elementwise_product: [GenericPolynomial<F, LagrangeForm, P, A>; N] =
+            std::array::from_fn(|i| {
+                let value = if i == 0 { F::ONE } else { F::ZERO };
+                let basis = initialize_in_with_alignment_of::<F, P, _>(
+                    value,
+                    domain_size,
+                    A::default(),
+                );
+
+                GenericPolynomial::from_storage(P::vec_from_base_vec(basis))
+            });
 
         pointwise_rational_in_extension(
             copy_permutation_columns_chunk.clone(),
@@ -709,33 +697,26 @@ pub(crate) fn compute_partial_products_in_extension<
             non_residues_chunk.clone(),
             beta,
             gamma,
-            &mut partial_elementwise_product_c0,
-            &mut partial_elementwise_product_c1,
+            &mut partial_elementwise_product,
             worker,
             ctx,
         );
 
-        partial_elementwise_products.push([
-            partial_elementwise_product_c0,
-            partial_elementwise_product_c1,
-        ]);
+        partial_elementwise_products.push(partial_elementwise_product);
     }
 
-    let [almost_z_poly_c0, almost_z_poly_c1] =
-        pointwise_product_in_extension::<F, P, EXT, A>(&partial_elementwise_products, worker, ctx);
+    let almost_z_poly = pointwise_product_in_extension::<F, P, N, EXT, A>(
+        &partial_elementwise_products,
+        worker,
+        ctx,
+    );
 
     // now we can compute almost z poly, by performing elementwise multiplication
     // that should be completed in base field
 
-    let almost_z_poly_c0 = P::vec_into_base_vec(almost_z_poly_c0.into_storage());
-    let almost_z_poly_c1 = P::vec_into_base_vec(almost_z_poly_c1.into_storage());
-    let [z_poly_c0, z_poly_c1] =
-        shifted_grand_product_in_extension::<F, EXT, A>(almost_z_poly_c0, almost_z_poly_c1, worker);
-    let z_poly_c0 = P::vec_from_base_vec(z_poly_c0);
-    let z_poly_c0 = GenericPolynomial::from_storage(z_poly_c0);
-
-    let z_poly_c1 = P::vec_from_base_vec(z_poly_c1);
-    let z_poly_c1 = GenericPolynomial::from_storage(z_poly_c1);
+    let almost_z_poly = almost_z_poly.map(|el| P::vec_into_base_vec(el.into_storage()));
+    let z_poly = shifted_grand_product_in_extension::<F, N, EXT, A>(almost_z_poly, worker);
+    let z_poly = z_poly.map(|el| GenericPolynomial::from_storage(P::vec_from_base_vec(el)));
 
     // and now we need to re-materialize partial elementwise products like
     // partial_0 = z(x) * partial_elementwise_products[0]
@@ -746,10 +727,10 @@ pub(crate) fn compute_partial_products_in_extension<
 
     if partial_elementwise_products.len() == 1 {
         // there are no intermediate products in practice, we can go directly from z(x) to z(x * omega)
-        ([z_poly_c0, z_poly_c1], partial_elementwise_products)
+        (z_poly, partial_elementwise_products)
     } else {
         // we have to compute intermediates
-        let mut previous = [[z_poly_c0.clone(), z_poly_c1.clone()]];
+        let mut previous = [z_poly.clone()];
         let mut full_elementwise_products = Vec::new_in(B::default());
 
         let _ = partial_elementwise_products.pop().unwrap();
@@ -757,19 +738,20 @@ pub(crate) fn compute_partial_products_in_extension<
         // we have to apply pointwise products on top of Z(x)
 
         for el in partial_elementwise_products.into_iter() {
-            let [mut el_c0, mut el_c1] = el;
-            pointwise_product_in_extension_into::<F, P, EXT, A>(
-                &previous, &mut el_c0, &mut el_c1, worker, ctx,
+            let mut el = el;
+            pointwise_product_in_extension_into::<F, P, N, EXT, A>(
+                &previous, &mut el, worker, ctx,
             );
 
             // we have new pointwise in el, and untouched previous, so we can reuse the storage
 
-            Clone::clone_from(&mut previous[0][0], &el_c0);
-            Clone::clone_from(&mut previous[0][1], &el_c1);
-            full_elementwise_products.push([el_c0, el_c1]);
+            for (dst, src) in previous[0].iter_mut().zip(el.iter()) {
+                Clone::clone_from(dst, src);
+            }
+            full_elementwise_products.push(el);
         }
 
-        ([z_poly_c0, z_poly_c1], full_elementwise_products)
+        (z_poly, full_elementwise_products)
     }
 }
 
@@ -999,23 +981,24 @@ pub(crate) fn compute_partial_products_in_extension<
 
 pub(crate) fn compute_quotient_terms_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
     domain_size: usize,
     degree: usize,
+    num_cosets: usize,
     witness: &WitnessStorage<F, P, A, B>,
-    grand_products: &SecondStageProductsStorage<F, P, A, B>,
+    grand_products: &SecondStageProductsStorage<F, N, P, A, B>,
     setup: &SetupStorage<F, P, A, B>,
     num_intermediate_products: usize,
-    beta: ExtensionField<F, 2, EXT>,
-    gamma: ExtensionField<F, 2, EXT>,
-    alphas: Vec<ExtensionField<F, 2, EXT>>,
+    beta: ExtensionField<F, N, EXT>,
+    gamma: ExtensionField<F, N, EXT>,
+    alphas: Vec<ExtensionField<F, N, EXT>>,
     x_poly: &ArcGenericLdeStorage<F, P, A, B>,
-    dst_c0: &mut ArcGenericLdeStorage<F, P, A, B>,
-    dst_c1: &mut ArcGenericLdeStorage<F, P, A, B>,
+    dst: &mut [ArcGenericLdeStorage<F, P, A, B>; N],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
@@ -1038,8 +1021,8 @@ pub(crate) fn compute_quotient_terms_in_extension<
 
     assert_eq!(alphas.len(), num_intermediate_products + 1);
 
-    let z_poly_shifted: [_; 2] = grand_products.z_poly.clone().map(|el| {
-        let subset = el.subset_for_degree(degree);
+    let z_poly_shifted: [_; N] = grand_products.z_poly.clone().map(|el| {
+        let subset = el.subset_for_cosets(0..num_cosets);
         let subset_len = subset.storage.len();
         let mut owned_set = Vec::with_capacity_in(subset_len, B::default());
         worker.scope(subset_len, |scope, chunk_size| {
@@ -1067,13 +1050,13 @@ pub(crate) fn compute_quotient_terms_in_extension<
     let lhs = grand_products
         .intermediate_polys
         .iter()
-        .map(|el| el.clone().map(|el| el.subset_for_degree(degree)))
+        .map(|el| el.clone().map(|el| el.subset_for_cosets(0..num_cosets)))
         .chain([z_poly_shifted]);
 
     let z_poly = grand_products
         .z_poly
         .clone()
-        .map(|el| el.owned_subset_for_degree(degree));
+        .map(|el| el.owned_subset_for_degree(num_cosets));
 
     if crate::config::DEBUG_SATISFIABLE == true {
         assert_eq!(
@@ -1086,30 +1069,27 @@ pub(crate) fn compute_quotient_terms_in_extension<
         grand_products
             .intermediate_polys
             .iter()
-            .map(|el| el.clone().map(|el| el.subset_for_degree(degree))),
+            .map(|el| el.clone().map(|el| el.subset_for_cosets(0..num_cosets))),
     );
 
     let mut columns_chunks = Vec::with_capacity_in(witness.variables_columns.len(), B::default());
     witness
         .variables_columns
         .iter()
-        .map(|el| el.subset_for_degree(degree))
+        .map(|el| el.subset_for_cosets(0..num_cosets))
         .collect_into(&mut columns_chunks);
 
     let mut sigmas_chunks = Vec::with_capacity_in(setup.copy_permutation_polys.len(), B::default());
     setup
         .copy_permutation_polys
         .iter()
-        .map(|el| el.subset_for_degree(degree))
+        .map(|el| el.subset_for_cosets(0..num_cosets))
         .collect_into(&mut sigmas_chunks);
 
     // lhs * denom - rhs * num == 0, and make it over coset
 
-    let beta_c0 = P::constant(beta.coeffs[0], ctx);
-    let beta_c1 = P::constant(beta.coeffs[1], ctx);
-
-    let gamma_c0 = P::constant(gamma.coeffs[0], ctx);
-    let gamma_c1 = P::constant(gamma.coeffs[1], ctx);
+    let beta = beta.coeffs.map(|el| P::constant(el, ctx));
+    let gamma = gamma.coeffs.map(|el| P::constant(el, ctx));
 
     for (_relation_idx, (((((lhs, rhs), alpha), non_residues), variables), sigmas)) in lhs
         .zip(rhs)
@@ -1119,8 +1099,7 @@ pub(crate) fn compute_quotient_terms_in_extension<
         .zip(sigmas_chunks.chunks(degree))
         .enumerate()
     {
-        let alpha_c0 = P::constant(alpha.coeffs[0], ctx);
-        let alpha_c1 = P::constant(alpha.coeffs[1], ctx);
+        let alpha = alpha.coeffs.map(|el| P::constant(el, ctx));
 
         assert_eq!(variables.len(), sigmas.len());
         assert_eq!(variables.len(), non_residues.len());
@@ -1130,8 +1109,7 @@ pub(crate) fn compute_quotient_terms_in_extension<
 
         worker.scope(0, |scope, _| {
             for iterator in iterators.into_iter() {
-                let mut dst_c0 = dst_c0.clone();
-                let mut dst_c1 = dst_c1.clone();
+                let mut dst = dst.clone();
                 let lhs = lhs.clone();
                 let rhs = rhs.clone();
                 let x_poly = x_poly.clone();
@@ -1142,71 +1120,69 @@ pub(crate) fn compute_quotient_terms_in_extension<
                     for _ in 0..num_iterations {
                         let (outer, inner) = iterator.current();
 
-                        let mut lhs_contribution_c0 = lhs[0].storage[outer].storage[inner];
-                        let mut lhs_contribution_c1 = lhs[1].storage[outer].storage[inner];
+                        let mut lhs_contribution: [P; N] =
+                            std::array::from_fn(|i| lhs[i].storage[outer].storage[inner]);
                         for (variables, sigma) in variables.iter().zip(sigmas.iter()) {
                             // denominator is w + beta * sigma(x) + gamma
-                            let mut subres_c0 = sigma.storage[outer].storage[inner];
-                            subres_c0.mul_assign(&beta_c0, &mut ctx);
-                            subres_c0
-                                .add_assign(&variables.storage[outer].storage[inner], &mut ctx);
-                            subres_c0.add_assign(&gamma_c0, &mut ctx);
-
-                            let mut subres_c1 = sigma.storage[outer].storage[inner];
-                            subres_c1.mul_assign(&beta_c1, &mut ctx);
-                            subres_c1.add_assign(&gamma_c1, &mut ctx);
-
-                            mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                &mut lhs_contribution_c0,
-                                &mut lhs_contribution_c1,
-                                &subres_c0,
-                                &subres_c1,
+                            let mut subres = [sigma.storage[outer].storage[inner]; N];
+                            for (i, dst) in subres.iter_mut().enumerate() {
+                                dst.mul_assign(&beta[i], &mut ctx);
+                                if i == 0 {
+                                    dst.add_assign(
+                                        &variables.storage[outer].storage[inner],
+                                        &mut ctx,
+                                    );
+                                }
+                                dst.add_assign(&gamma[i], &mut ctx);
+                            }
+
+                            mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                                &mut lhs_contribution,
+                                &subres,
                                 &mut ctx,
                             );
                         }
 
-                        let mut rhs_contribution_c0 = rhs[0].storage[outer].storage[inner];
-                        let mut rhs_contribution_c1 = rhs[1].storage[outer].storage[inner];
+                        let mut rhs_contribution: [P; N] =
+                            std::array::from_fn(|i| rhs[i].storage[outer].storage[inner]);
                         let x_poly_value = x_poly.storage[outer].storage[inner];
                         for (non_res, variables) in non_residues.iter().zip(variables.iter()) {
                             // numerator is w + beta * non_res * x + gamma
-                            let mut subres_c0 = x_poly_value;
-                            subres_c0.mul_assign(non_res, &mut ctx);
-                            subres_c0.mul_assign(&beta_c0, &mut ctx);
-                            subres_c0
-                                .add_assign(&variables.storage[outer].storage[inner], &mut ctx);
-                            subres_c0.add_assign(&gamma_c0, &mut ctx);
-
-                            let mut subres_c1 = x_poly_value;
-                            subres_c1.mul_assign(non_res, &mut ctx);
-                            subres_c1.mul_assign(&beta_c1, &mut ctx);
-                            subres_c1.add_assign(&gamma_c1, &mut ctx);
-
-                            mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                &mut rhs_contribution_c0,
-                                &mut rhs_contribution_c1,
-                                &subres_c0,
-                                &subres_c1,
+                            let mut common = x_poly_value;
+                            common.mul_assign(non_res, &mut ctx);
+                            let mut subres = [common; N];
+                            for (i, dst) in subres.iter_mut().enumerate() {
+                                dst.mul_assign(&beta[i], &mut ctx);
+                                if i == 0 {
+                                    dst.add_assign(
+                                        &variables.storage[outer].storage[inner],
+                                        &mut ctx,
+                                    );
+                                }
+                                dst.add_assign(&gamma[i], &mut ctx);
+                            }
+
+                            mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                                &mut rhs_contribution,
+                                &subres,
                                 &mut ctx,
                             );
                         }
 
-                        let mut contribution_c0 = lhs_contribution_c0;
-                        contribution_c0.sub_assign(&rhs_contribution_c0, &mut ctx);
-                        let mut contribution_c1 = lhs_contribution_c1;
-                        contribution_c1.sub_assign(&rhs_contribution_c1, &mut ctx);
+                        let mut contribution = lhs_contribution;
+                        for (dst, src) in contribution.iter_mut().zip(rhs_contribution.iter()) {
+                            dst.sub_assign(src, &mut ctx);
+                        }
 
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            &mut contribution_c0,
-                            &mut contribution_c1,
-                            &alpha_c0,
-                            &alpha_c1,
+                        mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                            &mut contribution,
+                            &alpha,
                             &mut ctx,
                         );
 
                         if crate::config::DEBUG_SATISFIABLE == true && outer == 0 {
                             // only on base coset
-                            let t = [contribution_c0];
+                            let t = [contribution[0]];
                             let as_base = P::slice_into_base_slice(&t);
                             for el in as_base.iter() {
                                 if el.is_zero() == false {
@@ -1232,13 +1208,11 @@ pub(crate) fn compute_quotient_terms_in_extension<
                             }
                         }
 
-                        unsafe { std::sync::Arc::get_mut_unchecked(&mut dst_c0.storage[outer]) }
-                            .storage[inner]
-                            .add_assign(&contribution_c0, &mut ctx);
-
-                        unsafe { std::sync::Arc::get_mut_unchecked(&mut dst_c1.storage[outer]) }
-                            .storage[inner]
-                            .add_assign(&contribution_c1, &mut ctx);
+                        for (dst, src) in dst.iter_mut().zip(contribution.iter()) {
+                            unsafe { std::sync::Arc::get_mut_unchecked(&mut dst.storage[outer]) }
+                                .storage[inner]
+                                .add_assign(src, &mut ctx);
+                        }
 
                         iterator.advance();
                     }
@@ -59,16 +59,38 @@ impl<
     }
 
     // for 1 variable
+    #[track_caller]
     #[inline]
     fn alloc_variable_without_value(&mut self) -> Variable {
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.record_places(
+                self.next_available_place_idx,
+                1,
+                std::panic::Location::caller(),
+            );
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.current_stats_mut().num_variables += 1;
+        }
         let var = Variable::from_variable_index(self.next_available_place_idx);
         self.next_available_place_idx += 1;
 
         var
     }
+    #[track_caller]
     #[inline]
     fn alloc_multiple_variables_without_values<const N: usize>(&mut self) -> [Variable; N] {
         debug_assert!(N < u32::MAX as usize);
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.record_places(
+                self.next_available_place_idx,
+                N,
+                std::panic::Location::caller(),
+            );
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.current_stats_mut().num_variables += N;
+        }
         let current_idx = self.next_available_place_idx;
         self.next_available_place_idx += N as u64;
 
@@ -77,15 +99,37 @@ impl<
 
         result
     }
+    #[track_caller]
     #[inline]
     fn alloc_witness_without_value(&mut self) -> Witness {
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.record_places(
+                self.next_available_place_idx,
+                1,
+                std::panic::Location::caller(),
+            );
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.current_stats_mut().num_witnesses += 1;
+        }
         let wit = Witness::from_witness_index(self.next_available_place_idx);
         self.next_available_place_idx += 1;
 
         wit
     }
+    #[track_caller]
     #[inline]
     fn alloc_multiple_witnesses_without_values<const N: usize>(&mut self) -> [Witness; N] {
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.record_places(
+                self.next_available_place_idx,
+                N,
+                std::panic::Location::caller(),
+            );
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.current_stats_mut().num_witnesses += N;
+        }
         let current_idx = self.next_available_place_idx;
         self.next_available_place_idx += N as u64;
 
@@ -353,6 +397,11 @@ impl<
         }
 
         self.copy_permutation_data[column][row] = var;
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces
+                .current_stats_mut()
+                .num_copy_permutation_cells += 1;
+        }
     }
     #[inline]
     fn place_constants<const N: usize>(
@@ -423,6 +472,25 @@ impl<
 
         self.witness_placement_data[column][row] = witness;
     }
+    #[inline(always)]
+    fn push_namespace(&mut self, name: &str) {
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.push(name);
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.push_scope(name);
+        }
+    }
+    #[inline(always)]
+    fn pop_namespace(&mut self) {
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.pop();
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.pop_scope();
+        }
+    }
+    #[track_caller]
     #[inline]
     fn place_gate<G: Gate<F>>(&mut self, gate: &G, row: usize) {
         debug_assert!(
@@ -457,6 +525,9 @@ impl<
             self.next_available_row += 1;
             debug_assert!(self.gates_application_sets.len() == row);
             self.gates_application_sets.push(idx);
+            if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+                self.namespaces.current_stats_mut().num_general_purpose_rows += 1;
+            }
         } else {
             debug_assert!(matches!(
                 gate.placement_type(),
@@ -471,6 +542,11 @@ impl<
             "exhausted general purpose columns capacity trying to add gate {}",
             std::any::type_name::<G>()
         );
+
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites
+                .record_general_purpose_row(row, std::panic::Location::caller());
+        }
     }
 
     #[inline]
@@ -541,6 +617,11 @@ impl<
             self.copy_permutation_data[offset].push(var);
             debug_assert_eq!(self.copy_permutation_data[offset].len(), row + 1);
         }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces
+                .current_stats_mut()
+                .num_copy_permutation_cells += 1;
+        }
     }
     #[inline]
     fn place_witness_specialized<G: Gate<F>>(
@@ -601,8 +682,9 @@ impl<
         );
         self.witness_placement_data[offset].push(witness);
     }
+    #[track_caller]
     #[inline(always)]
-    fn place_gate_specialized<G: Gate<F>>(&mut self, _gate: &G, _repetition: usize, row: usize) {
+    fn place_gate_specialized<G: Gate<F>>(&mut self, _gate: &G, repetition: usize, row: usize) {
         debug_assert!(
             self.gate_is_allowed::<G>(),
             "gate {} is not configured for CS",
@@ -626,6 +708,19 @@ impl<
             .entry(std::any::TypeId::of::<G>())
             .or_default();
         *entry = std::cmp::max(row, *entry);
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces
+                .current_stats_mut()
+                .num_specialized_gate_instances += 1;
+        }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_CALL_SITES {
+            self.call_sites.record_specialized_instance(
+                std::any::TypeId::of::<G>(),
+                repetition,
+                row,
+                std::panic::Location::caller(),
+            );
+        }
         // actually we do not need to "do" anything here, let the gate handle it's placement itself.
         // May be later on we will intoduce counters for self-checks
     }
@@ -804,8 +899,14 @@ impl<
             }
             offset += 1;
         }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces
+                .current_stats_mut()
+                .num_copy_permutation_cells += N;
+        }
     }
 
+    #[track_caller]
     fn perform_lookup<const KEYS: usize, const VALUES: usize>(
         &mut self,
         table_id: u32,
@@ -858,6 +959,10 @@ impl<
     }
 
     fn enforce_lookup<const N: usize>(&mut self, table_id: u32, keys_and_values: &[Variable; N]) {
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces.current_stats_mut().num_lookups += 1;
+        }
+
         match self.lookup_parameters {
             LookupParameters::NoLookup => {
                 panic!("Lookup is not allowed for that CS");
@@ -936,6 +1041,11 @@ impl<
         for (offset, var) in var.iter().enumerate() {
             self.copy_permutation_data[starting_column + offset][row] = *var;
         }
+        if <Self::Config as CSConfig>::DebugConfig::TRACK_NAMESPACES {
+            self.namespaces
+                .current_stats_mut()
+                .num_copy_permutation_cells += N;
+        }
     }
 
     // Lookup table related things
@@ -1046,7 +1156,7 @@ impl<
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
 
     use std::alloc::Global;
 
@@ -1058,9 +1168,11 @@ mod test {
     use crate::cs::cs_builder_verifier::CsVerifierBuilder;
     use crate::cs::gates::{fma_gate_without_constant::*, NopGate, ReductionGate, ZeroCheckGate};
 
+    use crate::cs::implementations::encoding::EncodableTreeHasher;
+    use crate::cs::implementations::namespaces::NamespaceReport;
     use crate::cs::implementations::pow::NoPow;
-    use crate::cs::implementations::prover::ProofConfig;
-    use crate::cs::implementations::transcript::GoldilocksPoisedonTranscript;
+    use crate::cs::implementations::prover::{ProofConfig, ZeroKnowledgeError, SALT_SIZE};
+    use crate::cs::implementations::transcript::{GoldilocksPoisedonTranscript, Transcript};
 
     use crate::dag::CircuitResolverOpts;
     use crate::field::goldilocks::GoldilocksExt2;
@@ -1175,6 +1287,7 @@ mod test {
         };
 
         let (proof, vk) = cs.prove_one_shot::<
+            2,
             GoldilocksExt2,
             GoldilocksPoisedonTranscript,
             GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
@@ -1198,65 +1311,87 @@ mod test {
         );
 
         assert!(is_valid);
-    }
 
-    #[test]
-    #[ignore = "Computation of poly pairs for lookups unimplemented"]
-    fn prove_simple_with_lookups() {
-        pub const TEST_TABLE_NAME: &str = "Test table";
+        use crate::cs::implementations::verifier::{OracleType, VerificationError};
+        use crate::field::ExtensionField;
 
-        #[derive(Derivative)]
-        #[derivative(Clone, Copy, Debug)]
-        pub struct TestTableMarker;
+        let mut malformed_proof = proof.clone();
+        let _ = malformed_proof.witness_oracle_cap.pop();
+        let result = verifier.verify_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed_proof);
+        assert_eq!(
+            result,
+            Err(VerificationError::MalformedCap {
+                oracle: OracleType::Witness,
+                expected: vk.fixed_parameters.cap_size,
+                got: vk.fixed_parameters.cap_size - 1,
+            })
+        );
 
-        type P = GoldilocksField;
-        // type P = MixedGL;
-        type RCfg = <DevCSConfig as CSConfig>::ResolverConfig;
+        let mut malformed_proof = proof.clone();
+        malformed_proof.values_at_z[0].add_assign(&ExtensionField::<F, 2, GoldilocksExt2>::ONE);
+        let result = verifier.verify_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed_proof);
+        assert_eq!(result, Err(VerificationError::QuotientIdentityFailed));
+
+        let mut malformed_proof = proof.clone();
+        malformed_proof.queries_per_fri_repetition[1]
+            .witness_query
+            .leaf_elements[0]
+            .add_assign(&F::ONE);
+        let result = verifier.verify_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed_proof);
+        assert!(matches!(
+            result,
+            Err(VerificationError::InvalidMerklePath {
+                query_idx: 1,
+                oracle: OracleType::Witness,
+                ..
+            })
+        ));
 
-        pub fn create_test_table<F: SmallField>() -> LookupTable<F, 3> {
-            let mut all_keys = Vec::with_capacity(64);
-            for a in 0..8 {
-                for b in 0..8 {
-                    let key = smallvec::smallvec![
-                        F::from_u64_unchecked(a as u64),
-                        F::from_u64_unchecked(b as u64)
-                    ];
-                    all_keys.push(key);
-                }
-            }
-            LookupTable::new_from_keys_and_generation_function(
-                &all_keys,
-                TEST_TABLE_NAME.to_string(),
-                2,
-                |keys| {
-                    let a = keys[0].as_u64_reduced() as u8;
-                    let b = keys[1].as_u64_reduced() as u8;
+        let worker = Worker::new_with_num_threads(2);
+        let proofs = vec![proof.clone(), malformed_proof, proof];
+        let results = verifier.verify_batch::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &proofs, &worker);
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+    }
 
-                    let xor_result = a ^ b;
-                    let or_result = a | b;
-                    let and_result = a & b;
-                    let value =
-                        (xor_result as u64) << 32 | (or_result as u64) << 16 | (and_result as u64);
+    #[test]
+    fn prove_simple_over_cubic_extension() {
+        use crate::cs::gates::{ConstantsAllocatorGate, FmaGateInExtensionWithoutConstant};
+        use crate::cs::implementations::verifier::VerificationError;
+        use crate::field::goldilocks::GoldilocksExt3;
+        use crate::field::ExtensionField;
 
-                    smallvec::smallvec![F::from_u64_unchecked(value)]
-                },
-            )
-        }
+        type P = GoldilocksField;
+        type Ext = GoldilocksExt3;
+        type H = GoldilocksPoseidonSponge<AbsorptionModeOverwrite>;
 
         let geometry = CSGeometry {
-            num_columns_under_copy_permutation: 8,
+            num_columns_under_copy_permutation: 12,
             num_witness_columns: 0,
-            num_constant_columns: 2,
+            num_constant_columns: 6,
             max_allowed_constraint_degree: 8,
         };
 
-        // let cs = CSReferenceImplementation::<
-        //     GoldilocksField,
-        //     P,
-        //     DevCSConfig,
-        //     _,
-        //     _,
-        // >::new_for_geometry(geometry, 512, 128);
+        let max_variables = 512;
+        let max_trace_len = 64;
 
         fn configure<
             T: CsBuilderImpl<F, T>,
@@ -1265,21 +1400,14 @@ mod test {
         >(
             builder: CsBuilder<T, F, GC, TB>,
         ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
-            // allow lookup
-            let builder = builder.allow_lookup(LookupParameters::TableIdAsVariable {
-                width: 3,
-                share_table_id: false,
-            });
-
             let builder = ConstantsAllocatorGate::configure_builder(
                 builder,
                 GatePlacementStrategy::UseGeneralPurposeColumns,
             );
-            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            let builder = FmaGateInExtensionWithoutConstant::<F, Ext, 3>::configure_builder(
                 builder,
                 GatePlacementStrategy::UseGeneralPurposeColumns,
             );
-            // we pad with NOP gates, so we should formally allow it
             let builder = NopGate::configure_builder(
                 builder,
                 GatePlacementStrategy::UseGeneralPurposeColumns,
@@ -1288,94 +1416,123 @@ mod test {
             builder
         }
 
-        let builder = new_builder(CsReferenceImplementationBuilder::<
-            GoldilocksField,
-            P,
-            DevCSConfig,
-        >::new(geometry, 128));
-        let builder = configure(builder);
-        let mut cs = builder.build(CircuitResolverOpts::new(512));
-
-        let table = create_test_table();
-        let table_id = cs.add_lookup_table::<TestTableMarker, 3>(table);
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, max_trace_len);
+        let builder = new_builder::<_, F>(builder_impl);
 
-        for _ in 0..100 {
-            let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
-            let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
-            let c = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(3));
+        let builder = configure(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(max_variables));
 
-            let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+        let mut k = ExtensionField::<F, 3, Ext>::ONE;
+        k.coeffs[2] = F::MINUS_ONE;
+        let mut m = ExtensionField::<F, 3, Ext>::TWO;
+        m.coeffs[1] = F::TWO;
+
+        let mut acc: [Variable; 3] = std::array::from_fn(|idx| {
+            cs.alloc_single_variable_from_witness(F::from_u64_unchecked(idx as u64 + 1))
+        });
+        for round in 0..16 {
+            let b: [Variable; 3] = std::array::from_fn(|idx| {
+                cs.alloc_single_variable_from_witness(F::from_u64_unchecked(
+                    (round * 3 + idx) as u64 + 7,
+                ))
+            });
+            acc = FmaGateInExtensionWithoutConstant::<F, Ext, 3>::compute_fma_in_extension(
                 &mut cs,
-                GoldilocksField::TWO,
-                (a, b),
-                GoldilocksField::MINUS_ONE,
-                c,
+                k,
+                (acc, b),
+                m,
+                b,
             );
-
-            let [_e] = cs.perform_lookup(table_id, &[a, d]);
         }
 
-        use super::gates::constant_allocator::*;
-
-        // make few constants
-        cs.allocate_constant(GoldilocksField::from_u64_unchecked(3));
+        let zero = cs.allocate_constant(F::ZERO);
+        let one = cs.allocate_constant(F::ONE);
+        let _ = FmaGateInExtensionWithoutConstant::<F, Ext, 3>::create_inversion_constraint(
+            &mut cs, acc, zero, one,
+        );
 
         cs.pad_and_shrink();
 
-        dbg!(&cs.max_trace_len);
-
-        let worker = Worker::new();
+        let worker = Worker::new_with_num_threads(1);
         let cs = cs.into_assembly::<Global>();
 
-        let lde_factor_to_use = 16;
         let proof_config = ProofConfig {
-            fri_lde_factor: lde_factor_to_use,
+            fri_lde_factor: 16,
             pow_bits: 0,
             ..Default::default()
         };
 
-        let (proof, vk) = cs.prove_one_shot::<
-            GoldilocksExt2,
-            GoldilocksPoisedonTranscript,
-            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
-            NoPow,
-        >(&worker, proof_config, ());
+        let (proof, vk) = cs.prove_one_shot::<3, Ext, GoldilocksPoisedonTranscript, H, NoPow>(
+            &worker,
+            proof_config,
+            (),
+        );
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, Ext, 3>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
         let verifier = builder.build(());
 
-        let is_valid = verifier.verify::<
-            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
-            GoldilocksPoisedonTranscript,
-            NoPow
-        >(
+        let is_valid = verifier.verify::<H, GoldilocksPoisedonTranscript, NoPow>((), &vk, &proof);
+        assert!(is_valid);
+
+        let mut malformed_proof = proof.clone();
+        malformed_proof.values_at_z[0].add_assign(&ExtensionField::<F, 3, Ext>::ONE);
+        let result = verifier.verify_detailed::<H, GoldilocksPoisedonTranscript, NoPow>(
             (),
             &vk,
-            &proof,
+            &malformed_proof,
         );
+        assert_eq!(result, Err(VerificationError::QuotientIdentityFailed));
 
-        assert!(is_valid);
+        // every coefficient of the extension is committed in FRI
+        let mut malformed_proof = proof;
+        malformed_proof.final_fri_monomials[2][0].add_assign(&F::ONE);
+        assert!(!verifier.verify::<H, GoldilocksPoisedonTranscript, NoPow>(
+            (),
+            &vk,
+            &malformed_proof
+        ));
     }
 
     #[test]
-    #[ignore = "Unimplemented part of prover is called"]
-    fn prove_simple_specialized() {
-        type P = GoldilocksField;
-        // type P = MixedGL;
-        type RCfg = <DevCSConfig as CSConfig>::ResolverConfig;
+    fn prove_simple_over_babybear() {
+        prove_simple_over_babybear_impl::<crate::field::babybear::BabyBearField>();
+    }
+
+    #[test]
+    fn prove_simple_over_packed_babybear() {
+        prove_simple_over_babybear_impl::<crate::field::babybear::MixedBabyBear>();
+    }
+
+    fn prove_simple_over_babybear_impl<
+        P: crate::field::traits::field_like::PrimeFieldLikeVectorized<
+            Base = crate::field::babybear::BabyBearField,
+        >,
+    >() {
+        use crate::algebraic_props::sponge::BabyBearPoseidon2Sponge;
+        use crate::cs::gates::ConstantsAllocatorGate;
+        use crate::cs::implementations::transcript::BabyBearPoseidon2Transcript;
+        use crate::cs::implementations::verifier::VerificationError;
+        use crate::field::babybear::{BabyBearExt4, BabyBearField};
+        use crate::field::ExtensionField;
+
+        type F = BabyBearField;
+        type Ext = BabyBearExt4;
+        type TR = BabyBearPoseidon2Transcript;
+        type H = BabyBearPoseidon2Sponge<AbsorptionModeOverwrite>;
 
         let geometry = CSGeometry {
-            num_columns_under_copy_permutation: 0,
+            num_columns_under_copy_permutation: 8,
             num_witness_columns: 0,
-            num_constant_columns: 0,
+            num_constant_columns: 2,
             max_allowed_constraint_degree: 8,
         };
 
-        let max_variables = 1 << 16;
-        let max_trace_len = 64;
+        let max_variables = 512;
+        let max_trace_len = 128;
 
         fn configure<
             T: CsBuilderImpl<F, T>,
@@ -1384,17 +1541,24 @@ mod test {
         >(
             builder: CsBuilder<T, F, GC, TB>,
         ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
-            let builder = BooleanConstraintGate::configure_builder(
+            let builder = ConstantsAllocatorGate::configure_builder(
                 builder,
-                GatePlacementStrategy::UseSpecializedColumns {
-                    num_repetitions: 2,
-                    share_constants: false,
-                },
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = ZeroCheckGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+                false,
             );
             let builder = NopGate::configure_builder(
                 builder,
                 GatePlacementStrategy::UseGeneralPurposeColumns,
             );
+
             builder
         }
 
@@ -1405,58 +1569,1089 @@ mod test {
         let builder = configure(builder);
         let mut cs = builder.build(CircuitResolverOpts::new(max_variables));
 
-        for _i in 0..50 {
-            let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
-            let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(0));
-
-            if _i % 2 == 0 {
-                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, a);
-                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, b);
+        let mut previous = None;
+        for round in 0..100 {
+            let a = if let Some(previous) = previous.take() {
+                previous
             } else {
-                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, b);
-                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, a);
-            }
+                cs.alloc_single_variable_from_witness(F::from_u64_unchecked(1))
+            };
+            let b = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(round + 2));
+            let c = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(round + 3));
+
+            let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::TWO,
+                (a, b),
+                F::MINUS_ONE,
+                c,
+            );
+            let e = ZeroCheckGate::check_if_zero(&mut cs, d);
+            previous = Some(e);
         }
 
-        cs.pad_and_shrink();
+        cs.allocate_constant(F::from_u64_unchecked(3));
+        cs.allocate_constant(F::from_u64_unchecked(4));
 
-        dbg!(&cs.max_trace_len);
+        cs.pad_and_shrink();
 
         let worker = Worker::new_with_num_threads(1);
-
         let cs = cs.into_assembly::<Global>();
 
-        // assert!(cs.check_if_satisfied(&worker));
-
-        let lde_factor_to_use = 16;
+        // PoW challenge is witnessed as two 32-bit words, that doesn't fit into BabyBear
         let proof_config = ProofConfig {
-            fri_lde_factor: lde_factor_to_use,
+            fri_lde_factor: 16,
             pow_bits: 0,
             ..Default::default()
         };
 
-        let (proof, vk) = cs.prove_one_shot::<
-            GoldilocksExt2,
-            GoldilocksPoisedonTranscript,
-            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
-            NoPow,
-        >(&worker, proof_config, ());
+        let (proof, vk) = cs.prove_one_shot::<4, Ext, TR, H, NoPow>(&worker, proof_config, ());
 
-        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, Ext, 4>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = configure(builder);
         let verifier = builder.build(());
 
-        let is_valid = verifier.verify::<
-            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
-            GoldilocksPoisedonTranscript,
-            NoPow
-        >(
-            (),
-            &vk,
-            &proof,
-        );
+        let is_valid = verifier.verify::<H, TR, NoPow>((), &vk, &proof);
+        assert!(is_valid);
+
+        let mut malformed_proof = proof.clone();
+        malformed_proof.values_at_z[0].add_assign(&ExtensionField::<F, 4, Ext>::ONE);
+        let result = verifier.verify_detailed::<H, TR, NoPow>((), &vk, &malformed_proof);
+        assert_eq!(result, Err(VerificationError::QuotientIdentityFailed));
+
+        let mut malformed_proof = proof;
+        malformed_proof.final_fri_monomials[3][0].add_assign(&F::ONE);
+        assert!(!verifier.verify::<H, TR, NoPow>((), &vk, &malformed_proof));
+    }
+
+    pub(crate) fn configure_fma_chain<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        use super::gates::constant_allocator::*;
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        builder
+    }
+
+    // chain of `num_gates` FMA gates, two of them take a row. Trace is not padded yet
+    pub(crate) fn synthesize_fma_chain(
+        geometry: CSGeometry,
+        max_trace_len: usize,
+        num_gates: usize,
+    ) -> CSReferenceImplementation<
+        F,
+        GoldilocksField,
+        DevCSConfig,
+        impl GateConfigurationHolder<F>,
+        impl StaticToolboxHolder,
+    > {
+        let builder_impl = CsReferenceImplementationBuilder::<F, GoldilocksField, DevCSConfig>::new(
+            geometry,
+            max_trace_len,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure_fma_chain(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(1024));
+
+        let mut previous = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(1));
+        for _ in 0..num_gates {
+            let b = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(2));
+            let c = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(3));
+
+            previous = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::TWO,
+                (previous, b),
+                F::MINUS_ONE,
+                c,
+            );
+        }
+
+        // make few constants
+        cs.allocate_constant(F::from_u64_unchecked(3));
+
+        cs
+    }
+
+    #[test]
+    fn prove_simple_with_fri_schedule() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        // 150 gates take two per row, so the trace is padded to 128
+        let mut cs = synthesize_fma_chain(geometry, 128, 150);
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(1);
+        let cs = cs.into_assembly::<Global>();
+
+        // fold by 16 first, and then by 4, so final degree is 2
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            pow_bits: 0,
+            fri_folding_schedule: Some(vec![4, 2]),
+            ..Default::default()
+        };
+
+        let (proof, vk) = cs.prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, ());
+
+        assert_eq!(proof.fri_intermediate_oracles_caps.len(), 1);
+        assert_eq!(proof.final_fri_monomials[0].len(), 2);
+        assert_eq!(
+            proof.queries_per_fri_repetition[0].fri_queries[0]
+                .leaf_elements
+                .len(),
+            32
+        );
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure_fma_chain(builder);
+        let verifier = builder.build(());
+
+        let result = verifier.verify_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &proof);
+        assert_eq!(result, Ok(()));
+
+        use crate::cs::implementations::prover::FriScheduleError;
+        use crate::cs::implementations::verifier::VerificationError;
+
+        let mut malformed_proof = proof.clone();
+        malformed_proof.proof_config.fri_folding_schedule = Some(vec![6]);
+        let result = verifier.verify_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed_proof);
+        assert_eq!(
+            result,
+            Err(VerificationError::InvalidFriSchedule(
+                FriScheduleError::InvalidFoldingStep {
+                    step: 0,
+                    interpolation_degree_log2: 6,
+                }
+            ))
+        );
+
+        let mut malformed_proof = proof;
+        malformed_proof.proof_config.fri_folding_schedule = Some(vec![4, 4]);
+        let result = verifier.verify_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed_proof);
+        assert_eq!(
+            result,
+            Err(VerificationError::InvalidFriSchedule(
+                FriScheduleError::FoldsBeyondDegree {
+                    total_folding_log2: 8,
+                    initial_degree_log2: 7,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn prove_simple_with_deduplicated_paths() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let mut cs = synthesize_fma_chain(geometry, 128, 150);
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(1);
+        let cs = cs.into_assembly::<Global>();
+
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let (proof, vk) = cs.prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, ());
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure_fma_chain(builder);
+        let verifier = builder.build(());
+
+        let deduplicated = verifier
+            .deduplicate_query_paths::<
+                GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+                GoldilocksPoisedonTranscript,
+                NoPow,
+            >((), &vk, proof.clone())
+            .unwrap();
+        assert!(deduplicated.to_bytes().len() < proof.to_bytes().len());
+
+        use crate::cs::implementations::proof::ProofWithDeduplicatedPaths;
+
+        let encoded = deduplicated.to_bytes();
+        let deduplicated = ProofWithDeduplicatedPaths::<
+            F,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksExt2,
+        >::from_bytes(&encoded)
+        .unwrap();
+
+        let result = verifier.verify_deduplicated_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &deduplicated);
+        assert_eq!(result, Ok(()));
+
+        use crate::cs::implementations::verifier::{OracleType, VerificationError};
+
+        let mut malformed = deduplicated.clone();
+        malformed.paths.setup_oracle.pop();
+        let result = verifier.verify_deduplicated_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed);
+        assert_eq!(
+            result,
+            Err(VerificationError::MalformedDeduplicatedPaths {
+                oracle: OracleType::Setup
+            })
+        );
+
+        let mut malformed = deduplicated;
+        malformed.paths.fri_oracles[0][0] = malformed.paths.fri_oracles[0][1];
+        let result = verifier.verify_deduplicated_detailed::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &vk, &malformed);
+        assert!(matches!(
+            result,
+            Err(VerificationError::InvalidMerklePath {
+                oracle: OracleType::Fri(0),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn prove_simple_out_of_core() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let synthesize = || {
+            let mut cs = synthesize_fma_chain(geometry, 128, 100);
+            cs.pad_and_shrink();
+
+            cs.into_assembly::<Global>()
+        };
+
+        let worker = Worker::new_with_num_threads(4);
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let (proof, vk) = synthesize().prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config.clone(), ());
+
+        use crate::cs::implementations::out_of_core::OutOfCoreConfig;
+
+        let spill_directory = std::env::temp_dir().join("boojum_prove_simple_out_of_core");
+        let out_of_core = OutOfCoreConfig::new(spill_directory.clone());
+
+        let (spilled_proof, spilled_vk) = synthesize().prove_one_shot_out_of_core::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, (), &out_of_core);
+
+        // spilled oracles are removed as soon as proof is made
+        let num_leftover_files = std::fs::read_dir(&spill_directory)
+            .unwrap()
+            .filter(|el| {
+                el.as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .contains(&format!("_{}_", std::process::id()))
+            })
+            .count();
+        assert_eq!(num_leftover_files, 0);
+
+        assert_eq!(vk.to_bytes(), spilled_vk.to_bytes());
+        assert_eq!(proof.to_bytes(), spilled_proof.to_bytes());
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure_fma_chain(builder);
+        let verifier = builder.build(());
+
+        let is_valid = verifier.verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >((), &spilled_vk, &spilled_proof);
+        assert!(is_valid);
+    }
+
+    fn prove_with_checkpoints<
+        TR: Transcript<F, CompatibleCap = H::Output> + serde::Serialize + serde::de::DeserializeOwned,
+        H: EncodableTreeHasher<F>,
+    >(
+        name: &str,
+        transcript_params: TR::TransciptParameters,
+    ) where
+        H::Output: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        type P = GoldilocksField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let synthesize = || {
+            let mut cs = synthesize_fma_chain(geometry, 128, 100);
+            cs.pad_and_shrink();
+
+            cs.into_assembly::<Global>()
+        };
+
+        let worker = Worker::new_with_num_threads(4);
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let (proof, vk) = synthesize().prove_one_shot::<2, GoldilocksExt2, TR, H, NoPow>(
+            &worker,
+            proof_config.clone(),
+            transcript_params.clone(),
+        );
+
+        use crate::cs::implementations::out_of_core::OutOfCoreConfig;
+        use crate::cs::implementations::prover::{ProverStage, ProverState};
+
+        let spill_directory = std::env::temp_dir()
+            .join("boojum_prove_with_checkpoints")
+            .join(name);
+        // leftovers of interrupted runs
+        let _ = std::fs::remove_dir_all(&spill_directory);
+        let out_of_core = OutOfCoreConfig::new(spill_directory.clone());
+
+        let mut assembly = synthesize();
+        let setup_base = assembly.create_base_setup(&worker, &mut ());
+        let (setup, resumed_vk, setup_tree) = assembly.materialize_setup_storage_and_vk::<H>(
+            proof_config.fri_lde_factor,
+            proof_config.merkle_tree_cap_size,
+            &worker,
+            &mut (),
+        );
+        let witness_set = assembly.take_witness(&worker);
+
+        let mut state = assembly.start_proof::<2, GoldilocksExt2, TR, H>(
+            &worker,
+            witness_set,
+            &setup_base,
+            &resumed_vk,
+            proof_config,
+            transcript_params.clone(),
+            Some(&out_of_core),
+        );
+
+        let mut stages = vec![];
+        loop {
+            let checkpoint = bincode::serialize(&state).unwrap();
+            // simulate the prover being interrupted, spilled oracles must stay on disk
+            state.persist_spilled_oracles();
+            drop(state);
+            let num_spilled_files = std::fs::read_dir(&spill_directory).unwrap().count();
+            // deserialized state does not own the files, so dropping it keeps them around
+            let unclaimed =
+                bincode::deserialize::<ProverState<F, P, GoldilocksExt2, TR, H>>(&checkpoint)
+                    .unwrap();
+            drop(unclaimed);
+            assert_eq!(
+                std::fs::read_dir(&spill_directory).unwrap().count(),
+                num_spilled_files
+            );
+
+            state = bincode::deserialize::<ProverState<F, P, GoldilocksExt2, TR, H>>(&checkpoint)
+                .unwrap();
+            // files outside of the spill directory can not be claimed
+            let other_directory = OutOfCoreConfig::new(spill_directory.join("other"));
+            std::fs::create_dir_all(&other_directory.spill_directory).unwrap();
+            assert!(state.claim_spilled_files(&other_directory).is_err());
+            std::fs::remove_dir(&other_directory.spill_directory).unwrap();
+            state.claim_spilled_files(&out_of_core).unwrap();
+
+            stages.push(state.stage());
+            if state.stage() == ProverStage::FriCommitted {
+                break;
+            }
+
+            assembly.advance_proof::<2, GoldilocksExt2, TR, H, NoPow>(
+                &worker,
+                &mut state,
+                &setup_base,
+                &setup,
+                &setup_tree,
+                Some(&out_of_core),
+            );
+        }
+
+        assert_eq!(
+            stages,
+            vec![
+                ProverStage::WitnessCommitted,
+                ProverStage::SecondStageCommitted,
+                ProverStage::QuotientCommitted,
+                ProverStage::DeepOpeningsComputed,
+                ProverStage::FriCommitted,
+            ]
+        );
+
+        let resumed_proof = assembly.finish_proof::<2, GoldilocksExt2, TR, H, NoPow>(
+            &worker,
+            state,
+            &setup_base,
+            &setup,
+            &setup_tree,
+            Some(&out_of_core),
+        );
+        // claimed oracles and trees remove their files
+        assert_eq!(std::fs::read_dir(&spill_directory).unwrap().count(), 0);
+
+        assert_eq!(vk.to_bytes(), resumed_vk.to_bytes());
+        assert_eq!(proof.to_bytes(), resumed_proof.to_bytes());
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure_fma_chain(builder);
+        let verifier = builder.build(());
+
+        let is_valid =
+            verifier.verify::<H, TR, NoPow>(transcript_params.clone(), &resumed_vk, &resumed_proof);
+        assert!(is_valid);
+
+        // checkpoint is bound to the setup it was started with
+        let mut assembly = synthesize();
+        let witness_set = assembly.take_witness(&worker);
+        let mut state = assembly.start_proof::<2, GoldilocksExt2, TR, H>(
+            &worker,
+            witness_set,
+            &setup_base,
+            &resumed_vk,
+            resumed_proof.proof_config.clone(),
+            transcript_params,
+            None,
+        );
+        let (other_setup, _, other_setup_tree) = assembly.materialize_setup_storage_and_vk::<H>(
+            resumed_proof.proof_config.fri_lde_factor,
+            resumed_proof.proof_config.merkle_tree_cap_size * 2,
+            &worker,
+            &mut (),
+        );
+        let resumed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            assembly.advance_proof::<2, GoldilocksExt2, TR, H, NoPow>(
+                &worker,
+                &mut state,
+                &setup_base,
+                &other_setup,
+                &other_setup_tree,
+                None,
+            )
+        }));
+        assert!(resumed.is_err());
+    }
+
+    #[test]
+    fn prove_simple_with_checkpoints() {
+        prove_with_checkpoints::<
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+        >("poseidon", ());
+    }
+
+    #[test]
+    fn prove_with_checkpoints_over_byte_transcripts() {
+        use crate::cs::implementations::transcript::{Blake2sTranscript, Keccak256Transcript};
+
+        prove_with_checkpoints::<Blake2sTranscript, blake2::Blake2s256>("blake2s", ());
+        prove_with_checkpoints::<Keccak256Transcript, sha3::Keccak256>("keccak256", ());
+    }
+
+    #[test]
+    fn prove_simple_zero_knowledge() {
+        type H = GoldilocksPoseidonSponge<AbsorptionModeOverwrite>;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 1,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let max_trace_len = 256;
+
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            security_level: 20,
+            pow_bits: 0,
+            zero_knowledge: true,
+            ..Default::default()
+        };
+        let num_blinding_rows = proof_config.num_blinding_rows(max_trace_len);
+
+        let synthesize = || {
+            let mut cs = synthesize_fma_chain(geometry, max_trace_len, 40);
+            cs.add_blinding_rows(num_blinding_rows);
+            let (trace_len, _) = cs.pad_and_shrink();
+            assert_eq!(trace_len, max_trace_len);
+
+            cs.into_assembly::<Global>()
+        };
+
+        let worker = Worker::new_with_num_threads(4);
+
+        // more queries need more blinding rows than were reserved
+        let stronger_config = ProofConfig {
+            security_level: 100,
+            ..proof_config.clone()
+        };
+        assert_eq!(
+            synthesize().check_zero_knowledge_support(&stronger_config),
+            Err(ZeroKnowledgeError::NotEnoughBlindingRows {
+                reserved: num_blinding_rows,
+                required: stronger_config.num_blinding_rows(max_trace_len),
+            })
+        );
+
+        let (proof, vk) = synthesize()
+            .prove_one_shot::<2, GoldilocksExt2, GoldilocksPoisedonTranscript, H, NoPow>(
+                &worker,
+                proof_config.clone(),
+                (),
+            );
+        let (other_proof, other_vk) = synthesize()
+            .prove_one_shot::<2, GoldilocksExt2, GoldilocksPoisedonTranscript, H, NoPow>(
+                &worker,
+                proof_config,
+                (),
+            );
+
+        // same circuit and witness, but every oracle is blinded differently
+        assert_eq!(vk.to_bytes(), other_vk.to_bytes());
+        assert_ne!(proof.witness_oracle_cap, other_proof.witness_oracle_cap);
+        assert_ne!(proof.stage_2_oracle_cap, other_proof.stage_2_oracle_cap);
+        assert_ne!(proof.quotient_oracle_cap, other_proof.quotient_oracle_cap);
+
+        // every leaf is salted
+        let query = &proof.queries_per_fri_repetition[0];
+        assert_eq!(
+            query.witness_query.leaf_elements.len(),
+            geometry.num_columns_under_copy_permutation + geometry.num_witness_columns + SALT_SIZE
+        );
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure_fma_chain(builder);
+        let verifier = builder.build(());
+
+        assert!(verifier.verify::<H, GoldilocksPoisedonTranscript, NoPow>((), &vk, &proof));
+        assert!(verifier.verify::<H, GoldilocksPoisedonTranscript, NoPow>((), &vk, &other_proof));
+    }
+
+    #[test]
+    fn prove_aggregated_different_sizes() {
+        type H = GoldilocksPoseidonSponge<AbsorptionModeOverwrite>;
+        use crate::cs::implementations::aggregation::*;
+        use crate::cs::implementations::verifier::{OracleType, VerificationError};
+        use crate::field::ExtensionField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        // FMA gates take two per row
+        let synthesize = |num_gates: usize, max_trace_len: usize| {
+            let mut cs = synthesize_fma_chain(geometry, max_trace_len, num_gates);
+            let (trace_len, _) = cs.pad_and_shrink();
+            assert_eq!(trace_len, max_trace_len);
+
+            cs.into_assembly::<Global>()
+        };
+
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let worker = Worker::new_with_num_threads(4);
+        let mut ctx = ();
+
+        // the first and the last circuits share witness, second stage and quotient trees
+        let mut assemblies = vec![
+            synthesize(150, 128),
+            synthesize(40, 32),
+            synthesize(100, 128),
+        ];
+        let mut witness_sets = vec![];
+        let mut setups = vec![];
+        for cs in assemblies.iter_mut() {
+            let fri_lde_factor =
+                lde_factor_for_aggregation(proof_config.fri_lde_factor, 128, cs.max_trace_len);
+            let setup_base = cs.create_base_setup(&worker, &mut ctx);
+            let (setup, vk, setup_tree) = cs.materialize_setup_storage_and_vk::<H>(
+                fri_lde_factor,
+                proof_config.merkle_tree_cap_size,
+                &worker,
+                &mut ctx,
+            );
+            witness_sets.push(cs.take_witness(&worker));
+            setups.push((setup_base, setup, setup_tree, vk));
+        }
+
+        let circuits = assemblies
+            .iter()
+            .zip(witness_sets)
+            .zip(setups.iter())
+            .map(
+                |((assembly, witness_set), (setup_base, setup, setup_tree, vk))| {
+                    CircuitForAggregation {
+                        assembly,
+                        witness_set,
+                        setup_base,
+                        setup,
+                        setup_tree,
+                        vk,
+                    }
+                },
+            )
+            .collect();
+
+        let proof =
+            prove_aggregated::<_, _, _, 2, GoldilocksExt2, GoldilocksPoisedonTranscript, H, NoPow>(
+                &worker,
+                circuits,
+                proof_config,
+                (),
+            );
+
+        let verifier = AggregatedVerifier::new(
+            setups
+                .into_iter()
+                .map(|(_, _, _, vk)| {
+                    let builder_impl =
+                        CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+                    let builder = new_builder::<_, F>(builder_impl);
+
+                    (configure_fma_chain(builder).build(()), vk)
+                })
+                .collect(),
+        );
+
+        assert!(verifier.verify::<GoldilocksPoisedonTranscript, NoPow>((), &proof));
+        assert_eq!(proof.shared_oracles_caps.len(), 2);
+
+        let mut malformed = proof.clone();
+        malformed.circuits.pop();
+        assert_eq!(
+            verifier.verify_detailed::<GoldilocksPoisedonTranscript, NoPow>((), &malformed),
+            Err(VerificationError::InvalidNumberOfCircuits {
+                expected: 3,
+                got: 2
+            })
+        );
+
+        let mut malformed = proof.clone();
+        malformed.shared_oracles_caps.pop();
+        assert_eq!(
+            verifier.verify_detailed::<GoldilocksPoisedonTranscript, NoPow>((), &malformed),
+            Err(VerificationError::InvalidNumberOfSharedOracles {
+                expected: 2,
+                got: 1
+            })
+        );
+
+        let mut malformed = proof.clone();
+        malformed.circuits[1].values_at_z[0].add_assign(&ExtensionField::ONE);
+        assert_eq!(
+            verifier.verify_detailed::<GoldilocksPoisedonTranscript, NoPow>((), &malformed),
+            Err(VerificationError::Circuit {
+                circuit_idx: 1,
+                error: Box::new(VerificationError::QuotientIdentityFailed)
+            })
+        );
+
+        // leaf of the last circuit in the shared tree
+        let mut malformed = proof.clone();
+        malformed.queries_per_fri_repetition[0].shared_oracles_queries[0]
+            .witness_query
+            .leaf_elements
+            .last_mut()
+            .unwrap()
+            .add_assign(&F::ONE);
+        assert!(matches!(
+            verifier.verify_detailed::<GoldilocksPoisedonTranscript, NoPow>((), &malformed),
+            Err(VerificationError::InvalidMerklePath {
+                query_idx: 0,
+                oracle: OracleType::Witness,
+                ..
+            })
+        ));
+
+        for fri_lde_factor in [0, 3, 32] {
+            let mut malformed = proof.clone();
+            malformed.proof_config.fri_lde_factor = fri_lde_factor;
+            assert_eq!(
+                verifier.verify_detailed::<GoldilocksPoisedonTranscript, NoPow>((), &malformed),
+                Err(VerificationError::FriLdeFactorMismatch {
+                    vk: 16,
+                    proof: fri_lde_factor
+                })
+            );
+        }
+
+        let mut malformed = proof;
+        malformed.queries_per_fri_repetition[0].setup_queries[2].leaf_elements[0]
+            .add_assign(&F::ONE);
+        assert!(matches!(
+            verifier.verify_detailed::<GoldilocksPoisedonTranscript, NoPow>((), &malformed),
+            Err(VerificationError::Circuit { circuit_idx: 2, error }) if matches!(
+                *error,
+                VerificationError::InvalidMerklePath {
+                    query_idx: 0,
+                    oracle: OracleType::Setup,
+                    ..
+                }
+            )
+        ));
+    }
+
+    #[test]
+    #[ignore = "Computation of poly pairs for lookups unimplemented"]
+    fn prove_simple_with_lookups() {
+        pub const TEST_TABLE_NAME: &str = "Test table";
+
+        #[derive(Derivative)]
+        #[derivative(Clone, Copy, Debug)]
+        pub struct TestTableMarker;
+
+        type P = GoldilocksField;
+        // type P = MixedGL;
+        type RCfg = <DevCSConfig as CSConfig>::ResolverConfig;
+
+        pub fn create_test_table<F: SmallField>() -> LookupTable<F, 3> {
+            let mut all_keys = Vec::with_capacity(64);
+            for a in 0..8 {
+                for b in 0..8 {
+                    let key = smallvec::smallvec![
+                        F::from_u64_unchecked(a as u64),
+                        F::from_u64_unchecked(b as u64)
+                    ];
+                    all_keys.push(key);
+                }
+            }
+            LookupTable::new_from_keys_and_generation_function(
+                &all_keys,
+                TEST_TABLE_NAME.to_string(),
+                2,
+                |keys| {
+                    let a = keys[0].as_u64_reduced() as u8;
+                    let b = keys[1].as_u64_reduced() as u8;
+
+                    let xor_result = a ^ b;
+                    let or_result = a | b;
+                    let and_result = a & b;
+                    let value =
+                        (xor_result as u64) << 32 | (or_result as u64) << 16 | (and_result as u64);
+
+                    smallvec::smallvec![F::from_u64_unchecked(value)]
+                },
+            )
+        }
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        // let cs = CSReferenceImplementation::<
+        //     GoldilocksField,
+        //     P,
+        //     DevCSConfig,
+        //     _,
+        //     _,
+        // >::new_for_geometry(geometry, 512, 128);
+
+        fn configure<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            // allow lookup
+            let builder = builder.allow_lookup(LookupParameters::TableIdAsVariable {
+                width: 3,
+                share_table_id: false,
+            });
+
+            let builder = ConstantsAllocatorGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            // we pad with NOP gates, so we should formally allow it
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        let builder = new_builder(CsReferenceImplementationBuilder::<
+            GoldilocksField,
+            P,
+            DevCSConfig,
+        >::new(geometry, 128));
+        let builder = configure(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(512));
+
+        let table = create_test_table();
+        let table_id = cs.add_lookup_table::<TestTableMarker, 3>(table);
+
+        for _ in 0..100 {
+            let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
+            let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
+            let c = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(3));
+
+            let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                GoldilocksField::TWO,
+                (a, b),
+                GoldilocksField::MINUS_ONE,
+                c,
+            );
+
+            let [_e] = cs.perform_lookup(table_id, &[a, d]);
+        }
+
+        use super::gates::constant_allocator::*;
+
+        // make few constants
+        cs.allocate_constant(GoldilocksField::from_u64_unchecked(3));
+
+        cs.pad_and_shrink();
+
+        dbg!(&cs.max_trace_len);
+
+        let worker = Worker::new();
+        let cs = cs.into_assembly::<Global>();
+
+        let lde_factor_to_use = 16;
+        let proof_config = ProofConfig {
+            fri_lde_factor: lde_factor_to_use,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let (proof, vk) = cs.prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, ());
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let verifier = builder.build(());
+
+        let is_valid = verifier.verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >(
+            (),
+            &vk,
+            &proof,
+        );
+
+        assert!(is_valid);
+    }
+
+    #[test]
+    #[ignore = "Unimplemented part of prover is called"]
+    fn prove_simple_specialized() {
+        type P = GoldilocksField;
+        // type P = MixedGL;
+        type RCfg = <DevCSConfig as CSConfig>::ResolverConfig;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 0,
+            num_witness_columns: 0,
+            num_constant_columns: 0,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let max_variables = 1 << 16;
+        let max_trace_len = 64;
+
+        fn configure<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = BooleanConstraintGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseSpecializedColumns {
+                    num_repetitions: 2,
+                    share_constants: false,
+                },
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            builder
+        }
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, max_trace_len);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(max_variables));
+
+        for _i in 0..50 {
+            let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
+            let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(0));
+
+            if _i % 2 == 0 {
+                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, a);
+                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, b);
+            } else {
+                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, b);
+                let _ = BooleanConstraintGate::enforce_boolean(&mut cs, a);
+            }
+        }
+
+        cs.pad_and_shrink();
+
+        dbg!(&cs.max_trace_len);
+
+        let worker = Worker::new_with_num_threads(1);
+
+        let cs = cs.into_assembly::<Global>();
+
+        // assert!(cs.check_if_satisfied(&worker));
+
+        let lde_factor_to_use = 16;
+        let proof_config = ProofConfig {
+            fri_lde_factor: lde_factor_to_use,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let (proof, vk) = cs.prove_one_shot::<
+            2,
+            GoldilocksExt2,
+            GoldilocksPoisedonTranscript,
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            NoPow,
+        >(&worker, proof_config, ());
+
+        let builder_impl = CsVerifierBuilder::<F, GoldilocksExt2>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = configure(builder);
+        let verifier = builder.build(());
+
+        let is_valid = verifier.verify::<
+            GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+            GoldilocksPoisedonTranscript,
+            NoPow
+        >(
+            (),
+            &vk,
+            &proof,
+        );
 
         assert!(is_valid);
     }
@@ -1602,6 +2797,8 @@ mod test {
         let table2 = create_test_table_2();
         let table_id2 = cs.add_lookup_table::<TestTableMarker2, 3>(table2);
 
+        let mut first_d = None;
+
         for i in 0..101 {
             let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
             let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
@@ -1614,6 +2811,9 @@ mod test {
                 GoldilocksField::MINUS_ONE,
                 c,
             );
+            if i == 0 {
+                first_d = Some(d);
+            }
 
             // let [e] = cs.perform_lookup(table_id, &[a, d]);
 
@@ -1654,6 +2854,53 @@ mod test {
 
         assert!(cs.check_if_satisfied(&worker));
 
+        // break the value of FMA output that is also used in lookups
+        use crate::cs::implementations::satisfiability_test::UnsatisfiedConstraintKind;
+
+        let d = first_d.unwrap();
+        let original_value = cs.witness.as_ref().unwrap().all_values[d.0 as usize];
+        cs.witness.as_mut().unwrap().all_values[d.0 as usize].add_assign(&F::ONE);
+
+        let unsatisfied = cs.check_if_satisfied_detailed(&worker);
+        assert!(unsatisfied.iter().any(|el| {
+            el.kind == UnsatisfiedConstraintKind::GeneralPurposeColumns
+                && el
+                    .places
+                    .iter()
+                    .any(|(place, _)| *place == Place::from_variable(d))
+        }));
+        assert!(unsatisfied
+            .iter()
+            .any(|el| el.kind == UnsatisfiedConstraintKind::Lookup));
+        assert!(unsatisfied
+            .iter()
+            .all(|el| el.kind != UnsatisfiedConstraintKind::CopyPermutation));
+
+        cs.witness.as_mut().unwrap().all_values[d.0 as usize] = original_value;
+        assert!(cs.check_if_satisfied_detailed(&worker).is_empty());
+
+        // break a single copy of the FMA output in the trace, so it's cycle is not
+        // over equal values anymore
+        let (column, row) = cs
+            .copy_permutation_data
+            .iter()
+            .enumerate()
+            .find_map(|(column, placement)| {
+                placement
+                    .iter()
+                    .position(|el| *el == d)
+                    .map(|row| (column, row))
+            })
+            .unwrap();
+        let mut variables = cs.materialize_variables_polynomials(&worker);
+        variables[column].storage[row].add_assign(&F::ONE);
+        let variables: Vec<_> = variables.into_iter().map(std::sync::Arc::new).collect();
+
+        let broken = cs.find_broken_copy_constraints(&worker, &variables);
+        assert!(broken.contains(&(column, row, F::ONE)));
+        // previous cell in the cycle now points to the broken one
+        assert_eq!(broken.len(), 2);
+
         let lde_factor_to_use = 16;
         let proof_config = ProofConfig {
             fri_lde_factor: lde_factor_to_use,
@@ -1666,6 +2913,7 @@ mod test {
         // panic!();
 
         let (proof, vk) = cs.prove_one_shot::<
+            2,
             GoldilocksExt2,
             GoldilocksPoisedonTranscript,
             GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
@@ -1690,6 +2938,172 @@ mod test {
         assert!(is_valid);
     }
 
+    #[test]
+    fn call_sites_are_tracked() {
+        use super::gates::constant_allocator::*;
+        use crate::gadgets::traits::allocatable::CSAllocatable;
+        use crate::gadgets::u256::UInt256;
+        use ethereum_types::U256;
+
+        type P = GoldilocksField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 8,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfigWithCallSites>::new(geometry, 512);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        // range checks of uint limbs without lookups
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+        let mut cs = builder.build(CircuitResolverOpts::new(4096));
+
+        let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
+        let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
+        cs.push_namespace("outer");
+        cs.push_namespace("fma");
+        let fma_line = line!() + 1;
+        let d = FmaGateInBaseFieldWithoutConstant::compute_fma(
+            &mut cs,
+            GoldilocksField::TWO,
+            (a, b),
+            GoldilocksField::MINUS_ONE,
+            a,
+        );
+        cs.pop_namespace();
+        cs.pop_namespace();
+
+        // wide integers are attributed to the caller, and not to the limbs they are made of
+        cs.push_namespace("u256");
+        let u256_line = line!() + 1;
+        let u256 = UInt256::allocate(&mut cs, U256::from(0x1234_5678_9abc_def0u64));
+        cs.pop_namespace();
+
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(8);
+        let mut cs = cs.into_assembly::<Global>();
+
+        let site = cs
+            .call_sites
+            .place_call_site(Place::from_variable(d))
+            .cloned()
+            .unwrap();
+        assert_eq!(site.location.file(), file!());
+        assert_eq!(site.location.line(), fma_line);
+        assert_eq!(site.scope.as_deref(), Some("outer/fma"));
+        let site = cs
+            .call_sites
+            .place_call_site(Place::from_variable(a))
+            .cloned()
+            .unwrap();
+        assert!(site.scope.is_none());
+        for limb in u256.inner.iter() {
+            let site = cs
+                .call_sites
+                .place_call_site(Place::from_variable(limb.get_variable()))
+                .cloned()
+                .unwrap();
+            assert_eq!(site.location.file(), file!());
+            assert_eq!(site.location.line(), u256_line);
+            assert_eq!(site.scope.as_deref(), Some("u256"));
+        }
+
+        let report = cs.namespaces.report();
+        assert_eq!(report.own_stats.num_variables, 2);
+        assert_eq!(report.children.len(), 2);
+        let fma = &report.children[0].children[0];
+        assert_eq!(fma.name, "fma");
+        assert_eq!(fma.own_stats.num_variables, 1);
+        assert_eq!(fma.own_stats.num_general_purpose_rows, 1);
+        assert_eq!(fma.own_stats.num_copy_permutation_cells, 4);
+        assert_eq!(report.children[0].total_stats, fma.total_stats);
+
+        cs.witness.as_mut().unwrap().all_values[d.0 as usize].add_assign(&F::ONE);
+        let unsatisfied = cs.check_if_satisfied_detailed(&worker);
+        assert!(!unsatisfied.is_empty());
+        for el in unsatisfied.iter() {
+            let site = el.call_site.as_ref().unwrap();
+            assert_eq!(site.location.file(), file!());
+            assert_eq!(site.location.line(), fma_line);
+        }
+
+        cs.print_gate_stats();
+    }
+
+    #[test]
+    fn namespaces_are_accounted_without_call_sites() {
+        type P = GoldilocksField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, 128);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+        let mut cs = builder.build(CircuitResolverOpts::new(512));
+
+        let a = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(1));
+        let b = cs.alloc_single_variable_from_witness(GoldilocksField::from_u64_unchecked(2));
+        cs.push_namespace("fma");
+        let _ = FmaGateInBaseFieldWithoutConstant::compute_fma(
+            &mut cs,
+            GoldilocksField::TWO,
+            (a, b),
+            GoldilocksField::MINUS_ONE,
+            a,
+        );
+        cs.pop_namespace();
+
+        cs.pad_and_shrink();
+        let cs = cs.into_assembly::<Global>();
+        assert!(cs
+            .call_sites
+            .place_call_site(Place::from_variable(a))
+            .is_none());
+
+        let report = cs.namespaces.report();
+        assert_eq!(report.own_stats.num_variables, 2);
+        let fma = &report.children[0];
+        assert_eq!(fma.name, "fma");
+        assert_eq!(fma.own_stats.num_variables, 1);
+        assert_eq!(fma.own_stats.num_general_purpose_rows, 1);
+        assert_eq!(fma.own_stats.num_copy_permutation_cells, 4);
+
+        let decoded = NamespaceReport::from_json(&cs.namespaces.to_json()).unwrap();
+        assert_eq!(decoded.children[0].total_stats, fma.total_stats);
+    }
+
     // #[test]
     // fn prove_benchmark_simple() {
     //     use crate::cs::traits::destination_view::GateEvaluationReducingDestinationChunk;
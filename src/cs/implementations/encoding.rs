@@ -0,0 +1,1036 @@
+use std::error::Error;
+use std::io::{Read, Write};
+
+use blake2::Blake2s256;
+use blake2::Digest;
+
+use super::fast_serialization::{read_vec_from_buffer, write_vec_into_buffer, MemcopySerializable};
+use super::proof::{
+    DeduplicatedQueryPaths, OracleQuery, Proof, ProofWithDeduplicatedPaths, SingleRoundQueries,
+};
+use super::prover::ProofConfig;
+use super::setup::{GateDescription, TreeNode};
+use super::verifier::{VerificationKey, VerificationKeyCircuitGeometry};
+use super::*;
+
+use crate::algebraic_props::round_function::{AbsorptionModeAdd, AbsorptionModeOverwrite};
+use crate::algebraic_props::sponge::{GoldilocksPoseidon2Sponge, GoldilocksPoseidonSponge};
+use crate::cs::oracle::TreeHasher;
+use crate::field::goldilocks::GoldilocksField;
+use crate::field::ExtensionField;
+use crate::field::Field;
+use crate::field::FieldExtension;
+
+// Canonical binary encoding of proofs and verification keys. Everything is little-endian,
+// lengths are encoded as u64, and field elements are always written in the canonical form.
+// Every encoding starts with a header that identifies the field, extension and tree hasher,
+// so decoding into the wrong types fails instead of producing garbage
+
+pub const PROOF_ENCODING_MAGIC: [u8; 4] = *b"BJPF";
+pub const VK_ENCODING_MAGIC: [u8; 4] = *b"BJVK";
+pub const ENCODING_FORMAT_VERSION: u16 = 2;
+
+// magic, version, field modulus, extension degree and non-residue, hasher id, config digest
+const HEADER_SIZE: usize = 4 + 2 + 8 + 1 + 8 + 4 + 32;
+
+// we never trust the encoded lengths when preallocating
+const MAX_PREALLOCATED_ELEMENTS: usize = 1 << 16;
+
+const MAX_SELECTORS_TREE_DEPTH: usize = 64;
+
+/// Tree hasher that has a stable identifier and a canonical byte representation of its output,
+/// so commitments can be put into the binary encoding
+pub trait EncodableTreeHasher<F: SmallField>: TreeHasher<F> {
+    const HASHER_ID: u32;
+
+    fn write_output<W: Write>(output: &Self::Output, dst: W) -> Result<(), Box<dyn Error>>;
+    fn read_output<R: Read>(src: R) -> Result<Self::Output, Box<dyn Error>>;
+}
+
+impl<F: SmallField> EncodableTreeHasher<F> for blake2::Blake2s256 {
+    const HASHER_ID: u32 = 0x0000_0001;
+
+    fn write_output<W: Write>(output: &Self::Output, dst: W) -> Result<(), Box<dyn Error>> {
+        write_bytes_output(output, dst)
+    }
+
+    fn read_output<R: Read>(src: R) -> Result<Self::Output, Box<dyn Error>> {
+        read_bytes_output(src)
+    }
+}
+
+impl<F: SmallField> EncodableTreeHasher<F> for sha3::Keccak256 {
+    const HASHER_ID: u32 = 0x0000_0002;
+
+    fn write_output<W: Write>(output: &Self::Output, dst: W) -> Result<(), Box<dyn Error>> {
+        write_bytes_output(output, dst)
+    }
+
+    fn read_output<R: Read>(src: R) -> Result<Self::Output, Box<dyn Error>> {
+        read_bytes_output(src)
+    }
+}
+
+macro_rules! impl_encodable_for_algebraic_sponge {
+    ($sponge: ty, $id: expr) => {
+        impl EncodableTreeHasher<GoldilocksField> for $sponge {
+            const HASHER_ID: u32 = $id;
+
+            fn write_output<W: Write>(
+                output: &Self::Output,
+                mut dst: W,
+            ) -> Result<(), Box<dyn Error>> {
+                for el in output.iter() {
+                    write_field_element(el, &mut dst)?;
+                }
+
+                Ok(())
+            }
+
+            fn read_output<R: Read>(mut src: R) -> Result<Self::Output, Box<dyn Error>> {
+                let mut result = [GoldilocksField::ZERO; 4];
+                for dst in result.iter_mut() {
+                    *dst = read_field_element(&mut src)?;
+                }
+
+                Ok(result)
+            }
+        }
+    };
+}
+
+impl_encodable_for_algebraic_sponge!(
+    GoldilocksPoseidonSponge<AbsorptionModeOverwrite>,
+    0x0000_0100
+);
+impl_encodable_for_algebraic_sponge!(GoldilocksPoseidonSponge<AbsorptionModeAdd>, 0x0000_0101);
+impl_encodable_for_algebraic_sponge!(
+    GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>,
+    0x0000_0200
+);
+impl_encodable_for_algebraic_sponge!(GoldilocksPoseidon2Sponge<AbsorptionModeAdd>, 0x0000_0201);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncodingHeader {
+    pub magic: [u8; 4],
+    pub version: u16,
+    pub field_modulus: u64,
+    // zero degree means that the encoded object doesn't depend on the extension
+    pub extension_degree: u8,
+    pub extension_non_residue: u64,
+    pub hasher_id: u32,
+    pub config_digest: [u8; 32],
+}
+
+impl EncodingHeader {
+    fn new_for_proof<
+        F: SmallField,
+        H: EncodableTreeHasher<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    >(
+        config_digest: [u8; 32],
+    ) -> Self {
+        Self {
+            magic: PROOF_ENCODING_MAGIC,
+            version: ENCODING_FORMAT_VERSION,
+            field_modulus: F::CHAR,
+            extension_degree: N as u8,
+            extension_non_residue: EXT::non_residue().as_u64_reduced(),
+            hasher_id: H::HASHER_ID,
+            config_digest,
+        }
+    }
+
+    fn new_for_vk<F: SmallField, H: EncodableTreeHasher<F>>(config_digest: [u8; 32]) -> Self {
+        Self {
+            magic: VK_ENCODING_MAGIC,
+            version: ENCODING_FORMAT_VERSION,
+            field_modulus: F::CHAR,
+            extension_degree: 0,
+            extension_non_residue: 0,
+            hasher_id: H::HASHER_ID,
+            config_digest,
+        }
+    }
+
+    /// Reads the header from the beginning of the encoding without checking it
+    pub fn peek(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        MemcopySerializable::read_from_buffer(bytes)
+    }
+
+    // everything except the digest, that can only be checked after the body is decoded
+    fn check_against(&self, expected: &Self) -> Result<(), Box<dyn Error>> {
+        if self.magic != expected.magic {
+            return Err(Box::<dyn Error>::from(format!(
+                "invalid magic {:?}, expected {:?}",
+                self.magic, expected.magic
+            )));
+        }
+        if self.version != expected.version {
+            return Err(Box::<dyn Error>::from(format!(
+                "unsupported encoding version {}, expected {}",
+                self.version, expected.version
+            )));
+        }
+        if self.field_modulus != expected.field_modulus {
+            return Err(Box::<dyn Error>::from(format!(
+                "encoded for field with modulus 0x{:016x}, expected 0x{:016x}",
+                self.field_modulus, expected.field_modulus
+            )));
+        }
+        if self.extension_degree != expected.extension_degree
+            || self.extension_non_residue != expected.extension_non_residue
+        {
+            return Err(Box::<dyn Error>::from(format!(
+                "encoded for extension of degree {} with non-residue {}, expected degree {} with non-residue {}",
+                self.extension_degree,
+                self.extension_non_residue,
+                expected.extension_degree,
+                expected.extension_non_residue
+            )));
+        }
+        if self.hasher_id != expected.hasher_id {
+            return Err(Box::<dyn Error>::from(format!(
+                "encoded for tree hasher 0x{:08x}, expected 0x{:08x}",
+                self.hasher_id, expected.hasher_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn check_digest(&self, digest: &[u8; 32]) -> Result<(), Box<dyn Error>> {
+        if &self.config_digest != digest {
+            return Err(Box::<dyn Error>::from(
+                "configuration digest in the header doesn't match the encoded configuration",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl MemcopySerializable for EncodingHeader {
+    fn write_into_buffer<W: Write>(&self, mut dst: W) -> Result<(), Box<dyn Error>> {
+        dst.write_all(&self.magic).map_err(Box::new)?;
+        dst.write_all(&self.version.to_le_bytes())
+            .map_err(Box::new)?;
+        MemcopySerializable::write_into_buffer(&self.field_modulus, &mut dst)?;
+        dst.write_all(&[self.extension_degree]).map_err(Box::new)?;
+        MemcopySerializable::write_into_buffer(&self.extension_non_residue, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.hasher_id, &mut dst)?;
+        dst.write_all(&self.config_digest).map_err(Box::new)?;
+
+        Ok(())
+    }
+
+    fn read_from_buffer<R: Read>(mut src: R) -> Result<Self, Box<dyn Error>> {
+        let mut magic = [0u8; 4];
+        src.read_exact(&mut magic).map_err(Box::new)?;
+        let mut version = [0u8; 2];
+        src.read_exact(&mut version).map_err(Box::new)?;
+        let field_modulus: u64 = MemcopySerializable::read_from_buffer(&mut src)?;
+        let extension_degree = read_u8(&mut src)?;
+        let extension_non_residue: u64 = MemcopySerializable::read_from_buffer(&mut src)?;
+        let hasher_id: u32 = MemcopySerializable::read_from_buffer(&mut src)?;
+        let mut config_digest = [0u8; 32];
+        src.read_exact(&mut config_digest).map_err(Box::new)?;
+
+        Ok(Self {
+            magic,
+            version: u16::from_le_bytes(version),
+            field_modulus,
+            extension_degree,
+            extension_non_residue,
+            hasher_id,
+            config_digest,
+        })
+    }
+}
+
+fn write_bytes_output<W: Write>(output: &[u8; 32], mut dst: W) -> Result<(), Box<dyn Error>> {
+    dst.write_all(output).map_err(Box::new)?;
+
+    Ok(())
+}
+
+fn read_bytes_output<R: Read>(mut src: R) -> Result<[u8; 32], Box<dyn Error>> {
+    let mut output = [0u8; 32];
+    src.read_exact(&mut output).map_err(Box::new)?;
+
+    Ok(output)
+}
+
+fn read_u8<R: Read>(mut src: R) -> Result<u8, Box<dyn Error>> {
+    let mut buffer = [0u8; 1];
+    src.read_exact(&mut buffer).map_err(Box::new)?;
+
+    Ok(buffer[0])
+}
+
+fn write_bool<W: Write>(value: bool, mut dst: W) -> Result<(), Box<dyn Error>> {
+    dst.write_all(&[value as u8]).map_err(Box::new)?;
+
+    Ok(())
+}
+
+fn read_bool<R: Read>(src: R) -> Result<bool, Box<dyn Error>> {
+    match read_u8(src)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        other => Err(Box::<dyn Error>::from(format!(
+            "invalid boolean encoding 0x{:02x}",
+            other
+        ))),
+    }
+}
+
+fn read_length<R: Read>(src: R) -> Result<usize, Box<dyn Error>> {
+    MemcopySerializable::read_from_buffer(src)
+}
+
+fn write_field_element<F: SmallField, W: Write>(el: &F, dst: W) -> Result<(), Box<dyn Error>> {
+    MemcopySerializable::write_into_buffer(&el.as_u64_reduced(), dst)
+}
+
+fn read_field_element<F: SmallField, R: Read>(src: R) -> Result<F, Box<dyn Error>> {
+    let value: u64 = MemcopySerializable::read_from_buffer(src)?;
+    F::from_u64(value).ok_or_else(|| {
+        Box::<dyn Error>::from(format!(
+            "0x{:016x} is not a canonical field element for modulus 0x{:016x}",
+            value,
+            F::CHAR
+        ))
+    })
+}
+
+fn write_field_elements<F: SmallField, W: Write>(
+    src: &[F],
+    mut dst: W,
+) -> Result<(), Box<dyn Error>> {
+    MemcopySerializable::write_into_buffer(&src.len(), &mut dst)?;
+    for el in src.iter() {
+        write_field_element(el, &mut dst)?;
+    }
+
+    Ok(())
+}
+
+fn read_field_elements<F: SmallField, R: Read>(mut src: R) -> Result<Vec<F>, Box<dyn Error>> {
+    let length = read_length(&mut src)?;
+    let mut result = Vec::with_capacity(length.min(MAX_PREALLOCATED_ELEMENTS));
+    for _ in 0..length {
+        result.push(read_field_element(&mut src)?);
+    }
+
+    Ok(result)
+}
+
+fn write_ext_elements<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    W: Write,
+>(
+    src: &[ExtensionField<F, N, EXT>],
+    mut dst: W,
+) -> Result<(), Box<dyn Error>> {
+    MemcopySerializable::write_into_buffer(&src.len(), &mut dst)?;
+    for el in src.iter() {
+        for coeff in el.as_coeffs_in_base().iter() {
+            write_field_element(coeff, &mut dst)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_ext_elements<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    R: Read,
+>(
+    mut src: R,
+) -> Result<Vec<ExtensionField<F, N, EXT>>, Box<dyn Error>> {
+    let length = read_length(&mut src)?;
+    let mut result = Vec::with_capacity(length.min(MAX_PREALLOCATED_ELEMENTS));
+    for _ in 0..length {
+        let mut coeffs = [F::ZERO; N];
+        for coeff in coeffs.iter_mut() {
+            *coeff = read_field_element(&mut src)?;
+        }
+        result.push(ExtensionField::<F, N, EXT>::from_coeff_in_base(coeffs));
+    }
+
+    Ok(result)
+}
+
+fn write_hasher_outputs<F: SmallField, H: EncodableTreeHasher<F>, W: Write>(
+    src: &[H::Output],
+    mut dst: W,
+) -> Result<(), Box<dyn Error>> {
+    MemcopySerializable::write_into_buffer(&src.len(), &mut dst)?;
+    for el in src.iter() {
+        H::write_output(el, &mut dst)?;
+    }
+
+    Ok(())
+}
+
+fn read_hasher_outputs<F: SmallField, H: EncodableTreeHasher<F>, R: Read>(
+    mut src: R,
+) -> Result<Vec<H::Output>, Box<dyn Error>> {
+    let length = read_length(&mut src)?;
+    let mut result = Vec::with_capacity(length.min(MAX_PREALLOCATED_ELEMENTS));
+    for _ in 0..length {
+        result.push(H::read_output(&mut src)?);
+    }
+
+    Ok(result)
+}
+
+fn write_proof_config<W: Write>(config: &ProofConfig, mut dst: W) -> Result<(), Box<dyn Error>> {
+    MemcopySerializable::write_into_buffer(&config.fri_lde_factor, &mut dst)?;
+    MemcopySerializable::write_into_buffer(&config.merkle_tree_cap_size, &mut dst)?;
+    match config.fri_folding_schedule.as_ref() {
+        None => write_bool(false, &mut dst)?,
+        Some(schedule) => {
+            write_bool(true, &mut dst)?;
+            write_vec_into_buffer(schedule, &mut dst)?;
+        }
+    }
+    MemcopySerializable::write_into_buffer(&config.security_level, &mut dst)?;
+    MemcopySerializable::write_into_buffer(&config.pow_bits, &mut dst)?;
+    write_bool(config.zero_knowledge, &mut dst)?;
+
+    Ok(())
+}
+
+fn read_proof_config<R: Read>(mut src: R) -> Result<ProofConfig, Box<dyn Error>> {
+    let fri_lde_factor = MemcopySerializable::read_from_buffer(&mut src)?;
+    let merkle_tree_cap_size = MemcopySerializable::read_from_buffer(&mut src)?;
+    let fri_folding_schedule = if read_bool(&mut src)? {
+        Some(read_vec_from_buffer::<usize, std::alloc::Global, _>(
+            &mut src,
+        )?)
+    } else {
+        None
+    };
+    let security_level = MemcopySerializable::read_from_buffer(&mut src)?;
+    let pow_bits = MemcopySerializable::read_from_buffer(&mut src)?;
+    let zero_knowledge = read_bool(&mut src)?;
+
+    Ok(ProofConfig {
+        fri_lde_factor,
+        merkle_tree_cap_size,
+        fri_folding_schedule,
+        security_level,
+        pow_bits,
+        zero_knowledge,
+    })
+}
+
+/// Digest of the canonical encoding of the proof configuration, as written into the header
+pub fn proof_config_digest(config: &ProofConfig) -> [u8; 32] {
+    let mut buffer = vec![];
+    write_proof_config(config, &mut buffer).expect("must serialize");
+
+    digest_bytes(&buffer)
+}
+
+fn digest_bytes(bytes: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    output.copy_from_slice(Blake2s256::digest(bytes).as_slice());
+
+    output
+}
+
+fn write_lookup_parameters<W: Write>(
+    params: &LookupParameters,
+    mut dst: W,
+) -> Result<(), Box<dyn Error>> {
+    match *params {
+        LookupParameters::NoLookup => {
+            dst.write_all(&[0u8]).map_err(Box::new)?;
+        }
+        LookupParameters::TableIdAsVariable {
+            width,
+            share_table_id,
+        } => {
+            dst.write_all(&[1u8]).map_err(Box::new)?;
+            MemcopySerializable::write_into_buffer(&width, &mut dst)?;
+            write_bool(share_table_id, &mut dst)?;
+        }
+        LookupParameters::TableIdAsConstant {
+            width,
+            share_table_id,
+        } => {
+            dst.write_all(&[2u8]).map_err(Box::new)?;
+            MemcopySerializable::write_into_buffer(&width, &mut dst)?;
+            write_bool(share_table_id, &mut dst)?;
+        }
+        LookupParameters::UseSpecializedColumnsWithTableIdAsVariable {
+            width,
+            num_repetitions,
+            share_table_id,
+        } => {
+            dst.write_all(&[3u8]).map_err(Box::new)?;
+            MemcopySerializable::write_into_buffer(&width, &mut dst)?;
+            MemcopySerializable::write_into_buffer(&num_repetitions, &mut dst)?;
+            write_bool(share_table_id, &mut dst)?;
+        }
+        LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+            width,
+            num_repetitions,
+            share_table_id,
+        } => {
+            dst.write_all(&[4u8]).map_err(Box::new)?;
+            MemcopySerializable::write_into_buffer(&width, &mut dst)?;
+            MemcopySerializable::write_into_buffer(&num_repetitions, &mut dst)?;
+            write_bool(share_table_id, &mut dst)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_lookup_parameters<R: Read>(mut src: R) -> Result<LookupParameters, Box<dyn Error>> {
+    let params = match read_u8(&mut src)? {
+        0 => LookupParameters::NoLookup,
+        1 => LookupParameters::TableIdAsVariable {
+            width: MemcopySerializable::read_from_buffer(&mut src)?,
+            share_table_id: read_bool(&mut src)?,
+        },
+        2 => LookupParameters::TableIdAsConstant {
+            width: MemcopySerializable::read_from_buffer(&mut src)?,
+            share_table_id: read_bool(&mut src)?,
+        },
+        3 => LookupParameters::UseSpecializedColumnsWithTableIdAsVariable {
+            width: MemcopySerializable::read_from_buffer(&mut src)?,
+            num_repetitions: MemcopySerializable::read_from_buffer(&mut src)?,
+            share_table_id: read_bool(&mut src)?,
+        },
+        4 => LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+            width: MemcopySerializable::read_from_buffer(&mut src)?,
+            num_repetitions: MemcopySerializable::read_from_buffer(&mut src)?,
+            share_table_id: read_bool(&mut src)?,
+        },
+        other => {
+            return Err(Box::<dyn Error>::from(format!(
+                "unknown lookup parameters tag {}",
+                other
+            )))
+        }
+    };
+
+    Ok(params)
+}
+
+fn write_selectors_tree<W: Write>(node: &TreeNode, dst: &mut W) -> Result<(), Box<dyn Error>> {
+    match node {
+        TreeNode::Empty => {
+            dst.write_all(&[0u8]).map_err(Box::new)?;
+        }
+        TreeNode::GateOnly(description) => {
+            dst.write_all(&[1u8]).map_err(Box::new)?;
+            MemcopySerializable::write_into_buffer(&description.gate_idx, &mut *dst)?;
+            MemcopySerializable::write_into_buffer(&description.num_constants, &mut *dst)?;
+            MemcopySerializable::write_into_buffer(&description.degree, &mut *dst)?;
+            write_bool(description.needs_selector, &mut *dst)?;
+            write_bool(description.is_lookup, &mut *dst)?;
+        }
+        TreeNode::Fork { left, right } => {
+            dst.write_all(&[2u8]).map_err(Box::new)?;
+            write_selectors_tree(left, dst)?;
+            write_selectors_tree(right, dst)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_selectors_tree<R: Read>(src: &mut R, depth: usize) -> Result<TreeNode, Box<dyn Error>> {
+    if depth > MAX_SELECTORS_TREE_DEPTH {
+        return Err(Box::<dyn Error>::from(format!(
+            "selectors tree is deeper than {} levels",
+            MAX_SELECTORS_TREE_DEPTH
+        )));
+    }
+
+    let node = match read_u8(&mut *src)? {
+        0 => TreeNode::Empty,
+        1 => TreeNode::GateOnly(GateDescription {
+            gate_idx: MemcopySerializable::read_from_buffer(&mut *src)?,
+            num_constants: MemcopySerializable::read_from_buffer(&mut *src)?,
+            degree: MemcopySerializable::read_from_buffer(&mut *src)?,
+            needs_selector: read_bool(&mut *src)?,
+            is_lookup: read_bool(&mut *src)?,
+        }),
+        2 => {
+            let left = read_selectors_tree(src, depth + 1)?;
+            let right = read_selectors_tree(src, depth + 1)?;
+
+            TreeNode::Fork {
+                left: Box::new(left),
+                right: Box::new(right),
+            }
+        }
+        other => {
+            return Err(Box::<dyn Error>::from(format!(
+                "unknown selectors tree node tag {}",
+                other
+            )))
+        }
+    };
+
+    Ok(node)
+}
+
+impl MemcopySerializable for VerificationKeyCircuitGeometry {
+    fn write_into_buffer<W: Write>(&self, mut dst: W) -> Result<(), Box<dyn Error>> {
+        let CSGeometry {
+            num_columns_under_copy_permutation,
+            num_witness_columns,
+            num_constant_columns,
+            max_allowed_constraint_degree,
+        } = self.parameters;
+        MemcopySerializable::write_into_buffer(&num_columns_under_copy_permutation, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&num_witness_columns, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&num_constant_columns, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&max_allowed_constraint_degree, &mut dst)?;
+
+        write_lookup_parameters(&self.lookup_parameters, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.domain_size, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.total_tables_len, &mut dst)?;
+        write_vec_into_buffer(&self.public_inputs_locations, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.extra_constant_polys_for_selectors, &mut dst)?;
+        write_vec_into_buffer(&self.table_ids_column_idxes, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.quotient_degree, &mut dst)?;
+        write_selectors_tree(&self.selectors_placement, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.fri_lde_factor, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.cap_size, &mut dst)?;
+
+        Ok(())
+    }
+
+    fn read_from_buffer<R: Read>(mut src: R) -> Result<Self, Box<dyn Error>> {
+        let parameters = CSGeometry {
+            num_columns_under_copy_permutation: MemcopySerializable::read_from_buffer(&mut src)?,
+            num_witness_columns: MemcopySerializable::read_from_buffer(&mut src)?,
+            num_constant_columns: MemcopySerializable::read_from_buffer(&mut src)?,
+            max_allowed_constraint_degree: MemcopySerializable::read_from_buffer(&mut src)?,
+        };
+
+        Ok(Self {
+            parameters,
+            lookup_parameters: read_lookup_parameters(&mut src)?,
+            domain_size: MemcopySerializable::read_from_buffer(&mut src)?,
+            total_tables_len: MemcopySerializable::read_from_buffer(&mut src)?,
+            public_inputs_locations: read_vec_from_buffer(&mut src)?,
+            extra_constant_polys_for_selectors: MemcopySerializable::read_from_buffer(&mut src)?,
+            table_ids_column_idxes: read_vec_from_buffer(&mut src)?,
+            quotient_degree: MemcopySerializable::read_from_buffer(&mut src)?,
+            selectors_placement: read_selectors_tree(&mut src, 0)?,
+            fri_lde_factor: MemcopySerializable::read_from_buffer(&mut src)?,
+            cap_size: MemcopySerializable::read_from_buffer(&mut src)?,
+        })
+    }
+}
+
+fn write_oracle_query<F: SmallField, H: EncodableTreeHasher<F>, W: Write>(
+    query: &OracleQuery<F, H>,
+    mut dst: W,
+) -> Result<(), Box<dyn Error>> {
+    write_field_elements(&query.leaf_elements, &mut dst)?;
+    write_hasher_outputs::<F, H, _>(&query.proof, &mut dst)?;
+
+    Ok(())
+}
+
+fn read_oracle_query<F: SmallField, H: EncodableTreeHasher<F>, R: Read>(
+    mut src: R,
+) -> Result<OracleQuery<F, H>, Box<dyn Error>> {
+    Ok(OracleQuery {
+        leaf_elements: read_field_elements(&mut src)?,
+        proof: read_hasher_outputs::<F, H, _>(&mut src)?,
+    })
+}
+
+fn write_single_round_queries<F: SmallField, H: EncodableTreeHasher<F>, W: Write>(
+    queries: &SingleRoundQueries<F, H>,
+    mut dst: W,
+) -> Result<(), Box<dyn Error>> {
+    write_oracle_query(&queries.witness_query, &mut dst)?;
+    write_oracle_query(&queries.stage_2_query, &mut dst)?;
+    write_oracle_query(&queries.quotient_query, &mut dst)?;
+    write_oracle_query(&queries.setup_query, &mut dst)?;
+    MemcopySerializable::write_into_buffer(&queries.fri_queries.len(), &mut dst)?;
+    for query in queries.fri_queries.iter() {
+        write_oracle_query(query, &mut dst)?;
+    }
+
+    Ok(())
+}
+
+fn read_single_round_queries<F: SmallField, H: EncodableTreeHasher<F>, R: Read>(
+    mut src: R,
+) -> Result<SingleRoundQueries<F, H>, Box<dyn Error>> {
+    let witness_query = read_oracle_query(&mut src)?;
+    let stage_2_query = read_oracle_query(&mut src)?;
+    let quotient_query = read_oracle_query(&mut src)?;
+    let setup_query = read_oracle_query(&mut src)?;
+    let num_fri_queries = read_length(&mut src)?;
+    let mut fri_queries = Vec::with_capacity(num_fri_queries.min(MAX_PREALLOCATED_ELEMENTS));
+    for _ in 0..num_fri_queries {
+        fri_queries.push(read_oracle_query(&mut src)?);
+    }
+
+    Ok(SingleRoundQueries {
+        witness_query,
+        stage_2_query,
+        quotient_query,
+        setup_query,
+        fri_queries,
+    })
+}
+
+impl<
+        F: SmallField,
+        H: EncodableTreeHasher<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    > MemcopySerializable for Proof<F, H, EXT, N>
+{
+    fn write_into_buffer<W: Write>(&self, mut dst: W) -> Result<(), Box<dyn Error>> {
+        let header =
+            EncodingHeader::new_for_proof::<F, H, N, EXT>(proof_config_digest(&self.proof_config));
+        MemcopySerializable::write_into_buffer(&header, &mut dst)?;
+
+        write_proof_config(&self.proof_config, &mut dst)?;
+        write_field_elements(&self.public_inputs, &mut dst)?;
+
+        write_hasher_outputs::<F, H, _>(&self.witness_oracle_cap, &mut dst)?;
+        write_hasher_outputs::<F, H, _>(&self.stage_2_oracle_cap, &mut dst)?;
+        write_hasher_outputs::<F, H, _>(&self.quotient_oracle_cap, &mut dst)?;
+        for monomials in self.final_fri_monomials.iter() {
+            write_field_elements(monomials, &mut dst)?;
+        }
+
+        write_ext_elements(&self.values_at_z, &mut dst)?;
+        write_ext_elements(&self.values_at_z_omega, &mut dst)?;
+        write_ext_elements(&self.values_at_0, &mut dst)?;
+
+        write_hasher_outputs::<F, H, _>(&self.fri_base_oracle_cap, &mut dst)?;
+        MemcopySerializable::write_into_buffer(
+            &self.fri_intermediate_oracles_caps.len(),
+            &mut dst,
+        )?;
+        for cap in self.fri_intermediate_oracles_caps.iter() {
+            write_hasher_outputs::<F, H, _>(cap, &mut dst)?;
+        }
+
+        MemcopySerializable::write_into_buffer(&self.queries_per_fri_repetition.len(), &mut dst)?;
+        for queries in self.queries_per_fri_repetition.iter() {
+            write_single_round_queries(queries, &mut dst)?;
+        }
+
+        MemcopySerializable::write_into_buffer(&self.pow_challenge, &mut dst)?;
+
+        Ok(())
+    }
+
+    fn read_from_buffer<R: Read>(mut src: R) -> Result<Self, Box<dyn Error>> {
+        let header: EncodingHeader = MemcopySerializable::read_from_buffer(&mut src)?;
+        header.check_against(&EncodingHeader::new_for_proof::<F, H, N, EXT>([0u8; 32]))?;
+
+        let proof_config = read_proof_config(&mut src)?;
+        header.check_digest(&proof_config_digest(&proof_config))?;
+
+        let public_inputs = read_field_elements(&mut src)?;
+
+        let witness_oracle_cap = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let stage_2_oracle_cap = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let quotient_oracle_cap = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let mut final_fri_monomials: [Vec<F>; N] = std::array::from_fn(|_| vec![]);
+        for monomials in final_fri_monomials.iter_mut() {
+            *monomials = read_field_elements(&mut src)?;
+        }
+
+        let values_at_z = read_ext_elements(&mut src)?;
+        let values_at_z_omega = read_ext_elements(&mut src)?;
+        let values_at_0 = read_ext_elements(&mut src)?;
+
+        let fri_base_oracle_cap = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let num_intermediate_oracles = read_length(&mut src)?;
+        let mut fri_intermediate_oracles_caps =
+            Vec::with_capacity(num_intermediate_oracles.min(MAX_PREALLOCATED_ELEMENTS));
+        for _ in 0..num_intermediate_oracles {
+            fri_intermediate_oracles_caps.push(read_hasher_outputs::<F, H, _>(&mut src)?);
+        }
+
+        let num_repetitions = read_length(&mut src)?;
+        let mut queries_per_fri_repetition =
+            Vec::with_capacity(num_repetitions.min(MAX_PREALLOCATED_ELEMENTS));
+        for _ in 0..num_repetitions {
+            queries_per_fri_repetition.push(read_single_round_queries(&mut src)?);
+        }
+
+        let pow_challenge = MemcopySerializable::read_from_buffer(&mut src)?;
+
+        Ok(Self {
+            proof_config,
+            public_inputs,
+            witness_oracle_cap,
+            stage_2_oracle_cap,
+            quotient_oracle_cap,
+            final_fri_monomials,
+            values_at_z,
+            values_at_z_omega,
+            values_at_0,
+            fri_base_oracle_cap,
+            fri_intermediate_oracles_caps,
+            queries_per_fri_repetition,
+            pow_challenge,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+// deduplicated paths follow the proof with empty paths
+impl<
+        F: SmallField,
+        H: EncodableTreeHasher<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    > MemcopySerializable for ProofWithDeduplicatedPaths<F, H, EXT, N>
+{
+    fn write_into_buffer<W: Write>(&self, mut dst: W) -> Result<(), Box<dyn Error>> {
+        MemcopySerializable::write_into_buffer(&self.proof, &mut dst)?;
+
+        write_hasher_outputs::<F, H, _>(&self.paths.witness_oracle, &mut dst)?;
+        write_hasher_outputs::<F, H, _>(&self.paths.stage_2_oracle, &mut dst)?;
+        write_hasher_outputs::<F, H, _>(&self.paths.quotient_oracle, &mut dst)?;
+        write_hasher_outputs::<F, H, _>(&self.paths.setup_oracle, &mut dst)?;
+        MemcopySerializable::write_into_buffer(&self.paths.fri_oracles.len(), &mut dst)?;
+        for paths in self.paths.fri_oracles.iter() {
+            write_hasher_outputs::<F, H, _>(paths, &mut dst)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_from_buffer<R: Read>(mut src: R) -> Result<Self, Box<dyn Error>> {
+        let proof = MemcopySerializable::read_from_buffer(&mut src)?;
+
+        let witness_oracle = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let stage_2_oracle = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let quotient_oracle = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let setup_oracle = read_hasher_outputs::<F, H, _>(&mut src)?;
+        let num_fri_oracles = read_length(&mut src)?;
+        let mut fri_oracles = Vec::with_capacity(num_fri_oracles.min(MAX_PREALLOCATED_ELEMENTS));
+        for _ in 0..num_fri_oracles {
+            fri_oracles.push(read_hasher_outputs::<F, H, _>(&mut src)?);
+        }
+
+        Ok(Self {
+            proof,
+            paths: DeduplicatedQueryPaths {
+                witness_oracle,
+                stage_2_oracle,
+                quotient_oracle,
+                setup_oracle,
+                fri_oracles,
+            },
+        })
+    }
+}
+
+impl<F: SmallField, H: EncodableTreeHasher<F>> MemcopySerializable for VerificationKey<F, H> {
+    fn write_into_buffer<W: Write>(&self, mut dst: W) -> Result<(), Box<dyn Error>> {
+        let mut encoded_geometry = vec![];
+        MemcopySerializable::write_into_buffer(&self.fixed_parameters, &mut encoded_geometry)?;
+
+        let header = EncodingHeader::new_for_vk::<F, H>(digest_bytes(&encoded_geometry));
+        MemcopySerializable::write_into_buffer(&header, &mut dst)?;
+
+        dst.write_all(&encoded_geometry).map_err(Box::new)?;
+        write_hasher_outputs::<F, H, _>(&self.setup_merkle_tree_cap, &mut dst)?;
+
+        Ok(())
+    }
+
+    fn read_from_buffer<R: Read>(mut src: R) -> Result<Self, Box<dyn Error>> {
+        let header: EncodingHeader = MemcopySerializable::read_from_buffer(&mut src)?;
+        header.check_against(&EncodingHeader::new_for_vk::<F, H>([0u8; 32]))?;
+
+        let fixed_parameters: VerificationKeyCircuitGeometry =
+            MemcopySerializable::read_from_buffer(&mut src)?;
+        let mut encoded_geometry = vec![];
+        MemcopySerializable::write_into_buffer(&fixed_parameters, &mut encoded_geometry)?;
+        header.check_digest(&digest_bytes(&encoded_geometry))?;
+
+        let setup_merkle_tree_cap = read_hasher_outputs::<F, H, _>(&mut src)?;
+
+        Ok(Self {
+            fixed_parameters,
+            setup_merkle_tree_cap,
+        })
+    }
+}
+
+fn decode_exact<T: MemcopySerializable>(bytes: &[u8]) -> Result<T, Box<dyn Error>> {
+    let mut src = bytes;
+    let result = T::read_from_buffer(&mut src)?;
+    if !src.is_empty() {
+        return Err(Box::<dyn Error>::from(format!(
+            "{} trailing bytes after the encoding",
+            src.len()
+        )));
+    }
+
+    Ok(result)
+}
+
+impl<
+        F: SmallField,
+        H: EncodableTreeHasher<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    > Proof<F, H, EXT, N>
+{
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![];
+        MemcopySerializable::write_into_buffer(self, &mut result).expect("must serialize");
+
+        result
+    }
+
+    /// Decodes the proof, rejecting encodings for other fields or hashers and any trailing bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        decode_exact(bytes)
+    }
+}
+
+impl<
+        F: SmallField,
+        H: EncodableTreeHasher<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    > ProofWithDeduplicatedPaths<F, H, EXT, N>
+{
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![];
+        MemcopySerializable::write_into_buffer(self, &mut result).expect("must serialize");
+
+        result
+    }
+
+    /// Decodes the proof, rejecting encodings for other fields or hashers and any trailing bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        decode_exact(bytes)
+    }
+}
+
+impl<F: SmallField, H: EncodableTreeHasher<F>> VerificationKey<F, H> {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = vec![];
+        MemcopySerializable::write_into_buffer(self, &mut result).expect("must serialize");
+
+        result
+    }
+
+    /// Decodes the verification key, rejecting encodings for other fields or hashers and any trailing bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        decode_exact(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::goldilocks::GoldilocksExt2;
+
+    type F = GoldilocksField;
+    type Ext = GoldilocksExt2;
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    fn load_proof_and_vk() -> (Proof<F, H, Ext>, VerificationKey<F, H>) {
+        let mut vk_file = std::fs::File::open("vk.json").unwrap();
+        let mut proof_file = std::fs::File::open("proof.json").unwrap();
+
+        let vk: VerificationKey<F, H> = serde_json::from_reader(&mut vk_file).unwrap();
+        let proof: Proof<F, H, Ext> = serde_json::from_reader(&mut proof_file).unwrap();
+
+        (proof, vk)
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let (proof, vk) = load_proof_and_vk();
+
+        let encoded_proof = proof.to_bytes();
+        let decoded_proof = Proof::<F, H, Ext>::from_bytes(&encoded_proof).unwrap();
+        assert_eq!(decoded_proof.to_bytes(), encoded_proof);
+        assert_eq!(
+            serde_json::to_value(&decoded_proof).unwrap(),
+            serde_json::to_value(&proof).unwrap()
+        );
+
+        let encoded_vk = vk.to_bytes();
+        let decoded_vk = VerificationKey::<F, H>::from_bytes(&encoded_vk).unwrap();
+        assert_eq!(decoded_vk, vk);
+
+        let json_size = std::fs::metadata("proof.json").unwrap().len() as usize;
+        assert!(encoded_proof.len() < json_size);
+
+        let header = EncodingHeader::peek(&encoded_proof).unwrap();
+        assert_eq!(header.magic, PROOF_ENCODING_MAGIC);
+        assert_eq!(header.hasher_id, <H as EncodableTreeHasher<F>>::HASHER_ID);
+        assert_eq!(
+            header.config_digest,
+            proof_config_digest(&proof.proof_config)
+        );
+    }
+
+    #[test]
+    fn test_malformed_encodings_are_rejected() {
+        let (proof, vk) = load_proof_and_vk();
+        let encoded_proof = proof.to_bytes();
+        let encoded_vk = vk.to_bytes();
+
+        // trailing and missing bytes
+        let mut extended = encoded_proof.clone();
+        extended.push(0);
+        assert!(Proof::<F, H, Ext>::from_bytes(&extended).is_err());
+        assert!(Proof::<F, H, Ext>::from_bytes(&encoded_proof[..encoded_proof.len() - 1]).is_err());
+
+        // wrong hasher or wrong object type
+        assert!(Proof::<F, blake2::Blake2s256, Ext>::from_bytes(&encoded_proof).is_err());
+        assert!(
+            Proof::<F, GoldilocksPoseidon2Sponge<AbsorptionModeAdd>, Ext>::from_bytes(
+                &encoded_proof
+            )
+            .is_err()
+        );
+        assert!(VerificationKey::<F, H>::from_bytes(&encoded_proof).is_err());
+        assert!(Proof::<F, H, Ext>::from_bytes(&encoded_vk).is_err());
+
+        // configuration that doesn't match the digest
+        let mut tampered = encoded_proof.clone();
+        tampered[HEADER_SIZE] ^= 1;
+        assert!(Proof::<F, H, Ext>::from_bytes(&tampered).is_err());
+
+        let mut tampered = encoded_vk;
+        tampered[HEADER_SIZE] ^= 1;
+        assert!(VerificationKey::<F, H>::from_bytes(&tampered).is_err());
+
+        // non-canonical public input, that follows the configuration without folding schedule
+        assert!(proof.proof_config.fri_folding_schedule.is_none());
+        let first_public_input_offset = HEADER_SIZE + 8 + 8 + 1 + 8 + 4 + 8;
+        let mut non_canonical = encoded_proof;
+        non_canonical[first_public_input_offset..][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(Proof::<F, H, Ext>::from_bytes(&non_canonical).is_err());
+    }
+}
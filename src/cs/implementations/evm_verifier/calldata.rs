@@ -0,0 +1,269 @@
+use super::*;
+
+use crate::cs::implementations::proof::{OracleQuery, Proof};
+use crate::cs::implementations::proof_layout::ProofLayout;
+use crate::cs::implementations::prover::{compute_fri_schedule_for_config, ProofConfig};
+use crate::cs::implementations::verifier::{
+    OpeningPoint, VerificationError, VerificationKeyCircuitGeometry, Verifier,
+};
+use crate::field::FieldExtension;
+use sha3::{Digest, Keccak256};
+
+/// Signature of the entry point of the generated verifier
+pub const VERIFY_FUNCTION_SIGNATURE: &str = "verify(uint256[])";
+
+/// Placement of the parts of a proof in the flat array of 32-byte words that the generated
+/// verifier takes. Everything that is absorbed into the transcript goes first and in the same
+/// order, then queries follow one by one. Field elements take a word each, extension elements
+/// take two words, and caps and Merkle paths take a word per hash. All offsets are in words
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalldataLayout {
+    pub cap_size: usize,
+    pub num_public_inputs: usize,
+    pub num_values_at_z: usize,
+    pub num_values_at_z_omega: usize,
+    pub num_values_at_0: usize,
+
+    pub public_inputs_offset: usize,
+    pub witness_cap_offset: usize,
+    pub stage_2_cap_offset: usize,
+    pub quotient_cap_offset: usize,
+    /// Values at `z`, at `z * omega` and at 0, one after another
+    pub values_offset: usize,
+    pub fri_base_cap_offset: usize,
+    pub fri_intermediate_caps_offset: usize,
+    /// Coefficients of the first half of the final monomials, then of the second one
+    pub final_monomials_offset: usize,
+    pub pow_challenge_offset: usize,
+    pub queries_offset: usize,
+
+    pub fri_schedule: Vec<usize>,
+    pub final_degree: usize,
+    pub pow_bits: u32,
+    pub num_queries: usize,
+    pub base_oracle_depth: usize,
+
+    pub witness_leaf_size: usize,
+    pub stage_2_leaf_size: usize,
+    pub quotient_leaf_size: usize,
+    pub setup_leaf_size: usize,
+    // offsets within a single query, each oracle query is a leaf followed by the path
+    pub witness_query_offset: usize,
+    pub stage_2_query_offset: usize,
+    pub quotient_query_offset: usize,
+    pub setup_query_offset: usize,
+    pub fri_queries_offset: usize,
+    pub query_size: usize,
+
+    pub total_len: usize,
+}
+
+impl CalldataLayout {
+    pub fn new<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
+        verifier: &Verifier<F, EXT>,
+        fixed_parameters: &VerificationKeyCircuitGeometry,
+        proof_config: &ProofConfig,
+    ) -> Result<Self, VerificationError> {
+        if proof_config.zero_knowledge {
+            return Err(VerificationError::ZeroKnowledgeIsNotSupported);
+        }
+        if fixed_parameters.cap_size != proof_config.merkle_tree_cap_size {
+            return Err(VerificationError::CapSizeMismatch {
+                vk: fixed_parameters.cap_size,
+                proof: proof_config.merkle_tree_cap_size,
+            });
+        }
+        if fixed_parameters.fri_lde_factor != proof_config.fri_lde_factor {
+            return Err(VerificationError::FriLdeFactorMismatch {
+                vk: fixed_parameters.fri_lde_factor,
+                proof: proof_config.fri_lde_factor,
+            });
+        }
+
+        let (pow_bits, num_queries, fri_schedule, final_degree) = compute_fri_schedule_for_config(
+            proof_config,
+            proof_config.fri_lde_factor.trailing_zeros(),
+            fixed_parameters.domain_size.trailing_zeros(),
+        )
+        .map_err(VerificationError::InvalidFriSchedule)?;
+        if pow_bits != proof_config.pow_bits {
+            return Err(VerificationError::PowBitsMismatch {
+                expected: pow_bits,
+                got: proof_config.pow_bits,
+            });
+        }
+
+        let proof_layout = ProofLayout::new(verifier, fixed_parameters, proof_config);
+        let cap_size = fixed_parameters.cap_size;
+        let num_public_inputs = fixed_parameters.public_inputs_locations.len();
+        let num_values_at_z = proof_layout.num_openings(OpeningPoint::Z);
+        let num_values_at_z_omega = proof_layout.num_openings(OpeningPoint::ZOmega);
+        let num_values_at_0 = proof_layout.num_openings(OpeningPoint::Zero);
+
+        let mut offset = 0;
+        let mut take = |len: usize| {
+            let start = offset;
+            offset += len;
+
+            start
+        };
+
+        let public_inputs_offset = take(num_public_inputs);
+        let witness_cap_offset = take(cap_size);
+        let stage_2_cap_offset = take(cap_size);
+        let quotient_cap_offset = take(cap_size);
+        let values_offset = take(2 * (num_values_at_z + num_values_at_z_omega + num_values_at_0));
+        let fri_base_cap_offset = take(cap_size);
+        let fri_intermediate_caps_offset = take((fri_schedule.len() - 1) * cap_size);
+        let final_monomials_offset = take(2 * final_degree);
+        let pow_challenge_offset = take(1);
+        let queries_offset = offset;
+
+        let base_oracle_depth = fixed_parameters.base_oracles_depth();
+        let witness_leaf_size = verifier.witness_leaf_size(fixed_parameters);
+        let stage_2_leaf_size = verifier.stage_2_leaf_size(fixed_parameters);
+        let quotient_leaf_size = verifier.quotient_leaf_size(fixed_parameters);
+        let setup_leaf_size = verifier.setup_leaf_size(fixed_parameters);
+
+        let mut offset = 0;
+        let mut take = |len: usize| {
+            let start = offset;
+            offset += len;
+
+            start
+        };
+
+        let witness_query_offset = take(witness_leaf_size + base_oracle_depth);
+        let stage_2_query_offset = take(stage_2_leaf_size + base_oracle_depth);
+        let quotient_query_offset = take(quotient_leaf_size + base_oracle_depth);
+        let setup_query_offset = take(setup_leaf_size + base_oracle_depth);
+        let mut depth = base_oracle_depth;
+        let mut fri_queries_len = 0;
+        for interpolation_degree_log2 in fri_schedule.iter() {
+            depth -= *interpolation_degree_log2;
+            fri_queries_len += (2 << *interpolation_degree_log2) + depth;
+        }
+        let fri_queries_offset = take(fri_queries_len);
+        let query_size = offset;
+
+        let total_len = queries_offset + num_queries * query_size;
+
+        Ok(Self {
+            cap_size,
+            num_public_inputs,
+            num_values_at_z,
+            num_values_at_z_omega,
+            num_values_at_0,
+            public_inputs_offset,
+            witness_cap_offset,
+            stage_2_cap_offset,
+            quotient_cap_offset,
+            values_offset,
+            fri_base_cap_offset,
+            fri_intermediate_caps_offset,
+            final_monomials_offset,
+            pow_challenge_offset,
+            queries_offset,
+            fri_schedule,
+            final_degree,
+            pow_bits,
+            num_queries,
+            base_oracle_depth,
+            witness_leaf_size,
+            stage_2_leaf_size,
+            quotient_leaf_size,
+            setup_leaf_size,
+            witness_query_offset,
+            stage_2_query_offset,
+            quotient_query_offset,
+            setup_query_offset,
+            fri_queries_offset,
+            query_size,
+            total_len,
+        })
+    }
+}
+
+fn field_element_word<F: SmallField>(el: &F) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&el.as_u64_reduced().to_be_bytes());
+
+    word
+}
+
+/// Flattens the proof into 32-byte words as described by `CalldataLayout`
+pub fn proof_into_words<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
+    proof: &Proof<F, Keccak256, EXT>,
+) -> Vec<[u8; 32]> {
+    let mut words = vec![];
+    let push_query = |words: &mut Vec<[u8; 32]>, query: &OracleQuery<F, Keccak256>| {
+        words.extend(query.leaf_elements.iter().map(field_element_word));
+        words.extend_from_slice(&query.proof);
+    };
+
+    words.extend(proof.public_inputs.iter().map(field_element_word));
+    words.extend_from_slice(&proof.witness_oracle_cap);
+    words.extend_from_slice(&proof.stage_2_oracle_cap);
+    words.extend_from_slice(&proof.quotient_oracle_cap);
+    for value in proof
+        .values_at_z
+        .iter()
+        .chain(proof.values_at_z_omega.iter())
+        .chain(proof.values_at_0.iter())
+    {
+        words.extend(value.as_coeffs_in_base().iter().map(field_element_word));
+    }
+    words.extend_from_slice(&proof.fri_base_oracle_cap);
+    for cap in proof.fri_intermediate_oracles_caps.iter() {
+        words.extend_from_slice(cap);
+    }
+    for monomials in proof.final_fri_monomials.iter() {
+        words.extend(monomials.iter().map(field_element_word));
+    }
+    let mut pow_challenge = [0u8; 32];
+    pow_challenge[24..].copy_from_slice(&proof.pow_challenge.to_be_bytes());
+    words.push(pow_challenge);
+
+    for queries in proof.queries_per_fri_repetition.iter() {
+        push_query(&mut words, &queries.witness_query);
+        push_query(&mut words, &queries.stage_2_query);
+        push_query(&mut words, &queries.quotient_query);
+        push_query(&mut words, &queries.setup_query);
+        for fri_query in queries.fri_queries.iter() {
+            push_query(&mut words, fri_query);
+        }
+    }
+
+    words
+}
+
+/// Selector of the entry point of the generated verifier
+pub fn verify_function_selector() -> [u8; 4] {
+    let hash = Keccak256::digest(VERIFY_FUNCTION_SIGNATURE.as_bytes());
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&hash[..4]);
+
+    selector
+}
+
+/// ABI encoded call of the generated verifier on the proof
+pub fn encode_proof_calldata<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
+    proof: &Proof<F, Keccak256, EXT>,
+) -> Vec<u8> {
+    let words = proof_into_words(proof);
+
+    let mut calldata = Vec::with_capacity(4 + 32 * (2 + words.len()));
+    calldata.extend(verify_function_selector());
+    // single dynamic argument: offset of its data, then length and elements
+    let mut offset = [0u8; 32];
+    offset[31] = 32;
+    calldata.extend(offset);
+    let mut len = [0u8; 32];
+    len[24..].copy_from_slice(&(words.len() as u64).to_be_bytes());
+    calldata.extend(len);
+    for word in words.iter() {
+        calldata.extend(word);
+    }
+
+    calldata
+}
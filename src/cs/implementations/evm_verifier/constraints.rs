@@ -0,0 +1,497 @@
+use super::symbolic::{ExpressionTape, SymbolicExt};
+use super::*;
+
+use crate::cs::gates::lookup_marker::{LookupFormalGate, LookupGateMarkerFormalEvaluator};
+use crate::cs::implementations::copy_permutation::non_residues_for_copy_permutation;
+use crate::cs::implementations::proof_layout::ProofLayout;
+use crate::cs::implementations::prover::ProofConfig;
+use crate::cs::implementations::verifier::{
+    compute_selector_subpath_at_z, OpeningPoint, VerificationKeyCircuitGeometry, Verifier,
+    VerifierPolyStorage, VerifierRelationDestination,
+};
+use crate::cs::traits::gate::GatePlacementStrategy;
+use crate::cs::LookupParameters;
+use crate::field::traits::field_like::PrimeFieldLike;
+use crate::field::{ExtensionField, Field, FieldExtension};
+use std::alloc::Global;
+use std::collections::HashMap;
+
+// Inputs of the tape: challenges drawn before the openings at z, followed by
+// `values_at_z`, `values_at_z_omega` and `values_at_0` in the order of the proof
+pub const BETA_INPUT: usize = 0;
+pub const GAMMA_INPUT: usize = 1;
+pub const LOOKUP_BETA_INPUT: usize = 2;
+pub const LOOKUP_GAMMA_INPUT: usize = 3;
+pub const ALPHA_INPUT: usize = 4;
+pub const Z_INPUT: usize = 5;
+pub const NUM_CHALLENGE_INPUTS: usize = 6;
+
+/// Checks that the verifier makes at `z`, recorded as a straight-line program. The proof is
+/// valid at `z` if all the differences are zero
+#[derive(Clone, Debug)]
+pub struct ConstraintsAtZ<F: SmallField> {
+    pub tape: ExpressionTape<F>,
+    /// Sum of lookup witness encodings at 0 minus sum of multiplicity encodings at 0
+    pub lookup_sumcheck: Option<SymbolicExt<F>>,
+    /// Combination of all the terms at `z` minus quotient at `z` times vanishing polynomial
+    pub quotient_identity: SymbolicExt<F>,
+}
+
+impl<F: SmallField> ConstraintsAtZ<F> {
+    pub fn new<EXT: FieldExtension<2, BaseField = F>>(
+        verifier: &Verifier<F, EXT>,
+        fixed_parameters: &VerificationKeyCircuitGeometry,
+        proof_config: &ProofConfig,
+    ) -> Self {
+        let layout = ProofLayout::new(verifier, fixed_parameters, proof_config);
+        let quotient_degree = verifier.quotient_degree(fixed_parameters);
+        let num_variable_polys = verifier.num_variable_polys();
+
+        let mut tape = ExpressionTape::<F>::new();
+        let ctx = &mut tape;
+
+        let challenges: Vec<_> = (0..NUM_CHALLENGE_INPUTS).map(|_| ctx.input()).collect();
+        let beta = challenges[BETA_INPUT];
+        let gamma = challenges[GAMMA_INPUT];
+        let lookup_beta = challenges[LOOKUP_BETA_INPUT];
+        let lookup_gamma = challenges[LOOKUP_GAMMA_INPUT];
+        let alpha = challenges[ALPHA_INPUT];
+        let z = challenges[Z_INPUT];
+
+        let values_at_z: Vec<_> = (0..layout.num_openings(OpeningPoint::Z))
+            .map(|_| ctx.input())
+            .collect();
+        let values_at_z_omega: Vec<_> = (0..layout.num_openings(OpeningPoint::ZOmega))
+            .map(|_| ctx.input())
+            .collect();
+        let values_at_0: Vec<_> = (0..layout.num_openings(OpeningPoint::Zero))
+            .map(|_| ctx.input())
+            .collect();
+
+        let variables_polys_values = values_at_z[layout.variables.clone()].to_vec();
+        let witness_polys_values = values_at_z[layout.witnesses.clone()].to_vec();
+        let constant_poly_values = values_at_z[layout.constants.clone()].to_vec();
+        let sigmas_values = &values_at_z[layout.sigmas.clone()];
+        let copy_permutation_z_at_z = values_at_z[layout.copy_permutation_z];
+        let grand_product_intermediate_polys =
+            &values_at_z[layout.copy_permutation_partial_products.clone()];
+        let multiplicities_polys_values = &values_at_z[layout.lookup_multiplicities.clone()];
+        let lookup_witness_encoding_polys_values =
+            &values_at_z[layout.lookup_witness_encodings.clone()];
+        let multiplicities_encoding_polys_values =
+            &values_at_z[layout.lookup_multiplicity_encodings.clone()];
+        let lookup_tables_columns = &values_at_z[layout.lookup_table_setup.clone()];
+        let quotient_chunks = &values_at_z[layout.quotient_chunks.clone()];
+        let copy_permutation_z_at_z_omega = values_at_z_omega[0];
+
+        // same number of terms and in the same order as the verifier uses
+        let num_lookup_terms = values_at_0.len();
+        let num_specialized_terms: usize = verifier
+            .evaluators_over_specialized_columns
+            .iter()
+            .map(|evaluator| evaluator.total_quotient_terms_over_all_repetitions)
+            .sum();
+        let num_general_purpose_terms: usize = verifier
+            .evaluators_over_general_purpose_columns
+            .iter()
+            .map(|evaluator| evaluator.total_quotient_terms_over_all_repetitions)
+            .sum();
+        let total_num_terms = num_lookup_terms
+            + num_specialized_terms
+            + num_general_purpose_terms
+            + 1
+            + 1
+            + grand_product_intermediate_polys.len();
+
+        let mut powers = Vec::with_capacity(total_num_terms);
+        let mut current = SymbolicExt::one(ctx);
+        for _ in 0..total_num_terms {
+            powers.push(current);
+            current.mul_assign(&alpha, ctx);
+        }
+        let (lookup_challenges, rest) = powers.split_at(num_lookup_terms);
+        let (specialized_evaluators_challenges, rest) = rest.split_at(num_specialized_terms);
+        let (general_purpose_challenges, remaining_challenges) =
+            rest.split_at(num_general_purpose_terms);
+
+        let mut t_accumulator = SymbolicExt::zero(ctx);
+
+        let mut selectors_buffer = HashMap::new();
+        for (gate_idx, evaluator) in verifier
+            .evaluators_over_general_purpose_columns
+            .iter()
+            .enumerate()
+        {
+            if let Some(path) = fixed_parameters
+                .selectors_placement
+                .output_placement(gate_idx)
+            {
+                assert!(
+                    selectors_buffer.contains_key(&path) == false,
+                    "same selector for different gates"
+                );
+
+                compute_selector_subpath_at_z(
+                    path,
+                    &mut selectors_buffer,
+                    &constant_poly_values,
+                    ctx,
+                );
+            } else {
+                assert!(evaluator.num_quotient_terms == 0);
+            }
+        }
+
+        let mut lookup_sumcheck = None;
+        if verifier.lookup_parameters != LookupParameters::NoLookup {
+            let mut witness_subsum = SymbolicExt::zero(ctx);
+            for a in values_at_0[layout.lookup_witness_encodings_at_zero.clone()].iter() {
+                witness_subsum.add_assign(a, ctx);
+            }
+            let mut multiplicities_subsum = SymbolicExt::zero(ctx);
+            for b in values_at_0[layout.lookup_multiplicity_encodings_at_zero].iter() {
+                multiplicities_subsum.add_assign(b, ctx);
+            }
+            witness_subsum.sub_assign(&multiplicities_subsum, ctx);
+            lookup_sumcheck = Some(witness_subsum);
+
+            // lookup over general purpose columns is enabled by the selector, and
+            // over specialized columns it's always on
+            let (column_elements_per_subargument, variables_offset, numerator) =
+                match verifier.lookup_parameters {
+                    LookupParameters::TableIdAsVariable { .. }
+                    | LookupParameters::TableIdAsConstant { .. } => {
+                        let lookup_evaluator_id = 0;
+                        let selector_subpath = fixed_parameters
+                            .selectors_placement
+                            .output_placement(lookup_evaluator_id)
+                            .expect("lookup gate must be placed");
+                        let selector = selectors_buffer
+                            .remove(&selector_subpath)
+                            .expect("path must be unique and precomputed");
+
+                        (
+                            verifier.lookup_parameters.columns_per_subargument() as usize,
+                            0,
+                            selector,
+                        )
+                    }
+                    LookupParameters::UseSpecializedColumnsWithTableIdAsConstant { .. }
+                    | LookupParameters::UseSpecializedColumnsWithTableIdAsVariable { .. } => (
+                        verifier
+                            .lookup_parameters
+                            .specialized_columns_per_subargument() as usize,
+                        verifier.parameters.num_columns_under_copy_permutation,
+                        SymbolicExt::one(ctx),
+                    ),
+                    _ => unreachable!(),
+                };
+
+            assert!(fixed_parameters.table_ids_column_idxes.len() <= 1);
+            let capacity =
+                column_elements_per_subargument + fixed_parameters.table_ids_column_idxes.len();
+            assert_eq!(lookup_tables_columns.len(), capacity);
+
+            let mut powers_of_gamma = Vec::with_capacity(capacity);
+            let mut tmp = SymbolicExt::one(ctx);
+            powers_of_gamma.push(tmp);
+            for _ in 1..capacity {
+                tmp.mul_assign(&lookup_gamma, ctx);
+                powers_of_gamma.push(tmp);
+            }
+
+            let mut lookup_table_columns_aggregated = lookup_beta;
+            for (gamma, column) in powers_of_gamma.iter().zip(lookup_tables_columns.iter()) {
+                SymbolicExt::mul_and_accumulate_into(
+                    &mut lookup_table_columns_aggregated,
+                    gamma,
+                    column,
+                    ctx,
+                );
+            }
+
+            let mut challenges_it = lookup_challenges.iter();
+
+            let num_lookup_subarguments = lookup_witness_encoding_polys_values.len();
+            let variables_columns_for_lookup = &variables_polys_values[variables_offset
+                ..(variables_offset + column_elements_per_subargument * num_lookup_subarguments)];
+            let table_id: Vec<_> = fixed_parameters
+                .table_ids_column_idxes
+                .first()
+                .map(|idx| constant_poly_values[*idx])
+                .into_iter()
+                .collect();
+
+            // first A polys
+            for (a_poly, witness_columns) in lookup_witness_encoding_polys_values
+                .iter()
+                .zip(variables_columns_for_lookup.chunks_exact(column_elements_per_subargument))
+            {
+                let alpha = *challenges_it
+                    .next()
+                    .expect("challenge for lookup A poly contribution");
+                let mut contribution = lookup_beta;
+                for (gamma, column) in powers_of_gamma
+                    .iter()
+                    .zip(witness_columns.iter().chain(table_id.iter()))
+                {
+                    SymbolicExt::mul_and_accumulate_into(&mut contribution, gamma, column, ctx);
+                }
+                contribution.mul_assign(a_poly, ctx);
+                contribution.sub_assign(&numerator, ctx);
+                contribution.mul_assign(&alpha, ctx);
+
+                t_accumulator.add_assign(&contribution, ctx);
+            }
+
+            // then B polys
+            for (b_poly, multiplicities_poly) in multiplicities_encoding_polys_values
+                .iter()
+                .zip(multiplicities_polys_values.iter())
+            {
+                let alpha = *challenges_it
+                    .next()
+                    .expect("challenge for lookup B poly contribution");
+                let mut contribution = lookup_table_columns_aggregated;
+                contribution.mul_assign(b_poly, ctx);
+                contribution.sub_assign(multiplicities_poly, ctx);
+                contribution.mul_assign(&alpha, ctx);
+
+                t_accumulator.add_assign(&contribution, ctx);
+            }
+        }
+
+        let constants_for_gates_over_general_purpose_columns = fixed_parameters
+            .extra_constant_polys_for_selectors
+            + verifier.parameters.num_constant_columns;
+
+        let src = VerifierPolyStorage::new(
+            variables_polys_values.clone(),
+            witness_polys_values,
+            constant_poly_values,
+        );
+
+        // then specialized gates
+        let mut challenges_offset = 0;
+        for (idx, (gate_type_id, evaluator)) in verifier
+            .gate_type_ids_for_specialized_columns
+            .iter()
+            .zip(verifier.evaluators_over_specialized_columns.iter())
+            .enumerate()
+        {
+            if gate_type_id == &std::any::TypeId::of::<LookupFormalGate>() {
+                continue;
+            }
+
+            let GatePlacementStrategy::UseSpecializedColumns {
+                num_repetitions, ..
+            } = verifier.placement_strategies[gate_type_id]
+            else {
+                unreachable!();
+            };
+            let (initial_offset, per_repetition_offset, _) =
+                verifier.offsets_for_specialized_evaluators[idx];
+            let mut final_offset = initial_offset;
+            for _ in 0..num_repetitions {
+                final_offset.add_offset(&per_repetition_offset);
+            }
+
+            let mut dst = VerifierRelationDestination {
+                accumulator: SymbolicExt::zero(ctx),
+                selector_value: SymbolicExt::one(ctx),
+                challenges: specialized_evaluators_challenges.to_vec(),
+                current_challenge_offset: challenges_offset,
+                _marker: std::marker::PhantomData,
+            };
+            let mut src = src.subset(
+                initial_offset.variables_offset..final_offset.variables_offset,
+                initial_offset.witnesses_offset..final_offset.witnesses_offset,
+                (constants_for_gates_over_general_purpose_columns + initial_offset.constants_offset)
+                    ..(constants_for_gates_over_general_purpose_columns
+                        + final_offset.constants_offset),
+            );
+
+            evaluator
+                .columnwise_symbolic_function
+                .as_ref()
+                .expect("must be properly configured")
+                .evaluate_over_columns(&mut src, &mut dst, ctx);
+
+            t_accumulator.add_assign(&dst.accumulator, ctx);
+            challenges_offset += evaluator.total_quotient_terms_over_all_repetitions;
+        }
+        assert_eq!(challenges_offset, num_specialized_terms);
+
+        // then general purpose gates
+        let src = src.subset(
+            0..verifier.parameters.num_columns_under_copy_permutation,
+            0..verifier.parameters.num_witness_columns,
+            0..constants_for_gates_over_general_purpose_columns,
+        );
+        let mut challenges_offset = 0;
+        for (gate_idx, evaluator) in verifier
+            .evaluators_over_general_purpose_columns
+            .iter()
+            .enumerate()
+        {
+            if evaluator.evaluator_type_id
+                == std::any::TypeId::of::<LookupGateMarkerFormalEvaluator>()
+            {
+                continue;
+            }
+            if evaluator.total_quotient_terms_over_all_repetitions == 0 {
+                continue;
+            }
+
+            let path = fixed_parameters
+                .selectors_placement
+                .output_placement(gate_idx)
+                .expect("gate must be placed");
+            let selector = selectors_buffer
+                .remove(&path)
+                .expect("path must be unique and precomputed");
+            let constant_placement_offset = path.len();
+
+            let mut dst = VerifierRelationDestination {
+                accumulator: SymbolicExt::zero(ctx),
+                selector_value: selector,
+                challenges: general_purpose_challenges.to_vec(),
+                current_challenge_offset: challenges_offset,
+                _marker: std::marker::PhantomData,
+            };
+            let mut source = src.clone();
+
+            evaluator
+                .rowwise_symbolic_function
+                .as_ref()
+                .expect("gate must be allowed")
+                .evaluate_over_general_purpose_columns(
+                    &mut source,
+                    &mut dst,
+                    constant_placement_offset,
+                    ctx,
+                );
+
+            t_accumulator.add_assign(&dst.accumulator, ctx);
+            challenges_offset += evaluator.total_quotient_terms_over_all_repetitions;
+        }
+        assert_eq!(challenges_offset, num_general_purpose_terms);
+
+        // then copy-permutation
+        let z_in_domain_size = z.pow_u64(fixed_parameters.domain_size, ctx);
+        let mut vanishing_at_z = z_in_domain_size;
+        vanishing_at_z.sub_assign(&SymbolicExt::one(ctx), ctx);
+
+        let mut challenges_it = remaining_challenges.iter();
+
+        {
+            // (z(x) - 1) * l(1)
+            let mut z_minus_one = z;
+            z_minus_one.sub_assign(&SymbolicExt::one(ctx), ctx);
+
+            let mut unnormalized_l1_inverse_at_z = vanishing_at_z;
+            unnormalized_l1_inverse_at_z.mul_assign(&z_minus_one.inverse(ctx), ctx);
+
+            let alpha = *challenges_it.next().expect("challenge for z(1) == 1");
+            let mut contribution = copy_permutation_z_at_z;
+            contribution.sub_assign(&SymbolicExt::one(ctx), ctx);
+            contribution.mul_assign(&unnormalized_l1_inverse_at_z, ctx);
+            contribution.mul_assign(&alpha, ctx);
+
+            t_accumulator.add_assign(&contribution, ctx);
+        }
+
+        let non_residues = non_residues_for_copy_permutation::<F, Global>(
+            fixed_parameters.domain_size as usize,
+            num_variable_polys,
+        );
+
+        let lhs = grand_product_intermediate_polys
+            .iter()
+            .chain(std::iter::once(&copy_permutation_z_at_z_omega));
+        let rhs = std::iter::once(&copy_permutation_z_at_z).chain(grand_product_intermediate_polys);
+
+        for (((((lhs, rhs), alpha), non_residues), variables), sigmas) in lhs
+            .zip(rhs)
+            .zip(&mut challenges_it)
+            .zip(non_residues.chunks(quotient_degree))
+            .zip(variables_polys_values.chunks(quotient_degree))
+            .zip(sigmas_values.chunks(quotient_degree))
+        {
+            let mut lhs = *lhs;
+            for (variable, sigma) in variables.iter().zip(sigmas.iter()) {
+                // denominator is w + beta * sigma(x) + gamma
+                let mut subres = *sigma;
+                subres.mul_assign(&beta, ctx);
+                subres.add_assign(variable, ctx);
+                subres.add_assign(&gamma, ctx);
+                lhs.mul_assign(&subres, ctx);
+            }
+
+            let mut rhs = *rhs;
+            for (non_res, variable) in non_residues.iter().zip(variables.iter()) {
+                // numerator is w + beta * non_res * x + gamma
+                let mut subres = z;
+                subres.mul_assign(&SymbolicExt::constant(*non_res, ctx), ctx);
+                subres.mul_assign(&beta, ctx);
+                subres.add_assign(variable, ctx);
+                subres.add_assign(&gamma, ctx);
+                rhs.mul_assign(&subres, ctx);
+            }
+
+            let mut contribution = lhs;
+            contribution.sub_assign(&rhs, ctx);
+            contribution.mul_assign(alpha, ctx);
+
+            t_accumulator.add_assign(&contribution, ctx);
+        }
+        assert_eq!(challenges_it.len(), 0, "must exhaust all the challenges");
+
+        let mut t_from_chunks = SymbolicExt::zero(ctx);
+        let mut pow = SymbolicExt::one(ctx);
+        for el in quotient_chunks.iter() {
+            let mut tmp = *el;
+            tmp.mul_assign(&pow, ctx);
+            pow.mul_assign(&z_in_domain_size, ctx);
+            t_from_chunks.add_assign(&tmp, ctx);
+        }
+        t_from_chunks.mul_assign(&vanishing_at_z, ctx);
+
+        let mut quotient_identity = t_accumulator;
+        quotient_identity.sub_assign(&t_from_chunks, ctx);
+
+        Self {
+            tape,
+            lookup_sumcheck,
+            quotient_identity,
+        }
+    }
+
+    /// Places the challenges and openings into the inputs of the tape
+    pub fn inputs<EXT: FieldExtension<2, BaseField = F>>(
+        challenges: [ExtensionField<F, 2, EXT>; NUM_CHALLENGE_INPUTS],
+        values_at_z: &[ExtensionField<F, 2, EXT>],
+        values_at_z_omega: &[ExtensionField<F, 2, EXT>],
+        values_at_0: &[ExtensionField<F, 2, EXT>],
+    ) -> Vec<ExtensionField<F, 2, EXT>> {
+        let mut inputs = challenges.to_vec();
+        inputs.extend_from_slice(values_at_z);
+        inputs.extend_from_slice(values_at_z_omega);
+        inputs.extend_from_slice(values_at_0);
+
+        inputs
+    }
+
+    /// Runs the checks over the given inputs
+    pub fn holds<EXT: FieldExtension<2, BaseField = F>>(
+        &self,
+        inputs: &[ExtensionField<F, 2, EXT>],
+    ) -> bool {
+        let values = self.tape.evaluate(inputs);
+        let is_zero = |el: &SymbolicExt<F>| {
+            ExpressionTape::value_of(el, &values) == ExtensionField::<F, 2, EXT>::ZERO
+        };
+
+        self.lookup_sumcheck.iter().all(is_zero) && is_zero(&self.quotient_identity)
+    }
+}
@@ -0,0 +1,12 @@
+//! Generation of Solidity verifiers for proofs committed with Keccak256 over Goldilocks.
+//!
+//! The generated contract replays the `Keccak256Transcript`, checks constraints at `z` using
+//! the gate evaluators of the verifier run over symbolic values, and then checks FRI queries.
+//! Proofs are passed to it as a flat array of words, see `calldata::CalldataLayout`
+
+use super::*;
+
+pub mod calldata;
+pub mod constraints;
+pub mod solidity;
+pub mod symbolic;
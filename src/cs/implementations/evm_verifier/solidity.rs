@@ -0,0 +1,1467 @@
+use super::calldata::CalldataLayout;
+use super::constraints::{ConstraintsAtZ, NUM_CHALLENGE_INPUTS};
+use super::symbolic::{SymbolicExt, TapeOp};
+use super::*;
+
+use crate::cs::implementations::fri::precompute_interpolation_steps;
+use crate::cs::implementations::proof_layout::ProofLayout;
+use crate::cs::implementations::prover::ProofConfig;
+use crate::cs::implementations::utils::domain_generator_for_size;
+use crate::cs::implementations::verifier::{VerificationError, VerificationKey, Verifier};
+use crate::field::FieldExtension;
+use sha3::Keccak256;
+use std::fmt::Write;
+
+/// Generates a Solidity contract that verifies proofs for the verification key. Proofs must be
+/// made with `Keccak256` tree hasher, `Keccak256Transcript` and `Keccak256` PoW, using the same
+/// proof config, and passed to `verify(uint256[])` encoded by `encode_proof_calldata`.
+///
+/// Gate constraints are emitted as straight-line code, so the size of the contract grows with
+/// the number and complexity of the gates
+pub fn generate_solidity_verifier<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
+    contract_name: &str,
+    verifier: &Verifier<F, EXT>,
+    vk: &VerificationKey<F, Keccak256>,
+    proof_config: &ProofConfig,
+) -> Result<String, VerificationError> {
+    verifier.check_verification_key(vk)?;
+    let layout = CalldataLayout::new(verifier, &vk.fixed_parameters, proof_config)?;
+    let constraints = ConstraintsAtZ::new(verifier, &vk.fixed_parameters, proof_config);
+
+    let mut out = String::new();
+    writeln!(out, "// SPDX-License-Identifier: MIT OR Apache-2.0").unwrap();
+    writeln!(out, "pragma solidity ^0.8.19;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "/// @notice Verifier of boojum proofs for a single verification key. Generated, do not edit"
+    )
+    .unwrap();
+    writeln!(out, "contract {} {{", contract_name).unwrap();
+    write_constants(&mut out, verifier, vk, &layout, &constraints);
+    out.push_str(STATIC_PART);
+    write_base_oracle_simulation(&mut out, verifier, vk, proof_config, &layout);
+    write_constraints_at_z(&mut out, &constraints);
+    writeln!(out, "}}").unwrap();
+
+    Ok(out)
+}
+
+fn hex_of_u64s<F: SmallField>(elements: &[F]) -> String {
+    elements
+        .iter()
+        .map(|el| format!("{:016x}", el.as_u64_reduced()))
+        .collect()
+}
+
+fn write_constants<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
+    out: &mut String,
+    verifier: &Verifier<F, EXT>,
+    vk: &VerificationKey<F, Keccak256>,
+    layout: &CalldataLayout,
+    constraints: &ConstraintsAtZ<F>,
+) {
+    let lde_domain_size =
+        vk.fixed_parameters.domain_size * vk.fixed_parameters.fri_lde_factor as u64;
+    let lde_domain_log2 = lde_domain_size.trailing_zeros() as usize;
+
+    let mut omegas = vec![];
+    let mut omegas_inversed = vec![];
+    for i in 0..=lde_domain_log2 {
+        let omega = domain_generator_for_size::<F>(1u64 << i);
+        omegas.push(omega);
+        omegas_inversed.push(omega.inverse().unwrap());
+    }
+    let max_interpolation_degree_log2 = layout.fri_schedule.iter().copied().max().unwrap();
+    let interpolation_steps =
+        precompute_interpolation_steps(max_interpolation_degree_log2, &omegas_inversed);
+
+    let mut fri_schedule = 0u128;
+    for (idx, interpolation_degree_log2) in layout.fri_schedule.iter().enumerate() {
+        assert!(*interpolation_degree_log2 < 256);
+        fri_schedule |= (*interpolation_degree_log2 as u128) << (8 * idx);
+    }
+
+    let max_leaf_size = [
+        layout.witness_leaf_size,
+        layout.stage_2_leaf_size,
+        layout.quotient_leaf_size,
+        layout.setup_leaf_size,
+        2 << max_interpolation_degree_log2,
+    ]
+    .into_iter()
+    .max()
+    .unwrap();
+
+    // everything that goes into the transcript, it's more than enough for the buffer
+    let num_values = layout.num_values_at_z + layout.num_values_at_z_omega + layout.num_values_at_0;
+    let transcript_capacity = 32 * layout.cap_size * (4 + layout.fri_schedule.len())
+        + 8 * (layout.num_public_inputs + 2 * num_values + 2 * layout.final_degree + 2);
+
+    let mut num_pow_seed_challenges = 256 / F::CHAR_BITS;
+    if num_pow_seed_challenges % F::CHAR_BITS != 0 {
+        num_pow_seed_challenges += 1;
+    }
+
+    let setup_cap: String = vk
+        .setup_merkle_tree_cap
+        .iter()
+        .flat_map(|el| el.iter().map(|byte| format!("{:02x}", byte)))
+        .collect();
+
+    let constants: Vec<(&str, String)> = vec![
+        ("P", format!("{}", F::CHAR)),
+        (
+            "NON_RESIDUE",
+            format!("{}", EXT::non_residue().as_u64_reduced()),
+        ),
+        (
+            "MULTIPLICATIVE_GENERATOR",
+            format!("{}", F::multiplicative_generator().as_u64_reduced()),
+        ),
+        (
+            "MULTIPLICATIVE_GENERATOR_INVERSE",
+            format!(
+                "{}",
+                F::multiplicative_generator()
+                    .inverse()
+                    .unwrap()
+                    .as_u64_reduced()
+            ),
+        ),
+        (
+            "OMEGA",
+            format!(
+                "{}",
+                domain_generator_for_size::<F>(vk.fixed_parameters.domain_size).as_u64_reduced()
+            ),
+        ),
+        ("CAP_SIZE", format!("{}", layout.cap_size)),
+        ("NUM_PUBLIC_INPUTS", format!("{}", layout.num_public_inputs)),
+        ("NUM_VALUES", format!("{}", num_values)),
+        (
+            "PUBLIC_INPUTS_OFFSET",
+            format!("{}", layout.public_inputs_offset),
+        ),
+        (
+            "WITNESS_CAP_OFFSET",
+            format!("{}", layout.witness_cap_offset),
+        ),
+        (
+            "STAGE_2_CAP_OFFSET",
+            format!("{}", layout.stage_2_cap_offset),
+        ),
+        (
+            "QUOTIENT_CAP_OFFSET",
+            format!("{}", layout.quotient_cap_offset),
+        ),
+        ("VALUES_OFFSET", format!("{}", layout.values_offset)),
+        (
+            "FRI_BASE_CAP_OFFSET",
+            format!("{}", layout.fri_base_cap_offset),
+        ),
+        (
+            "FRI_INTERMEDIATE_CAPS_OFFSET",
+            format!("{}", layout.fri_intermediate_caps_offset),
+        ),
+        (
+            "FINAL_MONOMIALS_OFFSET",
+            format!("{}", layout.final_monomials_offset),
+        ),
+        ("FINAL_DEGREE", format!("{}", layout.final_degree)),
+        (
+            "POW_CHALLENGE_OFFSET",
+            format!("{}", layout.pow_challenge_offset),
+        ),
+        ("POW_BITS", format!("{}", layout.pow_bits)),
+        (
+            "NUM_POW_SEED_CHALLENGES",
+            format!("{}", num_pow_seed_challenges),
+        ),
+        ("QUERIES_OFFSET", format!("{}", layout.queries_offset)),
+        ("QUERY_SIZE", format!("{}", layout.query_size)),
+        ("NUM_QUERIES", format!("{}", layout.num_queries)),
+        ("PROOF_LENGTH", format!("{}", layout.total_len)),
+        (
+            "WITNESS_QUERY_OFFSET",
+            format!("{}", layout.witness_query_offset),
+        ),
+        (
+            "STAGE_2_QUERY_OFFSET",
+            format!("{}", layout.stage_2_query_offset),
+        ),
+        (
+            "QUOTIENT_QUERY_OFFSET",
+            format!("{}", layout.quotient_query_offset),
+        ),
+        (
+            "SETUP_QUERY_OFFSET",
+            format!("{}", layout.setup_query_offset),
+        ),
+        (
+            "FRI_QUERIES_OFFSET",
+            format!("{}", layout.fri_queries_offset),
+        ),
+        ("WITNESS_LEAF_SIZE", format!("{}", layout.witness_leaf_size)),
+        ("STAGE_2_LEAF_SIZE", format!("{}", layout.stage_2_leaf_size)),
+        (
+            "QUOTIENT_LEAF_SIZE",
+            format!("{}", layout.quotient_leaf_size),
+        ),
+        ("SETUP_LEAF_SIZE", format!("{}", layout.setup_leaf_size)),
+        ("MAX_LEAF_SIZE", format!("{}", max_leaf_size)),
+        ("BASE_ORACLE_DEPTH", format!("{}", layout.base_oracle_depth)),
+        ("LDE_DOMAIN_LOG2", format!("{}", lde_domain_log2)),
+        ("NUM_FRI_STEPS", format!("{}", layout.fri_schedule.len())),
+        ("FRI_SCHEDULE", format!("0x{:x}", fri_schedule)),
+        (
+            "TOTAL_FOLDING_CHALLENGES",
+            format!("{}", layout.fri_schedule.iter().sum::<usize>()),
+        ),
+        (
+            "MAX_FOLDING_DEGREE",
+            format!("{}", 1 << max_interpolation_degree_log2),
+        ),
+        ("TRANSCRIPT_CAPACITY", format!("{}", transcript_capacity)),
+        (
+            "NUM_CONSTRAINT_INPUTS",
+            format!("{}", constraints.tape.num_inputs),
+        ),
+    ];
+
+    for (name, value) in constants.into_iter() {
+        writeln!(out, "    uint256 internal constant {} = {};", name, value).unwrap();
+    }
+    writeln!(
+        out,
+        "    bool internal constant HAS_LOOKUP = {};",
+        verifier.lookup_parameters.lookup_is_allowed()
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    bytes internal constant SETUP_CAP = hex\"{}\";",
+        setup_cap
+    )
+    .unwrap();
+    // roots of unity of sizes 2^0, 2^1, ... up to the size of the LDE domain
+    writeln!(
+        out,
+        "    bytes internal constant OMEGAS = hex\"{}\";",
+        hex_of_u64s(&omegas)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    bytes internal constant OMEGAS_INVERSED = hex\"{}\";",
+        hex_of_u64s(&omegas_inversed)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    bytes internal constant INTERPOLATION_STEPS = hex\"{}\";",
+        hex_of_u64s(&interpolation_steps)
+    )
+    .unwrap();
+}
+
+// Simulates the value of the base FRI oracle at the queried point from the values of the base
+// oracles, in the same order as the verifier does it. Layout of the leafs is fixed by the verification
+// key, so it's generated as a sequence of runs over contiguous parts of the leafs
+fn write_base_oracle_simulation<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
+    out: &mut String,
+    verifier: &Verifier<F, EXT>,
+    vk: &VerificationKey<F, Keccak256>,
+    proof_config: &ProofConfig,
+    layout: &CalldataLayout,
+) {
+    let proof_layout = ProofLayout::new(verifier, &vk.fixed_parameters, proof_config);
+    let num_variables = proof_layout.variables.len();
+    let num_witnesses = proof_layout.witnesses.len();
+    let num_constants = proof_layout.constants.len();
+    let num_sigmas = proof_layout.sigmas.len();
+    let num_partial_products = proof_layout.copy_permutation_partial_products.len();
+    let num_multiplicities = proof_layout.lookup_multiplicities.len();
+    let num_witness_encodings = proof_layout.lookup_witness_encodings.len();
+    let num_multiplicity_encodings = proof_layout.lookup_multiplicity_encodings.len();
+    let num_table_setups = proof_layout.lookup_table_setup.len();
+    let num_quotient_chunks = proof_layout.quotient_chunks.len();
+
+    let witness = "WITNESS_QUERY_OFFSET";
+    let stage_2 = "STAGE_2_QUERY_OFFSET";
+    let quotient = "QUOTIENT_QUERY_OFFSET";
+    let setup = "SETUP_QUERY_OFFSET";
+
+    let lookup_witness_encodings_offset = 2 + 2 * num_partial_products;
+    let lookup_multiplicity_encodings_offset =
+        lookup_witness_encodings_offset + 2 * num_witness_encodings;
+
+    // (oracle, offset in the leaf, number of polys, polys are over extension)
+    let at_z = [
+        (witness, 0, num_variables, false),
+        (witness, num_variables, num_witnesses, false),
+        (setup, num_sigmas, num_constants, false),
+        (setup, 0, num_sigmas, false),
+        (stage_2, 0, 1, true),
+        (stage_2, 2, num_partial_products, true),
+        (
+            witness,
+            num_variables + num_witnesses,
+            num_multiplicities,
+            false,
+        ),
+        (
+            stage_2,
+            lookup_witness_encodings_offset,
+            num_witness_encodings,
+            true,
+        ),
+        (
+            stage_2,
+            lookup_multiplicity_encodings_offset,
+            num_multiplicity_encodings,
+            true,
+        ),
+        (setup, num_sigmas + num_constants, num_table_setups, false),
+        (quotient, 0, num_quotient_chunks, true),
+    ];
+    let at_z_omega = [(stage_2, 0, 1, true)];
+    let at_zero = [
+        (
+            stage_2,
+            lookup_witness_encodings_offset,
+            num_witness_encodings,
+            true,
+        ),
+        (
+            stage_2,
+            lookup_multiplicity_encodings_offset,
+            num_multiplicity_encodings,
+            true,
+        ),
+    ];
+
+    writeln!(out).unwrap();
+    writeln!(out, "    function _simulateBaseOracle(uint256[] calldata proof, Challenges memory challenges, uint256 queryStart, uint256 x) private pure returns (uint256 simulated) {{").unwrap();
+    writeln!(
+        out,
+        "        Quotiening memory q = Quotiening(0, 1, challenges.quotiening);"
+    )
+    .unwrap();
+
+    let mut value_idx = 0;
+    let points = [
+        (&at_z[..], "challenges.z"),
+        (&at_z_omega[..], "challenges.zOmega"),
+        (&at_zero[..], "0"),
+    ];
+    for (runs, point) in points.into_iter() {
+        let mut is_empty = true;
+        for (oracle, offset, num_polys, is_extension) in runs.iter() {
+            if *num_polys == 0 {
+                continue;
+            }
+            writeln!(
+                out,
+                "        _accumulate(proof, q, queryStart + {} + {}, {}, {}, VALUES_OFFSET + {});",
+                oracle,
+                offset,
+                num_polys,
+                is_extension,
+                2 * value_idx
+            )
+            .unwrap();
+            value_idx += num_polys;
+            is_empty = false;
+        }
+        if is_empty == false {
+            writeln!(
+                out,
+                "        simulated = _eAdd(simulated, _eMul(q.sum, _eInv(_eSub(x, {}))));",
+                point
+            )
+            .unwrap();
+            writeln!(out, "        q.sum = 0;").unwrap();
+        }
+    }
+    assert_eq!(
+        value_idx,
+        layout.num_values_at_z + layout.num_values_at_z_omega + layout.num_values_at_0
+    );
+
+    // public inputs are opened at the rows they are placed at, grouped by row in the same way as the verifier does
+    let omega = domain_generator_for_size::<F>(vk.fixed_parameters.domain_size);
+    let mut opening_tuples: Vec<(F, Vec<(usize, usize)>)> = vec![];
+    for (idx, (column, row)) in vk
+        .fixed_parameters
+        .public_inputs_locations
+        .iter()
+        .copied()
+        .enumerate()
+    {
+        let open_at = omega.pow_u64(row as u64);
+        if let Some(pos) = opening_tuples.iter().position(|el| el.0 == open_at) {
+            opening_tuples[pos].1.push((column, idx));
+        } else {
+            opening_tuples.push((open_at, vec![(column, idx)]));
+        }
+    }
+    for (open_at, set) in opening_tuples.iter() {
+        for (column, idx) in set.iter() {
+            writeln!(out, "        _accumulateOne(q, proof[queryStart + WITNESS_QUERY_OFFSET + {}], proof[PUBLIC_INPUTS_OFFSET + {}]);", column, idx).unwrap();
+        }
+        writeln!(
+            out,
+            "        simulated = _eAdd(simulated, _eMul(q.sum, _eInv(_eSub(x, {}))));",
+            open_at.as_u64_reduced()
+        )
+        .unwrap();
+        writeln!(out, "        q.sum = 0;").unwrap();
+    }
+    writeln!(out, "    }}").unwrap();
+}
+
+fn write_constraints_at_z<F: SmallField>(out: &mut String, constraints: &ConstraintsAtZ<F>) {
+    let tape = &constraints.tape;
+
+    // inputs are read in place, and every other operation gets a slot in memory
+    let mut slots = vec![None; tape.ops.len()];
+    let mut num_slots = 0;
+    for (idx, op) in tape.ops.iter().enumerate() {
+        if let TapeOp::Input(..) = op {
+            continue;
+        }
+        slots[idx] = Some(num_slots);
+        num_slots += 1;
+    }
+    let operand = |el: &SymbolicExt<F>| match el {
+        SymbolicExt::Constant(constant) => format!("{}", constant.as_u64_reduced()),
+        SymbolicExt::Value(idx) => match tape.ops[*idx] {
+            TapeOp::Input(input) => format!("inp[{}]", input),
+            _ => format!("v[{}]", slots[*idx].unwrap()),
+        },
+    };
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    // inputs are {} challenges, then values at z, at z * omega and at 0",
+        NUM_CHALLENGE_INPUTS
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    function _checkConstraintsAtZ(uint256[] memory inp) private pure {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        uint256[] memory v = new uint256[]({});",
+        num_slots.max(1)
+    )
+    .unwrap();
+    for (idx, op) in tape.ops.iter().enumerate() {
+        let expression = match op {
+            TapeOp::Input(..) => continue,
+            TapeOp::Add(a, b) => format!("_eAdd({}, {})", operand(a), operand(b)),
+            TapeOp::Sub(a, b) => format!("_eSub({}, {})", operand(a), operand(b)),
+            TapeOp::Mul(a, b) => format!("_eMul({}, {})", operand(a), operand(b)),
+            TapeOp::Negate(a) => format!("_eSub(0, {})", operand(a)),
+            TapeOp::Inverse(a) => format!("_eInv({})", operand(a)),
+        };
+        writeln!(out, "        v[{}] = {};", slots[idx].unwrap(), expression).unwrap();
+    }
+    if let Some(lookup_sumcheck) = constraints.lookup_sumcheck.as_ref() {
+        writeln!(
+            out,
+            "        if ({} != 0) revert LookupSumcheckFailed();",
+            operand(lookup_sumcheck)
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "        if ({} != 0) revert QuotientIdentityFailed();",
+        operand(&constraints.quotient_identity)
+    )
+    .unwrap();
+    writeln!(out, "    }}").unwrap();
+}
+
+// Everything that doesn't depend on the verification key beyond the constants above
+const STATIC_PART: &str = r#"
+    uint256 internal constant MASK_64 = 0xffffffffffffffff;
+
+    // oracles in `InvalidMerklePath`: 0 is witness, 1 is stage 2, 2 is quotient,
+    // 3 is setup and 4 + i is the i-th FRI oracle
+    error ProofLengthMismatch(uint256 expected, uint256 got);
+    error NonCanonicalFieldElement();
+    error LookupSumcheckFailed();
+    error QuotientIdentityFailed();
+    error InvalidPoW();
+    error InvalidMerklePath(uint256 queryIdx, uint256 oracle);
+    error FriFoldingMismatch(uint256 queryIdx, uint256 friStep);
+    error FriFinalMonomialsMismatch(uint256 queryIdx);
+
+    // Same as `Keccak256Transcript`: the seed is followed by the buffer of absorbed bytes in memory,
+    // and challenges are taken as little-endian 8-byte chunks of the seed
+    struct Transcript {
+        uint256 state;
+        uint256 len;
+        uint256 available;
+        bool seeded;
+    }
+
+    // Elements of the quadratic extension are packed as c0 | (c1 << 64)
+    struct Challenges {
+        uint256 z;
+        uint256 zOmega;
+        uint256 quotiening;
+        uint256[] folding;
+        uint256[] foldingBuffer;
+        bytes setupCap;
+        bytes omegas;
+        bytes omegasInversed;
+        bytes interpolationSteps;
+        uint256 scratch;
+        uint256 bits;
+        uint256 numBits;
+    }
+
+    struct Quotiening {
+        uint256 sum;
+        uint256 power;
+        uint256 challenge;
+    }
+
+    struct FriState {
+        uint256 folded;
+        uint256 index;
+        uint256 cosetInverse;
+        uint256 point;
+        uint256 ptr;
+        uint256 depth;
+        uint256 skippedBits;
+        uint256 challengeOffset;
+    }
+
+    /// @notice Verifies a proof encoded by `encode_proof_calldata`. Public inputs are the
+    /// first `NUM_PUBLIC_INPUTS` words of the proof. Reverts if the proof is not valid
+    function verify(uint256[] calldata proof) external pure returns (bool) {
+        if (proof.length != PROOF_LENGTH) revert ProofLengthMismatch(PROOF_LENGTH, proof.length);
+
+        Transcript memory transcript = _newTranscript();
+        Challenges memory challenges = _checkOpeningsAtZ(proof, transcript);
+        _commitFriOracles(proof, transcript, challenges);
+        _checkPoW(proof, transcript);
+
+        for (uint256 queryIdx = 0; queryIdx < NUM_QUERIES; queryIdx++) {
+            _verifyQuery(proof, transcript, challenges, queryIdx);
+        }
+
+        return true;
+    }
+
+    function _checkOpeningsAtZ(uint256[] calldata proof, Transcript memory transcript)
+        private
+        pure
+        returns (Challenges memory challenges)
+    {
+        challenges.setupCap = SETUP_CAP;
+        for (uint256 i = 0; i < CAP_SIZE; i++) {
+            _absorbHash(transcript, _readBytes32(challenges.setupCap, i));
+        }
+        for (uint256 i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            _absorbField(transcript, proof[PUBLIC_INPUTS_OFFSET + i]);
+        }
+        _absorbCap(proof, transcript, WITNESS_CAP_OFFSET);
+
+        uint256[] memory inputs = new uint256[](NUM_CONSTRAINT_INPUTS);
+        // beta and gamma for copy-permutation, then for lookup
+        inputs[0] = _drawExt(transcript);
+        inputs[1] = _drawExt(transcript);
+        if (HAS_LOOKUP) {
+            inputs[2] = _drawExt(transcript);
+            inputs[3] = _drawExt(transcript);
+        }
+        _absorbCap(proof, transcript, STAGE_2_CAP_OFFSET);
+        // alpha
+        inputs[4] = _drawExt(transcript);
+        _absorbCap(proof, transcript, QUOTIENT_CAP_OFFSET);
+        // z
+        inputs[5] = _drawExt(transcript);
+
+        for (uint256 i = 0; i < NUM_VALUES; i++) {
+            _absorbField(transcript, proof[VALUES_OFFSET + 2 * i]);
+            _absorbField(transcript, proof[VALUES_OFFSET + 2 * i + 1]);
+            inputs[6 + i] = _ext(proof, VALUES_OFFSET + 2 * i);
+        }
+        _checkConstraintsAtZ(inputs);
+
+        challenges.z = inputs[5];
+        challenges.zOmega = _eMulBase(inputs[5], OMEGA);
+        challenges.quotiening = _drawExt(transcript);
+        challenges.omegas = OMEGAS;
+        challenges.omegasInversed = OMEGAS_INVERSED;
+        challenges.interpolationSteps = INTERPOLATION_STEPS;
+        challenges.foldingBuffer = new uint256[](MAX_FOLDING_DEGREE);
+        bytes memory scratch = new bytes(8 * MAX_LEAF_SIZE + 32);
+        uint256 scratchPtr;
+        assembly {
+            scratchPtr := add(scratch, 32)
+        }
+        challenges.scratch = scratchPtr;
+    }
+
+    function _commitFriOracles(uint256[] calldata proof, Transcript memory transcript, Challenges memory challenges)
+        private
+        pure
+    {
+        challenges.folding = new uint256[](TOTAL_FOLDING_CHALLENGES);
+        uint256 offset = 0;
+        for (uint256 step = 0; step < NUM_FRI_STEPS; step++) {
+            _absorbCap(proof, transcript, _friCapOffset(step));
+            uint256 current = _drawExt(transcript);
+            uint256 interpolationDegreeLog2 = _friStepLog2(step);
+            for (uint256 i = 0; i < interpolationDegreeLog2; i++) {
+                challenges.folding[offset + i] = current;
+                current = _eMul(current, current);
+            }
+            offset += interpolationDegreeLog2;
+        }
+        for (uint256 i = 0; i < 2 * FINAL_DEGREE; i++) {
+            _absorbField(transcript, proof[FINAL_MONOMIALS_OFFSET + i]);
+        }
+    }
+
+    function _checkPoW(uint256[] calldata proof, Transcript memory transcript) private pure {
+        if (POW_BITS == 0) return;
+
+        uint256 challenge = proof[POW_CHALLENGE_OFFSET];
+        if (challenge > MASK_64) revert InvalidPoW();
+
+        bytes memory seed = new bytes(8 * NUM_POW_SEED_CHALLENGES + 8 + 32);
+        uint256 ptr;
+        assembly {
+            ptr := add(seed, 32)
+        }
+        for (uint256 i = 0; i < NUM_POW_SEED_CHALLENGES; i++) {
+            _storeLe64(ptr + 8 * i, _drawField(transcript));
+        }
+        _storeLe64(ptr + 8 * NUM_POW_SEED_CHALLENGES, challenge);
+        uint256 len = 8 * NUM_POW_SEED_CHALLENGES + 8;
+        bytes32 h;
+        assembly {
+            h := keccak256(ptr, len)
+        }
+        if ((_reverse64(uint256(h) >> 192) & ((1 << POW_BITS) - 1)) != 0) revert InvalidPoW();
+
+        _absorbField(transcript, challenge & 0xffffffff);
+        _absorbField(transcript, challenge >> 32);
+    }
+
+    function _verifyQuery(
+        uint256[] calldata proof,
+        Transcript memory transcript,
+        Challenges memory challenges,
+        uint256 queryIdx
+    ) private pure {
+        uint256 index = _drawQueryIndex(transcript, challenges);
+        uint256 queryStart = QUERIES_OFFSET + queryIdx * QUERY_SIZE;
+
+        // index of the leaf in the tree is a bitreversed power of the domain generator
+        uint256 x = MULTIPLICATIVE_GENERATOR;
+        for (uint256 i = 0; i < LDE_DOMAIN_LOG2; i++) {
+            if (((index >> i) & 1) == 1) {
+                x = mulmod(x, _readU64(challenges.omegas, i + 1), P);
+            }
+        }
+
+        _checkBaseOracles(proof, challenges, queryIdx, queryStart, index);
+        uint256 simulated = _simulateBaseOracle(proof, challenges, queryStart, x);
+
+        FriState memory state = FriState({
+            folded: simulated,
+            index: index,
+            cosetInverse: MULTIPLICATIVE_GENERATOR_INVERSE,
+            point: x,
+            ptr: queryStart + FRI_QUERIES_OFFSET,
+            depth: BASE_ORACLE_DEPTH,
+            skippedBits: 0,
+            challengeOffset: 0
+        });
+        for (uint256 step = 0; step < NUM_FRI_STEPS; step++) {
+            _friStep(proof, challenges, state, queryIdx, step, index);
+        }
+
+        // horner rule over the final monomials
+        uint256 result = 0;
+        for (uint256 i = FINAL_DEGREE; i > 0; i--) {
+            uint256 coeff = proof[FINAL_MONOMIALS_OFFSET + i - 1] | (proof[FINAL_MONOMIALS_OFFSET + FINAL_DEGREE + i - 1] << 64);
+            result = _eAdd(_eMulBase(result, state.point), coeff);
+        }
+        if (result != state.folded) revert FriFinalMonomialsMismatch(queryIdx);
+    }
+
+    function _checkBaseOracles(
+        uint256[] calldata proof,
+        Challenges memory challenges,
+        uint256 queryIdx,
+        uint256 queryStart,
+        uint256 index
+    ) private pure {
+        bytes32 root;
+        uint256 capIdx;
+        (root, capIdx) = _merkleRoot(proof, queryStart + WITNESS_QUERY_OFFSET, WITNESS_LEAF_SIZE, BASE_ORACLE_DEPTH, index, challenges.scratch);
+        if (root != bytes32(proof[WITNESS_CAP_OFFSET + capIdx])) revert InvalidMerklePath(queryIdx, 0);
+        (root, capIdx) = _merkleRoot(proof, queryStart + STAGE_2_QUERY_OFFSET, STAGE_2_LEAF_SIZE, BASE_ORACLE_DEPTH, index, challenges.scratch);
+        if (root != bytes32(proof[STAGE_2_CAP_OFFSET + capIdx])) revert InvalidMerklePath(queryIdx, 1);
+        (root, capIdx) = _merkleRoot(proof, queryStart + QUOTIENT_QUERY_OFFSET, QUOTIENT_LEAF_SIZE, BASE_ORACLE_DEPTH, index, challenges.scratch);
+        if (root != bytes32(proof[QUOTIENT_CAP_OFFSET + capIdx])) revert InvalidMerklePath(queryIdx, 2);
+        (root, capIdx) = _merkleRoot(proof, queryStart + SETUP_QUERY_OFFSET, SETUP_LEAF_SIZE, BASE_ORACLE_DEPTH, index, challenges.scratch);
+        if (root != _readBytes32(challenges.setupCap, capIdx)) revert InvalidMerklePath(queryIdx, 3);
+    }
+
+    function _friStep(
+        uint256[] calldata proof,
+        Challenges memory challenges,
+        FriState memory state,
+        uint256 queryIdx,
+        uint256 step,
+        uint256 queryIndex
+    ) private pure {
+        uint256 interpolationDegreeLog2 = _friStepLog2(step);
+        uint256 degree = 1 << interpolationDegreeLog2;
+        uint256 leaf = state.ptr;
+        uint256 treeIdx = state.index >> interpolationDegreeLog2;
+        state.depth -= interpolationDegreeLog2;
+
+        (bytes32 root, uint256 capIdx) = _merkleRoot(proof, leaf, 2 * degree, state.depth, treeIdx, challenges.scratch);
+        if (root != bytes32(proof[_friCapOffset(step) + capIdx])) revert InvalidMerklePath(queryIdx, 4 + step);
+
+        // leaf contains the value folded so far
+        uint256 inLeaf = state.index & (degree - 1);
+        if (state.folded != (proof[leaf + inLeaf] | (proof[leaf + degree + inLeaf] << 64))) {
+            revert FriFoldingMismatch(queryIdx, step);
+        }
+
+        uint256[] memory elements = challenges.foldingBuffer;
+        for (uint256 i = 0; i < degree; i++) {
+            elements[i] = proof[leaf + i] | (proof[leaf + degree + i] << 64);
+        }
+
+        uint256 basePow = _powerChunk(challenges, queryIndex, state.skippedBits, interpolationDegreeLog2);
+        uint256 cosetInverse = state.cosetInverse;
+        for (uint256 round = 0; round < interpolationDegreeLog2; round++) {
+            uint256 challenge = challenges.folding[state.challengeOffset + round];
+            uint256 half = degree >> (round + 1);
+            for (uint256 i = 0; i < half; i++) {
+                uint256 a = elements[2 * i];
+                uint256 b = elements[2 * i + 1];
+                uint256 pow = mulmod(mulmod(basePow, _readU64(challenges.interpolationSteps, i), P), cosetInverse, P);
+                elements[i] = _eAdd(_eAdd(a, b), _eMulBase(_eMul(_eSub(a, b), challenge), pow));
+            }
+            basePow = mulmod(basePow, basePow, P);
+            cosetInverse = mulmod(cosetInverse, cosetInverse, P);
+            state.point = mulmod(state.point, state.point, P);
+        }
+
+        state.folded = elements[0];
+        state.cosetInverse = cosetInverse;
+        state.index = treeIdx;
+        state.ptr = leaf + 2 * degree + state.depth;
+        state.skippedBits += interpolationDegreeLog2;
+        state.challengeOffset += interpolationDegreeLog2;
+    }
+
+    // product of inverses of the roots of unity selected by the bits of the query index,
+    // skipping the ones that are consumed by the folding
+    function _powerChunk(
+        Challenges memory challenges,
+        uint256 queryIndex,
+        uint256 skippedBits,
+        uint256 interpolationDegreeLog2
+    ) private pure returns (uint256 result) {
+        result = 1;
+        for (uint256 j = interpolationDegreeLog2; skippedBits + j < LDE_DOMAIN_LOG2; j++) {
+            if (((queryIndex >> (skippedBits + j)) & 1) == 1) {
+                result = mulmod(result, _readU64(challenges.omegasInversed, j + 1), P);
+            }
+        }
+    }
+
+    function _drawQueryIndex(Transcript memory transcript, Challenges memory challenges)
+        private
+        pure
+        returns (uint256 index)
+    {
+        while (challenges.numBits < LDE_DOMAIN_LOG2) {
+            challenges.bits |= _draw64(transcript) << challenges.numBits;
+            challenges.numBits += 64;
+        }
+        index = challenges.bits & ((1 << LDE_DOMAIN_LOG2) - 1);
+        challenges.bits >>= LDE_DOMAIN_LOG2;
+        challenges.numBits -= LDE_DOMAIN_LOG2;
+    }
+
+    function _friStepLog2(uint256 step) private pure returns (uint256) {
+        return (FRI_SCHEDULE >> (8 * step)) & 0xff;
+    }
+
+    function _friCapOffset(uint256 step) private pure returns (uint256) {
+        if (step == 0) return FRI_BASE_CAP_OFFSET;
+        return FRI_INTERMEDIATE_CAPS_OFFSET + (step - 1) * CAP_SIZE;
+    }
+
+    // Merkle tree
+
+    // hashes the leaf and the path that follows it, and returns the element of the cap it must be equal to
+    function _merkleRoot(
+        uint256[] calldata proof,
+        uint256 leafStart,
+        uint256 leafSize,
+        uint256 depth,
+        uint256 index,
+        uint256 scratch
+    ) private pure returns (bytes32 node, uint256 capIdx) {
+        for (uint256 i = 0; i < leafSize; i++) {
+            uint256 el = proof[leafStart + i];
+            if (el >= P) revert NonCanonicalFieldElement();
+            _storeLe64(scratch + 8 * i, el);
+        }
+        uint256 len = 8 * leafSize;
+        assembly {
+            node := keccak256(scratch, len)
+        }
+        uint256 pathStart = leafStart + leafSize;
+        for (uint256 i = 0; i < depth; i++) {
+            bytes32 sibling = bytes32(proof[pathStart + i]);
+            if ((index & 1) == 0) {
+                node = _hashNode(node, sibling);
+            } else {
+                node = _hashNode(sibling, node);
+            }
+            index >>= 1;
+        }
+        capIdx = index;
+    }
+
+    function _hashNode(bytes32 left, bytes32 right) private pure returns (bytes32 result) {
+        assembly {
+            mstore(0x00, left)
+            mstore(0x20, right)
+            result := keccak256(0x00, 0x40)
+        }
+    }
+
+    // Transcript
+
+    function _newTranscript() private pure returns (Transcript memory transcript) {
+        bytes memory region = new bytes(32 + TRANSCRIPT_CAPACITY + 32);
+        uint256 ptr;
+        assembly {
+            ptr := add(region, 32)
+        }
+        transcript.state = ptr;
+    }
+
+    function _absorbField(Transcript memory transcript, uint256 el) private pure {
+        if (el >= P) revert NonCanonicalFieldElement();
+        _storeLe64(transcript.state + 32 + transcript.len, el);
+        transcript.len += 8;
+    }
+
+    function _absorbHash(Transcript memory transcript, bytes32 h) private pure {
+        uint256 dst = transcript.state + 32 + transcript.len;
+        assembly {
+            mstore(dst, h)
+        }
+        transcript.len += 32;
+    }
+
+    function _absorbCap(uint256[] calldata proof, Transcript memory transcript, uint256 offset) private pure {
+        for (uint256 i = 0; i < CAP_SIZE; i++) {
+            _absorbHash(transcript, bytes32(proof[offset + i]));
+        }
+    }
+
+    // new seed is a hash of the previous seed (if any) and everything absorbed since then
+    function _reseed(Transcript memory transcript) private pure {
+        uint256 state = transcript.state;
+        uint256 len = transcript.len;
+        bytes32 h;
+        if (transcript.seeded) {
+            assembly {
+                h := keccak256(state, add(32, len))
+            }
+        } else {
+            assembly {
+                h := keccak256(add(state, 32), len)
+            }
+        }
+        assembly {
+            mstore(state, h)
+        }
+        transcript.seeded = true;
+        transcript.len = 0;
+        transcript.available = 32;
+    }
+
+    function _draw64(Transcript memory transcript) private pure returns (uint256) {
+        if (transcript.len != 0 || transcript.available == 0) {
+            _reseed(transcript);
+        }
+        uint256 state = transcript.state;
+        uint256 seed;
+        assembly {
+            seed := mload(state)
+        }
+        uint256 offset = 32 - transcript.available;
+        transcript.available -= 8;
+
+        return _reverse64((seed >> (8 * (24 - offset))) & MASK_64);
+    }
+
+    function _drawField(Transcript memory transcript) private pure returns (uint256) {
+        return _draw64(transcript) % P;
+    }
+
+    function _drawExt(Transcript memory transcript) private pure returns (uint256) {
+        uint256 c0 = _drawField(transcript);
+        uint256 c1 = _drawField(transcript);
+
+        return c0 | (c1 << 64);
+    }
+
+    // Encoding
+
+    function _reverse64(uint256 x) private pure returns (uint256) {
+        x = ((x & 0xff00ff00ff00ff00) >> 8) | ((x & 0x00ff00ff00ff00ff) << 8);
+        x = ((x & 0xffff0000ffff0000) >> 16) | ((x & 0x0000ffff0000ffff) << 16);
+        x = (x >> 32) | ((x & 0xffffffff) << 32);
+
+        return x;
+    }
+
+    // writes 32 bytes, with the value in little-endian in the first 8 of them
+    function _storeLe64(uint256 dst, uint256 x) private pure {
+        uint256 word = _reverse64(x) << 192;
+        assembly {
+            mstore(dst, word)
+        }
+    }
+
+    function _readU64(bytes memory data, uint256 idx) private pure returns (uint256 result) {
+        assembly {
+            result := shr(192, mload(add(add(data, 32), mul(idx, 8))))
+        }
+    }
+
+    function _readBytes32(bytes memory data, uint256 idx) private pure returns (bytes32 result) {
+        assembly {
+            result := mload(add(add(data, 32), mul(idx, 32)))
+        }
+    }
+
+    function _ext(uint256[] calldata proof, uint256 idx) private pure returns (uint256) {
+        return proof[idx] | (proof[idx + 1] << 64);
+    }
+
+    // Field arithmetic
+
+    function _fInv(uint256 x) private pure returns (uint256 result) {
+        result = 1;
+        uint256 e = P - 2;
+        while (e != 0) {
+            if ((e & 1) == 1) {
+                result = mulmod(result, x, P);
+            }
+            x = mulmod(x, x, P);
+            e >>= 1;
+        }
+    }
+
+    function _eAdd(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a & MASK_64, b & MASK_64, P) | (addmod(a >> 64, b >> 64, P) << 64);
+    }
+
+    function _eSub(uint256 a, uint256 b) private pure returns (uint256) {
+        return addmod(a & MASK_64, P - (b & MASK_64), P) | (addmod(a >> 64, P - (b >> 64), P) << 64);
+    }
+
+    function _eMul(uint256 a, uint256 b) private pure returns (uint256) {
+        uint256 a0 = a & MASK_64;
+        uint256 a1 = a >> 64;
+        uint256 b0 = b & MASK_64;
+        uint256 b1 = b >> 64;
+        uint256 c0 = (a0 * b0 + NON_RESIDUE * mulmod(a1, b1, P)) % P;
+        uint256 c1 = (a0 * b1 + a1 * b0) % P;
+
+        return c0 | (c1 << 64);
+    }
+
+    function _eMulBase(uint256 a, uint256 b) private pure returns (uint256) {
+        return mulmod(a & MASK_64, b, P) | (mulmod(a >> 64, b, P) << 64);
+    }
+
+    // zero for zero
+    function _eInv(uint256 a) private pure returns (uint256) {
+        uint256 a0 = a & MASK_64;
+        uint256 a1 = a >> 64;
+        uint256 norm = addmod(mulmod(a0, a0, P), P - mulmod(NON_RESIDUE, mulmod(a1, a1, P), P), P);
+        uint256 normInverse = _fInv(norm);
+
+        return mulmod(a0, normInverse, P) | (mulmod(P - a1, normInverse, P) << 64);
+    }
+
+    // Quotiening, sum of challenge powers times (f(x) - f(at)) over contiguous polys of a leaf
+
+    function _accumulate(
+        uint256[] calldata proof,
+        Quotiening memory q,
+        uint256 leafStart,
+        uint256 numPolys,
+        bool isExtension,
+        uint256 valuesStart
+    ) private pure {
+        for (uint256 i = 0; i < numPolys; i++) {
+            uint256 value = isExtension ? _ext(proof, leafStart + 2 * i) : proof[leafStart + i];
+            _accumulateOne(q, value, _ext(proof, valuesStart + 2 * i));
+        }
+    }
+
+    function _accumulateOne(Quotiening memory q, uint256 value, uint256 valueAt) private pure {
+        q.sum = _eAdd(q.sum, _eMul(q.power, _eSub(value, valueAt)));
+        q.power = _eMul(q.power, q.challenge);
+    }
+"#;
+
+#[cfg(test)]
+mod test {
+    use super::super::calldata::{encode_proof_calldata, proof_into_words};
+    use super::*;
+    use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::*;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::cs_builder_verifier::CsVerifierBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::pow::NoPow;
+    use crate::cs::implementations::proof::Proof;
+    use crate::cs::implementations::transcript::{
+        GoldilocksPoisedon2Transcript, Keccak256Transcript, Transcript,
+    };
+    use crate::cs::implementations::verifier::VerificationKey;
+    use crate::cs::oracle::TreeHasher;
+    use crate::cs::traits::cs::ConstraintSystem;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, LookupParameters, StaticToolboxHolder};
+    use crate::dag::CircuitResolverOpts;
+    use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+    use crate::field::{ExtensionField, Field, U64Representable};
+    use crate::implementations::poseidon2::Poseidon2Goldilocks;
+    use crate::worker::Worker;
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+    type Ext = GoldilocksExt2;
+
+    // replays the transcript of the verifier up to `z`
+    fn challenges_at_z<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output, TransciptParameters = ()>,
+    >(
+        verifier: &Verifier<F, Ext>,
+        vk: &VerificationKey<F, H>,
+        proof: &Proof<F, H, Ext>,
+    ) -> [ExtensionField<F, 2, Ext>; NUM_CHALLENGE_INPUTS] {
+        let mut transcript = TR::new(());
+        let draw = |transcript: &mut TR| {
+            ExtensionField::<F, 2, Ext>::from_coeff_in_base(
+                transcript.get_multiple_challenges_fixed::<2>(),
+            )
+        };
+
+        transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+        transcript.witness_field_elements(&proof.public_inputs);
+        transcript.witness_merkle_tree_cap(&proof.witness_oracle_cap);
+        let mut challenges = [ExtensionField::<F, 2, Ext>::ZERO; NUM_CHALLENGE_INPUTS];
+        challenges[0] = draw(&mut transcript);
+        challenges[1] = draw(&mut transcript);
+        if verifier.lookup_parameters.lookup_is_allowed() {
+            challenges[2] = draw(&mut transcript);
+            challenges[3] = draw(&mut transcript);
+        }
+        transcript.witness_merkle_tree_cap(&proof.stage_2_oracle_cap);
+        challenges[4] = draw(&mut transcript);
+        transcript.witness_merkle_tree_cap(&proof.quotient_oracle_cap);
+        challenges[5] = draw(&mut transcript);
+
+        challenges
+    }
+
+    #[test]
+    fn test_constraints_at_z_with_lookups() {
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let mut vk_file = std::fs::File::open("vk.json").unwrap();
+        let mut proof_file = std::fs::File::open("proof.json").unwrap();
+        let vk: VerificationKey<F, H> = serde_json::from_reader(&mut vk_file).unwrap();
+        let proof: Proof<F, H, Ext> = serde_json::from_reader(&mut proof_file).unwrap();
+
+        // same configuration as the one that produced the proof
+        type Poseidon2Gate = Poseidon2FlattenedGate<F, 8, 12, 4, Poseidon2Goldilocks>;
+
+        let builder_impl =
+            CsVerifierBuilder::<F, Ext>::new_from_parameters(vk.fixed_parameters.parameters);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = builder.allow_lookup(
+            LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseSpecializedColumns {
+                num_repetitions: 1,
+                share_constants: false,
+            },
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = Poseidon2Gate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<8>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<_, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+        let verifier = builder.build(());
+
+        let constraints = ConstraintsAtZ::new(&verifier, &vk.fixed_parameters, &proof.proof_config);
+        assert!(constraints.lookup_sumcheck.is_some());
+
+        let challenges =
+            challenges_at_z::<H, GoldilocksPoisedon2Transcript>(&verifier, &vk, &proof);
+        let mut inputs = ConstraintsAtZ::inputs(
+            challenges,
+            &proof.values_at_z,
+            &proof.values_at_z_omega,
+            &proof.values_at_0,
+        );
+        assert_eq!(inputs.len(), constraints.tape.num_inputs);
+        assert!(constraints.holds(&inputs));
+
+        let mut tampered = inputs.clone();
+        tampered[NUM_CHALLENGE_INPUTS].add_assign(&ExtensionField::ONE);
+        assert!(constraints.holds(&tampered) == false);
+
+        // sumcheck alone is also enforced
+        let last = inputs.len() - 1;
+        inputs[last].add_assign(&ExtensionField::ONE);
+        assert!(constraints.holds(&inputs) == false);
+    }
+
+    // simple circuit with public inputs, proven with everything that the generated contract expects
+    fn prove_keccak_circuit() -> (
+        Verifier<F, Ext>,
+        VerificationKey<F, Keccak256>,
+        Proof<F, Keccak256, Ext>,
+        ProofConfig,
+    ) {
+        type P = GoldilocksField;
+
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+
+        fn configure<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = ConstantsAllocatorGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = PublicInputGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = NopGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            builder
+        }
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, 128);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(512));
+
+        let mut previous = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(1));
+        PublicInputGate::new(previous).add_to_cs(&mut cs);
+        for _ in 0..100 {
+            let b = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(2));
+            let c = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(3));
+
+            previous = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::TWO,
+                (previous, b),
+                F::MINUS_ONE,
+                c,
+            );
+        }
+        PublicInputGate::new(previous).add_to_cs(&mut cs);
+        cs.allocate_constant(F::from_u64_unchecked(3));
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(1);
+        let cs = cs.into_assembly::<Global>();
+
+        let proof_config = ProofConfig {
+            fri_lde_factor: 8,
+            pow_bits: 0,
+            ..Default::default()
+        };
+
+        let (proof, vk) = cs.prove_one_shot::<2, Ext, Keccak256Transcript, Keccak256, NoPow>(
+            &worker,
+            proof_config.clone(),
+            (),
+        );
+
+        let builder_impl = CsVerifierBuilder::<F, Ext>::new_from_parameters(geometry);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let verifier = builder.build(());
+        assert!(verifier.verify::<Keccak256, Keccak256Transcript, NoPow>((), &vk, &proof));
+
+        (verifier, vk, proof, proof_config)
+    }
+
+    #[test]
+    fn test_generate_verifier_for_keccak_proof() {
+        let (verifier, vk, proof, proof_config) = prove_keccak_circuit();
+
+        let source =
+            generate_solidity_verifier("SimpleVerifier", &verifier, &vk, &proof_config).unwrap();
+        assert!(source.contains("contract SimpleVerifier {"));
+        assert!(source.contains("function verify(uint256[] calldata proof) external pure"));
+        assert!(source.contains("uint256 internal constant NUM_PUBLIC_INPUTS = 2;"));
+        assert!(source.contains("bool internal constant HAS_LOOKUP = false;"));
+        assert!(source.contains(&format!("uint256 internal constant P = {};", F::CHAR)));
+
+        let layout = CalldataLayout::new(&verifier, &vk.fixed_parameters, &proof_config).unwrap();
+        assert!(source.contains(&format!(
+            "uint256 internal constant PROOF_LENGTH = {};",
+            layout.total_len
+        )));
+        let words = proof_into_words(&proof);
+        assert_eq!(words.len(), layout.total_len);
+        assert_eq!(
+            encode_proof_calldata(&proof).len(),
+            4 + 32 * 2 + 32 * layout.total_len
+        );
+
+        let read = |idx: usize| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&words[idx][24..]);
+            F::from_u64_unchecked(u64::from_be_bytes(bytes))
+        };
+        assert_eq!(
+            read(layout.public_inputs_offset + 1),
+            proof.public_inputs[1]
+        );
+        assert_eq!(
+            words[layout.witness_cap_offset],
+            proof.witness_oracle_cap[0]
+        );
+        assert_eq!(
+            words[layout.queries_offset + layout.quotient_query_offset],
+            field_element_bytes(
+                proof.queries_per_fri_repetition[0]
+                    .quotient_query
+                    .leaf_elements[0]
+            )
+        );
+        assert_eq!(
+            read(layout.final_monomials_offset + layout.final_degree),
+            proof.final_fri_monomials[1][0]
+        );
+
+        // openings are read by the contract from the words, and are enough to check the constraints
+        let constraints = ConstraintsAtZ::new(&verifier, &vk.fixed_parameters, &proof_config);
+        assert!(constraints.lookup_sumcheck.is_none());
+        let num_values = layout.num_values_at_z + layout.num_values_at_z_omega;
+        let values: Vec<_> = (0..num_values)
+            .map(|idx| {
+                ExtensionField::<F, 2, Ext>::from_coeff_in_base([
+                    read(layout.values_offset + 2 * idx),
+                    read(layout.values_offset + 2 * idx + 1),
+                ])
+            })
+            .collect();
+        let challenges = challenges_at_z::<Keccak256, Keccak256Transcript>(&verifier, &vk, &proof);
+        let inputs = ConstraintsAtZ::inputs(
+            challenges,
+            &values[..layout.num_values_at_z],
+            &values[layout.num_values_at_z..],
+            &[],
+        );
+        assert!(constraints.holds(&inputs));
+    }
+
+    // compiles the contract with `solc` from `PATH` and returns its runtime bytecode
+    fn compile_runtime_bytecode(contract_name: &str, source: &str) -> Vec<u8> {
+        use std::process::Command;
+
+        let dir = std::env::temp_dir().join(format!("boojum_solidity_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.sol", contract_name));
+        std::fs::write(&path, source).unwrap();
+
+        let output = Command::new("solc")
+            .args(["--optimize", "--via-ir", "--bin-runtime"])
+            .arg(&path)
+            .output()
+            .expect("solc must be available in PATH");
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(
+            output.status.success(),
+            "failed to compile the verifier: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let mut lines = stdout.lines();
+        lines
+            .by_ref()
+            .find(|line| line.starts_with("Binary of the runtime part"))
+            .expect("solc must output the runtime bytecode");
+
+        hex::decode(lines.next().unwrap().trim()).unwrap()
+    }
+
+    #[test]
+    #[ignore = "requires solc in PATH, run by the evm-verifier CI job"]
+    fn test_generated_verifier_in_evm() {
+        use revm::primitives::{AccountInfo, Address, Bytecode, Bytes, TransactTo, U256};
+        use revm::{Evm, InMemoryDB};
+
+        let (verifier, vk, proof, proof_config) = prove_keccak_circuit();
+        let source =
+            generate_solidity_verifier("SimpleVerifier", &verifier, &vk, &proof_config).unwrap();
+        let bytecode = Bytecode::new_raw(Bytes::from(compile_runtime_bytecode(
+            "SimpleVerifier",
+            &source,
+        )));
+
+        // code is placed directly, so the contract size limit doesn't apply
+        let contract = Address::repeat_byte(0x11);
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            contract,
+            AccountInfo::new(U256::ZERO, 1, bytecode.hash_slow(), bytecode),
+        );
+
+        let call = |calldata: Vec<u8>| {
+            let mut evm = Evm::builder()
+                .with_db(db.clone())
+                .modify_tx_env(|tx| {
+                    tx.caller = Address::repeat_byte(0x22);
+                    tx.transact_to = TransactTo::Call(contract);
+                    tx.data = Bytes::from(calldata);
+                    tx.gas_limit = 1_000_000_000;
+                })
+                .build();
+
+            evm.transact().unwrap().result
+        };
+
+        let calldata = encode_proof_calldata(&proof);
+        let result = call(calldata.clone());
+        assert!(result.is_success(), "valid proof is rejected: {:?}", result);
+        let mut expected_output = [0u8; 32];
+        expected_output[31] = 1;
+        assert_eq!(&result.output().unwrap()[..], &expected_output[..]);
+
+        // flip a bit in the first value at z, that changes the transcript and breaks
+        // the quotient identity
+        let layout = CalldataLayout::new(&verifier, &vk.fixed_parameters, &proof_config).unwrap();
+        let mut malformed_calldata = calldata;
+        malformed_calldata[4 + 32 * 2 + 32 * layout.values_offset + 31] ^= 1;
+        let result = call(malformed_calldata);
+        assert!(!result.is_success(), "malformed proof is accepted");
+    }
+
+    fn field_element_bytes(el: F) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&el.as_u64_reduced().to_be_bytes());
+
+        word
+    }
+}
@@ -0,0 +1,241 @@
+use super::*;
+
+use crate::field::traits::field_like::PrimeFieldLike;
+use crate::field::{ExtensionField, Field, FieldExtension, PrimeField};
+use std::collections::HashMap;
+
+// To emit gate constraints as code we evaluate them over a "symbolic" field element, that
+// records every operation into a tape instead of computing it. Operations over constants are
+// folded right away, so global constants of the gates never touch the tape, and the same
+// operation over the same operands is recorded only once
+
+/// Value of a quadratic extension element during symbolic evaluation: either a constant
+/// from the base field, or the result of some operation recorded in the `ExpressionTape`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SymbolicExt<F: SmallField> {
+    Constant(F),
+    Value(usize),
+}
+
+impl<F: SmallField> std::fmt::Display for SymbolicExt<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolicExt::Constant(constant) => write!(f, "{}", constant),
+            SymbolicExt::Value(idx) => write!(f, "v{}", idx),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TapeOp<F: SmallField> {
+    /// Reads the input with the given index
+    Input(usize),
+    Add(SymbolicExt<F>, SymbolicExt<F>),
+    Sub(SymbolicExt<F>, SymbolicExt<F>),
+    Mul(SymbolicExt<F>, SymbolicExt<F>),
+    Negate(SymbolicExt<F>),
+    /// Inverse of the value, or zero if the value is zero
+    Inverse(SymbolicExt<F>),
+}
+
+/// Straight-line program over the quadratic extension. It's a context of `SymbolicExt`
+#[derive(Clone, Debug)]
+pub struct ExpressionTape<F: SmallField> {
+    pub ops: Vec<TapeOp<F>>,
+    pub num_inputs: usize,
+    known_ops: HashMap<TapeOp<F>, usize>,
+}
+
+impl<F: SmallField> Default for ExpressionTape<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: SmallField> ExpressionTape<F> {
+    pub fn new() -> Self {
+        Self {
+            ops: vec![],
+            num_inputs: 0,
+            known_ops: HashMap::new(),
+        }
+    }
+
+    /// Allocates the next input of the tape
+    pub fn input(&mut self) -> SymbolicExt<F> {
+        let idx = self.num_inputs;
+        self.num_inputs += 1;
+
+        self.record(TapeOp::Input(idx))
+    }
+
+    fn record(&mut self, op: TapeOp<F>) -> SymbolicExt<F> {
+        if let Some(idx) = self.known_ops.get(&op).copied() {
+            return SymbolicExt::Value(idx);
+        }
+
+        let idx = self.ops.len();
+        self.ops.push(op);
+        self.known_ops.insert(op, idx);
+
+        SymbolicExt::Value(idx)
+    }
+
+    /// Evaluates all the operations of the tape over given inputs
+    pub fn evaluate<EXT: FieldExtension<2, BaseField = F>>(
+        &self,
+        inputs: &[ExtensionField<F, 2, EXT>],
+    ) -> Vec<ExtensionField<F, 2, EXT>> {
+        assert_eq!(inputs.len(), self.num_inputs);
+
+        let mut values: Vec<ExtensionField<F, 2, EXT>> = Vec::with_capacity(self.ops.len());
+        for op in self.ops.iter() {
+            let get = |el: &SymbolicExt<F>| match el {
+                SymbolicExt::Constant(constant) => {
+                    ExtensionField::<F, 2, EXT>::from_coeff_in_base([*constant, F::ZERO])
+                }
+                SymbolicExt::Value(idx) => values[*idx],
+            };
+
+            let value = match op {
+                TapeOp::Input(idx) => inputs[*idx],
+                TapeOp::Add(a, b) => {
+                    let mut result = get(a);
+                    Field::add_assign(&mut result, &get(b));
+
+                    result
+                }
+                TapeOp::Sub(a, b) => {
+                    let mut result = get(a);
+                    Field::sub_assign(&mut result, &get(b));
+
+                    result
+                }
+                TapeOp::Mul(a, b) => {
+                    let mut result = get(a);
+                    Field::mul_assign(&mut result, &get(b));
+
+                    result
+                }
+                TapeOp::Negate(a) => {
+                    let mut result = get(a);
+                    Field::negate(&mut result);
+
+                    result
+                }
+                TapeOp::Inverse(a) => {
+                    PrimeField::inverse(&get(a)).unwrap_or(ExtensionField::<F, 2, EXT>::ZERO)
+                }
+            };
+            values.push(value);
+        }
+
+        values
+    }
+
+    /// Value of the symbolic element in the result of `evaluate`
+    pub fn value_of<EXT: FieldExtension<2, BaseField = F>>(
+        el: &SymbolicExt<F>,
+        values: &[ExtensionField<F, 2, EXT>],
+    ) -> ExtensionField<F, 2, EXT> {
+        match el {
+            SymbolicExt::Constant(constant) => {
+                ExtensionField::<F, 2, EXT>::from_coeff_in_base([*constant, F::ZERO])
+            }
+            SymbolicExt::Value(idx) => values[*idx],
+        }
+    }
+}
+
+impl<F: SmallField> PrimeFieldLike for SymbolicExt<F> {
+    type Base = F;
+    type Context = ExpressionTape<F>;
+
+    #[inline]
+    fn zero(_ctx: &mut Self::Context) -> Self {
+        SymbolicExt::Constant(F::ZERO)
+    }
+    #[inline]
+    fn one(_ctx: &mut Self::Context) -> Self {
+        SymbolicExt::Constant(F::ONE)
+    }
+    #[inline]
+    fn minus_one(_ctx: &mut Self::Context) -> Self {
+        SymbolicExt::Constant(F::MINUS_ONE)
+    }
+    fn add_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
+        *self = match (*self, *other) {
+            (SymbolicExt::Constant(mut a), SymbolicExt::Constant(b)) => {
+                Field::add_assign(&mut a, &b);
+                SymbolicExt::Constant(a)
+            }
+            (SymbolicExt::Constant(a), b) if Field::is_zero(&a) => b,
+            (a, SymbolicExt::Constant(b)) if Field::is_zero(&b) => a,
+            (a, b) => ctx.record(TapeOp::Add(a, b)),
+        };
+
+        self
+    }
+    fn sub_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
+        *self = match (*self, *other) {
+            (SymbolicExt::Constant(mut a), SymbolicExt::Constant(b)) => {
+                Field::sub_assign(&mut a, &b);
+                SymbolicExt::Constant(a)
+            }
+            (SymbolicExt::Constant(a), b) if Field::is_zero(&a) => ctx.record(TapeOp::Negate(b)),
+            (a, SymbolicExt::Constant(b)) if Field::is_zero(&b) => a,
+            (a, b) => ctx.record(TapeOp::Sub(a, b)),
+        };
+
+        self
+    }
+    fn mul_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
+        *self = match (*self, *other) {
+            (SymbolicExt::Constant(mut a), SymbolicExt::Constant(b)) => {
+                Field::mul_assign(&mut a, &b);
+                SymbolicExt::Constant(a)
+            }
+            (SymbolicExt::Constant(a), _) | (_, SymbolicExt::Constant(a)) if Field::is_zero(&a) => {
+                SymbolicExt::Constant(F::ZERO)
+            }
+            (SymbolicExt::Constant(a), b) if a == F::ONE => b,
+            (a, SymbolicExt::Constant(b)) if b == F::ONE => a,
+            (a, b) => ctx.record(TapeOp::Mul(a, b)),
+        };
+
+        self
+    }
+    #[inline]
+    fn square(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
+        let this = *self;
+        self.mul_assign(&this, ctx)
+    }
+    fn negate(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
+        *self = match *self {
+            SymbolicExt::Constant(mut a) => {
+                Field::negate(&mut a);
+                SymbolicExt::Constant(a)
+            }
+            a => ctx.record(TapeOp::Negate(a)),
+        };
+
+        self
+    }
+    #[inline]
+    fn double(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
+        let this = *self;
+        self.add_assign(&this, ctx)
+    }
+    fn inverse(&self, ctx: &mut Self::Context) -> Self {
+        match *self {
+            SymbolicExt::Constant(a) => {
+                SymbolicExt::Constant(PrimeField::inverse(&a).unwrap_or(F::ZERO))
+            }
+            a => ctx.record(TapeOp::Inverse(a)),
+        }
+    }
+    #[inline]
+    fn constant(value: Self::Base, _ctx: &mut Self::Context) -> Self {
+        SymbolicExt::Constant(value)
+    }
+}
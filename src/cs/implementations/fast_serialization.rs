@@ -22,7 +22,8 @@ pub fn read_vec_from_buffer<T: MemcopySerializable, A: GoodAllocator, R: Read>(
     let length: u64 = u64::from_le_bytes(len_le_bytes);
     let length = length as usize;
 
-    let mut result = Vec::with_capacity_in(length, A::default());
+    // length is not trusted, so we do not preallocate too much
+    let mut result = Vec::with_capacity_in(length.min(1 << 16), A::default());
     for _ in 0..length {
         let el: T = MemcopySerializable::read_from_buffer(&mut src)?;
         result.push(el);
@@ -31,6 +31,13 @@ use crate::fft::bitreverse_enumeration_inplace;
 
 use crate::field::{Field, FieldExtension};
 
+use super::prover::MAX_FRI_FOLDING_DEGREE_LOG2;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound(
+    serialize = "H::Output: serde::Serialize, [Vec<F, A>; N]: serde::Serialize",
+    deserialize = "H::Output: serde::de::DeserializeOwned, [Vec<F, A>; N]: serde::de::DeserializeOwned"
+))]
 pub struct FriOracles<
     F: SmallField,
     H: TreeHasher<F>,
@@ -41,37 +48,44 @@ pub struct FriOracles<
     // we do not store "leaf" sources for the base oracle, but store all other oracles
     // and their leafs
     pub base_oracle: MerkleTreeWithCap<F, H, A, B>,
-    pub leaf_sources_for_intermediate_oracles: Vec<(Vec<F, A>, Vec<F, A>), B>,
+    #[serde(serialize_with = "crate::serde_utils::serialize_vec_of_arrays_with_allocator")]
+    #[serde(deserialize_with = "crate::serde_utils::deserialize_vec_of_arrays_with_allocator")]
+    pub leaf_sources_for_intermediate_oracles: Vec<[Vec<F, A>; N], B>,
+    #[serde(serialize_with = "crate::utils::serialize_vec_with_allocator")]
+    #[serde(deserialize_with = "crate::utils::deserialize_vec_with_allocator")]
     pub intermediate_oracles: Vec<MerkleTreeWithCap<F, H, A, B>, B>,
-    pub monomial_forms: [Vec<F, A>; 2],
+    #[serde(with = "crate::serde_utils::BigArraySerde")]
+    pub monomial_forms: [Vec<F, A>; N],
 }
 
 pub fn do_fri<
     F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     T: Transcript<F>,
     H: TreeHasher<F, Output = T::CompatibleCap>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
-    rs_code_word_c0: ArcGenericLdeStorage<F, P, A, B>,
-    rs_code_word_c1: ArcGenericLdeStorage<F, P, A, B>,
+    rs_code_word: [ArcGenericLdeStorage<F, P, A, B>; N],
     transcript: &mut T,
     interpolation_log2s_schedule: Vec<usize>,
     lde_degree: usize,
     cap_size: usize,
     worker: &Worker,
     ctx: &mut P::Context,
-) -> FriOracles<F, H, A, B, 2> {
+) -> FriOracles<F, H, A, B, N> {
     // first we have to construct our "base" oracle
 
     let now = std::time::Instant::now();
 
-    debug_assert_eq!(rs_code_word_c0.outer_len(), rs_code_word_c1.outer_len());
-    debug_assert_eq!(rs_code_word_c0.inner_len(), rs_code_word_c1.inner_len());
+    for coeff in rs_code_word[1..].iter() {
+        debug_assert_eq!(rs_code_word[0].outer_len(), coeff.outer_len());
+        debug_assert_eq!(rs_code_word[0].inner_len(), coeff.inner_len());
+    }
 
-    let full_size = rs_code_word_c0.outer_len() * rs_code_word_c0.inner_len() * P::SIZE_FACTOR;
+    let full_size = rs_code_word[0].outer_len() * rs_code_word[0].inner_len() * P::SIZE_FACTOR;
     let degree = full_size / lde_degree;
     let mut final_degree = degree;
     for interpolation_log2 in interpolation_log2s_schedule.iter() {
@@ -167,12 +181,11 @@ pub fn do_fri<
     // same would apply if we would use higher roots of -1
 
     // create first oracle. It's special because
-    // what we have have is two polynomials that represent c0 and c1 coefficients of the extension,
+    // what we have have is N polynomials that represent coefficients of the extension,
     // and we want to take X elements from every coset in there, and place into the leaf
 
-    let mut base_sources = Vec::with_capacity_in(2, B::default());
-    base_sources.push(rs_code_word_c0.clone());
-    base_sources.push(rs_code_word_c1.clone());
+    let mut base_sources = Vec::with_capacity_in(N, B::default());
+    base_sources.extend(rs_code_word.iter().cloned());
 
     let base_oracle_elements_per_leaf = interpolation_log2s_schedule[0];
 
@@ -199,35 +212,18 @@ pub fn do_fri<
 
     let reduction_degree_log_2 = it.next().unwrap();
 
-    let (c0_values, c1_values) = {
+    let values = {
         log!("Fold degree by {}", 1 << reduction_degree_log_2);
         assert!(reduction_degree_log_2 > 0);
-        assert!(reduction_degree_log_2 < 4);
-
-        let c0 = transcript.get_challenge();
-        let c1 = transcript.get_challenge();
-
-        let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
-        challenge_powers.push((c0, c1));
-        use crate::field::ExtensionField;
-        let as_extension = ExtensionField::<F, 2, EXT> {
-            coeffs: [c0, c1],
-            _marker: std::marker::PhantomData,
-        };
+        assert!(reduction_degree_log_2 <= MAX_FRI_FOLDING_DEGREE_LOG2);
 
-        let mut current = as_extension;
-
-        for _ in 1..reduction_degree_log_2 {
-            current.square();
-            let [c0, c1] = current.into_coeffs_in_base();
-            challenge_powers.push((c0, c1));
-        }
+        let challenge_powers =
+            draw_challenge_powers::<F, N, EXT, T>(transcript, reduction_degree_log_2);
 
         // now interpolate as described above
 
-        let (c0, c1) = interpolate_independent_cosets::<F, P, EXT, A, B>(
-            rs_code_word_c0.clone(),
-            rs_code_word_c1.clone(),
+        interpolate_independent_cosets::<F, P, N, EXT, A, B>(
+            rs_code_word.clone(),
             reduction_degree_log_2,
             &roots,
             challenge_powers,
@@ -235,26 +231,23 @@ pub fn do_fri<
             &mut coset_inverse,
             worker,
             ctx,
-        );
-
-        (c0, c1)
+        )
     };
 
-    intermediate_sources.push((c0_values, c1_values));
+    intermediate_sources.push(values);
 
     for reduction_degree_log_2 in it {
         log!("Fold degree by {}", 1 << reduction_degree_log_2);
 
         assert!(reduction_degree_log_2 > 0);
-        assert!(reduction_degree_log_2 < 4);
+        assert!(reduction_degree_log_2 <= MAX_FRI_FOLDING_DEGREE_LOG2);
 
         // make intermediate oracle for the next folding
-        let mut sources = Vec::with_capacity_in(2, B::default());
-        let (c0_values, c1_values) = intermediate_sources
+        let mut sources = Vec::with_capacity_in(N, B::default());
+        let previous_values = intermediate_sources
             .last()
             .expect("previous folding result exists");
-        sources.push(c0_values);
-        sources.push(c1_values);
+        sources.extend(previous_values.iter());
         let intermediate_oracle =
             MerkleTreeWithCap::<F, H, A, B>::construct_by_chunking_from_flat_sources(
                 &sources,
@@ -268,32 +261,15 @@ pub fn do_fri<
         intermediate_oracles.push(intermediate_oracle);
         // compute next folding
 
-        let c0 = transcript.get_challenge();
-        let c1 = transcript.get_challenge();
-
-        let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
-        challenge_powers.push((c0, c1));
-        use crate::field::ExtensionField;
-        let as_extension = ExtensionField::<F, 2, EXT> {
-            coeffs: [c0, c1],
-            _marker: std::marker::PhantomData,
-        };
-
-        let mut current = as_extension;
-
-        for _ in 1..reduction_degree_log_2 {
-            current.square();
-            let [c0, c1] = current.into_coeffs_in_base();
-            challenge_powers.push((c0, c1));
-        }
+        let challenge_powers =
+            draw_challenge_powers::<F, N, EXT, T>(transcript, reduction_degree_log_2);
 
         // now interpolate as described above
 
-        let (c0_source, c1_source) = intermediate_sources.last().cloned().unwrap();
+        let source = intermediate_sources.last().cloned().unwrap();
 
-        let (new_c0, new_c1) = interpolate_flattened_cosets::<F, EXT, A>(
-            c0_source,
-            c1_source,
+        let new_values = interpolate_flattened_cosets::<F, N, EXT, A>(
+            source,
             reduction_degree_log_2,
             &roots,
             challenge_powers,
@@ -302,45 +278,42 @@ pub fn do_fri<
             worker,
         );
 
-        intermediate_sources.push((new_c0, new_c1));
+        intermediate_sources.push(new_values);
     }
 
     // we can now interpolate the last sets to get monomial forms
 
     log!("Interpolating low degree poly");
 
-    let (mut c0_source, mut c1_source) = intermediate_sources.last().cloned().unwrap();
-
-    bitreverse_enumeration_inplace(&mut c0_source);
-    bitreverse_enumeration_inplace(&mut c1_source);
+    let mut sources = intermediate_sources.last().cloned().unwrap();
 
     let coset = coset_inverse.inverse().unwrap();
     // IFFT our presumable LDE of some low degree poly
-    let fft_size = c0_source.len();
-    crate::fft::ifft_natural_to_natural(&mut c0_source, coset, &roots[..fft_size / 2]);
-    crate::fft::ifft_natural_to_natural(&mut c1_source, coset, &roots[..fft_size / 2]);
+    let fft_size = sources[0].len();
+    for source in sources.iter_mut() {
+        bitreverse_enumeration_inplace(source);
+        crate::fft::ifft_natural_to_natural(source, coset, &roots[..fft_size / 2]);
+    }
 
     assert_eq!(final_degree, fft_size / lde_degree);
 
     // self-check
     if crate::config::DEBUG_SATISFIABLE == false {
-        for el in c0_source[final_degree..].iter() {
-            assert_eq!(*el, F::ZERO);
-        }
-
-        for el in c1_source[final_degree..].iter() {
-            assert_eq!(*el, F::ZERO);
+        for source in sources.iter() {
+            for el in source[final_degree..].iter() {
+                assert_eq!(*el, F::ZERO);
+            }
         }
     }
 
     // add to the transcript
-    transcript.witness_field_elements(&c0_source[..final_degree]);
-    transcript.witness_field_elements(&c1_source[..final_degree]);
+    for source in sources.iter() {
+        transcript.witness_field_elements(&source[..final_degree]);
+    }
 
     // now we should do some PoW and we are good to go
 
-    let monomial_form_0 = c0_source[..(fft_size / lde_degree)].to_vec_in(A::default());
-    let monomial_form_1 = c1_source[..(fft_size / lde_degree)].to_vec_in(A::default());
+    let monomial_forms = sources.map(|el| el[..(fft_size / lde_degree)].to_vec_in(A::default()));
 
     log!(
         "FRI for base size 2^{} is done over {:?}",
@@ -352,21 +325,47 @@ pub fn do_fri<
         base_oracle: fri_base_oracle,
         leaf_sources_for_intermediate_oracles: intermediate_sources,
         intermediate_oracles,
-        monomial_forms: [monomial_form_0, monomial_form_1],
+        monomial_forms,
     }
 }
 
+// draws a challenge from the extension and computes it's consecutive squares, one per folding by 2
+pub(crate) fn draw_challenge_powers<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+    T: Transcript<F>,
+>(
+    transcript: &mut T,
+    reduction_degree_log_2: usize,
+) -> Vec<[F; N]> {
+    use crate::field::ExtensionField;
+
+    let challenge = transcript.get_multiple_challenges_fixed::<N>();
+
+    let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
+    challenge_powers.push(challenge);
+    let mut current = ExtensionField::<F, N, EXT> {
+        coeffs: challenge,
+        _marker: std::marker::PhantomData,
+    };
+
+    for _ in 1..reduction_degree_log_2 {
+        current.square();
+        challenge_powers.push(current.into_coeffs_in_base());
+    }
+
+    challenge_powers
+}
+
 // this is quasi-vectorization
 #[inline(always)]
-#[unroll::unroll_for_loops]
-fn fold_multiple<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
-    c0_chunk: &[F],
-    c1_chunk: &[F],
-    dst_c0: &mut [MaybeUninit<F>],
-    dst_c1: &mut [MaybeUninit<F>],
+fn fold_multiple<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+    src: [&[F]; N],
+    dst: [&mut [MaybeUninit<F>]; N],
     roots: &[F],
     coset_inverse: &F,
-    (c0, c1): (F, F),
+    challenge: [F; N],
 ) {
     use crate::field::traits::field::Field;
     use crate::field::ExtensionField;
@@ -375,206 +374,131 @@ fn fold_multiple<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
     // where f(x), f(-x) and alpha are extension field elements,
     // and x is in the base field
 
-    // So in practice we only do single multiplication of Fp2 by Fp2 here
-
-    let mut src_c0_chunks = c0_chunk.array_chunks::<32>();
-    let mut src_c1_chunks = c1_chunk.array_chunks::<32>();
+    // So in practice we only do single multiplication of Fp^N by Fp^N here
 
-    let mut dst_c0_chunks = dst_c0.array_chunks_mut::<16>();
-    let mut dst_c1_chunks = dst_c1.array_chunks_mut::<16>();
-
-    let mut roots_chunks = roots.array_chunks::<16>();
-
-    let challenge_as_extension = ExtensionField::<F, 2, EXT> {
-        coeffs: [c0, c1],
+    let challenge_as_extension = ExtensionField::<F, N, EXT> {
+        coeffs: challenge,
         _marker: std::marker::PhantomData,
     };
 
-    for ((((c0_pairs, c1_pairs), dst_c0), dst_c1), roots) in (&mut src_c0_chunks)
-        .zip(&mut src_c1_chunks)
-        .zip(&mut dst_c0_chunks)
-        .zip(&mut dst_c1_chunks)
-        .zip(&mut roots_chunks)
-    {
-        for i in 0..16 {
-            let f_at_x_c0 = c0_pairs[2 * i];
-            let f_at_minus_x_c0 = c0_pairs[2 * i + 1];
-            let mut diff_c0 = f_at_x_c0;
-            diff_c0.sub_assign(&f_at_minus_x_c0);
-            diff_c0.mul_assign(&roots[i]);
-            diff_c0.mul_assign(coset_inverse);
-
-            let f_at_x_c1 = c1_pairs[2 * i];
-            let f_at_minus_x_c1 = c1_pairs[2 * i + 1];
-            let mut diff_c1 = f_at_x_c1;
-            diff_c1.sub_assign(&f_at_minus_x_c1);
-            diff_c1.mul_assign(&roots[i]);
-            diff_c1.mul_assign(coset_inverse);
-
-            // now we multiply
-            let mut diff_as_extension = ExtensionField::<F, 2, EXT> {
-                coeffs: [diff_c0, diff_c1],
-                _marker: std::marker::PhantomData,
-            };
-
-            diff_as_extension.mul_assign(&challenge_as_extension);
-
-            let [mut other_c0, mut other_c1] = diff_as_extension.into_coeffs_in_base();
-
-            other_c0.add_assign(&f_at_x_c0).add_assign(&f_at_minus_x_c0);
-            other_c1.add_assign(&f_at_x_c1).add_assign(&f_at_minus_x_c1);
-
-            dst_c0[i].write(other_c0);
-            dst_c1[i].write(other_c1);
-        }
-    }
-
-    // and now over remainders
-    let c0_pairs = src_c0_chunks.remainder();
-    let c1_pairs = src_c1_chunks.remainder();
-
-    let dst_c0 = dst_c0_chunks.into_remainder();
-    let dst_c1 = dst_c1_chunks.into_remainder();
-
-    let roots = roots_chunks.remainder();
-
-    let bound = dst_c0.len();
+    let bound = dst[0].len();
+    debug_assert_eq!(roots.len(), bound);
+    let mut dst = dst;
 
     for i in 0..bound {
-        let f_at_x_c0 = c0_pairs[2 * i];
-        let f_at_minus_x_c0 = c0_pairs[2 * i + 1];
-        let mut diff_c0 = f_at_x_c0;
-        diff_c0.sub_assign(&f_at_minus_x_c0);
-        diff_c0.mul_assign(&roots[i]);
-        diff_c0.mul_assign(coset_inverse);
-
-        let f_at_x_c1 = c1_pairs[2 * i];
-        let f_at_minus_x_c1 = c1_pairs[2 * i + 1];
-        let mut diff_c1 = f_at_x_c1;
-        diff_c1.sub_assign(&f_at_minus_x_c1);
-        diff_c1.mul_assign(&roots[i]);
-        diff_c1.mul_assign(coset_inverse);
+        let mut diff = [F::ZERO; N];
+        for (diff, src) in diff.iter_mut().zip(src.iter()) {
+            *diff = src[2 * i];
+            diff.sub_assign(&src[2 * i + 1]);
+            diff.mul_assign(&roots[i]);
+            diff.mul_assign(coset_inverse);
+        }
 
         // now we multiply
-        let mut diff_as_extension = ExtensionField::<F, 2, EXT> {
-            coeffs: [diff_c0, diff_c1],
+        let mut diff_as_extension = ExtensionField::<F, N, EXT> {
+            coeffs: diff,
             _marker: std::marker::PhantomData,
         };
 
         diff_as_extension.mul_assign(&challenge_as_extension);
 
-        let [mut other_c0, mut other_c1] = diff_as_extension.into_coeffs_in_base();
+        let other = diff_as_extension.into_coeffs_in_base();
 
-        other_c0.add_assign(&f_at_x_c0).add_assign(&f_at_minus_x_c0);
-        other_c1.add_assign(&f_at_x_c1).add_assign(&f_at_minus_x_c1);
-
-        dst_c0[i].write(other_c0);
-        dst_c1[i].write(other_c1);
+        for ((mut other, src), dst) in other.into_iter().zip(src.iter()).zip(dst.iter_mut()) {
+            other.add_assign(&src[2 * i]).add_assign(&src[2 * i + 1]);
+            dst[i].write(other);
+        }
     }
 }
 
 fn interpolate_independent_cosets<
     F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
-    c0_source: ArcGenericLdeStorage<F, P, A, B>,
-    c1_source: ArcGenericLdeStorage<F, P, A, B>,
+    source: [ArcGenericLdeStorage<F, P, A, B>; N],
     interpolation_degree_log2: usize,
     roots_precomputation: &[F],
-    challenges: Vec<(F, F)>,
+    challenges: Vec<[F; N]>,
     original_lde_degree: usize,
     coset_inverse: &mut F,
     worker: &Worker,
     _ctx: &mut P::Context,
-) -> (Vec<F, A>, Vec<F, A>) {
-    let full_size = c0_source.outer_len() * c0_source.inner_len() * P::SIZE_FACTOR;
+) -> [Vec<F, A>; N] {
+    let full_size = source[0].outer_len() * source[0].inner_len() * P::SIZE_FACTOR;
     debug_assert_eq!(roots_precomputation.len() * 2, full_size);
     let mut interpolation_degree_log2 = interpolation_degree_log2;
     let result_size = full_size >> 1;
     interpolation_degree_log2 -= 1;
     debug_assert!(result_size > 0);
-    let mut result_c0 = Vec::with_capacity_in(result_size, A::default());
-    let mut result_c1 = Vec::with_capacity_in(result_size, A::default());
+    let mut result: [Vec<F, A>; N] =
+        std::array::from_fn(|_| Vec::with_capacity_in(result_size, A::default()));
 
     // we fold as many times as we need, but after first folding we should understand that our memory layout is not
     // beneficial for FRI, so in practice we work over independent field elements
 
-    let (c0, c1) = challenges[0];
+    let challenge = challenges[0];
 
     // even though our cosets are continuous in memory, total placement is just bitreversed
 
-    for (coset_idx, (c0_coset, c1_coset)) in c0_source
-        .storage
-        .iter()
-        .zip(c1_source.storage.iter())
-        .enumerate()
-    {
-        let work_size = c0_coset.domain_size() / 2;
+    let num_cosets = source[0].storage.len();
+    for coset_idx in 0..num_cosets {
+        let work_size = source[0].storage[coset_idx].domain_size() / 2;
         let roots = &roots_precomputation[coset_idx * work_size..(coset_idx + 1) * work_size];
-        let dst_c0 =
-            &mut result_c0.spare_capacity_mut()[coset_idx * work_size..(coset_idx + 1) * work_size];
-        let dst_c1 =
-            &mut result_c1.spare_capacity_mut()[coset_idx * work_size..(coset_idx + 1) * work_size];
+        let src = source
+            .each_ref()
+            .map(|el| P::slice_into_base_slice(&el.storage[coset_idx].storage));
+        let dst = result.each_mut().map(|el| {
+            &mut el.spare_capacity_mut()[coset_idx * work_size..(coset_idx + 1) * work_size]
+        });
+        debug_assert_eq!(dst[0].len() * 2, src[0].len());
 
         worker.scope(work_size, |scope, chunk_size| {
-            let src_c0 = P::slice_into_base_slice(&c0_coset.storage);
-            let src_c1 = P::slice_into_base_slice(&c1_coset.storage);
-            debug_assert_eq!(dst_c0.len() * 2, src_c0.len());
-
-            for ((((c0_chunk, c1_chunk), dst0_chunk), dst1_chunk), roots) in src_c0
-                .chunks(chunk_size * 2)
-                .zip(src_c1.chunks(chunk_size * 2))
-                .zip(dst_c0.chunks_mut(chunk_size))
-                .zip(dst_c1.chunks_mut(chunk_size))
-                .zip(roots.chunks(chunk_size))
+            let src_chunks = crate::cs::implementations::utils::chunk_columns(src, chunk_size * 2);
+            let dst_chunks = crate::cs::implementations::utils::chunk_columns_mut(dst, chunk_size);
+
+            for ((src_chunk, dst_chunk), roots) in
+                src_chunks.zip(dst_chunks).zip(roots.chunks(chunk_size))
             {
-                scope.spawn(|_| {
-                    fold_multiple::<F, EXT>(
-                        c0_chunk,
-                        c1_chunk,
-                        dst0_chunk,
-                        dst1_chunk,
+                let coset_inverse = &*coset_inverse;
+                scope.spawn(move |_| {
+                    fold_multiple::<F, N, EXT>(
+                        src_chunk,
+                        dst_chunk,
                         roots,
-                        &*coset_inverse,
-                        (c0, c1),
+                        coset_inverse,
+                        challenge,
                     );
                 })
             }
         });
     }
 
-    unsafe {
-        result_c0.set_len(result_size);
-        result_c1.set_len(result_size);
+    for el in result.iter_mut() {
+        unsafe { el.set_len(result_size) };
     }
 
     coset_inverse.square();
 
     if crate::config::DEBUG_SATISFIABLE == false {
         let coset = coset_inverse.inverse().unwrap();
-        let mut tmp = result_c0.clone();
-        bitreverse_enumeration_inplace(&mut tmp);
-        crate::field::traits::field_like::ifft_natural_to_natural(&mut tmp, coset);
-        for el in tmp[(tmp.len() / original_lde_degree)..].iter() {
-            debug_assert_eq!(*el, F::ZERO);
-        }
-
-        let mut tmp = result_c1.clone();
-        bitreverse_enumeration_inplace(&mut tmp);
-        crate::field::traits::field_like::ifft_natural_to_natural(&mut tmp, coset);
-        for el in tmp[(tmp.len() / original_lde_degree)..].iter() {
-            debug_assert_eq!(*el, F::ZERO);
+        for el in result.iter() {
+            let mut tmp = el.clone();
+            bitreverse_enumeration_inplace(&mut tmp);
+            crate::field::traits::field_like::ifft_natural_to_natural(&mut tmp, coset);
+            for el in tmp[(tmp.len() / original_lde_degree)..].iter() {
+                debug_assert_eq!(*el, F::ZERO);
+            }
         }
     }
 
     let challenges = challenges[1..].to_vec();
 
-    interpolate_flattened_cosets::<F, EXT, A>(
-        result_c0,
-        result_c1,
+    interpolate_flattened_cosets::<F, N, EXT, A>(
+        result,
         interpolation_degree_log2,
         roots_precomputation,
         challenges,
@@ -586,95 +510,113 @@ fn interpolate_independent_cosets<
 
 fn interpolate_flattened_cosets<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    c0_source: Vec<F, A>,
-    c1_source: Vec<F, A>,
+    source: [Vec<F, A>; N],
     interpolation_degree_log2: usize,
     roots_precomputation: &[F],
-    challenges: Vec<(F, F)>,
+    challenges: Vec<[F; N]>,
     original_lde_degree: usize,
     coset_inverse: &mut F,
     worker: &Worker,
-) -> (Vec<F, A>, Vec<F, A>) {
-    let full_size = c0_source.len();
+) -> [Vec<F, A>; N] {
+    let full_size = source[0].len();
     debug_assert_eq!(interpolation_degree_log2, challenges.len());
     let max_result_size = full_size >> 1;
     debug_assert!(max_result_size > 0);
 
-    let mut c0_source = c0_source;
-    let mut c1_source = c1_source;
+    let mut source = source;
 
-    let mut result_c0 = Vec::with_capacity_in(max_result_size, A::default());
-    let mut result_c1 = Vec::with_capacity_in(max_result_size, A::default());
+    let mut result: [Vec<F, A>; N] =
+        std::array::from_fn(|_| Vec::with_capacity_in(max_result_size, A::default()));
 
-    for (c0, c1) in challenges.into_iter() {
-        let work_size = c0_source.len() / 2;
+    for challenge in challenges.into_iter() {
+        let work_size = source[0].len() / 2;
 
-        result_c0.clear();
-        result_c1.clear();
+        for el in result.iter_mut() {
+            el.clear();
+        }
 
         let roots = &roots_precomputation[0..work_size];
-        let dst_c0 = &mut result_c0.spare_capacity_mut()[..work_size];
-        let dst_c1 = &mut result_c1.spare_capacity_mut()[..work_size];
+        let src = source.each_ref().map(|el| &el[..]);
+        let dst = result
+            .each_mut()
+            .map(|el| &mut el.spare_capacity_mut()[..work_size]);
+        debug_assert_eq!(dst[0].len() * 2, src[0].len());
 
         worker.scope(work_size, |scope, chunk_size| {
-            debug_assert_eq!(dst_c0.len() * 2, c0_source.len());
-
-            for ((((c0_chunk, c1_chunk), dst0_chunk), dst1_chunk), roots) in c0_source
-                .chunks(chunk_size * 2)
-                .zip(c1_source.chunks(chunk_size * 2))
-                .zip(dst_c0.chunks_mut(chunk_size))
-                .zip(dst_c1.chunks_mut(chunk_size))
-                .zip(roots.chunks(chunk_size))
+            let src_chunks = crate::cs::implementations::utils::chunk_columns(src, chunk_size * 2);
+            let dst_chunks = crate::cs::implementations::utils::chunk_columns_mut(dst, chunk_size);
+
+            for ((src_chunk, dst_chunk), roots) in
+                src_chunks.zip(dst_chunks).zip(roots.chunks(chunk_size))
             {
-                scope.spawn(|_| {
-                    fold_multiple::<F, EXT>(
-                        c0_chunk,
-                        c1_chunk,
-                        dst0_chunk,
-                        dst1_chunk,
+                let coset_inverse = &*coset_inverse;
+                scope.spawn(move |_| {
+                    fold_multiple::<F, N, EXT>(
+                        src_chunk,
+                        dst_chunk,
                         roots,
-                        &*coset_inverse,
-                        (c0, c1),
+                        coset_inverse,
+                        challenge,
                     );
                 })
             }
         });
 
-        unsafe {
-            result_c0.set_len(work_size);
-            result_c1.set_len(work_size);
+        for el in result.iter_mut() {
+            unsafe { el.set_len(work_size) };
         }
 
         coset_inverse.square();
 
         if crate::config::DEBUG_SATISFIABLE == false {
             let coset = coset_inverse.inverse().unwrap();
-
-            let mut tmp = result_c0.clone();
-            bitreverse_enumeration_inplace(&mut tmp);
-            crate::field::traits::field_like::ifft_natural_to_natural(&mut tmp, coset);
-            for el in tmp[(tmp.len() / original_lde_degree)..].iter() {
-                debug_assert_eq!(*el, F::ZERO);
-            }
-
-            let mut tmp = result_c1.clone();
-            bitreverse_enumeration_inplace(&mut tmp);
-            crate::field::traits::field_like::ifft_natural_to_natural(&mut tmp, coset);
-            for el in tmp[(tmp.len() / original_lde_degree)..].iter() {
-                debug_assert_eq!(*el, F::ZERO);
+            for el in result.iter() {
+                let mut tmp = el.clone();
+                bitreverse_enumeration_inplace(&mut tmp);
+                crate::field::traits::field_like::ifft_natural_to_natural(&mut tmp, coset);
+                for el in tmp[(tmp.len() / original_lde_degree)..].iter() {
+                    debug_assert_eq!(*el, F::ZERO);
+                }
             }
         }
 
         // swap source and result to reuse the buffer
 
-        std::mem::swap(&mut c0_source, &mut result_c0);
-        std::mem::swap(&mut c1_source, &mut result_c1);
+        std::mem::swap(&mut source, &mut result);
     }
 
-    (c0_source, c1_source)
+    source
+}
+
+// Leafs are interpolated in the bitreversed enumeration, so e.g. for 8 elements
+// those are ordered as bitreverses of [0..=7], namely [0, 4, 2, 6, 1, 5, 3, 7].
+// We need exactly half of the steps, because separation by half of the leaf is exactly -1,
+// so for 8 elements we need [1, sqrt4(1), sqrt8(1), sqrt4(1)*sqrt8(1)] (inversed), and in general
+// every bit of the index contributes the next root of unity
+pub fn precompute_interpolation_steps<F: SmallField>(
+    max_interpolation_degree_log2: usize,
+    precomputed_powers_inversed: &[F],
+) -> Vec<F> {
+    assert!(max_interpolation_degree_log2 > 0);
+    assert!(max_interpolation_degree_log2 <= MAX_FRI_FOLDING_DEGREE_LOG2);
+    let num_bits = max_interpolation_degree_log2 - 1;
+    // we need roots up to 2^{max_interpolation_degree_log2}
+    assert!(precomputed_powers_inversed.len() > max_interpolation_degree_log2);
+
+    let mut result = vec![F::ONE; 1 << num_bits];
+    for (idx, el) in result.iter_mut().enumerate() {
+        for bit in 0..num_bits {
+            if idx & (1 << bit) != 0 {
+                el.mul_assign(&precomputed_powers_inversed[bit + 2]);
+            }
+        }
+    }
+
+    result
 }
 
 // We will query oracles where leafs are made from different number of elements
@@ -773,7 +715,8 @@ impl<
         P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
         A: GoodAllocator,
         B: GoodAllocator,
-    > QuerySource<F> for SecondStageProductsStorage<F, P, A, B>
+        const N: usize,
+    > QuerySource<F> for SecondStageProductsStorage<F, N, P, A, B>
 {
     // witness is copy_permutation z_polys, then intermediate products, then lookup's A and B polys
     fn get_elements(
@@ -808,11 +751,8 @@ impl<
         P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
         A: GoodAllocator,
         B: GoodAllocator,
-    > QuerySource<F>
-    for (
-        ArcGenericLdeStorage<F, P, A, B>,
-        ArcGenericLdeStorage<F, P, A, B>,
-    )
+        const N: usize,
+    > QuerySource<F> for [ArcGenericLdeStorage<F, P, A, B>; N]
 {
     fn get_elements(
         &self,
@@ -824,12 +764,11 @@ impl<
         dst: &mut Vec<F>,
     ) {
         assert!(lde_factor > coset_idx);
-        // we put num_elements from first, and then from second
+        // we put num_elements from every coefficient one after another
         assert!(num_elements.is_power_of_two());
         let (inner_start_aligned, _) = split_inner(inner_idx, num_elements);
-        let source = [&self.0, &self.1];
         let inner_start_aligned = inner_start_aligned * num_elements;
-        for subsource in source.iter() {
+        for subsource in self.iter() {
             assert_eq!(subsource.storage[coset_idx].domain_size(), domain_size);
             let as_base = P::slice_into_base_slice(&subsource.storage[coset_idx].storage);
             dst.extend_from_slice(
@@ -839,7 +778,7 @@ impl<
     }
 }
 
-impl<'a, F: SmallField, A: GoodAllocator> QuerySource<F> for &'a (Vec<F, A>, Vec<F, A>) {
+impl<'a, F: SmallField, const N: usize, A: GoodAllocator> QuerySource<F> for &'a [Vec<F, A>; N] {
     fn get_elements(
         &self,
         lde_factor: usize,
@@ -850,41 +789,13 @@ impl<'a, F: SmallField, A: GoodAllocator> QuerySource<F> for &'a (Vec<F, A>, Vec
         dst: &mut Vec<F>,
     ) {
         assert!(lde_factor > coset_idx);
-        // we put num_elements from one, then another
+        // we put num_elements from every coefficient one after another
         assert!(num_elements.is_power_of_two());
         let (inner_start_aligned, _) = split_inner(inner_idx, num_elements);
         let mut inner_start_aligned = inner_start_aligned * num_elements;
         // we did flatten the sources, so we should offset
         inner_start_aligned += domain_size * coset_idx;
 
-        let subsource = &self.0;
-        assert_eq!(subsource.len(), domain_size * lde_factor);
-        dst.extend_from_slice(
-            &subsource[inner_start_aligned..(inner_start_aligned + num_elements)],
-        );
-
-        let subsource = &self.1;
-        assert_eq!(subsource.len(), domain_size * lde_factor);
-        dst.extend_from_slice(
-            &subsource[inner_start_aligned..(inner_start_aligned + num_elements)],
-        );
-    }
-}
-
-impl<'a, F: SmallField, const N: usize, A: GoodAllocator> QuerySource<F> for &'a [Vec<F, A>; N] {
-    fn get_elements(
-        &self,
-        lde_factor: usize,
-        coset_idx: usize,
-        domain_size: usize,
-        inner_idx: usize,
-        num_elements: usize,
-        dst: &mut Vec<F>,
-    ) {
-        assert!(lde_factor > coset_idx);
-        // we put num_elements from one, then another
-        assert!(num_elements.is_power_of_two());
-        let (inner_start_aligned, _) = split_inner(inner_idx, num_elements);
         for subsource in self.iter() {
             assert_eq!(subsource.len(), domain_size * lde_factor);
             dst.extend_from_slice(
@@ -102,6 +102,19 @@ impl<F: SmallField> LookupTableWrapper<F> {
         }
     }
 
+    pub fn try_lookup_row(&self, entry: &[F]) -> Option<u32> {
+        match self {
+            Self::W1(inner) => inner.try_lookup_row(entry),
+            Self::W2(inner) => inner.try_lookup_row(entry),
+            Self::W3(inner) => inner.try_lookup_row(entry),
+            Self::W4(inner) => inner.try_lookup_row(entry),
+            Self::W5(inner) => inner.try_lookup_row(entry),
+            Self::W6(inner) => inner.try_lookup_row(entry),
+            Self::W7(inner) => inner.try_lookup_row(entry),
+            Self::W8(inner) => inner.try_lookup_row(entry),
+        }
+    }
+
     pub fn num_keys(&self) -> usize {
         match self {
             Self::W1(inner) => inner.num_keys(),
@@ -386,6 +399,17 @@ impl<F: SmallField, const N: usize> LookupTable<F, N> {
         row_idx as u32
     }
 
+    pub fn try_lookup_row(&self, entry: &[F]) -> Option<u32> {
+        if entry.len() != N {
+            return None;
+        }
+        let mut key = [F::ZERO; N];
+        key.copy_from_slice(entry);
+        let key = ContentLookupKey(key);
+
+        self.content_cache.get(&key).map(|row_idx| *row_idx as u32)
+    }
+
     pub fn num_values(&self) -> usize {
         self.num_value_columns
     }
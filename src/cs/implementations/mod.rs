@@ -1,10 +1,14 @@
 use super::*;
 
+pub mod aggregation;
 pub mod buffering_source;
+pub mod call_sites;
 pub mod convenience;
 pub mod copy_permutation;
 pub mod cs;
+pub mod encoding;
 pub mod evaluator_data;
+pub mod evm_verifier;
 pub mod fast_serialization;
 pub mod fri;
 pub mod hints;
@@ -12,15 +16,19 @@ pub mod lookup_argument;
 pub mod lookup_argument_in_ext;
 pub mod lookup_placement;
 pub mod lookup_table;
+pub mod namespaces;
+pub mod out_of_core;
 pub mod polynomial;
 pub mod polynomial_storage;
 pub mod pow;
 pub mod proof;
+pub mod proof_layout;
 pub mod prover;
 pub mod reference_cs;
 pub mod satisfiability_test;
 pub mod setup;
 pub mod setup_storage;
+pub mod soundness;
 pub mod transcript;
 pub mod utils;
 pub mod verifier;
@@ -0,0 +1,253 @@
+use super::*;
+
+/// Resources used by the circuit within a namespace
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct NamespaceStats {
+    pub num_general_purpose_rows: usize,
+    pub num_specialized_gate_instances: usize,
+    pub num_variables: usize,
+    pub num_witnesses: usize,
+    pub num_lookups: usize,
+    pub num_copy_permutation_cells: usize,
+}
+
+impl NamespaceStats {
+    pub fn add_assign(&mut self, other: &Self) {
+        self.num_general_purpose_rows += other.num_general_purpose_rows;
+        self.num_specialized_gate_instances += other.num_specialized_gate_instances;
+        self.num_variables += other.num_variables;
+        self.num_witnesses += other.num_witnesses;
+        self.num_lookups += other.num_lookups;
+        self.num_copy_permutation_cells += other.num_copy_permutation_cells;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NamespaceNode {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// Resources used directly in this namespace, not including children
+    pub own_stats: NamespaceStats,
+}
+
+/// Hierarchical accounting of resources per named namespace. Namespaces with the same name
+/// under the same parent are merged, so a namespace pushed in a loop accumulates all iterations
+#[derive(Clone, Debug)]
+pub struct NamespacesTracker {
+    pub nodes: Vec<NamespaceNode>,
+    current: usize,
+}
+
+pub const ROOT_NAMESPACE_NAME: &str = "root";
+
+impl Default for NamespacesTracker {
+    fn default() -> Self {
+        Self {
+            nodes: vec![NamespaceNode {
+                name: ROOT_NAMESPACE_NAME.to_owned(),
+                parent: None,
+                children: vec![],
+                own_stats: NamespaceStats::default(),
+            }],
+            current: 0,
+        }
+    }
+}
+
+/// Nested report over the namespaces tree
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct NamespaceReport {
+    pub name: String,
+    pub own_stats: NamespaceStats,
+    /// Resources used in this namespace and all it's children
+    pub total_stats: NamespaceStats,
+    pub children: Vec<NamespaceReport>,
+}
+
+impl NamespaceReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report must serialize")
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+impl NamespacesTracker {
+    pub fn push(&mut self, name: &str) {
+        let existing = self.nodes[self.current]
+            .children
+            .iter()
+            .copied()
+            .find(|el| self.nodes[*el].name == name);
+        let idx = match existing {
+            Some(idx) => idx,
+            None => {
+                let idx = self.nodes.len();
+                self.nodes.push(NamespaceNode {
+                    name: name.to_owned(),
+                    parent: Some(self.current),
+                    children: vec![],
+                    own_stats: NamespaceStats::default(),
+                });
+                self.nodes[self.current].children.push(idx);
+
+                idx
+            }
+        };
+
+        self.current = idx;
+    }
+
+    pub fn pop(&mut self) {
+        self.current = self.nodes[self.current]
+            .parent
+            .expect("trying to pop a namespace, but none was pushed");
+    }
+
+    #[inline(always)]
+    pub fn current_stats_mut(&mut self) -> &mut NamespaceStats {
+        &mut self.nodes[self.current].own_stats
+    }
+
+    pub fn total_stats(&self, idx: usize) -> NamespaceStats {
+        let mut result = self.nodes[idx].own_stats;
+        for child in self.nodes[idx].children.iter() {
+            result.add_assign(&self.total_stats(*child));
+        }
+
+        result
+    }
+
+    pub fn report(&self) -> NamespaceReport {
+        self.report_for_node(0)
+    }
+
+    fn report_for_node(&self, idx: usize) -> NamespaceReport {
+        let node = &self.nodes[idx];
+        let children: Vec<_> = node
+            .children
+            .iter()
+            .map(|el| self.report_for_node(*el))
+            .collect();
+        let mut total_stats = node.own_stats;
+        for child in children.iter() {
+            total_stats.add_assign(&child.total_stats);
+        }
+
+        NamespaceReport {
+            name: node.name.clone(),
+            own_stats: node.own_stats,
+            total_stats,
+            children,
+        }
+    }
+
+    pub fn print_tree(&self) {
+        let report = self.report();
+        let total_rows = report.total_stats.num_general_purpose_rows;
+        print_report(&report, 0, total_rows);
+    }
+
+    pub fn to_json(&self) -> String {
+        self.report().to_json()
+    }
+}
+
+fn print_report(report: &NamespaceReport, depth: usize, total_rows: usize) {
+    let stats = &report.total_stats;
+    let rows_percentage = if total_rows == 0 {
+        0f64
+    } else {
+        (stats.num_general_purpose_rows as f64) * 100f64 / (total_rows as f64)
+    };
+    log!(
+        "{}{}: {} general purpose rows ({:.2}%), {} specialized gate instances, {} variables, {} witnesses, {} lookups, {} copy-permutation cells",
+        " ".repeat(depth * 2),
+        &report.name,
+        stats.num_general_purpose_rows,
+        rows_percentage,
+        stats.num_specialized_gate_instances,
+        stats.num_variables,
+        stats.num_witnesses,
+        stats.num_lookups,
+        stats.num_copy_permutation_cells,
+    );
+
+    for child in report.children.iter() {
+        print_report(child, depth + 1, total_rows);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn namespaces_are_merged_and_accumulated() {
+        let mut tracker = NamespacesTracker::default();
+        tracker.current_stats_mut().num_variables += 1;
+        for _ in 0..3 {
+            tracker.push("loop");
+            tracker.current_stats_mut().num_general_purpose_rows += 2;
+            tracker.push("inner \"body\"");
+            tracker.current_stats_mut().num_lookups += 1;
+            tracker.pop();
+            tracker.pop();
+        }
+
+        let report = tracker.report();
+        assert_eq!(report.children.len(), 1);
+        assert_eq!(report.children[0].own_stats.num_general_purpose_rows, 6);
+        assert_eq!(report.children[0].total_stats.num_lookups, 3);
+        assert_eq!(report.total_stats.num_variables, 1);
+        assert_eq!(report.total_stats.num_general_purpose_rows, 6);
+        assert_eq!(tracker.total_stats(0), report.total_stats);
+    }
+
+    #[test]
+    fn report_json_roundtrip() {
+        let mut tracker = NamespacesTracker::default();
+        tracker.current_stats_mut().num_witnesses += 5;
+        tracker.push("hash");
+        tracker.current_stats_mut().num_general_purpose_rows += 7;
+        tracker.current_stats_mut().num_copy_permutation_cells += 28;
+        tracker.push("round \"0\"\n");
+        tracker.current_stats_mut().num_lookups += 4;
+        tracker.pop();
+        tracker.pop();
+        tracker.push("range checks");
+        tracker.current_stats_mut().num_specialized_gate_instances += 3;
+        tracker.pop();
+
+        let report = tracker.report();
+        let json = tracker.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["name"], ROOT_NAMESPACE_NAME);
+        assert_eq!(value["children"][0]["children"][0]["name"], "round \"0\"\n");
+        assert_eq!(value["total_stats"]["num_lookups"], 4);
+
+        let decoded = NamespaceReport::from_json(&json).unwrap();
+        assert_eq!(decoded.to_json(), json);
+        assert_eq!(decoded.children.len(), 2);
+        assert_eq!(
+            decoded.children[0].children[0].own_stats,
+            report.children[0].children[0].own_stats
+        );
+        assert_eq!(decoded.children[1].own_stats, report.children[1].own_stats);
+        assert_eq!(decoded.total_stats, report.total_stats);
+
+        assert!(NamespaceReport::from_json("{\"name\":\"root\"}").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn unbalanced_pop_panics() {
+        let mut tracker = NamespacesTracker::default();
+        tracker.pop();
+    }
+}
@@ -0,0 +1,690 @@
+use super::fri::QuerySource;
+use super::polynomial::lde::ArcGenericLdeStorage;
+use super::polynomial::GenericPolynomial;
+use super::*;
+use crate::cs::oracle::merkle_tree::{MerkleProofSource, MerkleTreeWithCap};
+use crate::cs::oracle::TreeHasher;
+use crate::cs::traits::GoodAllocator;
+use crate::utils::allocate_in_with_alignment_of;
+
+use memmap2::MmapMut;
+use std::alloc::Global;
+use std::fs::{File, OpenOptions};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// Out-of-core proving: full LDEs of the witness, second stage and quotient oracles are written
+// into memory mapped files as soon as they are computed, and in-memory copies are released right away.
+// Merkle trees are built by streaming over those files and are spilled too, so only the caps stay in memory.
+// Quotient and DEEP are then computed coset by coset, loading only one coset of every spilled oracle
+// at a time, and FRI queries only touch the leafs and paths they open. So peak memory is bounded by
+// the setup, one oracle's LDE while it is being computed, and one coset of the rest of the trace.
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OutOfCoreConfig {
+    pub spill_directory: PathBuf,
+}
+
+impl OutOfCoreConfig {
+    pub fn new<T: Into<PathBuf>>(spill_directory: T) -> Self {
+        Self {
+            spill_directory: spill_directory.into(),
+        }
+    }
+
+    fn create_spill_file(&self, name: &str) -> std::io::Result<(File, PathBuf)> {
+        static SPILL_FILES_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        std::fs::create_dir_all(&self.spill_directory)?;
+        let idx = SPILL_FILES_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = self.spill_directory.join(format!(
+            "boojum_{}_{}_{}.bin",
+            name,
+            std::process::id(),
+            idx
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+
+        Ok((file, path))
+    }
+
+    fn contains(&self, path: &Path) -> std::io::Result<bool> {
+        let directory = self.spill_directory.canonicalize()?;
+        let path = path.canonicalize()?;
+
+        Ok(path.parent() == Some(directory.as_path()))
+    }
+}
+
+/// File that backs a spilled oracle or tree. Files created by the prover are owned and
+/// removed on drop. Files referenced by a checkpoint are not owned after deserialization
+/// and must be claimed with the same `OutOfCoreConfig`, so loading a stale or untrusted
+/// checkpoint never removes files outside of the spill directory.
+#[derive(Debug)]
+struct SpillFile {
+    path: PathBuf,
+    remove_on_drop: bool,
+}
+
+impl SpillFile {
+    fn claim(&mut self, config: &OutOfCoreConfig) -> std::io::Result<()> {
+        if config.contains(&self.path)? == false {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!(
+                    "{:?} is not in the spill directory {:?}",
+                    self.path, config.spill_directory
+                ),
+            ));
+        }
+        self.remove_on_drop = true;
+
+        Ok(())
+    }
+
+    fn map_existing(&self, expected_size: usize) -> std::io::Result<MmapMut> {
+        let file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+        let size = file.metadata()?.len();
+        if size != expected_size as u64 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "spilled file {:?} has size {}, while {} was expected",
+                    self.path, size, expected_size
+                ),
+            ));
+        }
+        // SAFETY: file is only ever written by the prover that created it
+        unsafe { MmapMut::map_mut(&file) }
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        if self.remove_on_drop {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+// We only spill plain values (field elements and hashes), that have no padding and no pointers inside
+fn mapped_slice<T: Copy>(mmap: &MmapMut) -> &[T] {
+    debug_assert!(mmap.as_ptr().addr() % std::mem::align_of::<T>() == 0);
+    let len = mmap.len() / std::mem::size_of::<T>();
+    // SAFETY: mapping is page aligned and only ever contains values of type T written by us
+    unsafe { std::slice::from_raw_parts(mmap.as_ptr().cast::<T>(), len) }
+}
+
+fn mapped_slice_mut<T: Copy>(mmap: &mut MmapMut) -> &mut [T] {
+    debug_assert!(mmap.as_ptr().addr() % std::mem::align_of::<T>() == 0);
+    let len = mmap.len() / std::mem::size_of::<T>();
+    // SAFETY: same as above
+    unsafe { std::slice::from_raw_parts_mut(mmap.as_mut_ptr().cast::<T>(), len) }
+}
+
+/// Flat LDE values of a set of polynomials, stored in a memory mapped file.
+/// Every column is laid out coset by coset, so element `i` of the column
+/// belongs to the leaf `i` of the corresponding oracle. All the cosets of the LDE
+/// are stored, even if only some of them are committed.
+pub struct SpilledOracle<F: SmallField> {
+    // mapping is released before the file is removed
+    mmap: MmapMut,
+    file: SpillFile,
+    num_columns: usize,
+    lde_factor: usize,
+    domain_size: usize,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: SmallField> std::fmt::Debug for SpilledOracle<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpilledOracle")
+            .field("file", &self.file)
+            .field("num_columns", &self.num_columns)
+            .field("lde_factor", &self.lde_factor)
+            .field("domain_size", &self.domain_size)
+            .finish()
+    }
+}
+
+impl<F: SmallField> SpilledOracle<F> {
+    pub fn spill_from_lde_storages<
+        P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+        A: GoodAllocator,
+        B: GoodAllocator,
+    >(
+        config: &OutOfCoreConfig,
+        name: &str,
+        sources: &[ArcGenericLdeStorage<F, P, A, B>],
+        worker: &Worker,
+    ) -> std::io::Result<Self> {
+        assert!(!sources.is_empty());
+        assert!(std::mem::align_of::<F>() <= 8);
+
+        let now = std::time::Instant::now();
+
+        let num_columns = sources.len();
+        let lde_factor = sources[0].outer_len();
+        let domain_size = sources[0].inner_len() * P::SIZE_FACTOR;
+        let column_size = lde_factor * domain_size;
+        for source in sources.iter() {
+            assert_eq!(source.outer_len(), lde_factor);
+            assert_eq!(source.inner_len() * P::SIZE_FACTOR, domain_size);
+        }
+
+        let (file, path) = config.create_spill_file(name)?;
+        let size_in_bytes = num_columns * column_size * std::mem::size_of::<F>();
+        file.set_len(size_in_bytes as u64)?;
+        // SAFETY: file was just created by us and is not shared with anyone else
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+
+        let mut new = Self {
+            mmap,
+            file: SpillFile {
+                path,
+                remove_on_drop: true,
+            },
+            num_columns,
+            lde_factor,
+            domain_size,
+            _marker: std::marker::PhantomData,
+        };
+
+        let dst = mapped_slice_mut::<F>(&mut new.mmap);
+        worker.scope(num_columns, |scope, chunk_size| {
+            for (dst, src) in dst
+                .chunks_mut(chunk_size * column_size)
+                .zip(sources.chunks(chunk_size))
+            {
+                scope.spawn(move |_| {
+                    for (dst, src) in dst.chunks_mut(column_size).zip(src.iter()) {
+                        for (dst, coset) in dst.chunks_mut(domain_size).zip(src.storage.iter()) {
+                            dst.copy_from_slice(P::slice_into_base_slice(&coset.storage));
+                        }
+                    }
+                });
+            }
+        });
+
+        new.mmap.flush()?;
+
+        log!(
+            "Spilled {} columns of size 2^{} for {} oracle in {:?}",
+            num_columns,
+            column_size.trailing_zeros(),
+            name,
+            now.elapsed()
+        );
+
+        Ok(new)
+    }
+
+    /// Keeps the file on disk when the oracle is dropped, so that a checkpoint that
+    /// references it can be resumed later
+    pub fn persist(&mut self) {
+        self.file.remove_on_drop = false;
+    }
+
+    /// Takes ownership of the file referenced by a checkpoint, so it's removed when the
+    /// oracle is dropped. Fails if the file is not in the spill directory of `config`
+    pub fn claim(&mut self, config: &OutOfCoreConfig) -> std::io::Result<()> {
+        self.file.claim(config)
+    }
+
+    #[inline]
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    #[inline]
+    pub fn lde_factor(&self) -> usize {
+        self.lde_factor
+    }
+
+    #[inline]
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    #[inline]
+    pub fn column(&self, idx: usize) -> &[F] {
+        let column_size = self.lde_factor * self.domain_size;
+        &mapped_slice::<F>(&self.mmap)[(idx * column_size)..((idx + 1) * column_size)]
+    }
+
+    pub fn columns(&self) -> Vec<&[F]> {
+        (0..self.num_columns).map(|idx| self.column(idx)).collect()
+    }
+
+    /// Builds the tree over the first `lde_factor` cosets, that are the committed ones,
+    /// with `salt` appended to every leaf
+    pub fn build_merkle_tree<
+        H: TreeHasher<F>,
+        P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    >(
+        &self,
+        lde_factor: usize,
+        salt: &[ArcGenericLdeStorage<F, P, Global, Global>],
+        cap_size: usize,
+        worker: &Worker,
+    ) -> MerkleTreeWithCap<F, H> {
+        assert!(lde_factor <= self.lde_factor);
+        let committed_size = lde_factor * self.domain_size;
+        // salt only has a few columns, so it's fine to flatten it in memory
+        let salt: Vec<Vec<F>> = salt
+            .iter()
+            .map(|column| {
+                assert_eq!(column.outer_len(), lde_factor);
+                let mut flattened = Vec::with_capacity(committed_size);
+                for coset in column.storage.iter() {
+                    flattened.extend_from_slice(P::slice_into_base_slice(&coset.storage));
+                }
+
+                flattened
+            })
+            .collect();
+        let columns: Vec<&[F]> = (0..self.num_columns)
+            .map(|idx| &self.column(idx)[..committed_size])
+            .chain(salt.iter().map(|el| &el[..]))
+            .collect();
+        MerkleTreeWithCap::<F, H>::construct_by_chunking_from_flat_slices(
+            &columns, 1, cap_size, worker,
+        )
+    }
+
+    /// Copies the given cosets of every column into memory, in the same shape
+    /// as in-memory LDE storages. Cosets are re-enumerated from 0 in the result
+    pub fn load_cosets<P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>>(
+        &self,
+        cosets: Range<usize>,
+        worker: &Worker,
+    ) -> Vec<ArcGenericLdeStorage<F, P, Global, Global>> {
+        assert!(cosets.end <= self.lde_factor);
+        assert!(cosets.len().is_power_of_two());
+
+        let domain_size = self.domain_size;
+        let mut result: Vec<_> = (0..self.num_columns)
+            .map(|_| ArcGenericLdeStorage::empty_with_capacity_in(cosets.len(), Global))
+            .collect();
+
+        worker.scope(self.num_columns, |scope, chunk_size| {
+            for (chunk_idx, dst) in result.chunks_mut(chunk_size).enumerate() {
+                let cosets = cosets.clone();
+                scope.spawn(move |_| {
+                    for (idx, dst) in dst.iter_mut().enumerate() {
+                        let column = self.column(chunk_idx * chunk_size + idx);
+                        for coset_idx in cosets.clone() {
+                            let src =
+                                &column[(coset_idx * domain_size)..((coset_idx + 1) * domain_size)];
+                            let mut values =
+                                allocate_in_with_alignment_of::<F, P, Global>(domain_size, Global);
+                            values.extend_from_slice(src);
+                            let values = P::vec_from_base_vec(values);
+                            dst.storage
+                                .push(Arc::new(GenericPolynomial::from_storage(values)));
+                        }
+                    }
+                });
+            }
+        });
+
+        result
+    }
+}
+
+// Spilled oracle is checkpointed by reference: we only store the location and shape, and
+// the file is mapped again on deserialization. Files are removed when the owning oracle is dropped,
+// so the oracle must be persisted if it's dropped after the checkpoint is taken and before proving
+// is resumed. If the process is killed files just stay on disk and can be picked up on resume.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SpilledOracleDescription {
+    path: PathBuf,
+    num_columns: usize,
+    lde_factor: usize,
+    domain_size: usize,
+}
+
+impl<F: SmallField> serde::Serialize for SpilledOracle<F> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let description = SpilledOracleDescription {
+            path: self.file.path.clone(),
+            num_columns: self.num_columns,
+            lde_factor: self.lde_factor,
+            domain_size: self.domain_size,
+        };
+
+        serde::Serialize::serialize(&description, serializer)
+    }
+}
+
+impl<'de, F: SmallField> serde::Deserialize<'de> for SpilledOracle<F> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let SpilledOracleDescription {
+            path,
+            num_columns,
+            lde_factor,
+            domain_size,
+        } = <SpilledOracleDescription as serde::Deserialize>::deserialize(deserializer)?;
+
+        // not owned until claimed
+        let file = SpillFile {
+            path,
+            remove_on_drop: false,
+        };
+        let expected_size = num_columns * lde_factor * domain_size * std::mem::size_of::<F>();
+        let mmap = file
+            .map_existing(expected_size)
+            .map_err(|err| D::Error::custom(format!("can not map {:?}: {}", file.path, err)))?;
+
+        Ok(Self {
+            mmap,
+            file,
+            num_columns,
+            lde_factor,
+            domain_size,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<F: SmallField> QuerySource<F> for SpilledOracle<F> {
+    fn get_elements(
+        &self,
+        lde_factor: usize,
+        coset_idx: usize,
+        domain_size: usize,
+        inner_idx: usize,
+        num_elements: usize,
+        dst: &mut Vec<F>,
+    ) {
+        assert!(lde_factor > coset_idx);
+        assert!(lde_factor <= self.lde_factor);
+        assert_eq!(domain_size, self.domain_size);
+        assert_eq!(
+            num_elements, 1,
+            "we query setup/witness oracles only by 1 element per leaf"
+        );
+        let idx = coset_idx * domain_size + inner_idx;
+        for column_idx in 0..self.num_columns {
+            dst.push(self.column(column_idx)[idx]);
+        }
+    }
+}
+
+/// Merkle tree of a spilled oracle. Leaf hashes and every layer of nodes are stored one after
+/// another in a memory mapped file, and only the cap is kept in memory.
+pub struct SpilledMerkleTree<F: SmallField, H: TreeHasher<F>> {
+    mmap: MmapMut,
+    file: SpillFile,
+    num_leafs: usize,
+    num_node_layers: usize,
+    cap: Vec<H::Output>,
+    _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: SmallField, H: TreeHasher<F>> SpilledMerkleTree<F, H> {
+    pub fn spill(
+        config: &OutOfCoreConfig,
+        name: &str,
+        tree: MerkleTreeWithCap<F, H>,
+    ) -> std::io::Result<Self> {
+        let num_leafs = tree.leaf_hashes.len();
+        let num_node_layers = tree.node_hashes_enumerated_from_leafs.len();
+        let total_num_hashes = num_leafs
+            + tree
+                .node_hashes_enumerated_from_leafs
+                .iter()
+                .map(|el| el.len())
+                .sum::<usize>();
+
+        let (file, path) = config.create_spill_file(&format!("{}_tree", name))?;
+        file.set_len((total_num_hashes * std::mem::size_of::<H::Output>()) as u64)?;
+        // SAFETY: file was just created by us and is not shared with anyone else
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+
+        let dst = mapped_slice_mut::<H::Output>(&mut mmap);
+        let mut offset = 0;
+        for layer in
+            std::iter::once(&tree.leaf_hashes).chain(tree.node_hashes_enumerated_from_leafs.iter())
+        {
+            dst[offset..(offset + layer.len())].copy_from_slice(layer);
+            offset += layer.len();
+        }
+        debug_assert_eq!(offset, total_num_hashes);
+        mmap.flush()?;
+
+        let cap = tree.get_cap();
+
+        Ok(Self {
+            mmap,
+            file: SpillFile {
+                path,
+                remove_on_drop: true,
+            },
+            num_leafs,
+            num_node_layers,
+            cap,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    /// Same as `SpilledOracle::persist`
+    pub fn persist(&mut self) {
+        self.file.remove_on_drop = false;
+    }
+
+    /// Same as `SpilledOracle::claim`
+    pub fn claim(&mut self, config: &OutOfCoreConfig) -> std::io::Result<()> {
+        self.file.claim(config)
+    }
+
+    fn total_num_hashes(num_leafs: usize, num_node_layers: usize) -> usize {
+        (0..=num_node_layers).map(|layer| num_leafs >> layer).sum()
+    }
+
+    // layer 0 is leaf hashes
+    fn layer(&self, idx: usize) -> &[H::Output] {
+        let offset = Self::total_num_hashes(self.num_leafs, idx) - (self.num_leafs >> idx);
+        let size = self.num_leafs >> idx;
+
+        &mapped_slice::<H::Output>(&self.mmap)[offset..(offset + size)]
+    }
+}
+
+impl<F: SmallField, H: TreeHasher<F>> MerkleProofSource<F, H> for SpilledMerkleTree<F, H> {
+    fn get_cap(&self) -> Vec<H::Output> {
+        self.cap.clone()
+    }
+
+    // same as `MerkleTreeWithCap::get_proof`
+    fn get_proof(&self, idx: usize) -> (H::Output, Vec<H::Output>) {
+        let mut result = Vec::with_capacity(self.num_node_layers);
+        let leaf_hash = self.layer(0)[idx];
+        let mut idx = idx;
+        for layer in 0..self.num_node_layers {
+            result.push(self.layer(layer)[idx ^ 1]);
+            idx >>= 1;
+        }
+
+        (leaf_hash, result)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound = "T: serde::Serialize + serde::de::DeserializeOwned")]
+struct SpilledMerkleTreeDescription<T> {
+    path: PathBuf,
+    num_leafs: usize,
+    num_node_layers: usize,
+    cap: Vec<T>,
+}
+
+impl<F: SmallField, H: TreeHasher<F>> serde::Serialize for SpilledMerkleTree<F, H>
+where
+    H::Output: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let description = SpilledMerkleTreeDescription {
+            path: self.file.path.clone(),
+            num_leafs: self.num_leafs,
+            num_node_layers: self.num_node_layers,
+            cap: self.cap.clone(),
+        };
+
+        serde::Serialize::serialize(&description, serializer)
+    }
+}
+
+impl<'de, F: SmallField, H: TreeHasher<F>> serde::Deserialize<'de> for SpilledMerkleTree<F, H>
+where
+    H::Output: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let SpilledMerkleTreeDescription {
+            path,
+            num_leafs,
+            num_node_layers,
+            cap,
+        } = <SpilledMerkleTreeDescription<H::Output> as serde::Deserialize>::deserialize(
+            deserializer,
+        )?;
+
+        if num_leafs.is_power_of_two() == false
+            || num_node_layers > num_leafs.trailing_zeros() as usize
+        {
+            return Err(D::Error::custom(format!(
+                "invalid shape of the spilled tree {:?}",
+                path
+            )));
+        }
+
+        // not owned until claimed
+        let file = SpillFile {
+            path,
+            remove_on_drop: false,
+        };
+        let expected_size =
+            Self::total_num_hashes(num_leafs, num_node_layers) * std::mem::size_of::<H::Output>();
+        let mmap = file
+            .map_existing(expected_size)
+            .map_err(|err| D::Error::custom(format!("can not map {:?}: {}", file.path, err)))?;
+
+        Ok(Self {
+            mmap,
+            file,
+            num_leafs,
+            num_node_layers,
+            cap,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+/// Merkle tree of a committed oracle, that is either kept in memory or was spilled to disk.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub enum OracleTree<F: SmallField, H: TreeHasher<F>> {
+    InMemory(MerkleTreeWithCap<F, H>),
+    Spilled(SpilledMerkleTree<F, H>),
+}
+
+impl<F: SmallField, H: TreeHasher<F>> OracleTree<F, H> {
+    pub fn persist(&mut self) {
+        if let Self::Spilled(tree) = self {
+            tree.persist();
+        }
+    }
+
+    pub fn claim(&mut self, config: &OutOfCoreConfig) -> std::io::Result<()> {
+        match self {
+            Self::InMemory(_) => Ok(()),
+            Self::Spilled(tree) => tree.claim(config),
+        }
+    }
+}
+
+impl<F: SmallField, H: TreeHasher<F>> MerkleProofSource<F, H> for OracleTree<F, H> {
+    fn get_cap(&self) -> Vec<H::Output> {
+        match self {
+            Self::InMemory(tree) => MerkleProofSource::get_cap(tree),
+            Self::Spilled(tree) => tree.get_cap(),
+        }
+    }
+
+    fn get_proof(&self, idx: usize) -> (H::Output, Vec<H::Output>) {
+        match self {
+            Self::InMemory(tree) => MerkleProofSource::get_proof(tree, idx),
+            Self::Spilled(tree) => tree.get_proof(idx),
+        }
+    }
+}
+
+/// Source of leafs for oracle queries, that is either kept in memory or was spilled to disk.
+pub enum OracleLeafSource<'a, F: SmallField, S: QuerySource<F>> {
+    InMemory(&'a S),
+    Spilled(&'a SpilledOracle<F>),
+}
+
+impl<'a, F: SmallField, S: QuerySource<F>> OracleLeafSource<'a, F, S> {
+    /// Prefers spilled copy if it exists
+    pub fn select(in_memory: Option<&'a S>, spilled: Option<&'a SpilledOracle<F>>) -> Self {
+        match (spilled, in_memory) {
+            (Some(spilled), _) => Self::Spilled(spilled),
+            (None, Some(in_memory)) => Self::InMemory(in_memory),
+            (None, None) => panic!("oracle must be either in memory or spilled"),
+        }
+    }
+}
+
+impl<'a, F: SmallField, S: QuerySource<F>> QuerySource<F> for OracleLeafSource<'a, F, S> {
+    fn get_elements(
+        &self,
+        lde_factor: usize,
+        coset_idx: usize,
+        domain_size: usize,
+        inner_idx: usize,
+        num_elements: usize,
+        dst: &mut Vec<F>,
+    ) {
+        match self {
+            Self::InMemory(source) => source.get_elements(
+                lde_factor,
+                coset_idx,
+                domain_size,
+                inner_idx,
+                num_elements,
+                dst,
+            ),
+            Self::Spilled(source) => source.get_elements(
+                lde_factor,
+                coset_idx,
+                domain_size,
+                inner_idx,
+                num_elements,
+                dst,
+            ),
+        }
+    }
+}
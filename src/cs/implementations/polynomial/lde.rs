@@ -307,6 +307,20 @@ impl<
         Self { storage }
     }
 
+    // shallow clone of some consecutive cosets, for readonly
+    #[track_caller]
+    pub fn subset_for_cosets(&self, cosets: std::ops::Range<usize>) -> Self {
+        assert!(cosets.end <= self.storage.len());
+        assert!(cosets.len().is_power_of_two());
+
+        let mut storage = Vec::with_capacity_in(cosets.len(), B::default());
+        for i in cosets {
+            storage.push(Arc::clone(&self.storage[i]));
+        }
+
+        Self { storage }
+    }
+
     // deep clone, when we want to create a new one
     pub fn owned_subset_for_degree(&self, degree: usize) -> Self {
         assert!(degree <= self.storage.len());
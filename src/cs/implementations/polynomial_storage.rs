@@ -13,8 +13,11 @@ use crate::field::PrimeField;
 use std::ops::Range;
 use std::sync::Arc;
 
-#[derive(Derivative)]
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone, Debug)]
+#[serde(
+    bound = "F: serde::Serialize + serde::de::DeserializeOwned, P: serde::Serialize + serde::de::DeserializeOwned"
+)]
 pub struct WitnessStorage<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F> = F,
@@ -24,15 +27,25 @@ pub struct WitnessStorage<
     // We store full LDEs of the original polynomials.
     // For those we can produce adapters to properly iterate over
     // future leafs of the oracles
+    #[serde(serialize_with = "crate::utils::serialize_vec_with_allocator")]
+    #[serde(deserialize_with = "crate::utils::deserialize_vec_with_allocator")]
     pub variables_columns: Vec<ArcGenericLdeStorage<F, P, A, B>, B>,
+    #[serde(serialize_with = "crate::utils::serialize_vec_with_allocator")]
+    #[serde(deserialize_with = "crate::utils::deserialize_vec_with_allocator")]
     pub witness_columns: Vec<ArcGenericLdeStorage<F, P, A, B>, B>,
+    #[serde(serialize_with = "crate::utils::serialize_vec_with_allocator")]
+    #[serde(deserialize_with = "crate::utils::deserialize_vec_with_allocator")]
     pub lookup_multiplicities_polys: Vec<ArcGenericLdeStorage<F, P, A, B>, B>,
 }
 
-#[derive(Derivative)]
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone, Debug)]
+#[serde(
+    bound = "F: serde::Serialize + serde::de::DeserializeOwned, P: serde::Serialize + serde::de::DeserializeOwned"
+)]
 pub struct SecondStageProductsStorage<
     F: PrimeField,
+    const N: usize,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F> = F,
     A: GoodAllocator = Global,
     B: GoodAllocator = Global,
@@ -40,10 +53,17 @@ pub struct SecondStageProductsStorage<
     // We store full LDEs of the original polynomials.
     // For those we can produce adapters to properly iterate over
     // future leafs of the oracles
-    pub z_poly: [ArcGenericLdeStorage<F, P, A, B>; 2],
-    pub intermediate_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; 2], B>,
-    pub lookup_witness_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; 2], B>,
-    pub lookup_multiplicities_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; 2], B>,
+    #[serde(with = "crate::serde_utils::BigArraySerde")]
+    pub z_poly: [ArcGenericLdeStorage<F, P, A, B>; N],
+    #[serde(serialize_with = "crate::serde_utils::serialize_vec_of_arrays_with_allocator")]
+    #[serde(deserialize_with = "crate::serde_utils::deserialize_vec_of_arrays_with_allocator")]
+    pub intermediate_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; N], B>,
+    #[serde(serialize_with = "crate::serde_utils::serialize_vec_of_arrays_with_allocator")]
+    #[serde(deserialize_with = "crate::serde_utils::deserialize_vec_of_arrays_with_allocator")]
+    pub lookup_witness_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; N], B>,
+    #[serde(serialize_with = "crate::serde_utils::serialize_vec_of_arrays_with_allocator")]
+    #[serde(deserialize_with = "crate::serde_utils::deserialize_vec_of_arrays_with_allocator")]
+    pub lookup_multiplicities_encoding_polys: Vec<[ArcGenericLdeStorage<F, P, A, B>; N], B>,
 }
 
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
@@ -672,6 +692,17 @@ impl<
             .chain(self.constant_columns.iter())
             .chain(self.lookup_tables_columns.iter())
     }
+
+    // shallow view over some cosets of the LDE
+    pub(crate) fn subset_for_cosets(&self, cosets: Range<usize>) -> Self {
+        Self {
+            copy_permutation_polys: subset_for_cosets(&self.copy_permutation_polys, &cosets),
+            constant_columns: subset_for_cosets(&self.constant_columns, &cosets),
+            lookup_tables_columns: subset_for_cosets(&self.lookup_tables_columns, &cosets),
+            table_ids_column_idxes: self.table_ids_column_idxes.clone(),
+            used_lde_degree: cosets.len(),
+        }
+    }
 }
 
 impl<
@@ -689,14 +720,59 @@ impl<
             .chain(self.witness_columns.iter())
             .chain(self.lookup_multiplicities_polys.iter())
     }
+
+    pub(crate) fn into_flattened_source(
+        self,
+    ) -> impl Iterator<Item = ArcGenericLdeStorage<F, P, A, B>> {
+        self.variables_columns
+            .into_iter()
+            .chain(self.witness_columns)
+            .chain(self.lookup_multiplicities_polys)
+    }
+
+    // shallow view over some cosets of the LDE
+    pub(crate) fn subset_for_cosets(&self, cosets: Range<usize>) -> Self {
+        Self {
+            variables_columns: subset_for_cosets(&self.variables_columns, &cosets),
+            witness_columns: subset_for_cosets(&self.witness_columns, &cosets),
+            lookup_multiplicities_polys: subset_for_cosets(
+                &self.lookup_multiplicities_polys,
+                &cosets,
+            ),
+        }
+    }
+
+    // inverse of `flattened_source`
+    pub(crate) fn from_flattened_source(
+        source: impl IntoIterator<Item = ArcGenericLdeStorage<F, P, A, B>>,
+        num_variables_columns: usize,
+        num_witness_columns: usize,
+    ) -> Self {
+        let mut source = source.into_iter();
+        let mut variables_columns = Vec::with_capacity_in(num_variables_columns, B::default());
+        variables_columns.extend((&mut source).take(num_variables_columns));
+        let mut witness_columns = Vec::with_capacity_in(num_witness_columns, B::default());
+        witness_columns.extend((&mut source).take(num_witness_columns));
+        let mut lookup_multiplicities_polys = Vec::new_in(B::default());
+        lookup_multiplicities_polys.extend(source);
+        assert_eq!(variables_columns.len(), num_variables_columns);
+        assert_eq!(witness_columns.len(), num_witness_columns);
+
+        Self {
+            variables_columns,
+            witness_columns,
+            lookup_multiplicities_polys,
+        }
+    }
 }
 
 impl<
         F: PrimeField,
+        const N: usize,
         P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
         A: GoodAllocator,
         B: GoodAllocator,
-    > SecondStageProductsStorage<F, P, A, B>
+    > SecondStageProductsStorage<F, N, P, A, B>
 {
     pub(crate) fn flattened_source(
         &self,
@@ -707,6 +783,80 @@ impl<
             .chain(self.lookup_witness_encoding_polys.iter().flatten())
             .chain(self.lookup_multiplicities_encoding_polys.iter().flatten())
     }
+
+    pub(crate) fn into_flattened_source(
+        self,
+    ) -> impl Iterator<Item = ArcGenericLdeStorage<F, P, A, B>> {
+        self.z_poly
+            .into_iter()
+            .chain(self.intermediate_polys.into_iter().flatten())
+            .chain(self.lookup_witness_encoding_polys.into_iter().flatten())
+            .chain(
+                self.lookup_multiplicities_encoding_polys
+                    .into_iter()
+                    .flatten(),
+            )
+    }
+
+    // shallow view over some cosets of the LDE
+    pub(crate) fn subset_for_cosets(&self, cosets: Range<usize>) -> Self {
+        Self::from_flattened_source(
+            self.flattened_source()
+                .map(|el| el.subset_for_cosets(cosets.clone())),
+            self.intermediate_polys.len(),
+            self.lookup_witness_encoding_polys.len(),
+        )
+    }
+
+    // inverse of `flattened_source`, where every extension field poly takes N consecutive base polys
+    pub(crate) fn from_flattened_source(
+        source: impl IntoIterator<Item = ArcGenericLdeStorage<F, P, A, B>>,
+        num_intermediate_polys: usize,
+        num_lookup_subarguments: usize,
+    ) -> Self {
+        let mut source = source.into_iter().peekable();
+        let next_poly = |source: &mut std::iter::Peekable<_>| -> [ArcGenericLdeStorage<F, P, A, B>; N] {
+            std::array::from_fn(|_| source.next().expect("must have all coefficients"))
+        };
+        let z_poly = next_poly(&mut source);
+        let mut intermediate_polys = Vec::with_capacity_in(num_intermediate_polys, B::default());
+        for _ in 0..num_intermediate_polys {
+            intermediate_polys.push(next_poly(&mut source));
+        }
+        let mut lookup_witness_encoding_polys =
+            Vec::with_capacity_in(num_lookup_subarguments, B::default());
+        for _ in 0..num_lookup_subarguments {
+            lookup_witness_encoding_polys.push(next_poly(&mut source));
+        }
+        let mut lookup_multiplicities_encoding_polys = Vec::new_in(B::default());
+        while source.peek().is_some() {
+            lookup_multiplicities_encoding_polys.push(next_poly(&mut source));
+        }
+
+        Self {
+            z_poly,
+            intermediate_polys,
+            lookup_witness_encoding_polys,
+            lookup_multiplicities_encoding_polys,
+        }
+    }
+}
+
+fn subset_for_cosets<
+    F: PrimeField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    A: GoodAllocator,
+    B: GoodAllocator,
+>(
+    columns: &[ArcGenericLdeStorage<F, P, A, B>],
+    cosets: &Range<usize>,
+) -> Vec<ArcGenericLdeStorage<F, P, A, B>, B> {
+    let mut result = Vec::with_capacity_in(columns.len(), B::default());
+    for el in columns.iter() {
+        result.push(el.subset_for_cosets(cosets.clone()));
+    }
+
+    result
 }
 
 #[cfg(test)]
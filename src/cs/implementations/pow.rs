@@ -223,3 +223,205 @@ impl PoWRunner for Keccak256 {
         u64::from_le_bytes(le_bytes).trailing_zeros() >= pow_bits
     }
 }
+
+use crate::algebraic_props::round_function::{AbsorptionModeTrait, AlgebraicRoundFunction};
+use crate::algebraic_props::sponge::SimpleAlgebraicSponge;
+
+const ALGEBRAIC_NO_RESULT: u64 = u64::MAX;
+const ALGEBRAIC_ROUNDS_PER_INVOCAITON: usize = 1 << 16u32;
+
+fn seed_into_sponge_field<F: SmallField, T: SmallField>(seed: Vec<T>) -> Vec<F> {
+    assert_eq!(
+        T::CHAR,
+        F::CHAR,
+        "algebraic PoW must be run over the same field as the sponge"
+    );
+
+    seed.into_iter()
+        .map(|el| F::from_u64_unchecked(el.as_u64_reduced()))
+        .collect()
+}
+
+fn bytes_into_sponge_field<F: SmallField>(seed: Vec<u8>) -> Vec<F> {
+    assert!(
+        F::CAPACITY_BITS >= 32,
+        "algebraic PoW requires a field with at least 32 bits of capacity"
+    );
+    seed.chunks(4)
+        .map(|chunk| {
+            let mut le_bytes = [0u8; 4];
+            le_bytes[..chunk.len()].copy_from_slice(chunk);
+            F::from_u64_unchecked(u32::from_le_bytes(le_bytes) as u64)
+        })
+        .collect()
+}
+
+/// Algebraic sponges absorb the seed as field elements, and then the challenge as two 32-bit limbs
+/// (the same way as it's committed to the transcript). The first element of the commitment
+/// is expected to have at least `pow_bits` trailing zeroes. Unlike for byte-oriented hashes
+/// it's cheap to check such PoW in-circuit.
+///
+/// Both the seed bytes and the challenge limbs must fit into a field element, so fields with
+/// `CAPACITY_BITS < 32` (e.g. BabyBear) are not supported and running PoW over them panics.
+/// Proofs over such fields have to use `pow_bits: 0` or a byte-oriented PoW runner
+impl<
+        F: SmallField,
+        R: AlgebraicRoundFunction<F, AW, SW, CW>,
+        M: AbsorptionModeTrait<F>,
+        const AW: usize,
+        const SW: usize,
+        const CW: usize,
+    > SimpleAlgebraicSponge<F, AW, SW, CW, R, M>
+{
+    #[inline]
+    fn pow_challenge_is_valid(base_sponge: &Self, pow_bits: u32, challenge: u64) -> bool {
+        let (low, high) = (challenge as u32, (challenge >> 32) as u32);
+        let mut sponge = *base_sponge;
+        sponge.absorb(&[
+            F::from_u64_unchecked(low as u64),
+            F::from_u64_unchecked(high as u64),
+        ]);
+        let [result] = sponge.finalize::<1>();
+
+        result.as_u64_reduced().trailing_zeros() >= pow_bits
+    }
+}
+
+impl<
+        F: SmallField,
+        R: AlgebraicRoundFunction<F, AW, SW, CW>,
+        M: AbsorptionModeTrait<F>,
+        const AW: usize,
+        const SW: usize,
+        const CW: usize,
+    > PoWRunner for SimpleAlgebraicSponge<F, AW, SW, CW, R, M>
+{
+    fn run_from_field_elements<T: SmallField>(seed: Vec<T>, pow_bits: u32, worker: &Worker) -> u64 {
+        assert!(pow_bits <= 32);
+        assert!(
+            F::CAPACITY_BITS >= 32,
+            "algebraic PoW requires a field with at least 32 bits of capacity"
+        );
+
+        let seed = seed_into_sponge_field::<F, T>(seed);
+        let mut base_sponge = Self::default();
+        base_sponge.absorb(&seed);
+
+        if pow_bits <= ALGEBRAIC_ROUNDS_PER_INVOCAITON.trailing_zeros() {
+            // serial case
+            log!("Do serial PoW");
+            for challenge in 0u64..(ALGEBRAIC_NO_RESULT - 1) {
+                if Self::pow_challenge_is_valid(&base_sponge, pow_bits, challenge) {
+                    return challenge;
+                }
+            }
+        }
+
+        use std::sync::atomic::AtomicU64;
+        use std::sync::atomic::Ordering;
+
+        let result = std::sync::Arc::new(AtomicU64::new(ALGEBRAIC_NO_RESULT));
+
+        log!("Do parallel PoW");
+
+        let pow_rounds_per_invocation = ALGEBRAIC_ROUNDS_PER_INVOCAITON as u64;
+        // it's good to parallelize
+        let num_workers = worker.num_cores as u64;
+        worker.scope(0, |scope, _| {
+            for worker_idx in 0..num_workers {
+                let result = std::sync::Arc::clone(&result);
+                scope.spawn(move |_| {
+                    for i in
+                        0..((ALGEBRAIC_NO_RESULT - 1) / num_workers / pow_rounds_per_invocation)
+                    {
+                        let base = (worker_idx + i * num_workers) * pow_rounds_per_invocation;
+                        let current_flag = result.load(Ordering::Relaxed);
+                        if current_flag == ALGEBRAIC_NO_RESULT {
+                            for j in 0..pow_rounds_per_invocation {
+                                let challenge_u64 = base + j;
+                                if Self::pow_challenge_is_valid(
+                                    &base_sponge,
+                                    pow_bits,
+                                    challenge_u64,
+                                ) {
+                                    let _ = result.compare_exchange(
+                                        ALGEBRAIC_NO_RESULT,
+                                        challenge_u64,
+                                        Ordering::Acquire,
+                                        Ordering::Relaxed,
+                                    );
+
+                                    break;
+                                }
+                            }
+                        } else {
+                            break;
+                        }
+                    }
+                })
+            }
+        });
+
+        let challenge_u64 = result.load(Ordering::SeqCst);
+
+        assert!(Self::pow_challenge_is_valid(
+            &base_sponge,
+            pow_bits,
+            challenge_u64
+        ));
+
+        challenge_u64
+    }
+
+    fn run_from_bytes(seed: Vec<u8>, pow_bits: u32, worker: &Worker) -> u64 {
+        Self::run_from_field_elements(bytes_into_sponge_field::<F>(seed), pow_bits, worker)
+    }
+
+    fn verify_from_field_elements<T: SmallField>(
+        seed: Vec<T>,
+        pow_bits: u32,
+        challenge: u64,
+    ) -> bool {
+        let seed = seed_into_sponge_field::<F, T>(seed);
+        let mut base_sponge = Self::default();
+        base_sponge.absorb(&seed);
+
+        Self::pow_challenge_is_valid(&base_sponge, pow_bits, challenge)
+    }
+
+    fn verify_from_bytes(seed: Vec<u8>, pow_bits: u32, challenge: u64) -> bool {
+        Self::verify_from_field_elements(bytes_into_sponge_field::<F>(seed), pow_bits, challenge)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::field::U64Representable;
+
+    type F = GoldilocksField;
+    type Pow = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    #[test]
+    fn algebraic_pow_roundtrip() {
+        let worker = Worker::new_with_num_threads(4);
+        let seed: Vec<F> = (0..5).map(|el| F::from_u64_unchecked(el + 1)).collect();
+        for pow_bits in [4, 18] {
+            let challenge = Pow::run_from_field_elements(seed.clone(), pow_bits, &worker);
+            assert!(Pow::verify_from_field_elements(
+                seed.clone(),
+                pow_bits,
+                challenge
+            ));
+        }
+
+        // serial search returns the first suitable challenge
+        let challenge = Pow::run_from_field_elements(seed.clone(), 4, &worker);
+        for other in 0..challenge {
+            assert!(!Pow::verify_from_field_elements(seed.clone(), 4, other));
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use crate::cs::{
-    oracle::{merkle_tree::MerkleTreeWithCap, TreeHasher},
-    traits::GoodAllocator,
+use crate::cs::oracle::{
+    merkle_tree::{MerkleProofSource, MerkleTreeWithCap},
+    TreeHasher,
 };
 
-use super::{fri::QuerySource, prover::ProofConfig, *};
+use super::{fri::QuerySource, prover::ProofConfig, verifier::OracleType, *};
 
 #[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
@@ -45,6 +45,17 @@ impl<F: SmallField, H: TreeHasher<F>> SingleRoundQueries<F, H> {
     }
 }
 
+/// Openings of witness, second stage, quotient and setup oracles at the same point
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct BaseOraclesQueries<F: SmallField, H: TreeHasher<F>> {
+    pub witness_query: OracleQuery<F, H>,
+    pub stage_2_query: OracleQuery<F, H>,
+    pub quotient_query: OracleQuery<F, H>,
+    pub setup_query: OracleQuery<F, H>,
+}
+
 #[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(
     Clone(bound = ""),
@@ -62,8 +73,8 @@ pub struct OracleQuery<F: SmallField, H: TreeHasher<F>> {
 }
 
 impl<F: SmallField, H: TreeHasher<F>> OracleQuery<F, H> {
-    pub fn construct<S: QuerySource<F>, A: GoodAllocator, B: GoodAllocator>(
-        tree: &MerkleTreeWithCap<F, H, A, B>,
+    pub fn construct<S: QuerySource<F>, T: MerkleProofSource<F, H>>(
+        tree: &T,
         source: &S,
         lde_factor: usize,
         coset_idx: usize,
@@ -118,7 +129,12 @@ use crate::field::FieldExtension;
 #[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
 #[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
-pub struct Proof<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> {
+pub struct Proof<
+    F: SmallField,
+    H: TreeHasher<F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+> {
     pub proof_config: ProofConfig,
 
     pub public_inputs: Vec<F>,
@@ -126,11 +142,12 @@ pub struct Proof<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseFie
     pub witness_oracle_cap: Vec<H::Output>,
     pub stage_2_oracle_cap: Vec<H::Output>,
     pub quotient_oracle_cap: Vec<H::Output>,
-    pub final_fri_monomials: [Vec<F>; 2],
+    #[serde(with = "crate::serde_utils::BigArraySerde")]
+    pub final_fri_monomials: [Vec<F>; N],
 
-    pub values_at_z: Vec<ExtensionField<F, 2, EXT>>,
-    pub values_at_z_omega: Vec<ExtensionField<F, 2, EXT>>,
-    pub values_at_0: Vec<ExtensionField<F, 2, EXT>>,
+    pub values_at_z: Vec<ExtensionField<F, N, EXT>>,
+    pub values_at_z_omega: Vec<ExtensionField<F, N, EXT>>,
+    pub values_at_0: Vec<ExtensionField<F, N, EXT>>,
 
     pub fri_base_oracle_cap: Vec<H::Output>,
     pub fri_intermediate_oracles_caps: Vec<Vec<H::Output>>,
@@ -142,7 +159,9 @@ pub struct Proof<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseFie
     pub _marker: std::marker::PhantomData<EXT>,
 }
 
-impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> Proof<F, H, EXT> {
+impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<N, BaseField = F>, const N: usize>
+    Proof<F, H, EXT, N>
+{
     pub fn is_same_geometry(a: &Self, b: &Self) -> bool {
         if a.proof_config != b.proof_config {
             return false;
@@ -201,7 +220,7 @@ impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> Pro
 
     pub fn transmute_to_another_formal_hasher<HH: TreeHasher<F, Output = H::Output>>(
         self,
-    ) -> Proof<F, HH, EXT> {
+    ) -> Proof<F, HH, EXT, N> {
         let Proof {
             proof_config,
             public_inputs,
@@ -240,3 +259,269 @@ impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<2, BaseField = F>> Pro
         }
     }
 }
+
+/// Part of an `AggregatedProof` that is specific to one of the circuits. Up to the DEEP
+/// openings every circuit is proven with its own transcript, but its witness, second stage
+/// and quotient oracles are committed by trees shared with other circuits of the same size,
+/// see `SharedOraclesCaps`
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "")]
+pub struct AggregatedCircuitProof<
+    F: SmallField,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+> {
+    pub public_inputs: Vec<F>,
+
+    pub values_at_z: Vec<ExtensionField<F, N, EXT>>,
+    pub values_at_z_omega: Vec<ExtensionField<F, N, EXT>>,
+    pub values_at_0: Vec<ExtensionField<F, N, EXT>>,
+}
+
+/// Caps of witness, second stage and quotient trees shared by all the circuits with
+/// the same trace length. A leaf of such a tree is the concatenation of the leafs
+/// of those circuits, in the order of circuits
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct SharedOraclesCaps<F: SmallField, H: TreeHasher<F>> {
+    pub witness_oracle_cap: Vec<H::Output>,
+    pub stage_2_oracle_cap: Vec<H::Output>,
+    pub quotient_oracle_cap: Vec<H::Output>,
+}
+
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct SharedOraclesQueries<F: SmallField, H: TreeHasher<F>> {
+    pub witness_query: OracleQuery<F, H>,
+    pub stage_2_query: OracleQuery<F, H>,
+    pub quotient_query: OracleQuery<F, H>,
+}
+
+/// Base oracles of all the circuits are opened at the same point of the LDE domain,
+/// so a single FRI query covers all of them. Shared trees are opened once per group
+/// of circuits, and setup trees once per circuit
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct AggregatedSingleRoundQueries<F: SmallField, H: TreeHasher<F>> {
+    pub shared_oracles_queries: Vec<SharedOraclesQueries<F, H>>,
+    pub setup_queries: Vec<OracleQuery<F, H>>,
+
+    pub fri_queries: Vec<OracleQuery<F, H>>,
+}
+
+/// Proof of several circuits with a single FRI. See `aggregation` module for details
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct AggregatedProof<
+    F: SmallField,
+    H: TreeHasher<F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+> {
+    // LDE factor is the one of the largest circuit
+    pub proof_config: ProofConfig,
+
+    pub circuits: Vec<AggregatedCircuitProof<F, EXT, N>>,
+    // groups of circuits with the same trace length, in the order of their first circuits
+    pub shared_oracles_caps: Vec<SharedOraclesCaps<F, H>>,
+
+    #[serde(with = "crate::serde_utils::BigArraySerde")]
+    pub final_fri_monomials: [Vec<F>; N],
+    pub fri_base_oracle_cap: Vec<H::Output>,
+    pub fri_intermediate_oracles_caps: Vec<Vec<H::Output>>,
+
+    pub queries_per_fri_repetition: Vec<AggregatedSingleRoundQueries<F, H>>,
+
+    pub pow_challenge: u64,
+
+    pub _marker: std::marker::PhantomData<EXT>,
+}
+
+/// Merkle paths of all the FRI repetitions merged per oracle, so that siblings shared
+/// between the queries are included only once. See `MerkleTreeWithCap::get_multi_proof`
+/// for the layout
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct DeduplicatedQueryPaths<F: SmallField, H: TreeHasher<F>> {
+    pub witness_oracle: Vec<H::Output>,
+    pub stage_2_oracle: Vec<H::Output>,
+    pub quotient_oracle: Vec<H::Output>,
+    pub setup_oracle: Vec<H::Output>,
+
+    pub fri_oracles: Vec<Vec<H::Output>>,
+}
+
+/// `Proof` where queries carry only the leaf elements, and Merkle paths are in the
+/// deduplicated form. Queried indexes are not a part of it: they are drawn from the transcript
+/// by the verifier anyway
+#[derive(derivative::Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone(bound = ""), Debug(bound = ""), Hash(bound = ""))]
+#[serde(bound = "H::Output: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct ProofWithDeduplicatedPaths<
+    F: SmallField,
+    H: TreeHasher<F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+> {
+    pub proof: Proof<F, H, EXT, N>,
+    pub paths: DeduplicatedQueryPaths<F, H>,
+}
+
+impl<F: SmallField, H: TreeHasher<F>, EXT: FieldExtension<N, BaseField = F>, const N: usize>
+    ProofWithDeduplicatedPaths<F, H, EXT, N>
+{
+    /// Merges the paths of the proof. `query_indices` are indexes of the queried leafs
+    /// in the base oracles for every FRI repetition
+    pub fn from_proof(mut proof: Proof<F, H, EXT, N>, query_indices: &[usize]) -> Self {
+        assert_eq!(query_indices.len(), proof.queries_per_fri_repetition.len());
+
+        let queries = &proof.queries_per_fri_repetition;
+        let witness_oracle = merge_paths(queries.iter().map(|el| &el.witness_query), query_indices);
+        let stage_2_oracle = merge_paths(queries.iter().map(|el| &el.stage_2_query), query_indices);
+        let quotient_oracle =
+            merge_paths(queries.iter().map(|el| &el.quotient_query), query_indices);
+        let setup_oracle = merge_paths(queries.iter().map(|el| &el.setup_query), query_indices);
+
+        let num_fri_oracles = queries.first().map(|el| el.fri_queries.len()).unwrap_or(0);
+        let mut fri_oracles = Vec::with_capacity(num_fri_oracles);
+        let mut tree_indices = query_indices.to_vec();
+        for fri_step in 0..num_fri_oracles {
+            for (idx, queries) in tree_indices.iter_mut().zip(queries.iter()) {
+                // leafs are made of the elements of the extension field
+                let num_elements = queries.fri_queries[fri_step].leaf_elements.len() / N;
+                assert!(num_elements.is_power_of_two());
+                *idx >>= num_elements.trailing_zeros();
+            }
+            fri_oracles.push(merge_paths(
+                queries.iter().map(|el| &el.fri_queries[fri_step]),
+                &tree_indices,
+            ));
+        }
+
+        for queries in proof.queries_per_fri_repetition.iter_mut() {
+            queries.witness_query.proof.clear();
+            queries.stage_2_query.proof.clear();
+            queries.quotient_query.proof.clear();
+            queries.setup_query.proof.clear();
+            for query in queries.fri_queries.iter_mut() {
+                query.proof.clear();
+            }
+        }
+
+        Self {
+            proof,
+            paths: DeduplicatedQueryPaths {
+                witness_oracle,
+                stage_2_oracle,
+                quotient_oracle,
+                setup_oracle,
+                fri_oracles,
+            },
+        }
+    }
+
+    /// Restores individual paths of the queries. `query_indices` must have an index for every
+    /// FRI repetition, and `base_oracles_depth` is the number of layers between the leafs and
+    /// the cap in the base oracles. Returns the oracle with malformed paths on failure
+    pub fn restore_paths(
+        &self,
+        query_indices: &[usize],
+        base_oracles_depth: usize,
+    ) -> Result<Proof<F, H, EXT, N>, OracleType> {
+        assert_eq!(
+            query_indices.len(),
+            self.proof.queries_per_fri_repetition.len()
+        );
+
+        let mut proof = self.proof.clone();
+        let queries = &mut proof.queries_per_fri_repetition;
+        for (oracle, merged) in [
+            (OracleType::Witness, &self.paths.witness_oracle),
+            (OracleType::Stage2, &self.paths.stage_2_oracle),
+            (OracleType::Quotient, &self.paths.quotient_oracle),
+            (OracleType::Setup, &self.paths.setup_oracle),
+        ] {
+            let mut oracle_queries: Vec<_> = queries
+                .iter_mut()
+                .map(|el| match oracle {
+                    OracleType::Witness => &mut el.witness_query,
+                    OracleType::Stage2 => &mut el.stage_2_query,
+                    OracleType::Quotient => &mut el.quotient_query,
+                    OracleType::Setup => &mut el.setup_query,
+                    OracleType::Fri(_) => unreachable!(),
+                })
+                .collect();
+            split_paths(
+                &mut oracle_queries,
+                merged,
+                query_indices,
+                base_oracles_depth,
+            )
+            .ok_or(oracle)?;
+        }
+
+        let mut tree_indices = query_indices.to_vec();
+        let mut depths = vec![base_oracles_depth; query_indices.len()];
+        for (fri_step, merged) in self.paths.fri_oracles.iter().enumerate() {
+            let oracle = OracleType::Fri(fri_step);
+            let mut oracle_queries = Vec::with_capacity(queries.len());
+            for ((queries, idx), depth) in queries
+                .iter_mut()
+                .zip(tree_indices.iter_mut())
+                .zip(depths.iter_mut())
+            {
+                let query = queries.fri_queries.get_mut(fri_step).ok_or(oracle)?;
+                let num_elements = query.leaf_elements.len() / N;
+                if num_elements.is_power_of_two() == false {
+                    return Err(oracle);
+                }
+                let interpolation_degree_log2 = num_elements.trailing_zeros() as usize;
+                *idx >>= interpolation_degree_log2;
+                *depth = depth.checked_sub(interpolation_degree_log2).ok_or(oracle)?;
+                oracle_queries.push(query);
+            }
+            // all the queries are folded by the same schedule
+            if depths.iter().any(|el| *el != depths[0]) {
+                return Err(oracle);
+            }
+            let depth = depths.first().copied().unwrap_or(0);
+            split_paths(&mut oracle_queries, merged, &tree_indices, depth).ok_or(oracle)?;
+        }
+
+        Ok(proof)
+    }
+}
+
+fn merge_paths<'a, F: SmallField, H: TreeHasher<F>>(
+    queries: impl Iterator<Item = &'a OracleQuery<F, H>>,
+    indices: &[usize],
+) -> Vec<H::Output> {
+    let paths: Vec<_> = queries.map(|el| &el.proof[..]).collect();
+
+    MerkleTreeWithCap::<F, H>::multi_proof_from_paths(&paths, indices)
+}
+
+fn split_paths<F: SmallField, H: TreeHasher<F>>(
+    queries: &mut [&mut OracleQuery<F, H>],
+    merged: &[H::Output],
+    indices: &[usize],
+    depth: usize,
+) -> Option<()> {
+    let leaf_hashes: Vec<_> = queries
+        .iter()
+        .map(|el| H::hash_into_leaf(&el.leaf_elements))
+        .collect();
+    let paths =
+        MerkleTreeWithCap::<F, H>::paths_from_multi_proof(merged, &leaf_hashes, indices, depth)?;
+    for (query, path) in queries.iter_mut().zip(paths.into_iter()) {
+        query.proof = path;
+    }
+
+    Some(())
+}
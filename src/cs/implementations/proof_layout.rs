@@ -0,0 +1,441 @@
+use super::proof::Proof;
+use super::prover::ProofConfig;
+use super::verifier::{OpeningPoint, VerificationKeyCircuitGeometry, Verifier};
+use super::*;
+
+use crate::cs::implementations::copy_permutation::num_intermediate_partial_product_relations;
+use crate::cs::oracle::TreeHasher;
+use crate::field::{ExtensionField, FieldExtension};
+use std::ops::Range;
+
+// Openings at z go in the same order as the verifier reads them: variables and witnesses,
+// setup constants and sigmas, copy-permutation grand product and partial products, then
+// everything related to lookups, chunks of the quotient and finally masking polynomials
+// of zero-knowledge proofs. Only the grand product is opened at z * omega, and only the lookup
+// encoding polynomials are opened at 0 for the sumcheck
+
+/// Polynomial opened in the proof. Indexes are counted from zero within every kind
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum PolyId {
+    Variable(usize),
+    Witness(usize),
+    Constant(usize),
+    Sigma(usize),
+    CopyPermutationZ,
+    CopyPermutationPartialProduct(usize),
+    LookupMultiplicity(usize),
+    LookupWitnessEncoding(usize),
+    LookupMultiplicityEncoding(usize),
+    /// Columns of the lookup tables, the last one encodes table IDs
+    LookupTableSetup(usize),
+    QuotientChunk(usize),
+    /// Random polynomials committed with the quotient in zero-knowledge mode
+    MaskingPoly(usize),
+}
+
+/// Placement of every opened polynomial in `values_at_z`, `values_at_z_omega` and `values_at_0`
+/// of a proof
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProofLayout {
+    pub variables: Range<usize>,
+    pub witnesses: Range<usize>,
+    pub constants: Range<usize>,
+    pub sigmas: Range<usize>,
+    pub copy_permutation_z: usize,
+    pub copy_permutation_partial_products: Range<usize>,
+    pub lookup_multiplicities: Range<usize>,
+    pub lookup_witness_encodings: Range<usize>,
+    pub lookup_multiplicity_encodings: Range<usize>,
+    pub lookup_table_setup: Range<usize>,
+    pub quotient_chunks: Range<usize>,
+    pub masking_polys: Range<usize>,
+
+    pub lookup_witness_encodings_at_zero: Range<usize>,
+    pub lookup_multiplicity_encodings_at_zero: Range<usize>,
+}
+
+impl ProofLayout {
+    pub fn new<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+        verifier: &Verifier<F, EXT, N>,
+        fixed_parameters: &VerificationKeyCircuitGeometry,
+        proof_config: &ProofConfig,
+    ) -> Self {
+        let num_variable_polys = verifier.num_variable_polys();
+        let num_witness_polys = verifier.num_witness_polys();
+        let num_constant_polys = verifier.num_constant_polys(fixed_parameters);
+        let num_copy_permutation_polys = verifier.num_copy_permutation_polys();
+        let quotient_degree = verifier.quotient_degree(fixed_parameters);
+        let num_intermediate_partial_product_relations =
+            num_intermediate_partial_product_relations(num_copy_permutation_polys, quotient_degree);
+
+        let (num_lookup_subarguments, num_multiplicities_polys, num_lookup_table_setup_polys) =
+            if verifier.lookup_parameters.lookup_is_allowed() {
+                (
+                    verifier.num_sublookup_arguments(),
+                    verifier.num_multipicities_polys(
+                        fixed_parameters.total_tables_len as usize,
+                        fixed_parameters.domain_size,
+                    ),
+                    verifier.num_lookup_table_setup_polys(),
+                )
+            } else {
+                (0, 0, 0)
+            };
+
+        let mut offset = 0;
+        let mut take = |len: usize| {
+            let range = offset..(offset + len);
+            offset += len;
+
+            range
+        };
+
+        let variables = take(num_variable_polys);
+        let witnesses = take(num_witness_polys);
+        let constants = take(num_constant_polys);
+        let sigmas = take(num_copy_permutation_polys);
+        let copy_permutation_z = take(1).start;
+        let copy_permutation_partial_products = take(num_intermediate_partial_product_relations);
+        let lookup_multiplicities = take(num_multiplicities_polys);
+        let lookup_witness_encodings = take(num_lookup_subarguments);
+        let lookup_multiplicity_encodings = take(num_multiplicities_polys);
+        let lookup_table_setup = take(num_lookup_table_setup_polys);
+        let quotient_chunks = take(quotient_degree);
+        let masking_polys = take(proof_config.num_masking_polys());
+
+        Self {
+            variables,
+            witnesses,
+            constants,
+            sigmas,
+            copy_permutation_z,
+            copy_permutation_partial_products,
+            lookup_multiplicities,
+            lookup_witness_encodings,
+            lookup_multiplicity_encodings,
+            lookup_table_setup,
+            quotient_chunks,
+            masking_polys,
+            lookup_witness_encodings_at_zero: 0..num_lookup_subarguments,
+            lookup_multiplicity_encodings_at_zero: num_lookup_subarguments
+                ..(num_lookup_subarguments + num_multiplicities_polys),
+        }
+    }
+
+    fn ranges(&self, point: OpeningPoint) -> Vec<(Range<usize>, fn(usize) -> PolyId)> {
+        match point {
+            OpeningPoint::Z => vec![
+                (self.variables.clone(), PolyId::Variable),
+                (self.witnesses.clone(), PolyId::Witness),
+                (self.constants.clone(), PolyId::Constant),
+                (self.sigmas.clone(), PolyId::Sigma),
+                (
+                    self.copy_permutation_z..(self.copy_permutation_z + 1),
+                    |_| PolyId::CopyPermutationZ,
+                ),
+                (
+                    self.copy_permutation_partial_products.clone(),
+                    PolyId::CopyPermutationPartialProduct,
+                ),
+                (
+                    self.lookup_multiplicities.clone(),
+                    PolyId::LookupMultiplicity,
+                ),
+                (
+                    self.lookup_witness_encodings.clone(),
+                    PolyId::LookupWitnessEncoding,
+                ),
+                (
+                    self.lookup_multiplicity_encodings.clone(),
+                    PolyId::LookupMultiplicityEncoding,
+                ),
+                (self.lookup_table_setup.clone(), PolyId::LookupTableSetup),
+                (self.quotient_chunks.clone(), PolyId::QuotientChunk),
+                (self.masking_polys.clone(), PolyId::MaskingPoly),
+            ],
+            OpeningPoint::ZOmega => vec![(0..1, |_| PolyId::CopyPermutationZ)],
+            OpeningPoint::Zero => vec![
+                (
+                    self.lookup_witness_encodings_at_zero.clone(),
+                    PolyId::LookupWitnessEncoding,
+                ),
+                (
+                    self.lookup_multiplicity_encodings_at_zero.clone(),
+                    PolyId::LookupMultiplicityEncoding,
+                ),
+            ],
+        }
+    }
+
+    pub fn num_openings(&self, point: OpeningPoint) -> usize {
+        self.ranges(point).last().map(|el| el.0.end).unwrap_or(0)
+    }
+
+    /// Index of the polynomial value at the point, or `None` if it's not opened there
+    pub fn index_of(&self, poly: PolyId, point: OpeningPoint) -> Option<usize> {
+        self.ranges(point)
+            .into_iter()
+            .flat_map(|(range, id)| {
+                let start = range.start;
+                range.map(move |idx| (idx, id(idx - start)))
+            })
+            .find(|(_, id)| *id == poly)
+            .map(|(idx, _)| idx)
+    }
+
+    /// Polynomials opened at the point, in the order of the values in the proof
+    pub fn opened_polys(&self, point: OpeningPoint) -> Vec<PolyId> {
+        self.ranges(point)
+            .into_iter()
+            .flat_map(|(range, id)| {
+                let start = range.start;
+                range.map(move |idx| id(idx - start))
+            })
+            .collect()
+    }
+}
+
+impl<F: SmallField, H: TreeHasher<F>, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    Proof<F, H, EXT, N>
+{
+    /// Claimed value of the polynomial at the point, or `None` if it's not opened there
+    pub fn opening_of(
+        &self,
+        layout: &ProofLayout,
+        poly: PolyId,
+        point: OpeningPoint,
+    ) -> Option<ExtensionField<F, N, EXT>> {
+        let idx = layout.index_of(poly, point)?;
+        let values = match point {
+            OpeningPoint::Z => &self.values_at_z,
+            OpeningPoint::ZOmega => &self.values_at_z_omega,
+            OpeningPoint::Zero => &self.values_at_0,
+        };
+
+        values.get(idx).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_verifier::CsVerifierBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::cs::test::{configure_fma_chain, synthesize_fma_chain};
+    use crate::cs::implementations::pow::NoPow;
+    use crate::cs::implementations::transcript::GoldilocksPoisedon2Transcript;
+    use crate::cs::implementations::verifier::VerificationKey;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+    use crate::field::Field;
+    use crate::implementations::poseidon2::Poseidon2Goldilocks;
+    use crate::worker::Worker;
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+    type Ext = GoldilocksExt2;
+    type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    #[test]
+    fn test_layout_of_proof_with_lookups() {
+        let mut vk_file = std::fs::File::open("vk.json").unwrap();
+        let mut proof_file = std::fs::File::open("proof.json").unwrap();
+        let vk: VerificationKey<F, H> = serde_json::from_reader(&mut vk_file).unwrap();
+        let proof: Proof<F, H, Ext> = serde_json::from_reader(&mut proof_file).unwrap();
+
+        // same configuration as the one that produced the proof
+        type Poseidon2Gate = Poseidon2FlattenedGate<F, 8, 12, 4, Poseidon2Goldilocks>;
+
+        let builder_impl =
+            CsVerifierBuilder::<F, Ext>::new_from_parameters(vk.fixed_parameters.parameters);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = builder.allow_lookup(
+            LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseSpecializedColumns {
+                num_repetitions: 1,
+                share_constants: false,
+            },
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = Poseidon2Gate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<8>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<_, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+        let verifier = builder.build(());
+
+        assert!(verifier.verify::<H, GoldilocksPoisedon2Transcript, NoPow>((), &vk, &proof));
+
+        let layout = ProofLayout::new(&verifier, &vk.fixed_parameters, &proof.proof_config);
+        for (point, values) in [
+            (OpeningPoint::Z, &proof.values_at_z),
+            (OpeningPoint::ZOmega, &proof.values_at_z_omega),
+            (OpeningPoint::Zero, &proof.values_at_0),
+        ] {
+            assert_eq!(layout.num_openings(point), values.len());
+            let polys = layout.opened_polys(point);
+            assert_eq!(polys.len(), values.len());
+            for (idx, poly) in polys.into_iter().enumerate() {
+                assert_eq!(layout.index_of(poly, point), Some(idx));
+                assert_eq!(proof.opening_of(&layout, poly, point), Some(values[idx]));
+            }
+        }
+
+        assert_eq!(layout.variables.start, 0);
+        assert_eq!(layout.lookup_table_setup.len(), 3 + 1);
+        assert_eq!(
+            proof.opening_of(&layout, PolyId::Variable(3), OpeningPoint::Z),
+            Some(proof.values_at_z[3])
+        );
+        assert_eq!(
+            proof.opening_of(&layout, PolyId::QuotientChunk(0), OpeningPoint::Z),
+            Some(proof.values_at_z[layout.quotient_chunks.start])
+        );
+        assert_eq!(
+            proof.opening_of(&layout, PolyId::Variable(3), OpeningPoint::ZOmega),
+            None
+        );
+        assert_eq!(
+            proof.opening_of(
+                &layout,
+                PolyId::Variable(layout.variables.len()),
+                OpeningPoint::Z
+            ),
+            None
+        );
+
+        // lookup sumcheck, evaluated outside of the verifier
+        let sum_at_zero = |poly: fn(usize) -> PolyId, num_polys: usize| {
+            let mut sum = ExtensionField::<F, 2, Ext>::ZERO;
+            for idx in 0..num_polys {
+                let value = proof
+                    .opening_of(&layout, poly(idx), OpeningPoint::Zero)
+                    .unwrap();
+                sum.add_assign(&value);
+            }
+
+            sum
+        };
+        assert_eq!(
+            sum_at_zero(
+                PolyId::LookupWitnessEncoding,
+                layout.lookup_witness_encodings.len()
+            ),
+            sum_at_zero(
+                PolyId::LookupMultiplicityEncoding,
+                layout.lookup_multiplicity_encodings.len()
+            ),
+        );
+        assert!(layout.masking_polys.is_empty());
+
+        // zero-knowledge proofs also open masking polynomials after the quotient chunks
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 8,
+            num_witness_columns: 0,
+            num_constant_columns: 2,
+            max_allowed_constraint_degree: 8,
+        };
+        let max_trace_len = 256;
+
+        let proof_config = ProofConfig {
+            fri_lde_factor: 16,
+            security_level: 20,
+            pow_bits: 0,
+            zero_knowledge: true,
+            ..Default::default()
+        };
+
+        let mut cs = synthesize_fma_chain(geometry, max_trace_len, 40);
+        cs.add_blinding_rows(proof_config.num_blinding_rows(max_trace_len));
+        cs.pad_and_shrink();
+
+        let worker = Worker::new_with_num_threads(4);
+        let (proof, vk) = cs
+            .into_assembly::<Global>()
+            .prove_one_shot::<2, Ext, GoldilocksPoisedon2Transcript, H, NoPow>(
+                &worker,
+                proof_config,
+                (),
+            );
+
+        let builder_impl = CsVerifierBuilder::<F, Ext>::new_from_parameters(geometry);
+        let verifier = configure_fma_chain(new_builder::<_, F>(builder_impl)).build(());
+        assert!(verifier.verify::<H, GoldilocksPoisedon2Transcript, NoPow>((), &vk, &proof));
+
+        let layout = ProofLayout::new(&verifier, &vk.fixed_parameters, &proof.proof_config);
+        assert_eq!(layout.masking_polys.len(), 1);
+        assert_eq!(layout.masking_polys.start, layout.quotient_chunks.end);
+        assert_eq!(
+            layout.num_openings(OpeningPoint::Z),
+            proof.values_at_z.len()
+        );
+        assert_eq!(
+            layout.opened_polys(OpeningPoint::Z).last(),
+            Some(&PolyId::MaskingPoly(0))
+        );
+        assert_eq!(
+            proof.opening_of(&layout, PolyId::MaskingPoly(0), OpeningPoint::Z),
+            proof.values_at_z.last().copied()
+        );
+    }
+}
@@ -18,17 +18,18 @@ use std::alloc::Global;
 
 use super::hints::*;
 use super::polynomial::lde::GenericLdeStorage;
-use super::polynomial::BitreversedLagrangeForm;
+use super::polynomial::{BitreversedLagrangeForm, LagrangeForm, Polynomial};
 use super::polynomial_storage::SetupStorage;
 use super::proof::Proof;
 use super::transcript::Transcript;
-use super::verifier::VerificationKey;
+use super::verifier::{OracleType, VerificationKey};
 use super::*;
 use crate::cs::implementations::buffering_source::*;
-use crate::cs::implementations::proof::SingleRoundQueries;
+use crate::cs::implementations::proof::{BaseOraclesQueries, OracleQuery, SingleRoundQueries};
 use crate::cs::implementations::transcript::BoolsBuffer;
 use crate::cs::traits::gate::GatePlacementStrategy;
 use crate::field::traits::field_like::mul_assign_vectorized_in_extension;
+use std::ops::Range;
 use std::sync::Arc;
 
 use super::pow::*;
@@ -36,20 +37,28 @@ use crate::cs::gates::lookup_marker::LookupFormalGate;
 use crate::cs::implementations::witness::WitnessSet;
 use crate::utils::allocate_in_with_alignment_of;
 
-use crate::cs::implementations::fri::do_fri;
+use crate::cs::implementations::fri::{do_fri, split_inner, FriOracles, QuerySource};
+use crate::cs::implementations::out_of_core::{
+    OracleLeafSource, OracleTree, OutOfCoreConfig, SpilledMerkleTree, SpilledOracle,
+};
 use crate::cs::implementations::polynomial::MonomialForm;
 
 use crate::cs::implementations::polynomial_storage::TraceHolder;
 use crate::cs::implementations::polynomial_storage::*;
 use crate::cs::implementations::reference_cs::*;
+use crate::cs::implementations::setup::TreeNode;
 use crate::cs::implementations::utils::*;
-use crate::cs::oracle::merkle_tree::MerkleTreeWithCap;
+use crate::cs::oracle::merkle_tree::{MerkleProofSource, MerkleTreeWithCap};
 use crate::cs::oracle::TreeHasher;
 use crate::cs::traits::destination_view::*;
 use crate::cs::traits::evaluator::*;
 use crate::cs::traits::trace_source::*;
 use crate::cs::traits::GoodAllocator;
 
+/// Number of random elements that are appended to every leaf of witness, second stage and
+/// quotient oracles in zero-knowledge mode, so that caps and paths say nothing about unopened leafs
+pub const SALT_SIZE: usize = 4;
+
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct ProofConfig {
@@ -58,6 +67,52 @@ pub struct ProofConfig {
     pub fri_folding_schedule: Option<Vec<usize>>,
     pub security_level: usize,
     pub pow_bits: u32,
+    /// Randomizes blinding rows of the trace, salts leafs of every committed oracle and masks
+    /// the DEEP batch. See `CSReferenceAssembly::check_zero_knowledge_support` for circuits
+    /// that can be proven in this mode
+    #[serde(default)]
+    pub zero_knowledge: bool,
+}
+
+impl ProofConfig {
+    /// Number of blinding rows (see `add_blinding_rows`) that a circuit with a trace of
+    /// `trace_len` rows must reserve to be proven with this config in zero-knowledge mode
+    pub fn num_blinding_rows(&self, trace_len: usize) -> usize {
+        let (_, num_queries, _, _) = compute_fri_schedule_for_config(
+            self,
+            self.fri_lde_factor.trailing_zeros(),
+            trace_len.trailing_zeros(),
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+
+        // every polynomial is opened at z, z * omega and 0, and every query opens base
+        // oracles at one point. FRI oracles are linear combinations that include the masking
+        // polynomial, so those do not need anything from the trace. We need a random value
+        // per opening, and we get one per pair of rows
+        let num_openings = 3 + num_queries;
+
+        2 * num_openings
+    }
+
+    /// Number of random elements at the end of every leaf of witness, second stage and quotient oracles
+    pub fn salt_size(&self) -> usize {
+        if self.zero_knowledge {
+            SALT_SIZE
+        } else {
+            0
+        }
+    }
+
+    /// Number of random polynomials (in extension) that are committed after the quotient chunks
+    /// in zero-knowledge mode. Those are opened at z and included into the DEEP batch,
+    /// so FRI oracles are masked, but do not take part in the quotient identity
+    pub fn num_masking_polys(&self) -> usize {
+        if self.zero_knowledge {
+            1
+        } else {
+            0
+        }
+    }
 }
 
 impl std::default::Default for ProofConfig {
@@ -68,10 +123,463 @@ impl std::default::Default for ProofConfig {
             fri_folding_schedule: None,
             security_level: 100,
             pow_bits: 20,
+            zero_knowledge: false,
         }
     }
 }
 
+/// Proving is split into stages, each of them finishes with a commitment
+/// (or with a set of evaluations) that is absorbed by the transcript. Stage
+/// denotes the last completed one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum ProverStage {
+    WitnessCommitted,
+    SecondStageCommitted,
+    QuotientCommitted,
+    DeepOpeningsComputed,
+    FriCommitted,
+}
+
+/// Everything the prover carries between stages, including the transcript. It can be
+/// serialized after any stage and proving can be resumed from it with the same setup,
+/// producing exactly the same proof. The state keeps the cap of the setup it was started with, and
+/// resuming with another setup panics. Spilled oracles and trees are serialized by the path
+/// of the corresponding file, so those files must survive until proving is resumed:
+/// call `persist_spilled_oracles` before dropping the state that was checkpointed.
+/// Files are not owned by a deserialized state until `claim_spilled_files` is called.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(
+    bound = "P: serde::Serialize + serde::de::DeserializeOwned, TR: serde::Serialize + serde::de::DeserializeOwned, H::Output: serde::Serialize + serde::de::DeserializeOwned"
+)]
+pub struct ProverState<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    TR: Transcript<F>,
+    H: TreeHasher<F>,
+    const N: usize = 2,
+> {
+    stage: ProverStage,
+    proof_config: ProofConfig,
+    transcript: TR,
+    setup_cap: Vec<H::Output>,
+    pub(crate) public_inputs_values: Vec<F>,
+    public_inputs_with_locations: Vec<(usize, usize, F)>,
+    // base traces are only needed to compute the second stage polys
+    #[serde(serialize_with = "crate::utils::serialize_vec_arc")]
+    #[serde(deserialize_with = "crate::utils::deserialize_vec_arc")]
+    variables_columns: Vec<Arc<GenericPolynomial<F, LagrangeForm, P>>>,
+    #[serde(serialize_with = "crate::utils::serialize_vec_arc")]
+    #[serde(deserialize_with = "crate::utils::deserialize_vec_arc")]
+    multiplicities_columns: Vec<Arc<GenericPolynomial<F, LagrangeForm, P>>>,
+    // in-memory LDEs are released as soon as the oracle is spilled
+    witness_storage: Option<WitnessStorage<F, P, Global, Global>>,
+    witness_tree: Option<OracleTree<F, H>>,
+    spilled_witness: Option<SpilledOracle<F>>,
+    // salt of every committed leaf, empty unless in zero-knowledge mode
+    witness_salt: Vec<ArcGenericLdeStorage<F, P, Global, Global>>,
+    beta: ExtensionField<F, N, EXT>,
+    gamma: ExtensionField<F, N, EXT>,
+    lookup_beta: ExtensionField<F, N, EXT>,
+    lookup_gamma: ExtensionField<F, N, EXT>,
+    second_stage_polys_storage: Option<SecondStageProductsStorage<F, N, P, Global, Global>>,
+    second_stage_tree: Option<OracleTree<F, H>>,
+    spilled_second_stage: Option<SpilledOracle<F>>,
+    second_stage_salt: Vec<ArcGenericLdeStorage<F, P, Global, Global>>,
+    quotient_chunks_ldes: Option<Vec<ArcGenericLdeStorage<F, P, Global, Global>>>,
+    quotients_tree: Option<OracleTree<F, H>>,
+    spilled_quotients: Option<SpilledOracle<F>>,
+    quotients_salt: Vec<ArcGenericLdeStorage<F, P, Global, Global>>,
+    pub(crate) values_at_z: Vec<ExtensionField<F, N, EXT>>,
+    pub(crate) values_at_z_omega: Vec<ExtensionField<F, N, EXT>>,
+    pub(crate) values_at_0: Vec<ExtensionField<F, N, EXT>>,
+    pub(crate) fri_base_oracle_sources: Option<[ArcGenericLdeStorage<F, P, Global, Global>; N]>,
+    fri_data: Option<FriOracles<F, H, Global, Global, N>>,
+    fri_folding_schedule: Vec<usize>,
+    num_queries: usize,
+    pow_challenge: u64,
+}
+
+impl<
+        F: SmallField,
+        P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F>,
+    > ProverState<F, P, EXT, TR, H, N>
+{
+    pub fn stage(&self) -> ProverStage {
+        self.stage
+    }
+
+    pub fn proof_config(&self) -> &ProofConfig {
+        &self.proof_config
+    }
+
+    fn check_setup(&self, setup_tree: &MerkleTreeWithCap<F, H>) {
+        assert!(
+            self.setup_cap == setup_tree.get_cap(),
+            "proving must be resumed with the same setup as it was started with"
+        );
+    }
+
+    /// Keeps files of spilled oracles on disk when the state is dropped,
+    /// so proving can be resumed from a checkpoint of this state
+    pub fn persist_spilled_oracles(&mut self) {
+        for spilled in [
+            self.spilled_witness.as_mut(),
+            self.spilled_second_stage.as_mut(),
+            self.spilled_quotients.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            spilled.persist();
+        }
+        for tree in [
+            self.witness_tree.as_mut(),
+            self.second_stage_tree.as_mut(),
+            self.quotients_tree.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            tree.persist();
+        }
+    }
+
+    /// Takes ownership of the files of spilled oracles after the state was deserialized,
+    /// so those are removed when proving is done. Fails if any of the files is not
+    /// in the spill directory of `out_of_core`
+    pub fn claim_spilled_files(&mut self, out_of_core: &OutOfCoreConfig) -> std::io::Result<()> {
+        for spilled in [
+            self.spilled_witness.as_mut(),
+            self.spilled_second_stage.as_mut(),
+            self.spilled_quotients.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            spilled.claim(out_of_core)?;
+        }
+        for tree in [
+            self.witness_tree.as_mut(),
+            self.second_stage_tree.as_mut(),
+            self.quotients_tree.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            tree.claim(out_of_core)?;
+        }
+
+        Ok(())
+    }
+
+    /// Commits to the oracle of the last completed stage with a tree over this oracle only,
+    /// and absorbs the cap of the tree into the transcript. Witness, second stage and quotient
+    /// oracles are computed without the commitment, so those can also be committed by
+    /// a tree shared with other proofs, see `commit_shared_oracle`
+    pub(crate) fn commit_oracle(&mut self, out_of_core: Option<&OutOfCoreConfig>, worker: &Worker)
+    where
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    {
+        let lde_factor = self.proof_config.fri_lde_factor;
+        let cap_size = self.proof_config.merkle_tree_cap_size;
+
+        let tree = match self.stage {
+            ProverStage::WitnessCommitted => {
+                assert!(self.witness_tree.is_none());
+                let storage = self
+                    .witness_storage
+                    .take()
+                    .expect("witness must be computed");
+                let num_variables_columns = storage.variables_columns.len();
+                let num_witness_columns = storage.witness_columns.len();
+                let (tree, ldes, spilled) = commit_to_oracle::<F, P, H>(
+                    storage.into_flattened_source().collect(),
+                    &self.witness_salt,
+                    lde_factor,
+                    cap_size,
+                    out_of_core,
+                    "witness",
+                    worker,
+                );
+                self.witness_storage = ldes.map(|el| {
+                    WitnessStorage::from_flattened_source(
+                        el,
+                        num_variables_columns,
+                        num_witness_columns,
+                    )
+                });
+                self.spilled_witness = spilled;
+
+                self.witness_tree.insert(tree)
+            }
+            ProverStage::SecondStageCommitted => {
+                assert!(self.second_stage_tree.is_none());
+                let storage = self
+                    .second_stage_polys_storage
+                    .take()
+                    .expect("second stage must be computed");
+                let num_intermediate_polys = storage.intermediate_polys.len();
+                let num_lookup_subarguments = storage.lookup_witness_encoding_polys.len();
+                let (tree, ldes, spilled) = commit_to_oracle::<F, P, H>(
+                    storage.into_flattened_source().collect(),
+                    &self.second_stage_salt,
+                    lde_factor,
+                    cap_size,
+                    out_of_core,
+                    "second_stage",
+                    worker,
+                );
+                self.second_stage_polys_storage = ldes.map(|el| {
+                    SecondStageProductsStorage::from_flattened_source(
+                        el,
+                        num_intermediate_polys,
+                        num_lookup_subarguments,
+                    )
+                });
+                self.spilled_second_stage = spilled;
+
+                self.second_stage_tree.insert(tree)
+            }
+            ProverStage::QuotientCommitted => {
+                assert!(self.quotients_tree.is_none());
+                let storage = self
+                    .quotient_chunks_ldes
+                    .take()
+                    .expect("quotient must be computed");
+                let (tree, ldes, spilled) = commit_to_oracle::<F, P, H>(
+                    storage,
+                    &self.quotients_salt,
+                    lde_factor,
+                    cap_size,
+                    out_of_core,
+                    "quotient",
+                    worker,
+                );
+                self.quotient_chunks_ldes = ldes;
+                self.spilled_quotients = spilled;
+
+                self.quotients_tree.insert(tree)
+            }
+            stage => panic!("there is no oracle to commit after {:?}", stage),
+        };
+
+        let cap = tree.get_cap();
+        self.transcript.witness_merkle_tree_cap(&cap);
+    }
+
+    /// Columns of the uncommitted oracle of the last completed stage, with the salt, in the order
+    /// of the leaf elements. Trees shared by several proofs are built over columns of all of them
+    pub(crate) fn uncommitted_oracle_columns(
+        &self,
+    ) -> Vec<ArcGenericLdeStorage<F, P, Global, Global>> {
+        let lde_factor = self.proof_config.fri_lde_factor;
+        let (columns, salt): (Vec<_>, _) = match self.stage {
+            ProverStage::WitnessCommitted => {
+                assert!(self.witness_tree.is_none());
+                let storage = self
+                    .witness_storage
+                    .as_ref()
+                    .expect("witness must be computed");
+
+                (storage.flattened_source().collect(), &self.witness_salt)
+            }
+            ProverStage::SecondStageCommitted => {
+                assert!(self.second_stage_tree.is_none());
+                let storage = self
+                    .second_stage_polys_storage
+                    .as_ref()
+                    .expect("second stage must be computed");
+
+                (
+                    storage.flattened_source().collect(),
+                    &self.second_stage_salt,
+                )
+            }
+            ProverStage::QuotientCommitted => {
+                assert!(self.quotients_tree.is_none());
+                let storage = self
+                    .quotient_chunks_ldes
+                    .as_ref()
+                    .expect("quotient must be computed");
+
+                (storage.iter().collect(), &self.quotients_salt)
+            }
+            stage => panic!("there is no oracle to commit after {:?}", stage),
+        };
+
+        columns
+            .into_iter()
+            .map(|el| el.subset_for_degree(lde_factor))
+            .chain(salt.iter().cloned())
+            .collect()
+    }
+
+    /// Absorbs the cap of a tree shared with other proofs instead of committing to the oracle
+    /// of the last completed stage with its own tree. Leafs of such a tree must contain
+    /// the leafs of this oracle, see `uncommitted_oracle_columns`
+    pub(crate) fn commit_shared_oracle(&mut self, cap: &[TR::CompatibleCap]) {
+        self.transcript.witness_merkle_tree_cap(cap);
+    }
+
+    /// Appends the leaf of the witness, second stage or quotient oracle at the given
+    /// point to `dst`. Oracle must be computed, but doesn't have to be committed
+    pub(crate) fn get_oracle_leaf_elements(
+        &self,
+        oracle: OracleType,
+        domain_size: usize,
+        coset_idx: usize,
+        inner_idx: usize,
+        dst: &mut Vec<F>,
+    ) {
+        let lde_factor = self.proof_config.fri_lde_factor;
+        match oracle {
+            OracleType::Witness => SaltedLeafSource {
+                source: OracleLeafSource::select(
+                    self.witness_storage.as_ref(),
+                    self.spilled_witness.as_ref(),
+                ),
+                salt: &self.witness_salt,
+            }
+            .get_elements(lde_factor, coset_idx, domain_size, inner_idx, 1, dst),
+            OracleType::Stage2 => SaltedLeafSource {
+                source: OracleLeafSource::select(
+                    self.second_stage_polys_storage.as_ref(),
+                    self.spilled_second_stage.as_ref(),
+                ),
+                salt: &self.second_stage_salt,
+            }
+            .get_elements(lde_factor, coset_idx, domain_size, inner_idx, 1, dst),
+            OracleType::Quotient => SaltedLeafSource {
+                source: OracleLeafSource::select(
+                    self.quotient_chunks_ldes.as_ref(),
+                    self.spilled_quotients.as_ref(),
+                ),
+                salt: &self.quotients_salt,
+            }
+            .get_elements(lde_factor, coset_idx, domain_size, inner_idx, 1, dst),
+            oracle => panic!("{:?} oracle is not a part of the prover state", oracle),
+        }
+    }
+
+    // Quotient and DEEP are computed over the full LDE at once when the trace is in memory,
+    // and coset by coset otherwise
+    fn coset_batches(&self, num_cosets: usize) -> Vec<Range<usize>> {
+        if self.spilled_witness.is_some() {
+            (0..num_cosets).map(|idx| idx..(idx + 1)).collect()
+        } else {
+            vec![0..num_cosets]
+        }
+    }
+
+    fn witness_for_cosets(
+        &self,
+        cosets: Range<usize>,
+        num_variables_columns: usize,
+        num_witness_columns: usize,
+        worker: &Worker,
+    ) -> WitnessStorage<F, P, Global, Global> {
+        match (&self.witness_storage, &self.spilled_witness) {
+            (_, Some(spilled)) => WitnessStorage::from_flattened_source(
+                spilled.load_cosets::<P>(cosets, worker),
+                num_variables_columns,
+                num_witness_columns,
+            ),
+            (Some(in_memory), None) => in_memory.subset_for_cosets(cosets),
+            (None, None) => panic!("witness LDEs must be available"),
+        }
+    }
+
+    fn num_intermediate_partial_products(
+        &self,
+        num_lookup_subarguments: usize,
+        num_multiplicities_polys: usize,
+    ) -> usize {
+        match (&self.second_stage_polys_storage, &self.spilled_second_stage) {
+            // z(x), intermediate products and lookup polys are all in extension
+            (_, Some(spilled)) => {
+                spilled.num_columns() / N - 1 - num_lookup_subarguments - num_multiplicities_polys
+            }
+            (Some(in_memory), None) => in_memory.intermediate_polys.len(),
+            (None, None) => panic!("second stage LDEs must be available"),
+        }
+    }
+
+    fn second_stage_for_cosets(
+        &self,
+        cosets: Range<usize>,
+        num_lookup_subarguments: usize,
+        num_multiplicities_polys: usize,
+        worker: &Worker,
+    ) -> SecondStageProductsStorage<F, N, P, Global, Global> {
+        match (&self.second_stage_polys_storage, &self.spilled_second_stage) {
+            (_, Some(spilled)) => SecondStageProductsStorage::from_flattened_source(
+                spilled.load_cosets::<P>(cosets, worker),
+                self.num_intermediate_partial_products(
+                    num_lookup_subarguments,
+                    num_multiplicities_polys,
+                ),
+                num_lookup_subarguments,
+            ),
+            (Some(in_memory), None) => in_memory.subset_for_cosets(cosets),
+            (None, None) => panic!("second stage LDEs must be available"),
+        }
+    }
+
+    fn quotient_chunks_for_cosets(
+        &self,
+        cosets: Range<usize>,
+        worker: &Worker,
+    ) -> Vec<ArcGenericLdeStorage<F, P, Global, Global>> {
+        match (&self.quotient_chunks_ldes, &self.spilled_quotients) {
+            (_, Some(spilled)) => spilled.load_cosets::<P>(cosets, worker),
+            (Some(in_memory), None) => in_memory
+                .iter()
+                .map(|el| el.subset_for_cosets(cosets.clone()))
+                .collect(),
+            (None, None) => panic!("quotient LDEs must be available"),
+        }
+    }
+
+    fn oracles_for_cosets(
+        &self,
+        cosets: Range<usize>,
+        setup: &SetupStorage<F, P>,
+        num_variables_columns: usize,
+        num_witness_columns: usize,
+        num_lookup_subarguments: usize,
+        num_multiplicities_polys: usize,
+        worker: &Worker,
+    ) -> (
+        TraceHolder<F, P>,
+        SecondStageProductsStorage<F, N, P>,
+        Vec<ArcGenericLdeStorage<F, P, Global, Global>>,
+    ) {
+        let trace_holder = TraceHolder {
+            variables: self.witness_for_cosets(
+                cosets.clone(),
+                num_variables_columns,
+                num_witness_columns,
+                worker,
+            ),
+            setup: setup.subset_for_cosets(cosets.clone()),
+        };
+        let second_stage = self.second_stage_for_cosets(
+            cosets.clone(),
+            num_lookup_subarguments,
+            num_multiplicities_polys,
+            worker,
+        );
+        let quotient_chunks = self.quotient_chunks_for_cosets(cosets, worker);
+
+        (trace_holder, second_stage, quotient_chunks)
+    }
+}
+
 impl<
         F: SmallField,
         P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
@@ -151,7 +659,74 @@ impl<
     }
 
     pub fn prove_cpu_basic<
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        witness_set: WitnessSet<F>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        vk: &VerificationKey<F, H>,
+        proof_config: ProofConfig,
+        transcript_params: TR::TransciptParameters,
+    ) -> Proof<F, H, EXT, N> {
+        self.prove_cpu_impl::<N, EXT, TR, H, POW>(
+            worker,
+            witness_set,
+            setup_base,
+            setup,
+            setup_tree,
+            vk,
+            proof_config,
+            transcript_params,
+            None,
+        )
+    }
+
+    /// Same as `prove_cpu_basic`, but LDEs of the witness, second stage and quotient oracles
+    /// are spilled into memory mapped files in `out_of_core.spill_directory` and released from
+    /// memory right away. Merkle trees are built from the spilled copies and spilled too, quotient
+    /// and DEEP are computed coset by coset, and queries only read the leafs and paths they open.
+    /// Produces exactly the same proof as `prove_cpu_basic`.
+    pub fn prove_cpu_out_of_core<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        witness_set: WitnessSet<F>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        vk: &VerificationKey<F, H>,
+        proof_config: ProofConfig,
+        transcript_params: TR::TransciptParameters,
+        out_of_core: &OutOfCoreConfig,
+    ) -> Proof<F, H, EXT, N> {
+        self.prove_cpu_impl::<N, EXT, TR, H, POW>(
+            worker,
+            witness_set,
+            setup_base,
+            setup,
+            setup_tree,
+            vk,
+            proof_config,
+            transcript_params,
+            Some(out_of_core),
+        )
+    }
+
+    fn prove_cpu_impl<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         TR: Transcript<F>,
         H: TreeHasher<F, Output = TR::CompatibleCap>,
         POW: PoWRunner,
@@ -165,11 +740,160 @@ impl<
         vk: &VerificationKey<F, H>,
         proof_config: ProofConfig,
         transcript_params: TR::TransciptParameters,
-    ) -> Proof<F, H, EXT> {
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) -> Proof<F, H, EXT, N> {
+        profile_fn!(prove_cpu_basic);
+
+        let state = self.start_proof_impl::<N, EXT, TR, H>(
+            worker,
+            witness_set,
+            setup_base,
+            vk,
+            proof_config,
+            transcript_params,
+            out_of_core,
+        );
+
+        self.finish_proof_impl::<N, EXT, TR, H, POW>(
+            worker,
+            state,
+            setup_base,
+            setup,
+            setup_tree,
+            out_of_core,
+        )
+    }
+
+    /// Commits to the witness and returns the state that should be passed
+    /// to `advance_proof` or `finish_proof`.
+    pub fn start_proof<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F> + serde::Serialize + serde::de::DeserializeOwned,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    >(
+        &self,
+        worker: &Worker,
+        witness_set: WitnessSet<F>,
+        setup_base: &SetupBaseStorage<F, P>,
+        vk: &VerificationKey<F, H>,
+        proof_config: ProofConfig,
+        transcript_params: TR::TransciptParameters,
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) -> ProverState<F, P, EXT, TR, H, N> {
+        self.start_proof_impl::<N, EXT, TR, H>(
+            worker,
+            witness_set,
+            setup_base,
+            vk,
+            proof_config,
+            transcript_params,
+            out_of_core,
+        )
+    }
+
+    /// Runs the stage that follows the last completed one. Setup and
+    /// out-of-core configuration must be the same as used in `start_proof`,
+    /// panics if the setup tree is different.
+    pub fn advance_proof<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F> + serde::Serialize + serde::de::DeserializeOwned,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        state: &mut ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) {
+        self.advance_proof_impl::<N, EXT, TR, H, POW>(
+            worker,
+            state,
+            setup_base,
+            setup,
+            setup_tree,
+            out_of_core,
+        )
+    }
+
+    /// Runs all the remaining stages and answers the queries.
+    pub fn finish_proof<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F> + serde::Serialize + serde::de::DeserializeOwned,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        state: ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) -> Proof<F, H, EXT, N> {
+        self.finish_proof_impl::<N, EXT, TR, H, POW>(
+            worker,
+            state,
+            setup_base,
+            setup,
+            setup_tree,
+            out_of_core,
+        )
+    }
+
+    fn start_proof_impl<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    >(
+        &self,
+        worker: &Worker,
+        witness_set: WitnessSet<F>,
+        setup_base: &SetupBaseStorage<F, P>,
+        vk: &VerificationKey<F, H>,
+        proof_config: ProofConfig,
+        transcript_params: TR::TransciptParameters,
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) -> ProverState<F, P, EXT, TR, H, N> {
+        let mut state = self.compute_witness::<N, EXT, TR, H>(
+            worker,
+            witness_set,
+            setup_base,
+            vk,
+            proof_config,
+            transcript_params,
+        );
+        state.commit_oracle(out_of_core, worker);
+
+        state
+    }
+
+    /// Computes the witness oracle, but leaves it uncommitted,
+    /// see `ProverState::commit_oracle`
+    pub(crate) fn compute_witness<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    >(
+        &self,
+        worker: &Worker,
+        witness_set: WitnessSet<F>,
+        setup_base: &SetupBaseStorage<F, P>,
+        vk: &VerificationKey<F, H>,
+        proof_config: ProofConfig,
+        transcript_params: TR::TransciptParameters,
+    ) -> ProverState<F, P, EXT, TR, H, N> {
         assert!(proof_config.fri_lde_factor.is_power_of_two());
         assert!(proof_config.fri_lde_factor > 1);
 
-        profile_fn!(prove_cpu_basic);
+        profile_fn!(start_proof);
         profile_section!(sect_1);
 
         let base_system_degree = self.max_trace_len;
@@ -183,9 +907,14 @@ impl<
             multiplicities,
         } = witness_set;
 
-        let variables_columns = variables;
-        let witness_columns = witness;
+        let mut variables_columns = variables;
+        let mut witness_columns = witness;
         let mutliplicities_columns = multiplicities;
+
+        if proof_config.zero_knowledge {
+            self.blind_witness(&proof_config, &mut variables_columns, &mut witness_columns)
+                .unwrap_or_else(|err| panic!("{}", err));
+        }
         let public_inputs_only_values = public_inputs_values;
         let public_inputs_with_values = public_inputs_with_locations;
 
@@ -194,14 +923,7 @@ impl<
             public_inputs_with_values.len()
         );
 
-        let interactive_soundness =
-            (F::CAPACITY_BITS * 2) - base_system_degree.trailing_zeros() as usize;
-        dbg!(interactive_soundness);
-
-        let cap_size = proof_config.merkle_tree_cap_size;
-        assert!(cap_size > 0);
-
-        let table_ids_column_idxes = setup.table_ids_column_idxes.clone();
+        assert!(proof_config.merkle_tree_cap_size > 0);
 
         let now = std::time::Instant::now();
 
@@ -213,50 +935,12 @@ impl<
         let mut owned_ctx = P::Context::placeholder();
         let ctx = &mut owned_ctx;
 
-        let selectors_placement = setup_base.selectors_placement.clone();
-        let sigmas = setup_base.copy_permutation_polys.clone();
-
-        let domain_size = sigmas[0].domain_size(); // counted in elements of P
-        assert_eq!(base_system_degree, domain_size);
-        assert!(domain_size.is_power_of_two());
-
-        let x_poly = materialize_x_poly(domain_size, worker);
-
-        let x_poly = std::sync::Arc::new(x_poly);
-
-        let (max_constraint_contribution_degree, _number_of_constant_polys) =
-            selectors_placement.compute_stats();
-
-        let quotient_degree_from_general_purpose_gate_terms =
-            if max_constraint_contribution_degree > 0 {
-                max_constraint_contribution_degree - 1
-            } else {
-                0
-            };
-
-        let max_degree_from_specialized_gates = self
-            .evaluation_data_over_specialized_columns
-            .evaluators_over_specialized_columns
-            .iter()
-            .map(|el| el.max_constraint_degree - 1)
-            .max()
-            .unwrap_or(0);
-
-        let quotient_degree_from_gate_terms = std::cmp::max(
-            quotient_degree_from_general_purpose_gate_terms,
-            max_degree_from_specialized_gates,
+        assert_eq!(
+            base_system_degree,
+            setup_base.copy_permutation_polys[0].domain_size()
         );
 
-        let min_lde_degree_for_gates = if quotient_degree_from_gate_terms.is_power_of_two() {
-            quotient_degree_from_gate_terms
-        } else {
-            quotient_degree_from_gate_terms.next_power_of_two()
-        };
-
-        // In our proof system RS code rate (we usually use "lde factor" that is an inverse of the rate)
-        // is decoupled from the minimal LDE factor we need to compute quotient degree at the end,
-        // so we propagate the parameters to the corresponding places
-        let quotient_degree = min_lde_degree_for_gates;
+        let quotient_degree = self.compute_quotient_degree(setup_base);
 
         dbg!(quotient_degree);
 
@@ -278,8 +962,6 @@ impl<
             })
             .collect();
 
-        let num_variable_polys = variables_columns.len();
-
         let witness_columns: Vec<_> = witness_columns
             .into_iter()
             .map(|el| {
@@ -291,8 +973,6 @@ impl<
             })
             .collect();
 
-        let num_witness_polys = witness_columns.len();
-
         let mutliplicities_columns: Vec<_> = mutliplicities_columns
             .into_iter()
             .map(|el| {
@@ -307,9 +987,6 @@ impl<
         let num_multiplicities_polys = mutliplicities_columns.len();
         assert_eq!(num_multiplicities_polys, self.num_multipicities_polys());
 
-        let num_constant_polys = setup.constant_columns.len();
-        let num_copy_permutation_polys = setup.copy_permutation_polys.len();
-
         let used_lde_degree = std::cmp::max(proof_config.fri_lde_factor, quotient_degree);
         log!("Will operate with LDEs of factor {}", used_lde_degree);
 
@@ -322,46 +999,351 @@ impl<
             ctx,
         );
 
-        let mut source = vec![];
-        source.extend(
-            witness_storage
-                .variables_columns
-                .iter()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
-        source.extend(
-            witness_storage
-                .witness_columns
-                .iter()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
-        source.extend(
-            witness_storage
-                .lookup_multiplicities_polys
-                .iter()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
+        log!("Witness LDE taken {:?}", now.elapsed());
+
+        let witness_salt = generate_salt(&proof_config, base_system_degree);
+
+        let zero = ExtensionField::<F, N, EXT>::ZERO;
+
+        ProverState {
+            stage: ProverStage::WitnessCommitted,
+            proof_config,
+            transcript,
+            setup_cap: vk.setup_merkle_tree_cap.clone(),
+            public_inputs_values: public_inputs_only_values,
+            public_inputs_with_locations: public_inputs_with_values,
+            variables_columns,
+            multiplicities_columns: mutliplicities_columns,
+            witness_storage: Some(witness_storage),
+            witness_tree: None,
+            spilled_witness: None,
+            witness_salt,
+            beta: zero,
+            gamma: zero,
+            lookup_beta: zero,
+            lookup_gamma: zero,
+            second_stage_polys_storage: None,
+            second_stage_tree: None,
+            spilled_second_stage: None,
+            second_stage_salt: vec![],
+            quotient_chunks_ldes: None,
+            quotients_tree: None,
+            spilled_quotients: None,
+            quotients_salt: vec![],
+            values_at_z: vec![],
+            values_at_z_omega: vec![],
+            values_at_0: vec![],
+            fri_base_oracle_sources: None,
+            fri_data: None,
+            fri_folding_schedule: vec![],
+            num_queries: 0,
+            pow_challenge: 0,
+        }
+    }
+
+    pub(crate) fn advance_proof_impl<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        state: &mut ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) {
+        state.check_setup(setup_tree);
+        let now = std::time::Instant::now();
+
+        match state.stage {
+            ProverStage::WitnessCommitted => {
+                self.compute_second_stage(worker, state, setup_base);
+                state.commit_oracle(out_of_core, worker);
+            }
+            ProverStage::SecondStageCommitted => {
+                self.compute_quotient(worker, state, setup_base, setup);
+                state.commit_oracle(out_of_core, worker);
+            }
+            ProverStage::QuotientCommitted => {
+                self.compute_deep_openings(worker, state, setup_base, setup)
+            }
+            ProverStage::DeepOpeningsComputed => {
+                self.commit_fri::<N, EXT, TR, H, POW>(worker, state)
+            }
+            ProverStage::FriCommitted => {
+                panic!("all the stages are completed, only queries are left")
+            }
+        }
+
+        log!("Reached stage {:?} in {:?}", state.stage, now.elapsed());
+    }
+
+    fn finish_proof_impl<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        mut state: ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        out_of_core: Option<&OutOfCoreConfig>,
+    ) -> Proof<F, H, EXT, N> {
+        state.check_setup(setup_tree);
+        while state.stage != ProverStage::FriCommitted {
+            self.advance_proof_impl::<N, EXT, TR, H, POW>(
+                worker,
+                &mut state,
+                setup_base,
+                setup,
+                setup_tree,
+                out_of_core,
+            );
+        }
+
+        profile_fn!(finish_proof);
+
+        let domain_size = self.max_trace_len;
+        let lde_factor_for_fri = state.proof_config.fri_lde_factor;
+
+        let fri_data = state.fri_data.take().expect("FRI must be committed");
+        let base_fri_source = state
+            .fri_base_oracle_sources
+            .take()
+            .expect("DEEP openings must be computed");
+
+        let mut proof = Proof::<F, H, EXT, N> {
+            proof_config: state.proof_config.clone(),
+            public_inputs: std::mem::take(&mut state.public_inputs_values),
+            witness_oracle_cap: state
+                .witness_tree
+                .as_ref()
+                .expect("witness must be committed")
+                .get_cap(),
+            stage_2_oracle_cap: state
+                .second_stage_tree
+                .as_ref()
+                .expect("second stage must be committed")
+                .get_cap(),
+            quotient_oracle_cap: state
+                .quotients_tree
+                .as_ref()
+                .expect("quotient must be committed")
+                .get_cap(),
+            final_fri_monomials: fri_data.monomial_forms.clone(),
+            values_at_z: std::mem::take(&mut state.values_at_z),
+            values_at_z_omega: std::mem::take(&mut state.values_at_z_omega),
+            values_at_0: std::mem::take(&mut state.values_at_0),
+            pow_challenge: state.pow_challenge,
+            fri_base_oracle_cap: fri_data.base_oracle.get_cap(),
+            fri_intermediate_oracles_caps: fri_data
+                .intermediate_oracles
+                .iter()
+                .map(|el| el.get_cap())
+                .collect(),
+            queries_per_fri_repetition: vec![],
+            _marker: std::marker::PhantomData,
+        };
+
+        let max_needed_bits = (domain_size * lde_factor_for_fri).trailing_zeros() as usize;
+        let mut bools_buffer = BoolsBuffer {
+            available: vec![],
+            max_needed: max_needed_bits,
+        };
+
+        let num_bits_for_in_coset_index =
+            max_needed_bits - lde_factor_for_fri.trailing_zeros() as usize;
+
+        for _query_idx in 0..state.num_queries {
+            let query_index_lsb_first_bits =
+                bools_buffer.get_bits(&mut state.transcript, max_needed_bits);
+            // we consider it to be some convenient for us encoding of coset + inner index.
+
+            let inner_idx = u64_from_lsb_first_bits(
+                &query_index_lsb_first_bits[0..num_bits_for_in_coset_index],
+            ) as usize;
+            let coset_idx =
+                u64_from_lsb_first_bits(&query_index_lsb_first_bits[num_bits_for_in_coset_index..])
+                    as usize;
+
+            let BaseOraclesQueries {
+                witness_query,
+                stage_2_query,
+                quotient_query,
+                setup_query,
+            } = state.query_base_oracles(setup, setup_tree, domain_size, coset_idx, inner_idx);
+
+            let fri_queries = query_fri_oracles(
+                &fri_data,
+                &base_fri_source,
+                &state.fri_folding_schedule,
+                lde_factor_for_fri,
+                coset_idx,
+                domain_size,
+                inner_idx,
+            );
+
+            let queries = SingleRoundQueries {
+                witness_query,
+                stage_2_query,
+                quotient_query,
+                setup_query,
+                fri_queries,
+            };
+
+            proof.queries_per_fri_repetition.push(queries);
+        }
+
+        proof
+    }
+
+    /// Checks that the circuit can be proven in zero-knowledge mode with the given config.
+    /// Blinding rows can only hold random values in general purpose columns: gates over specialized
+    /// columns are enforced over every row, and multiplicities of lookups would reveal what was looked up
+    pub fn check_zero_knowledge_support(
+        &self,
+        proof_config: &ProofConfig,
+    ) -> Result<(), ZeroKnowledgeError> {
+        if self.lookup_parameters.lookup_is_allowed() {
+            return Err(ZeroKnowledgeError::LookupsAreUsed);
+        }
+        if self
+            .evaluation_data_over_specialized_columns
+            .evaluators_over_specialized_columns
+            .is_empty()
+            == false
+        {
+            return Err(ZeroKnowledgeError::SpecializedColumnsAreUsed);
+        }
+
+        let required = proof_config.num_blinding_rows(self.max_trace_len);
+        if self.blinding_rows.len() < required {
+            return Err(ZeroKnowledgeError::NotEnoughBlindingRows {
+                reserved: self.blinding_rows.len(),
+                required,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Fills blinding rows with random values. Copiable cells in those rows are
+    /// paired (see `add_blinding_rows`), so values are random per pair of rows
+    fn blind_witness(
+        &self,
+        proof_config: &ProofConfig,
+        variables_columns: &mut [Polynomial<F, LagrangeForm, Global>],
+        witness_columns: &mut [Polynomial<F, LagrangeForm, Global>],
+    ) -> Result<(), ZeroKnowledgeError> {
+        self.check_zero_knowledge_support(proof_config)?;
+
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+
+        for column in variables_columns.iter_mut() {
+            for row in self.blinding_rows.clone().step_by(2) {
+                let value = F::from_u64_with_reduction(rng.gen());
+                column.storage[row] = value;
+                column.storage[row + 1] = value;
+            }
+        }
+
+        for column in witness_columns.iter_mut() {
+            for row in self.blinding_rows.clone() {
+                column.storage[row] = F::from_u64_with_reduction(rng.gen());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn compute_quotient_degree(&self, setup_base: &SetupBaseStorage<F, P>) -> usize {
+        let (max_constraint_contribution_degree, _number_of_constant_polys) =
+            setup_base.selectors_placement.compute_stats();
+
+        let quotient_degree_from_general_purpose_gate_terms =
+            if max_constraint_contribution_degree > 0 {
+                max_constraint_contribution_degree - 1
+            } else {
+                0
+            };
+
+        let max_degree_from_specialized_gates = self
+            .evaluation_data_over_specialized_columns
+            .evaluators_over_specialized_columns
+            .iter()
+            .map(|el| el.max_constraint_degree - 1)
+            .max()
+            .unwrap_or(0);
+
+        let quotient_degree_from_gate_terms = std::cmp::max(
+            quotient_degree_from_general_purpose_gate_terms,
+            max_degree_from_specialized_gates,
+        );
+
+        let min_lde_degree_for_gates = if quotient_degree_from_gate_terms.is_power_of_two() {
+            quotient_degree_from_gate_terms
+        } else {
+            quotient_degree_from_gate_terms.next_power_of_two()
+        };
+
+        // In our proof system RS code rate (we usually use "lde factor" that is an inverse of the rate)
+        // is decoupled from the minimal LDE factor we need to compute quotient degree at the end,
+        // so we propagate the parameters to the corresponding places
+        min_lde_degree_for_gates
+    }
+
+    /// Computes the second stage oracle, but leaves it uncommitted,
+    /// see `ProverState::commit_oracle`
+    pub(crate) fn compute_second_stage<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    >(
+        &self,
+        worker: &Worker,
+        state: &mut ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+    ) {
+        assert_eq!(state.stage, ProverStage::WitnessCommitted);
 
-        log!("Witness LDE taken {:?}", now.elapsed());
+        profile_fn!(compute_second_stage);
 
-        let witness_tree = MerkleTreeWithCap::<F, H>::construct(source, cap_size, worker);
+        let proof_config = state.proof_config.clone();
+        let domain_size = self.max_trace_len;
+        let quotient_degree = self.compute_quotient_degree(setup_base);
+        let used_lde_degree = std::cmp::max(proof_config.fri_lde_factor, quotient_degree);
 
-        let witness_tree_cap = witness_tree.get_cap();
+        let mut owned_ctx = P::Context::placeholder();
+        let ctx = &mut owned_ctx;
 
-        profile_section!(mt_cap);
+        let sigmas = setup_base.copy_permutation_polys.clone();
+        let x_poly = materialize_x_poly(domain_size, worker);
+        let x_poly = std::sync::Arc::new(x_poly);
 
-        transcript.witness_merkle_tree_cap(witness_tree_cap.as_ref());
+        // base traces are not needed anymore after this stage
+        let variables_columns = std::mem::take(&mut state.variables_columns);
+        let mutliplicities_columns = std::mem::take(&mut state.multiplicities_columns);
 
-        drop(mt_cap);
+        let transcript = &mut state.transcript;
 
         // here we commit to our original witness,
         // potentially including lookup related one
 
-        let beta_coeffs = transcript.get_multiple_challenges_fixed::<2>();
-        let beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(beta_coeffs);
+        let beta_coeffs = transcript.get_multiple_challenges_fixed::<N>();
+        let beta = ExtensionField::<F, N, EXT>::from_coeff_in_base(beta_coeffs);
 
-        let gamma_coeffs = transcript.get_multiple_challenges_fixed::<2>();
-        let gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(gamma_coeffs);
+        let gamma_coeffs = transcript.get_multiple_challenges_fixed::<N>();
+        let gamma = ExtensionField::<F, N, EXT>::from_coeff_in_base(gamma_coeffs);
 
         let copy_permutation_chunking_degree = quotient_degree;
 
@@ -371,6 +1353,7 @@ impl<
             super::copy_permutation::compute_partial_products_in_extension::<
                 F,
                 P,
+                N,
                 EXT,
                 Global,
                 Global,
@@ -389,8 +1372,6 @@ impl<
         // but now over copy-permutation and lookup grand products,
         // as well as auxilary polys for lookup argument
 
-        let num_intermediate_partial_product_relations = intermediate_products.len();
-
         let (
             (lookup_witness_encoding_polys, lookup_multiplicities_encoding_polys),
             lookup_beta,
@@ -399,11 +1380,11 @@ impl<
             profile_section!(get_lookups);
 
             // lookup argument related parts
-            let lookup_beta = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_beta);
+            let lookup_beta = transcript.get_multiple_challenges_fixed::<N>();
+            let lookup_beta = ExtensionField::<F, N, EXT>::from_coeff_in_base(lookup_beta);
 
-            let lookup_gamma = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_gamma);
+            let lookup_gamma = transcript.get_multiple_challenges_fixed::<N>();
+            let lookup_gamma = ExtensionField::<F, N, EXT>::from_coeff_in_base(lookup_gamma);
 
             let contributions = match self.lookup_parameters {
                 LookupParameters::NoLookup => {
@@ -460,8 +1441,8 @@ impl<
                         }
                     }
                     super::lookup_argument_in_ext::compute_lookup_poly_pairs_specialized(
-                        variables_columns.clone(),
-                        mutliplicities_columns.clone(),
+                        variables_columns,
+                        mutliplicities_columns,
                         setup_base.constant_columns.clone(),
                         setup_base.lookup_tables_columns.clone(),
                         setup_base.table_ids_column_idxes.clone(),
@@ -477,7 +1458,7 @@ impl<
 
             (contributions, lookup_beta, lookup_gamma)
         } else {
-            let zero = ExtensionField::<F, 2, EXT>::ZERO;
+            let zero = ExtensionField::<F, N, EXT>::ZERO;
 
             ((vec![], vec![]), zero, zero)
         };
@@ -500,7 +1481,6 @@ impl<
             .collect();
 
         drop(lde_lookups);
-        drop(sect_1);
 
         let second_stage_polys_storage = SecondStageProductsStorage::from_base_trace_ext(
             z_poly.clone(),
@@ -514,56 +1494,58 @@ impl<
 
         log!("Second stage LDE taken {:?}", now.elapsed());
 
-        // and commit
-        profile_section!(commit_comething);
+        state.beta = beta;
+        state.gamma = gamma;
+        state.lookup_beta = lookup_beta;
+        state.lookup_gamma = lookup_gamma;
+        state.second_stage_polys_storage = Some(second_stage_polys_storage);
+        state.second_stage_salt = generate_salt(&proof_config, self.max_trace_len);
+        state.stage = ProverStage::SecondStageCommitted;
+    }
 
-        let mut source = vec![];
-        source.extend(
-            second_stage_polys_storage
-                .z_poly
-                .iter()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
-        source.extend(
-            second_stage_polys_storage
-                .intermediate_polys
-                .iter()
-                .flatten()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
-        source.extend(
-            second_stage_polys_storage
-                .lookup_witness_encoding_polys
-                .iter()
-                .flatten()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
-        source.extend(
-            second_stage_polys_storage
-                .lookup_multiplicities_encoding_polys
-                .iter()
-                .flatten()
-                .map(|el| el.subset_for_degree(proof_config.fri_lde_factor)),
-        );
+    /// Computes the quotient oracle, but leaves it uncommitted,
+    /// see `ProverState::commit_oracle`
+    pub(crate) fn compute_quotient<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    >(
+        &self,
+        worker: &Worker,
+        state: &mut ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+    ) {
+        assert_eq!(state.stage, ProverStage::SecondStageCommitted);
+
+        profile_fn!(compute_quotient);
 
-        let second_stage_tree = MerkleTreeWithCap::<F, H>::construct(source, cap_size, worker);
+        let proof_config = state.proof_config.clone();
+        let base_system_degree = self.max_trace_len;
+        let domain_size = self.max_trace_len;
+        let quotient_degree = self.compute_quotient_degree(setup_base);
+        let used_lde_degree = std::cmp::max(proof_config.fri_lde_factor, quotient_degree);
 
-        // now we can commit to grand products and get new challenges
-        let second_stage_tree_cap = second_stage_tree.get_cap();
+        let mut owned_ctx = P::Context::placeholder();
+        let ctx = &mut owned_ctx;
 
-        transcript.witness_merkle_tree_cap(second_stage_tree_cap.as_ref());
+        let selectors_placement = setup_base.selectors_placement.clone();
 
-        drop(commit_comething);
+        let (beta, gamma) = (state.beta, state.gamma);
+        let (lookup_beta, lookup_gamma) = (state.lookup_beta, state.lookup_gamma);
 
         let now = std::time::Instant::now();
 
-        let alpha = transcript.get_multiple_challenges_fixed::<2>();
-        let alpha = ExtensionField::<F, 2, EXT>::from_coeff_in_base(alpha);
+        let alpha = state.transcript.get_multiple_challenges_fixed::<N>();
+        let alpha = ExtensionField::<F, N, EXT>::from_coeff_in_base(alpha);
 
         // two extra relations per lookup subargument - for A and B polys
         let num_lookup_subarguments = self.num_sublookup_arguments();
         let num_multiplicities_polys = self.num_multipicities_polys();
         let total_num_lookup_argument_terms = num_lookup_subarguments + num_multiplicities_polys;
+        let num_intermediate_partial_product_relations = state
+            .num_intermediate_partial_products(num_lookup_subarguments, num_multiplicities_polys);
 
         let total_num_gate_terms_for_specialized_columns = self
             .evaluation_data_over_specialized_columns
@@ -625,22 +1607,236 @@ impl<
         let remaining_challenges = rest.to_vec();
         // now we can evaluate the constraints
 
-        let inner_size = variables_columns[0].storage.len(); // counted in elements of P
-
-        let trace_holder = TraceHolder {
-            variables: witness_storage,
-            setup: setup.clone(),
+        let coset = if crate::config::DEBUG_SATISFIABLE == false {
+            F::multiplicative_generator()
+        } else {
+            F::ONE
         };
 
+        let x_poly_lde = materialize_x_poly_as_arc_lde::<F, P, Global, Global>(
+            domain_size,
+            used_lde_degree,
+            coset,
+            worker,
+            ctx,
+        );
+
+        let unnormalized_l1_inverse = unnormalized_l1_inverse::<F, P, Global, Global>(
+            domain_size,
+            quotient_degree, // we do not need full degree
+            F::multiplicative_generator(),
+            worker,
+            ctx,
+        );
+
+        // if the trace was spilled we only load one coset of it at a time
+        let mut quotients: [Vec<Vec<P>>; N] =
+            std::array::from_fn(|_| Vec::with_capacity(quotient_degree));
+        for cosets in state.coset_batches(quotient_degree) {
+            let trace_holder = TraceHolder {
+                variables: state.witness_for_cosets(
+                    cosets.clone(),
+                    self.parameters.num_columns_under_copy_permutation,
+                    self.parameters.num_witness_columns,
+                    worker,
+                ),
+                setup: setup.subset_for_cosets(cosets.clone()),
+            };
+            let second_stage_polys_storage = state.second_stage_for_cosets(
+                cosets.clone(),
+                num_lookup_subarguments,
+                num_multiplicities_polys,
+                worker,
+            );
+
+            let batch = self.evaluate_quotient_terms_over_cosets::<N, EXT>(
+                cosets.clone(),
+                &trace_holder,
+                &second_stage_polys_storage,
+                &x_poly_lde.subset_for_cosets(cosets.clone()),
+                &unnormalized_l1_inverse.subset_for_cosets(cosets),
+                &selectors_placement,
+                quotient_degree,
+                &pregenerated_challenges_for_lookup,
+                &pregenerated_challenges_for_gates_over_specialized_columns,
+                &pregenerated_challenges_for_gates_over_general_purpose_columns,
+                &remaining_challenges,
+                (beta, gamma),
+                (lookup_beta, lookup_gamma),
+                worker,
+                ctx,
+            );
+            for (dst, src) in quotients.iter_mut().zip(batch.into_iter()) {
+                dst.extend(src);
+            }
+        }
+
+        profile_section!(sect_6);
+
+        let outer_size = quotients[0].len();
+        assert_eq!(outer_size, quotient_degree);
+        let quotient_domain_size = outer_size * domain_size;
+        let inverse_twiddles =
+            P::precompute_inverse_twiddles_for_fft::<Global>(quotient_domain_size, worker, ctx);
+
+        let quotient_monomials = quotients.map(|mut q_as_vectors| {
+            if crate::config::DEBUG_SATISFIABLE == false {
+                divide_by_vanishing_for_bitreversed_coset_enumeration(
+                    &mut q_as_vectors,
+                    worker,
+                    ctx,
+                );
+            }
+
+            let q: Vec<Vec<F>> = q_as_vectors
+                .into_iter()
+                .map(|el| P::vec_into_base_vec(el))
+                .collect();
+
+            let flattened = flatten_presumably_bitreversed::<_, P, _, _>(q, worker);
+            let mut monomial_form = P::vec_from_base_vec(flattened);
+
+            if crate::config::DEBUG_SATISFIABLE {
+                let as_slice = P::slice_into_base_slice(&monomial_form);
+                for (idx, el) in as_slice.iter().step_by(quotient_degree).enumerate() {
+                    assert_eq!(*el, F::ZERO, "poly is not divisible at row {}", idx);
+                }
+            }
+
+            P::ifft_natural_to_natural(&mut monomial_form, coset, &inverse_twiddles, ctx);
+
+            if crate::config::DEBUG_SATISFIABLE == false {
+                assert!(
+                    P::slice_into_base_slice(&monomial_form)
+                        .last()
+                        .unwrap()
+                        .is_zero(),
+                    "unsatisfied",
+                );
+            }
+
+            GenericPolynomial::<F, MonomialForm, P, _>::from_storage(monomial_form)
+        });
+
+        // now we can chunk every polynomial and LDE them
+
+        // NOTE: for all the polynomials below we will have an option to perform evaluation at random
+        // point by using barycentric formula, while for quotient we could potentially save monomial form
+        // for some time and use horner rule. We neglect small change of proof time in favor of simplicity
+        // and memory consumption
+
+        let mut quotient_chunks = vec![];
+        // we layout [c0, c1, ...] in every chunk
+        let mut chunked_coefficients = quotient_monomials.map(|el| {
+            el.chunk_into_subpolys_of_degree::<Global>(base_system_degree)
+                .into_iter()
+        });
+
+        for _ in 0..quotient_degree {
+            for coeff in chunked_coefficients.iter_mut() {
+                quotient_chunks.push(coeff.next().expect("must have a chunk").into_storage());
+            }
+        }
+
+        // masking polynomials are committed right after the chunks and have the same degree,
+        // so the DEEP batch and every FRI oracle are uniformly random
+        {
+            use rand::Rng;
+            let mut rng = rand::thread_rng();
+            for _ in 0..(proof_config.num_masking_polys() * N) {
+                let mut coeffs =
+                    allocate_in_with_alignment_of::<F, P, Global>(base_system_degree, Global);
+                coeffs
+                    .extend((0..base_system_degree).map(|_| F::from_u64_with_reduction(rng.gen())));
+                quotient_chunks.push(P::vec_from_base_vec(coeffs));
+            }
+        }
+
+        // how we should LDE quotients and form another oracle
+
+        let forward_twiddles =
+            P::precompute_forward_twiddles_for_fft::<Global>(domain_size, worker, ctx);
+
+        // we do not need full quotient LDE degree here, only large enough for FRI
+        let quotient_chunks_ldes = transform_monomials_to_lde(
+            quotient_chunks,
+            domain_size,
+            proof_config.fri_lde_factor,
+            &forward_twiddles,
+            worker,
+            ctx,
+        );
+
+        drop(sect_6);
+        profile_section!(sect_7);
+
+        log!("Quotient work and LDE taken {:?}", now.elapsed());
+
+        state.quotient_chunks_ldes = Some(quotient_chunks_ldes);
+        state.quotients_salt = generate_salt(&proof_config, domain_size);
+        state.stage = ProverStage::QuotientCommitted;
+    }
+
+    /// Evaluates all the terms of the quotient, before division by the vanishing polynomial,
+    /// over some consecutive cosets of the LDE. Witness, second stage and setup storages must contain
+    /// exactly those cosets, so it can be done for the full LDE at once, or coset by coset
+    /// if the trace was spilled.
+    fn evaluate_quotient_terms_over_cosets<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    >(
+        &self,
+        cosets: Range<usize>,
+        trace_holder: &TraceHolder<F, P>,
+        second_stage_polys_storage: &SecondStageProductsStorage<F, N, P>,
+        x_poly_lde: &ArcGenericLdeStorage<F, P>,
+        unnormalized_l1_inverse: &ArcGenericLdeStorage<F, P>,
+        selectors_placement: &TreeNode,
+        quotient_degree: usize,
+        pregenerated_challenges_for_lookup: &[ExtensionField<F, N, EXT>],
+        pregenerated_challenges_for_gates_over_specialized_columns: &[ExtensionField<F, N, EXT>],
+        pregenerated_challenges_for_gates_over_general_purpose_columns: &[ExtensionField<
+            F,
+            N,
+            EXT,
+        >],
+        remaining_challenges: &[ExtensionField<F, N, EXT>],
+        copy_permutation_challenges: (ExtensionField<F, N, EXT>, ExtensionField<F, N, EXT>),
+        lookup_challenges: (ExtensionField<F, N, EXT>, ExtensionField<F, N, EXT>),
+        worker: &Worker,
+        ctx: &mut P::Context,
+    ) -> [Vec<Vec<P>>; N] {
+        let num_cosets = cosets.len();
+        let domain_size = self.max_trace_len;
+        let inner_size = domain_size / P::SIZE_FACTOR; // counted in elements of P
+                                                       // sanity checks below expect the main domain to be the first coset
+        let debug_satisfiable = crate::config::DEBUG_SATISFIABLE && cosets.start == 0;
+
+        let (beta, gamma) = copy_permutation_challenges;
+        let (lookup_beta, lookup_gamma) = lookup_challenges;
+        let table_ids_column_idxes = trace_holder.setup.table_ids_column_idxes.clone();
+        let num_intermediate_partial_product_relations =
+            second_stage_polys_storage.intermediate_polys.len();
+        let num_lookup_subarguments = self.num_sublookup_arguments();
+        let num_multiplicities_polys = self.num_multipicities_polys();
+        let total_num_lookup_argument_terms = num_lookup_subarguments + num_multiplicities_polys;
+        let total_num_gate_terms_for_specialized_columns =
+            pregenerated_challenges_for_gates_over_specialized_columns.len();
+        let pregenerated_challenges_for_gates_over_specialized_columns =
+            pregenerated_challenges_for_gates_over_specialized_columns.to_vec();
+        let pregenerated_challenges_for_gates_over_general_purpose_columns =
+            pregenerated_challenges_for_gates_over_general_purpose_columns.to_vec();
+
         let sources = ProverTraceView::chunks_from_trace_for_degree(
-            &trace_holder,
-            quotient_degree,
+            trace_holder,
+            num_cosets,
             worker.num_cores,
         );
 
         let mut destination = GateEvaluationReducingDestination::<F, P>::new(
             inner_size,
-            quotient_degree,
+            num_cosets,
+            N,
             vec![], // we will reassign later on
             ctx,
         );
@@ -724,7 +1920,7 @@ impl<
             let pregenerated_challenges_for_gates_over_specialized_columns: Vec<_> =
                 pregenerated_challenges_for_gates_over_specialized_columns
                     .into_iter()
-                    .map(|el| el.into_coeffs_in_base())
+                    .map(|el| el.into_coeffs_in_base().to_vec())
                     .collect();
             destination.challenges_powers =
                 std::sync::Arc::new(pregenerated_challenges_for_gates_over_specialized_columns);
@@ -805,7 +2001,7 @@ impl<
         let pregenerated_challenges_for_gates_over_general_purpose_columns: Vec<_> =
             pregenerated_challenges_for_gates_over_general_purpose_columns
                 .into_iter()
-                .map(|el| el.into_coeffs_in_base())
+                .map(|el| el.into_coeffs_in_base().to_vec())
                 .collect();
         destination.challenges_powers =
             std::sync::Arc::new(pregenerated_challenges_for_gates_over_general_purpose_columns);
@@ -830,13 +2026,13 @@ impl<
                     compute_selector_subpath(
                         path.clone(),
                         &mut selectors_buffer,
-                        quotient_degree,
-                        setup,
+                        num_cosets,
+                        &trace_holder.setup,
                         worker,
                         ctx,
                     );
 
-                    if crate::config::DEBUG_SATISFIABLE {
+                    if debug_satisfiable {
                         let selector = selectors_buffer
                             // .remove(&path)
                             .get(&path)
@@ -932,7 +2128,7 @@ impl<
                                     // dbg!(&evaluator.debug_name);
                                     // dbg!(&path);
 
-                                    if crate::config::DEBUG_SATISFIABLE {
+                                    if debug_satisfiable {
                                         let selector = selectors_buffer
                                             // .remove(&path)
                                             .get(&path)
@@ -1085,13 +2281,13 @@ impl<
         }
 
         drop(sect_5);
-        profile_section!(sect_6);
 
-        let [quotient_c0s, quotient_c1s] =
+        let quotients =
             Arc::try_unwrap(destination.quotient_buffers).expect("must be exclusively owned");
+        assert_eq!(quotients.len(), N);
 
-        if crate::config::DEBUG_SATISFIABLE {
-            for (idx, el) in quotient_c0s[0].iter().enumerate() {
+        if debug_satisfiable {
+            for (idx, el) in quotients[0][0].iter().enumerate() {
                 if el.is_zero() == false {
                     let mut normal_enumeration = idx.reverse_bits();
                     normal_enumeration >>= usize::BITS - domain_size.trailing_zeros();
@@ -1108,272 +2304,206 @@ impl<
             }
         }
 
-        let inner_size = domain_size / P::SIZE_FACTOR;
-        let outer_size = quotient_c0s.len();
-        let quotient_domain_size = outer_size * domain_size;
-        let inverse_twiddles =
-            P::precompute_inverse_twiddles_for_fft::<Global>(quotient_domain_size, worker, ctx);
-
-        let coset = if crate::config::DEBUG_SATISFIABLE == false {
-            F::multiplicative_generator()
-        } else {
-            F::ONE
-        };
-
-        let x_poly_lde = materialize_x_poly_as_arc_lde::<F, P, Global, Global>(
-            domain_size,
-            used_lde_degree,
-            coset,
-            worker,
-            ctx,
-        );
-
-        let coset = if crate::config::DEBUG_SATISFIABLE == false {
-            F::multiplicative_generator()
-        } else {
-            F::ONE
-        };
-
-        let unnormalized_l1_inverse = unnormalized_l1_inverse::<F, P, Global, Global>(
-            domain_size,
-            quotient_degree, // we do not need full degree
-            F::multiplicative_generator(),
-            worker,
-            ctx,
-        );
-
-        let quotient_monomials = {
-            // we will need to add the corresponding contribution from copy permutation
+        // we will need to add the corresponding contribution from copy permutation
 
-            // z(1) == 1 => (z(x) - 1) * L_1(x) == 0
-            // that is equivalent to divisibility check that
-            // (z(x) - 1) is divisible by (x - 1)
+        // z(1) == 1 => (z(x) - 1) * L_1(x) == 0
+        // that is equivalent to divisibility check that
+        // (z(x) - 1) is divisible by (x - 1)
 
-            // But in order to batch division by x^n - 1 below we will compute an (unnormalized)
-            // (z(x) - 1) * L_1(x) term anyway
+        // But in order to batch division by x^n - 1 below we will compute an (unnormalized)
+        // (z(x) - 1) * L_1(x) term anyway
 
-            // later on we will need terms like
-            // partial_product_i = partial_product_{i-1} * rational_function, but we have everything precomputed
-            // for it already
+        // later on we will need terms like
+        // partial_product_i = partial_product_{i-1} * rational_function, but we have everything precomputed
+        // for it already
 
-            // the last part is z(x * omega) = partial_product_{n-1} * rational_function
+        // the last part is z(x * omega) = partial_product_{n-1} * rational_function
 
-            let mut q_c0_as_lde =
-                ArcGenericLdeStorage::empty_with_capacity_in(quotient_c0s.len(), Global);
-            let mut q_c1_as_lde =
-                ArcGenericLdeStorage::empty_with_capacity_in(quotient_c0s.len(), Global);
-            for el in quotient_c0s.into_iter() {
-                q_c0_as_lde
+        let mut quotients = quotients.into_iter();
+        let mut dst: [ArcGenericLdeStorage<F, P, Global, Global>; N] = std::array::from_fn(|_| {
+            let quotient = quotients.next().expect("must have all the coefficients");
+            let mut q_as_lde = ArcGenericLdeStorage::empty_with_capacity_in(quotient.len(), Global);
+            for el in quotient.into_iter() {
+                q_as_lde
                     .storage
                     .push(Arc::new(GenericPolynomial::from_storage(el)));
             }
-            for el in quotient_c1s.into_iter() {
-                q_c1_as_lde
-                    .storage
-                    .push(Arc::new(GenericPolynomial::from_storage(el)));
-            }
-
-            let mut challenges_it = remaining_challenges.iter();
-            let mut lookup_challenges_it = pregenerated_challenges_for_lookup.iter();
 
-            let one = P::one(ctx);
-            let alpha_power = challenges_it.next().expect("must have enough challenges");
-            let alpha_power_c0 = P::constant(alpha_power.coeffs[0], ctx);
-            let alpha_power_c1 = P::constant(alpha_power.coeffs[1], ctx);
-
-            let z_poly_c0 = second_stage_polys_storage.z_poly[0].subset_for_degree(quotient_degree);
-            let z_poly_c1 = second_stage_polys_storage.z_poly[1].subset_for_degree(quotient_degree);
-
-            let mut dst = [q_c0_as_lde, q_c1_as_lde];
+            q_as_lde
+        });
 
-            if crate::config::DEBUG_SATISFIABLE == false {
-                let src = [
-                    z_poly_c0.clone(),
-                    z_poly_c1.clone(),
-                    unnormalized_l1_inverse,
-                ];
-
-                let op = #[inline(always)]
-                move |dst: &mut [ArcGenericLdeStorage<F, P, Global, Global>; 2],
-                               src: &[ArcGenericLdeStorage<F, P, Global, Global>; 3],
-                               outer: usize,
-                               inner: usize,
-                               ctx: &mut P::Context| {
-                    let mut z_c0 = src[0].storage[outer].storage[inner];
-                    // sub 1
-                    z_c0.sub_assign(&one, ctx);
-                    let mut z_c1 = src[1].storage[outer].storage[inner];
-                    let l1_inv = &src[2].storage[outer].storage[inner];
-                    z_c0.mul_assign(l1_inv, ctx);
-                    z_c1.mul_assign(l1_inv, ctx);
-                    // mul by alpha
-                    mul_assign_vectorized_in_extension::<F, P, EXT>(
-                        &mut z_c0,
-                        &mut z_c1,
-                        &alpha_power_c0,
-                        &alpha_power_c1,
-                        ctx,
-                    );
+        let mut challenges_it = remaining_challenges.iter();
+        let mut lookup_challenges_it = pregenerated_challenges_for_lookup.iter();
+
+        let one = P::one(ctx);
+        let alpha_power = challenges_it.next().expect("must have enough challenges");
+        let alpha_power = alpha_power.coeffs.map(|el| P::constant(el, ctx));
+
+        let z_poly = second_stage_polys_storage.z_poly.clone();
+
+        if crate::config::DEBUG_SATISFIABLE == false {
+            let l1_inverse = unnormalized_l1_inverse.clone();
+
+            let op = #[inline(always)]
+            move |dst: &mut [ArcGenericLdeStorage<F, P, Global, Global>; N],
+                           src: &[ArcGenericLdeStorage<F, P, Global, Global>; N],
+                           outer: usize,
+                           inner: usize,
+                           ctx: &mut P::Context| {
+                let mut z: [P; N] = std::array::from_fn(|i| src[i].storage[outer].storage[inner]);
+                // sub 1
+                z[0].sub_assign(&one, ctx);
+                let l1_inv = &l1_inverse.storage[outer].storage[inner];
+                for el in z.iter_mut() {
+                    el.mul_assign(l1_inv, ctx);
+                }
+                // mul by alpha
+                mul_assign_vectorized_in_extension::<F, P, N, EXT>(&mut z, &alpha_power, ctx);
 
+                for (dst, z) in dst.iter_mut().zip(z.iter()) {
                     unsafe {
-                        Arc::get_mut_unchecked(&mut dst[0].storage[outer]).storage[inner]
-                            .add_assign(&z_c0, ctx);
-
-                        Arc::get_mut_unchecked(&mut dst[1].storage[outer]).storage[inner]
-                            .add_assign(&z_c1, ctx);
+                        Arc::get_mut_unchecked(&mut dst.storage[outer]).storage[inner]
+                            .add_assign(z, ctx);
                     }
-                };
+                }
+            };
 
-                apply_multiop(&mut dst, &src, &op, worker, ctx)
-            } else {
+            apply_multiop(&mut dst, &z_poly, &op, worker, ctx)
+        } else if cosets.start == 0 {
+            for (idx, z_poly) in z_poly.iter().enumerate() {
+                let expected = if idx == 0 { F::ONE } else { F::ZERO };
                 debug_assert_eq!(
-                    P::slice_into_base_slice(&z_poly_c0.storage[0].storage)[0],
-                    F::ONE
-                );
-                debug_assert_eq!(
-                    P::slice_into_base_slice(&z_poly_c1.storage[0].storage)[0],
-                    F::ZERO
-                );
-            }
-
-            let [mut q_c0_as_lde, mut q_c1_as_lde] = dst;
-
-            let num_challenges = num_intermediate_partial_product_relations + 1;
-            let mut alphas = Vec::with_capacity(num_challenges);
-            for _ in 0..num_challenges {
-                alphas.push(
-                    challenges_it
-                        .next()
-                        .copied()
-                        .expect("challenge for copy-permutation part"),
+                    P::slice_into_base_slice(&z_poly.storage[0].storage)[0],
+                    expected
                 );
             }
+        }
 
-            crate::cs::implementations::copy_permutation::compute_quotient_terms_in_extension(
-                domain_size,
-                quotient_degree,
-                &trace_holder.variables,
-                &second_stage_polys_storage,
-                &trace_holder.setup,
-                num_intermediate_partial_product_relations,
-                beta,
-                gamma,
-                alphas,
-                &x_poly_lde,
-                &mut q_c0_as_lde,
-                &mut q_c1_as_lde,
-                worker,
-                ctx,
+        let num_challenges = num_intermediate_partial_product_relations + 1;
+        let mut alphas = Vec::with_capacity(num_challenges);
+        for _ in 0..num_challenges {
+            alphas.push(
+                challenges_it
+                    .next()
+                    .copied()
+                    .expect("challenge for copy-permutation part"),
             );
+        }
 
-            // now we add contribution from lookups - that at the domain
-
-            // A(x) * (gamma^0 * column_0 + ... + gamma^n * column_n) == lookup_selector
-            // B(x) * (gamma^0 * column_0 + ... + gamma^n * column_n) == multiplicity column
-
-            // each of those is 1 term per lookup subargument
-
-            match self.lookup_parameters {
-                LookupParameters::NoLookup => {}
-                LookupParameters::TableIdAsConstant { .. }
-                | LookupParameters::TableIdAsVariable { .. } => {
-                    // lookup argument related parts
-
-                    // exists by our setup
-                    let lookup_evaluator_id = 0;
-                    let selector_subpath = setup_base
-                        .selectors_placement
-                        .output_placement(lookup_evaluator_id)
-                        .expect("lookup gate must be placed");
-                    let _columns_per_subargument = self.lookup_parameters.columns_per_subargument();
-
-                    let _selector = selectors_buffer
-                        .get(&selector_subpath)
-                        .cloned()
-                        .expect("path must be unique and precomputed");
-                    let mut lookup_terms_challenges =
-                        Vec::with_capacity(total_num_lookup_argument_terms);
-                    for _ in 0..total_num_lookup_argument_terms {
-                        lookup_terms_challenges.push(
-                            lookup_challenges_it
-                                .next()
-                                .copied()
-                                .expect("challenge for lookup argument A/B polys"),
-                        );
-                    }
-
-                    todo!()
+        crate::cs::implementations::copy_permutation::compute_quotient_terms_in_extension(
+            domain_size,
+            quotient_degree,
+            num_cosets,
+            &trace_holder.variables,
+            second_stage_polys_storage,
+            &trace_holder.setup,
+            num_intermediate_partial_product_relations,
+            beta,
+            gamma,
+            alphas,
+            x_poly_lde,
+            &mut dst,
+            worker,
+            ctx,
+        );
 
-                    // super::lookup_argument::compute_quotient_terms_for_lookup_over_general_purpose_gates(
-                    //     &trace_holder.variables,
-                    //     &second_stage_polys_storage,
-                    //     &trace_holder.setup,
-                    //     selector,
-                    //     lookup_beta,
-                    //     lookup_gamma,
-                    //     lookup_terms_challenges,
-                    //     table_ids_column_idxes.clone(),
-                    //     columns_per_subargument as usize,
-                    //     num_lookup_subarguments,
-                    //     set_idx,
-                    //     quotient_degree,
-                    //     &mut q_as_lde,
-                    //     worker,
-                    //     ctx,
-                    // );
+        // now we add contribution from lookups - that at the domain
+
+        // A(x) * (gamma^0 * column_0 + ... + gamma^n * column_n) == lookup_selector
+        // B(x) * (gamma^0 * column_0 + ... + gamma^n * column_n) == multiplicity column
+
+        // each of those is 1 term per lookup subargument
+
+        match self.lookup_parameters {
+            LookupParameters::NoLookup => {}
+            LookupParameters::TableIdAsConstant { .. }
+            | LookupParameters::TableIdAsVariable { .. } => {
+                // lookup argument related parts
+
+                // exists by our setup
+                let lookup_evaluator_id = 0;
+                let selector_subpath = selectors_placement
+                    .output_placement(lookup_evaluator_id)
+                    .expect("lookup gate must be placed");
+                let _columns_per_subargument = self.lookup_parameters.columns_per_subargument();
+
+                let _selector = selectors_buffer
+                    .get(&selector_subpath)
+                    .cloned()
+                    .expect("path must be unique and precomputed");
+                let mut lookup_terms_challenges =
+                    Vec::with_capacity(total_num_lookup_argument_terms);
+                for _ in 0..total_num_lookup_argument_terms {
+                    lookup_terms_challenges.push(
+                        lookup_challenges_it
+                            .next()
+                            .copied()
+                            .expect("challenge for lookup argument A/B polys"),
+                    );
                 }
-                LookupParameters::UseSpecializedColumnsWithTableIdAsConstant { .. }
-                | LookupParameters::UseSpecializedColumnsWithTableIdAsVariable { .. } => {
-                    // lookup argument related parts
-
-                    let mut lookup_terms_challenges =
-                        Vec::with_capacity(total_num_lookup_argument_terms);
-                    for _ in 0..total_num_lookup_argument_terms {
-                        lookup_terms_challenges.push(
-                            lookup_challenges_it
-                                .next()
-                                .copied()
-                                .expect("challenge for lookup argument A/B polys"),
-                        );
-                    }
-
-                    let columns_per_subargument =
-                        self.lookup_parameters.specialized_columns_per_subargument();
 
-                    super::lookup_argument_in_ext::compute_quotient_terms_for_lookup_specialized(
-                        &trace_holder.variables,
-                        &second_stage_polys_storage,
-                        &trace_holder.setup,
-                        lookup_beta,
-                        lookup_gamma,
-                        lookup_terms_challenges,
-                        table_ids_column_idxes.clone(),
-                        columns_per_subargument as usize,
-                        num_lookup_subarguments,
-                        num_multiplicities_polys,
-                        self.parameters.num_columns_under_copy_permutation,
-                        quotient_degree,
-                        &mut q_c0_as_lde,
-                        &mut q_c1_as_lde,
-                        worker,
-                        ctx,
+                todo!()
+
+                // super::lookup_argument::compute_quotient_terms_for_lookup_over_general_purpose_gates(
+                //     &trace_holder.variables,
+                //     &second_stage_polys_storage,
+                //     &trace_holder.setup,
+                //     selector,
+                //     lookup_beta,
+                //     lookup_gamma,
+                //     lookup_terms_challenges,
+                //     table_ids_column_idxes.clone(),
+                //     columns_per_subargument as usize,
+                //     num_lookup_subarguments,
+                //     set_idx,
+                //     quotient_degree,
+                //     &mut q_as_lde,
+                //     worker,
+                //     ctx,
+                // );
+            }
+            LookupParameters::UseSpecializedColumnsWithTableIdAsConstant { .. }
+            | LookupParameters::UseSpecializedColumnsWithTableIdAsVariable { .. } => {
+                // lookup argument related parts
+
+                let mut lookup_terms_challenges =
+                    Vec::with_capacity(total_num_lookup_argument_terms);
+                for _ in 0..total_num_lookup_argument_terms {
+                    lookup_terms_challenges.push(
+                        lookup_challenges_it
+                            .next()
+                            .copied()
+                            .expect("challenge for lookup argument A/B polys"),
                     );
                 }
-            }
 
-            assert_eq!(challenges_it.len(), 0, "must exhaust all the challenges");
+                let columns_per_subargument =
+                    self.lookup_parameters.specialized_columns_per_subargument();
+
+                super::lookup_argument_in_ext::compute_quotient_terms_for_lookup_specialized(
+                    &trace_holder.variables,
+                    second_stage_polys_storage,
+                    &trace_holder.setup,
+                    lookup_beta,
+                    lookup_gamma,
+                    lookup_terms_challenges,
+                    table_ids_column_idxes,
+                    columns_per_subargument as usize,
+                    num_lookup_subarguments,
+                    num_multiplicities_polys,
+                    self.parameters.num_columns_under_copy_permutation,
+                    num_cosets,
+                    &mut dst,
+                    worker,
+                    ctx,
+                );
+            }
+        }
 
-            let mut q_c0_as_vectors: Vec<Vec<P>> = q_c0_as_lde
-                .storage
-                .into_iter()
-                .map(|el| {
-                    Arc::try_unwrap(el)
-                        .expect("must be exclusively owned")
-                        .into_storage()
-                })
-                .collect();
+        assert_eq!(challenges_it.len(), 0, "must exhaust all the challenges");
 
-            let mut q_c1_as_vectors: Vec<Vec<P>> = q_c1_as_lde
+        dst.map(|q_as_lde| {
+            q_as_lde
                 .storage
                 .into_iter()
                 .map(|el| {
@@ -1381,130 +2511,86 @@ impl<
                         .expect("must be exclusively owned")
                         .into_storage()
                 })
-                .collect();
-
-            if crate::config::DEBUG_SATISFIABLE == false {
-                divide_by_vanishing_for_bitreversed_coset_enumeration(
-                    &mut q_c0_as_vectors,
-                    worker,
-                    ctx,
-                );
-                divide_by_vanishing_for_bitreversed_coset_enumeration(
-                    &mut q_c1_as_vectors,
-                    worker,
-                    ctx,
-                );
-            }
+                .collect()
+        })
+    }
 
-            let q_c0: Vec<Vec<F>> = q_c0_as_vectors
-                .into_iter()
-                .map(|el| P::vec_into_base_vec(el))
-                .collect();
+    fn compute_deep_openings<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+    >(
+        &self,
+        worker: &Worker,
+        state: &mut ProverState<F, P, EXT, TR, H, N>,
+        setup_base: &SetupBaseStorage<F, P>,
+        setup: &SetupStorage<F, P>,
+    ) {
+        assert_eq!(state.stage, ProverStage::QuotientCommitted);
 
-            let q_c1: Vec<Vec<F>> = q_c1_as_vectors
-                .into_iter()
-                .map(|el| P::vec_into_base_vec(el))
-                .collect();
+        profile_fn!(compute_deep_openings);
+        profile_section!(sect_7);
 
-            let flattened_c0 = flatten_presumably_bitreversed::<_, P, _, _>(q_c0, worker);
-            let flattened_c1 = flatten_presumably_bitreversed::<_, P, _, _>(q_c1, worker);
-            let mut monomial_form_c0 = P::vec_from_base_vec(flattened_c0);
-            let mut monomial_form_c1 = P::vec_from_base_vec(flattened_c1);
+        let proof_config = state.proof_config.clone();
+        let domain_size = self.max_trace_len;
+        let inner_size = domain_size / P::SIZE_FACTOR;
+        let quotient_degree = self.compute_quotient_degree(setup_base);
+        let used_lde_degree = std::cmp::max(proof_config.fri_lde_factor, quotient_degree);
 
-            if crate::config::DEBUG_SATISFIABLE {
-                let as_slice = P::slice_into_base_slice(&monomial_form_c0);
-                for (idx, el) in as_slice.iter().step_by(quotient_degree).enumerate() {
-                    assert_eq!(*el, F::ZERO, "poly is not divisible at row {}", idx);
-                }
-            }
+        let mut owned_ctx = P::Context::placeholder();
+        let ctx = &mut owned_ctx;
 
-            P::ifft_natural_to_natural(&mut monomial_form_c0, coset, &inverse_twiddles, ctx);
-            P::ifft_natural_to_natural(&mut monomial_form_c1, coset, &inverse_twiddles, ctx);
+        let num_variable_polys = self.parameters.num_columns_under_copy_permutation;
+        let num_witness_polys = self.parameters.num_witness_columns;
+        let num_constant_polys = setup.constant_columns.len();
+        let num_copy_permutation_polys = setup.copy_permutation_polys.len();
+        let num_lookup_subarguments = self.num_sublookup_arguments();
+        let num_multiplicities_polys = self.num_multipicities_polys();
+        let total_num_lookup_argument_terms = num_lookup_subarguments + num_multiplicities_polys;
+        let num_intermediate_partial_product_relations = state
+            .num_intermediate_partial_products(num_lookup_subarguments, num_multiplicities_polys);
 
-            if crate::config::DEBUG_SATISFIABLE == false {
-                assert!(
-                    P::slice_into_base_slice(&monomial_form_c0)
-                        .last()
-                        .unwrap()
-                        .is_zero(),
-                    "unsatisfied",
-                );
-                assert!(
-                    P::slice_into_base_slice(&monomial_form_c1)
-                        .last()
-                        .unwrap()
-                        .is_zero(),
-                    "unsatisfied",
-                );
-            }
+        // evaluations at points outside of the domain only need the main domain
+        let (trace_holder, second_stage_polys_storage, quotient_chunks_ldes) = state
+            .oracles_for_cosets(
+                0..1,
+                setup,
+                num_variable_polys,
+                num_witness_polys,
+                num_lookup_subarguments,
+                num_multiplicities_polys,
+                worker,
+            );
 
-            [
-                GenericPolynomial::<F, MonomialForm, P, _>::from_storage(monomial_form_c0),
-                GenericPolynomial::<F, MonomialForm, P, _>::from_storage(monomial_form_c1),
-            ]
+        let coset = if crate::config::DEBUG_SATISFIABLE == false {
+            F::multiplicative_generator()
+        } else {
+            F::ONE
         };
 
-        // now we can chunk every polynomial and LDE them
-
-        // NOTE: for all the polynomials below we will have an option to perform evaluation at random
-        // point by using barycentric formula, while for quotient we could potentially save monomial form
-        // for some time and use horner rule. We neglect small change of proof time in favor of simplicity
-        // and memory consumption
-
-        let mut quotient_chunks = vec![];
-        let [q_c0, q_c1] = quotient_monomials;
-        // we layout [c0, c1] in every chunk
-
-        for (c0, c1) in q_c0
-            .chunk_into_subpolys_of_degree::<Global>(base_system_degree)
-            .into_iter()
-            .zip(q_c1.chunk_into_subpolys_of_degree::<Global>(base_system_degree))
-        {
-            let c0 = c0.into_storage();
-            let c1 = c1.into_storage();
-            quotient_chunks.push(c0);
-            quotient_chunks.push(c1);
-        }
-
-        // how we should LDE quotients and form another oracle
-
-        let forward_twiddles =
-            P::precompute_forward_twiddles_for_fft::<Global>(domain_size, worker, ctx);
-
-        // we do not need full quotient LDE degree here, only large enough for FRI
-        let quotient_chunks_ldes = transform_monomials_to_lde(
-            quotient_chunks,
+        let x_poly_lde = materialize_x_poly_as_arc_lde::<F, P, Global, Global>(
             domain_size,
-            proof_config.fri_lde_factor,
-            &forward_twiddles,
+            used_lde_degree,
+            coset,
             worker,
             ctx,
         );
 
-        drop(sect_6);
-        profile_section!(sect_7);
-
-        log!("Quotient work and LDE taken {:?}", now.elapsed());
-
-        let source = quotient_chunks_ldes.clone();
-        let quotients_tree = MerkleTreeWithCap::<F, H>::construct(source, cap_size, worker);
-
-        // now we can commit to grand products and get new challenges
-        let quotients_tree_cap = quotients_tree.get_cap();
-
-        transcript.witness_merkle_tree_cap(quotients_tree_cap.as_ref());
+        let public_inputs_with_values = &state.public_inputs_with_locations;
+        let transcript = &mut state.transcript;
 
         // now evaluate corresponding polynomials at corresponding z-s, and check equality
 
         let now = std::time::Instant::now();
 
-        let z = transcript.get_multiple_challenges_fixed::<2>();
-        let z = ExtensionField::<F, 2, EXT>::from_coeff_in_base(z);
+        let z = transcript.get_multiple_challenges_fixed::<N>();
+        let z = ExtensionField::<F, N, EXT>::from_coeff_in_base(z);
 
         // we have to evaluate all sets at the corresponding point
 
-        // let mut naive = ExtensionField::<F, 2, EXT>::ZERO;
-        // let mut current = ExtensionField::<F, 2, EXT>::ONE;
+        // let mut naive = ExtensionField::<F, N, EXT>::ZERO;
+        // let mut current = ExtensionField::<F, N, EXT>::ONE;
         // for (c0, c1) in P::slice_into_base_slice(&quotient_chunks_tmp[0]).iter()
         //     .zip(P::slice_into_base_slice(&quotient_chunks_tmp[1]).iter()) {
         //     let mut tmp = ExtensionField::from_coeff_in_base([*c0, *c1]);
@@ -1516,32 +2602,32 @@ impl<
 
         // dbg!(&naive);
 
-        let [precomputed_lagranges_c0, precomputed_lagranges_c1] =
-            precompute_for_barycentric_evaluation_in_extension::<F, EXT, P, Global>(
+        let precomputed_lagranges =
+            precompute_for_barycentric_evaluation_in_extension::<F, N, EXT, P, Global>(
                 domain_size,
                 F::multiplicative_generator(),
                 z,
                 worker,
                 ctx,
             );
+        let precomputed_lagranges = precomputed_lagranges
+            .each_ref()
+            .map(|el| P::slice_into_base_slice(el));
 
         let mut all_polys_at_zs = vec![];
 
         let evaluate_from_base = |coeffs: &[P]| {
             barycentric_evaluate_base_at_extension_for_bitreversed_parallel(
                 P::slice_into_base_slice(coeffs),
-                P::slice_into_base_slice(&precomputed_lagranges_c0),
-                P::slice_into_base_slice(&precomputed_lagranges_c1),
+                precomputed_lagranges,
                 worker,
             )
         };
 
-        let evaluate_from_extension = |coeffs_c0: &[P], coeffs_c1: &[P]| {
-            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                P::slice_into_base_slice(coeffs_c0),
-                P::slice_into_base_slice(coeffs_c1),
-                P::slice_into_base_slice(&precomputed_lagranges_c0),
-                P::slice_into_base_slice(&precomputed_lagranges_c1),
+        let evaluate_from_extension = |coeffs: [&[P]; N]| {
+            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, N, EXT>(
+                coeffs.map(|el| P::slice_into_base_slice(el)),
+                precomputed_lagranges,
                 worker,
             )
         };
@@ -1554,7 +2640,7 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         all_polys_at_zs.extend(
             trace_holder
@@ -1563,7 +2649,7 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         // "normal" setup
         all_polys_at_zs.extend(
@@ -1573,7 +2659,7 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         all_polys_at_zs.extend(
             trace_holder
@@ -1582,31 +2668,27 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         // copy-permutation
-        all_polys_at_zs.push(ExtensionField::<F, 2, EXT>::from_coeff_in_base(
+        all_polys_at_zs.push(ExtensionField::<F, N, EXT>::from_coeff_in_base(
             evaluate_from_extension(
-                &second_stage_polys_storage.z_poly[0].storage[0]
-                    .as_ref()
-                    .storage,
-                &second_stage_polys_storage.z_poly[1].storage[0]
-                    .as_ref()
-                    .storage,
+                second_stage_polys_storage
+                    .z_poly
+                    .each_ref()
+                    .map(|el| &el.storage[0].as_ref().storage[..]),
             ),
         ));
         all_polys_at_zs.extend(
             second_stage_polys_storage
                 .intermediate_polys
                 .iter()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
+                .map(|el| {
+                    evaluate_from_extension(
+                        el.each_ref().map(|el| &el.storage[0].as_ref().storage[..]),
+                    )
                 })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
 
         assert_eq!(
@@ -1627,34 +2709,30 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         // lookup witness and multiplicities
         all_polys_at_zs.extend(
             second_stage_polys_storage
                 .lookup_witness_encoding_polys
                 .iter()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
+                .map(|el| {
+                    evaluate_from_extension(
+                        el.each_ref().map(|el| &el.storage[0].as_ref().storage[..]),
+                    )
                 })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         all_polys_at_zs.extend(
             second_stage_polys_storage
                 .lookup_multiplicities_encoding_polys
                 .iter()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
+                .map(|el| {
+                    evaluate_from_extension(
+                        el.each_ref().map(|el| &el.storage[0].as_ref().storage[..]),
+                    )
                 })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         // lookup setup
         // tables columns
@@ -1665,21 +2743,19 @@ impl<
                 .iter()
                 .map(|el| &el.storage[0].as_ref().storage)
                 .map(|el| evaluate_from_base(el))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
         // table ids are not needed as those come from constatns in general
         // quotient
         all_polys_at_zs.extend(
             quotient_chunks_ldes
-                .array_chunks::<2>()
-                .map(|[a, b]| {
-                    [
-                        &a.storage[0].as_ref().storage,
-                        &b.storage[0].as_ref().storage,
-                    ]
+                .array_chunks::<N>()
+                .map(|el| {
+                    evaluate_from_extension(
+                        el.each_ref().map(|el| &el.storage[0].as_ref().storage[..]),
+                    )
                 })
-                .map(|[a, b]| evaluate_from_extension(a, b))
-                .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
         );
 
         let expected_lookup_polys_total = if self.lookup_parameters.lookup_is_allowed() {
@@ -1696,7 +2772,8 @@ impl<
             1 + // z_poly
             num_intermediate_partial_product_relations + // partial products in copy-permutation
             expected_lookup_polys_total + // everything from lookup
-            quotient_degree; // chunks of quotient poly
+            quotient_degree + // chunks of quotient poly
+            proof_config.num_masking_polys(); // committed with the quotient
 
         assert_eq!(all_polys_at_zs.len(), num_poly_values_at_z);
 
@@ -1707,33 +2784,32 @@ impl<
         let mut z_omega = z;
         z_omega.mul_assign_by_base(&domain_generator_for_size::<F>(domain_size as u64));
 
-        let [precomputed_lagranges_c0, precomputed_lagranges_c1] =
-            precompute_for_barycentric_evaluation_in_extension::<F, EXT, P, Global>(
+        let precomputed_lagranges =
+            precompute_for_barycentric_evaluation_in_extension::<F, N, EXT, P, Global>(
                 domain_size,
                 F::multiplicative_generator(),
                 z_omega,
                 worker,
                 ctx,
             );
-
-        let evaluate_from_extension = |coeffs_c0: &[P], coeffs_c1: &[P]| {
-            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                P::slice_into_base_slice(coeffs_c0),
-                P::slice_into_base_slice(coeffs_c1),
-                P::slice_into_base_slice(&precomputed_lagranges_c0),
-                P::slice_into_base_slice(&precomputed_lagranges_c1),
+        let precomputed_lagranges = precomputed_lagranges
+            .each_ref()
+            .map(|el| P::slice_into_base_slice(el));
+
+        let evaluate_from_extension = |coeffs: [&[P]; N]| {
+            barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, N, EXT>(
+                coeffs.map(|el| P::slice_into_base_slice(el)),
+                precomputed_lagranges,
                 worker,
             )
         };
 
-        let all_polys_at_zomegas = vec![ExtensionField::<F, 2, EXT>::from_coeff_in_base(
+        let all_polys_at_zomegas = vec![ExtensionField::<F, N, EXT>::from_coeff_in_base(
             evaluate_from_extension(
-                &second_stage_polys_storage.z_poly[0].storage[0]
-                    .as_ref()
-                    .storage,
-                &second_stage_polys_storage.z_poly[1].storage[0]
-                    .as_ref()
-                    .storage,
+                second_stage_polys_storage
+                    .z_poly
+                    .each_ref()
+                    .map(|el| &el.storage[0].as_ref().storage[..]),
             ),
         )];
         assert_eq!(all_polys_at_zomegas.len(), 1);
@@ -1744,25 +2820,25 @@ impl<
 
         let mut all_polys_at_zero = vec![];
 
-        drop(sect_7);
         profile_section!(sect_8);
 
         if self.lookup_parameters != LookupParameters::NoLookup {
-            let [precomputed_lagranges_c0, precomputed_lagranges_c1] =
-                precompute_for_barycentric_evaluation_in_extension::<F, EXT, P, Global>(
+            let precomputed_lagranges =
+                precompute_for_barycentric_evaluation_in_extension::<F, N, EXT, P, Global>(
                     domain_size,
                     F::multiplicative_generator(),
-                    ExtensionField::<F, 2, EXT>::ZERO,
+                    ExtensionField::<F, N, EXT>::ZERO,
                     worker,
                     ctx,
                 );
-
-            let evaluate_from_extension = |coeffs_c0: &[P], coeffs_c1: &[P]| {
-                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, EXT>(
-                    P::slice_into_base_slice(coeffs_c0),
-                    P::slice_into_base_slice(coeffs_c1),
-                    P::slice_into_base_slice(&precomputed_lagranges_c0),
-                    P::slice_into_base_slice(&precomputed_lagranges_c1),
+            let precomputed_lagranges = precomputed_lagranges
+                .each_ref()
+                .map(|el| P::slice_into_base_slice(el));
+
+            let evaluate_from_extension = |coeffs: [&[P]; N]| {
+                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, N, EXT>(
+                    coeffs.map(|el| P::slice_into_base_slice(el)),
+                    precomputed_lagranges,
                     worker,
                 )
             };
@@ -1771,27 +2847,23 @@ impl<
                 second_stage_polys_storage
                     .lookup_witness_encoding_polys
                     .iter()
-                    .map(|[a, b]| {
-                        [
-                            &a.storage[0].as_ref().storage,
-                            &b.storage[0].as_ref().storage,
-                        ]
+                    .map(|el| {
+                        evaluate_from_extension(
+                            el.each_ref().map(|el| &el.storage[0].as_ref().storage[..]),
+                        )
                     })
-                    .map(|[a, b]| evaluate_from_extension(a, b))
-                    .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                    .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
             );
             all_polys_at_zero.extend(
                 second_stage_polys_storage
                     .lookup_multiplicities_encoding_polys
                     .iter()
-                    .map(|[a, b]| {
-                        [
-                            &a.storage[0].as_ref().storage,
-                            &b.storage[0].as_ref().storage,
-                        ]
+                    .map(|el| {
+                        evaluate_from_extension(
+                            el.each_ref().map(|el| &el.storage[0].as_ref().storage[..]),
+                        )
                     })
-                    .map(|[a, b]| evaluate_from_extension(a, b))
-                    .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base(el)),
+                    .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(el)),
             );
         }
 
@@ -1806,7 +2878,7 @@ impl<
         {
             let omega = domain_generator_for_size::<F>(domain_size as u64);
 
-            for (column, row, value) in public_inputs_with_values.into_iter() {
+            for (column, row, value) in public_inputs_with_values.iter().copied() {
                 let open_at = omega.pow_u64(row as u64);
                 let pos = public_input_opening_tuples
                     .iter()
@@ -1825,8 +2897,7 @@ impl<
 
         // Since it will be the last step before FRI, we will use challenges from extension
 
-        let c0 = transcript.get_challenge();
-        let c1 = transcript.get_challenge();
+        let challenge = transcript.get_multiple_challenges_fixed::<N>();
 
         let mut total_num_challenges = 0;
         total_num_challenges += num_poly_values_at_z;
@@ -1836,203 +2907,171 @@ impl<
             total_num_challenges += subset.len();
         }
 
-        let challenges = materialize_ext_challenge_powers::<F, EXT>((c0, c1), total_num_challenges);
+        let challenges =
+            materialize_ext_challenge_powers::<F, N, EXT>(challenge, total_num_challenges);
         // we may use lower factor LDE for FRI if we want
         let lde_factor_for_fri = proof_config.fri_lde_factor;
 
-        let mut outer_c0 = Vec::with_capacity(lde_factor_for_fri);
-        let mut outer_c1 = Vec::with_capacity(lde_factor_for_fri);
-        for _ in 0..lde_factor_for_fri {
-            let buff = vec![P::zero(ctx); inner_size];
-            outer_c0.push(buff);
-            let buff = vec![P::zero(ctx); inner_size];
-            outer_c1.push(buff);
-        }
-
-        let x_poly_lde_subset = x_poly_lde.subset_for_degree(lde_factor_for_fri);
-
-        let mut challenges_offset = 0;
+        let mut outer: [Vec<Vec<P>>; N] =
+            std::array::from_fn(|_| Vec::with_capacity(lde_factor_for_fri));
 
+        // all the sources below only contain cosets of the current batch
         let map_base_for_quotening =
             move |input: &[ArcGenericLdeStorage<F, P, Global, Global>]| {
                 input
                     .iter()
-                    .map(|el| {
-                        let c0 = el.subset_for_degree(lde_factor_for_fri);
-
-                        [Some(c0), None]
-                    })
-                    .collect::<Vec<_>>()
+                    .map(|el| std::array::from_fn(|i| if i == 0 { Some(el.clone()) } else { None }))
+                    .collect::<Vec<[_; N]>>()
             };
 
         let map_extension_for_quotening =
-            move |input: &[[ArcGenericLdeStorage<F, P, Global, Global>; 2]]| {
+            move |input: &[[ArcGenericLdeStorage<F, P, Global, Global>; N]]| {
                 input
                     .iter()
-                    .map(|[a, b]| {
-                        let c0 = a.subset_for_degree(lde_factor_for_fri);
-                        let c1 = b.subset_for_degree(lde_factor_for_fri);
-
-                        [Some(c0), Some(c1)]
-                    })
+                    .map(|el| el.clone().map(Some))
                     .collect::<Vec<_>>()
             };
 
-        let mut sources = vec![];
-        // witness
-        sources.extend(map_base_for_quotening(
-            &trace_holder.variables.variables_columns,
-        ));
-        sources.extend(map_base_for_quotening(
-            &trace_holder.variables.witness_columns,
-        ));
-        // normal setup
-        sources.extend(map_base_for_quotening(&trace_holder.setup.constant_columns));
-        sources.extend(map_base_for_quotening(
-            &trace_holder.setup.copy_permutation_polys,
-        ));
-        // copy permutation
-        sources.extend(map_extension_for_quotening(&[second_stage_polys_storage
-            .z_poly
-            .clone()]));
-        sources.extend(map_extension_for_quotening(
-            &second_stage_polys_storage.intermediate_polys,
-        ));
-        // lookup if exists
-        sources.extend(map_base_for_quotening(
-            &trace_holder.variables.lookup_multiplicities_polys,
-        ));
-        sources.extend(map_extension_for_quotening(
-            &second_stage_polys_storage.lookup_witness_encoding_polys,
-        ));
-        sources.extend(map_extension_for_quotening(
-            &second_stage_polys_storage.lookup_multiplicities_encoding_polys,
-        ));
-        // lookup setup
-        if self.lookup_parameters.lookup_is_allowed() {
-            sources.extend(map_base_for_quotening(
-                &trace_holder.setup.lookup_tables_columns,
-            ));
-        }
-        // quotient
-        let quotinents: Vec<_> = quotient_chunks_ldes.array_chunks::<2>().cloned().collect();
-        sources.extend(map_extension_for_quotening(&quotinents));
-
-        let num_challenges_required = sources.len();
-
-        let values_at_z = all_polys_at_zs.clone();
-
-        assert_eq!(values_at_z.len(), num_challenges_required);
-
-        log!("Making quotiening at Z");
+        drop(sect_8);
+        profile_section!(sect_9);
 
-        quotening_operation_in_extension(
-            &mut outer_c0,
-            &mut outer_c1,
-            sources,
-            values_at_z,
-            &x_poly_lde_subset,
-            z,
-            &challenges[challenges_offset..(challenges_offset + num_challenges_required)],
-            worker,
-            ctx,
-        );
+        drop(trace_holder);
+        drop(second_stage_polys_storage);
+        drop(quotient_chunks_ldes);
+
+        // if the trace was spilled we only load one coset of it at a time
+        for cosets in state.coset_batches(lde_factor_for_fri) {
+            let (trace_holder, second_stage_polys_storage, quotient_chunks_ldes) = state
+                .oracles_for_cosets(
+                    cosets.clone(),
+                    setup,
+                    num_variable_polys,
+                    num_witness_polys,
+                    num_lookup_subarguments,
+                    num_multiplicities_polys,
+                    worker,
+                );
+            let x_poly_lde_subset = x_poly_lde.subset_for_cosets(cosets.clone());
 
-        challenges_offset += num_challenges_required;
+            let zero = P::zero(ctx);
+            let mut batch: [Vec<Vec<P>>; N] =
+                std::array::from_fn(|_| vec![vec![zero; inner_size]; cosets.len()]);
 
-        // now at z_omega
+            let mut challenges_offset = 0;
 
-        let mut sources = vec![];
-        sources.extend(map_extension_for_quotening(&[second_stage_polys_storage
-            .z_poly
-            .clone()]));
+            let mut sources = vec![];
+            // witness
+            sources.extend(map_base_for_quotening(
+                &trace_holder.variables.variables_columns,
+            ));
+            sources.extend(map_base_for_quotening(
+                &trace_holder.variables.witness_columns,
+            ));
+            // normal setup
+            sources.extend(map_base_for_quotening(&trace_holder.setup.constant_columns));
+            sources.extend(map_base_for_quotening(
+                &trace_holder.setup.copy_permutation_polys,
+            ));
+            // copy permutation
+            sources.extend(map_extension_for_quotening(&[second_stage_polys_storage
+                .z_poly
+                .clone()]));
+            sources.extend(map_extension_for_quotening(
+                &second_stage_polys_storage.intermediate_polys,
+            ));
+            // lookup if exists
+            sources.extend(map_base_for_quotening(
+                &trace_holder.variables.lookup_multiplicities_polys,
+            ));
+            sources.extend(map_extension_for_quotening(
+                &second_stage_polys_storage.lookup_witness_encoding_polys,
+            ));
+            sources.extend(map_extension_for_quotening(
+                &second_stage_polys_storage.lookup_multiplicities_encoding_polys,
+            ));
+            // lookup setup
+            if self.lookup_parameters.lookup_is_allowed() {
+                sources.extend(map_base_for_quotening(
+                    &trace_holder.setup.lookup_tables_columns,
+                ));
+            }
+            // quotient
+            let quotinents: Vec<_> = quotient_chunks_ldes.array_chunks::<N>().cloned().collect();
+            sources.extend(map_extension_for_quotening(&quotinents));
 
-        let num_challenges_required = sources.len();
+            let num_challenges_required = sources.len();
 
-        let values_at_z_omega = all_polys_at_zomegas.clone();
+            let values_at_z = all_polys_at_zs.clone();
 
-        assert_eq!(values_at_z_omega.len(), num_challenges_required);
+            assert_eq!(values_at_z.len(), num_challenges_required);
 
-        log!("Making quotiening at Z*omega");
+            log!("Making quotiening at Z");
 
-        quotening_operation_in_extension(
-            &mut outer_c0,
-            &mut outer_c1,
-            sources,
-            values_at_z_omega,
-            &x_poly_lde_subset,
-            z_omega,
-            &challenges[challenges_offset..(challenges_offset + num_challenges_required)],
-            worker,
-            ctx,
-        );
+            quotening_operation_in_extension(
+                &mut batch,
+                sources,
+                values_at_z,
+                &x_poly_lde_subset,
+                z,
+                &challenges[challenges_offset..(challenges_offset + num_challenges_required)],
+                worker,
+                ctx,
+            );
 
-        drop(sect_8);
-        profile_section!(sect_9);
+            challenges_offset += num_challenges_required;
 
-        challenges_offset += num_challenges_required;
+            // now at z_omega
 
-        // and now at 0 for sumcheck for lookup argument
-        if self.lookup_parameters != LookupParameters::NoLookup {
             let mut sources = vec![];
-            sources.extend(map_extension_for_quotening(
-                &second_stage_polys_storage.lookup_witness_encoding_polys,
-            ));
-            sources.extend(map_extension_for_quotening(
-                &second_stage_polys_storage.lookup_multiplicities_encoding_polys,
-            ));
+            sources.extend(map_extension_for_quotening(&[second_stage_polys_storage
+                .z_poly
+                .clone()]));
 
             let num_challenges_required = sources.len();
 
-            let values_at_zero = all_polys_at_zero.clone();
+            let values_at_z_omega = all_polys_at_zomegas.clone();
 
-            assert_eq!(values_at_zero.len(), num_challenges_required);
+            assert_eq!(values_at_z_omega.len(), num_challenges_required);
 
-            log!("Making quotiening at 0 for lookups sumchecks");
+            log!("Making quotiening at Z*omega");
 
             quotening_operation_in_extension(
-                &mut outer_c0,
-                &mut outer_c1,
+                &mut batch,
                 sources,
-                values_at_zero,
+                values_at_z_omega,
                 &x_poly_lde_subset,
-                ExtensionField::<F, 2, EXT>::ZERO,
+                z_omega,
                 &challenges[challenges_offset..(challenges_offset + num_challenges_required)],
                 worker,
                 ctx,
             );
 
             challenges_offset += num_challenges_required;
-        }
 
-        // add public inputs by quotening
-        {
-            for (open_at, set) in public_input_opening_tuples.into_iter() {
-                let mut sources = Vec::with_capacity(set.len());
-                let mut values = Vec::with_capacity(set.len());
-                for (column, expected_value) in set.into_iter() {
-                    sources.extend(map_base_for_quotening(&[trace_holder
-                        .variables
-                        .variables_columns[column]
-                        .clone()]));
-                    let expected_value =
-                        ExtensionField::<F, 2, EXT>::from_coeff_in_base([expected_value, F::ZERO]);
-                    values.push(expected_value);
-                }
+            // and now at 0 for sumcheck for lookup argument
+            if self.lookup_parameters != LookupParameters::NoLookup {
+                let mut sources = vec![];
+                sources.extend(map_extension_for_quotening(
+                    &second_stage_polys_storage.lookup_witness_encoding_polys,
+                ));
+                sources.extend(map_extension_for_quotening(
+                    &second_stage_polys_storage.lookup_multiplicities_encoding_polys,
+                ));
+
                 let num_challenges_required = sources.len();
-                assert_eq!(values.len(), num_challenges_required);
 
-                log!("Making quotiening at {} for public inputs", open_at);
+                let values_at_zero = all_polys_at_zero.clone();
 
-                let open_at = ExtensionField::<F, 2, EXT>::from_coeff_in_base([open_at, F::ZERO]);
+                assert_eq!(values_at_zero.len(), num_challenges_required);
+
+                log!("Making quotiening at 0 for lookups sumchecks");
 
                 quotening_operation_in_extension(
-                    &mut outer_c0,
-                    &mut outer_c1,
+                    &mut batch,
                     sources,
-                    values,
+                    values_at_zero,
                     &x_poly_lde_subset,
-                    open_at,
+                    ExtensionField::<F, N, EXT>::ZERO,
                     &challenges[challenges_offset..(challenges_offset + num_challenges_required)],
                     worker,
                     ctx,
@@ -2040,31 +3079,108 @@ impl<
 
                 challenges_offset += num_challenges_required;
             }
+
+            // add public inputs by quotening
+            {
+                for (open_at, set) in public_input_opening_tuples.iter() {
+                    let mut sources = Vec::with_capacity(set.len());
+                    let mut values = Vec::with_capacity(set.len());
+                    for (column, expected_value) in set.iter().copied() {
+                        sources.extend(map_base_for_quotening(&[trace_holder
+                            .variables
+                            .variables_columns[column]
+                            .clone()]));
+                        let mut expected_value_in_extension = ExtensionField::<F, N, EXT>::ZERO;
+                        expected_value_in_extension.coeffs[0] = expected_value;
+                        let expected_value = expected_value_in_extension;
+                        values.push(expected_value);
+                    }
+                    let num_challenges_required = sources.len();
+                    assert_eq!(values.len(), num_challenges_required);
+
+                    log!("Making quotiening at {} for public inputs", open_at);
+
+                    let mut open_at_in_extension = ExtensionField::<F, N, EXT>::ZERO;
+                    open_at_in_extension.coeffs[0] = *open_at;
+                    let open_at = open_at_in_extension;
+
+                    quotening_operation_in_extension(
+                        &mut batch,
+                        sources,
+                        values,
+                        &x_poly_lde_subset,
+                        open_at,
+                        &challenges
+                            [challenges_offset..(challenges_offset + num_challenges_required)],
+                        worker,
+                        ctx,
+                    );
+
+                    challenges_offset += num_challenges_required;
+                }
+            }
+
+            assert_eq!(challenges_offset, challenges.len());
+
+            for (dst, src) in outer.iter_mut().zip(batch.into_iter()) {
+                dst.extend(src);
+            }
         }
 
-        assert_eq!(challenges_offset, challenges.len());
+        // for simplicity we transform every coefficient into ArcGenericPoly
+
+        let as_polys = outer.map(|el| {
+            let as_poly = GenericLdeStorage {
+                storage: el
+                    .into_iter()
+                    .map(|el| {
+                        GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(el)
+                    })
+                    .collect(),
+            };
+
+            ArcGenericLdeStorage::from_owned(as_poly)
+        });
+
+        log!("Batched FRI opening computation taken {:?}", now.elapsed());
+
+        state.values_at_z = all_polys_at_zs;
+        state.values_at_z_omega = all_polys_at_zomegas;
+        state.values_at_0 = all_polys_at_zero;
+        state.fri_base_oracle_sources = Some(as_polys);
+
+        state.stage = ProverStage::DeepOpeningsComputed;
+    }
 
-        // for simplicity we transform c0 and c1 into ArcGenericPoly
+    fn commit_fri<
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F, Output = TR::CompatibleCap>,
+        POW: PoWRunner,
+    >(
+        &self,
+        worker: &Worker,
+        state: &mut ProverState<F, P, EXT, TR, H, N>,
+    ) {
+        assert_eq!(state.stage, ProverStage::DeepOpeningsComputed);
 
-        let c0_as_poly = GenericLdeStorage {
-            storage: outer_c0
-                .into_iter()
-                .map(|el| GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(el))
-                .collect(),
-        };
+        profile_fn!(commit_fri);
 
-        let c0_as_poly = ArcGenericLdeStorage::from_owned(c0_as_poly);
+        let proof_config = state.proof_config.clone();
+        let cap_size = proof_config.merkle_tree_cap_size;
+        let domain_size = self.max_trace_len;
+        let lde_factor_for_fri = proof_config.fri_lde_factor;
 
-        let c1_as_poly = GenericLdeStorage {
-            storage: outer_c1
-                .into_iter()
-                .map(|el| GenericPolynomial::<F, BitreversedLagrangeForm, P, _>::from_storage(el))
-                .collect(),
-        };
+        let mut owned_ctx = P::Context::placeholder();
+        let ctx = &mut owned_ctx;
 
-        let c1_as_poly = ArcGenericLdeStorage::from_owned(c1_as_poly);
+        let as_polys = state
+            .fri_base_oracle_sources
+            .clone()
+            .expect("DEEP openings must be computed");
 
-        log!("Batched FRI opening computation taken {:?}", now.elapsed());
+        let transcript = &mut state.transcript;
 
         // now we just have to do FRI. In general our strategy is:
         // - access oracles at single evaluation point (path), and get wide leafs
@@ -2079,21 +3195,20 @@ impl<
             num_queries,                  // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = compute_fri_schedule(
-            proof_config.security_level as u32,
-            cap_size,
-            basic_pow_bits,
+        ) = compute_fri_schedule_for_config(
+            &proof_config,
             lde_factor_for_fri.trailing_zeros(),
             domain_size.trailing_zeros(),
-        );
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+        assert!(new_pow_bits <= basic_pow_bits);
 
         dbg!(&interpolation_log2s_schedule);
         dbg!(cap_size);
 
-        let fri_data = do_fri::<F, P, EXT, TR, H, Global, Global>(
-            c0_as_poly.clone(),
-            c1_as_poly.clone(),
-            &mut transcript,
+        let fri_data = do_fri::<F, P, N, EXT, TR, H, Global, Global>(
+            as_polys,
+            transcript,
             interpolation_log2s_schedule.clone(),
             lde_factor_for_fri,
             cap_size,
@@ -2101,171 +3216,308 @@ impl<
             ctx,
         );
 
-        assert_eq!(fri_data.monomial_forms[0].len(), final_expected_degree);
-        assert_eq!(fri_data.monomial_forms[1].len(), final_expected_degree);
+        for monomial_form in fri_data.monomial_forms.iter() {
+            assert_eq!(monomial_form.len(), final_expected_degree);
+        }
 
         // now we can do PoW if we want
+        let pow_challenge = run_pow::<F, TR, POW>(transcript, new_pow_bits, worker);
 
-        let pow_challenge = if new_pow_bits != 0 {
-            log!("Doing PoW");
-
-            let now = std::time::Instant::now();
+        state.fri_data = Some(fri_data);
+        state.fri_folding_schedule = interpolation_log2s_schedule;
+        state.num_queries = num_queries;
+        state.pow_challenge = pow_challenge;
+        state.stage = ProverStage::FriCommitted;
+    }
+}
 
-            // pull enough challenges from the transcript
-            let mut num_challenges = 256 / F::CHAR_BITS;
-            if num_challenges % F::CHAR_BITS != 0 {
-                num_challenges += 1;
-            }
-            let challenges = transcript.get_multiple_challenges(num_challenges);
-            let pow_challenge = POW::run_from_field_elements(challenges, new_pow_bits, worker);
+impl<
+        F: SmallField,
+        P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        TR: Transcript<F>,
+        H: TreeHasher<F>,
+    > ProverState<F, P, EXT, TR, H, N>
+{
+    /// Opens witness, second stage, quotient and setup oracles at the same point.
+    /// Oracles must be committed, so it's only valid after the quotient stage
+    pub(crate) fn query_base_oracles(
+        &self,
+        setup: &SetupStorage<F, P>,
+        setup_tree: &MerkleTreeWithCap<F, H>,
+        domain_size: usize,
+        coset_idx: usize,
+        inner_idx: usize,
+    ) -> BaseOraclesQueries<F, H> {
+        let lde_factor = self.proof_config.fri_lde_factor;
+
+        let witness_oracle_storage =
+            OracleLeafSource::select(self.witness_storage.as_ref(), self.spilled_witness.as_ref());
+        let second_stage_oracle_storage = OracleLeafSource::select(
+            self.second_stage_polys_storage.as_ref(),
+            self.spilled_second_stage.as_ref(),
+        );
+        let quotient_oracle_storage = OracleLeafSource::select(
+            self.quotient_chunks_ldes.as_ref(),
+            self.spilled_quotients.as_ref(),
+        );
 
-            assert!(F::CAPACITY_BITS >= 32);
-            let (low, high) = (pow_challenge as u32, (pow_challenge >> 32) as u32);
-            let low = F::from_u64_unchecked(low as u64);
-            let high = F::from_u64_unchecked(high as u64);
-            transcript.witness_field_elements(&[low, high]);
+        let witness_query = OracleQuery::construct(
+            self.witness_tree
+                .as_ref()
+                .expect("witness must be committed"),
+            &SaltedLeafSource {
+                source: witness_oracle_storage,
+                salt: &self.witness_salt,
+            },
+            lde_factor,
+            coset_idx,
+            domain_size,
+            inner_idx,
+            1,
+        );
 
-            log!("PoW for {} bits taken {:?}", new_pow_bits, now.elapsed());
+        let stage_2_query = OracleQuery::construct(
+            self.second_stage_tree
+                .as_ref()
+                .expect("second stage must be committed"),
+            &SaltedLeafSource {
+                source: second_stage_oracle_storage,
+                salt: &self.second_stage_salt,
+            },
+            lde_factor,
+            coset_idx,
+            domain_size,
+            inner_idx,
+            1,
+        );
 
-            pow_challenge
-        } else {
-            0
-        };
+        let quotient_query = OracleQuery::construct(
+            self.quotients_tree
+                .as_ref()
+                .expect("quotient must be committed"),
+            &SaltedLeafSource {
+                source: quotient_oracle_storage,
+                salt: &self.quotients_salt,
+            },
+            lde_factor,
+            coset_idx,
+            domain_size,
+            inner_idx,
+            1,
+        );
 
-        assert!(
-            proof_config.fri_folding_schedule.is_none(),
-            "we do not yet support externally provided FRI schedule"
+        let setup_query = OracleQuery::construct(
+            setup_tree,
+            setup,
+            lde_factor,
+            coset_idx,
+            domain_size,
+            inner_idx,
+            1,
         );
 
-        let mut proof = Proof::<F, H, EXT> {
-            proof_config,
-            public_inputs: public_inputs_only_values,
-            witness_oracle_cap: witness_tree_cap.clone(),
-            stage_2_oracle_cap: second_stage_tree_cap.clone(),
-            quotient_oracle_cap: quotients_tree_cap.clone(),
-            final_fri_monomials: fri_data.monomial_forms.clone(),
-            values_at_z: all_polys_at_zs.clone(),
-            values_at_z_omega: all_polys_at_zomegas.clone(),
-            values_at_0: all_polys_at_zero.clone(),
-            pow_challenge,
-            fri_base_oracle_cap: fri_data.base_oracle.get_cap(),
-            fri_intermediate_oracles_caps: fri_data
-                .intermediate_oracles
-                .iter()
-                .map(|el| el.get_cap())
-                .collect(),
-            queries_per_fri_repetition: vec![],
-            _marker: std::marker::PhantomData,
-        };
+        BaseOraclesQueries {
+            witness_query,
+            stage_2_query,
+            quotient_query,
+            setup_query,
+        }
+    }
+}
 
-        let max_needed_bits = (domain_size * lde_factor_for_fri).trailing_zeros() as usize;
-        let mut bools_buffer = BoolsBuffer {
-            available: vec![],
-            max_needed: max_needed_bits,
+/// Opens every FRI oracle at the point where the base oracle is opened
+pub(crate) fn query_fri_oracles<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    H: TreeHasher<F>,
+    const N: usize,
+>(
+    fri_data: &FriOracles<F, H, Global, Global, N>,
+    base_fri_source: &[ArcGenericLdeStorage<F, P, Global, Global>; N],
+    interpolation_log2s_schedule: &[usize],
+    lde_factor: usize,
+    coset_idx: usize,
+    domain_size: usize,
+    inner_idx: usize,
+) -> Vec<OracleQuery<F, H>> {
+    let mut domain_size = domain_size;
+    let mut fri_queries = Vec::with_capacity(interpolation_log2s_schedule.len());
+    let mut inner_idx = inner_idx;
+    for (idx, interpolation_degree_log2) in interpolation_log2s_schedule.iter().enumerate() {
+        let fri_oracle_query = if idx == 0 {
+            OracleQuery::construct(
+                &fri_data.base_oracle,
+                base_fri_source,
+                lde_factor,
+                coset_idx,
+                domain_size,
+                inner_idx,
+                1 << *interpolation_degree_log2,
+            )
+        } else {
+            OracleQuery::construct(
+                &fri_data.intermediate_oracles[idx - 1],
+                &&fri_data.leaf_sources_for_intermediate_oracles[idx - 1],
+                lde_factor,
+                coset_idx,
+                domain_size,
+                inner_idx,
+                1 << *interpolation_degree_log2,
+            )
         };
+        inner_idx >>= *interpolation_degree_log2;
+        domain_size >>= *interpolation_degree_log2;
+        fri_queries.push(fri_oracle_query)
+    }
 
-        let base_fri_source = (c0_as_poly, c1_as_poly);
-
-        let num_bits_for_in_coset_index =
-            max_needed_bits - lde_factor_for_fri.trailing_zeros() as usize;
+    fri_queries
+}
 
-        for _query_idx in 0..num_queries {
-            let query_index_lsb_first_bits =
-                bools_buffer.get_bits(&mut transcript, max_needed_bits);
-            // we consider it to be some convenient for us encoding of coset + inner index.
+/// Does PoW for a challenge drawn from the transcript and commits the result into it.
+/// Returns `0` and leaves the transcript untouched if `pow_bits` is zero
+pub(crate) fn run_pow<F: SmallField, TR: Transcript<F>, POW: PoWRunner>(
+    transcript: &mut TR,
+    pow_bits: u32,
+    worker: &Worker,
+) -> u64 {
+    if pow_bits == 0 {
+        return 0;
+    }
 
-            let inner_idx = u64_from_lsb_first_bits(
-                &query_index_lsb_first_bits[0..num_bits_for_in_coset_index],
-            ) as usize;
-            let coset_idx =
-                u64_from_lsb_first_bits(&query_index_lsb_first_bits[num_bits_for_in_coset_index..])
-                    as usize;
+    log!("Doing PoW");
 
-            use crate::cs::implementations::proof::OracleQuery;
+    let now = std::time::Instant::now();
 
-            let witness_query = OracleQuery::construct(
-                &witness_tree,
-                &trace_holder.variables,
-                lde_factor_for_fri,
-                coset_idx,
-                domain_size,
-                inner_idx,
-                1,
-            );
+    // pull enough challenges from the transcript
+    let mut num_challenges = 256 / F::CHAR_BITS;
+    if num_challenges % F::CHAR_BITS != 0 {
+        num_challenges += 1;
+    }
+    let challenges = transcript.get_multiple_challenges(num_challenges);
+    let pow_challenge = POW::run_from_field_elements(challenges, pow_bits, worker);
 
-            let second_stage_query = OracleQuery::construct(
-                &second_stage_tree,
-                &second_stage_polys_storage,
-                lde_factor_for_fri,
-                coset_idx,
-                domain_size,
-                inner_idx,
-                1,
-            );
+    assert!(F::CAPACITY_BITS >= 32);
+    let (low, high) = (pow_challenge as u32, (pow_challenge >> 32) as u32);
+    let low = F::from_u64_unchecked(low as u64);
+    let high = F::from_u64_unchecked(high as u64);
+    transcript.witness_field_elements(&[low, high]);
 
-            let quotient_query = OracleQuery::construct(
-                &quotients_tree,
-                &quotient_chunks_ldes,
-                lde_factor_for_fri,
-                coset_idx,
-                domain_size,
-                inner_idx,
-                1,
-            );
+    log!("PoW for {} bits taken {:?}", pow_bits, now.elapsed());
 
-            let setup_query = OracleQuery::construct(
-                setup_tree,
-                setup,
-                lde_factor_for_fri,
-                coset_idx,
-                domain_size,
-                inner_idx,
-                1,
-            );
+    pow_challenge
+}
 
-            let mut domain_size = domain_size;
-            let mut fri_queries = Vec::with_capacity(interpolation_log2s_schedule.len());
-            let mut inner_idx = inner_idx;
-            for (idx, interpolation_degree_log2) in interpolation_log2s_schedule.iter().enumerate()
-            {
-                let fri_oracle_query = if idx == 0 {
-                    OracleQuery::construct(
-                        &fri_data.base_oracle,
-                        &base_fri_source,
-                        lde_factor_for_fri,
-                        coset_idx,
-                        domain_size,
-                        inner_idx,
-                        1 << *interpolation_degree_log2,
-                    )
-                } else {
-                    OracleQuery::construct(
-                        &fri_data.intermediate_oracles[idx - 1],
-                        &&fri_data.leaf_sources_for_intermediate_oracles[idx - 1],
-                        lde_factor_for_fri,
-                        coset_idx,
-                        domain_size,
-                        inner_idx,
-                        1 << *interpolation_degree_log2,
-                    )
-                };
-                inner_idx >>= *interpolation_degree_log2;
-                domain_size >>= *interpolation_degree_log2;
-                fri_queries.push(fri_oracle_query)
+// Random values for `salt_size` elements of every leaf of an oracle, over the committed cosets only
+fn generate_salt<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+>(
+    proof_config: &ProofConfig,
+    domain_size: usize,
+) -> Vec<ArcGenericLdeStorage<F, P, Global, Global>> {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+
+    (0..proof_config.salt_size())
+        .map(|_| {
+            let mut column =
+                ArcGenericLdeStorage::empty_with_capacity_in(proof_config.fri_lde_factor, Global);
+            for _ in 0..proof_config.fri_lde_factor {
+                let mut values = allocate_in_with_alignment_of::<F, P, Global>(domain_size, Global);
+                values.extend((0..domain_size).map(|_| F::from_u64_with_reduction(rng.gen())));
+                let values = P::vec_from_base_vec(values);
+                column
+                    .storage
+                    .push(Arc::new(GenericPolynomial::from_storage(values)));
             }
 
-            let queries = SingleRoundQueries {
-                witness_query,
-                stage_2_query: second_stage_query,
-                quotient_query,
-                setup_query,
-                fri_queries,
-            };
+            column
+        })
+        .collect()
+}
 
-            proof.queries_per_fri_repetition.push(queries);
+// Leaf source of an oracle followed by the salt of its leafs
+struct SaltedLeafSource<
+    'a,
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    S: QuerySource<F>,
+> {
+    source: S,
+    salt: &'a [ArcGenericLdeStorage<F, P, Global, Global>],
+}
+
+impl<
+        'a,
+        F: SmallField,
+        P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+        S: QuerySource<F>,
+    > QuerySource<F> for SaltedLeafSource<'a, F, P, S>
+{
+    fn get_elements(
+        &self,
+        lde_factor: usize,
+        coset_idx: usize,
+        domain_size: usize,
+        inner_idx: usize,
+        num_elements: usize,
+        dst: &mut Vec<F>,
+    ) {
+        self.source.get_elements(
+            lde_factor,
+            coset_idx,
+            domain_size,
+            inner_idx,
+            num_elements,
+            dst,
+        );
+        let (outer, inner) = split_inner(inner_idx, P::SIZE_FACTOR);
+        for column in self.salt.iter() {
+            dst.push(column.storage[coset_idx].storage[outer].as_base_elements()[inner]);
         }
+    }
+}
 
-        proof
+// Commits to the first `lde_factor` cosets of the LDEs, with `salt` appended to every leaf.
+// If out-of-core proving is enabled the full LDEs and the tree are spilled, and in-memory copies
+// are released before the tree is built, otherwise LDEs are returned back
+fn commit_to_oracle<
+    F: SmallField,
+    P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    H: TreeHasher<F>,
+>(
+    source: Vec<ArcGenericLdeStorage<F, P, Global, Global>>,
+    salt: &[ArcGenericLdeStorage<F, P, Global, Global>],
+    lde_factor: usize,
+    cap_size: usize,
+    out_of_core: Option<&OutOfCoreConfig>,
+    name: &str,
+    worker: &Worker,
+) -> (
+    OracleTree<F, H>,
+    Option<Vec<ArcGenericLdeStorage<F, P, Global, Global>>>,
+    Option<SpilledOracle<F>>,
+) {
+    if let Some(out_of_core) = out_of_core {
+        let spilled = SpilledOracle::spill_from_lde_storages(out_of_core, name, &source, worker)
+            .unwrap_or_else(|err| panic!("failed to spill {} oracle: {}", name, err));
+        drop(source);
+        let tree = spilled.build_merkle_tree::<H, P>(lde_factor, salt, cap_size, worker);
+        let tree = SpilledMerkleTree::spill(out_of_core, name, tree)
+            .unwrap_or_else(|err| panic!("failed to spill {} tree: {}", name, err));
+
+        (OracleTree::Spilled(tree), None, Some(spilled))
+    } else {
+        let committed = source
+            .iter()
+            .map(|el| el.subset_for_degree(lde_factor))
+            .chain(salt.iter().cloned())
+            .collect();
+        let tree = MerkleTreeWithCap::<F, H>::construct(committed, cap_size, worker);
+
+        (OracleTree::InMemory(tree), Some(source), None)
     }
 }
 
@@ -2371,24 +3623,207 @@ pub fn compute_fri_schedule(
     )
 }
 
-pub fn materialize_ext_challenge_powers<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
-    (c0, c1): (F, F),
+/// Largest supported folding step, so every FRI leaf has at most 2^5 elements of the extension
+pub const MAX_FRI_FOLDING_DEGREE_LOG2: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FriScheduleError {
+    EmptySchedule,
+    InvalidFoldingStep {
+        step: usize,
+        interpolation_degree_log2: usize,
+    },
+    FoldsBeyondDegree {
+        total_folding_log2: usize,
+        initial_degree_log2: usize,
+    },
+    OracleSmallerThanCap {
+        step: usize,
+        num_leaves_log2: usize,
+        cap_size_log2: usize,
+    },
+}
+
+impl std::fmt::Display for FriScheduleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptySchedule => write!(f, "FRI folding schedule is empty"),
+            Self::InvalidFoldingStep {
+                step,
+                interpolation_degree_log2,
+            } => write!(
+                f,
+                "FRI folding step {} folds by 2^{}, while steps from 2^1 to 2^{} are supported",
+                step, interpolation_degree_log2, MAX_FRI_FOLDING_DEGREE_LOG2
+            ),
+            Self::FoldsBeyondDegree {
+                total_folding_log2,
+                initial_degree_log2,
+            } => write!(
+                f,
+                "FRI folding schedule folds by 2^{} in total, but degree is only 2^{}",
+                total_folding_log2, initial_degree_log2
+            ),
+            Self::OracleSmallerThanCap {
+                step,
+                num_leaves_log2,
+                cap_size_log2,
+            } => write!(
+                f,
+                "FRI oracle at step {} has 2^{} leaves, that is less than cap size 2^{}",
+                step, num_leaves_log2, cap_size_log2
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FriScheduleError {}
+
+/// Reason why a circuit can not be proven in zero-knowledge mode
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ZeroKnowledgeError {
+    LookupsAreUsed,
+    SpecializedColumnsAreUsed,
+    NotEnoughBlindingRows { reserved: usize, required: usize },
+}
+
+impl std::fmt::Display for ZeroKnowledgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LookupsAreUsed => write!(
+                f,
+                "zero-knowledge mode is not supported for circuits with lookups"
+            ),
+            Self::SpecializedColumnsAreUsed => write!(
+                f,
+                "zero-knowledge mode is only supported for gates over general purpose columns"
+            ),
+            Self::NotEnoughBlindingRows { reserved, required } => write!(
+                f,
+                "circuit has {} blinding rows, but {} are required for zero-knowledge",
+                reserved, required
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ZeroKnowledgeError {}
+
+/// Same as `compute_fri_schedule`, but follows the folding schedule from the config if it's present.
+/// Number of queries and PoW bits do not depend on the schedule
+pub fn compute_fri_schedule_for_config(
+    proof_config: &ProofConfig,
+    rate_log_two: u32,
+    initial_degree_log_two: u32,
+) -> Result<
+    (
+        u32,        // updated POW bits if needed
+        usize,      // num queries
+        Vec<usize>, // folding schedule,
+        usize,      // final poly degree to expect
+    ),
+    FriScheduleError,
+> {
+    let (new_pow_bits, num_queries, default_schedule, default_final_degree) = compute_fri_schedule(
+        proof_config.security_level as u32,
+        proof_config.merkle_tree_cap_size,
+        proof_config.pow_bits,
+        rate_log_two,
+        initial_degree_log_two,
+    );
+
+    let Some(schedule) = proof_config.fri_folding_schedule.as_ref() else {
+        return Ok((
+            new_pow_bits,
+            num_queries,
+            default_schedule,
+            default_final_degree,
+        ));
+    };
+
+    let final_degree_log_two = validate_fri_schedule(
+        schedule,
+        proof_config.merkle_tree_cap_size,
+        rate_log_two,
+        initial_degree_log_two,
+    )?;
+
+    Ok((
+        new_pow_bits,
+        num_queries,
+        schedule.clone(),
+        1 << final_degree_log_two,
+    ))
+}
+
+/// Checks that the schedule can be used for the given code, and returns the log2 of the final degree
+pub fn validate_fri_schedule(
+    schedule: &[usize],
+    cap_size: usize,
+    rate_log_two: u32,
+    initial_degree_log_two: u32,
+) -> Result<usize, FriScheduleError> {
+    assert!(cap_size.is_power_of_two());
+
+    if schedule.is_empty() {
+        return Err(FriScheduleError::EmptySchedule);
+    }
+
+    let cap_size_log2 = cap_size.trailing_zeros() as usize;
+    let mut degree_log2 = initial_degree_log_two as usize;
+    let mut total_folding_log2 = 0;
+    for (step, interpolation_degree_log2) in schedule.iter().copied().enumerate() {
+        if interpolation_degree_log2 == 0 || interpolation_degree_log2 > MAX_FRI_FOLDING_DEGREE_LOG2
+        {
+            return Err(FriScheduleError::InvalidFoldingStep {
+                step,
+                interpolation_degree_log2,
+            });
+        }
+        total_folding_log2 += interpolation_degree_log2;
+        if total_folding_log2 > initial_degree_log_two as usize {
+            return Err(FriScheduleError::FoldsBeyondDegree {
+                total_folding_log2,
+                initial_degree_log2: initial_degree_log_two as usize,
+            });
+        }
+
+        // every leaf contains all the elements that are folded together
+        let num_leaves_log2 = degree_log2 + rate_log_two as usize - interpolation_degree_log2;
+        if num_leaves_log2 < cap_size_log2 {
+            return Err(FriScheduleError::OracleSmallerThanCap {
+                step,
+                num_leaves_log2,
+                cap_size_log2,
+            });
+        }
+        degree_log2 -= interpolation_degree_log2;
+    }
+
+    Ok(degree_log2)
+}
+
+pub fn materialize_ext_challenge_powers<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+>(
+    challenge: [F; N],
     num_challenges: usize,
-) -> Vec<(F, F)> {
+) -> Vec<[F; N]> {
     debug_assert!(num_challenges >= 2);
     let mut result = Vec::with_capacity(num_challenges);
-    result.push((F::ONE, F::ZERO));
-    result.push((c0, c1));
-    let as_extension = ExtensionField::<F, 2, EXT> {
-        coeffs: [c0, c1],
+    result.push(ExtensionField::<F, N, EXT>::ONE.into_coeffs_in_base());
+    result.push(challenge);
+    let as_extension = ExtensionField::<F, N, EXT> {
+        coeffs: challenge,
         _marker: std::marker::PhantomData,
     };
 
     let mut current = as_extension;
     for _ in 2..num_challenges {
         current.mul_assign(&as_extension);
-        let [c0, c1] = current.into_coeffs_in_base();
-        result.push((c0, c1));
+        result.push(current.into_coeffs_in_base());
     }
 
     result
@@ -2397,44 +3832,37 @@ pub fn materialize_ext_challenge_powers<F: SmallField, EXT: FieldExtension<2, Ba
 fn quotening_operation<
     F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
+    const N: usize,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
-    destination_c0: &mut Vec<Vec<P, A>, B>,
-    destination_c1: &mut Vec<Vec<P, A>, B>,
+    destination: &mut [Vec<Vec<P, A>, B>; N],
     polynomial_ldes: Vec<ArcGenericLdeStorage<F, P, A, B>>,
     values_at_z: Vec<F>,
     precomputed_cosets: &ArcGenericLdeStorage<F, P, A, B>,
     at: F,
-    challenge_coefficients: &[(F, F)],
+    challenge_coefficients: &[[F; N]],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
 
-    assert_eq!(destination_c0.len(), destination_c1.len());
-    assert_eq!(destination_c0.len(), precomputed_cosets.outer_len());
+    for el in destination.iter() {
+        assert_eq!(el.len(), precomputed_cosets.outer_len());
+        assert_eq!(el[0].len(), precomputed_cosets.inner_len());
+    }
     assert_eq!(polynomial_ldes.len(), values_at_z.len());
     assert_eq!(polynomial_ldes.len(), challenge_coefficients.len());
-    assert_eq!(destination_c0[0].len(), precomputed_cosets.inner_len());
     let polynomial_ldes_ref = &polynomial_ldes;
     let values_at_z_ref = &values_at_z;
     let challenge_coefficients_ref = challenge_coefficients;
 
     // we will work over every coset independently for simplicity
-    for (coset_idx, (c0, c1)) in destination_c0
-        .iter_mut()
-        .zip(destination_c1.iter_mut())
-        .enumerate()
-    {
-        debug_assert_eq!(c0.len(), c1.len());
-        let work_size = c0.len();
+    for coset_idx in 0..precomputed_cosets.outer_len() {
+        let dst = destination.each_mut().map(|el| &mut el[coset_idx][..]);
+        let work_size = dst[0].len();
         worker.scope(work_size, |scope, chunk_size| {
-            for (chunk_idx, (c0, c1)) in c0
-                .chunks_mut(chunk_size)
-                .zip(c1.chunks_mut(chunk_size))
-                .enumerate()
-            {
+            for (chunk_idx, dst) in chunk_columns_mut(dst, chunk_size).enumerate() {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
                     let start = chunk_idx * chunk_size;
@@ -2454,12 +3882,14 @@ fn quotening_operation<
                     batch_inverse_inplace::<_, A>(&mut denoms);
                     let denoms = P::vec_from_base_vec(denoms);
 
-                    // buffers to compute c0 and c1 coefficients before placing them into destination
-                    let mut buffer_c0 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c0.resize(end - start, P::zero(&mut ctx));
+                    // buffers to compute coefficients before placing them into destination
+                    let zero = P::zero(&mut ctx);
+                    let mut buffer: [Vec<P, A>; N] = std::array::from_fn(|_| {
+                        let mut buffer = Vec::with_capacity_in(end - start, A::default());
+                        buffer.resize(end - start, zero);
 
-                    let mut buffer_c1 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c1.resize(end - start, P::zero(&mut ctx));
+                        buffer
+                    });
 
                     for ((src_poly, value_at_z), challenge_coefficients) in polynomial_ldes_ref
                         .iter()
@@ -2469,50 +3899,34 @@ fn quotening_operation<
                         let src_coset = &src_poly.storage[coset_idx];
                         let src_chunk = &src_coset.storage[start..end];
 
-                        let (challenge_c0, challenge_c1) = challenge_coefficients;
-                        let challenge_c0 = P::constant(*challenge_c0, &mut ctx);
-                        let challenge_c1 = P::constant(*challenge_c1, &mut ctx);
-                        debug_assert_eq!(src_chunk.len(), buffer_c0.len());
+                        let challenge = challenge_coefficients.map(|el| P::constant(el, &mut ctx));
+                        debug_assert_eq!(src_chunk.len(), buffer[0].len());
                         let value_at_z = P::constant(*value_at_z, &mut ctx);
 
-                        for ((src, dst_c0), dst_c1) in src_chunk
-                            .iter()
-                            .zip(buffer_c0.iter_mut())
-                            .zip(buffer_c1.iter_mut())
-                        {
-                            // src is in the base field, so in fact we do two independent operations
+                        for (idx, src) in src_chunk.iter().enumerate() {
+                            // src is in the base field, so in fact we do N independent operations
                             let mut tmp = *src;
                             tmp.sub_assign(&value_at_z, &mut ctx);
 
-                            let mut c0 = tmp;
-                            c0.mul_assign(&challenge_c0, &mut ctx);
-
-                            let mut c1 = tmp;
-                            c1.mul_assign(&challenge_c1, &mut ctx);
-
-                            dst_c0.add_assign(&c0, &mut ctx);
-                            dst_c1.add_assign(&c1, &mut ctx);
+                            for (dst, challenge) in buffer.iter_mut().zip(challenge.iter()) {
+                                let mut subres = tmp;
+                                subres.mul_assign(challenge, &mut ctx);
+                                dst[idx].add_assign(&subres, &mut ctx);
+                            }
                         }
                     }
 
                     // numerators were aggregated, now "divide" once
-                    for ((dst_c0, dst_c1), denom) in buffer_c0
-                        .iter_mut()
-                        .zip(buffer_c1.iter_mut())
-                        .zip(denoms.into_iter())
-                    {
-                        dst_c0.mul_assign(&denom, &mut ctx);
-                        dst_c1.mul_assign(&denom, &mut ctx);
+                    for buffer in buffer.iter_mut() {
+                        for (dst, denom) in buffer.iter_mut().zip(denoms.iter()) {
+                            dst.mul_assign(denom, &mut ctx);
+                        }
                     }
 
-                    for (((dst_c0, dst_c1), src_c0), src_c1) in c0
-                        .iter_mut()
-                        .zip(c1.iter_mut())
-                        .zip(buffer_c0.into_iter())
-                        .zip(buffer_c1.into_iter())
-                    {
-                        dst_c0.add_assign(&src_c0, &mut ctx);
-                        dst_c1.add_assign(&src_c1, &mut ctx);
+                    for (dst, src) in dst.into_iter().zip(buffer.into_iter()) {
+                        for (dst, src) in dst.iter_mut().zip(src.into_iter()) {
+                            dst.add_assign(&src, &mut ctx);
+                        }
                     }
                 })
             }
@@ -2522,46 +3936,39 @@ fn quotening_operation<
 
 fn quotening_operation_in_extension<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
     B: GoodAllocator,
 >(
-    destination_c0: &mut Vec<Vec<P, A>, B>,
-    destination_c1: &mut Vec<Vec<P, A>, B>,
-    polynomial_ldes: Vec<[Option<ArcGenericLdeStorage<F, P, A, B>>; 2]>,
-    values_at_z: Vec<ExtensionField<F, 2, EXT>>,
+    destination: &mut [Vec<Vec<P, A>, B>; N],
+    polynomial_ldes: Vec<[Option<ArcGenericLdeStorage<F, P, A, B>>; N]>,
+    values_at_z: Vec<ExtensionField<F, N, EXT>>,
     precomputed_cosets: &ArcGenericLdeStorage<F, P, A, B>,
-    at: ExtensionField<F, 2, EXT>,
-    challenge_coefficients: &[(F, F)],
+    at: ExtensionField<F, N, EXT>,
+    challenge_coefficients: &[[F; N]],
     worker: &Worker,
     ctx: &mut P::Context,
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
 
-    assert_eq!(destination_c0.len(), destination_c1.len());
-    assert_eq!(destination_c0.len(), precomputed_cosets.outer_len());
+    for el in destination.iter() {
+        assert_eq!(el.len(), precomputed_cosets.outer_len());
+        assert_eq!(el[0].len(), precomputed_cosets.inner_len());
+    }
     assert_eq!(polynomial_ldes.len(), values_at_z.len());
     assert_eq!(polynomial_ldes.len(), challenge_coefficients.len());
-    assert_eq!(destination_c0[0].len(), precomputed_cosets.inner_len());
     let polynomial_ldes_ref = &polynomial_ldes;
     let values_at_z_ref = &values_at_z;
     let challenge_coefficients_ref = challenge_coefficients;
 
     // we will work over every coset independently for simplicity
-    for (coset_idx, (c0, c1)) in destination_c0
-        .iter_mut()
-        .zip(destination_c1.iter_mut())
-        .enumerate()
-    {
-        debug_assert_eq!(c0.len(), c1.len());
-        let work_size = c0.len();
+    for coset_idx in 0..precomputed_cosets.outer_len() {
+        let dst = destination.each_mut().map(|el| &mut el[coset_idx][..]);
+        let work_size = dst[0].len();
         worker.scope(work_size, |scope, chunk_size| {
-            for (chunk_idx, (c0, c1)) in c0
-                .chunks_mut(chunk_size)
-                .zip(c1.chunks_mut(chunk_size))
-                .enumerate()
-            {
+            for (chunk_idx, mut dst) in chunk_columns_mut(dst, chunk_size).enumerate() {
                 let mut ctx = *ctx;
                 scope.spawn(move |_| {
                     let start = chunk_idx * chunk_size;
@@ -2571,133 +3978,89 @@ fn quotening_operation_in_extension<
                     }
                     // denominator is shared between everything, and we can compute it once here
                     let roots = &precomputed_cosets.storage[coset_idx].storage[start..end];
-                    let at_c0 = P::constant(at.coeffs[0], &mut ctx);
-                    let at_c1 = P::constant(at.coeffs[1], &mut ctx);
-                    let mut roots_minus_z_c0 = roots.to_vec_in(A::default());
-                    let mut at_c1_negated = at_c1;
-                    at_c1_negated.negate(&mut ctx);
-                    let mut at_c1_negated_vec =
-                        Vec::with_capacity_in(roots_minus_z_c0.len(), A::default());
-                    at_c1_negated_vec.resize(roots_minus_z_c0.len(), at_c1_negated);
+                    let mut at_negated = at;
+                    at_negated.negate();
+                    let zero = P::zero(&mut ctx);
 
                     // denominator for (x - z), but roots are in base, so only one subtraction is needed
-                    for el in roots_minus_z_c0.iter_mut() {
-                        el.sub_assign(&at_c0, &mut ctx);
-                    }
+                    let num_base_elements = (end - start) * P::SIZE_FACTOR;
+                    let mut denoms: [Vec<F, A>; N] = std::array::from_fn(|i| {
+                        let mut denoms = allocate_in_with_alignment_of::<F, P, A>(
+                            num_base_elements,
+                            A::default(),
+                        );
+                        denoms.resize(num_base_elements, at_negated.coeffs[i]);
 
-                    let mut denoms_c0 = P::vec_into_base_vec(roots_minus_z_c0);
-                    let mut denoms_c1 = P::vec_into_base_vec(at_c1_negated_vec);
-                    batch_inverse_inplace_in_extension::<F, EXT, A>(&mut denoms_c0, &mut denoms_c1);
-                    let denoms_c0 = P::vec_from_base_vec(denoms_c0);
-                    let denoms_c1 = P::vec_from_base_vec(denoms_c1);
+                        denoms
+                    });
+                    for (dst, root) in denoms[0]
+                        .iter_mut()
+                        .zip(P::slice_into_base_slice(roots).iter())
+                    {
+                        dst.add_assign(root);
+                    }
 
-                    let zero = P::zero(&mut ctx);
+                    batch_inverse_inplace_in_extension::<F, N, EXT, A>(
+                        denoms.each_mut().map(|el| &mut el[..]),
+                    );
+                    let denoms = denoms.map(|el| P::vec_from_base_vec(el));
 
-                    // buffers to compute c0 and c1 coefficients before placing them into destination
-                    let mut buffer_c0 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c0.resize(end - start, zero);
+                    // buffers to compute coefficients before placing them into destination
+                    let mut buffer: [Vec<P, A>; N] = std::array::from_fn(|_| {
+                        let mut buffer = Vec::with_capacity_in(end - start, A::default());
+                        buffer.resize(end - start, zero);
 
-                    let mut buffer_c1 = Vec::with_capacity_in(end - start, A::default());
-                    buffer_c1.resize(end - start, zero);
+                        buffer
+                    });
 
-                    for (([src_poly_c0, src_poly_c1], value_at_z), challenge_coefficients) in
-                        polynomial_ldes_ref
-                            .iter()
-                            .zip(values_at_z_ref.iter())
-                            .zip(challenge_coefficients_ref.iter())
+                    for ((src_poly, value_at_z), challenge_coefficients) in polynomial_ldes_ref
+                        .iter()
+                        .zip(values_at_z_ref.iter())
+                        .zip(challenge_coefficients_ref.iter())
                     {
-                        let src_coset_c0 = &src_poly_c0
-                            .as_ref()
-                            .expect("c0 must never be empty")
-                            .storage[coset_idx];
-                        let src_coset_c1 = src_poly_c1.as_ref().map(|el| &el.storage[coset_idx]);
-                        let src_chunk_c0 = &src_coset_c0.storage[start..end];
-                        let src_chunk_c1 = src_coset_c1.map(|el| &el.storage[start..end]);
-
-                        let (challenge_c0, challenge_c1) = challenge_coefficients;
-                        let challenge_c0 = P::constant(*challenge_c0, &mut ctx);
-                        let challenge_c1 = P::constant(*challenge_c1, &mut ctx);
-                        debug_assert_eq!(src_chunk_c0.len(), buffer_c0.len());
-                        let value_at_z_c0 = P::constant(value_at_z.coeffs[0], &mut ctx);
-                        let value_at_z_c1 = P::constant(value_at_z.coeffs[1], &mut ctx);
-                        let mut value_at_z_c1_negated = value_at_z_c1;
-                        value_at_z_c1_negated.negate(&mut ctx);
-
-                        // we branch based on what we have
-
-                        if let Some(src_chunk_c1) = src_chunk_c1 {
-                            debug_assert_eq!(src_chunk_c1.len(), buffer_c1.len());
-
-                            for (((src_c0, src_c1), dst_c0), dst_c1) in src_chunk_c0
-                                .iter()
-                                .zip(src_chunk_c1.iter())
-                                .zip(buffer_c0.iter_mut())
-                                .zip(buffer_c1.iter_mut())
-                            {
-                                // src is in the base field, so in fact we do two independent operations
-                                let mut tmp_c0 = *src_c0;
-                                tmp_c0.sub_assign(&value_at_z_c0, &mut ctx);
-
-                                let mut tmp_c1 = *src_c1;
-                                tmp_c1.sub_assign(&value_at_z_c1, &mut ctx);
-
-                                mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                    &mut tmp_c0,
-                                    &mut tmp_c1,
-                                    &challenge_c0,
-                                    &challenge_c1,
-                                    &mut ctx,
-                                );
+                        // polys in the base field only have the first coefficient,
+                        // and we implicitly treat all others as zeroes
+                        let src_chunk = src_poly.each_ref().map(|el| {
+                            el.as_ref()
+                                .map(|el| &el.storage[coset_idx].storage[start..end])
+                        });
+                        assert!(src_chunk[0].is_some(), "c0 must never be empty");
 
-                                dst_c0.add_assign(&tmp_c0, &mut ctx);
-                                dst_c1.add_assign(&tmp_c1, &mut ctx);
-                            }
-                        } else {
-                            for ((src, dst_c0), dst_c1) in src_chunk_c0
-                                .iter()
-                                .zip(buffer_c0.iter_mut())
-                                .zip(buffer_c1.iter_mut())
+                        let challenge = challenge_coefficients.map(|el| P::constant(el, &mut ctx));
+                        let value_at_z = value_at_z.coeffs.map(|el| P::constant(el, &mut ctx));
+
+                        for idx in 0..(end - start) {
+                            let mut tmp = [zero; N];
+                            for ((tmp, src), value_at_z) in
+                                tmp.iter_mut().zip(src_chunk.iter()).zip(value_at_z.iter())
                             {
-                                // src is in the base field, so in fact we do two independent operations
-                                let mut tmp_c0 = *src;
-                                tmp_c0.sub_assign(&value_at_z_c0, &mut ctx);
-
-                                let mut tmp_c1 = value_at_z_c1_negated;
-
-                                mul_assign_vectorized_in_extension::<F, P, EXT>(
-                                    &mut tmp_c0,
-                                    &mut tmp_c1,
-                                    &challenge_c0,
-                                    &challenge_c1,
-                                    &mut ctx,
-                                );
+                                if let Some(src) = src {
+                                    *tmp = src[idx];
+                                }
+                                tmp.sub_assign(value_at_z, &mut ctx);
+                            }
 
-                                dst_c0.add_assign(&tmp_c0, &mut ctx);
-                                dst_c1.add_assign(&tmp_c1, &mut ctx);
+                            mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                                &mut tmp, &challenge, &mut ctx,
+                            );
+
+                            for (dst, tmp) in buffer.iter_mut().zip(tmp.iter()) {
+                                dst[idx].add_assign(tmp, &mut ctx);
                             }
                         }
                     }
 
                     // numerators were aggregated, now "divide" once
-                    for (((dst_c0, dst_c1), denom_c0), denom_c1) in buffer_c0
-                        .iter_mut()
-                        .zip(buffer_c1.iter_mut())
-                        .zip(denoms_c0.into_iter())
-                        .zip(denoms_c1.into_iter())
-                    {
-                        mul_assign_vectorized_in_extension::<F, P, EXT>(
-                            dst_c0, dst_c1, &denom_c0, &denom_c1, &mut ctx,
+                    for idx in 0..(end - start) {
+                        let mut value: [P; N] = std::array::from_fn(|i| buffer[i][idx]);
+                        let denom: [P; N] = std::array::from_fn(|i| denoms[i][idx]);
+                        mul_assign_vectorized_in_extension::<F, P, N, EXT>(
+                            &mut value, &denom, &mut ctx,
                         );
-                    }
 
-                    for (((dst_c0, dst_c1), src_c0), src_c1) in c0
-                        .iter_mut()
-                        .zip(c1.iter_mut())
-                        .zip(buffer_c0.into_iter())
-                        .zip(buffer_c1.into_iter())
-                    {
-                        dst_c0.add_assign(&src_c0, &mut ctx);
-                        dst_c1.add_assign(&src_c1, &mut ctx);
+                        for (dst, value) in dst.iter_mut().zip(value.iter()) {
+                            dst[idx].add_assign(value, &mut ctx);
+                        }
                     }
                 })
             }
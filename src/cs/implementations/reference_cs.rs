@@ -1,3 +1,5 @@
+use self::call_sites::CallSitesTracker;
+use self::namespaces::NamespacesTracker;
 use self::traits::GoodAllocator;
 use self::witness::WitnessVec;
 
@@ -69,9 +71,13 @@ pub struct CSReferenceImplementation<
     pub(crate) lookup_marker_gate_idx: Option<u32>,
     pub(crate) table_ids_as_variables: Vec<Variable>,
     pub(crate) public_inputs: Vec<(usize, usize)>,
+    pub(crate) blinding_rows: std::ops::Range<usize>,
 
     pub(crate) specialized_gates_rough_stats: HashMap<TypeId, usize>,
 
+    pub(crate) call_sites: CallSitesTracker,
+    pub(crate) namespaces: NamespacesTracker,
+
     pub(crate) static_toolbox: T,
     pub(crate) gates_configuration: GC,
 
@@ -109,7 +115,11 @@ pub struct CSReferenceAssembly<
 
     pub specialized_gates_rough_stats: HashMap<TypeId, usize>,
 
+    pub call_sites: CallSitesTracker,
+    pub namespaces: NamespacesTracker,
+
     pub public_inputs: Vec<(usize, usize)>,
+    pub blinding_rows: std::ops::Range<usize>, // rows that are only filled in zero-knowledge mode
 
     pub placement_strategies: HashMap<TypeId, GatePlacementStrategy>,
 }
@@ -210,7 +220,10 @@ impl<
             lookup_tables,
             lookup_multiplicities,
             specialized_gates_rough_stats,
+            call_sites,
+            namespaces,
             public_inputs,
+            blinding_rows,
             gates_configuration,
             evaluation_data_over_general_purpose_columns,
             evaluation_data_over_specialized_columns,
@@ -247,9 +260,12 @@ impl<
             lookup_multiplicities,
             witness: None,
             specialized_gates_rough_stats,
+            call_sites,
+            namespaces,
             evaluation_data_over_general_purpose_columns,
             evaluation_data_over_specialized_columns,
             public_inputs,
+            blinding_rows,
             placement_strategies,
         }
     }
@@ -278,6 +294,7 @@ impl<
         );
 
         self.public_inputs = hint.public_inputs.clone();
+        self.blinding_rows = hint.blinding_rows.clone();
         self.max_trace_len = hint.final_trace_len;
 
         let new = self.into_assembly();
@@ -1,18 +1,206 @@
 use self::traits::GoodAllocator;
 
+use super::call_sites::{CallSite, CallSitesTracker};
+use super::polynomial::{LagrangeForm, Polynomial};
 use super::reference_cs::CSReferenceAssembly;
+use super::reference_cs::INITIAL_LOOKUP_TABLE_ID_VALUE;
 use super::*;
 
-use crate::config::DevCSConfig;
+use crate::config::*;
 
 use crate::cs::implementations::polynomial_storage::SatisfiabilityCheckRowView;
+use crate::cs::implementations::setup::materialize_x_by_non_residue_polys;
 use crate::cs::traits::evaluator::GatePlacementType;
 use crate::cs::traits::gate::GatePlacementStrategy;
+use std::alloc::Global;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
 
 type RCFG = <DevCSConfig as CSConfig>::ResolverConfig;
 
-impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A> {
+/// Part of the constraint system where an unsatisfied constraint was found
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnsatisfiedConstraintKind {
+    GeneralPurposeColumns,
+    SpecializedColumns,
+    CopyPermutation,
+    Lookup,
+}
+
+#[derive(Clone, Debug)]
+pub struct UnsatisfiedConstraint<F: SmallField> {
+    pub kind: UnsatisfiedConstraintKind,
+    pub row: usize,
+    pub gate_debug_name: String,
+    /// Index of the gate instance on the row for gates placed multiple times per row,
+    /// index of the repetition for specialized columns and lookups, and the column index
+    /// for copy-permutation
+    pub instance_idx: usize,
+    pub term_idx: usize,
+    /// Value of the violated term. For copy-permutation it's the difference between
+    /// the value in the cell and the value of the variable, and for lookups it's `None`
+    pub term_value: Option<F>,
+    /// Variables and witnesses used by the failed instance, with their values
+    pub places: Vec<(Place, F)>,
+    /// Where the failed gate instance was placed. Only available if CS was configured
+    /// to track call sites, and not available for lookups
+    pub call_site: Option<CallSite>,
+    /// Where every entry of `places` was allocated, if CS was configured to track call sites
+    pub places_call_sites: Vec<Option<CallSite>>,
+}
+
+impl<F: SmallField> std::fmt::Display for UnsatisfiedConstraint<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            UnsatisfiedConstraintKind::GeneralPurposeColumns
+            | UnsatisfiedConstraintKind::SpecializedColumns => write!(
+                f,
+                "Unsatisfied at row {} with value {} for term number {} for subinstance number {} of gate {} over {:?}",
+                self.row,
+                self.term_value.unwrap_or(F::ZERO),
+                self.term_idx,
+                self.instance_idx,
+                &self.gate_debug_name,
+                self.kind,
+            )?,
+            UnsatisfiedConstraintKind::CopyPermutation => write!(
+                f,
+                "Copy-permutation is broken at row {} in column {} used by gate {}",
+                self.row, self.instance_idx, &self.gate_debug_name,
+            )?,
+            UnsatisfiedConstraintKind::Lookup => write!(
+                f,
+                "Lookup entry at row {} for subargument {} is not in the tables",
+                self.row, self.instance_idx,
+            )?,
+        }
+
+        if let Some(call_site) = self.call_site.as_ref() {
+            write!(f, ", placed at {}", call_site)?;
+        }
+
+        for (idx, (place, value)) in self.places.iter().enumerate() {
+            write!(f, "\n{:?} = {}", place, value)?;
+            if let Some(Some(call_site)) = self.places_call_sites.get(idx) {
+                write!(f, ", allocated at {}", call_site)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn collect_places<F: SmallField>(
+    copy_permutation_data: &[Vec<Variable>],
+    witness_placement_data: &[Vec<Witness>],
+    view: &SatisfiabilityCheckRowView<F>,
+    variables_range: Range<usize>,
+    witnesses_range: Range<usize>,
+    row: usize,
+) -> Vec<(Place, F)> {
+    let mut result = vec![];
+    for column in variables_range {
+        if let Some(variable) = copy_permutation_data[column].get(row).copied() {
+            if variable.is_placeholder() == false {
+                let value = view.variables[column].storage[row];
+                result.push((Place::from_variable(variable), value));
+            }
+        }
+    }
+
+    for column in witnesses_range {
+        if let Some(witness) = witness_placement_data[column].get(row).copied() {
+            if witness.is_placeholder() == false {
+                let value = view.witness[column].storage[row];
+                result.push((Place::from_witness(witness), value));
+            }
+        }
+    }
+
+    result
+}
+
+fn collect_places_call_sites<F: SmallField>(
+    call_sites: &CallSitesTracker,
+    places: &[(Place, F)],
+) -> Vec<Option<CallSite>> {
+    places
+        .iter()
+        .map(|(place, _)| call_sites.place_call_site(*place).cloned())
+        .collect()
+}
+
+impl<F: SmallField, CFG: CSConfig, A: GoodAllocator> CSReferenceAssembly<F, F, CFG, A> {
+    /// Walks the permutation cycles defined by setup sigmas and returns `(column, row, difference)`
+    /// for every cell of `variables` whose value differs from the value in the cell it's sigma
+    /// points to
+    pub(crate) fn find_broken_copy_constraints(
+        &self,
+        worker: &Worker,
+        variables: &[Arc<Polynomial<F, LagrangeForm, Global>>],
+    ) -> Vec<(usize, usize, F)> {
+        if variables.is_empty() {
+            return vec![];
+        }
+
+        let sigmas = self.create_permutation_polys(worker, &mut ());
+        debug_assert_eq!(sigmas.len(), variables.len());
+        // identity permutation is x * non_residue for every column, so it uniquely names a cell
+        let identities = materialize_x_by_non_residue_polys::<F, F>(
+            variables.len(),
+            self.max_trace_len,
+            worker,
+            &mut (),
+        );
+        let mut cells = HashMap::with_capacity(variables.len() * self.max_trace_len);
+        for (column, poly) in identities.iter().enumerate() {
+            for (row, el) in poly.storage.iter().enumerate() {
+                cells.insert(el.as_u64_reduced(), (column, row));
+            }
+        }
+
+        let mut result = vec![];
+        for (column, (sigma, poly)) in sigmas.iter().zip(variables.iter()).enumerate() {
+            for (row, (sigma, value)) in sigma.storage.iter().zip(poly.storage.iter()).enumerate() {
+                let (next_column, next_row) = cells[&sigma.as_u64_reduced()];
+                let next_value = variables[next_column].storage[next_row];
+                if *value != next_value {
+                    let mut difference = *value;
+                    difference.sub_assign(&next_value);
+                    result.push((column, row, difference));
+                }
+            }
+        }
+
+        result
+    }
+
     pub fn check_if_satisfied(&mut self, worker: &Worker) -> bool {
+        let unsatisfied = self.check_if_satisfied_detailed(worker);
+        for el in unsatisfied.iter() {
+            log!("{}", el);
+        }
+
+        unsatisfied.is_empty()
+    }
+
+    /// Checks every gate over general purpose and specialized columns, copy-permutation
+    /// and lookups, and returns all constraints that do not hold. If CS was configured
+    /// with `CSDebugConfig::TRACK_CALL_SITES` then failures are attributed to source locations
+    pub fn check_if_satisfied_detailed(
+        &mut self,
+        worker: &Worker,
+    ) -> Vec<UnsatisfiedConstraint<F>> {
+        assert!(
+            CFG::SetupConfig::KEEP_SETUP,
+            "CS is not configured to keep setup to know variables placement"
+        );
+        assert!(
+            CFG::WitnessConfig::EVALUATE_WITNESS,
+            "CS is not configured to have witness available"
+        );
+
         let (constants, selectors_placement, _) = self.create_constant_setup_polys(worker);
         let (_deg, num_constants_for_general_purpose_columns) = selectors_placement.compute_stats();
         log!("Constants are ready");
@@ -21,6 +209,8 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
         let witness = self.materialize_witness_polynomials(worker);
         log!("Witnesses are ready");
 
+        let mut result = vec![];
+
         let view = SatisfiabilityCheckRowView::from_storages(variables, witness, constants);
         let mut view_over_general_purpose_columns = view.subset(
             0..self.parameters.num_columns_under_copy_permutation,
@@ -65,93 +255,59 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
                 view_over_general_purpose_columns.advance_manually();
                 continue;
             }
-            let gate_debug_name = &evaluator.debug_name;
-            let num_constants_used = evaluator.num_required_constants;
             let mut this_view = view_over_general_purpose_columns.clone();
 
-            let placement = evaluator.placement_type;
             let evaluation_fn = &**evaluator
                 .rowwise_satisfiability_function
                 .as_ref()
                 .expect("must exist");
-            match placement {
-                GatePlacementType::UniqueOnRow => {
-                    evaluation_fn.evaluate_over_general_purpose_columns(
-                        &mut this_view,
-                        &mut dst,
-                        constants_placement_offset,
-                        &mut (),
-                    );
-
-                    for (idx, term) in dst.iter().enumerate() {
-                        if term.is_zero() == false {
-                            panic!(
-                                "Unsatisfied at row {} with value {} for term number {} for unique instance of gate {}",
-                                row, term, idx, gate_debug_name
-                            );
-                        }
-                    }
-                }
-                GatePlacementType::MultipleOnRow { per_chunk_offset } => {
-                    let mut t_view = view_over_general_purpose_columns.clone();
-                    evaluation_fn.evaluate_over_general_purpose_columns(
-                        &mut this_view,
-                        &mut dst,
-                        constants_placement_offset,
-                        &mut (),
-                    );
-
-                    for (chunk_idx, terms) in dst.chunks(num_terms).enumerate() {
-                        for (idx, term) in terms.iter().enumerate() {
-                            if term.is_zero() == false {
-                                let mut vars = vec![];
-                                let mut wits = vec![];
-                                let mut constants = vec![];
-                                let start_variables = per_chunk_offset.variables_offset * chunk_idx;
-                                let start_witnesses = per_chunk_offset.witnesses_offset * chunk_idx;
-
-                                for i in 0..per_chunk_offset.variables_offset {
-                                    vars.push(t_view.get_variable_value(start_variables + i));
-                                }
-
-                                for i in 0..per_chunk_offset.witnesses_offset {
-                                    wits.push(t_view.get_witness_value(start_witnesses + i));
-                                }
-
-                                t_view.constants_offset = constants_placement_offset;
-
-                                for i in 0..num_constants_used {
-                                    constants.push(t_view.get_constant_value(i));
-                                }
-
-                                dbg!(vars);
-                                dbg!(wits);
-                                dbg!(&constants);
-
-                                let mut vars = vec![];
-                                let mut wits = vec![];
-                                let start_variables = per_chunk_offset.variables_offset * chunk_idx;
-                                let start_witnesses = per_chunk_offset.witnesses_offset * chunk_idx;
-
-                                for i in 0..per_chunk_offset.variables_offset {
-                                    vars.push(self.copy_permutation_data[start_variables + i][row]);
-                                }
-
-                                for i in 0..per_chunk_offset.witnesses_offset {
-                                    wits.push(
-                                        self.witness_placement_data[start_witnesses + i][row],
-                                    );
-                                }
-
-                                dbg!(vars);
-                                dbg!(wits);
-
-                                panic!(
-                                    "Unsatisfied at row {} with value {} for term number {} for subinstance number {} of gate {}",
-                                    row, term, idx, chunk_idx, gate_debug_name
-                                );
-                            }
-                        }
+            evaluation_fn.evaluate_over_general_purpose_columns(
+                &mut this_view,
+                &mut dst,
+                constants_placement_offset,
+                &mut (),
+            );
+
+            // unique instance may use any column in the row
+            let (variables_per_instance, witnesses_per_instance) = match evaluator.placement_type {
+                GatePlacementType::UniqueOnRow => (
+                    self.parameters.num_columns_under_copy_permutation,
+                    self.parameters.num_witness_columns,
+                ),
+                GatePlacementType::MultipleOnRow { per_chunk_offset } => (
+                    per_chunk_offset.variables_offset,
+                    per_chunk_offset.witnesses_offset,
+                ),
+            };
+
+            for (instance_idx, terms) in dst.chunks(num_terms).enumerate() {
+                for (term_idx, term) in terms.iter().enumerate() {
+                    if term.is_zero() == false {
+                        let start_variables = variables_per_instance * instance_idx;
+                        let start_witnesses = witnesses_per_instance * instance_idx;
+                        let places = collect_places(
+                            &self.copy_permutation_data,
+                            &self.witness_placement_data,
+                            &view,
+                            start_variables..(start_variables + variables_per_instance),
+                            start_witnesses..(start_witnesses + witnesses_per_instance),
+                            row,
+                        );
+
+                        let places_call_sites =
+                            collect_places_call_sites(&self.call_sites, &places);
+
+                        result.push(UnsatisfiedConstraint {
+                            kind: UnsatisfiedConstraintKind::GeneralPurposeColumns,
+                            row,
+                            gate_debug_name: evaluator.debug_name.clone(),
+                            instance_idx,
+                            term_idx,
+                            term_value: Some(*term),
+                            places,
+                            call_site: self.call_sites.general_purpose_row_call_site(row).cloned(),
+                            places_call_sites,
+                        });
                     }
                 }
             }
@@ -159,6 +315,9 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
             view_over_general_purpose_columns.advance_manually();
         }
 
+        // ranges of variable columns per specialized gate, to attribute copy-permutation failures
+        let mut specialized_variables_ranges = vec![];
+
         // now specialized rows
         {
             // we expect our gates to be narrow, so we do not need to buffer row, and instead
@@ -175,6 +334,7 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
             > = vec![];
             let mut views = vec![];
             let mut evaluator_names = vec![];
+            let mut gate_type_ids = vec![];
 
             for (idx, (gate_type_id, evaluator)) in self
                 .evaluation_data_over_specialized_columns
@@ -187,21 +347,6 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
                 )
                 .enumerate()
             {
-                use crate::cs::gates::lookup_marker::LookupFormalGate;
-                if gate_type_id == &std::any::TypeId::of::<LookupFormalGate>() {
-                    continue;
-                }
-                assert!(
-                    evaluator.total_quotient_terms_over_all_repetitions != 0,
-                    "evaluator {} has not contribution to quotient",
-                    &evaluator.debug_name,
-                );
-                log!(
-                    "Will be evaluating {} over specialized columns",
-                    &evaluator.debug_name
-                );
-
-                let num_terms = evaluator.num_quotient_terms;
                 let placement_strategy = self
                     .placement_strategies
                     .get(gate_type_id)
@@ -215,12 +360,37 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
                     unreachable!();
                 };
 
-                let total_terms = num_terms * num_repetitions;
-
                 let (initial_offset, per_repetition_offset, total_constants_available) = self
                     .evaluation_data_over_specialized_columns
                     .offsets_for_specialized_evaluators[idx];
 
+                let mut final_offset = initial_offset;
+                for _ in 0..num_repetitions {
+                    final_offset.add_offset(&per_repetition_offset);
+                }
+
+                specialized_variables_ranges.push((
+                    initial_offset.variables_offset..final_offset.variables_offset,
+                    &evaluator.debug_name,
+                ));
+
+                use crate::cs::gates::lookup_marker::LookupFormalGate;
+                if gate_type_id == &std::any::TypeId::of::<LookupFormalGate>() {
+                    continue;
+                }
+                assert!(
+                    evaluator.total_quotient_terms_over_all_repetitions != 0,
+                    "evaluator {} has not contribution to quotient",
+                    &evaluator.debug_name,
+                );
+                log!(
+                    "Will be evaluating {} over specialized columns",
+                    &evaluator.debug_name
+                );
+
+                let num_terms = evaluator.num_quotient_terms;
+                let total_terms = num_terms * num_repetitions;
+
                 let placement_data = (
                     num_repetitions,
                     share_constants,
@@ -237,23 +407,10 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
                     .expect("must be properly configured");
                 evaluation_functions.push(&**t);
 
-                let (
-                    num_repetitions,
-                    share_constants,
-                    initial_offset,
-                    per_repetition_offset,
-                    _total_constants_available,
-                    _total_terms,
-                ) = placement_data;
-
                 // we self-check again
                 if share_constants {
                     assert_eq!(per_repetition_offset.constants_offset, 0);
                 }
-                let mut final_offset = initial_offset;
-                for _ in 0..num_repetitions {
-                    final_offset.add_offset(&per_repetition_offset);
-                }
 
                 let source = view.subset(
                     initial_offset.variables_offset..final_offset.variables_offset,
@@ -266,21 +423,23 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
                 views.push(source);
 
                 evaluator_names.push(&evaluator.debug_name);
+                gate_type_ids.push(*gate_type_id);
             }
 
             for row in 0..self.max_trace_len {
-                for (((placement_data, evaluation_fn), source), evaluator_name) in
+                for ((((placement_data, evaluation_fn), source), evaluator_name), gate_type_id) in
                     specialized_placement_data
                         .iter()
                         .zip(evaluation_functions.iter())
                         .zip(views.iter_mut())
                         .zip(evaluator_names.iter())
+                        .zip(gate_type_ids.iter())
                 {
                     let (
                         num_repetitions,
                         _share_constants,
-                        _initial_offset,
-                        _per_repetition_offset,
+                        initial_offset,
+                        per_repetition_offset,
                         _total_constants_available,
                         total_terms,
                     ) = *placement_data;
@@ -290,65 +449,188 @@ impl<F: SmallField, A: GoodAllocator> CSReferenceAssembly<F, F, DevCSConfig, A>
                     dst.clear();
 
                     evaluation_fn.evaluate_over_columns(source, &mut dst, &mut ());
-                    for (chunk_idx, terms) in dst.chunks(num_terms).enumerate() {
-                        for (idx, term) in terms.iter().enumerate() {
+                    for (repetition_idx, terms) in dst.chunks(num_terms).enumerate() {
+                        for (term_idx, term) in terms.iter().enumerate() {
                             if term.is_zero() == false {
-                                panic!(
-                                "Unsatisfied at row {} with value {} for term number {} for subinstance number {} of gate {}",
-                                row, term, idx, chunk_idx, evaluator_name
-                            );
-
-                                // let mut vars = vec![];
-                                // let mut wits = vec![];
-                                // let mut constants = vec![];
-                                // let start_variables = per_chunk_offset.variables_offset * chunk_idx;
-                                // let start_witnesses = per_chunk_offset.witnesses_offset * chunk_idx;
-
-                                // for i in 0..per_chunk_offset.variables_offset {
-                                //     vars.push(t_view.get_variable_value(start_variables + i));
-                                // }
+                                let start_variables = initial_offset.variables_offset
+                                    + per_repetition_offset.variables_offset * repetition_idx;
+                                let start_witnesses = initial_offset.witnesses_offset
+                                    + per_repetition_offset.witnesses_offset * repetition_idx;
+                                let places = collect_places(
+                                    &self.copy_permutation_data,
+                                    &self.witness_placement_data,
+                                    &view,
+                                    start_variables
+                                        ..(start_variables
+                                            + per_repetition_offset.variables_offset),
+                                    start_witnesses
+                                        ..(start_witnesses
+                                            + per_repetition_offset.witnesses_offset),
+                                    row,
+                                );
 
-                                // for i in 0..per_chunk_offset.witnesses_offset {
-                                //     wits.push(t_view.get_witness_value(start_witnesses + i));
-                                // }
+                                let places_call_sites =
+                                    collect_places_call_sites(&self.call_sites, &places);
+
+                                result.push(UnsatisfiedConstraint {
+                                    kind: UnsatisfiedConstraintKind::SpecializedColumns,
+                                    row,
+                                    gate_debug_name: (*evaluator_name).clone(),
+                                    instance_idx: repetition_idx,
+                                    term_idx,
+                                    term_value: Some(*term),
+                                    places,
+                                    call_site: self
+                                        .call_sites
+                                        .specialized_instance_call_site(
+                                            *gate_type_id,
+                                            repetition_idx,
+                                            row,
+                                        )
+                                        .cloned(),
+                                    places_call_sites,
+                                });
+                            }
+                        }
+                    }
 
-                                // for i in 0..self.parameters.num_constant_columns {
-                                //     constants.push(t_view.get_constant_value(i));
-                                // }
+                    source.advance_manually();
+                }
+            }
+        }
 
-                                // dbg!(vars);
-                                // dbg!(wits);
-                                // dbg!(&constants[constants_placement_offset..]);
+        // copy-permutation: every cell must hold the same value as the cell
+        // it's sigma points to, so all cells of one cycle are equal
+        for (column, row, difference) in self.find_broken_copy_constraints(worker, &view.variables)
+        {
+            let variable = self.copy_permutation_data[column][row];
+            let value = view.variables[column].storage[row];
+
+            let (gate_debug_name, call_site) =
+                if column < self.parameters.num_columns_under_copy_permutation {
+                    let name = self.gates_application_sets.get(row).map(|gate_idx| {
+                        &self
+                            .evaluation_data_over_general_purpose_columns
+                            .evaluators_over_general_purpose_columns[*gate_idx]
+                            .debug_name
+                    });
+
+                    (name, self.call_sites.general_purpose_row_call_site(row))
+                } else {
+                    let name = specialized_variables_ranges
+                        .iter()
+                        .find(|(range, _)| range.contains(&column))
+                        .map(|(_, name)| *name);
 
-                                // let mut vars = vec![];
-                                // let mut wits = vec![];
-                                // let start_variables = per_chunk_offset.variables_offset * chunk_idx;
-                                // let start_witnesses = per_chunk_offset.witnesses_offset * chunk_idx;
+                    (name, None)
+                };
 
-                                // for i in 0..per_chunk_offset.variables_offset {
-                                //     vars.push(self.copy_permutation_data[start_variables + i][row]);
-                                // }
+            result.push(UnsatisfiedConstraint {
+                kind: UnsatisfiedConstraintKind::CopyPermutation,
+                row,
+                gate_debug_name: gate_debug_name.cloned().unwrap_or_default(),
+                instance_idx: column,
+                term_idx: 0,
+                term_value: Some(difference),
+                places: vec![(Place::from_variable(variable), value)],
+                call_site: call_site.cloned(),
+                places_call_sites: vec![self
+                    .call_sites
+                    .place_call_site(Place::from_variable(variable))
+                    .cloned()],
+            });
+        }
 
-                                // for i in 0..per_chunk_offset.witnesses_offset {
-                                //     wits.push(self.witness_placement_data[start_variables + i][row]);
-                                // }
+        // lookups. Placement over general purpose columns is not supported by the prover,
+        // so we only check the specialized one
+        match self.lookup_parameters {
+            LookupParameters::UseSpecializedColumnsWithTableIdAsVariable {
+                width,
+                num_repetitions,
+                ..
+            }
+            | LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width,
+                num_repetitions,
+                ..
+            } => {
+                let width = width as usize;
+                let table_id_in_constant = matches!(
+                    self.lookup_parameters,
+                    LookupParameters::UseSpecializedColumnsWithTableIdAsConstant { .. }
+                );
+                let num_variables_per_subargument = if table_id_in_constant {
+                    width
+                } else {
+                    width + 1
+                };
 
-                                // dbg!(vars);
-                                // dbg!(wits);
+                let (initial_offset, per_repetition_offset, _) = self
+                    .evaluation_data_over_specialized_columns
+                    .offsets_for_specialized_evaluators[0];
+                let lookup_gate_debug_name = &self
+                    .evaluation_data_over_specialized_columns
+                    .evaluators_over_specialized_columns[0]
+                    .debug_name;
+                let table_id_constant_column =
+                    num_constants_for_general_purpose_columns + initial_offset.constants_offset;
+
+                let mut entry = Vec::with_capacity(width);
+                for row in 0..self.max_trace_len {
+                    for repetition_idx in 0..num_repetitions {
+                        let start = initial_offset.variables_offset
+                            + per_repetition_offset.variables_offset * repetition_idx;
+                        entry.clear();
+                        for column in start..(start + width) {
+                            entry.push(view.variables[column].storage[row]);
+                        }
+                        let table_id = if table_id_in_constant {
+                            view.constants[table_id_constant_column].storage[row]
+                        } else {
+                            view.variables[start + width].storage[row]
+                        };
+                        let table_id = table_id.as_u64_reduced();
+
+                        let is_valid = table_id >= INITIAL_LOOKUP_TABLE_ID_VALUE as u64
+                            && table_id
+                                < INITIAL_LOOKUP_TABLE_ID_VALUE as u64
+                                    + self.lookup_tables.len() as u64
+                            && self.lookup_tables
+                                [(table_id - INITIAL_LOOKUP_TABLE_ID_VALUE as u64) as usize]
+                                .try_lookup_row(&entry)
+                                .is_some();
+
+                        if is_valid == false {
+                            let places = collect_places(
+                                &self.copy_permutation_data,
+                                &self.witness_placement_data,
+                                &view,
+                                start..(start + num_variables_per_subargument),
+                                0..0,
+                                row,
+                            );
 
-                                // panic!(
-                                //     "Unsatisfied at row {} with value {} for term number {} for subinstance number {} of gate {}",
-                                //     row, term, idx, chunk_idx, gate_debug_name
-                                // );
-                            }
+                            let places_call_sites =
+                                collect_places_call_sites(&self.call_sites, &places);
+
+                            result.push(UnsatisfiedConstraint {
+                                kind: UnsatisfiedConstraintKind::Lookup,
+                                row,
+                                gate_debug_name: lookup_gate_debug_name.clone(),
+                                instance_idx: repetition_idx,
+                                term_idx: 0,
+                                term_value: None,
+                                places,
+                                call_site: None,
+                                places_call_sites,
+                            });
                         }
                     }
-
-                    source.advance_manually();
                 }
             }
+            _ => {}
         }
 
-        true
+        result
     }
 }
@@ -1,5 +1,6 @@
 use self::traits::GoodAllocator;
 
+use super::call_sites::CallSite;
 use super::hints::{DenseVariablesCopyHint, DenseWitnessCopyHint};
 use super::polynomial_storage::{SetupBaseStorage, SetupStorage};
 use super::utils::*;
@@ -15,13 +16,14 @@ use crate::cs::oracle::merkle_tree::MerkleTreeWithCap;
 use crate::cs::oracle::TreeHasher;
 use crate::cs::toolboxes::gate_config::GateConfigurationHolder;
 use crate::cs::toolboxes::static_toolbox::StaticToolboxHolder;
+use crate::cs::traits::cs::ConstraintSystem;
 use crate::dag::CircuitResolver;
 use crate::utils::*;
 use std::alloc::Global;
 use std::collections::HashSet;
 use std::sync::Arc;
 
-fn materialize_x_by_non_residue_polys<
+pub(crate) fn materialize_x_by_non_residue_polys<
     F: SmallField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
 >(
@@ -85,6 +87,8 @@ pub struct FinalizationHintsForProver {
     pub nop_gates_to_add: usize,
     pub final_trace_len: usize,
     pub public_inputs: Vec<(usize, usize)>,
+    #[serde(default)]
+    pub blinding_rows: std::ops::Range<usize>,
 }
 
 impl<
@@ -96,6 +100,40 @@ impl<
         CR: CircuitResolver<F, CFG::ResolverConfig>,
     > CSReferenceImplementation<F, P, CFG, GC, T, CR>
 {
+    /// Reserves `num_rows` rows of `NopGate`s that are used to blind the witness when proving
+    /// in zero-knowledge mode. Every copiable cell in such rows is paired with the cell in the same
+    /// column of the next row by a fresh variable, so that the copy-permutation grand product
+    /// is also randomized over them. Values are zeroes, and are only replaced by random ones
+    /// by the prover. See `ProofConfig::num_blinding_rows` for the number of rows required
+    pub fn add_blinding_rows(&mut self, num_rows: usize) {
+        assert!(num_rows % 2 == 0, "blinding rows are paired");
+        assert!(
+            self.blinding_rows.is_empty(),
+            "blinding rows are already reserved"
+        );
+
+        let first_row = self.next_available_row;
+        for _ in 0..(num_rows / 2) {
+            let variables: Vec<_> = (0..self.parameters.num_columns_under_copy_permutation)
+                .map(|_| self.alloc_single_variable_from_witness(F::ZERO))
+                .collect();
+
+            if CFG::SetupConfig::KEEP_SETUP == false {
+                continue;
+            }
+
+            for _ in 0..2 {
+                let row = self.next_available_row;
+                NopGate::new().add_to_cs(self);
+                for (column, variable) in variables.iter().copied().enumerate() {
+                    self.place_variable(variable, row, column);
+                }
+            }
+        }
+
+        self.blinding_rows = first_row..self.next_available_row;
+    }
+
     pub fn pad_and_shrink(&mut self) -> (usize, FinalizationHintsForProver) {
         // first we pad-cleanup all the gates
         assert!(
@@ -105,6 +143,7 @@ impl<
 
         let mut finalization_hints = FinalizationHintsForProver {
             public_inputs: self.public_inputs.clone(),
+            blinding_rows: self.blinding_rows.clone(),
             ..Default::default()
         };
 
@@ -356,6 +395,7 @@ impl<
         // first we pad-cleanup all the gates
 
         self.public_inputs = hint.public_inputs.clone();
+        self.blinding_rows = hint.blinding_rows.clone();
 
         let preliminary_required_size = hint.final_trace_len;
 
@@ -1322,6 +1362,70 @@ impl<
                 &evaluator.debug_name
             );
         }
+
+        if CFG::DebugConfig::TRACK_CALL_SITES {
+            self.print_gate_stats_per_call_site();
+        }
+
+        if self.namespaces.nodes.len() > 1 {
+            log!("Resources used per namespace:");
+            self.namespaces.print_tree();
+        }
+    }
+
+    fn print_gate_stats_per_call_site(&self) {
+        let mut general_purpose_stats: HashMap<(usize, &CallSite), usize> = HashMap::new();
+        for (gate_idx, call_site) in self
+            .gates_application_sets
+            .iter()
+            .zip(self.call_sites.general_purpose_rows.iter())
+        {
+            if let Some(call_site) = call_site.as_ref() {
+                *general_purpose_stats
+                    .entry((*gate_idx, call_site))
+                    .or_default() += 1;
+            }
+        }
+
+        let mut general_purpose_stats: Vec<_> = general_purpose_stats.into_iter().collect();
+        general_purpose_stats.sort_by(|a, b| b.1.cmp(&a.1));
+        for ((gate_idx, call_site), num_rows) in general_purpose_stats.into_iter() {
+            let gate = &self
+                .evaluation_data_over_general_purpose_columns
+                .evaluators_over_general_purpose_columns[gate_idx];
+            log!(
+                "{} general purpose rows of {} gate placed at {}",
+                num_rows,
+                &gate.debug_name,
+                call_site
+            );
+        }
+
+        let mut specialized_stats: HashMap<(std::any::TypeId, &CallSite), usize> = HashMap::new();
+        for ((gate_type_id, _row, _repetition), call_site) in
+            self.call_sites.specialized_instances.iter()
+        {
+            *specialized_stats
+                .entry((*gate_type_id, call_site))
+                .or_default() += 1;
+        }
+
+        let mut specialized_stats: Vec<_> = specialized_stats.into_iter().collect();
+        specialized_stats.sort_by(|a, b| b.1.cmp(&a.1));
+        for ((gate_type_id, call_site), num_instances) in specialized_stats.into_iter() {
+            let evaluator_idx = self
+                .evaluation_data_over_specialized_columns
+                .gate_type_id_into_evaluator_index_over_specialized_columns[&gate_type_id];
+            let evaluator = &self
+                .evaluation_data_over_specialized_columns
+                .evaluators_over_specialized_columns[evaluator_idx];
+            log!(
+                "{} instances of specialized {} gate placed at {}",
+                num_instances,
+                &evaluator.debug_name,
+                call_site
+            );
+        }
     }
 }
 
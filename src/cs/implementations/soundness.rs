@@ -0,0 +1,603 @@
+use super::proof_layout::ProofLayout;
+use super::prover::{compute_fri_schedule_for_config, FriScheduleError, ProofConfig};
+use super::verifier::{OpeningPoint, VerificationKeyCircuitGeometry, Verifier};
+use super::*;
+
+use crate::field::FieldExtension;
+
+// Soundness estimates for the IOP as implemented by the prover. All the numbers are in bits,
+// so the error probability of every component is 2^{-bits}, and components are combined by the
+// union bound. Challenges are drawn from the extension of degree N.
+//
+// Conjectured model follows the ethSTARK conjecture: FRI list size is 1, every query gives
+// log2(1/rate) bits, and commit phase errors are |D|/|K| per folded codeword.
+//
+// Proven model is the Johnson bound regime of the BCIKS20 correlated agreement theorem, as
+// summarized in the ethSTARK documentation and in "A summary on the FRI low degree test"
+// by U. Haböck: for a proximity parameter m >= 3 every query gives
+// -log2(sqrt(rate) * (1 + 1/2m)) bits, and the list size is (m + 1/2) / sqrt(rate).
+
+const MIN_JOHNSON_PROXIMITY_PARAMETER: usize = 3;
+const MAX_JOHNSON_PROXIMITY_PARAMETER: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SoundnessModel {
+    Proven,
+    Conjectured,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SoundnessError {
+    InvalidLdeFactor(usize),
+    InvalidDomainSize(u64),
+    FriSchedule(FriScheduleError),
+}
+
+impl std::fmt::Display for SoundnessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLdeFactor(lde_factor) => write!(
+                f,
+                "FRI LDE factor must be a power of two larger than 1, got {}",
+                lde_factor
+            ),
+            Self::InvalidDomainSize(domain_size) => {
+                write!(f, "domain size must be a power of two, got {}", domain_size)
+            }
+            Self::FriSchedule(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SoundnessError {}
+
+impl From<FriScheduleError> for SoundnessError {
+    fn from(value: FriScheduleError) -> Self {
+        Self::FriSchedule(value)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoundnessBreakdown {
+    // random linear combination of all the committed polynomials into the FRI oracle
+    pub fri_batching: f64,
+    pub fri_commit_phase: f64,
+    // includes grinding
+    pub fri_query_phase: f64,
+    pub deep_ali: f64,
+    pub copy_permutation: f64,
+    pub lookup: f64,
+    pub total: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecurityReport {
+    pub extension_field_bits: f64,
+    pub num_queries: usize,
+    // PoW bits as they will be used by the prover, that may be less than requested
+    pub grinding_bits: u32,
+    pub folding_schedule: Vec<usize>,
+    // number of openings that are batched into the FRI oracle
+    pub num_batched_polys: usize,
+    // proximity parameter that gave the best proven estimate
+    pub johnson_proximity_parameter: usize,
+    pub proven: SoundnessBreakdown,
+    pub conjectured: SoundnessBreakdown,
+}
+
+impl SecurityReport {
+    /// Estimates the soundness of proving a circuit with the given verifier and geometry under
+    /// the configuration. Numbers of batched openings and constraint terms are exact, as the
+    /// verifier knows every gate. Returns an error if the configuration can not be used with the geometry
+    pub fn new<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+        verifier: &Verifier<F, EXT, N>,
+        geometry: &VerificationKeyCircuitGeometry,
+        proof_config: &ProofConfig,
+    ) -> Result<Self, SoundnessError> {
+        if !proof_config.fri_lde_factor.is_power_of_two() || proof_config.fri_lde_factor == 1 {
+            return Err(SoundnessError::InvalidLdeFactor(
+                proof_config.fri_lde_factor,
+            ));
+        }
+        if !geometry.domain_size.is_power_of_two() {
+            return Err(SoundnessError::InvalidDomainSize(geometry.domain_size));
+        }
+
+        let (grinding_bits, num_queries, folding_schedule, _final_degree) =
+            compute_fri_schedule_for_config(
+                proof_config,
+                proof_config.fri_lde_factor.trailing_zeros(),
+                geometry.domain_size.trailing_zeros(),
+            )?;
+
+        let extension_field_bits = (N as f64) * (F::CHAR as f64).log2();
+        let params = ArgumentParameters::new(verifier, geometry, proof_config);
+
+        let conjectured = params.conjectured_breakdown(
+            extension_field_bits,
+            num_queries,
+            grinding_bits,
+            &folding_schedule,
+        );
+
+        let mut best_proven = None;
+        for m in MIN_JOHNSON_PROXIMITY_PARAMETER..=MAX_JOHNSON_PROXIMITY_PARAMETER {
+            let candidate = params.proven_breakdown(
+                extension_field_bits,
+                num_queries,
+                grinding_bits,
+                &folding_schedule,
+                m,
+            );
+            match best_proven {
+                Some((_, SoundnessBreakdown { total, .. })) if total >= candidate.total => {}
+                _ => best_proven = Some((m, candidate)),
+            }
+        }
+        let (johnson_proximity_parameter, proven) = best_proven.unwrap();
+
+        Ok(Self {
+            extension_field_bits,
+            num_queries,
+            grinding_bits,
+            folding_schedule,
+            num_batched_polys: params.num_batched_polys,
+            johnson_proximity_parameter,
+            proven,
+            conjectured,
+        })
+    }
+
+    pub fn breakdown(&self, model: SoundnessModel) -> &SoundnessBreakdown {
+        match model {
+            SoundnessModel::Proven => &self.proven,
+            SoundnessModel::Conjectured => &self.conjectured,
+        }
+    }
+
+    pub fn security_bits(&self, model: SoundnessModel) -> f64 {
+        self.breakdown(model).total
+    }
+}
+
+struct ArgumentParameters {
+    trace_len: f64,
+    lde_domain_size: f64,
+    rate: f64,
+    quotient_degree: f64,
+    extension_degree: usize,
+    num_batched_polys: usize,
+    num_leaf_elements: usize,
+    num_constraint_terms: usize,
+    num_copy_permutation_polys: usize,
+    num_lookup_subarguments: usize,
+    lookup_width: usize,
+    total_tables_len: usize,
+}
+
+impl ArgumentParameters {
+    fn new<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+        verifier: &Verifier<F, EXT, N>,
+        geometry: &VerificationKeyCircuitGeometry,
+        proof_config: &ProofConfig,
+    ) -> Self {
+        let trace_len = geometry.domain_size as usize;
+
+        let num_lookup_subarguments = if verifier.lookup_parameters.lookup_is_allowed() {
+            verifier.num_sublookup_arguments()
+        } else {
+            0
+        };
+
+        // every opening (and every public input) gets it's own challenge in the DEEP batch
+        let layout = ProofLayout::new(verifier, geometry, proof_config);
+        let num_batched_polys = layout.num_openings(OpeningPoint::Z)
+            + layout.num_openings(OpeningPoint::ZOmega)
+            + layout.num_openings(OpeningPoint::Zero)
+            + geometry.public_inputs_locations.len();
+        let num_leaf_elements = verifier.witness_leaf_size(geometry)
+            + verifier.stage_2_leaf_size(geometry)
+            + verifier.quotient_leaf_size(geometry)
+            + proof_config.num_masking_polys() * N
+            + verifier.setup_leaf_size(geometry)
+            + 3 * proof_config.salt_size();
+
+        Self {
+            trace_len: trace_len as f64,
+            lde_domain_size: (trace_len * proof_config.fri_lde_factor) as f64,
+            rate: 1f64 / (proof_config.fri_lde_factor as f64),
+            quotient_degree: geometry.quotient_degree as f64,
+            extension_degree: N,
+            num_batched_polys,
+            num_leaf_elements,
+            num_constraint_terms: verifier.num_quotient_terms(geometry),
+            num_copy_permutation_polys: verifier.num_copy_permutation_polys(),
+            num_lookup_subarguments,
+            lookup_width: verifier.lookup_parameters.lookup_width(),
+            total_tables_len: geometry.total_tables_len as usize,
+        }
+    }
+
+    // arguments that don't depend on the FRI soundness model
+    fn copy_permutation_bits(&self, field_bits: f64) -> f64 {
+        // grand product identity is of degree num_polys * n in the challenges
+        field_bits - (self.num_copy_permutation_polys as f64 * self.trace_len).log2()
+    }
+
+    fn lookup_bits(&self, field_bits: f64) -> f64 {
+        if self.num_lookup_subarguments == 0 {
+            return f64::INFINITY;
+        }
+        // log-derivative identity cleared from denominators, and columns aggregation
+        let degree =
+            (self.num_lookup_subarguments as f64) * self.trace_len + (self.total_tables_len as f64);
+        let degree = degree * ((self.lookup_width + 1) as f64);
+
+        field_bits - degree.log2()
+    }
+
+    fn deep_ali_bits(&self, field_bits: f64, list_size: f64) -> f64 {
+        let ali_error = list_size * (self.num_constraint_terms as f64);
+        let deep_error = list_size * (self.quotient_degree + 1f64) * self.trace_len;
+        let field_size_minus_domain = field_bits.exp2() - self.lde_domain_size;
+
+        -((ali_error + deep_error) / field_size_minus_domain).log2()
+    }
+
+    fn conjectured_breakdown(
+        &self,
+        field_bits: f64,
+        num_queries: usize,
+        grinding_bits: u32,
+        folding_schedule: &[usize],
+    ) -> SoundnessBreakdown {
+        let domain_bits = self.lde_domain_size.log2();
+        let fri_batching = field_bits - domain_bits - (self.num_batched_polys as f64).log2();
+        let num_folding_challenges: usize = folding_schedule.iter().map(|el| 1 << el).sum();
+        let fri_commit_phase =
+            field_bits - domain_bits - (num_folding_challenges.max(1) as f64).log2();
+        let fri_query_phase = (num_queries as f64) * (-self.rate.log2()) + (grinding_bits as f64);
+
+        let mut result = SoundnessBreakdown {
+            fri_batching,
+            fri_commit_phase,
+            fri_query_phase,
+            deep_ali: self.deep_ali_bits(field_bits, 1f64),
+            copy_permutation: self.copy_permutation_bits(field_bits),
+            lookup: self.lookup_bits(field_bits),
+            total: 0f64,
+        };
+        result.total = union_bound(&result);
+
+        result
+    }
+
+    fn proven_breakdown(
+        &self,
+        field_bits: f64,
+        num_queries: usize,
+        grinding_bits: u32,
+        folding_schedule: &[usize],
+        m: usize,
+    ) -> SoundnessBreakdown {
+        let m = m as f64;
+        let sqrt_rate = self.rate.sqrt();
+        let list_size = (m + 0.5f64) / sqrt_rate;
+
+        // correlated agreement for the curve of a given degree:
+        // degree * (m + 1/2)^7 * |D|^2 / (3 * rate^{3/2} * |K|)
+        let single_curve_error_bits = 7f64 * (m + 0.5f64).log2()
+            + 2f64 * self.lde_domain_size.log2()
+            - (3f64 * self.rate.powf(1.5f64)).log2()
+            - field_bits;
+        let fri_batching =
+            -(single_curve_error_bits + ((self.num_batched_polys - 1).max(1) as f64).log2());
+        let curves_degree: usize = folding_schedule.iter().map(|el| (1 << el) - 1).sum();
+        let fri_commit_phase = -(single_curve_error_bits + (curves_degree.max(1) as f64).log2());
+
+        let per_query_agreement = sqrt_rate * (1f64 + 1f64 / (2f64 * m));
+        let fri_query_phase =
+            -(num_queries as f64) * per_query_agreement.log2() + (grinding_bits as f64);
+
+        let mut result = SoundnessBreakdown {
+            fri_batching,
+            fri_commit_phase,
+            fri_query_phase,
+            deep_ali: self.deep_ali_bits(field_bits, list_size),
+            copy_permutation: self.copy_permutation_bits(field_bits),
+            lookup: self.lookup_bits(field_bits),
+            total: 0f64,
+        };
+        result.total = union_bound(&result);
+
+        result
+    }
+
+    // rough cost of the proof in field elements, where every hash output is counted as 4 elements
+    fn estimated_proof_size(&self, num_queries: usize, folding_schedule: &[usize]) -> usize {
+        let depth = self.lde_domain_size.log2() as usize;
+        let num_base_oracles = 4;
+        let mut per_query = self.num_leaf_elements + num_base_oracles * depth * 4;
+        let mut current_depth = depth;
+        for interpolation_log2 in folding_schedule.iter() {
+            per_query += self.extension_degree * (1 << interpolation_log2) + current_depth * 4;
+            current_depth = current_depth.saturating_sub(*interpolation_log2);
+        }
+
+        num_queries * per_query
+    }
+}
+
+fn union_bound(breakdown: &SoundnessBreakdown) -> f64 {
+    let components = [
+        breakdown.fri_batching,
+        breakdown.fri_commit_phase,
+        breakdown.fri_query_phase,
+        breakdown.deep_ali,
+        breakdown.copy_permutation,
+        breakdown.lookup,
+    ];
+    let total_error: f64 = components.iter().map(|el| (-el).exp2()).sum();
+
+    -total_error.log2()
+}
+
+/// Finds the configuration with the smallest estimated proof size, that reaches the target
+/// security level in the given model. LDE factors up to `max_lde_factor` and grinding up to
+/// `max_pow_bits` are considered, and the cap size is taken from the geometry. Note that
+/// the setup depends on the LDE factor, so it must be regenerated for the resulting configuration
+pub fn find_cheapest_proof_config<
+    F: SmallField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+>(
+    verifier: &Verifier<F, EXT, N>,
+    geometry: &VerificationKeyCircuitGeometry,
+    target_security_bits: u32,
+    model: SoundnessModel,
+    max_lde_factor: usize,
+    max_pow_bits: u32,
+) -> Option<(ProofConfig, SecurityReport)> {
+    assert!(max_lde_factor.is_power_of_two());
+    assert!(geometry.cap_size.is_power_of_two());
+
+    // we only ever need as many queries as to reach the target with the worst rate
+    let max_security_level = (target_security_bits as usize) * 4;
+
+    let mut best: Option<(usize, ProofConfig, SecurityReport)> = None;
+    let mut fri_lde_factor = 2;
+    while fri_lde_factor <= max_lde_factor {
+        for pow_bits in 0..=max_pow_bits {
+            let mut security_level = (pow_bits + 1) as usize;
+            while security_level <= max_security_level {
+                let proof_config = ProofConfig {
+                    fri_lde_factor,
+                    merkle_tree_cap_size: geometry.cap_size,
+                    fri_folding_schedule: None,
+                    security_level,
+                    pow_bits,
+                    zero_knowledge: false,
+                };
+                let report = SecurityReport::new(verifier, geometry, &proof_config)
+                    .expect("default FRI schedule is always valid");
+                if report.security_bits(model) >= target_security_bits as f64 {
+                    let params = ArgumentParameters::new(verifier, geometry, &proof_config);
+                    let cost =
+                        params.estimated_proof_size(report.num_queries, &report.folding_schedule);
+                    let is_better = match best.as_ref() {
+                        Some((best_cost, best_config, _)) => {
+                            cost < *best_cost
+                                || (cost == *best_cost
+                                    && fri_lde_factor < best_config.fri_lde_factor)
+                        }
+                        None => true,
+                    };
+                    if is_better {
+                        best = Some((cost, proof_config, report));
+                    }
+                    break;
+                }
+
+                security_level += 1;
+            }
+        }
+
+        fri_lde_factor *= 2;
+    }
+
+    best.map(|(_, config, report)| (config, report))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_verifier::CsVerifierBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::proof::Proof;
+    use crate::cs::implementations::verifier::VerificationKey;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+    use crate::implementations::poseidon2::Poseidon2Goldilocks;
+
+    type F = GoldilocksField;
+    type Ext = GoldilocksExt2;
+
+    fn load_verifier_and_geometry() -> (Verifier<F, Ext>, VerificationKeyCircuitGeometry) {
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+        let mut vk_file = std::fs::File::open("vk.json").unwrap();
+        let vk: VerificationKey<F, H> = serde_json::from_reader(&mut vk_file).unwrap();
+
+        // same configuration as the one that produced the verification key
+        type Poseidon2Gate = Poseidon2FlattenedGate<F, 8, 12, 4, Poseidon2Goldilocks>;
+
+        let builder_impl =
+            CsVerifierBuilder::<F, Ext>::new_from_parameters(vk.fixed_parameters.parameters);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = builder.allow_lookup(
+            LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 8,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseSpecializedColumns {
+                num_repetitions: 1,
+                share_constants: false,
+            },
+        );
+        let builder = U8x4FMAGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = Poseidon2Gate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<8>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<_, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        (builder.build(()), vk.fixed_parameters)
+    }
+
+    #[test]
+    fn test_security_report() {
+        let (verifier, geometry) = load_verifier_and_geometry();
+        let proof_config = ProofConfig {
+            fri_lde_factor: geometry.fri_lde_factor,
+            merkle_tree_cap_size: geometry.cap_size,
+            fri_folding_schedule: None,
+            security_level: 100,
+            pow_bits: 0,
+            zero_knowledge: false,
+        };
+
+        let report = SecurityReport::new(&verifier, &geometry, &proof_config).unwrap();
+
+        assert_eq!(
+            report.num_queries,
+            100 / geometry.fri_lde_factor.trailing_zeros() as usize
+        );
+        assert!(report.proven.total <= report.conjectured.total);
+        assert!(report.conjectured.fri_query_phase >= 100f64);
+        assert!(report.proven.fri_query_phase < report.conjectured.fri_query_phase);
+        // with only 128 bits in the extension the commit phase is what limits the proven estimate
+        assert!(report.proven.total < 100f64);
+
+        // every opening of a proof for the same circuit is batched
+        type H = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+        let mut proof_file = std::fs::File::open("proof.json").unwrap();
+        let proof: Proof<F, H, Ext> = serde_json::from_reader(&mut proof_file).unwrap();
+        assert_eq!(
+            report.num_batched_polys,
+            proof.values_at_z.len()
+                + proof.values_at_z_omega.len()
+                + proof.values_at_0.len()
+                + proof.public_inputs.len()
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_reported() {
+        let (verifier, geometry) = load_verifier_and_geometry();
+        let mut proof_config = ProofConfig {
+            fri_lde_factor: 3,
+            merkle_tree_cap_size: geometry.cap_size,
+            fri_folding_schedule: None,
+            security_level: 100,
+            pow_bits: 0,
+            zero_knowledge: false,
+        };
+        assert_eq!(
+            SecurityReport::new(&verifier, &geometry, &proof_config),
+            Err(SoundnessError::InvalidLdeFactor(3))
+        );
+
+        proof_config.fri_lde_factor = geometry.fri_lde_factor;
+        proof_config.fri_folding_schedule = Some(vec![]);
+        assert_eq!(
+            SecurityReport::new(&verifier, &geometry, &proof_config),
+            Err(SoundnessError::FriSchedule(FriScheduleError::EmptySchedule))
+        );
+    }
+
+    #[test]
+    fn test_parameters_search() {
+        let (verifier, geometry) = load_verifier_and_geometry();
+
+        for model in [SoundnessModel::Conjectured, SoundnessModel::Proven] {
+            let target = match model {
+                SoundnessModel::Conjectured => 90,
+                SoundnessModel::Proven => 50,
+            };
+            let (config, report) =
+                find_cheapest_proof_config(&verifier, &geometry, target, model, 16, 20).unwrap();
+            assert!(report.security_bits(model) >= target as f64);
+            assert_eq!(
+                report,
+                SecurityReport::new(&verifier, &geometry, &config).unwrap()
+            );
+        }
+
+        // not reachable over 128 bits of the extension
+        assert!(find_cheapest_proof_config(
+            &verifier,
+            &geometry,
+            128,
+            SoundnessModel::Proven,
+            16,
+            20
+        )
+        .is_none());
+    }
+}
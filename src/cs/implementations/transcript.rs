@@ -43,8 +43,13 @@ pub trait Transcript<F: PrimeField>: Clone + Send + Sync + std::fmt::Debug {
 use crate::algebraic_props::round_function::*;
 use crate::algebraic_props::sponge::*;
 
-#[derive(Derivative)]
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone, Debug)]
+#[serde(bound = "")]
+#[serde(
+    into = "AlgebraicSpongeBasedTranscriptState<F>",
+    try_from = "AlgebraicSpongeBasedTranscriptState<F>"
+)]
 pub struct AlgebraicSpongeBasedTranscript<
     F: SmallField,
     const AW: usize,
@@ -128,6 +133,76 @@ impl<
     }
 }
 
+// Sponge is generic over the round function and absorption mode, so we only
+// serialize it's state, and restore the rest from the type
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Debug)]
+#[serde(bound = "")]
+pub struct AlgebraicSpongeBasedTranscriptState<F: SmallField> {
+    buffer: Vec<F>,
+    available_challenges: Vec<F>,
+    sponge_buffer: Vec<F>,
+    sponge_filled: usize,
+    sponge_state: Vec<F>,
+}
+
+impl<
+        F: SmallField,
+        const AW: usize,
+        const SW: usize,
+        const CW: usize,
+        R: AlgebraicRoundFunction<F, AW, SW, CW>,
+        M: AbsorptionModeTrait<F>,
+    > From<AlgebraicSpongeBasedTranscript<F, AW, SW, CW, R, M>>
+    for AlgebraicSpongeBasedTranscriptState<F>
+{
+    fn from(value: AlgebraicSpongeBasedTranscript<F, AW, SW, CW, R, M>) -> Self {
+        Self {
+            buffer: value.buffer,
+            available_challenges: value.available_challenges,
+            sponge_buffer: value.sponge.buffer.to_vec(),
+            sponge_filled: value.sponge.filled,
+            sponge_state: value.sponge.state.to_vec(),
+        }
+    }
+}
+
+impl<
+        F: SmallField,
+        const AW: usize,
+        const SW: usize,
+        const CW: usize,
+        R: AlgebraicRoundFunction<F, AW, SW, CW>,
+        M: AbsorptionModeTrait<F>,
+    > TryFrom<AlgebraicSpongeBasedTranscriptState<F>>
+    for AlgebraicSpongeBasedTranscript<F, AW, SW, CW, R, M>
+{
+    type Error = String;
+
+    fn try_from(value: AlgebraicSpongeBasedTranscriptState<F>) -> Result<Self, Self::Error> {
+        let mut sponge = SimpleAlgebraicSponge::<F, AW, SW, CW, R, M>::default();
+        sponge.buffer = value.sponge_buffer.try_into().map_err(|el: Vec<F>| {
+            format!("sponge buffer must have {} elements, got {}", AW, el.len())
+        })?;
+        sponge.state = value.sponge_state.try_into().map_err(|el: Vec<F>| {
+            format!("sponge state must have {} elements, got {}", SW, el.len())
+        })?;
+        if value.sponge_filled >= AW {
+            return Err(format!(
+                "sponge can not have {} filled elements out of {}",
+                value.sponge_filled, AW
+            ));
+        }
+        sponge.filled = value.sponge_filled;
+
+        Ok(Self {
+            buffer: value.buffer,
+            available_challenges: value.available_challenges,
+            sponge,
+        })
+    }
+}
+
 use crate::implementations::poseidon_goldilocks_naive::PoseidonGoldilocks;
 
 pub type GoldilocksPoisedonTranscript = AlgebraicSpongeBasedTranscript<
@@ -150,14 +225,63 @@ pub type GoldilocksPoisedon2Transcript = AlgebraicSpongeBasedTranscript<
     AbsorptionModeOverwrite,
 >;
 
-#[derive(Derivative)]
+use crate::field::babybear::BabyBearField;
+use crate::implementations::poseidon2_babybear::Poseidon2BabyBear;
+
+pub type BabyBearPoseidon2Transcript = AlgebraicSpongeBasedTranscript<
+    BabyBearField,
+    8,
+    16,
+    8,
+    Poseidon2BabyBear,
+    AbsorptionModeOverwrite,
+>;
+
+// Hasher state of byte oriented transcripts is always a fresh hasher that has absorbed
+// the last produced output, so it's enough to only keep that output
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ByteTranscriptState {
+    seed: Option<[u8; 32]>,
+    buffer: Vec<u8>,
+    available_challenge_bytes: Vec<u8>,
+}
+
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone, Debug)]
+#[serde(into = "ByteTranscriptState", from = "ByteTranscriptState")]
 pub struct Blake2sTranscript {
     inner: blake2::Blake2s256,
+    seed: Option<[u8; 32]>,
     buffer: Vec<u8>,
     available_challenge_bytes: Vec<u8>,
 }
 
+impl From<Blake2sTranscript> for ByteTranscriptState {
+    fn from(value: Blake2sTranscript) -> Self {
+        Self {
+            seed: value.seed,
+            buffer: value.buffer,
+            available_challenge_bytes: value.available_challenge_bytes,
+        }
+    }
+}
+
+impl From<ByteTranscriptState> for Blake2sTranscript {
+    fn from(value: ByteTranscriptState) -> Self {
+        let mut inner = blake2::Blake2s256::new();
+        if let Some(seed) = value.seed {
+            inner.update(seed);
+        }
+
+        Self {
+            inner,
+            seed: value.seed,
+            buffer: value.buffer,
+            available_challenge_bytes: value.available_challenge_bytes,
+        }
+    }
+}
+
 use blake2::Digest;
 
 impl<F: SmallField> Transcript<F> for Blake2sTranscript {
@@ -169,6 +293,7 @@ impl<F: SmallField> Transcript<F> for Blake2sTranscript {
     fn new(_params: Self::TransciptParameters) -> Self {
         Self {
             inner: blake2::Blake2s256::new(),
+            seed: None,
             buffer: Vec::with_capacity(64),
             available_challenge_bytes: Vec::with_capacity(32),
         }
@@ -195,6 +320,7 @@ impl<F: SmallField> Transcript<F> for Blake2sTranscript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
         }
 
@@ -218,6 +344,7 @@ impl<F: SmallField> Transcript<F> for Blake2sTranscript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
 
             assert!(self.available_challenge_bytes.is_empty() == false);
@@ -237,6 +364,7 @@ impl<F: SmallField> Transcript<F> for Blake2sTranscript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
         }
 
@@ -251,6 +379,7 @@ impl<F: SmallField> Transcript<F> for Blake2sTranscript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
 
             assert!(self.available_challenge_bytes.is_empty() == false);
@@ -259,14 +388,42 @@ impl<F: SmallField> Transcript<F> for Blake2sTranscript {
     }
 }
 
-#[derive(Derivative)]
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Clone, Debug)]
+#[serde(into = "ByteTranscriptState", from = "ByteTranscriptState")]
 pub struct Keccak256Transcript {
     inner: sha3::Keccak256,
+    seed: Option<[u8; 32]>,
     buffer: Vec<u8>,
     available_challenge_bytes: Vec<u8>,
 }
 
+impl From<Keccak256Transcript> for ByteTranscriptState {
+    fn from(value: Keccak256Transcript) -> Self {
+        Self {
+            seed: value.seed,
+            buffer: value.buffer,
+            available_challenge_bytes: value.available_challenge_bytes,
+        }
+    }
+}
+
+impl From<ByteTranscriptState> for Keccak256Transcript {
+    fn from(value: ByteTranscriptState) -> Self {
+        let mut inner = sha3::Keccak256::new();
+        if let Some(seed) = value.seed {
+            inner.update(seed);
+        }
+
+        Self {
+            inner,
+            seed: value.seed,
+            buffer: value.buffer,
+            available_challenge_bytes: value.available_challenge_bytes,
+        }
+    }
+}
+
 impl<F: SmallField> Transcript<F> for Keccak256Transcript {
     type CompatibleCap = [u8; 32];
     type TransciptParameters = ();
@@ -276,6 +433,7 @@ impl<F: SmallField> Transcript<F> for Keccak256Transcript {
     fn new(_params: Self::TransciptParameters) -> Self {
         Self {
             inner: sha3::Keccak256::new(),
+            seed: None,
             buffer: Vec::with_capacity(64),
             available_challenge_bytes: Vec::with_capacity(32),
         }
@@ -302,6 +460,7 @@ impl<F: SmallField> Transcript<F> for Keccak256Transcript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
         }
 
@@ -325,6 +484,7 @@ impl<F: SmallField> Transcript<F> for Keccak256Transcript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
 
             assert!(self.available_challenge_bytes.is_empty() == false);
@@ -344,6 +504,7 @@ impl<F: SmallField> Transcript<F> for Keccak256Transcript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
         }
 
@@ -358,6 +519,7 @@ impl<F: SmallField> Transcript<F> for Keccak256Transcript {
             output[..].copy_from_slice(raw_output.as_slice());
 
             self.inner.update(output);
+            self.seed = Some(output);
             self.available_challenge_bytes.extend(output);
 
             assert!(self.available_challenge_bytes.is_empty() == false);
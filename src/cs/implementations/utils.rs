@@ -473,38 +473,42 @@ pub(crate) fn batch_inverse_inplace<F: PrimeField, A: GoodAllocator>(input: &mut
 
 pub(crate) fn batch_inverse_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    input_c0: &[F],
-    input_c1: &[F],
-    into_c0: &mut Vec<F, A>,
-    into_c1: &mut Vec<F, A>,
+    input: [&[F]; N],
+    into: &mut [Vec<F, A>; N],
 ) {
-    assert!(into_c0.is_empty());
-    assert!(into_c1.is_empty());
-
-    assert_eq!(input_c0.len(), input_c1.len());
+    for (src, dst) in input.iter().zip(into.iter()) {
+        assert!(dst.is_empty());
+        assert_eq!(input[0].len(), src.len());
+    }
 
-    if input_c0.is_empty() {
+    if input[0].is_empty() {
         return;
     }
 
+    let read = |columns: &[&[F]; N], idx: usize| {
+        ExtensionField::<F, N, EXT>::from_coeff_in_base(std::array::from_fn(|i| columns[i][idx]))
+    };
+
     // we do Montgomery batch inversion trick, and reuse a buffer
     let one = {
         use crate::field::traits::field::Field;
 
-        ExtensionField::<F, 2, EXT>::ONE
+        ExtensionField::<F, N, EXT>::ONE
     };
-    into_c0.push(one.coeffs[0]);
-    into_c1.push(one.coeffs[1]);
-    let mut accumulator =
-        ExtensionField::<F, 2, EXT>::from_coeff_in_base([input_c0[0], input_c1[0]]);
-    for (el_c0, el_c1) in input_c0[1..].iter().zip(input_c1[1..].iter()) {
-        into_c0.push(accumulator.coeffs[0]);
-        into_c1.push(accumulator.coeffs[1]);
-
-        let el = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*el_c0, *el_c1]);
+    for (dst, el) in into.iter_mut().zip(one.coeffs.iter()) {
+        dst.push(*el);
+    }
+    let mut accumulator = read(&input, 0);
+    for idx in 1..input[0].len() {
+        for (dst, el) in into.iter_mut().zip(accumulator.coeffs.iter()) {
+            dst.push(*el);
+        }
+
+        let el = read(&input, idx);
         crate::field::Field::mul_assign(&mut accumulator, &el);
     }
 
@@ -518,57 +522,61 @@ pub(crate) fn batch_inverse_in_extension<
 
     // grand_inverse = a^-1 b^-1 c^-1 d^-1
 
-    for (((tmp_c0, tmp_c1), original_c0), original_c1) in into_c0
-        .iter_mut()
-        .rev()
-        .zip(into_c1.iter_mut().rev())
-        .zip(input_c0.iter().rev())
-        .zip(input_c1.iter().rev())
-    {
-        let mut tmp = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*tmp_c0, *tmp_c1]); // abc
+    for idx in (0..input[0].len()).rev() {
+        let mut tmp = ExtensionField::<F, N, EXT>::from_coeff_in_base(std::array::from_fn(|i| {
+            into[i][idx]
+        })); // abc
         crate::field::Field::mul_assign(&mut tmp, &grand_inverse); // d^-1
 
         // write back
-        *tmp_c0 = tmp.coeffs[0];
-        *tmp_c1 = tmp.coeffs[1];
+        for (dst, el) in into.iter_mut().zip(tmp.coeffs.iter()) {
+            dst[idx] = *el;
+        }
 
-        let original =
-            ExtensionField::<F, 2, EXT>::from_coeff_in_base([*original_c0, *original_c1]);
+        let original = read(&input, idx);
         crate::field::Field::mul_assign(&mut grand_inverse, &original); // e.g. it's now a^-1 b^-1 c^-1
     }
 
-    assert_eq!(input_c0.len(), into_c0.len());
-    assert_eq!(input_c1.len(), into_c1.len());
+    for (src, dst) in input.iter().zip(into.iter()) {
+        assert_eq!(src.len(), dst.len());
+    }
 }
 
 pub(crate) fn batch_inverse_inplace_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    input_c0: &mut [F],
-    input_c1: &mut [F],
+    input: [&mut [F]; N],
 ) {
-    assert_eq!(input_c0.len(), input_c1.len());
+    let len = input[0].len();
+    for el in input.iter() {
+        assert_eq!(len, el.len());
+    }
 
-    if input_c0.is_empty() {
+    if len == 0 {
         return;
     }
 
-    let mut into = Vec::with_capacity_in(input_c0.len(), A::default());
+    let mut input = input;
+    let read = |columns: &[&mut [F]; N], idx: usize| {
+        ExtensionField::<F, N, EXT>::from_coeff_in_base(std::array::from_fn(|i| columns[i][idx]))
+    };
+
+    let mut into = Vec::with_capacity_in(len, A::default());
 
     // we do Montgomery batch inversion trick, and reuse a buffer
     let one = {
         use crate::field::traits::field::Field;
 
-        ExtensionField::<F, 2, EXT>::ONE
+        ExtensionField::<F, N, EXT>::ONE
     };
     into.push(one);
-    let mut accumulator =
-        ExtensionField::<F, 2, EXT>::from_coeff_in_base([input_c0[0], input_c1[0]]);
-    for (el_c0, el_c1) in input_c0[1..].iter().zip(input_c1[1..].iter()) {
+    let mut accumulator = read(&input, 0);
+    for idx in 1..len {
         into.push(accumulator);
-        let el = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*el_c0, *el_c1]);
+        let el = read(&input, idx);
         crate::field::Field::mul_assign(&mut accumulator, &el);
     }
 
@@ -582,20 +590,15 @@ pub(crate) fn batch_inverse_inplace_in_extension<
 
     // grand_inverse = a^-1 b^-1 c^-1 d^-1
 
-    for ((tmp, original_c0), original_c1) in into
-        .into_iter()
-        .rev()
-        .zip(input_c0.iter_mut().rev())
-        .zip(input_c1.iter_mut().rev())
-    {
+    for (idx, tmp) in into.into_iter().enumerate().rev() {
         let mut tmp = tmp; // abc
         crate::field::Field::mul_assign(&mut tmp, &grand_inverse); // d^-1
-        let original =
-            ExtensionField::<F, 2, EXT>::from_coeff_in_base([*original_c0, *original_c1]);
+        let original = read(&input, idx);
         crate::field::Field::mul_assign(&mut grand_inverse, &original); // e.g. it's now a^-1 b^-1 c^-1
 
-        *original_c0 = tmp.coeffs[0];
-        *original_c1 = tmp.coeffs[1];
+        for (dst, el) in input.iter_mut().zip(tmp.coeffs.iter()) {
+            dst[idx] = *el;
+        }
     }
 }
 
@@ -614,25 +617,69 @@ pub(crate) fn batch_inverse_inplace_parallel<F: PrimeField, A: GoodAllocator>(
 
 pub(crate) fn batch_inverse_inplace_parallel_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     A: GoodAllocator,
 >(
-    input_c0: &mut [F],
-    input_c1: &mut [F],
+    input: [&mut [F]; N],
     worker: &Worker,
 ) {
-    worker.scope(input_c0.len(), |scope, chunk_size| {
-        for (dst_c0, dst_c1) in input_c0
-            .chunks_mut(chunk_size)
-            .zip(input_c1.chunks_mut(chunk_size))
-        {
+    worker.scope(input[0].len(), |scope, chunk_size| {
+        for dst in chunk_columns_mut(input, chunk_size) {
             scope.spawn(move |_| {
-                batch_inverse_inplace_in_extension::<F, EXT, A>(dst_c0, dst_c1);
+                batch_inverse_inplace_in_extension::<F, N, EXT, A>(dst);
             });
         }
     });
 }
 
+/// Splits every column of extension field elements (one column per coefficient)
+/// into chunks of the same size, and groups chunks with the same index
+pub(crate) fn chunk_columns<'a, T, const N: usize>(
+    columns: [&'a [T]; N],
+    chunk_size: usize,
+) -> impl Iterator<Item = [&'a [T]; N]> {
+    let mut iters = columns.map(|el| el.chunks(chunk_size));
+    std::iter::from_fn(move || {
+        let mut is_empty = false;
+        let chunk: [&'a [T]; N] = std::array::from_fn(|i| {
+            let next = iters[i].next();
+            is_empty |= next.is_none();
+
+            next.unwrap_or(&[])
+        });
+
+        if is_empty {
+            None
+        } else {
+            Some(chunk)
+        }
+    })
+}
+
+/// Same as `chunk_columns`, but over mutable columns
+pub(crate) fn chunk_columns_mut<'a, T, const N: usize>(
+    columns: [&'a mut [T]; N],
+    chunk_size: usize,
+) -> impl Iterator<Item = [&'a mut [T]; N]> {
+    let mut iters = columns.map(|el| el.chunks_mut(chunk_size));
+    std::iter::from_fn(move || {
+        let mut is_empty = false;
+        let chunk: [&'a mut [T]; N] = std::array::from_fn(|i| {
+            let next = iters[i].next();
+            is_empty |= next.is_none();
+
+            next.unwrap_or(&mut [])
+        });
+
+        if is_empty {
+            None
+        } else {
+            Some(chunk)
+        }
+    })
+}
+
 pub fn make_non_residues<F: PrimeField>(num: usize, domain_size: usize) -> Vec<F> {
     assert!(domain_size.is_power_of_two());
     assert!(domain_size <= u64::MAX as usize);
@@ -906,24 +953,28 @@ pub fn precompute_for_barycentric_evaluation<
 // precompute values useful for barycentric evaluation
 pub fn precompute_for_barycentric_evaluation_in_extension<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
     A: GoodAllocator,
 >(
     n: usize,
     coset: F,
-    at: ExtensionField<F, 2, EXT>,
+    at: ExtensionField<F, N, EXT>,
     worker: &Worker,
     ctx: &mut P::Context,
-) -> [Vec<P, A>; 2] {
+) -> [Vec<P, A>; N] {
     if n == 1 {
-        let mut result_c0 = Vec::with_capacity_in(1, A::default());
-        result_c0.push(P::one(ctx));
-
-        let mut result_c1 = Vec::with_capacity_in(1, A::default());
-        result_c1.push(P::zero(ctx));
+        return std::array::from_fn(|i| {
+            let mut result = Vec::with_capacity_in(1, A::default());
+            if i == 0 {
+                result.push(P::one(ctx));
+            } else {
+                result.push(P::zero(ctx));
+            }
 
-        return [result_c0, result_c1];
+            result
+        });
     }
 
     // L_i(X) = (omega^i / N) / (X - omega^i) * (X^N - 1)
@@ -946,17 +997,19 @@ pub fn precompute_for_barycentric_evaluation_in_extension<
 
     // compute vector of (X - coset * omega^i)
 
-    let mut storage_c0 = allocate_in_with_alignment_of::<F, P, A>(n, A::default());
-    let mut storage_c1 = allocate_in_with_alignment_of::<F, P, A>(n, A::default());
+    let mut storage: [Vec<F, A>; N] =
+        std::array::from_fn(|_| allocate_in_with_alignment_of::<F, P, A>(n, A::default()));
 
-    // because we only subtract c0, we can just fill it
-    storage_c1.resize(n, at.coeffs[1]);
+    // because we only subtract c0, we can just fill the rest
+    for (dst, coeff) in storage.iter_mut().zip(at.coeffs.iter()).skip(1) {
+        dst.resize(n, *coeff);
+    }
 
     let omega = domain_generator_for_size::<F>(n as u64);
     let at_c0 = at.coeffs[0];
 
     worker.scope(n, |scope, chunk_size| {
-        for (chunk_idx, chunk_c0) in storage_c0.spare_capacity_mut()[..n]
+        for (chunk_idx, chunk_c0) in storage[0].spare_capacity_mut()[..n]
             .chunks_mut(chunk_size)
             .enumerate()
         {
@@ -974,36 +1027,33 @@ pub fn precompute_for_barycentric_evaluation_in_extension<
         }
     });
 
-    unsafe { storage_c0.set_len(n) };
+    unsafe { storage[0].set_len(n) };
 
-    assert_eq!(storage_c0.len(), storage_c1.len());
+    for el in storage.iter() {
+        assert_eq!(storage[0].len(), el.len());
+    }
 
-    let mut result_c0 = storage_c0;
-    let mut result_c1 = storage_c1;
-    batch_inverse_inplace_parallel_in_extension::<F, EXT, A>(
-        &mut result_c0,
-        &mut result_c1,
+    let mut result = storage;
+    batch_inverse_inplace_parallel_in_extension::<F, N, EXT, A>(
+        result.each_mut().map(|el| &mut el[..]),
         worker,
     );
 
     // * omega^i * constant_factor
 
-    worker.scope(result_c0.len(), |scope, chunk_size| {
-        for (chunk_idx, (chunk_c0, chunk_c1)) in result_c0
-            .chunks_mut(chunk_size)
-            .zip(result_c1.chunks_mut(chunk_size))
-            .enumerate()
+    worker.scope(n, |scope, chunk_size| {
+        for (chunk_idx, mut chunk) in
+            chunk_columns_mut(result.each_mut().map(|el| &mut el[..]), chunk_size).enumerate()
         {
             scope.spawn(move |_| {
                 let mut current = constant_factor;
                 current.mul_assign_by_base(&omega.pow_u64((chunk_idx * chunk_size) as u64));
-                for (el_c0, el_c1) in chunk_c0.iter_mut().zip(chunk_c1.iter_mut()) {
-                    mul_assign_in_extension::<F, EXT>(
-                        el_c0,
-                        el_c1,
-                        &current.coeffs[0],
-                        &current.coeffs[1],
-                    );
+                for idx in 0..chunk[0].len() {
+                    let mut el: [F; N] = std::array::from_fn(|i| chunk[i][idx]);
+                    mul_assign_in_extension::<F, N, EXT>(&mut el, &current.coeffs);
+                    for (dst, src) in chunk.iter_mut().zip(el.into_iter()) {
+                        dst[idx] = src;
+                    }
 
                     current.mul_assign_by_base(&omega);
                 }
@@ -1011,13 +1061,11 @@ pub fn precompute_for_barycentric_evaluation_in_extension<
         }
     });
 
-    bitreverse_enumeration_inplace(&mut result_c0);
-    bitreverse_enumeration_inplace(&mut result_c1);
+    result.map(|mut el| {
+        bitreverse_enumeration_inplace(&mut el);
 
-    [
-        P::vec_from_base_vec(result_c0),
-        P::vec_from_base_vec(result_c1),
-    ]
+        P::vec_from_base_vec(el)
+    })
 }
 
 // No parallelism here - we parallelize externally
@@ -1082,163 +1130,139 @@ pub(crate) fn barycentric_evaluate_at_for_bitreversed_parallel<F: PrimeField>(
     result
 }
 
-pub(crate) fn barycentric_evaluate_base_at_extension_for_bitreversed_parallel<F: PrimeField>(
+pub(crate) fn barycentric_evaluate_base_at_extension_for_bitreversed_parallel<
+    F: PrimeField,
+    const N: usize,
+>(
     values: &[F],
-    precomputed_lagrange_poly_evals_c0: &[F],
-    precomputed_lagrange_poly_evals_c1: &[F],
+    precomputed_lagrange_poly_evals: [&[F]; N],
     worker: &Worker,
-) -> [F; 2] {
-    debug_assert_eq!(values.len(), precomputed_lagrange_poly_evals_c0.len());
-    debug_assert_eq!(values.len(), precomputed_lagrange_poly_evals_c1.len());
+) -> [F; N] {
+    for el in precomputed_lagrange_poly_evals.iter() {
+        debug_assert_eq!(values.len(), el.len());
+    }
 
     if values.len() == 1 {
         // evaluation is just a constant
-        return [values[0], values[0]];
+        let mut result = [F::ZERO; N];
+        result[0] = values[0];
+
+        return result;
     };
 
     if values.len() < worker.num_cores {
         // do single thread
-        let mut dst_c0 = F::ZERO;
-        let mut dst_c1 = F::ZERO;
-        for ((a, b_c0), b_c1) in values
-            .iter()
-            .zip(precomputed_lagrange_poly_evals_c0.iter())
-            .zip(precomputed_lagrange_poly_evals_c1.iter())
-        {
-            let mut c0 = *b_c0;
-            c0.mul_assign(a);
-            let mut c1 = *b_c1;
-            c1.mul_assign(a);
-
-            dst_c0.add_assign(&c0);
-            dst_c1.add_assign(&c1);
+        let mut result = [F::ZERO; N];
+        for (idx, a) in values.iter().enumerate() {
+            for (dst, b) in result.iter_mut().zip(precomputed_lagrange_poly_evals.iter()) {
+                let mut tmp = b[idx];
+                tmp.mul_assign(a);
+                dst.add_assign(&tmp);
+            }
         }
 
-        return [dst_c0, dst_c1];
+        return result;
     }
 
     let chunk_size = worker.get_chunk_size(values.len());
     let num_chunks = Worker::compute_num_chunks(values.len(), chunk_size);
 
-    let mut chunk_evals_c0 = vec![F::ZERO; num_chunks];
-    let mut chunk_evals_c1 = vec![F::ZERO; num_chunks];
+    let mut chunk_evals = vec![[F::ZERO; N]; num_chunks];
 
     worker.scope(values.len(), |scope, chunk_size| {
-        for ((((a, b_c0), b_c1), dst_c0), dst_c1) in values
+        for ((a, b), dst) in values
             .chunks(chunk_size)
-            .zip(precomputed_lagrange_poly_evals_c0.chunks(chunk_size))
-            .zip(precomputed_lagrange_poly_evals_c1.chunks(chunk_size))
-            .zip(chunk_evals_c0.iter_mut())
-            .zip(chunk_evals_c1.iter_mut())
+            .zip(chunk_columns(precomputed_lagrange_poly_evals, chunk_size))
+            .zip(chunk_evals.iter_mut())
         {
             scope.spawn(move |_| {
-                for ((a, b_c0), b_c1) in a.iter().zip(b_c0.iter()).zip(b_c1.iter()) {
-                    let mut tmp = *a;
-                    tmp.mul_assign(b_c0);
-                    dst_c0.add_assign(&tmp);
-
-                    let mut tmp = *a;
-                    tmp.mul_assign(b_c1);
-                    dst_c1.add_assign(&tmp);
+                for (idx, a) in a.iter().enumerate() {
+                    for (dst, b) in dst.iter_mut().zip(b.iter()) {
+                        let mut tmp = *a;
+                        tmp.mul_assign(&b[idx]);
+                        dst.add_assign(&tmp);
+                    }
                 }
             });
         }
     });
 
-    let mut result_c0 = chunk_evals_c0[0];
-    let mut result_c1 = chunk_evals_c1[0];
-    for el in chunk_evals_c0.into_iter().skip(1) {
-        result_c0.add_assign(&el);
-    }
-    for el in chunk_evals_c1.into_iter().skip(1) {
-        result_c1.add_assign(&el);
+    let mut result = chunk_evals[0];
+    for el in chunk_evals.into_iter().skip(1) {
+        for (dst, src) in result.iter_mut().zip(el.iter()) {
+            dst.add_assign(src);
+        }
     }
 
-    [result_c0, result_c1]
+    result
 }
 
 pub(crate) fn barycentric_evaluate_extension_at_extension_for_bitreversed_parallel<
     F: PrimeField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
 >(
-    values_c0: &[F],
-    values_c1: &[F],
-    precomputed_lagrange_poly_evals_c0: &[F],
-    precomputed_lagrange_poly_evals_c1: &[F],
+    values: [&[F]; N],
+    precomputed_lagrange_poly_evals: [&[F]; N],
     worker: &Worker,
-) -> [F; 2] {
-    debug_assert_eq!(values_c0.len(), precomputed_lagrange_poly_evals_c0.len());
-    debug_assert_eq!(values_c0.len(), precomputed_lagrange_poly_evals_c1.len());
-    debug_assert_eq!(values_c1.len(), precomputed_lagrange_poly_evals_c0.len());
-    debug_assert_eq!(values_c1.len(), precomputed_lagrange_poly_evals_c1.len());
+) -> [F; N] {
+    let len = values[0].len();
+    for (a, b) in values.iter().zip(precomputed_lagrange_poly_evals.iter()) {
+        debug_assert_eq!(len, a.len());
+        debug_assert_eq!(len, b.len());
+    }
 
-    if values_c0.len() == 1 {
+    if len == 1 {
         // evaluation is just a constant
-        return [values_c0[0], values_c1[0]];
+        return values.map(|el| el[0]);
+    };
+
+    let accumulate = |dst: &mut [F; N], a: &[&[F]; N], b: &[&[F]; N], idx: usize| {
+        let mut a: [F; N] = std::array::from_fn(|i| a[i][idx]);
+        let b: [F; N] = std::array::from_fn(|i| b[i][idx]);
+        mul_assign_in_extension::<F, N, EXT>(&mut a, &b);
+        for (dst, src) in dst.iter_mut().zip(a.iter()) {
+            dst.add_assign(src);
+        }
     };
 
-    if values_c0.len() < worker.num_cores {
+    if len < worker.num_cores {
         // do single thread
-        let mut dst_c0 = F::ZERO;
-        let mut dst_c1 = F::ZERO;
-        for (((a_c0, a_c1), b_c0), b_c1) in values_c0
-            .iter()
-            .zip(values_c1.iter())
-            .zip(precomputed_lagrange_poly_evals_c0.iter())
-            .zip(precomputed_lagrange_poly_evals_c1.iter())
-        {
-            let mut a_c0 = *a_c0;
-            let mut a_c1 = *a_c1;
-            mul_assign_in_extension::<F, EXT>(&mut a_c0, &mut a_c1, b_c0, b_c1);
-            dst_c0.add_assign(&a_c0);
-            dst_c1.add_assign(&a_c1);
+        let mut result = [F::ZERO; N];
+        for idx in 0..len {
+            accumulate(&mut result, &values, &precomputed_lagrange_poly_evals, idx);
         }
 
-        return [dst_c0, dst_c1];
+        return result;
     }
 
-    let chunk_size = worker.get_chunk_size(values_c0.len());
-    let num_chunks = Worker::compute_num_chunks(values_c0.len(), chunk_size);
+    let chunk_size = worker.get_chunk_size(len);
+    let num_chunks = Worker::compute_num_chunks(len, chunk_size);
 
-    let mut chunk_evals_c0 = vec![F::ZERO; num_chunks];
-    let mut chunk_evals_c1 = vec![F::ZERO; num_chunks];
+    let mut chunk_evals = vec![[F::ZERO; N]; num_chunks];
 
-    worker.scope(values_c0.len(), |scope, chunk_size| {
-        for (((((a_c0, a_c1), b_c0), b_c1), dst_c0), dst_c1) in values_c0
-            .chunks(chunk_size)
-            .zip(values_c1.chunks(chunk_size))
-            .zip(precomputed_lagrange_poly_evals_c0.chunks(chunk_size))
-            .zip(precomputed_lagrange_poly_evals_c1.chunks(chunk_size))
-            .zip(chunk_evals_c0.iter_mut())
-            .zip(chunk_evals_c1.iter_mut())
+    worker.scope(len, |scope, chunk_size| {
+        for ((a, b), dst) in chunk_columns(values, chunk_size)
+            .zip(chunk_columns(precomputed_lagrange_poly_evals, chunk_size))
+            .zip(chunk_evals.iter_mut())
         {
+            let accumulate = &accumulate;
             scope.spawn(move |_| {
-                for (((a_c0, a_c1), b_c0), b_c1) in a_c0
-                    .iter()
-                    .zip(a_c1.iter())
-                    .zip(b_c0.iter())
-                    .zip(b_c1.iter())
-                {
-                    let mut a_c0 = *a_c0;
-                    let mut a_c1 = *a_c1;
-                    mul_assign_in_extension::<F, EXT>(&mut a_c0, &mut a_c1, b_c0, b_c1);
-                    dst_c0.add_assign(&a_c0);
-                    dst_c1.add_assign(&a_c1);
+                for idx in 0..a[0].len() {
+                    accumulate(dst, &a, &b, idx);
                 }
             });
         }
     });
 
-    let mut result_c0 = chunk_evals_c0[0];
-    let mut result_c1 = chunk_evals_c1[0];
-    for el in chunk_evals_c0.into_iter().skip(1) {
-        result_c0.add_assign(&el);
-    }
-    for el in chunk_evals_c1.into_iter().skip(1) {
-        result_c1.add_assign(&el);
+    let mut result = chunk_evals[0];
+    for el in chunk_evals.into_iter().skip(1) {
+        for (dst, src) in result.iter_mut().zip(el.iter()) {
+            dst.add_assign(src);
+        }
     }
 
-    [result_c0, result_c1]
+    result
 }
 
 // signature is over P to keep our alignment at bay
@@ -1793,14 +1817,12 @@ mod test {
         let mut input_c1 = input_c0.clone();
         input_c1.reverse();
 
-        let mut inv_dst_c0 = vec![];
-        let mut inv_dst_c1 = vec![];
-        batch_inverse_in_extension::<F, GoldilocksExt2, Global>(
-            &input_c0,
-            &input_c1,
-            &mut inv_dst_c0,
-            &mut inv_dst_c1,
+        let mut inv_dst = [vec![], vec![]];
+        batch_inverse_in_extension::<F, 2, GoldilocksExt2, Global>(
+            [&input_c0, &input_c1],
+            &mut inv_dst,
         );
+        let [inv_dst_c0, inv_dst_c1] = inv_dst;
 
         let mut reference_c0 = vec![];
         let mut reference_c1 = vec![];
@@ -1896,7 +1918,7 @@ mod test {
             let worker = Worker::new();
 
             let [precomps_c0, precomps_c1] =
-                precompute_for_barycentric_evaluation_in_extension::<F, Ext, F, Global>(
+                precompute_for_barycentric_evaluation_in_extension::<F, 2, Ext, F, Global>(
                     size,
                     F::multiplicative_generator(),
                     z,
@@ -1925,11 +1947,9 @@ mod test {
             }
 
             let barycentric_val =
-                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, Ext>(
-                    &forward_c0,
-                    &forward_c1,
-                    &precomps_c0,
-                    &precomps_c1,
+                barycentric_evaluate_extension_at_extension_for_bitreversed_parallel::<F, 2, Ext>(
+                    [&forward_c0, &forward_c1],
+                    [&precomps_c0, &precomps_c1],
                     &worker,
                 );
 
@@ -1,4 +1,7 @@
+use super::proof::OracleQuery;
 use super::proof::Proof;
+use super::proof::ProofWithDeduplicatedPaths;
+use super::prover::ProofConfig;
 use super::transcript::Transcript;
 use super::*;
 
@@ -16,12 +19,14 @@ use crate::cs::traits::trace_source::TraceSourceDerivable;
 use crate::field::Field;
 use crate::field::PrimeField;
 use std::any::TypeId;
+use std::borrow::Cow;
 
 use crate::cs::gates::lookup_marker::*;
 use crate::cs::implementations::setup::TreeNode;
 use std::alloc::Global;
 use std::ops::Range;
 
+use crate::cs::implementations::evm_verifier::symbolic::{ExpressionTape, SymbolicExt};
 use crate::cs::implementations::pow::PoWRunner;
 use crate::field::ExtensionField;
 use crate::field::FieldExtension;
@@ -353,9 +358,10 @@ pub fn compute_selector_subpath_at_z<
 
 pub struct VerifierProxy<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     GC: GateConfigurationHolder<F>,
     T: StaticToolboxHolder,
+    const N: usize = 2,
 > {
     // when we init we get the following from VK
     pub parameters: CSGeometry,
@@ -364,12 +370,12 @@ pub struct VerifierProxy<
     pub gates_configuration: GC,
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT, N>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     // pub(crate) gate_type_ids_for_general_purpose_columns: Vec<TypeId>,
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT, N>>,
     pub(crate) general_purpose_evaluators_comparison_functions:
         HashMap<TypeId, Vec<(GateBatchEvaluationComparisonFunction, usize)>>,
 
@@ -382,12 +388,13 @@ pub struct VerifierProxy<
 
 impl<
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         GC: GateConfigurationHolder<F>,
         T: StaticToolboxHolder,
-    > VerifierProxy<F, EXT, GC, T>
+    > VerifierProxy<F, EXT, GC, T, N>
 {
-    pub fn into_verifier(self) -> Verifier<F, EXT> {
+    pub fn into_verifier(self) -> Verifier<F, EXT, N> {
         let Self {
             parameters,
             lookup_parameters,
@@ -418,7 +425,7 @@ impl<
             placement_strategies.insert(*gate_type_id, placement_strategy);
         }
 
-        Verifier::<F, EXT> {
+        Verifier::<F, EXT, N> {
             parameters,
             lookup_parameters,
             gate_type_ids_for_specialized_columns,
@@ -433,19 +440,301 @@ impl<
     }
 }
 
-pub struct Verifier<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
+/// Oracle (committed set of polynomials) that a verification failure refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OracleType {
+    Setup,
+    Witness,
+    Stage2,
+    Quotient,
+    /// FRI oracle by the folding step, where `0` is the base FRI oracle
+    Fri(usize),
+}
+
+/// Point at which the proof provides claimed polynomial values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OpeningPoint {
+    Z,
+    ZOmega,
+    Zero,
+}
+
+/// Reason why `Verifier::verify_detailed` rejected a proof. Every variant that is produced
+/// while checking FRI queries carries the index of the query repetition it failed at.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum VerificationError {
+    CircuitParametersMismatch,
+    LookupParametersMismatch,
+    CapSizeMismatch {
+        vk: usize,
+        proof: usize,
+    },
+    FriLdeFactorMismatch {
+        vk: usize,
+        proof: usize,
+    },
+    MalformedCap {
+        oracle: OracleType,
+        expected: usize,
+        got: usize,
+    },
+    InvalidNumberOfPublicInputs {
+        expected: usize,
+        got: usize,
+    },
+    InvalidNumberOfOpenings {
+        point: OpeningPoint,
+        expected: usize,
+        got: usize,
+    },
+    LookupSumcheckFailed,
+    QuotientIdentityFailed,
+    PowBitsMismatch {
+        expected: u32,
+        got: u32,
+    },
+    InvalidNumberOfFriOracles {
+        expected: usize,
+        got: usize,
+    },
+    FinalDegreeMismatch {
+        expected: usize,
+        got: usize,
+    },
+    MalformedFinalMonomials,
+    InvalidPoW {
+        pow_bits: u32,
+        challenge: u64,
+    },
+    InvalidNumberOfQueries {
+        expected: usize,
+        got: usize,
+    },
+    InvalidLeafSize {
+        query_idx: usize,
+        oracle: OracleType,
+        expected: usize,
+        got: usize,
+    },
+    InvalidMerklePathLength {
+        query_idx: usize,
+        oracle: OracleType,
+        expected: usize,
+        got: usize,
+    },
+    InvalidMerklePath {
+        query_idx: usize,
+        oracle: OracleType,
+        leaf_idx: usize,
+    },
+    InvalidNumberOfFriQueries {
+        query_idx: usize,
+        expected: usize,
+        got: usize,
+    },
+    FriFoldingMismatch {
+        query_idx: usize,
+        fri_step: usize,
+    },
+    FriFinalMonomialsMismatch {
+        query_idx: usize,
+    },
+    InvalidFriSchedule(crate::cs::implementations::prover::FriScheduleError),
+    InvalidNumberOfCircuits {
+        expected: usize,
+        got: usize,
+    },
+    /// Number of trees shared by aggregated circuits of the same size is not
+    /// the number of distinct sizes
+    InvalidNumberOfSharedOracles {
+        expected: usize,
+        got: usize,
+    },
+    /// Proof is made with another configuration than the one that is fixed for the verifier,
+    /// e.g. the one that compression circuit was set up for
+    ProofConfigMismatch,
+    /// Verifier can not check proofs in zero-knowledge mode, as it does not know about salted
+    /// leafs and masking polynomials
+    ZeroKnowledgeIsNotSupported,
+    /// Deduplicated Merkle paths can not be split into paths of individual queries
+    MalformedDeduplicatedPaths {
+        oracle: OracleType,
+    },
+    /// Check that is specific to one of the circuits of an aggregated proof failed
+    Circuit {
+        circuit_idx: usize,
+        error: Box<VerificationError>,
+    },
+}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CircuitParametersMismatch => {
+                write!(f, "Different circuit parameters in verifier and VK")
+            }
+            Self::LookupParametersMismatch => {
+                write!(f, "Different lookup parameters in verifier and VK")
+            }
+            Self::CapSizeMismatch { vk, proof } => write!(
+                f,
+                "Different cap size in proof as VK: VK has {}, proof has {}",
+                vk, proof
+            ),
+            Self::FriLdeFactorMismatch { vk, proof } => write!(
+                f,
+                "Different FRI LDE factor in proof as VK: VK has {}, proof has {}",
+                vk, proof
+            ),
+            Self::MalformedCap {
+                oracle,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Cap is malformed for {:?} oracle: expected {} elements, got {}",
+                oracle, expected, got
+            ),
+            Self::InvalidNumberOfPublicInputs { expected, got } => write!(
+                f,
+                "Invalid number of public inputs: expected {}, got {}",
+                expected, got
+            ),
+            Self::InvalidNumberOfOpenings {
+                point,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Number of openings at {:?} is unexpected: expected {}, got {}",
+                point, expected, got
+            ),
+            Self::LookupSumcheckFailed => write!(f, "Lookup sumcheck is invalid"),
+            Self::QuotientIdentityFailed => write!(f, "Invalid quotient at Z"),
+            Self::PowBitsMismatch { expected, got } => write!(
+                f,
+                "PoW bits computation diverged: expected {}, proof config has {}",
+                expected, got
+            ),
+            Self::InvalidNumberOfFriOracles { expected, got } => write!(
+                f,
+                "Unexpected number of intermediate FRI oracles: expected {}, got {}",
+                expected, got
+            ),
+            Self::FinalDegreeMismatch { expected, got } => write!(
+                f,
+                "Expected final degree diverged: expected {}, got {}",
+                expected, got
+            ),
+            Self::MalformedFinalMonomials => {
+                write!(f, "Final FRI monomials have invalid lengths")
+            }
+            Self::InvalidPoW {
+                pow_bits,
+                challenge,
+            } => write!(
+                f,
+                "PoW is invalid for {} bits with challenge 0x{:016x}",
+                pow_bits, challenge
+            ),
+            Self::InvalidNumberOfQueries { expected, got } => write!(
+                f,
+                "FRI queries number is invalid: expecting {}, prover provided {}",
+                expected, got
+            ),
+            Self::InvalidLeafSize {
+                query_idx,
+                oracle,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Invalid leaf size for {:?} oracle at query {}: expected {}, got {}",
+                oracle, query_idx, expected, got
+            ),
+            Self::InvalidMerklePathLength {
+                query_idx,
+                oracle,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Invalid Merkle proof length for {:?} oracle at query {}: expected {}, got {}",
+                oracle, query_idx, expected, got
+            ),
+            Self::InvalidMerklePath {
+                query_idx,
+                oracle,
+                leaf_idx,
+            } => write!(
+                f,
+                "Leaf {} of {:?} oracle is not in the tree at query {}",
+                leaf_idx, oracle, query_idx
+            ),
+            Self::InvalidNumberOfFriQueries {
+                query_idx,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Invalid number of FRI intermediate oracle queries at query {}: expected {}, got {}",
+                query_idx, expected, got
+            ),
+            Self::FriFoldingMismatch {
+                query_idx,
+                fri_step,
+            } => write!(
+                f,
+                "FRI element is not in the leaf for step {} at query {}",
+                fri_step, query_idx
+            ),
+            Self::FriFinalMonomialsMismatch { query_idx } => write!(
+                f,
+                "Not equal to evaluation from monomials at query {}",
+                query_idx
+            ),
+            Self::InvalidFriSchedule(err) => write!(f, "Invalid FRI schedule: {}", err),
+            Self::InvalidNumberOfCircuits { expected, got } => write!(
+                f,
+                "Invalid number of aggregated circuits: expected {}, got {}",
+                expected, got
+            ),
+            Self::InvalidNumberOfSharedOracles { expected, got } => write!(
+                f,
+                "Invalid number of shared oracles of aggregated circuits: expected {}, got {}",
+                expected, got
+            ),
+            Self::ProofConfigMismatch => {
+                write!(f, "Proof config is different from the expected one")
+            }
+            Self::ZeroKnowledgeIsNotSupported => {
+                write!(f, "Zero-knowledge proofs are not supported by this verifier")
+            }
+            Self::MalformedDeduplicatedPaths { oracle } => write!(
+                f,
+                "Malformed deduplicated Merkle paths for {:?} oracle",
+                oracle
+            ),
+            Self::Circuit { circuit_idx, error } => write!(f, "Circuit {}: {}", circuit_idx, error),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+pub struct Verifier<F: SmallField, EXT: FieldExtension<N, BaseField = F>, const N: usize = 2> {
     // when we init we get the following from VK
     pub parameters: CSGeometry,
     pub lookup_parameters: LookupParameters,
 
     pub gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT, N>>,
     pub offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     // pub(crate) gate_type_ids_for_general_purpose_columns: Vec<TypeId>,
     pub evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT>>,
+        Vec<TypeErasedGateEvaluationVerificationFunction<F, EXT, N>>,
 
     pub total_num_variables_for_specialized_columns: usize,
     pub total_num_witnesses_for_specialized_columns: usize,
@@ -461,7 +750,8 @@ pub struct Verifier<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
 #[derivative(Debug)]
 pub struct TypeErasedGateEvaluationVerificationFunction<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
 > {
     pub debug_name: String,
     pub unique_name: String,
@@ -478,9 +768,9 @@ pub struct TypeErasedGateEvaluationVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverSpecializedColumns<
                     F,
-                    ExtensionField<F, 2, EXT>,
-                    VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                    VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                    ExtensionField<F, N, EXT>,
+                    VerifierPolyStorage<F, ExtensionField<F, N, EXT>>,
+                    VerifierRelationDestination<F, ExtensionField<F, N, EXT>>,
                 >
                 + 'static
                 + Send
@@ -492,9 +782,38 @@ pub struct TypeErasedGateEvaluationVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                     F,
-                    ExtensionField<F, 2, EXT>,
-                    VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                    VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                    ExtensionField<F, N, EXT>,
+                    VerifierPolyStorage<F, ExtensionField<F, N, EXT>>,
+                    VerifierRelationDestination<F, ExtensionField<F, N, EXT>>,
+                >
+                + 'static
+                + Send
+                + Sync,
+        >,
+    >,
+    // same functions over symbolic values, so gate constraints can be emitted as code
+    #[derivative(Debug = "ignore")]
+    pub(crate) columnwise_symbolic_function: Option<
+        Box<
+            dyn GenericDynamicEvaluatorOverSpecializedColumns<
+                    F,
+                    SymbolicExt<F>,
+                    VerifierPolyStorage<F, SymbolicExt<F>>,
+                    VerifierRelationDestination<F, SymbolicExt<F>>,
+                >
+                + 'static
+                + Send
+                + Sync,
+        >,
+    >,
+    #[derivative(Debug = "ignore")]
+    pub(crate) rowwise_symbolic_function: Option<
+        Box<
+            dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
+                    F,
+                    SymbolicExt<F>,
+                    VerifierPolyStorage<F, SymbolicExt<F>>,
+                    VerifierRelationDestination<F, SymbolicExt<F>>,
                 >
                 + 'static
                 + Send
@@ -503,8 +822,8 @@ pub struct TypeErasedGateEvaluationVerificationFunction<
     >,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
-    TypeErasedGateEvaluationVerificationFunction<F, EXT>
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
+    TypeErasedGateEvaluationVerificationFunction<F, EXT, N>
 {
     pub fn from_evaluator<E: GateConstraintEvaluator<F>>(
         evaluator: E,
@@ -575,9 +894,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                             as Box<
                                 dyn GenericDynamicEvaluatorOverSpecializedColumns<
                                         F,
-                                        ExtensionField<F, 2, EXT>,
-                                        VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                                        VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                                        ExtensionField<F, N, EXT>,
+                                        VerifierPolyStorage<F, ExtensionField<F, N, EXT>>,
+                                        VerifierRelationDestination<F, ExtensionField<F, N, EXT>>,
                                     >
                                     + 'static
                                     + Send
@@ -602,9 +921,65 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                             as Box<
                                 dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                                         F,
-                                        ExtensionField<F, 2, EXT>,
-                                        VerifierPolyStorage<F, ExtensionField<F, 2, EXT>>,
-                                        VerifierRelationDestination<F, ExtensionField<F, 2, EXT>>,
+                                        ExtensionField<F, N, EXT>,
+                                        VerifierPolyStorage<F, ExtensionField<F, N, EXT>>,
+                                        VerifierRelationDestination<F, ExtensionField<F, N, EXT>>,
+                                    >
+                                    + 'static
+                                    + Send
+                                    + Sync,
+                            >),
+                    )
+                }
+            };
+
+        // global constants of the gates are folded, so nothing is recorded into this tape
+        let mut tape = ExpressionTape::<F>::new();
+        let (specialized_symbolic_evaluator, general_purpose_symbolic_evaluator) =
+            match placement_strategy {
+                GatePlacementStrategy::UseSpecializedColumns { .. } => {
+                    let specialized_evaluator = GenericColumnwiseEvaluator {
+                        evaluator: evaluator.clone(),
+                        global_constants: evaluator
+                            .create_global_constants::<SymbolicExt<F>>(&mut tape),
+                        num_repetitions: num_repetitions_on_row,
+                        per_chunk_offset: final_per_chunk_offset,
+                    };
+
+                    (
+                        Some(Box::new(specialized_evaluator)
+                            as Box<
+                                dyn GenericDynamicEvaluatorOverSpecializedColumns<
+                                        F,
+                                        SymbolicExt<F>,
+                                        VerifierPolyStorage<F, SymbolicExt<F>>,
+                                        VerifierRelationDestination<F, SymbolicExt<F>>,
+                                    >
+                                    + 'static
+                                    + Send
+                                    + Sync,
+                            >),
+                        None,
+                    )
+                }
+                GatePlacementStrategy::UseGeneralPurposeColumns => {
+                    let general_purpose_evaluator = GenericRowwiseEvaluator {
+                        evaluator: evaluator.clone(),
+                        global_constants: evaluator
+                            .create_global_constants::<SymbolicExt<F>>(&mut tape),
+                        num_repetitions: num_repetitions_on_row,
+                        per_chunk_offset: final_per_chunk_offset,
+                    };
+
+                    (
+                        None,
+                        Some(Box::new(general_purpose_evaluator)
+                            as Box<
+                                dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
+                                        F,
+                                        SymbolicExt<F>,
+                                        VerifierPolyStorage<F, SymbolicExt<F>>,
+                                        VerifierRelationDestination<F, SymbolicExt<F>>,
                                     >
                                     + 'static
                                     + Send
@@ -613,6 +988,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
                     )
                 }
             };
+        debug_assert!(tape.ops.is_empty());
 
         let this_params = evaluator.unique_params();
 
@@ -642,6 +1018,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>>
             placement_type,
             columnwise_satisfiability_function: specialized_satisfiability_evaluator,
             rowwise_satisfiability_function: general_purpose_satisfiability_evaluator,
+            columnwise_symbolic_function: specialized_symbolic_evaluator,
+            rowwise_symbolic_function: general_purpose_symbolic_evaluator,
         };
 
         (new, comparator)
@@ -800,10 +1178,10 @@ impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
+impl<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>> Verifier<F, EXT, N> {
     #[inline]
     pub fn num_sublookup_arguments(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_sublookup_arguments(
+        SizeCalculator::<F, N, EXT>::num_sublookup_arguments(
             &self.parameters,
             &self.lookup_parameters,
         )
@@ -811,7 +1189,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
     #[inline]
     pub fn num_multipicities_polys(&self, total_tables_len: usize, domain_size: u64) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_multipicities_polys(
+        SizeCalculator::<F, N, EXT>::num_multipicities_polys(
             &self.lookup_parameters,
             total_tables_len,
             domain_size,
@@ -819,21 +1197,21 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn num_variable_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_variable_polys(
+        SizeCalculator::<F, N, EXT>::num_variable_polys(
             &self.parameters,
             self.total_num_variables_for_specialized_columns,
         )
     }
 
     pub fn num_witness_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_witness_polys(
+        SizeCalculator::<F, N, EXT>::num_witness_polys(
             &self.parameters,
             self.total_num_witnesses_for_specialized_columns,
         )
     }
 
     pub fn num_constant_polys(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_constant_polys(
+        SizeCalculator::<F, N, EXT>::num_constant_polys(
             &self.parameters,
             vk_fixed_params,
             self.total_num_constants_for_specialized_columns,
@@ -841,7 +1219,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn quotient_degree(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_degree(vk_fixed_params)
+        SizeCalculator::<F, N, EXT>::quotient_degree(vk_fixed_params)
     }
 
     pub fn num_copy_permutation_polys(&self) -> usize {
@@ -849,11 +1227,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn num_lookup_table_setup_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
+        SizeCalculator::<F, N, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
     }
 
     pub fn witness_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::witness_leaf_size(
+        SizeCalculator::<F, N, EXT>::witness_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -863,7 +1241,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn stage_2_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::stage_2_leaf_size(
+        SizeCalculator::<F, N, EXT>::stage_2_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -872,11 +1250,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
     }
 
     pub fn quotient_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_leaf_size(vk_fixed_params)
+        SizeCalculator::<F, N, EXT>::quotient_leaf_size(vk_fixed_params)
     }
 
     pub fn setup_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::setup_leaf_size(
+        SizeCalculator::<F, N, EXT>::setup_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -885,6 +1263,38 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         )
     }
 
+    /// Number of terms combined by powers of alpha into the quotient: lookup relations,
+    /// all the gate terms over every repetition, and copy-permutation relations
+    pub fn num_quotient_terms(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
+        let num_lookup_argument_terms = self.num_sublookup_arguments()
+            + self.num_multipicities_polys(
+                vk_fixed_params.total_tables_len as usize,
+                vk_fixed_params.domain_size,
+            );
+        let num_gate_terms_for_specialized_columns: usize = self
+            .evaluators_over_specialized_columns
+            .iter()
+            .map(|evaluator| evaluator.num_quotient_terms * evaluator.num_repetitions_on_row)
+            .sum();
+        let num_gate_terms_for_general_purpose_columns: usize = self
+            .evaluators_over_general_purpose_columns
+            .iter()
+            .map(|evaluator| evaluator.total_quotient_terms_over_all_repetitions)
+            .sum();
+
+        use crate::cs::implementations::copy_permutation::num_intermediate_partial_product_relations;
+        let num_intermediate_partial_product_relations = num_intermediate_partial_product_relations(
+            self.num_copy_permutation_polys(),
+            self.quotient_degree(vk_fixed_params),
+        );
+
+        num_lookup_argument_terms
+            + num_gate_terms_for_specialized_columns
+            + num_gate_terms_for_general_purpose_columns
+            + 2 // z(1) == 1 and z(x * omega) = ...
+            + num_intermediate_partial_product_relations
+    }
+
     pub fn verify<
         H: TreeHasher<F>,
         TR: Transcript<F, CompatibleCap = H::Output>,
@@ -893,40 +1303,337 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         &self,
         transcript_params: TR::TransciptParameters,
         vk: &VerificationKey<F, H>,
-        proof: &Proof<F, H, EXT>,
+        proof: &Proof<F, H, EXT, N>,
+    ) -> bool {
+        match self.verify_detailed::<H, TR, POW>(transcript_params, vk, proof) {
+            Ok(()) => true,
+            Err(error) => {
+                log!("{}", error);
+                false
+            }
+        }
+    }
+
+    /// Same as `verify`, but reports the first failed check instead of only
+    /// a validity flag
+    pub fn verify_detailed<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        vk: &VerificationKey<F, H>,
+        proof: &Proof<F, H, EXT, N>,
+    ) -> Result<(), VerificationError> {
+        self.check_verification_key(vk)?;
+
+        let mut transcript = TR::new(transcript_params);
+        transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+
+        self.verify_with_committed_vk::<H, TR, POW>(transcript, vk, proof)
+    }
+
+    pub fn verify_deduplicated<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        vk: &VerificationKey<F, H>,
+        proof: &ProofWithDeduplicatedPaths<F, H, EXT, N>,
     ) -> bool {
+        match self.verify_deduplicated_detailed::<H, TR, POW>(transcript_params, vk, proof) {
+            Ok(()) => true,
+            Err(error) => {
+                log!("{}", error);
+                false
+            }
+        }
+    }
+
+    /// Same as `verify_detailed`, but for the proof with deduplicated Merkle paths
+    pub fn verify_deduplicated_detailed<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        vk: &VerificationKey<F, H>,
+        proof: &ProofWithDeduplicatedPaths<F, H, EXT, N>,
+    ) -> Result<(), VerificationError> {
+        self.check_verification_key(vk)?;
+
+        let mut transcript = TR::new(transcript_params);
+        transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+
+        self.verify_with_committed_vk_and_paths::<H, TR, POW, _>(
+            transcript,
+            vk,
+            &proof.proof,
+            |query_indices| {
+                proof
+                    .restore_paths(query_indices, vk.fixed_parameters.base_oracles_depth())
+                    .map(Cow::Owned)
+                    .map_err(|oracle| VerificationError::MalformedDeduplicatedPaths { oracle })
+            },
+        )
+    }
+
+    /// Converts the proof into the form where Merkle paths of all the queries are merged
+    /// per oracle. Queried indexes are drawn by replaying the transcript, so the proof must
+    /// pass all the checks that precede the queries
+    pub fn deduplicate_query_paths<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        vk: &VerificationKey<F, H>,
+        proof: Proof<F, H, EXT, N>,
+    ) -> Result<ProofWithDeduplicatedPaths<F, H, EXT, N>, VerificationError> {
+        self.check_verification_key(vk)?;
+
+        let mut transcript = TR::new(transcript_params);
+        transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+
+        let query_indices = self.draw_query_indices::<H, TR, POW>(transcript, vk, &proof)?;
+
+        Ok(ProofWithDeduplicatedPaths::from_proof(
+            proof,
+            &query_indices,
+        ))
+    }
+
+    // replays the transcript up to the queries and returns the queried indexes in the base oracles.
+    // Also checks that the proof has exactly one set of queries per drawn index
+    fn draw_query_indices<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        mut transcript: TR,
+        vk: &VerificationKey<F, H>,
+        proof: &Proof<F, H, EXT, N>,
+    ) -> Result<Vec<usize>, VerificationError> {
+        let openings = CircuitOpenings::from_proof(proof);
+        self.verify_openings_at_z(&mut transcript, vk, &openings)?;
+
+        let mut fri = FriVerificationContext::<F, EXT, H, N>::new::<TR, POW>(
+            &mut transcript,
+            &proof.proof_config,
+            vk.fixed_parameters.domain_size,
+            &proof.fri_base_oracle_cap,
+            &proof.fri_intermediate_oracles_caps,
+            &proof.final_fri_monomials,
+            proof.pow_challenge,
+        )?;
+
+        if fri.num_queries != proof.queries_per_fri_repetition.len() {
+            return Err(VerificationError::InvalidNumberOfQueries {
+                expected: fri.num_queries,
+                got: proof.queries_per_fri_repetition.len(),
+            });
+        }
+
+        let query_indices = (0..fri.num_queries)
+            .map(|_| fri.draw_query_point(&mut transcript).base_tree_idx)
+            .collect();
+
+        Ok(query_indices)
+    }
+
+    /// Verifies many proofs of the same circuit. Parameters of the VK are checked
+    /// and the VK is committed into the transcript only once, and proofs are
+    /// then verified in parallel. Results are in the same order as `proofs`
+    pub fn verify_batch<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript_params: TR::TransciptParameters,
+        vk: &VerificationKey<F, H>,
+        proofs: &[Proof<F, H, EXT, N>],
+        worker: &Worker,
+    ) -> Vec<Result<(), VerificationError>> {
+        if let Err(error) = self.check_verification_key(vk) {
+            return vec![Err(error); proofs.len()];
+        }
+
         let mut transcript = TR::new(transcript_params);
+        transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
+
+        let mut results = vec![Ok(()); proofs.len()];
+        worker.scope(proofs.len(), |scope, chunk_size| {
+            for (src, dst) in proofs
+                .chunks(chunk_size)
+                .zip(results.chunks_mut(chunk_size))
+            {
+                let transcript = &transcript;
+                scope.spawn(move |_| {
+                    for (proof, dst) in src.iter().zip(dst.iter_mut()) {
+                        *dst = self.verify_with_committed_vk::<H, TR, POW>(
+                            transcript.clone(),
+                            vk,
+                            proof,
+                        );
+                    }
+                });
+            }
+        });
+
+        results
+    }
 
+    pub(crate) fn check_verification_key<H: TreeHasher<F>>(
+        &self,
+        vk: &VerificationKey<F, H>,
+    ) -> Result<(), VerificationError> {
         if self.parameters != vk.fixed_parameters.parameters {
-            log!("Different circuit parameters in verifier and VK");
-            return false;
+            return Err(VerificationError::CircuitParametersMismatch);
         }
 
         if self.lookup_parameters != vk.fixed_parameters.lookup_parameters {
-            log!("Different lookup parameters in verifier and VK");
-            return false;
+            return Err(VerificationError::LookupParametersMismatch);
+        }
+
+        if vk.fixed_parameters.cap_size != vk.setup_merkle_tree_cap.len() {
+            return Err(VerificationError::MalformedCap {
+                oracle: OracleType::Setup,
+                expected: vk.fixed_parameters.cap_size,
+                got: vk.setup_merkle_tree_cap.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // expects that VK was already checked by `check_verification_key` and
+    // that setup cap is already committed into the transcript
+    fn verify_with_committed_vk<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+    >(
+        &self,
+        transcript: TR,
+        vk: &VerificationKey<F, H>,
+        proof: &Proof<F, H, EXT, N>,
+    ) -> Result<(), VerificationError> {
+        self.verify_with_committed_vk_and_paths::<H, TR, POW, _>(transcript, vk, proof, |_| {
+            Ok(Cow::Borrowed(proof))
+        })
+    }
+
+    // same as `verify_with_committed_vk`, but Merkle paths of the queries are taken from the
+    // proof returned by `query_paths`, that gets the queried indexes in the base oracles. It's
+    // only called after all the checks that precede the queries
+    fn verify_with_committed_vk_and_paths<
+        'a,
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+        POW: PoWRunner,
+        QP: FnOnce(&[usize]) -> Result<Cow<'a, Proof<F, H, EXT, N>>, VerificationError>,
+    >(
+        &self,
+        mut transcript: TR,
+        vk: &VerificationKey<F, H>,
+        proof: &Proof<F, H, EXT, N>,
+        query_paths: QP,
+    ) -> Result<(), VerificationError> {
+        let openings = CircuitOpenings::from_proof(proof);
+        let openings_at_z = self.verify_openings_at_z(&mut transcript, vk, &openings)?;
+
+        let mut fri = FriVerificationContext::<F, EXT, H, N>::new::<TR, POW>(
+            &mut transcript,
+            &proof.proof_config,
+            vk.fixed_parameters.domain_size,
+            &proof.fri_base_oracle_cap,
+            &proof.fri_intermediate_oracles_caps,
+            &proof.final_fri_monomials,
+            proof.pow_challenge,
+        )?;
+
+        if fri.num_queries != proof.queries_per_fri_repetition.len() {
+            return Err(VerificationError::InvalidNumberOfQueries {
+                expected: fri.num_queries,
+                got: proof.queries_per_fri_repetition.len(),
+            });
         }
 
-        if vk.fixed_parameters.cap_size != proof.proof_config.merkle_tree_cap_size {
-            log!("Different cap sized in proof as VK");
-            return false;
+        // queries are not absorbed into the transcript, so all the points can be drawn at once
+        let query_points: Vec<_> = (0..fri.num_queries)
+            .map(|_| fri.draw_query_point(&mut transcript))
+            .collect();
+        let query_indices: Vec<_> = query_points.iter().map(|el| el.base_tree_idx).collect();
+        let proof_with_paths = query_paths(&query_indices)?;
+
+        for (query_idx, (queries, query_point)) in proof_with_paths
+            .queries_per_fri_repetition
+            .iter()
+            .zip(query_points.into_iter())
+            .enumerate()
+        {
+            let simulated_ext_element = self.verify_base_oracles_query(
+                vk,
+                &openings,
+                &openings_at_z,
+                query_idx,
+                &queries.witness_query,
+                &queries.stage_2_query,
+                &queries.quotient_query,
+                &queries.setup_query,
+                &query_point,
+            )?;
+
+            fri.verify_query(
+                query_idx,
+                &query_point,
+                simulated_ext_element,
+                &queries.fri_queries,
+            )?;
         }
 
-        if vk.fixed_parameters.fri_lde_factor != proof.proof_config.fri_lde_factor {
-            log!("Different FRI LDE factor in proof as VK");
-            return false;
+        Ok(())
+    }
+
+    /// Checks the claimed openings of a single circuit at `z` and draws challenges
+    /// to combine them into the base FRI oracle. Expects that setup cap is already
+    /// committed into the transcript
+    pub(crate) fn verify_openings_at_z<
+        H: TreeHasher<F>,
+        TR: Transcript<F, CompatibleCap = H::Output>,
+    >(
+        &self,
+        transcript: &mut TR,
+        vk: &VerificationKey<F, H>,
+        proof: &CircuitOpenings<'_, F, H, EXT, N>,
+    ) -> Result<OpeningsAtZ<F, EXT, N>, VerificationError> {
+        if vk.fixed_parameters.cap_size != proof.merkle_tree_cap_size {
+            return Err(VerificationError::CapSizeMismatch {
+                vk: vk.fixed_parameters.cap_size,
+                proof: proof.merkle_tree_cap_size,
+            });
         }
 
-        if vk.fixed_parameters.cap_size != vk.setup_merkle_tree_cap.len() {
-            log!("Cap is malformed");
-            return false;
+        if vk.fixed_parameters.fri_lde_factor != proof.fri_lde_factor {
+            return Err(VerificationError::FriLdeFactorMismatch {
+                vk: vk.fixed_parameters.fri_lde_factor,
+                proof: proof.fri_lde_factor,
+            });
         }
-        transcript.witness_merkle_tree_cap(&vk.setup_merkle_tree_cap);
 
         if proof.public_inputs.len() != vk.fixed_parameters.public_inputs_locations.len() {
             // VK mismatch
-            log!("Invalid number of public inputs");
-            return false;
+            return Err(VerificationError::InvalidNumberOfPublicInputs {
+                expected: vk.fixed_parameters.public_inputs_locations.len(),
+                got: proof.public_inputs.len(),
+            });
         }
 
         let num_public_inputs = proof.public_inputs.len();
@@ -946,40 +1653,46 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
         // commit witness
         if vk.fixed_parameters.cap_size != proof.witness_oracle_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::MalformedCap {
+                oracle: OracleType::Witness,
+                expected: vk.fixed_parameters.cap_size,
+                got: proof.witness_oracle_cap.len(),
+            });
         }
-        transcript.witness_merkle_tree_cap(&proof.witness_oracle_cap);
+        transcript.witness_merkle_tree_cap(proof.witness_oracle_cap);
 
         // draw challenges for stage 2
-        let beta = transcript.get_multiple_challenges_fixed::<2>();
-        let beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(beta);
-        let gamma = transcript.get_multiple_challenges_fixed::<2>();
-        let gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(gamma);
+        let beta = transcript.get_multiple_challenges_fixed::<N>();
+        let beta = ExtensionField::<F, N, EXT>::from_coeff_in_base(beta);
+        let gamma = transcript.get_multiple_challenges_fixed::<N>();
+        let gamma = ExtensionField::<F, N, EXT>::from_coeff_in_base(gamma);
 
         let (lookup_beta, lookup_gamma) = if self.lookup_parameters != LookupParameters::NoLookup {
             // lookup argument related parts
-            let lookup_beta = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_beta = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_beta);
-            let lookup_gamma = transcript.get_multiple_challenges_fixed::<2>();
-            let lookup_gamma = ExtensionField::<F, 2, EXT>::from_coeff_in_base(lookup_gamma);
+            let lookup_beta = transcript.get_multiple_challenges_fixed::<N>();
+            let lookup_beta = ExtensionField::<F, N, EXT>::from_coeff_in_base(lookup_beta);
+            let lookup_gamma = transcript.get_multiple_challenges_fixed::<N>();
+            let lookup_gamma = ExtensionField::<F, N, EXT>::from_coeff_in_base(lookup_gamma);
 
             (lookup_beta, lookup_gamma)
         } else {
-            let zero = ExtensionField::<F, 2, EXT>::ZERO;
+            let zero = ExtensionField::<F, N, EXT>::ZERO;
 
             (zero, zero)
         };
 
         if vk.fixed_parameters.cap_size != proof.stage_2_oracle_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::MalformedCap {
+                oracle: OracleType::Stage2,
+                expected: vk.fixed_parameters.cap_size,
+                got: proof.stage_2_oracle_cap.len(),
+            });
         }
-        transcript.witness_merkle_tree_cap(&proof.stage_2_oracle_cap);
+        transcript.witness_merkle_tree_cap(proof.stage_2_oracle_cap);
 
         // draw challenges for quotient
-        let alpha = transcript.get_multiple_challenges_fixed::<2>();
-        let alpha = ExtensionField::<F, 2, EXT>::from_coeff_in_base(alpha);
+        let alpha = transcript.get_multiple_challenges_fixed::<N>();
+        let alpha = ExtensionField::<F, N, EXT>::from_coeff_in_base(alpha);
 
         // two extra relations per lookup subargument - for A and B polys
         let num_lookup_subarguments = self.num_sublookup_arguments();
@@ -1038,6 +1751,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             + 1 // z(x * omega) = ...
             + num_intermediate_partial_product_relations // chunking copy permutation part
         ;
+        debug_assert_eq!(
+            total_num_terms,
+            self.num_quotient_terms(&vk.fixed_parameters)
+        );
 
         use crate::cs::implementations::utils::materialize_powers_serial;
 
@@ -1053,15 +1770,18 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
         // commit quotient
         if vk.fixed_parameters.cap_size != proof.quotient_oracle_cap.len() {
-            log!("Cap is malformed");
-            return false;
+            return Err(VerificationError::MalformedCap {
+                oracle: OracleType::Quotient,
+                expected: vk.fixed_parameters.cap_size,
+                got: proof.quotient_oracle_cap.len(),
+            });
         }
-        transcript.witness_merkle_tree_cap(&proof.quotient_oracle_cap);
+        transcript.witness_merkle_tree_cap(proof.quotient_oracle_cap);
 
         // get z
 
-        let z = transcript.get_multiple_challenges_fixed::<2>();
-        let z = ExtensionField::<F, 2, EXT>::from_coeff_in_base(z);
+        let z = transcript.get_multiple_challenges_fixed::<N>();
+        let z = ExtensionField::<F, N, EXT>::from_coeff_in_base(z);
 
         // commit claimed values at z, and form our poly storage
         for set in proof.values_at_z.iter() {
@@ -1124,21 +1844,31 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             1 + // z_poly
             num_intermediate_partial_product_relations + // partial products in copy-permutation
             expected_lookup_polys_total + // everything from lookup
-            quotient_degree; // chunks of quotient poly
+            quotient_degree + // chunks of quotient poly
+            proof.num_masking_polys; // committed with the quotient
 
         if proof.values_at_z.len() != num_poly_values_at_z {
-            log!("Number of openings at Z is unexpected");
-            return false;
+            return Err(VerificationError::InvalidNumberOfOpenings {
+                point: OpeningPoint::Z,
+                expected: num_poly_values_at_z,
+                got: proof.values_at_z.len(),
+            });
         }
 
         if proof.values_at_z_omega.len() != 1 {
-            log!("Number of openings at Z*omega is unexpected");
-            return false;
+            return Err(VerificationError::InvalidNumberOfOpenings {
+                point: OpeningPoint::ZOmega,
+                expected: 1,
+                got: proof.values_at_z_omega.len(),
+            });
         }
 
         if proof.values_at_0.len() != total_num_lookup_argument_terms {
-            log!("Number of openings at 0 is unexpected");
-            return false;
+            return Err(VerificationError::InvalidNumberOfOpenings {
+                point: OpeningPoint::Zero,
+                expected: total_num_lookup_argument_terms,
+                got: proof.values_at_0.len(),
+            });
         }
 
         // run verifier at z
@@ -1151,9 +1881,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 num_variable_polys,
             );
 
-            let all_values_at_z = &proof.values_at_z;
-            let all_values_at_z_omega = &proof.values_at_z_omega;
-            let all_values_at_0 = &proof.values_at_0;
+            let all_values_at_z = proof.values_at_z;
+            let all_values_at_z_omega = proof.values_at_z_omega;
+            let all_values_at_0 = proof.values_at_0;
 
             let lookup_challenges = &pregenerated_challenges_for_lookup;
             let specialized_evaluators_challenges =
@@ -1199,15 +1929,16 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 .take(num_lookup_table_setup_polys)
                 .copied()
                 .collect();
-            // quotient
-            let quotient_chunks: Vec<_> = source_it.copied().collect();
+            // quotient, masking polynomials are only used for DEEP
+            let quotient_chunks: Vec<_> = (&mut source_it).take(quotient_degree).copied().collect();
 
             assert_eq!(quotient_chunks.len(), quotient_degree);
+            assert_eq!(source_it.len(), proof.num_masking_polys);
 
             let mut source_it = all_values_at_z_omega.iter();
             let copy_permutation_z_at_z_omega = *source_it.next().unwrap();
 
-            let mut t_accumulator = ExtensionField::<F, 2, EXT>::ZERO;
+            let mut t_accumulator = ExtensionField::<F, N, EXT>::ZERO;
             // precompute selectors at z
 
             let mut selectors_buffer = HashMap::new();
@@ -1244,12 +1975,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 let multiplicities_encoding_polys_at_0 =
                     &all_values_at_0[num_lookup_subarguments..];
 
-                let mut witness_subsum = ExtensionField::<F, 2, EXT>::ZERO;
+                let mut witness_subsum = ExtensionField::<F, N, EXT>::ZERO;
                 for a in lookup_witness_encoding_polys_polys_at_0.iter() {
                     witness_subsum.add_assign(a);
                 }
 
-                let mut multiplicities_subsum = ExtensionField::<F, 2, EXT>::ZERO;
+                let mut multiplicities_subsum = ExtensionField::<F, N, EXT>::ZERO;
                 for b in multiplicities_encoding_polys_at_0.iter() {
                     multiplicities_subsum.add_assign(b);
                 }
@@ -1259,7 +1990,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         witness_subsum,
                         multiplicities_subsum
                     );
-                    return false;
+                    return Err(VerificationError::LookupSumcheckFailed);
                 }
 
                 // lookup argument related parts
@@ -1298,7 +2029,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         let capacity = column_elements_per_subargument
                             + ((vk.fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = ExtensionField::<F, 2, EXT>::ONE;
+                        let mut tmp = ExtensionField::<F, N, EXT>::ONE;
                         powers_of_gamma.push(tmp);
                         for _ in 1..capacity {
                             tmp.mul_assign(&lookup_gamma);
@@ -1311,7 +2042,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                            ExtensionField::<F, N, EXT>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -1353,7 +2084,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                                ExtensionField::<F, N, EXT>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -1419,7 +2150,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         let capacity = column_elements_per_subargument
                             + ((vk.fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = ExtensionField::<F, 2, EXT>::ONE;
+                        let mut tmp = ExtensionField::<F, N, EXT>::ONE;
                         powers_of_gamma.push(tmp);
                         for _ in 1..capacity {
                             tmp.mul_assign(&lookup_gamma);
@@ -1432,7 +2163,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                            ExtensionField::<F, N, EXT>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -1477,7 +2208,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                ExtensionField::<F, 2, EXT>::mul_and_accumulate_into(
+                                ExtensionField::<F, N, EXT>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -1487,7 +2218,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                             // mul by A(x)
                             contribution.mul_assign(a_poly);
                             // sub numerator
-                            contribution.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+                            contribution.sub_assign(&ExtensionField::<F, N, EXT>::ONE);
                             // mul by power of challenge
                             contribution.mul_assign(&alpha);
 
@@ -1619,8 +2350,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                     }
 
                     let mut dst = VerifierRelationDestination {
-                        accumulator: ExtensionField::<F, 2, EXT>::ZERO,
-                        selector_value: ExtensionField::<F, 2, EXT>::ONE,
+                        accumulator: ExtensionField::<F, N, EXT>::ZERO,
+                        selector_value: ExtensionField::<F, N, EXT>::ONE,
                         challenges: specialized_evaluators_challenges.clone(),
                         current_challenge_offset: challenges_offset,
                         _marker: std::marker::PhantomData,
@@ -1687,7 +2418,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                         let constant_placement_offset = path.len();
 
                         let mut dst = VerifierRelationDestination {
-                            accumulator: ExtensionField::<F, 2, EXT>::ZERO,
+                            accumulator: ExtensionField::<F, N, EXT>::ZERO,
                             selector_value: selector,
                             challenges: general_purpose_challenges.clone(),
                             current_challenge_offset: challenges_offset,
@@ -1726,14 +2457,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             let z_in_domain_size = z.pow_u64(vk.fixed_parameters.domain_size);
 
             let mut vanishing_at_z = z_in_domain_size;
-            vanishing_at_z.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+            vanishing_at_z.sub_assign(&ExtensionField::<F, N, EXT>::ONE);
 
             let mut challenges_it = remaining_challenges.iter();
 
             {
                 // (x^n - 1) / (x - 1),
                 let mut z_minus_one = z;
-                z_minus_one.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+                z_minus_one.sub_assign(&ExtensionField::<F, N, EXT>::ONE);
 
                 let mut unnormalized_l1_inverse_at_z = vanishing_at_z;
                 unnormalized_l1_inverse_at_z
@@ -1742,7 +2473,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 let alpha = *challenges_it.next().expect("challenge for z(1) == 1");
                 // (z(x) - 1) * l(1)
                 let mut contribution = copy_permutation_z_at_z;
-                contribution.sub_assign(&ExtensionField::<F, 2, EXT>::ONE);
+                contribution.sub_assign(&ExtensionField::<F, N, EXT>::ONE);
                 contribution.mul_assign(&unnormalized_l1_inverse_at_z);
                 contribution.mul_assign(&alpha);
 
@@ -1796,8 +2527,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
             assert_eq!(challenges_it.len(), 0, "must exhaust all the challenges");
 
-            let mut t_from_chunks = ExtensionField::<F, 2, EXT>::ZERO;
-            let mut pow = ExtensionField::<F, 2, EXT>::ONE;
+            let mut t_from_chunks = ExtensionField::<F, N, EXT>::ZERO;
+            let mut pow = ExtensionField::<F, N, EXT>::ONE;
             for el in quotient_chunks.into_iter() {
                 let mut tmp = el;
                 tmp.mul_assign(&pow);
@@ -1810,14 +2541,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             // assert_eq!(t_accumulator, t_from_chunks, "unsatisfied at Z",);
 
             if t_accumulator != t_from_chunks {
-                log!("Invalid quotient at Z");
-                return false;
+                return Err(VerificationError::QuotientIdentityFailed);
             }
         }
 
         // get challenges
-        let c0 = transcript.get_challenge();
-        let c1 = transcript.get_challenge();
+        let challenge = transcript.get_multiple_challenges_fixed::<N>();
 
         let mut total_num_challenges = 0;
         total_num_challenges += proof.values_at_z.len();
@@ -1828,135 +2557,592 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
         }
 
         let challenges_for_fri_quotiening =
-            crate::cs::implementations::prover::materialize_ext_challenge_powers::<F, EXT>(
-                (c0, c1),
+            crate::cs::implementations::prover::materialize_ext_challenge_powers::<F, N, EXT>(
+                challenge,
                 total_num_challenges,
             );
 
-        let (
-            new_pow_bits,                 // updated POW bits if needed
-            num_queries,                  // num queries
-            interpolation_log2s_schedule, // folding schedule
-            final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof.proof_config.security_level as u32,
-            proof.proof_config.merkle_tree_cap_size,
-            proof.proof_config.pow_bits,
-            proof.proof_config.fri_lde_factor.trailing_zeros(),
-            vk.fixed_parameters.domain_size.trailing_zeros(),
-        );
+        let omega = domain_generator_for_size::<F>(vk.fixed_parameters.domain_size);
+        let mut z_omega = z;
+        z_omega.mul_assign_by_base(&omega);
 
-        let mut expected_degree = vk.fixed_parameters.domain_size;
+        Ok(OpeningsAtZ {
+            z,
+            z_omega,
+            challenges_for_fri_quotiening,
+            public_input_opening_tuples,
+        })
+    }
+
+    /// Sizes of the witness, second stage and quotient leafs, including the salt
+    pub(crate) fn committed_leaf_sizes<H: TreeHasher<F>>(
+        &self,
+        vk: &VerificationKey<F, H>,
+        proof: &CircuitOpenings<'_, F, H, EXT, N>,
+    ) -> [usize; 3] {
+        let salt_size = proof.salt_size;
+
+        [
+            self.witness_leaf_size(&vk.fixed_parameters) + salt_size,
+            self.stage_2_leaf_size(&vk.fixed_parameters) + salt_size,
+            self.quotient_leaf_size(&vk.fixed_parameters) + proof.num_masking_polys * N + salt_size,
+        ]
+    }
 
-        if new_pow_bits != proof.proof_config.pow_bits {
-            log!("PoW bits computation diverged");
-            return false;
+    /// Checks inclusion of the base oracles' leafs at the queried point, and combines opened
+    /// values into the value of the base FRI oracle of this circuit at the same point
+    pub(crate) fn verify_base_oracles_query<H: TreeHasher<F>>(
+        &self,
+        vk: &VerificationKey<F, H>,
+        proof: &CircuitOpenings<'_, F, H, EXT, N>,
+        openings_at_z: &OpeningsAtZ<F, EXT, N>,
+        query_idx: usize,
+        witness_query: &OracleQuery<F, H>,
+        stage_2_query: &OracleQuery<F, H>,
+        quotient_query: &OracleQuery<F, H>,
+        setup_query: &OracleQuery<F, H>,
+        query_point: &FriQueryPoint<F>,
+    ) -> Result<ExtensionField<F, N, EXT>, VerificationError> {
+        let base_tree_idx = query_point.base_tree_idx;
+        let base_oracle_depth = vk.fixed_parameters.base_oracles_depth();
+        let [witness_leaf_size, stage_2_leaf_size, quotient_leaf_size] =
+            self.committed_leaf_sizes(vk, proof);
+
+        for (query, cap, leaf_size, oracle) in [
+            (
+                witness_query,
+                proof.witness_oracle_cap,
+                witness_leaf_size,
+                OracleType::Witness,
+            ),
+            (
+                stage_2_query,
+                proof.stage_2_oracle_cap,
+                stage_2_leaf_size,
+                OracleType::Stage2,
+            ),
+            (
+                quotient_query,
+                proof.quotient_oracle_cap,
+                quotient_leaf_size,
+                OracleType::Quotient,
+            ),
+            (
+                setup_query,
+                &vk.setup_merkle_tree_cap[..],
+                self.setup_leaf_size(&vk.fixed_parameters),
+                OracleType::Setup,
+            ),
+        ] {
+            verify_oracle_query(
+                query,
+                cap,
+                leaf_size,
+                base_oracle_depth,
+                query_idx,
+                oracle,
+                base_tree_idx,
+            )?;
         }
 
-        let mut fri_intermediate_challenges = vec![];
+        Ok(self.evaluate_base_oracles_at_query(
+            vk,
+            proof,
+            openings_at_z,
+            &witness_query.leaf_elements,
+            &stage_2_query.leaf_elements,
+            &quotient_query.leaf_elements,
+            &setup_query.leaf_elements,
+            query_point,
+        ))
+    }
 
-        {
-            // now witness base FRI oracle
-            if vk.fixed_parameters.cap_size != proof.fri_base_oracle_cap.len() {
-                log!("Cap is malformed");
-                return false;
-            }
-            transcript.witness_merkle_tree_cap(&proof.fri_base_oracle_cap);
+    /// Combines values of the base oracles, opened at the queried point, into the value of
+    /// the base FRI oracle of this circuit at the same point. Leafs must be already checked
+    pub(crate) fn evaluate_base_oracles_at_query<H: TreeHasher<F>>(
+        &self,
+        vk: &VerificationKey<F, H>,
+        proof: &CircuitOpenings<'_, F, H, EXT, N>,
+        openings_at_z: &OpeningsAtZ<F, EXT, N>,
+        witness_leaf: &[F],
+        stage_2_leaf: &[F],
+        quotient_leaf: &[F],
+        setup_leaf: &[F],
+        query_point: &FriQueryPoint<F>,
+    ) -> ExtensionField<F, N, EXT> {
+        let z = openings_at_z.z;
+        let z_omega = openings_at_z.z_omega;
+        let challenges_for_fri_quotiening = &openings_at_z.challenges_for_fri_quotiening;
+        let public_input_opening_tuples = &openings_at_z.public_input_opening_tuples;
+        let domain_element_for_quotiening = query_point.domain_element_for_quotiening;
 
-            let reduction_degree_log_2 = interpolation_log2s_schedule[0];
-            let c0 = transcript.get_challenge();
-            let c1 = transcript.get_challenge();
+        let num_lookup_subarguments = self.num_sublookup_arguments();
+        let num_multiplicities_polys = self.num_multipicities_polys(
+            vk.fixed_parameters.total_tables_len as usize,
+            vk.fixed_parameters.domain_size,
+        );
+        let num_variable_polys = self.num_variable_polys();
+        let num_witness_polys = self.num_witness_polys();
+        let num_constant_polys = self.num_constant_polys(&vk.fixed_parameters);
+        let quotient_degree = self.quotient_degree(&vk.fixed_parameters);
+        let num_copy_permutation_polys = num_variable_polys;
 
-            let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
-            challenge_powers.push((c0, c1));
-            let as_extension = ExtensionField::<F, 2, EXT> {
-                coeffs: [c0, c1],
-                _marker: std::marker::PhantomData,
-            };
+        use crate::cs::implementations::copy_permutation::num_intermediate_partial_product_relations;
+        let num_intermediate_partial_product_relations =
+            num_intermediate_partial_product_relations(num_copy_permutation_polys, quotient_degree);
+
+        // salt is at the end of every leaf, and is only hashed
+        let salt_size = proof.salt_size;
+        let [_, stage_2_leaf_size, quotient_leaf_size] = self.committed_leaf_sizes(vk, proof);
+
+        // now perform the quotiening operation
+        let mut simulated_ext_element = ExtensionField::<F, N, EXT>::ZERO;
+
+        let mut challenge_offset = 0;
+
+        let z_polys_offset = 0;
+        let intermediate_polys_offset = N;
+        let lookup_witness_encoding_polys_offset =
+            intermediate_polys_offset + num_intermediate_partial_product_relations * N;
+        let lookup_multiplicities_encoding_polys_offset =
+            lookup_witness_encoding_polys_offset + num_lookup_subarguments * N;
+        let copy_permutation_polys_offset = 0;
+        let constants_offset = 0 + num_copy_permutation_polys;
+        let lookup_tables_values_offset = 0 + num_copy_permutation_polys + num_constant_polys;
+        let variables_offset = 0;
+        let witness_columns_offset = num_variable_polys;
+        let lookup_multiplicities_offset = witness_columns_offset + num_witness_polys;
+
+        {
+            let cast_from_base = move |el: &[F]| {
+                el.iter()
+                    .map(|el| extension_from_base::<F, N, EXT>(*el))
+                    .collect::<Vec<_>>()
+            };
+
+            let cast_from_extension = move |el: &[F]| {
+                assert_eq!(el.len() % N, 0);
+
+                el.array_chunks::<N>()
+                    .map(|el| ExtensionField::<F, N, EXT>::from_coeff_in_base(*el))
+                    .collect::<Vec<_>>()
+            };
+
+            let mut sources = vec![];
+            // witness
+            sources.extend(cast_from_base(
+                &witness_leaf[variables_offset..(variables_offset + num_variable_polys)],
+            ));
+            sources.extend(cast_from_base(
+                &witness_leaf[witness_columns_offset..(witness_columns_offset + num_witness_polys)],
+            ));
+            // normal setup
+            sources.extend(cast_from_base(
+                &setup_leaf[constants_offset..(constants_offset + num_constant_polys)],
+            ));
+            sources.extend(cast_from_base(
+                &setup_leaf[copy_permutation_polys_offset
+                    ..(copy_permutation_polys_offset + num_copy_permutation_polys)],
+            ));
+            // copy-permutation
+            sources.extend(cast_from_extension(
+                &stage_2_leaf[z_polys_offset..intermediate_polys_offset],
+            ));
+            sources.extend(cast_from_extension(
+                &stage_2_leaf[intermediate_polys_offset..lookup_witness_encoding_polys_offset],
+            ));
+            // lookup if exists
+            sources.extend(cast_from_base(
+                &witness_leaf[lookup_multiplicities_offset
+                    ..(lookup_multiplicities_offset + num_multiplicities_polys)],
+            ));
+            sources.extend(cast_from_extension(
+                &stage_2_leaf[lookup_witness_encoding_polys_offset
+                    ..lookup_multiplicities_encoding_polys_offset],
+            ));
+            sources.extend(cast_from_extension(
+                &stage_2_leaf
+                    [lookup_multiplicities_encoding_polys_offset..(stage_2_leaf_size - salt_size)],
+            ));
+            // lookup setup
+            if self.lookup_parameters.lookup_is_allowed() {
+                let num_lookup_setups = self.lookup_parameters.lookup_width() + 1;
+                sources.extend(cast_from_base(
+                    &setup_leaf[lookup_tables_values_offset
+                        ..(lookup_tables_values_offset + num_lookup_setups)],
+                ));
+            }
+            // quotient and masking polynomials
+            sources.extend(cast_from_extension(
+                &quotient_leaf[..(quotient_leaf_size - salt_size)],
+            ));
+
+            let values_at_z = proof.values_at_z;
+            assert_eq!(sources.len(), values_at_z.len());
+            // log!("Making quotiening at Z");
+            quotening_operation(
+                &mut simulated_ext_element,
+                &sources,
+                values_at_z,
+                domain_element_for_quotiening,
+                z,
+                &challenges_for_fri_quotiening
+                    [challenge_offset..(challenge_offset + sources.len())],
+            );
+            challenge_offset += sources.len();
+
+            // now z*omega
+            let mut sources = vec![];
+            sources.extend(cast_from_extension(
+                &stage_2_leaf[z_polys_offset..intermediate_polys_offset],
+            ));
+
+            let values_at_z_omega = proof.values_at_z_omega;
+            assert_eq!(sources.len(), values_at_z_omega.len());
+            // log!("Making quotiening at Z*omega");
+            quotening_operation(
+                &mut simulated_ext_element,
+                &sources,
+                values_at_z_omega,
+                domain_element_for_quotiening,
+                z_omega,
+                &challenges_for_fri_quotiening
+                    [challenge_offset..(challenge_offset + sources.len())],
+            );
+
+            challenge_offset += sources.len();
+            // now at 0 if lookup is needed
+            if self.lookup_parameters.lookup_is_allowed() {
+                let mut sources = vec![];
+                // witness encoding
+                sources.extend(cast_from_extension(
+                    &stage_2_leaf[lookup_witness_encoding_polys_offset
+                        ..lookup_multiplicities_encoding_polys_offset],
+                ));
+                // multiplicities encoding
+                sources.extend(cast_from_extension(
+                    &stage_2_leaf[lookup_multiplicities_encoding_polys_offset
+                        ..(stage_2_leaf_size - salt_size)],
+                ));
+
+                let values_at_0 = proof.values_at_0;
+                assert_eq!(sources.len(), values_at_0.len());
+                // log!("Making quotiening at 0 for lookups sumchecks");
+                quotening_operation(
+                    &mut simulated_ext_element,
+                    &sources,
+                    values_at_0,
+                    domain_element_for_quotiening,
+                    ExtensionField::<F, N, EXT>::ZERO,
+                    &challenges_for_fri_quotiening
+                        [challenge_offset..(challenge_offset + sources.len())],
+                );
+
+                challenge_offset += sources.len();
+            }
+        }
 
-            let mut current = as_extension;
+        // and public inputs
+        for (open_at, set) in public_input_opening_tuples.iter() {
+            let mut sources = Vec::with_capacity(set.len());
+            let mut values = Vec::with_capacity(set.len());
+            for (column, expected_value) in set.iter() {
+                let el = extension_from_base::<F, N, EXT>(witness_leaf[*column]);
+                sources.push(el);
 
-            for _ in 1..reduction_degree_log_2 {
-                current.square();
-                let [c0, c1] = current.into_coeffs_in_base();
-                challenge_powers.push((c0, c1));
+                let value = extension_from_base::<F, N, EXT>(*expected_value);
+                values.push(value);
             }
+            let num_challenges_required = sources.len();
+            assert_eq!(values.len(), num_challenges_required);
+
+            // log!("Making quotiening at {} for public inputs", open_at);
+
+            let open_at = extension_from_base::<F, N, EXT>(*open_at);
+
+            quotening_operation(
+                &mut simulated_ext_element,
+                &sources,
+                &values,
+                domain_element_for_quotiening,
+                open_at,
+                &challenges_for_fri_quotiening
+                    [challenge_offset..(challenge_offset + sources.len())],
+            );
+
+            challenge_offset += num_challenges_required;
+        }
+
+        assert_eq!(challenge_offset, challenges_for_fri_quotiening.len());
+
+        simulated_ext_element
+    }
+}
+
+/// Checks the size of the opened leaf and its inclusion into the oracle with the given cap
+pub(crate) fn verify_oracle_query<F: SmallField, H: TreeHasher<F>>(
+    query: &OracleQuery<F, H>,
+    cap: &[H::Output],
+    leaf_size: usize,
+    depth: usize,
+    query_idx: usize,
+    oracle: OracleType,
+    leaf_idx: usize,
+) -> Result<(), VerificationError> {
+    if query.leaf_elements.len() != leaf_size {
+        return Err(VerificationError::InvalidLeafSize {
+            query_idx,
+            oracle,
+            expected: leaf_size,
+            got: query.leaf_elements.len(),
+        });
+    }
+    let leaf_hash = H::hash_into_leaf(&query.leaf_elements);
+    if query.proof.len() != depth {
+        return Err(VerificationError::InvalidMerklePathLength {
+            query_idx,
+            oracle,
+            expected: depth,
+            got: query.proof.len(),
+        });
+    }
+    let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
+        &query.proof,
+        cap,
+        leaf_hash,
+        leaf_idx,
+    );
+
+    if is_included == false {
+        return Err(VerificationError::InvalidMerklePath {
+            query_idx,
+            oracle,
+            leaf_idx,
+        });
+    }
+
+    Ok(())
+}
+
+/// Part of the proof of a single circuit that is checked at `z`. It's borrowed either
+/// from a `Proof`, or from one of the circuits of an `AggregatedProof`
+pub(crate) struct CircuitOpenings<
+    'a,
+    F: SmallField,
+    H: TreeHasher<F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize,
+> {
+    pub(crate) merkle_tree_cap_size: usize,
+    pub(crate) fri_lde_factor: usize,
+    pub(crate) public_inputs: &'a [F],
+    pub(crate) witness_oracle_cap: &'a [H::Output],
+    pub(crate) stage_2_oracle_cap: &'a [H::Output],
+    pub(crate) quotient_oracle_cap: &'a [H::Output],
+    pub(crate) values_at_z: &'a [ExtensionField<F, N, EXT>],
+    pub(crate) values_at_z_omega: &'a [ExtensionField<F, N, EXT>],
+    pub(crate) values_at_0: &'a [ExtensionField<F, N, EXT>],
+    pub(crate) salt_size: usize,
+    pub(crate) num_masking_polys: usize,
+}
+
+impl<
+        'a,
+        F: SmallField,
+        H: TreeHasher<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    > CircuitOpenings<'a, F, H, EXT, N>
+{
+    pub(crate) fn from_proof(proof: &'a Proof<F, H, EXT, N>) -> Self {
+        Self {
+            merkle_tree_cap_size: proof.proof_config.merkle_tree_cap_size,
+            fri_lde_factor: proof.proof_config.fri_lde_factor,
+            public_inputs: &proof.public_inputs,
+            witness_oracle_cap: &proof.witness_oracle_cap,
+            stage_2_oracle_cap: &proof.stage_2_oracle_cap,
+            quotient_oracle_cap: &proof.quotient_oracle_cap,
+            values_at_z: &proof.values_at_z,
+            values_at_z_omega: &proof.values_at_z_omega,
+            values_at_0: &proof.values_at_0,
+            salt_size: proof.proof_config.salt_size(),
+            num_masking_polys: proof.proof_config.num_masking_polys(),
+        }
+    }
+}
+
+/// Challenges drawn after the openings at `z` are checked
+pub(crate) struct OpeningsAtZ<F: SmallField, EXT: FieldExtension<N, BaseField = F>, const N: usize>
+{
+    pub(crate) z: ExtensionField<F, N, EXT>,
+    pub(crate) z_omega: ExtensionField<F, N, EXT>,
+    pub(crate) challenges_for_fri_quotiening: Vec<[F; N]>,
+    pub(crate) public_input_opening_tuples: Vec<(F, Vec<(usize, F)>)>,
+}
+
+/// Point of the LDE domain that is queried. It's the same for all the base oracles,
+/// as those are enumerated in the same way
+pub(crate) struct FriQueryPoint<F: SmallField> {
+    pub(crate) base_tree_idx: usize,
+    pub(crate) domain_element_for_quotiening: F,
+    power_chunks: Vec<F>,
+}
+
+/// FRI part of the verification. It doesn't depend on how the base FRI oracle
+/// is formed, so single and aggregated proofs share it
+pub(crate) struct FriVerificationContext<
+    'a,
+    F: SmallField,
+    EXT: FieldExtension<N, BaseField = F>,
+    H: TreeHasher<F>,
+    const N: usize,
+> {
+    pub(crate) num_queries: usize,
+    interpolation_log2s_schedule: Vec<usize>,
+    fri_intermediate_challenges: Vec<Vec<[F; N]>>,
+    fri_base_oracle_cap: &'a [H::Output],
+    fri_intermediate_oracles_caps: &'a [Vec<H::Output>],
+    final_fri_monomials: &'a [Vec<F>; N],
+    base_oracle_depth: usize,
+    max_needed_bits: usize,
+    bools_buffer: crate::cs::implementations::transcript::BoolsBuffer,
+    precomputed_powers: Vec<F>,
+    precomputed_powers_inversed: Vec<F>,
+    interpolation_steps: Vec<F>,
+    _marker: std::marker::PhantomData<EXT>,
+}
+
+impl<
+        'a,
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        H: TreeHasher<F>,
+    > FriVerificationContext<'a, F, EXT, H, N>
+{
+    /// Commits FRI oracles and final monomials of a proof of degree `domain_size` into the
+    /// transcript, draws folding challenges and checks PoW
+    pub(crate) fn new<TR: Transcript<F, CompatibleCap = H::Output>, POW: PoWRunner>(
+        transcript: &mut TR,
+        proof_config: &ProofConfig,
+        domain_size: u64,
+        fri_base_oracle_cap: &'a [H::Output],
+        fri_intermediate_oracles_caps: &'a [Vec<H::Output>],
+        final_fri_monomials: &'a [Vec<F>; N],
+        pow_challenge: u64,
+    ) -> Result<Self, VerificationError> {
+        use crate::cs::implementations::utils::domain_generator_for_size;
+
+        let (
+            new_pow_bits,                 // updated POW bits if needed
+            num_queries,                  // num queries
+            interpolation_log2s_schedule, // folding schedule
+            final_expected_degree,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
+            proof_config.fri_lde_factor.trailing_zeros(),
+            domain_size.trailing_zeros(),
+        )
+        .map_err(VerificationError::InvalidFriSchedule)?;
+        // default schedule is not validated, and may be empty for very small domains
+        crate::cs::implementations::prover::validate_fri_schedule(
+            &interpolation_log2s_schedule,
+            proof_config.merkle_tree_cap_size,
+            proof_config.fri_lde_factor.trailing_zeros(),
+            domain_size.trailing_zeros(),
+        )
+        .map_err(VerificationError::InvalidFriSchedule)?;
+
+        let mut expected_degree = domain_size;
+
+        if new_pow_bits != proof_config.pow_bits {
+            return Err(VerificationError::PowBitsMismatch {
+                expected: new_pow_bits,
+                got: proof_config.pow_bits,
+            });
+        }
+
+        let mut fri_intermediate_challenges = vec![];
+
+        {
+            // now witness base FRI oracle
+            if proof_config.merkle_tree_cap_size != fri_base_oracle_cap.len() {
+                return Err(VerificationError::MalformedCap {
+                    oracle: OracleType::Fri(0),
+                    expected: proof_config.merkle_tree_cap_size,
+                    got: fri_base_oracle_cap.len(),
+                });
+            }
+            transcript.witness_merkle_tree_cap(fri_base_oracle_cap);
+
+            let reduction_degree_log_2 = interpolation_log2s_schedule[0];
+            let challenge_powers =
+                crate::cs::implementations::fri::draw_challenge_powers::<F, N, EXT, TR>(
+                    transcript,
+                    reduction_degree_log_2,
+                );
             expected_degree >>= reduction_degree_log_2;
 
             fri_intermediate_challenges.push(challenge_powers);
         }
 
-        if interpolation_log2s_schedule[1..].len() != proof.fri_intermediate_oracles_caps.len() {
-            log!("Unexpected number of intermediate FRI oracles");
-            return false;
+        if interpolation_log2s_schedule[1..].len() != fri_intermediate_oracles_caps.len() {
+            return Err(VerificationError::InvalidNumberOfFriOracles {
+                expected: interpolation_log2s_schedule[1..].len(),
+                got: fri_intermediate_oracles_caps.len(),
+            });
         }
 
-        for (interpolation_degree_log2, cap) in interpolation_log2s_schedule[1..]
+        for (idx, (interpolation_degree_log2, cap)) in interpolation_log2s_schedule[1..]
             .iter()
-            .zip(proof.fri_intermediate_oracles_caps.iter())
+            .zip(fri_intermediate_oracles_caps.iter())
+            .enumerate()
         {
             // commit new oracle
-            if vk.fixed_parameters.cap_size != cap.len() {
-                log!("Cap is malformed");
-                return false;
+            if proof_config.merkle_tree_cap_size != cap.len() {
+                return Err(VerificationError::MalformedCap {
+                    oracle: OracleType::Fri(idx + 1),
+                    expected: proof_config.merkle_tree_cap_size,
+                    got: cap.len(),
+                });
             }
             transcript.witness_merkle_tree_cap(cap);
 
             // get challenge
             let reduction_degree_log_2 = *interpolation_degree_log2;
-            let c0 = transcript.get_challenge();
-            let c1 = transcript.get_challenge();
-
-            let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
-            challenge_powers.push((c0, c1));
-            let as_extension = ExtensionField::<F, 2, EXT> {
-                coeffs: [c0, c1],
-                _marker: std::marker::PhantomData,
-            };
-
-            let mut current = as_extension;
-
-            for _ in 1..reduction_degree_log_2 {
-                current.square();
-                let [c0, c1] = current.into_coeffs_in_base();
-                challenge_powers.push((c0, c1));
-            }
+            let challenge_powers =
+                crate::cs::implementations::fri::draw_challenge_powers::<F, N, EXT, TR>(
+                    transcript,
+                    reduction_degree_log_2,
+                );
 
             fri_intermediate_challenges.push(challenge_powers);
             expected_degree >>= reduction_degree_log_2;
         }
 
         if final_expected_degree != expected_degree as usize {
-            log!("Expected final degree diverged");
-            return false;
-        }
-
-        if proof.final_fri_monomials[0].len() != proof.final_fri_monomials[1].len() {
-            log!("Monomials coefficients length mismatch");
-            return false;
+            return Err(VerificationError::FinalDegreeMismatch {
+                expected: final_expected_degree,
+                got: expected_degree as usize,
+            });
         }
 
-        if proof.final_fri_monomials[0].len() == 0 || proof.final_fri_monomials[1].len() == 0 {
-            log!("Monomials coefficients length is zero");
-            return false;
-        }
-
-        if expected_degree as usize != proof.final_fri_monomials[0].len() {
-            log!("Unexpected number of monomials in FRI");
-            return false;
-        }
-        if expected_degree as usize != proof.final_fri_monomials[1].len() {
-            log!("Unexpected number of monomials in FRI");
-            return false;
+        if expected_degree == 0
+            || final_fri_monomials
+                .iter()
+                .any(|el| el.len() != expected_degree as usize)
+        {
+            return Err(VerificationError::MalformedFinalMonomials);
         }
 
         // witness monomial coeffs
-        transcript.witness_field_elements(&proof.final_fri_monomials[0]);
-        transcript.witness_field_elements(&proof.final_fri_monomials[1]);
+        for monomials in final_fri_monomials.iter() {
+            transcript.witness_field_elements(monomials);
+        }
 
         if new_pow_bits != 0 {
             log!("Doing PoW verification for {} bits", new_pow_bits);
-            log!("Prover gave challenge 0x{:016x}", proof.pow_challenge);
+            log!("Prover gave challenge 0x{:016x}", pow_challenge);
 
             // pull enough challenges from the transcript
             let mut num_challenges = 256 / F::CHAR_BITS;
@@ -1964,16 +3150,15 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
                 num_challenges += 1;
             }
             let challenges = transcript.get_multiple_challenges(num_challenges);
-            let pow_challenge = proof.pow_challenge;
+            let pow_challenge = pow_challenge;
 
-            let pow_is_valid = POW::verify_from_field_elements(
-                challenges,
-                proof.proof_config.pow_bits,
-                pow_challenge,
-            );
+            let pow_is_valid =
+                POW::verify_from_field_elements(challenges, proof_config.pow_bits, pow_challenge);
             if pow_is_valid == false {
-                log!("PoW is invalid");
-                return false;
+                return Err(VerificationError::InvalidPoW {
+                    pow_bits: proof_config.pow_bits,
+                    challenge: pow_challenge,
+                });
             }
 
             assert!(F::CAPACITY_BITS >= 32);
@@ -1985,20 +3170,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
 
         use crate::cs::implementations::transcript::BoolsBuffer;
 
-        let lde_domain_size: u64 =
-            vk.fixed_parameters.domain_size * proof.proof_config.fri_lde_factor as u64;
+        let lde_domain_size: u64 = domain_size * proof_config.fri_lde_factor as u64;
 
         let max_needed_bits = lde_domain_size.trailing_zeros() as usize;
-        let mut bools_buffer = BoolsBuffer {
+        let bools_buffer = BoolsBuffer {
             available: vec![],
             max_needed: max_needed_bits,
         };
 
-        let num_bits_for_in_coset_index =
-            max_needed_bits - proof.proof_config.fri_lde_factor.trailing_zeros() as usize;
-        let base_tree_index_shift = vk.fixed_parameters.domain_size.trailing_zeros();
-        assert_eq!(num_bits_for_in_coset_index, base_tree_index_shift as usize);
-
         // precompute once, will be handy later
         let mut precomputed_powers = vec![];
         let mut precomputed_powers_inversed = vec![];
@@ -2008,540 +3187,297 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>> Verifier<F, EXT> {
             precomputed_powers_inversed.push(omega.inverse().unwrap());
         }
 
-        let omega = precomputed_powers[vk.fixed_parameters.domain_size.trailing_zeros() as usize];
-
         // we also want to precompute "steps" for different interpolation degrees
-        // e.g. if we interpolate 8 elements,
-        // then those will be ordered as bitreverses of [0..=7], namely
-        // [0, 4, 2, 6, 1, 5, 3, 7]
-
-        // so we want to have exactly half of it, because separation by 4
-        // is exactly -1, so we need [1, sqrt4(1), sqrt8(1), sqrt4(1)*sqrt8(1)]
-
-        let mut interpolation_steps = [F::ONE; 4]; // max size
-        for idx in [1, 3].into_iter() {
-            interpolation_steps[idx].mul_assign(&precomputed_powers_inversed[2]);
-        }
-        for idx in [2, 3].into_iter() {
-            interpolation_steps[idx].mul_assign(&precomputed_powers_inversed[3]);
-        }
-
-        assert_eq!(interpolation_steps[0], F::ONE);
-        assert_eq!(interpolation_steps[1].pow_u64(4), F::ONE);
-        assert_eq!(interpolation_steps[2].pow_u64(8), F::ONE);
-
-        if num_queries != proof.queries_per_fri_repetition.len() {
-            log!(
-                "FRI queries number is invalid: expecting {}, prover provided {}",
-                num_queries,
-                proof.queries_per_fri_repetition.len(),
-            );
-        }
-
-        let base_oracle_depth = vk.fixed_parameters.base_oracles_depth();
-
-        let witness_leaf_size = self.witness_leaf_size(&vk.fixed_parameters);
-
-        let stage_2_leaf_size = self.stage_2_leaf_size(&vk.fixed_parameters);
-        let quotient_leaf_size = self.quotient_leaf_size(&vk.fixed_parameters);
-
-        let setup_leaf_size = self.setup_leaf_size(&vk.fixed_parameters);
-
-        for queries in proof.queries_per_fri_repetition.iter() {
-            let query_index_lsb_first_bits =
-                bools_buffer.get_bits(&mut transcript, max_needed_bits);
-            // we consider it to be some convenient for us encoding of coset + inner index.
-
-            // Small note on indexing: when we commit to elements we use bitreversal enumeration everywhere.
-            // So index `i` in the tree corresponds to the element of `omega^bitreverse(i)`.
-            // This gives us natural separation of LDE cosets, such that subtrees form independent cosets,
-            // and if cosets are in the form of `{1, gamma, ...} x {1, omega, ...} where gamma^lde_factor == omega,
-            // then subtrees are enumerated by bitreverse powers of gamma
-            use crate::cs::implementations::prover::u64_from_lsb_first_bits;
-
-            let inner_idx = u64_from_lsb_first_bits(
-                &query_index_lsb_first_bits[0..num_bits_for_in_coset_index],
-            ) as usize;
-            let coset_idx =
-                u64_from_lsb_first_bits(&query_index_lsb_first_bits[num_bits_for_in_coset_index..])
-                    as usize;
-            let base_tree_idx = (coset_idx << base_tree_index_shift) + inner_idx;
-            // log!("Verifying inclusion at index {}", base_tree_idx);
-
-            // first verify basic inclusion proofs
-            if queries.witness_query.leaf_elements.len() != witness_leaf_size {
-                log!("Invalid leaf size for witness oracle");
-                return false;
-            }
-            let leaf_hash = H::hash_into_leaf(&queries.witness_query.leaf_elements);
-            if queries.witness_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for witness oracle");
-                return false;
-            }
-            let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
-                &queries.witness_query.proof,
-                &proof.witness_oracle_cap,
-                leaf_hash,
-                base_tree_idx as usize,
-            );
-
-            if is_included == false {
-                log!("Witness query not in tree");
-                return false;
-            }
-
-            if queries.stage_2_query.leaf_elements.len() != stage_2_leaf_size {
-                log!("Invalid leaf size for stage 2 oracle");
-                return false;
-            }
-            let leaf_hash = H::hash_into_leaf(&queries.stage_2_query.leaf_elements);
-            if queries.stage_2_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for stage 2 oracle");
-                return false;
-            }
-            let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
-                &queries.stage_2_query.proof,
-                &proof.stage_2_oracle_cap,
-                leaf_hash,
-                base_tree_idx as usize,
-            );
+        let max_interpolation_degree_log2 = interpolation_log2s_schedule
+            .iter()
+            .copied()
+            .max()
+            .expect("schedule is validated");
+        let interpolation_steps = crate::cs::implementations::fri::precompute_interpolation_steps(
+            max_interpolation_degree_log2,
+            &precomputed_powers_inversed,
+        );
 
-            if is_included == false {
-                log!("Stage 2 query not in tree");
-                return false;
-            }
+        let base_oracle_depth = (lde_domain_size.trailing_zeros()
+            - proof_config.merkle_tree_cap_size.trailing_zeros())
+            as usize;
+
+        Ok(Self {
+            num_queries,
+            interpolation_log2s_schedule,
+            fri_intermediate_challenges,
+            fri_base_oracle_cap,
+            fri_intermediate_oracles_caps,
+            final_fri_monomials,
+            base_oracle_depth,
+            max_needed_bits,
+            bools_buffer,
+            precomputed_powers,
+            precomputed_powers_inversed,
+            interpolation_steps,
+            _marker: std::marker::PhantomData,
+        })
+    }
 
-            if queries.quotient_query.leaf_elements.len() != quotient_leaf_size {
-                log!("Invalid leaf size for quotient oracle");
-                return false;
-            }
-            let leaf_hash = H::hash_into_leaf(&queries.quotient_query.leaf_elements);
-            if queries.quotient_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for quotient oracle");
-                return false;
-            }
-            let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
-                &queries.quotient_query.proof,
-                &proof.quotient_oracle_cap,
-                leaf_hash,
-                base_tree_idx as usize,
-            );
+    pub(crate) fn draw_query_point<TR: Transcript<F>>(
+        &mut self,
+        transcript: &mut TR,
+    ) -> FriQueryPoint<F> {
+        let query_index_lsb_first_bits =
+            self.bools_buffer.get_bits(transcript, self.max_needed_bits);
+        // we consider it to be some convenient for us encoding of coset + inner index.
 
-            if is_included == false {
-                log!("Quotient query not in tree");
-                return false;
-            }
+        // Small note on indexing: when we commit to elements we use bitreversal enumeration everywhere.
+        // So index `i` in the tree corresponds to the element of `omega^bitreverse(i)`.
+        // This gives us natural separation of LDE cosets, such that subtrees form independent cosets,
+        // and if cosets are in the form of `{1, gamma, ...} x {1, omega, ...} where gamma^lde_factor == omega,
+        // then subtrees are enumerated by bitreverse powers of gamma. So the index in the base trees
+        // is the coset index followed by the inner index, and it doesn't depend on the domain size
+        use crate::cs::implementations::prover::u64_from_lsb_first_bits;
 
-            if queries.setup_query.leaf_elements.len() != setup_leaf_size {
-                log!("Invalid leaf size for setup oracle");
-                return false;
-            }
-            let leaf_hash = H::hash_into_leaf(&queries.setup_query.leaf_elements);
-            if queries.setup_query.proof.len() != base_oracle_depth {
-                log!("Invalid Merkle proof length for setup oracle");
-                return false;
-            }
-            let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
-                &queries.setup_query.proof,
-                &vk.setup_merkle_tree_cap,
-                leaf_hash,
-                base_tree_idx as usize,
-            );
+        let base_tree_idx = u64_from_lsb_first_bits(&query_index_lsb_first_bits) as usize;
 
-            if is_included == false {
-                log!("Setup query not in tree");
-                return false;
+        assert_eq!(
+            query_index_lsb_first_bits.len(),
+            self.precomputed_powers.len() - 1
+        );
+        let mut domain_element = F::ONE;
+        for (a, b) in query_index_lsb_first_bits
+            .iter()
+            .zip(self.precomputed_powers[1..].iter())
+        {
+            if *a {
+                domain_element.mul_assign(b);
             }
+        }
 
-            // now perform the quotiening operation
-            let mut simulated_ext_element = ExtensionField::<F, 2, EXT>::ZERO;
-
-            assert_eq!(
-                query_index_lsb_first_bits.len(),
-                precomputed_powers.len() - 1
-            );
+        // we will find it handy to have power of the generator with some bits masked to be zero
+        let mut power_chunks = vec![];
+        let mut skip_highest_powers = 0;
+        // TODO: we may save here (in circuits case especially) if we compute recursively
+        for interpolation_degree_log2 in self.interpolation_log2s_schedule.iter() {
             let mut domain_element = F::ONE;
             for (a, b) in query_index_lsb_first_bits
                 .iter()
-                .zip(precomputed_powers[1..].iter())
+                .skip(skip_highest_powers)
+                .zip(self.precomputed_powers_inversed[1..].iter())
+                .skip(*interpolation_degree_log2)
             {
                 if *a {
                     domain_element.mul_assign(b);
                 }
             }
+            skip_highest_powers += *interpolation_degree_log2;
+            power_chunks.push(domain_element);
+        }
 
-            // we will find it handy to have power of the generator with some bits masked to be zero
-            let mut power_chunks = vec![];
-            let mut skip_highest_powers = 0;
-            // TODO: we may save here (in circuits case especially) if we compute recursively
-            for interpolation_degree_log2 in interpolation_log2s_schedule.iter() {
-                let mut domain_element = F::ONE;
-                for (a, b) in query_index_lsb_first_bits
-                    .iter()
-                    .skip(skip_highest_powers)
-                    .zip(precomputed_powers_inversed[1..].iter())
-                    .skip(*interpolation_degree_log2)
-                {
-                    if *a {
-                        domain_element.mul_assign(b);
-                    }
-                }
-                skip_highest_powers += *interpolation_degree_log2;
-                power_chunks.push(domain_element);
-            }
+        // don't forget that we are shifted
+        let mut domain_element_for_quotiening = domain_element;
+        domain_element_for_quotiening.mul_assign(&F::multiplicative_generator());
 
-            // don't forget that we are shifted
-            let mut domain_element_for_quotiening = domain_element;
-            domain_element_for_quotiening.mul_assign(&F::multiplicative_generator());
+        FriQueryPoint {
+            base_tree_idx,
+            domain_element_for_quotiening,
+            power_chunks,
+        }
+    }
 
-            let mut domain_element_for_interpolation = domain_element_for_quotiening;
+    /// Checks that the value of the base FRI oracle at the queried point is correctly folded
+    /// down to the final monomials
+    pub(crate) fn verify_query(
+        &self,
+        query_idx: usize,
+        query_point: &FriQueryPoint<F>,
+        simulated_ext_element: ExtensionField<F, N, EXT>,
+        fri_queries: &[OracleQuery<F, H>],
+    ) -> Result<(), VerificationError> {
+        let mut domain_element_for_interpolation = query_point.domain_element_for_quotiening;
+        let base_coset_inverse = F::multiplicative_generator().inverse().unwrap();
+
+        let mut current_folded_value = simulated_ext_element;
+        let mut subidx = query_point.base_tree_idx;
+        let mut coset_inverse = base_coset_inverse;
+
+        if self.interpolation_log2s_schedule.len() != fri_queries.len() {
+            return Err(VerificationError::InvalidNumberOfFriQueries {
+                query_idx,
+                expected: self.interpolation_log2s_schedule.len(),
+                got: fri_queries.len(),
+            });
+        }
 
-            let mut challenge_offset = 0;
+        let mut expected_fri_query_len = self.base_oracle_depth;
 
-            let z_polys_offset = 0;
-            let intermediate_polys_offset = 2;
-            let lookup_witness_encoding_polys_offset =
-                intermediate_polys_offset + num_intermediate_partial_product_relations * 2;
-            let lookup_multiplicities_encoding_polys_offset =
-                lookup_witness_encoding_polys_offset + num_lookup_subarguments * 2;
-            let copy_permutation_polys_offset = 0;
-            let constants_offset = 0 + num_copy_permutation_polys;
-            let lookup_tables_values_offset = 0 + num_copy_permutation_polys + num_constant_polys;
-            let variables_offset = 0;
-            let witness_columns_offset = num_variable_polys;
-            let lookup_multiplicities_offset = witness_columns_offset + num_witness_polys;
-            let base_coset_inverse = F::multiplicative_generator().inverse().unwrap();
+        for (idx, (interpolation_degree_log2, fri_query)) in self
+            .interpolation_log2s_schedule
+            .iter()
+            .zip(fri_queries.iter())
+            .enumerate()
+        {
+            expected_fri_query_len -= *interpolation_degree_log2;
+            let interpolation_degree = 1 << *interpolation_degree_log2;
+            let subidx_in_leaf = subidx % interpolation_degree;
+            let tree_idx = subidx >> interpolation_degree_log2;
+
+            if fri_query.leaf_elements.len() != interpolation_degree * N {
+                // account for extension here
+                return Err(VerificationError::InvalidLeafSize {
+                    query_idx,
+                    oracle: OracleType::Fri(idx),
+                    expected: interpolation_degree * N,
+                    got: fri_query.leaf_elements.len(),
+                });
+            }
 
+            // leaf is made of the coefficients of the extension elements, one column per coefficient
+            let leaf_columns: Vec<_> = fri_query
+                .leaf_elements
+                .chunks_exact(interpolation_degree)
+                .collect();
+            let expected_value = current_folded_value.into_coeffs_in_base();
+            if leaf_columns
+                .iter()
+                .zip(expected_value.iter())
+                .any(|(column, coeff)| column[subidx_in_leaf] != *coeff)
             {
-                let mut z_omega = z;
-                z_omega.mul_assign_by_base(&omega);
-
-                let cast_from_base = move |el: &[F]| {
-                    el.iter()
-                        .map(|el| ExtensionField::<F, 2, EXT>::from_coeff_in_base([*el, F::ZERO]))
-                        .collect::<Vec<_>>()
-                };
-
-                let cast_from_extension = move |el: &[F]| {
-                    assert_eq!(el.len() % 2, 0);
-
-                    el.array_chunks::<2>()
-                        .map(|[a, b]| ExtensionField::<F, 2, EXT>::from_coeff_in_base([*a, *b]))
-                        .collect::<Vec<_>>()
-                };
-
-                let mut sources = vec![];
-                // witness
-                sources.extend(cast_from_base(
-                    &queries.witness_query.leaf_elements
-                        [variables_offset..(variables_offset + num_variable_polys)],
-                ));
-                sources.extend(cast_from_base(
-                    &queries.witness_query.leaf_elements
-                        [witness_columns_offset..(witness_columns_offset + num_witness_polys)],
-                ));
-                // normal setup
-                sources.extend(cast_from_base(
-                    &queries.setup_query.leaf_elements
-                        [constants_offset..(constants_offset + num_constant_polys)],
-                ));
-                sources.extend(cast_from_base(
-                    &queries.setup_query.leaf_elements[copy_permutation_polys_offset
-                        ..(copy_permutation_polys_offset + num_copy_permutation_polys)],
-                ));
-                // copy-permutation
-                sources.extend(cast_from_extension(
-                    &queries.stage_2_query.leaf_elements[z_polys_offset..intermediate_polys_offset],
-                ));
-                sources.extend(cast_from_extension(
-                    &queries.stage_2_query.leaf_elements
-                        [intermediate_polys_offset..lookup_witness_encoding_polys_offset],
-                ));
-                // lookup if exists
-                sources.extend(cast_from_base(
-                    &queries.witness_query.leaf_elements[lookup_multiplicities_offset
-                        ..(lookup_multiplicities_offset + num_multiplicities_polys)],
-                ));
-                sources.extend(cast_from_extension(
-                    &queries.stage_2_query.leaf_elements[lookup_witness_encoding_polys_offset
-                        ..lookup_multiplicities_encoding_polys_offset],
-                ));
-                sources.extend(cast_from_extension(
-                    &queries.stage_2_query.leaf_elements
-                        [lookup_multiplicities_encoding_polys_offset..],
-                ));
-                // lookup setup
-                if self.lookup_parameters.lookup_is_allowed() {
-                    let num_lookup_setups = self.lookup_parameters.lookup_width() + 1;
-                    sources.extend(cast_from_base(
-                        &queries.setup_query.leaf_elements[lookup_tables_values_offset
-                            ..(lookup_tables_values_offset + num_lookup_setups)],
-                    ));
-                }
-                // quotient
-                sources.extend(cast_from_extension(&queries.quotient_query.leaf_elements));
-
-                let values_at_z = &proof.values_at_z;
-                assert_eq!(sources.len(), values_at_z.len());
-                // log!("Making quotiening at Z");
-                quotening_operation(
-                    &mut simulated_ext_element,
-                    &sources,
-                    values_at_z,
-                    domain_element_for_quotiening,
-                    z,
-                    &challenges_for_fri_quotiening
-                        [challenge_offset..(challenge_offset + sources.len())],
-                );
-                challenge_offset += sources.len();
-
-                // now z*omega
-                let mut sources = vec![];
-                sources.extend(cast_from_extension(
-                    &queries.stage_2_query.leaf_elements[z_polys_offset..intermediate_polys_offset],
-                ));
-
-                let values_at_z_omega = &proof.values_at_z_omega;
-                assert_eq!(sources.len(), values_at_z_omega.len());
-                // log!("Making quotiening at Z*omega");
-                quotening_operation(
-                    &mut simulated_ext_element,
-                    &sources,
-                    values_at_z_omega,
-                    domain_element_for_quotiening,
-                    z_omega,
-                    &challenges_for_fri_quotiening
-                        [challenge_offset..(challenge_offset + sources.len())],
-                );
-
-                challenge_offset += sources.len();
-                // now at 0 if lookup is needed
-                if self.lookup_parameters.lookup_is_allowed() {
-                    let mut sources = vec![];
-                    // witness encoding
-                    sources.extend(cast_from_extension(
-                        &queries.stage_2_query.leaf_elements[lookup_witness_encoding_polys_offset
-                            ..lookup_multiplicities_encoding_polys_offset],
-                    ));
-                    // multiplicities encoding
-                    sources.extend(cast_from_extension(
-                        &queries.stage_2_query.leaf_elements
-                            [lookup_multiplicities_encoding_polys_offset..],
-                    ));
-
-                    let values_at_0 = &proof.values_at_0;
-                    assert_eq!(sources.len(), values_at_0.len());
-                    // log!("Making quotiening at 0 for lookups sumchecks");
-                    quotening_operation(
-                        &mut simulated_ext_element,
-                        &sources,
-                        values_at_0,
-                        domain_element_for_quotiening,
-                        ExtensionField::<F, 2, EXT>::ZERO,
-                        &challenges_for_fri_quotiening
-                            [challenge_offset..(challenge_offset + sources.len())],
-                    );
-
-                    challenge_offset += sources.len();
-                }
+                return Err(VerificationError::FriFoldingMismatch {
+                    query_idx,
+                    fri_step: idx,
+                });
             }
 
-            // and public inputs
-            for (open_at, set) in public_input_opening_tuples.iter() {
-                let mut sources = Vec::with_capacity(set.len());
-                let mut values = Vec::with_capacity(set.len());
-                for (column, expected_value) in set.iter() {
-                    let el = ExtensionField::<F, 2, EXT>::from_coeff_in_base([
-                        queries.witness_query.leaf_elements[*column],
-                        F::ZERO,
-                    ]);
-                    sources.push(el);
-
-                    let value =
-                        ExtensionField::<F, 2, EXT>::from_coeff_in_base([*expected_value, F::ZERO]);
-                    values.push(value);
-                }
-                let num_challenges_required = sources.len();
-                assert_eq!(values.len(), num_challenges_required);
-
-                // log!("Making quotiening at {} for public inputs", open_at);
-
-                let open_at = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*open_at, F::ZERO]);
-
-                quotening_operation(
-                    &mut simulated_ext_element,
-                    &sources,
-                    &values,
-                    domain_element_for_quotiening,
-                    open_at,
-                    &challenges_for_fri_quotiening
-                        [challenge_offset..(challenge_offset + sources.len())],
-                );
-
-                challenge_offset += num_challenges_required;
+            // verify query itself
+            let cap = if idx == 0 {
+                self.fri_base_oracle_cap
+            } else {
+                &self.fri_intermediate_oracles_caps[idx - 1][..]
+            };
+            let leaf_hash = H::hash_into_leaf(&fri_query.leaf_elements);
+            if fri_query.proof.len() != expected_fri_query_len {
+                return Err(VerificationError::InvalidMerklePathLength {
+                    query_idx,
+                    oracle: OracleType::Fri(idx),
+                    expected: expected_fri_query_len,
+                    got: fri_query.proof.len(),
+                });
             }
-
-            assert_eq!(challenge_offset, challenges_for_fri_quotiening.len());
-
-            let mut current_folded_value = simulated_ext_element;
-            let mut subidx = base_tree_idx;
-            let mut coset_inverse = base_coset_inverse;
-
-            if interpolation_log2s_schedule.len() != queries.fri_queries.len() {
-                log!("Invalid number of FRI intermediate oracle queries per repetition");
-                return false;
+            let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
+                &fri_query.proof,
+                cap,
+                leaf_hash,
+                tree_idx as usize,
+            );
+            if is_included == false {
+                return Err(VerificationError::InvalidMerklePath {
+                    query_idx,
+                    oracle: OracleType::Fri(idx),
+                    leaf_idx: tree_idx,
+                });
             }
 
-            let mut expected_fri_query_len = base_oracle_depth;
-
-            for (idx, (interpolation_degree_log2, fri_query)) in interpolation_log2s_schedule
-                .iter()
-                .zip(queries.fri_queries.iter())
-                .enumerate()
-            {
-                expected_fri_query_len -= *interpolation_degree_log2;
-                let interpolation_degree = 1 << *interpolation_degree_log2;
-                let subidx_in_leaf = subidx % interpolation_degree;
-                let tree_idx = subidx >> interpolation_degree_log2;
-
-                let [c0, c1] = current_folded_value.into_coeffs_in_base();
-                if c0 != fri_query.leaf_elements[subidx_in_leaf]
-                    || c1 != fri_query.leaf_elements[interpolation_degree + subidx_in_leaf]
-                {
-                    log!("FRI element is not in the leaf for step {}", idx);
-                    return false;
-                }
-
-                // verify query itself
-                let cap = if idx == 0 {
-                    &proof.fri_base_oracle_cap
-                } else {
-                    &proof.fri_intermediate_oracles_caps[idx - 1]
+            // interpolate
+            let mut elements_to_interpolate = Vec::with_capacity(interpolation_degree);
+            for i in 0..interpolation_degree {
+                let as_ext = ExtensionField::<F, N, EXT> {
+                    coeffs: std::array::from_fn(|j| leaf_columns[j][i]),
+                    _marker: std::marker::PhantomData,
                 };
-                if fri_query.leaf_elements.len() != interpolation_degree * 2 {
-                    // account for extension here
-                    log!("Invalid leaf size for FRI oracle number {}", idx);
-                    return false;
-                }
-                let leaf_hash = H::hash_into_leaf(&fri_query.leaf_elements);
-                if fri_query.proof.len() != expected_fri_query_len {
-                    log!("Invalid Merkle proof length for FRI oracle number {}", idx);
-                    return false;
-                }
-                let is_included = MerkleTreeWithCap::<F, H, Global, Global>::verify_proof_over_cap(
-                    &fri_query.proof,
-                    cap,
-                    leaf_hash,
-                    tree_idx as usize,
-                );
-                if is_included == false {
-                    log!("FRI leaf is not in the tree for step {}", idx);
-                    return false;
-                }
-
-                // interpolate
-                let mut elements_to_interpolate = Vec::with_capacity(interpolation_degree);
-                for (c0, c1) in fri_query.leaf_elements[..interpolation_degree]
-                    .iter()
-                    .zip(fri_query.leaf_elements[interpolation_degree..].iter())
-                {
-                    let as_ext = ExtensionField::<F, 2, EXT> {
-                        coeffs: [*c0, *c1],
-                        _marker: std::marker::PhantomData,
-                    };
-                    elements_to_interpolate.push(as_ext);
-                }
-
-                let mut next = Vec::with_capacity(interpolation_degree / 2);
-                let challenges = &fri_intermediate_challenges[idx];
-                assert_eq!(challenges.len(), *interpolation_degree_log2);
+                elements_to_interpolate.push(as_ext);
+            }
 
-                let mut base_pow = power_chunks[idx];
+            let mut next = Vec::with_capacity(interpolation_degree / 2);
+            let challenges = &self.fri_intermediate_challenges[idx];
+            assert_eq!(challenges.len(), *interpolation_degree_log2);
 
-                for (c0, c1) in challenges.iter() {
-                    let challenge = ExtensionField::<F, 2, EXT> {
-                        coeffs: [*c0, *c1],
-                        _marker: std::marker::PhantomData,
-                    };
-                    for (i, [a, b]) in elements_to_interpolate.array_chunks::<2>().enumerate() {
-                        let mut result = *a;
-                        result.add_assign(b);
-
-                        let mut diff = *a;
-                        diff.sub_assign(b);
-                        diff.mul_assign(&challenge);
-                        // divide by corresponding power
-                        let mut pow = base_pow;
-                        pow.mul_assign(&interpolation_steps[i]);
-                        pow.mul_assign(&coset_inverse);
-                        diff.mul_assign_by_base(&pow);
-
-                        result.add_assign(&diff);
-                        next.push(result);
-                    }
+            let mut base_pow = query_point.power_chunks[idx];
 
-                    std::mem::swap(&mut next, &mut elements_to_interpolate);
-                    next.clear();
-                    base_pow.square();
-                    coset_inverse.square();
+            for challenge in challenges.iter() {
+                let challenge = ExtensionField::<F, N, EXT> {
+                    coeffs: *challenge,
+                    _marker: std::marker::PhantomData,
+                };
+                for (i, [a, b]) in elements_to_interpolate.array_chunks::<2>().enumerate() {
+                    let mut result = *a;
+                    result.add_assign(b);
+
+                    let mut diff = *a;
+                    diff.sub_assign(b);
+                    diff.mul_assign(&challenge);
+                    // divide by corresponding power
+                    let mut pow = base_pow;
+                    pow.mul_assign(&self.interpolation_steps[i]);
+                    pow.mul_assign(&coset_inverse);
+                    diff.mul_assign_by_base(&pow);
+
+                    result.add_assign(&diff);
+                    next.push(result);
                 }
 
-                for _ in 0..*interpolation_degree_log2 {
-                    domain_element_for_interpolation.square();
-                }
+                std::mem::swap(&mut next, &mut elements_to_interpolate);
+                next.clear();
+                base_pow.square();
+                coset_inverse.square();
+            }
 
-                // recompute the index
-                subidx = tree_idx;
-                current_folded_value = elements_to_interpolate[0];
+            for _ in 0..*interpolation_degree_log2 {
+                domain_element_for_interpolation.square();
             }
 
-            // and we should evaluate monomial form and compare
+            // recompute the index
+            subidx = tree_idx;
+            current_folded_value = elements_to_interpolate[0];
+        }
 
-            let mut result_from_monomial = ExtensionField::<F, 2, EXT>::ZERO;
-            // horner rule
-            for (c0, c1) in proof.final_fri_monomials[0]
-                .iter()
-                .zip(proof.final_fri_monomials[1].iter())
-                .rev()
-            {
-                let coeff = ExtensionField::<F, 2, EXT> {
-                    coeffs: [*c0, *c1],
-                    _marker: std::marker::PhantomData,
-                };
+        // and we should evaluate monomial form and compare
 
-                result_from_monomial.mul_assign_by_base(&domain_element_for_interpolation);
-                result_from_monomial.add_assign(&coeff);
-            }
+        let mut result_from_monomial = ExtensionField::<F, N, EXT>::ZERO;
+        // horner rule
+        for i in (0..self.final_fri_monomials[0].len()).rev() {
+            let coeff = ExtensionField::<F, N, EXT> {
+                coeffs: std::array::from_fn(|j| self.final_fri_monomials[j][i]),
+                _marker: std::marker::PhantomData,
+            };
 
-            if result_from_monomial != current_folded_value {
-                log!("Not equal to evaluation from monomials");
-                return false;
-            }
+            result_from_monomial.mul_assign_by_base(&domain_element_for_interpolation);
+            result_from_monomial.add_assign(&coeff);
         }
 
-        true
+        if result_from_monomial != current_folded_value {
+            return Err(VerificationError::FriFinalMonomialsMismatch { query_idx });
+        }
+
+        Ok(())
     }
 }
 
-fn quotening_operation<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
-    dst: &mut ExtensionField<F, 2, EXT>,
-    polynomial_values: &Vec<ExtensionField<F, 2, EXT>>,
-    values_at: &Vec<ExtensionField<F, 2, EXT>>,
+fn extension_from_base<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+    el: F,
+) -> ExtensionField<F, N, EXT> {
+    let mut coeffs = [F::ZERO; N];
+    coeffs[0] = el;
+
+    ExtensionField::<F, N, EXT>::from_coeff_in_base(coeffs)
+}
+
+fn quotening_operation<F: SmallField, const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+    dst: &mut ExtensionField<F, N, EXT>,
+    polynomial_values: &[ExtensionField<F, N, EXT>],
+    values_at: &[ExtensionField<F, N, EXT>],
     domain_element: F,
-    at: ExtensionField<F, 2, EXT>,
-    challenge_coefficients: &[(F, F)],
+    at: ExtensionField<F, N, EXT>,
+    challenge_coefficients: &[[F; N]],
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
     assert_eq!(polynomial_values.len(), values_at.len());
     assert_eq!(polynomial_values.len(), challenge_coefficients.len());
 
-    let mut denom = ExtensionField::<F, 2, EXT>::from_coeff_in_base([domain_element, F::ZERO]);
+    let mut denom = extension_from_base::<F, N, EXT>(domain_element);
     denom.sub_assign(&at);
     denom = denom.inverse().expect("must exist");
 
-    let mut acc = ExtensionField::<F, 2, EXT>::ZERO;
+    let mut acc = ExtensionField::<F, N, EXT>::ZERO;
 
-    for ((poly_value, value_at), (c0, c1)) in polynomial_values
+    for ((poly_value, value_at), challenge) in polynomial_values
         .iter()
         .zip(values_at.iter())
         .zip(challenge_coefficients.iter())
@@ -2550,8 +3486,8 @@ fn quotening_operation<F: SmallField, EXT: FieldExtension<2, BaseField = F>>(
         let mut tmp = *poly_value;
         tmp.sub_assign(value_at);
 
-        let mut as_ext = ExtensionField::<F, 2, EXT> {
-            coeffs: [*c0, *c1],
+        let mut as_ext = ExtensionField::<F, N, EXT> {
+            coeffs: *challenge,
             _marker: std::marker::PhantomData,
         };
 
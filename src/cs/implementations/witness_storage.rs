@@ -119,17 +119,18 @@ impl<
 // here we want trivial context bound to use equalities
 impl<
         F: SmallField,
+        const N: usize,
         P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
         A: GoodAllocator,
         B: GoodAllocator,
-    > SecondStageProductsStorage<F, P, A, B>
+    > SecondStageProductsStorage<F, N, P, A, B>
 {
     pub fn from_base_trace_ext(
-        z_poly: [Arc<GenericPolynomial<F, LagrangeForm, P, A>>; 2],
-        intermediates: Vec<[Arc<GenericPolynomial<F, LagrangeForm, P, A>>; 2], B>,
-        lookup_witness_encoding_polys: Vec<[Arc<GenericPolynomial<F, LagrangeForm, P, A>>; 2], B>,
+        z_poly: [Arc<GenericPolynomial<F, LagrangeForm, P, A>>; N],
+        intermediates: Vec<[Arc<GenericPolynomial<F, LagrangeForm, P, A>>; N], B>,
+        lookup_witness_encoding_polys: Vec<[Arc<GenericPolynomial<F, LagrangeForm, P, A>>; N], B>,
         lookup_multiplicities_encoding_polys: Vec<
-            [Arc<GenericPolynomial<F, LagrangeForm, P, A>>; 2],
+            [Arc<GenericPolynomial<F, LagrangeForm, P, A>>; N],
             B,
         >,
         lde_degree: usize,
@@ -144,7 +145,7 @@ impl<
         let inverse_twiddles = P::precompute_inverse_twiddles_for_fft::<A>(poly_size, worker, ctx);
         let forward_twiddles = P::precompute_forward_twiddles_for_fft::<A>(poly_size, worker, ctx);
 
-        let mut z_polys = Vec::with_capacity_in(2, B::default());
+        let mut z_polys = Vec::with_capacity_in(N, B::default());
         z_polys.extend(z_poly);
 
         let z_polys = transform_from_arcs_to_lde_ext(
@@ -158,7 +159,7 @@ impl<
 
         let num_intermediate_polys = intermediates.len();
         let mut intermediate_polys =
-            Vec::with_capacity_in(num_intermediate_polys * 2, B::default());
+            Vec::with_capacity_in(num_intermediate_polys * N, B::default());
         intermediate_polys.extend(intermediates.into_iter().flatten());
         let intermediate_polys_flattened = transform_from_arcs_to_lde_ext(
             intermediate_polys,
@@ -171,7 +172,7 @@ impl<
 
         let num_lookup_witness_encoding_polys = lookup_witness_encoding_polys.len();
         let mut lookup_witness_encoding =
-            Vec::with_capacity_in(num_lookup_witness_encoding_polys * 2, B::default());
+            Vec::with_capacity_in(num_lookup_witness_encoding_polys * N, B::default());
         lookup_witness_encoding.extend(lookup_witness_encoding_polys.into_iter().flatten());
         let lookup_witness_encoding_polys_flattened = transform_from_arcs_to_lde_ext(
             lookup_witness_encoding,
@@ -184,7 +185,7 @@ impl<
 
         let num_lookup_multiplicities_encoding_polys = lookup_multiplicities_encoding_polys.len();
         let mut lookup_multiplicities_encoding =
-            Vec::with_capacity_in(num_lookup_multiplicities_encoding_polys * 2, B::default());
+            Vec::with_capacity_in(num_lookup_multiplicities_encoding_polys * N, B::default());
         lookup_multiplicities_encoding
             .extend(lookup_multiplicities_encoding_polys.into_iter().flatten());
         let lookup_multiplicities_encoding_polys_flattened = transform_from_arcs_to_lde_ext(
@@ -196,26 +197,26 @@ impl<
             ctx,
         );
 
-        let z_poly = z_polys.into_iter().array_chunks::<2>().next().unwrap();
+        let z_poly = z_polys.into_iter().array_chunks::<N>().next().unwrap();
         let mut intermediate_polys =
-            Vec::with_capacity_in(num_intermediate_polys * 2, B::default());
+            Vec::with_capacity_in(num_intermediate_polys * N, B::default());
         intermediate_polys_flattened
             .into_iter()
-            .array_chunks::<2>()
+            .array_chunks::<N>()
             .collect_into(&mut intermediate_polys);
 
         let mut lookup_witness_encoding_polys =
-            Vec::with_capacity_in(num_lookup_witness_encoding_polys * 2, B::default());
+            Vec::with_capacity_in(num_lookup_witness_encoding_polys * N, B::default());
         lookup_witness_encoding_polys_flattened
             .into_iter()
-            .array_chunks::<2>()
+            .array_chunks::<N>()
             .collect_into(&mut lookup_witness_encoding_polys);
 
         let mut lookup_multiplicities_encoding_polys =
-            Vec::with_capacity_in(num_lookup_multiplicities_encoding_polys * 2, B::default());
+            Vec::with_capacity_in(num_lookup_multiplicities_encoding_polys * N, B::default());
         lookup_multiplicities_encoding_polys_flattened
             .into_iter()
-            .array_chunks::<2>()
+            .array_chunks::<N>()
             .collect_into(&mut lookup_multiplicities_encoding_polys);
 
         SecondStageProductsStorage {
@@ -12,6 +12,44 @@ use super::*;
 
 use crate::field::traits::field_like::Flattener;
 
+/// Order of elements in the leaf, when every leaf takes several consecutive
+/// elements from every source
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum LeafLayout {
+    /// All the elements of the first source, then all the elements of the second one, etc.
+    #[default]
+    SourceMajor,
+    /// First element of every source, then second element of every source, etc.
+    ElementMajor,
+}
+
+impl LeafLayout {
+    /// Appends elements of the leaf `leaf_idx` to `dst` in this layout
+    pub fn collect_leaf<T: Copy>(
+        &self,
+        sources: &[&[T]],
+        leaf_idx: usize,
+        elements_per_leaf: usize,
+        dst: &mut Vec<T>,
+    ) {
+        let range = (leaf_idx * elements_per_leaf)..((leaf_idx + 1) * elements_per_leaf);
+        match self {
+            Self::SourceMajor => {
+                for src in sources.iter() {
+                    dst.extend_from_slice(&src[range.clone()]);
+                }
+            }
+            Self::ElementMajor => {
+                for idx in range {
+                    dst.extend(sources.iter().map(|src| src[idx]));
+                }
+            }
+        }
+    }
+}
+
 #[derive(Derivative, serde::Serialize, serde::Deserialize)]
 #[derivative(Debug, PartialEq(bound = ""), Eq)]
 pub struct MerkleTreeWithCap<
@@ -310,6 +348,43 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
         elements_to_take_per_leaf: usize,
         cap_size: usize,
         worker: &Worker,
+    ) -> Self {
+        let leafs_sources: Vec<&[F]> = leafs_sources.iter().map(|el| &el[..]).collect();
+
+        Self::construct_by_chunking_from_flat_slices(
+            &leafs_sources,
+            elements_to_take_per_leaf,
+            cap_size,
+            worker,
+        )
+    }
+
+    /// Same as `construct_by_chunking_from_flat_sources`, but sources can be any slices,
+    /// e.g. memory mapped files. Every worker only touches a contiguous range of every source,
+    /// so for file backed sources leaf hashes are computed by streaming over the file chunk by chunk.
+    pub fn construct_by_chunking_from_flat_slices(
+        leafs_sources: &[&[F]],
+        elements_to_take_per_leaf: usize,
+        cap_size: usize,
+        worker: &Worker,
+    ) -> Self {
+        Self::construct_by_chunking_from_flat_slices_with_layout(
+            leafs_sources,
+            elements_to_take_per_leaf,
+            LeafLayout::SourceMajor,
+            cap_size,
+            worker,
+        )
+    }
+
+    /// Same as `construct_by_chunking_from_flat_slices`, but elements are placed into
+    /// the leafs in the given layout. Leafs must be opened with the same layout
+    pub fn construct_by_chunking_from_flat_slices_with_layout(
+        leafs_sources: &[&[F]],
+        elements_to_take_per_leaf: usize,
+        leaf_layout: LeafLayout,
+        cap_size: usize,
+        worker: &Worker,
     ) -> Self {
         debug_assert!(cap_size > 0);
         debug_assert!(cap_size.is_power_of_two());
@@ -357,16 +432,15 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
 
                     let mut buffer = Vec::with_capacity(num_sources * elements_to_take_per_leaf);
 
-                    let mut start = 0;
-                    for dst in dst.iter_mut() {
+                    for (leaf_idx, dst) in dst.iter_mut().enumerate() {
                         buffer.clear();
-                        let end = start + elements_to_take_per_leaf;
-                        for src in sources.iter() {
-                            buffer.extend_from_slice(&src[start..end]);
-                        }
+                        leaf_layout.collect_leaf(
+                            &sources,
+                            leaf_idx,
+                            elements_to_take_per_leaf,
+                            &mut buffer,
+                        );
                         dst.write(H::hash_into_leaf(&buffer));
-
-                        start = end;
                     }
                 })
             }
@@ -503,3 +577,278 @@ impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
         cap_el == &current
     }
 }
+
+/// Anything that can produce a cap and Merkle paths of a committed oracle,
+/// no matter where the tree itself is stored
+pub trait MerkleProofSource<F: PrimeField, H: TreeHasher<F>> {
+    fn get_cap(&self) -> Vec<H::Output>;
+    fn get_proof(&self, idx: usize) -> (H::Output, Vec<H::Output>);
+}
+
+impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator> MerkleProofSource<F, H>
+    for MerkleTreeWithCap<F, H, A, B>
+{
+    fn get_cap(&self) -> Vec<H::Output> {
+        MerkleTreeWithCap::get_cap(self).to_vec()
+    }
+
+    fn get_proof(&self, idx: usize) -> (H::Output, Vec<H::Output>) {
+        MerkleTreeWithCap::get_proof::<Global>(self, idx)
+    }
+}
+
+// Multi-proofs merge Merkle paths of many leafs, so that every sibling is included
+// only once, and only if it can not be recomputed from the opened leafs themselves.
+// Siblings are listed layer by layer starting from the leafs, and within a layer in
+// the increasing order of the node index
+impl<F: PrimeField, H: TreeHasher<F>, A: GoodAllocator, B: GoodAllocator>
+    MerkleTreeWithCap<F, H, A, B>
+{
+    /// Returns the deduplicated siblings for the leafs at `indices`. Indexes may repeat
+    /// and come in any order
+    pub fn get_multi_proof<C: GoodAllocator>(&self, indices: &[usize]) -> Vec<H::Output, C> {
+        let depth = self.node_hashes_enumerated_from_leafs.len();
+        let mut result = Vec::new_in(C::default());
+        let mut layer = indices.to_vec();
+        layer.sort_unstable();
+        layer.dedup();
+        for i in 0..depth {
+            let hashes = if i == 0 {
+                &self.leaf_hashes[..]
+            } else {
+                &self.node_hashes_enumerated_from_leafs[i - 1][..]
+            };
+            for_each_node_without_known_pair(&layer, |idx| result.push(hashes[idx ^ 1]));
+
+            for idx in layer.iter_mut() {
+                *idx >>= 1;
+            }
+            layer.dedup();
+        }
+
+        result
+    }
+
+    /// Same as `get_multi_proof`, but takes the siblings from individual paths (as returned by
+    /// `get_proof`) of the leafs at `indices` instead of the tree itself
+    pub fn multi_proof_from_paths(paths: &[&[H::Output]], indices: &[usize]) -> Vec<H::Output> {
+        assert_eq!(paths.len(), indices.len());
+        let depth = paths.iter().map(|el| el.len()).max().unwrap_or(0);
+        let mut result = vec![];
+        let mut layer = indices.to_vec();
+        layer.sort_unstable();
+        layer.dedup();
+        let mut siblings = vec![];
+        for level in 0..depth {
+            siblings.clear();
+            for (path, idx) in paths.iter().zip(indices.iter()) {
+                if let Some(sibling) = path.get(level) {
+                    siblings.push((idx >> level, *sibling));
+                }
+            }
+            siblings.sort_by_key(|el| el.0);
+            for_each_node_without_known_pair(&layer, |idx| {
+                let pos = siblings
+                    .binary_search_by_key(&idx, |el| el.0)
+                    .expect("path for every index must be present");
+                result.push(siblings[pos].1);
+            });
+
+            for idx in layer.iter_mut() {
+                *idx >>= 1;
+            }
+            layer.dedup();
+        }
+
+        result
+    }
+
+    /// Restores individual paths (as returned by `get_proof`) of the leafs at `indices` from
+    /// the multi-proof. Returns `None` if the proof has a wrong number of elements
+    pub fn paths_from_multi_proof(
+        proof: &[H::Output],
+        leaf_hashes: &[H::Output],
+        indices: &[usize],
+        depth: usize,
+    ) -> Option<Vec<Vec<H::Output>>> {
+        Self::fold_multi_proof(proof, leaf_hashes, indices, depth).map(|(paths, _)| paths)
+    }
+
+    /// Checks the multi-proof for the leafs with `leaf_hashes` at `indices`, where `depth`
+    /// is the number of layers between the leafs and the cap
+    pub fn verify_multi_proof_over_cap(
+        proof: &[H::Output],
+        cap: &[H::Output],
+        leaf_hashes: &[H::Output],
+        indices: &[usize],
+        depth: usize,
+    ) -> bool {
+        let Some((_, roots)) = Self::fold_multi_proof(proof, leaf_hashes, indices, depth) else {
+            return false;
+        };
+
+        for (idx, mut root) in roots.into_iter() {
+            H::normalize_output(&mut root);
+            if cap.get(idx) != Some(&root) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // hashes the leafs up to the cap, and returns individual paths and the reached cap elements
+    fn fold_multi_proof(
+        proof: &[H::Output],
+        leaf_hashes: &[H::Output],
+        indices: &[usize],
+        depth: usize,
+    ) -> Option<(Vec<Vec<H::Output>>, Vec<(usize, H::Output)>)> {
+        if leaf_hashes.len() != indices.len() {
+            return None;
+        }
+
+        let mut layer: Vec<_> = indices
+            .iter()
+            .copied()
+            .zip(leaf_hashes.iter().copied())
+            .collect();
+        layer.sort_by_key(|el| el.0);
+        // the same leaf may be opened more than once, but always to the same value
+        for pair in layer.windows(2) {
+            if pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1 {
+                return None;
+            }
+        }
+        layer.dedup_by_key(|el| el.0);
+
+        let mut paths = vec![Vec::with_capacity(depth); indices.len()];
+        let mut proof = proof.iter();
+        let mut siblings = Vec::with_capacity(layer.len() * 2);
+        for level in 0..depth {
+            siblings.clear();
+            let mut next_layer = Vec::with_capacity(layer.len());
+            let mut i = 0;
+            while i < layer.len() {
+                let (idx, current) = layer[i];
+                let (left, right) =
+                    if idx & 1 == 0 && i + 1 < layer.len() && layer[i + 1].0 == idx + 1 {
+                        let pair = layer[i + 1].1;
+                        siblings.push((idx, pair));
+                        siblings.push((idx + 1, current));
+                        i += 2;
+
+                        (current, pair)
+                    } else {
+                        let pair = *proof.next()?;
+                        siblings.push((idx, pair));
+                        i += 1;
+
+                        if idx & 1 == 0 {
+                            (current, pair)
+                        } else {
+                            (pair, current)
+                        }
+                    };
+
+                next_layer.push((idx >> 1, H::hash_into_node(&left, &right, 0)));
+            }
+
+            for (path, idx) in paths.iter_mut().zip(indices.iter()) {
+                let pos = siblings
+                    .binary_search_by_key(&(idx >> level), |el| el.0)
+                    .expect("every node of the layer has a sibling");
+                path.push(siblings[pos].1);
+            }
+
+            layer = next_layer;
+        }
+
+        if proof.next().is_some() {
+            return None;
+        }
+
+        Some((paths, layer))
+    }
+}
+
+// calls `f` for every node of the sorted and deduplicated layer whose pair node is not in the layer
+fn for_each_node_without_known_pair(layer: &[usize], mut f: impl FnMut(usize)) {
+    let mut i = 0;
+    while i < layer.len() {
+        let idx = layer[i];
+        if idx & 1 == 0 && i + 1 < layer.len() && layer[i + 1] == idx + 1 {
+            i += 2;
+        } else {
+            f(idx);
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::field::U64Representable;
+
+    type F = GoldilocksField;
+    type H = blake2::Blake2s256;
+
+    #[test]
+    fn leaf_layouts() {
+        let worker = Worker::new_with_num_threads(4);
+        let elements_per_leaf = 4;
+        let sources: Vec<Vec<F>> = (0..3u64)
+            .map(|column| {
+                (0..64u64)
+                    .map(|el| F::from_u64_unchecked(column * 1000 + el))
+                    .collect()
+            })
+            .collect();
+        let sources: Vec<&[F]> = sources.iter().map(|el| &el[..]).collect();
+
+        let mut buffer = vec![];
+        LeafLayout::ElementMajor.collect_leaf(&sources, 1, elements_per_leaf, &mut buffer);
+        assert_eq!(
+            buffer,
+            [4u64, 1004, 2004, 5, 1005, 2005, 6, 1006, 2006, 7, 1007, 2007]
+                .map(F::from_u64_unchecked)
+        );
+
+        let mut caps = vec![];
+        for layout in [LeafLayout::SourceMajor, LeafLayout::ElementMajor] {
+            let tree =
+                MerkleTreeWithCap::<F, H>::construct_by_chunking_from_flat_slices_with_layout(
+                    &sources,
+                    elements_per_leaf,
+                    layout,
+                    4,
+                    &worker,
+                );
+            let cap = tree.get_cap();
+
+            for leaf_idx in [0, 5, 15] {
+                buffer.clear();
+                layout.collect_leaf(&sources, leaf_idx, elements_per_leaf, &mut buffer);
+                let (leaf_hash, path) = tree.get_proof::<Global>(leaf_idx);
+                assert_eq!(leaf_hash, H::hash_into_leaf(&buffer));
+                assert!(MerkleTreeWithCap::<F, H>::verify_proof_over_cap(
+                    &path, &cap, leaf_hash, leaf_idx
+                ));
+            }
+
+            caps.push(cap);
+        }
+
+        // layout changes the commitment, and the default one is the source major layout
+        assert_ne!(caps[0], caps[1]);
+        let tree = MerkleTreeWithCap::<F, H>::construct_by_chunking_from_flat_slices(
+            &sources,
+            elements_per_leaf,
+            4,
+            &worker,
+        );
+        assert_eq!(tree.get_cap(), caps[0]);
+    }
+}
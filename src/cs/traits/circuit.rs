@@ -42,10 +42,15 @@ use crate::gadgets::recursion::recursive_verifier_builder::CsRecursiveVerifierBu
 
 // Done object-safe traits for convenience, as well as holders
 
-pub trait ErasedBuilderForVerifier<F: SmallField, EXT: FieldExtension<2, BaseField = F>> {
+pub trait ErasedBuilderForVerifier<
+    F: SmallField,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
+>
+{
     fn geometry(&self) -> CSGeometry;
     fn lookup_parameters(&self) -> LookupParameters;
-    fn create_verifier(&self) -> Verifier<F, EXT>;
+    fn create_verifier(&self) -> Verifier<F, EXT, N>;
 }
 
 #[derive(Derivative)]
@@ -57,16 +62,17 @@ pub struct CircuitBuilderProxy<F: SmallField, C: CircuitBuilder<F>> {
 
 impl<F: SmallField, T: CircuitBuilder<F> + 'static> CircuitBuilderProxy<F, T> {
     // Create a boxed builder
-    pub fn dyn_verifier_builder<EXT: FieldExtension<2, BaseField = F>>(
-    ) -> Box<dyn ErasedBuilderForVerifier<F, EXT>> {
+    pub fn dyn_verifier_builder<const N: usize, EXT: FieldExtension<N, BaseField = F>>(
+    ) -> Box<dyn ErasedBuilderForVerifier<F, EXT, N>> {
         Box::<Self>::default()
     }
 
     // Create a boxed builder
     pub fn dyn_recursive_verifier_builder<
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
-    >() -> Box<dyn ErasedBuilderForRecursiveVerifier<F, EXT, CS>> {
+    >() -> Box<dyn ErasedBuilderForRecursiveVerifier<F, EXT, CS, N>> {
         Box::<Self>::default()
     }
 }
@@ -74,8 +80,12 @@ impl<F: SmallField, T: CircuitBuilder<F> + 'static> CircuitBuilderProxy<F, T> {
 // we do not provide default implementation for CircuitBuilder, because those would require non-trivial construction,
 // and instead we provide it for proxy
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, T: CircuitBuilder<F>>
-    ErasedBuilderForVerifier<F, EXT> for CircuitBuilderProxy<F, T>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        T: CircuitBuilder<F>,
+    > ErasedBuilderForVerifier<F, EXT, N> for CircuitBuilderProxy<F, T>
 {
     fn geometry(&self) -> CSGeometry {
         T::geometry()
@@ -83,9 +93,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, T: CircuitBuilder<F>>
     fn lookup_parameters(&self) -> LookupParameters {
         T::lookup_parameters()
     }
-    fn create_verifier(&self) -> Verifier<F, EXT> {
+    fn create_verifier(&self) -> Verifier<F, EXT, N> {
         let geometry = T::geometry();
-        let builder_impl = CsVerifierBuilder::<F, EXT>::new_from_parameters(geometry);
+        let builder_impl = CsVerifierBuilder::<F, EXT, N>::new_from_parameters(geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = T::configure_builder(builder);
@@ -97,21 +107,23 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, T: CircuitBuilder<F>>
 
 pub trait ErasedBuilderForRecursiveVerifier<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
+    const N: usize = 2,
 >
 {
     fn geometry(&self) -> CSGeometry;
     fn lookup_parameters(&self) -> LookupParameters;
-    fn create_recursive_verifier(&self, cs: &mut CS) -> RecursiveVerifier<F, EXT, CS>;
+    fn create_recursive_verifier(&self, cs: &mut CS) -> RecursiveVerifier<F, EXT, CS, N>;
 }
 
 impl<
         F: SmallField,
         CS: ConstraintSystem<F> + 'static,
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         T: CircuitBuilder<F>,
-    > ErasedBuilderForRecursiveVerifier<F, EXT, CS> for CircuitBuilderProxy<F, T>
+    > ErasedBuilderForRecursiveVerifier<F, EXT, CS, N> for CircuitBuilderProxy<F, T>
 {
     fn geometry(&self) -> CSGeometry {
         T::geometry()
@@ -119,10 +131,10 @@ impl<
     fn lookup_parameters(&self) -> LookupParameters {
         T::lookup_parameters()
     }
-    fn create_recursive_verifier(&self, cs: &mut CS) -> RecursiveVerifier<F, EXT, CS> {
+    fn create_recursive_verifier(&self, cs: &mut CS) -> RecursiveVerifier<F, EXT, CS, N> {
         let geometry = T::geometry();
         let builder_impl =
-            CsRecursiveVerifierBuilder::<'_, F, EXT, CS>::new_from_parameters(cs, geometry);
+            CsRecursiveVerifierBuilder::<'_, F, EXT, CS, N>::new_from_parameters(cs, geometry);
         let builder = new_builder::<_, F>(builder_impl);
 
         let builder = T::configure_builder(builder);
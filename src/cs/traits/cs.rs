@@ -76,12 +76,27 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
     // than doing += 1 on some sequential counter
 
     // we can declare some number of witnesses or variables (here we differentiate
-    // by logical meaning), and later on set values
+    // by logical meaning), and later on set values. Allocation functions track the caller,
+    // so CS configured with `CSDebugConfig::TRACK_CALL_SITES` can record where every place comes from
+    #[track_caller]
     fn alloc_variable_without_value(&mut self) -> Variable;
+    #[track_caller]
     fn alloc_multiple_variables_without_values<const N: usize>(&mut self) -> [Variable; N];
+    #[track_caller]
     fn alloc_witness_without_value(&mut self) -> Witness;
+    #[track_caller]
     fn alloc_multiple_witnesses_without_values<const N: usize>(&mut self) -> [Witness; N];
 
+    // Named namespaces are used for debugging and circuit size accounting: CS may track
+    // resources used per namespace, and record them together with call sites. Every push
+    // must be matched by a pop. Resources are accounted if `CSDebugConfig::TRACK_NAMESPACES`
+    // is set (e.g. `DevCSConfig`), and namespaces are used as call site scopes if
+    // `CSDebugConfig::TRACK_CALL_SITES` is set
+    #[inline(always)]
+    fn push_namespace(&mut self, _name: &str) {}
+    #[inline(always)]
+    fn pop_namespace(&mut self) {}
+
     // then in the most generic cases we can declare a values dependency
 
     fn set_values<const N: usize>(&mut self, places: &[Place; N], values: [F; N]);
@@ -108,6 +123,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
 
     // all other convenience functions can be expressed through the machinery above
 
+    #[track_caller]
     #[inline]
     fn alloc_single_variable_from_witness(&mut self, witness: F) -> Variable {
         let new_var = self.alloc_variable_without_value();
@@ -118,6 +134,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
         new_var
     }
 
+    #[track_caller]
     #[inline]
     fn alloc_multiple_variables_from_witnesses<const N: usize>(
         &mut self,
@@ -144,6 +161,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
     // a map of where each particular witness number W will go in the trace table, but for the hot path in circuit witness generation
     // we want to also degrade it down to just bumping a counter
 
+    #[track_caller]
     #[inline]
     fn alloc_single_witness(&mut self, witness: F) -> Witness {
         let new_var = self.alloc_witness_without_value();
@@ -154,6 +172,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
         new_var
     }
 
+    #[track_caller]
     #[inline]
     fn alloc_multiple_witnesses<const N: usize>(&mut self, witnesses: [F; N]) -> [Witness; N] {
         let new_vars = self.alloc_multiple_witnesses_without_values::<N>();
@@ -223,6 +242,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
     // There are for case of "general purpose columns" that may have different gates placed on different rows
     fn place_variable(&mut self, var: Variable, row: usize, column: usize);
     fn place_witness(&mut self, witness: Witness, row: usize, column: usize);
+    #[track_caller]
     fn place_gate<G: Gate<F>>(&mut self, gate: &G, row: usize);
     fn place_constants<const N: usize>(&mut self, constants: &[F; N], row: usize, offset: usize);
     fn place_multiple_variables_into_row<const N: usize>(
@@ -249,6 +269,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
         row: usize,
         column: usize,
     );
+    #[track_caller]
     fn place_gate_specialized<G: Gate<F>>(&mut self, gate: &G, repetition: usize, row: usize);
     fn place_constants_specialized<G: Gate<F>, const N: usize>(
         &mut self,
@@ -265,6 +286,7 @@ pub trait ConstraintSystem<F: SmallField>: Send + Sync {
         starting_column: usize,
     );
 
+    #[track_caller]
     fn perform_lookup<const KEYS: usize, const VALUES: usize>(
         &mut self,
         table_id: u32,
@@ -96,8 +96,10 @@ pub struct GateEvaluationReducingDestination<
     F: PrimeField,
     P: field::traits::field_like::PrimeFieldLikeVectorized<Base = F>,
 > {
-    pub challenges_powers: std::sync::Arc<Vec<[F; 2]>>, // we do not expect too many repetitions of the protocol
-    pub quotient_buffers: std::sync::Arc<[Vec<Vec<P>>; 2]>, // closely mimic structure of LDE storage
+    // gates are evaluated before the extension is known, so those are coefficients of challenges
+    // in the extension of any degree
+    pub challenges_powers: std::sync::Arc<Vec<Vec<F>>>, // we do not expect too many repetitions of the protocol
+    pub quotient_buffers: std::sync::Arc<Vec<Vec<Vec<P>>>>, // closely mimic structure of LDE storage
     pub ctx: P::Context,
 }
 
@@ -109,7 +111,7 @@ pub struct GateEvaluationReducingDestinationChunk<
 > {
     pub destination: GateEvaluationReducingDestination<F, P>,
     pub selectors_view: GenericLdeRowView<F, P>,
-    pub work_buffer: [P; 2], // we store as coefficients of Fp2
+    pub work_buffer: Vec<P>, // we store as coefficients in extension
     base_challenge_offset: usize,
     current_challenge_offset: usize,
 }
@@ -132,10 +134,13 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
     pub fn new(
         inner_size: usize,
         degree: usize,
-        pregenerated_challenges: Vec<[F; 2]>,
+        extension_degree: usize,
+        pregenerated_challenges: Vec<Vec<F>>,
         ctx: &mut P::Context,
     ) -> Self {
-        let buffer: [_; 2] = std::array::from_fn(|_| vec![vec![P::zero(ctx); inner_size]; degree]);
+        let buffer: Vec<_> = (0..extension_degree)
+            .map(|_| vec![vec![P::zero(ctx); inner_size]; degree])
+            .collect();
 
         Self {
             challenges_powers: std::sync::Arc::new(pregenerated_challenges),
@@ -165,7 +170,7 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
         for subiterator in lde_iterators.into_iter() {
             let chunk = GateEvaluationReducingDestinationChunk {
                 destination: self.clone(),
-                work_buffer: [zero; 2],
+                work_buffer: vec![zero; self.quotient_buffers.len()],
                 selectors_view: GenericLdeRowView {
                     over: selectors_storage.clone(),
                     iterator: subiterator,
@@ -199,7 +204,7 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
         for subiterator in lde_iterators.into_iter() {
             let chunk = GateEvaluationReducingDestinationChunk {
                 destination: self.clone(),
-                work_buffer: [zero; 2],
+                work_buffer: vec![zero; self.quotient_buffers.len()],
                 selectors_view: GenericLdeRowView {
                     over: ArcGenericLdeStorage {
                         storage: Vec::new(),
@@ -225,21 +230,15 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
     #[inline(always)]
     fn push_evaluation_result(&mut self, value: P, ctx: &mut P::Context) {
         // simultaneously reduce by multiplying by challenge and adding into accumulator
-        let mut c0 = value;
-        c0.mul_all_by_base(
-            &self.destination.challenges_powers[self.current_challenge_offset][0],
-            ctx,
-        );
-
-        let mut c1 = value;
-        c1.mul_all_by_base(
-            &self.destination.challenges_powers[self.current_challenge_offset][1],
-            ctx,
-        );
-
-        // add into accumulator
-        self.work_buffer[0].add_assign(&c0, ctx);
-        self.work_buffer[1].add_assign(&c1, ctx);
+        for (dst, challenge) in self
+            .work_buffer
+            .iter_mut()
+            .zip(self.destination.challenges_powers[self.current_challenge_offset].iter())
+        {
+            let mut tmp = value;
+            tmp.mul_all_by_base(challenge, ctx);
+            dst.add_assign(&tmp, ctx);
+        }
 
         self.current_challenge_offset += 1;
     }
@@ -288,22 +287,19 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLikeVectorized<Base
         }
 
         let zero = P::zero(ctx);
-        let [mut c0, mut c1] = std::mem::replace(&mut self.work_buffer, [zero; 2]);
-        // mul by selector once
-        c0.mul_assign(&gate_selector_value, ctx);
-        c1.mul_assign(&gate_selector_value, ctx);
-
-        // actually place it. When we created chunks those were non-overlapping, so we can
-        // transitively move statement over non-overlapping to the outer vector too
-        unsafe {
-            std::sync::Arc::get_mut_unchecked(&mut self.destination.quotient_buffers)[0][outer]
-                [inner]
-                .add_assign(&c0, ctx);
-
-            std::sync::Arc::get_mut_unchecked(&mut self.destination.quotient_buffers)[1][outer]
-                [inner]
-                .add_assign(&c1, ctx);
-        };
+        for (idx, dst) in self.work_buffer.iter_mut().enumerate() {
+            let mut value = std::mem::replace(dst, zero);
+            // mul by selector once
+            value.mul_assign(&gate_selector_value, ctx);
+
+            // actually place it. When we created chunks those were non-overlapping, so we can
+            // transitively move statement over non-overlapping to the outer vector too
+            unsafe {
+                std::sync::Arc::get_mut_unchecked(&mut self.destination.quotient_buffers)[idx]
+                    [outer][inner]
+                    .add_assign(&value, ctx);
+            };
+        }
 
         // reset offset of challenges for next row
         self.current_challenge_offset = self.base_challenge_offset;
@@ -101,6 +101,18 @@ impl<F: PrimeField, P: field::traits::field_like::PrimeFieldLike<Base = F>, cons
     }
 }
 
+impl<
+        F: PrimeField,
+        P: field::traits::field_like::PrimeFieldLike<Base = F>,
+        const N: usize,
+        const M: usize,
+    > ConstantsDescription<F, P> for [[P; N]; M]
+{
+    fn external_description(&self) -> Vec<P> {
+        Vec::from_iter(self.iter().flatten().copied())
+    }
+}
+
 // this is 100% NOT object safe trait, by design
 pub trait GateConstraintEvaluator<
     F: PrimeField
@@ -0,0 +1,98 @@
+use super::*;
+use derivative::*;
+
+/// Quadratic extension by x^2 - 11. A field of ~2^62 elements only gives ~62 bits of
+/// soundness for the challenges, so use `BabyBearExt4` for proving
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct BabyBearExt2;
+
+impl std::fmt::Display for BabyBearExt2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BabyBearExt2")
+    }
+}
+
+impl BabyBearExt2 {
+    const NON_RESIDUE: BabyBearField = BabyBearField(11u32);
+}
+
+impl crate::field::FieldExtension<2> for BabyBearExt2 {
+    const TWO_ADICITY: usize = 1;
+
+    type BaseField = BabyBearField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 2]) -> Self::BaseField {
+        crate::field::traits::field::quadratic_extension_norm::<BabyBearField, Self>(el)
+    }
+
+    fn frobenius_coeff() -> Self::BaseField {
+        Self::NON_RESIDUE.pow_u64((BabyBearField::CHAR - 1) / 2)
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 2] {
+        // 13 + x
+        [BabyBearField(13u32), BabyBearField(1u32)]
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
+
+/// Quartic extension by x^4 - 11. As p = 1 mod 4 it's enough for 11 to be a quadratic
+/// non-residue for the polynomial to be irreducible
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct BabyBearExt4;
+
+impl std::fmt::Display for BabyBearExt4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BabyBearExt4")
+    }
+}
+
+impl BabyBearExt4 {
+    const NON_RESIDUE: BabyBearField = BabyBearField(11u32);
+}
+
+impl crate::field::FieldExtension<4> for BabyBearExt4 {
+    // p + 1 and p^2 + 1 both have a single factor of 2
+    const TWO_ADICITY: usize = 2;
+
+    type BaseField = BabyBearField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 4]) -> Self::BaseField {
+        crate::field::traits::field::quartic_extension_norm::<BabyBearField, Self>(el)
+    }
+
+    fn frobenius_coeff() -> Self::BaseField {
+        Self::NON_RESIDUE.pow_u64((BabyBearField::CHAR - 1) / 4)
+    }
+
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 4] {
+        // 8 + x
+        [
+            BabyBearField(8u32),
+            BabyBearField(1u32),
+            BabyBearField(0u32),
+            BabyBearField(0u32),
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
@@ -0,0 +1,454 @@
+use crate::field::{
+    Field, PrimeField, SmallField, SmallFieldRepresentable, U64RawRepresentable, U64Representable,
+};
+use std::hash::{Hash, Hasher};
+
+mod extension;
+mod packed;
+
+pub use self::extension::{BabyBearExt2, BabyBearExt4};
+pub use self::packed::MixedBabyBear;
+
+use super::SqrtField;
+
+/// 31-bit field with a large power of two subgroup.
+///
+/// Its order is 15 * 2^27 + 1.
+/// Elements are always kept in the canonical form, so unlike for Goldilocks there is no
+/// "raw" non-reduced representation
+#[derive(Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[repr(transparent)]
+pub struct BabyBearField(pub u32);
+
+impl BabyBearField {
+    pub const MULTIPLICATIVE_GROUP_GENERATOR: Self = Self(31);
+    pub const RADIX_2_SUBGROUP_GENERATOR: Self = Self(0x1a427a41);
+    pub const ORDER_BITS: usize = 31;
+    pub const ORDER: u32 = 0x78000001;
+    pub const TWO_ADICITY: usize = 27;
+    pub const T: u64 = ((Self::ORDER - 1) >> Self::TWO_ADICITY) as u64;
+
+    #[inline(always)]
+    pub const fn from_u64_with_reduction_impl(value: u64) -> Self {
+        Self((value % (Self::ORDER as u64)) as u32)
+    }
+
+    #[inline(always)]
+    pub const fn to_reduced_u32(&self) -> u32 {
+        self.0
+    }
+
+    const fn compute_shifts() -> [Self; Self::CHAR_BITS] {
+        let mut result = [Self::ZERO; Self::CHAR_BITS];
+        let mut i = 0;
+        while i < Self::CHAR_BITS {
+            result[i] = Self(1u32 << i);
+            i += 1;
+        }
+
+        result
+    }
+
+    #[inline(always)]
+    pub(crate) const fn add_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        // both are below 2^31, so sum fits into u32
+        let mut sum = self.0 + other.0;
+        if sum >= Self::ORDER {
+            sum -= Self::ORDER;
+        }
+        self.0 = sum;
+
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) const fn sub_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        let (mut diff, borrow) = self.0.overflowing_sub(other.0);
+        if borrow {
+            diff = diff.wrapping_add(Self::ORDER);
+        }
+        self.0 = diff;
+
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) const fn mul_assign_impl(&'_ mut self, other: &Self) -> &'_ mut Self {
+        *self = Self::from_u64_with_reduction_impl((self.0 as u64) * (other.0 as u64));
+
+        self
+    }
+}
+
+impl PartialEq for BabyBearField {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<u64> for BabyBearField {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 as u64 == *other
+    }
+}
+
+impl Eq for BabyBearField {}
+
+impl Hash for BabyBearField {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.0)
+    }
+}
+
+impl std::fmt::Display for BabyBearField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl std::fmt::Debug for BabyBearField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{:08x}", self.0)
+    }
+}
+
+impl Field for BabyBearField {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+    const TWO: Self = Self(2);
+    const MINUS_ONE: Self = Self(Self::ORDER - 1);
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline(always)]
+    fn add_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.add_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn sub_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.sub_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn negate(&mut self) -> &mut Self {
+        if self.is_zero() == false {
+            self.0 = Self::ORDER - self.0;
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn mul_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        self.mul_assign_impl(other)
+    }
+
+    #[inline(always)]
+    fn square(&mut self) -> &mut Self {
+        let t = *self;
+        self.mul_assign_impl(&t)
+    }
+
+    #[inline(always)]
+    fn double(&mut self) -> &mut Self {
+        let t = *self;
+        self.add_assign_impl(&t)
+    }
+
+    #[inline(always)]
+    fn from_u64_with_reduction(value: u64) -> Self {
+        Self::from_u64_with_reduction_impl(value)
+    }
+}
+
+impl SqrtField for BabyBearField {
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(*self);
+        }
+
+        // Tonelli-Shanks, same as for Goldilocks
+
+        // any quadratic non-residue
+        const TONELLI_SHANKS_Z: BabyBearField = BabyBearField(11);
+
+        let mut omega = self.pow_u64(BabyBearField::T >> 1);
+        let mut a_omega = omega;
+        a_omega.mul_assign(self);
+        let mut b = a_omega;
+        b.mul_assign(&omega);
+
+        let mut i = 0;
+        let mut a0 = b;
+        while i < BabyBearField::TWO_ADICITY - 1 {
+            a0.square();
+            i += 1;
+        }
+
+        if a0 == BabyBearField::MINUS_ONE {
+            return None;
+        }
+
+        let mut v = BabyBearField::TWO_ADICITY;
+        let mut x = a_omega;
+        let mut z = TONELLI_SHANKS_Z.pow_u64(BabyBearField::T);
+
+        while b != BabyBearField::ONE {
+            let mut k = 0;
+            let mut tmp = b;
+            while tmp != BabyBearField::ONE {
+                tmp.square();
+                k += 1;
+            }
+
+            omega = z;
+
+            let mut i = 0;
+            while i < v - k - 1 {
+                omega.square();
+                i += 1;
+            }
+
+            z = omega;
+            z.square();
+
+            b.mul_assign(&z);
+            x.mul_assign(&omega);
+            v = k;
+        }
+
+        debug_assert!({
+            let mut tmp = x;
+            tmp.square();
+
+            tmp == *self
+        });
+
+        Some(x)
+    }
+}
+
+impl PrimeField for BabyBearField {
+    const CHAR_BITS: usize = Self::ORDER_BITS;
+    const CAPACITY_BITS: usize = Self::ORDER_BITS - 1;
+    const TWO_ADICITY: usize = Self::TWO_ADICITY;
+    const SHIFTS: &'static [Self] = &Self::compute_shifts();
+
+    #[inline(always)]
+    fn multiplicative_generator() -> Self {
+        Self::MULTIPLICATIVE_GROUP_GENERATOR
+    }
+
+    #[inline(always)]
+    fn radix_2_subgroup_generator() -> Self {
+        Self::RADIX_2_SUBGROUP_GENERATOR
+    }
+
+    #[inline(always)]
+    fn frobenius(&self, _power: usize) -> Self {
+        *self
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.pow_u64((Self::ORDER - 2) as u64))
+        }
+    }
+
+    fn legendre(&self) -> super::LegendreSymbol {
+        // s = self^((modulus - 1) // 2)
+        let s = self.pow_u64((Self::CHAR - 1) / 2);
+        if s == Self::ZERO {
+            super::LegendreSymbol::Zero
+        } else if s == Self::ONE {
+            super::LegendreSymbol::QuadraticResidue
+        } else {
+            super::LegendreSymbol::QuadraticNonResidue
+        }
+    }
+}
+
+impl U64RawRepresentable for BabyBearField {
+    #[inline(always)]
+    fn as_raw_u64(self) -> u64 {
+        self.0 as u64
+    }
+
+    #[inline(always)]
+    fn from_raw_u64_unchecked(value: u64) -> Self {
+        Self(value as u32)
+    }
+
+    #[inline(always)]
+    fn from_raw_u64_checked(value: u64) -> Option<Self> {
+        if value >= Self::ORDER as u64 {
+            None
+        } else {
+            Some(Self(value as u32))
+        }
+    }
+
+    #[inline(always)]
+    fn as_raw_u64_array<const N: usize>(input: [Self; N]) -> [u64; N] {
+        input.map(|el| el.0 as u64)
+    }
+}
+
+impl U64Representable for BabyBearField {
+    #[inline(always)]
+    fn as_u64(self) -> u64 {
+        self.0 as u64
+    }
+
+    #[inline(always)]
+    fn from_u64_unchecked(value: u64) -> Self {
+        debug_assert!(value < Self::ORDER as u64);
+        Self(value as u32)
+    }
+
+    #[inline(always)]
+    fn from_u64(value: u64) -> Option<Self> {
+        if value >= Self::ORDER as u64 {
+            None
+        } else {
+            Some(Self(value as u32))
+        }
+    }
+
+    #[inline(always)]
+    fn as_u64_array<const N: usize>(input: [Self; N]) -> [u64; N] {
+        input.map(|el| el.0 as u64)
+    }
+
+    #[inline(always)]
+    fn as_u64_reduced(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+impl SmallFieldRepresentable for BabyBearField {
+    #[inline(always)]
+    fn from_u128_reduced(value: u128) -> Self {
+        Self((value % (Self::ORDER as u128)) as u32)
+    }
+}
+
+impl SmallField for BabyBearField {
+    const CHAR: u64 = Self::ORDER as u64;
+    // a * b + c
+    #[inline(always)]
+    fn fma(a: Self, b: Self, c: Self) -> Self {
+        // (2^31)^2 + 2^31 fits into u64
+        Self::from_u64_with_reduction_impl((a.0 as u64) * (b.0 as u64) + (c.0 as u64))
+    }
+
+    // elements are 4 bytes wide
+    const CAN_CAST_VECTOR_TO_U64_LE_VECTOR: bool = false;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::{ExtensionField, FieldExtension};
+    use rand::{Rng, SeedableRng};
+
+    type F = BabyBearField;
+
+    #[test]
+    fn test_basic_properties() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let p = F::ORDER as u64;
+
+        for _ in 0..1000 {
+            let a: u64 = rng.gen_range(0..p);
+            let b: u64 = rng.gen_range(0..p);
+            let fa = F::from_u64_unchecked(a);
+            let fb = F::from_u64_unchecked(b);
+
+            let mut sum = fa;
+            sum.add_assign(&fb);
+            assert_eq!(sum.as_u64_reduced(), (a + b) % p);
+
+            let mut diff = fa;
+            diff.sub_assign(&fb);
+            assert_eq!(diff.as_u64_reduced(), (a + p - b) % p);
+
+            let mut product = fa;
+            product.mul_assign(&fb);
+            assert_eq!(product.as_u64_reduced(), (a * b) % p);
+
+            if let Some(inverse) = fa.inverse() {
+                let mut tmp = inverse;
+                tmp.mul_assign(&fa);
+                assert_eq!(tmp, F::ONE);
+            } else {
+                assert!(fa.is_zero());
+            }
+
+            let mut square = fa;
+            square.square();
+            assert_eq!(square.sqrt().map(|el| el.pow_u64(2)), Some(square));
+        }
+    }
+
+    #[test]
+    fn test_radix_2_generator() {
+        let generator = F::radix_2_subgroup_generator();
+        let mut tmp = generator.pow_u64(1u64 << (F::TWO_ADICITY - 1));
+        assert_eq!(tmp, F::MINUS_ONE);
+        tmp.square();
+        assert_eq!(tmp, F::ONE);
+    }
+
+    #[test]
+    fn test_extensions() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let a = ExtensionField::<F, 2, BabyBearExt2>::from_coeff_in_base(std::array::from_fn(
+                |_| F::from_u64_with_reduction(rng.gen()),
+            ));
+            let mut product = a.inverse().unwrap();
+            product.mul_assign(&a);
+            assert_eq!(product, ExtensionField::<F, 2, BabyBearExt2>::ONE);
+
+            let a = ExtensionField::<F, 4, BabyBearExt4>::from_coeff_in_base(std::array::from_fn(
+                |_| F::from_u64_with_reduction(rng.gen()),
+            ));
+            let mut product = a.inverse().unwrap();
+            product.mul_assign(&a);
+            assert_eq!(product, ExtensionField::<F, 4, BabyBearExt4>::ONE);
+        }
+    }
+    #[test]
+    fn test_extension_generators() {
+        fn assert_full_order<const N: usize, E: FieldExtension<N, BaseField = F>>(
+            prime_factors: &[u128],
+        ) {
+            let generator = ExtensionField::<F, N, E>::multiplicative_generator();
+            let group_order = (F::ORDER as u128).pow(N as u32) - 1;
+            for factor in prime_factors.iter() {
+                assert_eq!(group_order % factor, 0);
+                let power = group_order / factor;
+                let power = [power as u64, (power >> 64) as u64];
+                assert_ne!(
+                    generator.pow(&power),
+                    ExtensionField::<F, N, E>::ONE,
+                    "generator is in the subgroup of index {}",
+                    factor
+                );
+            }
+        }
+
+        // p^2 - 1 = 2^28 * 3 * 5 * 31 * 32472031
+        assert_full_order::<2, BabyBearExt2>(&[2, 3, 5, 31, 32472031]);
+        // p^4 - 1 = 2^29 * 3 * 5 * 31 * 97 * 12241 * 32472031 * 1706804017873
+        assert_full_order::<4, BabyBearExt4>(&[2, 3, 5, 31, 97, 12241, 32472031, 1706804017873]);
+    }
+}
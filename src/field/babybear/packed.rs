@@ -0,0 +1,377 @@
+use crate::cs::implementations::utils::precompute_twiddles_for_fft;
+use crate::cs::traits::GoodAllocator;
+use crate::field::{Field, PrimeField};
+use crate::worker::Worker;
+
+use super::BabyBearField;
+
+// 16 elements take 64 bytes, so we align to the cache line, that is also enough for any
+// SIMD width. There are no intrinsics here yet, but fixed size loops are vectorized by the compiler
+
+#[derive(Hash, Clone, Copy)]
+#[repr(C, align(64))]
+pub struct MixedBabyBear(pub [BabyBearField; 16]);
+
+impl std::fmt::Debug for MixedBabyBear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl std::fmt::Display for MixedBabyBear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl MixedBabyBear {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self([BabyBearField::ZERO; 16])
+    }
+
+    #[inline(always)]
+    pub fn from_constant(value: BabyBearField) -> Self {
+        Self([value; 16])
+    }
+
+    #[inline(always)]
+    pub fn from_array(value: [BabyBearField; 16]) -> Self {
+        Self(value)
+    }
+}
+
+impl Default for MixedBabyBear {
+    fn default() -> Self {
+        Self([BabyBearField::ZERO; 16])
+    }
+}
+
+impl PartialEq for MixedBabyBear {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for MixedBabyBear {}
+
+impl crate::field::traits::field_like::PrimeFieldLike for MixedBabyBear {
+    type Base = BabyBearField;
+    type Context = ();
+
+    #[inline(always)]
+    fn zero(_ctx: &mut Self::Context) -> Self {
+        Self([BabyBearField::ZERO; 16])
+    }
+    #[inline(always)]
+    fn one(_ctx: &mut Self::Context) -> Self {
+        Self([BabyBearField::ONE; 16])
+    }
+    #[inline(always)]
+    fn minus_one(_ctx: &mut Self::Context) -> Self {
+        Self([BabyBearField::MINUS_ONE; 16])
+    }
+
+    #[inline(always)]
+    #[unroll::unroll_for_loops]
+    fn add_assign(&mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            Field::add_assign(a, b);
+        }
+        self
+    }
+
+    #[inline(always)]
+    #[unroll::unroll_for_loops]
+    fn sub_assign(&'_ mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            Field::sub_assign(a, b);
+        }
+        self
+    }
+
+    #[inline(always)]
+    #[unroll::unroll_for_loops]
+    fn mul_assign(&'_ mut self, other: &Self, _ctx: &mut Self::Context) -> &mut Self {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            Field::mul_assign(a, b);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn square(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+        let t = *self;
+        self.mul_assign(&t, _ctx);
+
+        self
+    }
+
+    #[inline(always)]
+    #[unroll::unroll_for_loops]
+    fn negate(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+        for a in self.0.iter_mut() {
+            Field::negate(a);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn double(&'_ mut self, _ctx: &mut Self::Context) -> &'_ mut Self {
+        let t = *self;
+        self.add_assign(&t, _ctx);
+
+        self
+    }
+
+    #[inline(always)]
+    #[unroll::unroll_for_loops]
+    fn inverse(&self, _ctx: &mut Self::Context) -> Self {
+        let mut result = *self;
+        for i in 0..16 {
+            result.0[i] = PrimeField::inverse(&result.0[i]).expect("inverse must exist");
+        }
+
+        result
+    }
+
+    #[inline(always)]
+    fn constant(value: Self::Base, _ctx: &mut Self::Context) -> Self {
+        Self([value; 16])
+    }
+}
+
+impl crate::field::traits::field_like::PrimeFieldLikeVectorized for MixedBabyBear {
+    type Twiddles<A: GoodAllocator> = Vec<BabyBearField, A>;
+    type InverseTwiddles<A: GoodAllocator> = Vec<BabyBearField, A>;
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.0 == [BabyBearField::ZERO; 16]
+    }
+
+    #[inline(always)]
+    fn equals(&self, other: &Self) -> bool {
+        self.eq(&other)
+    }
+
+    #[inline(always)]
+    #[unroll::unroll_for_loops]
+    fn mul_all_by_base(&'_ mut self, other: &Self::Base, _ctx: &mut Self::Context) -> &'_ mut Self {
+        for a in self.0.iter_mut() {
+            Field::mul_assign(a, &other);
+        }
+        self
+    }
+
+    #[inline(always)]
+    fn slice_from_base_slice(input: &[Self::Base]) -> &[Self] {
+        if input.len() < Self::SIZE_FACTOR {
+            panic!("too small input size to cast");
+        }
+        debug_assert!(input.len() % Self::SIZE_FACTOR == 0);
+        debug_assert!(input.as_ptr().addr() % std::mem::align_of::<Self>() == 0);
+        let result_len = input.len() / 16;
+        unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut Self, result_len) }
+    }
+
+    #[inline(always)]
+    fn slice_into_base_slice(input: &[Self]) -> &[Self::Base] {
+        let result_len = input.len() * 16;
+        unsafe { std::slice::from_raw_parts(input.as_ptr() as *mut BabyBearField, result_len) }
+    }
+
+    #[inline(always)]
+    fn slice_into_base_slice_mut(input: &mut [Self]) -> &mut [Self::Base] {
+        let result_len = input.len() * 16;
+        unsafe { std::slice::from_raw_parts_mut(input.as_ptr() as *mut BabyBearField, result_len) }
+    }
+
+    #[inline(always)]
+    fn vec_from_base_vec<A: GoodAllocator>(input: Vec<Self::Base, A>) -> Vec<Self, A> {
+        if input.len() < Self::SIZE_FACTOR {
+            panic!("too small input size to cast");
+        }
+        let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
+        debug_assert!(ptr.addr() % std::mem::align_of::<Self>() == 0);
+        debug_assert!(len % Self::SIZE_FACTOR == 0);
+        debug_assert!(capacity % Self::SIZE_FACTOR == 0);
+
+        unsafe {
+            Vec::from_raw_parts_in(
+                ptr as _,
+                len / Self::SIZE_FACTOR,
+                capacity / Self::SIZE_FACTOR,
+                allocator,
+            )
+        }
+    }
+
+    #[inline(always)]
+    fn vec_into_base_vec<A: GoodAllocator>(input: Vec<Self, A>) -> Vec<Self::Base, A> {
+        let (ptr, len, capacity, allocator) = input.into_raw_parts_with_alloc();
+
+        unsafe {
+            Vec::from_raw_parts_in(
+                ptr as _,
+                len * Self::SIZE_FACTOR,
+                capacity * Self::SIZE_FACTOR,
+                allocator,
+            )
+        }
+    }
+
+    // there is no FFT over packed elements for this field, so we run the scalar one in place
+    #[inline(always)]
+    fn fft_natural_to_bitreversed<A: GoodAllocator>(
+        input: &mut [Self],
+        coset: Self::Base,
+        twiddles: &Self::Twiddles<A>,
+        _ctx: &mut Self::Context,
+    ) {
+        let input = Self::slice_into_base_slice_mut(input);
+        crate::fft::fft_natural_to_bitreversed::<BabyBearField>(input, coset, twiddles);
+    }
+
+    #[inline(always)]
+    fn ifft_natural_to_natural<A: GoodAllocator>(
+        input: &mut [Self],
+        coset: Self::Base,
+        twiddles: &Self::InverseTwiddles<A>,
+        _ctx: &mut Self::Context,
+    ) {
+        let input = Self::slice_into_base_slice_mut(input);
+        crate::fft::ifft_natural_to_natural::<BabyBearField>(input, coset, twiddles);
+    }
+
+    #[inline(always)]
+    fn precompute_forward_twiddles_for_fft<A: GoodAllocator>(
+        fft_size: usize,
+        worker: &Worker,
+        ctx: &mut Self::Context,
+    ) -> Self::Twiddles<A> {
+        precompute_twiddles_for_fft::<BabyBearField, BabyBearField, A, false>(
+            fft_size, &worker, ctx,
+        )
+    }
+
+    #[inline(always)]
+    fn precompute_inverse_twiddles_for_fft<A: GoodAllocator>(
+        fft_size: usize,
+        worker: &Worker,
+        ctx: &mut Self::Context,
+    ) -> Self::Twiddles<A> {
+        precompute_twiddles_for_fft::<BabyBearField, BabyBearField, A, true>(fft_size, &worker, ctx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::field::rand_from_rng;
+    use crate::field::traits::field_like::{PrimeFieldLike, PrimeFieldLikeVectorized};
+    use crate::utils::clone_respecting_allignment;
+    use std::alloc::Global;
+
+    const POLY_SIZE: usize = 1 << 10;
+
+    fn pack(input: &Vec<BabyBearField>) -> Vec<MixedBabyBear> {
+        MixedBabyBear::vec_from_base_vec(clone_respecting_allignment::<
+            BabyBearField,
+            MixedBabyBear,
+            _,
+        >(input))
+    }
+
+    #[test]
+    fn test_mixed_babybear_arithmetic() {
+        let mut ctx = ();
+        let mut rng = rand::thread_rng();
+
+        let a: Vec<BabyBearField> = (0..POLY_SIZE).map(|_| rand_from_rng(&mut rng)).collect();
+        let b: Vec<BabyBearField> = (0..POLY_SIZE).map(|_| rand_from_rng(&mut rng)).collect();
+
+        let mut sum = a.clone();
+        let mut difference = a.clone();
+        let mut product = a.clone();
+        let mut negated = a.clone();
+        for (((s, d), (p, n)), b) in sum
+            .iter_mut()
+            .zip(difference.iter_mut())
+            .zip(product.iter_mut().zip(negated.iter_mut()))
+            .zip(b.iter())
+        {
+            Field::add_assign(s, b);
+            Field::sub_assign(d, b);
+            Field::mul_assign(p, b);
+            Field::negate(n);
+        }
+
+        let bv = pack(&b);
+        let mut sum_v = pack(&a);
+        let mut difference_v = pack(&a);
+        let mut product_v = pack(&a);
+        let mut negated_v = pack(&a);
+        for (idx, b) in bv.iter().enumerate() {
+            sum_v[idx].add_assign(b, &mut ctx);
+            difference_v[idx].sub_assign(b, &mut ctx);
+            product_v[idx].mul_assign(b, &mut ctx);
+            negated_v[idx].negate(&mut ctx);
+        }
+
+        assert_eq!(MixedBabyBear::vec_into_base_vec(sum_v), sum);
+        assert_eq!(MixedBabyBear::vec_into_base_vec(difference_v), difference);
+        assert_eq!(MixedBabyBear::vec_into_base_vec(product_v), product);
+        assert_eq!(MixedBabyBear::vec_into_base_vec(negated_v), negated);
+    }
+
+    #[test]
+    fn test_mixed_babybear_fft() {
+        let mut ctx = ();
+        let mut rng = rand::thread_rng();
+        let worker = Worker::new();
+        let coset = BabyBearField::MULTIPLICATIVE_GROUP_GENERATOR;
+
+        let a: Vec<BabyBearField> = (0..POLY_SIZE).map(|_| rand_from_rng(&mut rng)).collect();
+
+        let forward_twiddles = BabyBearField::precompute_forward_twiddles_for_fft::<Global>(
+            POLY_SIZE, &worker, &mut ctx,
+        );
+        let mut expected = a.clone();
+        BabyBearField::fft_natural_to_bitreversed(
+            &mut expected,
+            coset,
+            &forward_twiddles,
+            &mut ctx,
+        );
+
+        let forward_twiddles = MixedBabyBear::precompute_forward_twiddles_for_fft::<Global>(
+            POLY_SIZE, &worker, &mut ctx,
+        );
+        let mut av = pack(&a);
+        MixedBabyBear::fft_natural_to_bitreversed(&mut av, coset, &forward_twiddles, &mut ctx);
+        assert_eq!(MixedBabyBear::slice_into_base_slice(&av), &expected[..]);
+
+        let inverse_twiddles = MixedBabyBear::precompute_inverse_twiddles_for_fft::<Global>(
+            POLY_SIZE, &worker, &mut ctx,
+        );
+        let mut av = pack(&a);
+        MixedBabyBear::ifft_natural_to_natural(
+            &mut av,
+            BabyBearField::ONE,
+            &inverse_twiddles,
+            &mut ctx,
+        );
+        MixedBabyBear::fft_natural_to_bitreversed(
+            &mut av,
+            BabyBearField::ONE,
+            &MixedBabyBear::precompute_forward_twiddles_for_fft::<Global>(
+                POLY_SIZE, &worker, &mut ctx,
+            ),
+            &mut ctx,
+        );
+        let mut roundtrip = MixedBabyBear::vec_into_base_vec(av);
+        crate::fft::bitreverse_enumeration_inplace(&mut roundtrip);
+        assert_eq!(roundtrip, a);
+    }
+}
@@ -25,12 +25,17 @@ impl crate::field::FieldExtension<2> for GoldilocksExt2 {
         Self::NON_RESIDUE
     }
 
-    fn compute_norm(_el: &[Self::BaseField; 2]) -> Self::BaseField {
-        todo!()
+    fn compute_norm(el: &[Self::BaseField; 2]) -> Self::BaseField {
+        crate::field::traits::field::quadratic_extension_norm::<GoldilocksField, Self>(el)
     }
 
+    fn frobenius_coeff() -> Self::BaseField {
+        Self::NON_RESIDUE.pow_u64((GoldilocksField::CHAR - 1) / 2)
+    }
+
+    // 11 + x, generator of the group of order p^2 - 1
     fn multiplicative_generator_coeffs() -> [Self::BaseField; 2] {
-        todo!()
+        [GoldilocksField(11u64), GoldilocksField(1u64)]
     }
 
     #[inline(always)]
@@ -45,16 +50,16 @@ impl crate::field::traits::field_like::PrimeFieldLikeExtension<2> for Goldilocks
     type BaseField = GoldilocksField;
 
     fn compute_norm(
-        _el: &[Self::BaseField; 2],
+        el: &[Self::BaseField; 2],
         _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
     ) -> Self::BaseField {
-        todo!()
+        <Self as crate::field::FieldExtension<2>>::compute_norm(el)
     }
 
     fn multiplicative_generator_coeffs(
         _ctx: &mut <Self::BaseField as crate::field::traits::field_like::PrimeFieldLike>::Context,
     ) -> [Self::BaseField; 2] {
-        todo!()
+        <Self as crate::field::FieldExtension<2>>::multiplicative_generator_coeffs()
     }
 
     fn mul_by_non_residue(
@@ -64,3 +69,104 @@ impl crate::field::traits::field_like::PrimeFieldLikeExtension<2> for Goldilocks
         el.mul_assign(&Self::NON_RESIDUE);
     }
 }
+
+/// Cubic extension by x^3 - 7. Multiplicative group of Goldilocks has order divisible by 3,
+/// and 7 is not a cubic residue, so the polynomial is irreducible
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct GoldilocksExt3;
+
+impl std::fmt::Display for GoldilocksExt3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GoldilocksExt3")
+    }
+}
+
+impl GoldilocksExt3 {
+    const NON_RESIDUE: GoldilocksField = GoldilocksField(7u64);
+}
+
+impl crate::field::FieldExtension<3> for GoldilocksExt3 {
+    // p^2 + p + 1 is odd
+    const TWO_ADICITY: usize = 0;
+
+    type BaseField = GoldilocksField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 3]) -> Self::BaseField {
+        crate::field::traits::field::cubic_extension_norm::<GoldilocksField, Self>(el)
+    }
+
+    fn frobenius_coeff() -> Self::BaseField {
+        Self::NON_RESIDUE.pow_u64((GoldilocksField::CHAR - 1) / 3)
+    }
+
+    // 5 + x, generator of the group of order p^3 - 1
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 3] {
+        [
+            GoldilocksField(5u64),
+            GoldilocksField(1u64),
+            GoldilocksField(0u64),
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
+
+/// Quartic extension by x^4 - 7. As p = 1 mod 4 it's enough for 7 to be a quadratic non-residue
+/// for the polynomial to be irreducible
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct GoldilocksExt4;
+
+impl std::fmt::Display for GoldilocksExt4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GoldilocksExt4")
+    }
+}
+
+impl GoldilocksExt4 {
+    const NON_RESIDUE: GoldilocksField = GoldilocksField(7u64);
+}
+
+impl crate::field::FieldExtension<4> for GoldilocksExt4 {
+    // p + 1 and p^2 + 1 both have a single factor of 2
+    const TWO_ADICITY: usize = 2;
+
+    type BaseField = GoldilocksField;
+
+    #[inline(always)]
+    fn non_residue() -> Self::BaseField {
+        Self::NON_RESIDUE
+    }
+
+    fn compute_norm(el: &[Self::BaseField; 4]) -> Self::BaseField {
+        crate::field::traits::field::quartic_extension_norm::<GoldilocksField, Self>(el)
+    }
+
+    fn frobenius_coeff() -> Self::BaseField {
+        Self::NON_RESIDUE.pow_u64((GoldilocksField::CHAR - 1) / 4)
+    }
+
+    // 8 + x, generator of the group of order p^4 - 1
+    fn multiplicative_generator_coeffs() -> [Self::BaseField; 4] {
+        [
+            GoldilocksField(8u64),
+            GoldilocksField(1u64),
+            GoldilocksField(0u64),
+            GoldilocksField(0u64),
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_by_non_residue(el: &mut Self::BaseField) {
+        el.mul_assign(&Self::NON_RESIDUE);
+    }
+}
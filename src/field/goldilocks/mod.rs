@@ -74,7 +74,7 @@ pub use x86_64_asm_impl::*;
 ))]
 pub use avx512_impl::*;
 
-pub use self::extension::GoldilocksExt2;
+pub use self::extension::{GoldilocksExt2, GoldilocksExt3, GoldilocksExt4};
 use self::inversion::try_inverse_u64;
 
 use super::SqrtField;
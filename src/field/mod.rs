@@ -1,3 +1,4 @@
+pub mod babybear;
 pub mod goldilocks;
 pub mod traits;
 
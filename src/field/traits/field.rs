@@ -125,6 +125,7 @@ pub trait Field:
 }
 
 use derivative::Derivative;
+use crate::serde_utils::BigArraySerde;
 
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug, Hash)]
@@ -317,94 +318,226 @@ pub trait FieldExtension<const DEGREE: usize>:
     fn multiplicative_generator_coeffs() -> [Self::BaseField; DEGREE];
     // norm
     fn compute_norm(el: &[Self::BaseField; DEGREE]) -> Self::BaseField;
+    // x^p = frobenius_coeff * x, that is non_residue^((p - 1) / DEGREE)
+    fn frobenius_coeff() -> Self::BaseField;
     // there is no &self paramter here as we do not expect runtime parametrization
     fn mul_by_non_residue(el: &mut Self::BaseField);
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(bound = "F: serde::Serialize + serde::de::DeserializeOwned")]
 pub struct ExtensionField<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> {
-    #[serde(bound(serialize = "[F; DEGREE]: serde::Serialize"))]
-    #[serde(bound(deserialize = "[F; DEGREE]: serde::de::DeserializeOwned"))]
+    #[serde(with = "BigArraySerde")]
     pub coeffs: [F; DEGREE],
     pub _marker: std::marker::PhantomData<E>,
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::cmp::PartialEq
-    for ExtensionField<F, 2, E>
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::cmp::PartialEq
+    for ExtensionField<F, DEGREE, E>
 {
     #[inline(always)]
     fn eq(&self, other: &Self) -> bool {
-        self.coeffs[0].eq(&other.coeffs[0]) && self.coeffs[1].eq(&other.coeffs[1])
+        self.coeffs
+            .iter()
+            .zip(other.coeffs.iter())
+            .all(|(a, b)| a.eq(b))
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::cmp::Eq for ExtensionField<F, 2, E> {}
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::cmp::Eq
+    for ExtensionField<F, DEGREE, E>
+{
+}
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::default::Default
-    for ExtensionField<F, 2, E>
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::hash::Hash
+    for ExtensionField<F, DEGREE, E>
+{
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.coeffs.hash(state);
+    }
+}
+
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::default::Default
+    for ExtensionField<F, DEGREE, E>
 {
     #[inline(always)]
     fn default() -> Self {
-        Self::ZERO
+        Self::from_coeff_in_base([F::ZERO; DEGREE])
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::fmt::Debug for ExtensionField<F, 2, E> {
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::fmt::Debug
+    for ExtensionField<F, DEGREE, E>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "F2{{")?;
-        writeln!(f, "\t{},", self.coeffs[0])?;
-        writeln!(f, "\t{},", self.coeffs[1])?;
+        writeln!(f, "F{}{{", DEGREE)?;
+        for el in self.coeffs.iter() {
+            writeln!(f, "\t{},", el)?;
+        }
         write!(f, "}}")
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> std::fmt::Display for ExtensionField<F, 2, E> {
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> std::fmt::Display
+    for ExtensionField<F, DEGREE, E>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "F2{{")?;
-        writeln!(f, "{},", self.coeffs[0])?;
-        writeln!(f, "{}", self.coeffs[1])?;
+        writeln!(f, "F{}{{", DEGREE)?;
+        for (idx, el) in self.coeffs.iter().enumerate() {
+            if idx == DEGREE - 1 {
+                writeln!(f, "{}", el)?;
+            } else {
+                writeln!(f, "{},", el)?;
+            }
+        }
         writeln!(f, "}}")
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> Field for ExtensionField<F, 2, E> {
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>>
+    ExtensionField<F, DEGREE, E>
+{
+    #[inline(always)]
+    pub fn mul_assign_by_base(&mut self, base: &F) {
+        for el in self.coeffs.iter_mut() {
+            el.mul_assign(base);
+        }
+    }
+    #[inline(always)]
+    pub const fn as_coeffs_in_base(&self) -> &[F; DEGREE] {
+        &self.coeffs
+    }
+    #[inline(always)]
+    pub const fn into_coeffs_in_base(self) -> [F; DEGREE] {
+        self.coeffs
+    }
+    #[inline(always)]
+    pub const fn from_coeff_in_base(coeffs: [F; DEGREE]) -> Self {
+        Self {
+            coeffs,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+// Multiplication in the binomial extension is a schoolbook one with reduction by the non-residue
+#[inline(always)]
+fn binomial_extension_mul<
+    F: Field,
+    const DEGREE: usize,
+    E: FieldExtension<DEGREE, BaseField = F>,
+>(
+    a: &[F; DEGREE],
+    b: &[F; DEGREE],
+) -> [F; DEGREE] {
+    let mut result = [F::ZERO; DEGREE];
+    let mut reduced = [F::ZERO; DEGREE];
+    for i in 0..DEGREE {
+        for j in 0..DEGREE {
+            let mut tmp = a[i];
+            tmp.mul_assign(&b[j]);
+            if i + j < DEGREE {
+                result[i + j].add_assign(&tmp);
+            } else {
+                reduced[i + j - DEGREE].add_assign(&tmp);
+            }
+        }
+    }
+    for (dst, mut src) in result.iter_mut().zip(reduced.into_iter()) {
+        E::mul_by_non_residue(&mut src);
+        dst.add_assign(&src);
+    }
+
+    result
+}
+
+// x^(p^k) = (x^DEGREE)^((p^k - 1) / DEGREE) * x = frobenius_coeff^k * x, as frobenius_coeff is
+// in the base field. Base field coefficients are fixed by the map, so coefficient of x^i
+// is multiplied by frobenius_coeff^(i * k)
+fn binomial_extension_frobenius<
+    F: Field,
+    const DEGREE: usize,
+    E: FieldExtension<DEGREE, BaseField = F>,
+>(
+    el: &[F; DEGREE],
+    power: usize,
+) -> [F; DEGREE] {
+    let multiplier = E::frobenius_coeff().pow_u64((power % DEGREE) as u64);
+    let mut current = F::ONE;
+    let mut result = *el;
+    for dst in result.iter_mut() {
+        dst.mul_assign(&current);
+        current.mul_assign(&multiplier);
+    }
+
+    result
+}
+
+// Quadratic extensions use Karatsuba multiplication and closed form inversion, and extensions
+// of higher degrees are binomial ones, so x^DEGREE = non-residue. Branches over DEGREE are
+// resolved at compile time
+
+impl<F: Field, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> Field
+    for ExtensionField<F, DEGREE, E>
+{
     const ZERO: Self = ExtensionField {
-        coeffs: [F::ZERO; 2],
+        coeffs: [F::ZERO; DEGREE],
         _marker: std::marker::PhantomData,
     };
-    const ONE: Self = ExtensionField {
-        coeffs: [F::ONE, F::ZERO],
-        _marker: std::marker::PhantomData,
+    const ONE: Self = {
+        let mut coeffs = [F::ZERO; DEGREE];
+        coeffs[0] = F::ONE;
+        ExtensionField {
+            coeffs,
+            _marker: std::marker::PhantomData,
+        }
     };
-    const TWO: Self = ExtensionField {
-        coeffs: [F::TWO, F::ZERO],
-        _marker: std::marker::PhantomData,
+    const TWO: Self = {
+        let mut coeffs = [F::ZERO; DEGREE];
+        coeffs[0] = F::TWO;
+        ExtensionField {
+            coeffs,
+            _marker: std::marker::PhantomData,
+        }
     };
-    const MINUS_ONE: Self = ExtensionField {
-        coeffs: [F::MINUS_ONE, F::ZERO],
-        _marker: std::marker::PhantomData,
+    const MINUS_ONE: Self = {
+        let mut coeffs = [F::ZERO; DEGREE];
+        coeffs[0] = F::MINUS_ONE;
+        ExtensionField {
+            coeffs,
+            _marker: std::marker::PhantomData,
+        }
     };
     #[inline]
     fn is_zero(&self) -> bool {
-        self.coeffs[0].is_zero() && self.coeffs[1].is_zero()
+        self.coeffs.iter().all(|el| el.is_zero())
     }
     #[inline]
     fn add_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
-        self.coeffs[0].add_assign(&other.coeffs[0]);
-        self.coeffs[1].add_assign(&other.coeffs[1]);
+        for (dst, src) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            dst.add_assign(src);
+        }
 
         self
     }
     #[inline]
     fn sub_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
-        self.coeffs[0].sub_assign(&other.coeffs[0]);
-        self.coeffs[1].sub_assign(&other.coeffs[1]);
+        for (dst, src) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            dst.sub_assign(src);
+        }
 
         self
     }
     #[inline]
     fn mul_assign(&'_ mut self, other: &Self) -> &'_ mut Self {
+        if DEGREE != 2 {
+            self.coeffs = binomial_extension_mul::<F, DEGREE, E>(&self.coeffs, &other.coeffs);
+
+            return self;
+        }
+
         let mut v0 = self.coeffs[0];
         v0.mul_assign(&other.coeffs[0]);
         let mut v1 = self.coeffs[1];
@@ -426,6 +559,12 @@ impl<F: Field, E: FieldExtension<2, BaseField = F>> Field for ExtensionField<F,
     }
     #[inline]
     fn square(&mut self) -> &mut Self {
+        if DEGREE != 2 {
+            self.coeffs = binomial_extension_mul::<F, DEGREE, E>(&self.coeffs, &self.coeffs);
+
+            return self;
+        }
+
         let mut v0 = self.coeffs[0];
         v0.sub_assign(&self.coeffs[1]);
         let mut v3 = self.coeffs[0];
@@ -447,28 +586,35 @@ impl<F: Field, E: FieldExtension<2, BaseField = F>> Field for ExtensionField<F,
     }
     #[inline]
     fn negate(&mut self) -> &mut Self {
-        self.coeffs[0].negate();
-        self.coeffs[1].negate();
+        for el in self.coeffs.iter_mut() {
+            el.negate();
+        }
 
         self
     }
     #[inline]
     fn double(&mut self) -> &mut Self {
-        self.coeffs[0].double();
-        self.coeffs[1].double();
+        for el in self.coeffs.iter_mut() {
+            el.double();
+        }
 
         self
     }
     #[inline]
     fn from_u64_with_reduction(value: u64) -> Self {
+        let mut coeffs = [F::ZERO; DEGREE];
+        coeffs[0] = F::from_u64_with_reduction(value);
+
         ExtensionField {
-            coeffs: [F::from_u64_with_reduction(value), F::ZERO],
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
 }
 
-impl<F: PrimeField, E: FieldExtension<2, BaseField = F>> PrimeField for ExtensionField<F, 2, E> {
+impl<F: PrimeField, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> PrimeField
+    for ExtensionField<F, DEGREE, E>
+{
     const CAPACITY_BITS: usize = 1;
     const CHAR_BITS: usize = F::CHAR_BITS;
     const TWO_ADICITY: usize = 0;
@@ -482,70 +628,145 @@ impl<F: PrimeField, E: FieldExtension<2, BaseField = F>> PrimeField for Extensio
         unreachable!()
     }
     fn inverse(&self) -> Option<Self> {
-        let mut v0 = self.coeffs[0];
-        v0.square();
-        let mut v1 = self.coeffs[1];
-        v1.square();
-        // v0 = v0 - beta * v1
-        let mut v1_by_nonresidue = v1;
-        E::mul_by_non_residue(&mut v1_by_nonresidue);
-        v0.sub_assign(&v1_by_nonresidue);
-        match v0.inverse() {
-            Some(inversed) => {
-                let mut c0 = self.coeffs[0];
-                c0.mul_assign(&inversed);
-                let mut c1 = self.coeffs[1];
-                c1.mul_assign(&inversed);
-                c1.negate();
-
-                let new = Self {
-                    coeffs: [c0, c1],
-                    _marker: std::marker::PhantomData,
-                };
-
-                Some(new)
+        // a^-1 = conj(a) / N(a), where conj(a) is a product of all non-trivial conjugates,
+        // so for the quadratic case it's just (a0, -a1)
+        let norm_inversed = E::compute_norm(&self.coeffs).inverse()?;
+        let mut result = if DEGREE == 2 {
+            let mut conjugate = self.coeffs;
+            conjugate[1].negate();
+
+            Self::from_coeff_in_base(conjugate)
+        } else {
+            let mut conjugate = Self::ONE;
+            for power in 1..DEGREE {
+                conjugate.mul_assign(&self.frobenius(power));
             }
-            None => None,
-        }
+
+            conjugate
+        };
+        result.mul_assign_by_base(&norm_inversed);
+
+        Some(result)
     }
-    fn frobenius(&self, _power: usize) -> Self {
-        unimplemented!()
+    fn frobenius(&self, power: usize) -> Self {
+        Self::from_coeff_in_base(binomial_extension_frobenius::<F, DEGREE, E>(
+            &self.coeffs,
+            power,
+        ))
     }
     fn legendre(&self) -> LegendreSymbol {
-        unimplemented!()
+        E::compute_norm(&self.coeffs).legendre()
     }
 }
 
-impl<F: Field, E: FieldExtension<2, BaseField = F>> ExtensionField<F, 2, E> {
-    #[inline(always)]
-    pub fn mul_assign_by_base(&mut self, base: &F)
-    where
-        F: Field,
-        E: FieldExtension<2, BaseField = F>,
-    {
-        self.coeffs[0].mul_assign(base);
-        self.coeffs[1].mul_assign(base);
-    }
-    #[inline(always)]
-    pub const fn as_coeffs_in_base(&self) -> &[F; 2] {
-        &self.coeffs
-    }
-    #[inline(always)]
-    pub const fn into_coeffs_in_base(self) -> [F; 2] {
-        self.coeffs
-    }
-    #[inline(always)]
-    pub const fn from_coeff_in_base(coeffs: [F; 2]) -> Self {
-        Self {
-            coeffs,
-            _marker: std::marker::PhantomData,
-        }
-    }
+/// Norm of the element of the quadratic binomial extension
+pub fn quadratic_extension_norm<F: Field, E: FieldExtension<2, BaseField = F>>(el: &[F; 2]) -> F {
+    // a0^2 - w * a1^2
+    let mut result = el[0];
+    result.square();
+    let mut tmp = el[1];
+    tmp.square();
+    E::mul_by_non_residue(&mut tmp);
+    result.sub_assign(&tmp);
+
+    result
+}
+
+/// Norm of the element of the cubic binomial extension
+pub fn cubic_extension_norm<F: Field, E: FieldExtension<3, BaseField = F>>(el: &[F; 3]) -> F {
+    let [c0, c1, c2] = cubic_extension_adjugate::<F, E>(el);
+    // a0 * c0 + w * (a2 * c1 + a1 * c2)
+    let mut t = el[2];
+    t.mul_assign(&c1);
+    let mut tmp = el[1];
+    tmp.mul_assign(&c2);
+    t.add_assign(&tmp);
+    E::mul_by_non_residue(&mut t);
+    let mut result = el[0];
+    result.mul_assign(&c0);
+    result.add_assign(&t);
+
+    result
+}
+
+#[inline(always)]
+fn cubic_extension_adjugate<F: Field, E: FieldExtension<3, BaseField = F>>(el: &[F; 3]) -> [F; 3] {
+    let [a0, a1, a2] = *el;
+    // c0 = a0^2 - w * a1 * a2
+    let mut c0 = a0;
+    c0.square();
+    let mut tmp = a1;
+    tmp.mul_assign(&a2);
+    E::mul_by_non_residue(&mut tmp);
+    c0.sub_assign(&tmp);
+    // c1 = w * a2^2 - a0 * a1
+    let mut c1 = a2;
+    c1.square();
+    E::mul_by_non_residue(&mut c1);
+    let mut tmp = a0;
+    tmp.mul_assign(&a1);
+    c1.sub_assign(&tmp);
+    // c2 = a1^2 - a0 * a2
+    let mut c2 = a1;
+    c2.square();
+    let mut tmp = a0;
+    tmp.mul_assign(&a2);
+    c2.sub_assign(&tmp);
+
+    [c0, c1, c2]
+}
+
+// for x^4 = w we use that a * conj(a), where conj(x) = -x, belongs to the subfield F[x^2]
+#[inline(always)]
+fn quartic_extension_product_with_conjugate<F: Field, E: FieldExtension<4, BaseField = F>>(
+    el: &[F; 4],
+) -> [F; 4] {
+    let [a0, a1, a2, a3] = *el;
+    // b0 = a0^2 + w * a2^2 - 2 * w * a1 * a3
+    let mut b0 = a2;
+    b0.square();
+    let mut tmp = a1;
+    tmp.mul_assign(&a3);
+    tmp.double();
+    b0.sub_assign(&tmp);
+    E::mul_by_non_residue(&mut b0);
+    let mut tmp = a0;
+    tmp.square();
+    b0.add_assign(&tmp);
+    // b2 = 2 * a0 * a2 - a1^2 - w * a3^2
+    let mut b2 = a0;
+    b2.mul_assign(&a2);
+    b2.double();
+    let mut tmp = a1;
+    tmp.square();
+    b2.sub_assign(&tmp);
+    let mut tmp = a3;
+    tmp.square();
+    E::mul_by_non_residue(&mut tmp);
+    b2.sub_assign(&tmp);
+
+    [b0, F::ZERO, b2, F::ZERO]
+}
+
+/// Norm of the element of the quartic binomial extension
+pub fn quartic_extension_norm<F: Field, E: FieldExtension<4, BaseField = F>>(el: &[F; 4]) -> F {
+    let [b0, _, b2, _] = quartic_extension_product_with_conjugate::<F, E>(el);
+    // b0^2 - w * b2^2
+    let mut result = b0;
+    result.square();
+    let mut tmp = b2;
+    tmp.square();
+    E::mul_by_non_residue(&mut tmp);
+    result.sub_assign(&tmp);
+
+    result
 }
 
 #[cfg(test)]
 mod test {
     use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+    use crate::field::SmallField;
+    use crypto_bigint::U256;
 
     use super::*;
 
@@ -605,4 +826,161 @@ mod test {
 
         assert_eq!(product, F::ONE);
     }
+
+    fn check_higher_degree_extension<const DEGREE: usize, E>()
+    where
+        E: FieldExtension<DEGREE, BaseField = Base>,
+        ExtensionField<Base, DEGREE, E>: PrimeField,
+    {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        for _ in 0..100 {
+            let a =
+                ExtensionField::<Base, DEGREE, E>::from_coeff_in_base(std::array::from_fn(|_| {
+                    Base::from_u64_with_reduction(rng.gen())
+                }));
+            let b =
+                ExtensionField::<Base, DEGREE, E>::from_coeff_in_base(std::array::from_fn(|_| {
+                    Base::from_u64_with_reduction(rng.gen())
+                }));
+
+            let mut ab = a;
+            ab.mul_assign(&b);
+            let mut ba = b;
+            ba.mul_assign(&a);
+            assert_eq!(ab, ba);
+
+            let mut square_by_mul = a;
+            square_by_mul.mul_assign(&a);
+            let mut square_by_square = a;
+            square_by_square.square();
+            assert_eq!(square_by_square, square_by_mul);
+
+            let mut double_by_mul = a;
+            double_by_mul.mul_assign(&ExtensionField::<Base, DEGREE, E>::TWO);
+            let mut double_by_add = a;
+            double_by_add.add_assign(&a);
+            assert_eq!(double_by_add, double_by_mul);
+
+            // norm is multiplicative
+            let mut norm_product = E::compute_norm(a.as_coeffs_in_base());
+            norm_product.mul_assign(&E::compute_norm(b.as_coeffs_in_base()));
+            assert_eq!(E::compute_norm(ab.as_coeffs_in_base()), norm_product);
+
+            let mut product = a.inverse().unwrap();
+            product.mul_assign(&a);
+            assert_eq!(product, ExtensionField::<Base, DEGREE, E>::ONE);
+
+            // Frobenius is exponentiation by p, and it's an automorphism of order DEGREE
+            assert_eq!(a.frobenius(1), a.pow_u64(Base::CHAR));
+            let mut frobenius_of_product = a.frobenius(2);
+            frobenius_of_product.mul_assign(&b.frobenius(2));
+            assert_eq!(ab.frobenius(2), frobenius_of_product);
+            assert_eq!(a.frobenius(DEGREE), a);
+
+            // Euler's criterion over the extension
+            let exp = field_order(DEGREE).wrapping_sub(&U256::ONE).shr_vartime(1);
+            let expected = match a.pow(&exp.to_words()) {
+                el if el == ExtensionField::<Base, DEGREE, E>::ONE => {
+                    LegendreSymbol::QuadraticResidue
+                }
+                _ => LegendreSymbol::QuadraticNonResidue,
+            };
+            assert_eq!(a.legendre(), expected);
+            assert_eq!(square_by_mul.legendre(), LegendreSymbol::QuadraticResidue);
+        }
+
+        assert!(ExtensionField::<Base, DEGREE, E>::ZERO.inverse().is_none());
+        assert_eq!(
+            ExtensionField::<Base, DEGREE, E>::ZERO.legendre(),
+            LegendreSymbol::Zero
+        );
+    }
+
+    fn field_order(degree: usize) -> U256 {
+        let p = U256::from_u64(Base::CHAR);
+        (1..degree).fold(p, |acc, _| acc.wrapping_mul(&p))
+    }
+
+    // generator has order p^DEGREE - 1, so it's not a root of unity of any order
+    // (p^DEGREE - 1) / q for prime q
+    fn check_multiplicative_generator<const DEGREE: usize, E>(prime_factors: &[u128])
+    where
+        E: FieldExtension<DEGREE, BaseField = Base>,
+        ExtensionField<Base, DEGREE, E>: PrimeField,
+    {
+        let group_order = field_order(DEGREE).wrapping_sub(&U256::ONE);
+        let generator = ExtensionField::<Base, DEGREE, E>::multiplicative_generator();
+
+        let mut remaining = group_order;
+        for q in prime_factors.iter().copied() {
+            let q = U256::from_u128(q);
+            while remaining.wrapping_rem(&q) == U256::ZERO {
+                remaining = remaining.wrapping_div(&q);
+            }
+
+            let exp = group_order.wrapping_div(&q);
+            assert_ne!(
+                generator.pow(&exp.to_words()),
+                ExtensionField::<Base, DEGREE, E>::ONE
+            );
+        }
+        // all prime factors are listed
+        assert_eq!(remaining, U256::ONE);
+    }
+
+    #[test]
+    fn test_quadratic_extension_generator() {
+        check_multiplicative_generator::<2, Ext>(&[
+            2,
+            3,
+            5,
+            7,
+            17,
+            179,
+            257,
+            65537,
+            7361031152998637,
+        ]);
+    }
+
+    #[test]
+    fn test_cubic_extension() {
+        check_higher_degree_extension::<3, crate::field::goldilocks::GoldilocksExt3>();
+        check_multiplicative_generator::<3, crate::field::goldilocks::GoldilocksExt3>(&[
+            2,
+            3,
+            5,
+            17,
+            257,
+            937,
+            65537,
+            724723,
+            167034643597991036904547663171,
+        ]);
+    }
+
+    #[test]
+    fn test_quartic_extension() {
+        check_higher_degree_extension::<4, crate::field::goldilocks::GoldilocksExt4>();
+        check_multiplicative_generator::<4, crate::field::goldilocks::GoldilocksExt4>(&[
+            2,
+            3,
+            5,
+            7,
+            13,
+            17,
+            37,
+            113,
+            179,
+            257,
+            1429,
+            65537,
+            274177,
+            118750098349,
+            67280421310721,
+            7361031152998637,
+        ]);
+    }
 }
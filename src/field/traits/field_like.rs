@@ -351,7 +351,9 @@ impl<'a, F: PrimeField, P: PrimeFieldLikeVectorized<Base = F>> Flattener<'a, F,
     }
 }
 
-impl<F: BaseField, E: FieldExtension<2, BaseField = F>> PrimeFieldLike for ExtensionField<F, 2, E> {
+impl<F: BaseField, const DEGREE: usize, E: FieldExtension<DEGREE, BaseField = F>> PrimeFieldLike
+    for ExtensionField<F, DEGREE, E>
+{
     type Base = F;
     type Context = ();
     #[inline(always)]
@@ -396,8 +398,11 @@ impl<F: BaseField, E: FieldExtension<2, BaseField = F>> PrimeFieldLike for Exten
     }
     #[inline(always)]
     fn constant(value: F, _ctx: &mut Self::Context) -> Self {
+        let mut coeffs = [F::ZERO; DEGREE];
+        coeffs[0] = value;
+
         Self {
-            coeffs: [value, F::ZERO],
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
@@ -613,45 +618,71 @@ impl<P: PrimeFieldLike, E: PrimeFieldLikeExtension<2, BaseField = P>>
     }
 }
 
+// Karatsuba for quadratic extensions, and schoolbook multiplication with reduction
+// by the non-residue for binomial extensions of higher degrees
 #[inline]
 pub fn mul_assign_vectorized_in_extension<
     F: PrimeField,
     P: PrimeFieldLikeVectorized<Base = F>,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
 >(
-    a_c0: &mut P,
-    a_c1: &mut P,
-    b_c0: &P,
-    b_c1: &P,
+    a: &mut [P; N],
+    b: &[P; N],
     ctx: &mut (),
 ) {
-    let mut v0 = *a_c0;
-    v0.mul_assign(b_c0, ctx);
-    let mut v1 = *a_c1;
-    v1.mul_assign(b_c1, ctx);
-    let t = *a_c0;
-    a_c1.add_assign(&t, ctx);
-    let mut t0 = *b_c0;
-    t0.add_assign(b_c1, ctx);
-    a_c1.mul_assign(&t0, ctx);
-    a_c1.sub_assign(&v0, ctx);
-    a_c1.sub_assign(&v1, ctx);
-    *a_c0 = v0;
+    if N != 2 {
+        let mut result = [P::zero(ctx); N];
+        let mut reduced = [P::zero(ctx); N];
+        for i in 0..N {
+            for j in 0..N {
+                let mut tmp = a[i];
+                tmp.mul_assign(&b[j], ctx);
+                if i + j < N {
+                    result[i + j].add_assign(&tmp, ctx);
+                } else {
+                    reduced[i + j - N].add_assign(&tmp, ctx);
+                }
+            }
+        }
+        for (dst, mut src) in result.iter_mut().zip(reduced.into_iter()) {
+            src.mul_all_by_base(&EXT::non_residue(), ctx);
+            dst.add_assign(&src, ctx);
+        }
+        *a = result;
+
+        return;
+    }
+
+    let mut v0 = a[0];
+    v0.mul_assign(&b[0], ctx);
+    let mut v1 = a[1];
+    v1.mul_assign(&b[1], ctx);
+    let t = a[0];
+    a[1].add_assign(&t, ctx);
+    let mut t0 = b[0];
+    t0.add_assign(&b[1], ctx);
+    a[1].mul_assign(&t0, ctx);
+    a[1].sub_assign(&v0, ctx);
+    a[1].sub_assign(&v1, ctx);
+    a[0] = v0;
     v1.mul_all_by_base(&EXT::non_residue(), ctx);
-    a_c0.add_assign(&v1, ctx);
+    a[0].add_assign(&v1, ctx);
 }
 
 #[inline]
-pub fn mul_assign_in_extension<F: PrimeField, EXT: FieldExtension<2, BaseField = F>>(
-    a_c0: &mut F,
-    a_c1: &mut F,
-    b_c0: &F,
-    b_c1: &F,
+pub fn mul_assign_in_extension<
+    F: PrimeField,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
+>(
+    a: &mut [F; N],
+    b: &[F; N],
 ) {
-    let mut a = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*a_c0, *a_c1]);
-    let b = ExtensionField::<F, 2, EXT>::from_coeff_in_base([*b_c0, *b_c1]);
-    crate::field::Field::mul_assign(&mut a, &b);
-    let [c0, c1] = a.into_coeffs_in_base();
-    *a_c0 = c0;
-    *a_c1 = c1;
+    let mut result = ExtensionField::<F, N, EXT>::from_coeff_in_base(*a);
+    crate::field::Field::mul_assign(
+        &mut result,
+        &ExtensionField::<F, N, EXT>::from_coeff_in_base(*b),
+    );
+    *a = result.into_coeffs_in_base();
 }
@@ -31,6 +31,7 @@ impl<F: SmallField> CSAllocatable<F> for Boolean<F> {
         false
     }
 
+    #[track_caller]
     #[inline(always)]
     #[must_use]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
@@ -39,6 +40,7 @@ impl<F: SmallField> CSAllocatable<F> for Boolean<F> {
         Self::from_variable_checked(cs, var)
     }
 
+    #[track_caller]
     #[must_use]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let var = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(witness as u64));
@@ -55,6 +57,7 @@ impl<F: SmallField> CSAllocatableExt<F> for Boolean<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -178,6 +181,7 @@ impl<F: SmallField> Boolean<F> {
         }
     }
 
+    #[track_caller]
     #[inline]
     #[must_use]
     pub fn from_variable_checked<CS: ConstraintSystem<F>>(cs: &mut CS, variable: Variable) -> Self {
@@ -221,6 +225,7 @@ impl<F: SmallField> Boolean<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: bool) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 32);
@@ -713,3 +718,27 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for Boolean<F> {
         dst.push(self.variable);
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for Boolean<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    #[inline]
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Self::enforce_equal(cs, a, b)
+    }
+}
+
+// `false < true`
+impl<F: SmallField> CircuitOrd<F> for Boolean<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let a_is_false = a.negated(cs);
+
+        a_is_false.and(cs, *b)
+    }
+}
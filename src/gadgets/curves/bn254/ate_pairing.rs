@@ -0,0 +1,573 @@
+use super::*;
+
+use pairing::bn256::{G1Affine, G2Affine};
+use pairing::GenericCurveAffine;
+
+// Optimal ate pairing, https://eprint.iacr.org/2008/096.pdf. Line functions are evaluated with G2
+// points in affine form on the twist, as an inversion only costs a couple of multiplications in
+// circuit. Evaluated at G1 point P, the line through T with slope lambda is
+// `y_P - lambda * x_P * w + (lambda * x_T - y_T) * w^3`
+
+/// G1 point as a pair of affine coordinates, `(0, 0)` encodes the point at infinity
+pub type BN254G1AffineCoordinates<NN> = (NN, NN);
+
+/// Non-adjacent form of the loop count `6x + 2`, least significant digit first
+fn ate_loop_count_naf() -> Vec<i8> {
+    let mut value = 6 * (BN254_X as i128) + 2;
+    let mut result = vec![];
+    while value != 0 {
+        let digit = if value & 1 == 1 { 2 - (value % 4) } else { 0 };
+        value -= digit;
+        value >>= 1;
+        result.push(digit as i8);
+    }
+
+    result
+}
+
+/// Adds `q` to `t`, or doubles `t` if `q` is `None`, and returns the new point together with
+/// coefficients of the line function evaluated at `p`
+fn line_step<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    t: &mut BN254G2AffinePoint<F, NN>,
+    q: Option<&mut BN254G2AffinePoint<F, NN>>,
+    p: &mut BN254G1AffineCoordinates<NN>,
+) -> (
+    BN254G2AffinePoint<F, NN>,
+    (NN, BN254Fq2<F, NN>, BN254Fq2<F, NN>),
+) {
+    let (mut lambda, mut x3) = match q {
+        None => {
+            // lambda = 3 * x_T^2 / (2 * y_T), x3 = lambda^2 - 2 * x_T
+            let mut x_squared = t.x.square(cs);
+            let mut x_squared_doubled = x_squared.double(cs);
+            let mut numerator = x_squared_doubled.add(cs, &mut x_squared);
+            let mut denominator = t.y.double(cs);
+            let mut lambda = numerator.div(cs, &mut denominator);
+            let mut lambda_squared = lambda.square(cs);
+            let mut x_doubled = t.x.double(cs);
+            let x3 = lambda_squared.sub(cs, &mut x_doubled);
+
+            (lambda, x3)
+        }
+        Some(q) => {
+            // lambda = (y_Q - y_T) / (x_Q - x_T), x3 = lambda^2 - x_T - x_Q
+            let mut numerator = q.y.sub(cs, &mut t.y);
+            let mut denominator = q.x.sub(cs, &mut t.x);
+            let mut lambda = numerator.div(cs, &mut denominator);
+            let mut lambda_squared = lambda.square(cs);
+            let mut x3 = lambda_squared.sub(cs, &mut t.x);
+            let x3 = x3.sub(cs, &mut q.x);
+
+            (lambda, x3)
+        }
+    };
+
+    // y3 = lambda * (x_T - x3) - y_T
+    let mut t0 = t.x.sub(cs, &mut x3);
+    let mut t0 = lambda.mul(cs, &mut t0);
+    let y3 = t0.sub(cs, &mut t.y);
+
+    let (x_p, y_p) = p;
+    let mut b = lambda.mul_by_base(cs, x_p);
+    let b = b.negated(cs);
+    let mut c = lambda.mul(cs, &mut t.x);
+    let c = c.sub(cs, &mut t.y);
+
+    (
+        BN254G2AffinePoint::from_xy_unchecked(x3, y3),
+        (y_p.clone(), b, c),
+    )
+}
+
+fn accumulate_line<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    f: &mut BN254Fq12<F, NN>,
+    line: (NN, BN254Fq2<F, NN>, BN254Fq2<F, NN>),
+    skip: Option<Boolean<F>>,
+    trivial_line: &(NN, BN254Fq2<F, NN>, BN254Fq2<F, NN>),
+) {
+    let (mut a, mut b, mut c) = line;
+    if let Some(skip) = skip {
+        // line `1 + 0 * w` doesn't change the accumulator
+        a = NN::conditionally_select(cs, skip, &trivial_line.0, &a);
+        b = BN254Fq2::conditionally_select(cs, skip, &trivial_line.1, &b);
+        c = BN254Fq2::conditionally_select(cs, skip, &trivial_line.2, &c);
+    }
+
+    *f = f.mul_by_line(cs, &mut a, &mut b, &mut c);
+}
+
+fn miller_loop_impl<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    pairs: &mut [(BN254G1AffineCoordinates<NN>, BN254G2AffinePoint<F, NN>)],
+    skip_flags: &[Option<Boolean<F>>],
+) -> BN254Fq12<F, NN> {
+    assert!(pairs.is_empty() == false);
+    assert_eq!(pairs.len(), skip_flags.len());
+    let params = pairs[0].1.x.get_params().clone();
+
+    let mut one = NN::allocated_constant(cs, Fq::one(), &params);
+    one.normalize(cs);
+    let zero = BN254Fq2::zero(cs, &params);
+    let trivial_line = (one, zero.clone(), zero);
+
+    let naf = ate_loop_count_naf();
+    let mut accumulators: Vec<_> = pairs.iter().map(|(_, q)| q.clone()).collect();
+    let mut negated: Vec<_> = pairs.iter_mut().map(|(_, q)| q.negated(cs)).collect();
+
+    // the most significant digit is 1, so we start from T = Q and f = 1
+    let mut f = BN254Fq12::one(cs, &params);
+    for (idx, digit) in naf.iter().enumerate().rev().skip(1) {
+        if idx != naf.len() - 2 {
+            f = f.square(cs);
+        }
+
+        for ((p, _), (t, skip)) in pairs
+            .iter_mut()
+            .zip(accumulators.iter_mut().zip(skip_flags.iter()))
+        {
+            let (new_t, line) = line_step(cs, t, None, p);
+            *t = new_t;
+            accumulate_line(cs, &mut f, line, *skip, &trivial_line);
+        }
+
+        if *digit != 0 {
+            for (((p, q), q_negated), (t, skip)) in pairs
+                .iter_mut()
+                .zip(negated.iter_mut())
+                .zip(accumulators.iter_mut().zip(skip_flags.iter()))
+            {
+                let q = if *digit == 1 { q } else { q_negated };
+                let (new_t, line) = line_step(cs, t, Some(q), p);
+                *t = new_t;
+                accumulate_line(cs, &mut f, line, *skip, &trivial_line);
+            }
+        }
+    }
+
+    // two more additions of Q1 = psi(Q) and Q2 = -psi^2(Q)
+    for ((p, q), (t, skip)) in pairs
+        .iter_mut()
+        .zip(accumulators.iter_mut().zip(skip_flags.iter()))
+    {
+        let mut q1 = q.psi(cs);
+        let mut q2 = q1.psi(cs);
+        let mut q2 = q2.negated(cs);
+
+        let (new_t, line) = line_step(cs, t, Some(&mut q1), p);
+        *t = new_t;
+        accumulate_line(cs, &mut f, line, *skip, &trivial_line);
+        let (_, line) = line_step(cs, t, Some(&mut q2), p);
+        accumulate_line(cs, &mut f, line, *skip, &trivial_line);
+    }
+
+    f
+}
+
+/// Computes the product of Miller loops over all the pairs. Points must be on the curves, in the
+/// prime order subgroups and not at infinity, otherwise the result is meaningless or the circuit
+/// is unsatisfiable
+pub fn miller_loop<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    pairs: &mut [(BN254G1AffineCoordinates<NN>, BN254G2AffinePoint<F, NN>)],
+) -> BN254Fq12<F, NN> {
+    let skip_flags = vec![None; pairs.len()];
+    miller_loop_impl(cs, pairs, &skip_flags)
+}
+
+fn exp_by_neg_x<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    value: &mut BN254Fq12<F, NN>,
+) -> BN254Fq12<F, NN> {
+    // elements are in the cyclotomic subgroup after the easy part, so inversion is conjugation
+    let mut result = value.pow_by_x(cs);
+    result.conjugate(cs)
+}
+
+/// Raises the output of the Miller loop to the power of `(p^12 - 1) / r`, multiplied by
+/// `2x * (6x^2 + 3x + 1)` that is coprime to `r`, so the result is still a non-degenerate pairing.
+/// The hard part is the addition chain from "Faster hashing to G2" by Fuentes-Castaneda, Knapp
+/// and Rodriguez-Henriquez
+pub fn final_exponentiation<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    f: &mut BN254Fq12<F, NN>,
+) -> BN254Fq12<F, NN> {
+    // easy part: f^((p^6 - 1) * (p^2 + 1))
+    let mut f_conjugate = f.conjugate(cs);
+    let mut f_inverse = f.inverse(cs);
+    let mut t = f_conjugate.mul(cs, &mut f_inverse);
+    let mut t_frobenius = t.frobenius_map(cs, 2);
+    let mut r = t_frobenius.mul(cs, &mut t);
+
+    // hard part
+    let mut y0 = exp_by_neg_x(cs, &mut r);
+    let mut y1 = y0.square(cs);
+    let mut y2 = y1.square(cs);
+    let mut y3 = y2.mul(cs, &mut y1);
+    let mut y4 = exp_by_neg_x(cs, &mut y3);
+    let mut y5 = y4.square(cs);
+    let mut y6 = exp_by_neg_x(cs, &mut y5);
+    let mut y3 = y3.conjugate(cs);
+    let mut y6 = y6.conjugate(cs);
+    let mut y7 = y6.mul(cs, &mut y4);
+    let mut y8 = y7.mul(cs, &mut y3);
+    let mut y9 = y8.mul(cs, &mut y1);
+    let mut y10 = y8.mul(cs, &mut y4);
+    let mut y11 = y10.mul(cs, &mut r);
+    let mut y12 = y9.frobenius_map(cs, 1);
+    let mut y13 = y12.mul(cs, &mut y11);
+    let mut y8 = y8.frobenius_map(cs, 2);
+    let mut y14 = y8.mul(cs, &mut y13);
+    let mut r = r.conjugate(cs);
+    let mut y15 = r.mul(cs, &mut y9);
+    let mut y15 = y15.frobenius_map(cs, 3);
+
+    y15.mul(cs, &mut y14)
+}
+
+/// Checks that `e(P_1, Q_1) * ... * e(P_n, Q_n) == 1`, as `ecPairing` precompile (EIP-197) does.
+/// Pairs where any of the points is at infinity (encoded as `(0, 0)`) are skipped, and the empty
+/// product is one. G2 points are checked to be in the prime order subgroup, and the result is
+/// `false` if any of them is not. The caller is responsible for checking that all the points are
+/// on the curves (see `g1_is_on_curve` and `BN254G2AffinePoint::is_on_curve`)
+pub fn multi_pairing_is_one<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    pairs: &mut [(BN254G1AffineCoordinates<NN>, BN254G2AffinePoint<F, NN>)],
+) -> Boolean<F> {
+    if pairs.is_empty() {
+        return Boolean::allocated_constant(cs, true);
+    }
+
+    let in_subgroup: Vec<_> = pairs
+        .iter_mut()
+        .map(|(_, q)| q.is_in_subgroup(cs))
+        .collect();
+    let pairing_is_one = multi_pairing_is_one_impl(cs, pairs, &in_subgroup);
+    let all_in_subgroup = Boolean::multi_and(cs, &in_subgroup);
+
+    pairing_is_one.and(cs, all_in_subgroup)
+}
+
+/// Same as `multi_pairing_is_one`, but pairs that are not marked as valid are skipped as well,
+/// so that points outside of the curve or the subgroup can't make the circuit unsatisfiable
+pub(crate) fn multi_pairing_is_one_impl<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    NN: NonNativeField<F, Fq>,
+>(
+    cs: &mut CS,
+    pairs: &mut [(BN254G1AffineCoordinates<NN>, BN254G2AffinePoint<F, NN>)],
+    is_valid: &[Boolean<F>],
+) -> Boolean<F> {
+    assert_eq!(pairs.len(), is_valid.len());
+    if pairs.is_empty() {
+        return Boolean::allocated_constant(cs, true);
+    }
+    let params = pairs[0].1.x.get_params().clone();
+
+    // points at infinity and invalid pairs are replaced by generators to avoid exceptional cases
+    // in the Miller loop, and line functions of such pairs are masked
+    let (g1_x, g1_y) = G1Affine::one().into_xy_unchecked();
+    let mut g1_x = NN::allocated_constant(cs, g1_x, &params);
+    let mut g1_y = NN::allocated_constant(cs, g1_y, &params);
+    g1_x.normalize(cs);
+    g1_y.normalize(cs);
+    let g2 = BN254G2AffinePoint::constant(cs, G2Affine::one(), &params);
+
+    let mut substituted = Vec::with_capacity(pairs.len());
+    let mut skip_flags = Vec::with_capacity(pairs.len());
+    for (((x, y), q), is_valid) in pairs.iter_mut().zip(is_valid.iter()) {
+        let x_is_zero = x.is_zero(cs);
+        let y_is_zero = y.is_zero(cs);
+        let p_is_zero = x_is_zero.and(cs, y_is_zero);
+        let q_is_zero = q.is_zero(cs);
+        let is_invalid = is_valid.negated(cs);
+        let skip = Boolean::multi_or(cs, &[p_is_zero, q_is_zero, is_invalid]);
+
+        let x = NN::conditionally_select(cs, skip, &g1_x, x);
+        let y = NN::conditionally_select(cs, skip, &g1_y, y);
+        let q = BN254G2AffinePoint::conditionally_select(cs, skip, &g2, q);
+        substituted.push(((x, y), q));
+        skip_flags.push(Some(skip));
+    }
+
+    let mut f = miller_loop_impl(cs, &mut substituted, &skip_flags);
+    let mut result = final_exponentiation(cs, &mut f);
+
+    result.is_one(cs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::*;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::{CSGeometry, GateConfigurationHolder, StaticToolboxHolder};
+    use crate::dag::CircuitResolverOpts;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::tables::range_check_16_bits::{
+        create_range_check_16_bits_table, RangeCheck16BitsTable,
+    };
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::Fr;
+    use pairing::GenericCurveProjective;
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+    type NN = NonNativeFieldOverU16<F, Fq, 17>;
+
+    fn configure<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 1,
+                num_repetitions: 10,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = DotProductGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns)
+    }
+
+    fn fq2_value<CS: ConstraintSystem<F>>(cs: &CS, value: &BN254Fq2<F, NN>) -> Fq2 {
+        Fq2 {
+            c0: value.c0.witness_hook(cs)().unwrap().get(),
+            c1: value.c1.witness_hook(cs)().unwrap().get(),
+        }
+    }
+
+    fn fq12_value<CS: ConstraintSystem<F>>(cs: &CS, value: &BN254Fq12<F, NN>) -> Fq12 {
+        let [c0, c1] = [&value.c0, &value.c1].map(|el| Fq6 {
+            c0: fq2_value(cs, &el.c0),
+            c1: fq2_value(cs, &el.c1),
+            c2: fq2_value(cs, &el.c2),
+        });
+
+        Fq12 { c0, c1 }
+    }
+
+    fn allocate_pair<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        p: G1Affine,
+        q: G2Affine,
+        params: &Arc<NonNativeFieldOverU16Params<Fq, 17>>,
+    ) -> (BN254G1AffineCoordinates<NN>, BN254G2AffinePoint<F, NN>) {
+        let (x, y) = if p.is_zero() {
+            (Fq::zero(), Fq::zero())
+        } else {
+            p.into_xy_unchecked()
+        };
+        let x = NN::allocate_checked(cs, x, params);
+        let y = NN::allocate_checked(cs, y, params);
+        let q = BN254G2AffinePoint::allocate_unchecked(cs, q, params);
+
+        ((x, y), q)
+    }
+
+    // some element without any structure
+    fn fq12_from_seed(seed: u64) -> Fq12 {
+        let mut coefficients = (0..6).map(|idx| Fq2 {
+            c0: Fq::from_str(&(seed * 1000 + 2 * idx + 1).to_string()).unwrap(),
+            c1: Fq::from_str(&(seed * 1000 + 2 * idx + 2).to_string()).unwrap(),
+        });
+        let mut next = || coefficients.next().unwrap();
+        let mut result = Fq12 {
+            c0: Fq6 {
+                c0: next(),
+                c1: next(),
+                c2: next(),
+            },
+            c1: Fq6 {
+                c0: next(),
+                c1: next(),
+                c2: next(),
+            },
+        };
+        for _ in 0..4 {
+            result.square();
+            result.add_assign(&Fq12::one());
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_tower_arithmetic() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 22);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let mut owned_cs = builder.build(CircuitResolverOpts::new(1 << 26));
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        let a_value = fq12_from_seed(1);
+        let b_value = fq12_from_seed(2);
+        let mut a = BN254Fq12::<F, NN>::allocate_checked(cs, a_value, &params);
+        let mut b = BN254Fq12::<F, NN>::allocate_checked(cs, b_value, &params);
+
+        let result = a.mul(cs, &mut b);
+        let mut expected = a_value;
+        expected.mul_assign(&b_value);
+        assert_eq!(fq12_value(&*cs, &result), expected);
+
+        let result = a.square(cs);
+        let mut expected = a_value;
+        expected.square();
+        assert_eq!(fq12_value(&*cs, &result), expected);
+
+        let result = a.inverse(cs);
+        let expected = a_value.inverse().unwrap();
+        assert_eq!(fq12_value(&*cs, &result), expected);
+
+        for power in 1..4 {
+            let result = a.frobenius_map(cs, power);
+            let mut expected = a_value;
+            expected.frobenius_map(power);
+            assert_eq!(fq12_value(&*cs, &result), expected);
+        }
+
+        // sparse multiplication by a line
+        let line_value = fq12_from_seed(3);
+        let mut line_a = NN::allocate_checked(cs, line_value.c0.c0.c0, &params);
+        let mut line_b = BN254Fq2::allocate_checked(cs, line_value.c1.c0, &params);
+        let mut line_c = BN254Fq2::allocate_checked(cs, line_value.c1.c1, &params);
+        let result = a.mul_by_line(cs, &mut line_a, &mut line_b, &mut line_c);
+        let mut sparse_value = Fq12::zero();
+        sparse_value.c0.c0.c0 = line_value.c0.c0.c0;
+        sparse_value.c1.c0 = line_value.c1.c0;
+        sparse_value.c1.c1 = line_value.c1.c1;
+        let mut expected = a_value;
+        expected.mul_assign(&sparse_value);
+        assert_eq!(fq12_value(&*cs, &result), expected);
+
+        // G2 endomorphism acts as multiplication by p
+        let q_value = G2Affine::one()
+            .mul(Fr::from_str("1234567").unwrap().into_repr())
+            .into_affine();
+        let mut q = BN254G2AffinePoint::<F, NN>::allocate_unchecked(cs, q_value, &params);
+        let is_on_curve = q.is_on_curve(cs);
+        assert!(is_on_curve.witness_hook(&*cs)().unwrap());
+        let result = q.psi(cs);
+        // p mod r
+        let p_mod_r = Fr::from_str(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+        )
+        .unwrap();
+        let expected = q_value.mul(p_mod_r.into_repr()).into_affine();
+        assert_eq!(
+            (fq2_value(&*cs, &result.x), fq2_value(&*cs, &result.y)),
+            expected.into_xy_unchecked()
+        );
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    #[ignore = "pairing check is a large circuit, run explicitly"]
+    fn test_multi_pairing_check() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 60,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 26);
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = configure(builder);
+        let mut owned_cs = builder.build(CircuitResolverOpts::new(1 << 30));
+
+        let table = create_range_check_16_bits_table();
+        owned_cs.add_lookup_table::<RangeCheck16BitsTable, 1>(table);
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+
+        let a = Fr::from_str("1234567").unwrap();
+        let b = Fr::from_str("7654321").unwrap();
+        let mut ab = a;
+        ab.mul_assign(&b);
+        ab.negate();
+
+        // e(a * P, b * Q) * e(-ab * P, Q) * e(0, Q) == 1
+        let p_a = G1Affine::one().mul(a.into_repr()).into_affine();
+        let q_b = G2Affine::one().mul(b.into_repr()).into_affine();
+        let p_ab = G1Affine::one().mul(ab.into_repr()).into_affine();
+        let mut pairs = vec![
+            allocate_pair(cs, p_a, q_b, &params),
+            allocate_pair(cs, p_ab, G2Affine::one(), &params),
+            allocate_pair(cs, G1Affine::zero(), G2Affine::one(), &params),
+        ];
+        let is_one = multi_pairing_is_one(cs, &mut pairs);
+        assert!(is_one.witness_hook(&*cs)().unwrap());
+
+        // e(a * P, b * Q) != 1
+        let mut pairs = vec![allocate_pair(cs, p_a, q_b, &params)];
+        let is_one = multi_pairing_is_one(cs, &mut pairs);
+        assert!(is_one.witness_hook(&*cs)().unwrap() == false);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
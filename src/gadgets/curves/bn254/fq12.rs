@@ -0,0 +1,205 @@
+use super::*;
+
+/// Element `c0 + c1 * w` of Fq12
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct BN254Fq12<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub c0: BN254Fq6<F, NN>,
+    pub c1: BN254Fq6<F, NN>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> BN254Fq12<F, NN> {
+    pub fn new(c0: BN254Fq6<F, NN>, c1: BN254Fq6<F, NN>) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn get_params(&self) -> &Arc<NN::Params> {
+        self.c0.get_params()
+    }
+
+    pub fn constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: Fq12,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = BN254Fq6::constant(cs, value.c0, params);
+        let c1 = BN254Fq6::constant(cs, value.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn one<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::constant(cs, Fq12::one(), params)
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: Fq12,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = BN254Fq6::allocate_checked(cs, witness.c0, params);
+        let c1 = BN254Fq6::allocate_checked(cs, witness.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        // c0 = a0 * b0 + a1 * b1 * v, c1 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1
+        let mut t0 = self.c0.mul(cs, &mut other.c0);
+        let mut t1 = self.c1.mul(cs, &mut other.c1);
+        let mut t1_by_v = t1.mul_by_nonresidue(cs);
+        let c0 = t0.add(cs, &mut t1_by_v);
+
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = other.c0.add(cs, &mut other.c1);
+        let mut t = a.mul(cs, &mut b);
+        let mut t = t.sub(cs, &mut t0);
+        let c1 = t.sub(cs, &mut t1);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // c0 = (a0 + a1) * (a0 + a1 * v) - a0 * a1 - a0 * a1 * v, c1 = 2 * a0 * a1
+        let mut t = self.c0.mul(cs, &mut self.c1);
+        let mut t_by_v = t.mul_by_nonresidue(cs);
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut c1_by_v = self.c1.mul_by_nonresidue(cs);
+        let mut b = self.c0.add(cs, &mut c1_by_v);
+        let mut c0 = a.mul(cs, &mut b);
+        let mut c0 = c0.sub(cs, &mut t);
+        let c0 = c0.sub(cs, &mut t_by_v);
+        let c1 = t.double(cs);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn conjugate<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c1 = self.c1.negated(cs);
+
+        Self::new(self.c0.clone(), c1)
+    }
+
+    /// Computes the inverse. Unsatisfiable if the element is zero
+    pub fn inverse<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // 1 / (a0 + a1 * w) = (a0 - a1 * w) / (a0^2 - a1^2 * v)
+        let mut c0_squared = self.c0.square(cs);
+        let mut c1_squared = self.c1.square(cs);
+        let mut c1_squared_by_v = c1_squared.mul_by_nonresidue(cs);
+        let mut norm = c0_squared.sub(cs, &mut c1_squared_by_v);
+        let mut norm_inverse = norm.inverse(cs);
+
+        let c0 = self.c0.mul(cs, &mut norm_inverse);
+        let mut c1 = self.c1.mul(cs, &mut norm_inverse);
+        let c1 = c1.negated(cs);
+
+        Self::new(c0, c1)
+    }
+
+    /// Multiplies by the sparse element `a + (b + c * v) * w` with `a` from the base field, that is
+    /// the form of line functions evaluated in the Miller loop
+    pub fn mul_by_line<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        a: &mut NN,
+        b: &mut BN254Fq2<F, NN>,
+        c: &mut BN254Fq2<F, NN>,
+    ) -> Self {
+        let mut t0 = BN254Fq6::new(
+            self.c0.c0.mul_by_base(cs, a),
+            self.c0.c1.mul_by_base(cs, a),
+            self.c0.c2.mul_by_base(cs, a),
+        );
+        let mut t1 = self.c1.mul_by_01(cs, b, c);
+
+        // c1 = (a0 + a1) * (a + b + c * v) - t0 - t1
+        let mut a_plus_b = b.c0.add(cs, a);
+        a_plus_b.normalize(cs);
+        let mut a_plus_b = BN254Fq2::new(a_plus_b, b.c1.clone());
+        let mut sum = self.c0.add(cs, &mut self.c1);
+        let mut c1 = sum.mul_by_01(cs, &mut a_plus_b, c);
+        let mut c1 = c1.sub(cs, &mut t0);
+        let c1 = c1.sub(cs, &mut t1);
+
+        // c0 = t0 + t1 * v
+        let mut t1_by_v = t1.mul_by_nonresidue(cs);
+        let c0 = t0.add(cs, &mut t1_by_v);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn frobenius_map<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, power: usize) -> Self {
+        assert!(power > 0 && power < 4);
+        // coefficient at w^i is multiplied by gamma^i, and w^i = v^(i / 2) * w^(i % 2)
+        let gamma = frobenius_gammas()[power - 1];
+        let mut gamma_powers = vec![Fq2::one()];
+        for _ in 1..6 {
+            let mut next = *gamma_powers.last().unwrap();
+            next.mul_assign(&gamma);
+            gamma_powers.push(next);
+        }
+
+        let map_coefficient = |cs: &mut CS, value: &mut BN254Fq2<F, NN>, w_power: usize| {
+            let mut mapped = value.frobenius_map(cs, power);
+            if w_power == 0 {
+                mapped
+            } else {
+                mapped.mul_by_constant(cs, gamma_powers[w_power])
+            }
+        };
+
+        let c0 = BN254Fq6::new(
+            map_coefficient(cs, &mut self.c0.c0, 0),
+            map_coefficient(cs, &mut self.c0.c1, 2),
+            map_coefficient(cs, &mut self.c0.c2, 4),
+        );
+        let c1 = BN254Fq6::new(
+            map_coefficient(cs, &mut self.c1.c0, 1),
+            map_coefficient(cs, &mut self.c1.c1, 3),
+            map_coefficient(cs, &mut self.c1.c2, 5),
+        );
+
+        Self::new(c0, c1)
+    }
+
+    /// Computes `self^x` for the BN254 parameter `x = 4965661367192848881`
+    pub fn pow_by_x<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut result = self.clone();
+        for i in (0..(u64::BITS - BN254_X.leading_zeros() - 1)).rev() {
+            result = result.square(cs);
+            if (BN254_X >> i) & 1 == 1 {
+                result = result.mul(cs, self);
+            }
+        }
+
+        result
+    }
+
+    pub fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
+        let c0_is_equal = self.c0.equals(cs, &mut other.c0);
+        let c1_is_equal = self.c1.equals(cs, &mut other.c1);
+
+        c0_is_equal.and(cs, c1_is_equal)
+    }
+
+    pub fn is_one<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.get_params().clone();
+        let mut one = Self::one(cs, &params);
+
+        self.equals(cs, &mut one)
+    }
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> Selectable<F> for BN254Fq12<F, NN> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let c0 = BN254Fq6::conditionally_select(cs, flag, &a.c0, &b.c0);
+        let c1 = BN254Fq6::conditionally_select(cs, flag, &a.c1, &b.c1);
+
+        Self::new(c0, c1)
+    }
+}
@@ -0,0 +1,233 @@
+use super::*;
+
+/// Element `c0 + c1 * u` of Fq2
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct BN254Fq2<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub c0: NN,
+    pub c1: NN,
+    pub _marker: std::marker::PhantomData<F>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> BN254Fq2<F, NN> {
+    pub fn new(c0: NN, c1: NN) -> Self {
+        Self {
+            c0,
+            c1,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn get_params(&self) -> &Arc<NN::Params> {
+        self.c0.get_params()
+    }
+
+    pub fn constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: Fq2,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let mut c0 = NN::allocated_constant(cs, value.c0, params);
+        let mut c1 = NN::allocated_constant(cs, value.c1, params);
+        c0.normalize(cs);
+        c1.normalize(cs);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::constant(cs, Fq2::zero(), params)
+    }
+
+    pub fn one<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::constant(cs, Fq2::one(), params)
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: Fq2,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = NN::allocate_checked(cs, witness.c0, params);
+        let c1 = NN::allocate_checked(cs, witness.c1, params);
+
+        Self::new(c0, c1)
+    }
+
+    pub fn normalize<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        self.c0.normalize(cs);
+        self.c1.normalize(cs);
+    }
+
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.add(cs, &mut other.c0);
+        let c1 = self.c1.add(cs, &mut other.c1);
+        let mut result = Self::new(c0, c1);
+        result.normalize(cs);
+
+        result
+    }
+
+    pub fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut other = self.clone();
+        self.add(cs, &mut other)
+    }
+
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.sub(cs, &mut other.c0);
+        let c1 = self.c1.sub(cs, &mut other.c1);
+        let mut result = Self::new(c0, c1);
+        result.normalize(cs);
+
+        result
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.negated(cs);
+        let c1 = self.c1.negated(cs);
+        let mut result = Self::new(c0, c1);
+        result.normalize(cs);
+
+        result
+    }
+
+    pub fn conjugate<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut c1 = self.c1.negated(cs);
+        c1.normalize(cs);
+
+        Self::new(self.c0.clone(), c1)
+    }
+
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        // Karatsuba: c0 = a0 * b0 - a1 * b1, c1 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1
+        let mut v0 = self.c0.mul(cs, &mut other.c0);
+        let mut v1 = self.c1.mul(cs, &mut other.c1);
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = other.c0.add(cs, &mut other.c1);
+        let mut t = a.mul(cs, &mut b);
+
+        let c0 = v0.sub(cs, &mut v1);
+        let mut c1 = t.sub(cs, &mut v0);
+        let c1 = c1.sub(cs, &mut v1);
+        let mut result = Self::new(c0, c1);
+        result.normalize(cs);
+
+        result
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // c0 = (a0 + a1) * (a0 - a1), c1 = 2 * a0 * a1
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = self.c0.sub(cs, &mut self.c1);
+        let c0 = a.mul(cs, &mut b);
+        let mut t = self.c0.mul(cs, &mut self.c1);
+        let c1 = t.double(cs);
+        let mut result = Self::new(c0, c1);
+        result.normalize(cs);
+
+        result
+    }
+
+    /// Multiplies by an element of the base field
+    pub fn mul_by_base<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut NN) -> Self {
+        let c0 = self.c0.mul(cs, other);
+        let c1 = self.c1.mul(cs, other);
+
+        Self::new(c0, c1)
+    }
+
+    /// Multiplies by a constant, that is cheaper for constants from the base field
+    pub fn mul_by_constant<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, value: Fq2) -> Self {
+        let params = self.get_params().clone();
+        if value.c1.is_zero() {
+            let mut constant = NN::allocated_constant(cs, value.c0, &params);
+            self.mul_by_base(cs, &mut constant)
+        } else {
+            let mut constant = Self::constant(cs, value, &params);
+            self.mul(cs, &mut constant)
+        }
+    }
+
+    /// Multiplies by `xi = 9 + u`
+    pub fn mul_by_nonresidue<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // (9 * a0 - a1) + (a0 + 9 * a1) * u
+        let mut c0_by_9 = mul_by_9(cs, &mut self.c0);
+        let mut c1_by_9 = mul_by_9(cs, &mut self.c1);
+        let c0 = c0_by_9.sub(cs, &mut self.c1);
+        let c1 = c1_by_9.add(cs, &mut self.c0);
+        let mut result = Self::new(c0, c1);
+        result.normalize(cs);
+
+        result
+    }
+
+    pub fn frobenius_map<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, power: usize) -> Self {
+        if power % 2 == 1 {
+            self.conjugate(cs)
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Computes the inverse. Unsatisfiable if the element is zero
+    pub fn inverse<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // 1 / (a0 + a1 * u) = (a0 - a1 * u) / (a0^2 + a1^2)
+        let mut c0_squared = self.c0.square(cs);
+        let mut c1_squared = self.c1.square(cs);
+        let mut norm = c0_squared.add(cs, &mut c1_squared);
+        norm.normalize(cs);
+        let mut norm_inverse = norm.inverse_unchecked(cs);
+
+        let c0 = self.c0.mul(cs, &mut norm_inverse);
+        let mut c1 = self.c1.mul(cs, &mut norm_inverse);
+        let mut c1 = c1.negated(cs);
+        c1.normalize(cs);
+
+        Self::new(c0, c1)
+    }
+
+    /// Computes `self / other`. Unsatisfiable if `other` is zero
+    pub fn div<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let mut inverse = other.inverse(cs);
+        self.mul(cs, &mut inverse)
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let c0_is_zero = self.c0.is_zero(cs);
+        let c1_is_zero = self.c1.is_zero(cs);
+
+        Boolean::multi_and(cs, &[c0_is_zero, c1_is_zero])
+    }
+
+    pub fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
+        let c0_is_equal = self.c0.equals(cs, &mut other.c0);
+        let c1_is_equal = self.c1.equals(cs, &mut other.c1);
+
+        Boolean::multi_and(cs, &[c0_is_equal, c1_is_equal])
+    }
+}
+
+fn mul_by_9<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    value: &mut NN,
+) -> NN {
+    let mut by_2 = value.double(cs);
+    let mut by_4 = by_2.double(cs);
+    let mut by_8 = by_4.double(cs);
+
+    by_8.add(cs, value)
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> Selectable<F> for BN254Fq2<F, NN> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let c0 = NN::conditionally_select(cs, flag, &a.c0, &b.c0);
+        let c1 = NN::conditionally_select(cs, flag, &a.c1, &b.c1);
+
+        Self::new(c0, c1)
+    }
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+/// Element `c0 + c1 * v + c2 * v^2` of Fq6
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct BN254Fq6<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub c0: BN254Fq2<F, NN>,
+    pub c1: BN254Fq2<F, NN>,
+    pub c2: BN254Fq2<F, NN>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> BN254Fq6<F, NN> {
+    pub fn new(c0: BN254Fq2<F, NN>, c1: BN254Fq2<F, NN>, c2: BN254Fq2<F, NN>) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    pub fn get_params(&self) -> &Arc<NN::Params> {
+        self.c0.get_params()
+    }
+
+    pub fn constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: Fq6,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = BN254Fq2::constant(cs, value.c0, params);
+        let c1 = BN254Fq2::constant(cs, value.c1, params);
+        let c2 = BN254Fq2::constant(cs, value.c2, params);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::constant(cs, Fq6::zero(), params)
+    }
+
+    pub fn one<CS: ConstraintSystem<F>>(cs: &mut CS, params: &Arc<NN::Params>) -> Self {
+        Self::constant(cs, Fq6::one(), params)
+    }
+
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: Fq6,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let c0 = BN254Fq2::allocate_checked(cs, witness.c0, params);
+        let c1 = BN254Fq2::allocate_checked(cs, witness.c1, params);
+        let c2 = BN254Fq2::allocate_checked(cs, witness.c2, params);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.add(cs, &mut other.c0);
+        let c1 = self.c1.add(cs, &mut other.c1);
+        let c2 = self.c2.add(cs, &mut other.c2);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.double(cs);
+        let c1 = self.c1.double(cs);
+        let c2 = self.c2.double(cs);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let c0 = self.c0.sub(cs, &mut other.c0);
+        let c1 = self.c1.sub(cs, &mut other.c1);
+        let c2 = self.c2.sub(cs, &mut other.c2);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c0.negated(cs);
+        let c1 = self.c1.negated(cs);
+        let c2 = self.c2.negated(cs);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        // Karatsuba, see https://eprint.iacr.org/2006/471.pdf, section 4
+        let mut v0 = self.c0.mul(cs, &mut other.c0);
+        let mut v1 = self.c1.mul(cs, &mut other.c1);
+        let mut v2 = self.c2.mul(cs, &mut other.c2);
+
+        // c0 = ((a1 + a2) * (b1 + b2) - v1 - v2) * xi + v0
+        let mut a = self.c1.add(cs, &mut self.c2);
+        let mut b = other.c1.add(cs, &mut other.c2);
+        let mut t = a.mul(cs, &mut b);
+        let mut t = t.sub(cs, &mut v1);
+        let mut t = t.sub(cs, &mut v2);
+        let mut t = t.mul_by_nonresidue(cs);
+        let c0 = t.add(cs, &mut v0);
+
+        // c1 = (a0 + a1) * (b0 + b1) - v0 - v1 + xi * v2
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = other.c0.add(cs, &mut other.c1);
+        let mut t = a.mul(cs, &mut b);
+        let mut t = t.sub(cs, &mut v0);
+        let mut t = t.sub(cs, &mut v1);
+        let mut v2_by_xi = v2.mul_by_nonresidue(cs);
+        let c1 = t.add(cs, &mut v2_by_xi);
+
+        // c2 = (a0 + a2) * (b0 + b2) - v0 - v2 + v1
+        let mut a = self.c0.add(cs, &mut self.c2);
+        let mut b = other.c0.add(cs, &mut other.c2);
+        let mut t = a.mul(cs, &mut b);
+        let mut t = t.sub(cs, &mut v0);
+        let mut t = t.sub(cs, &mut v2);
+        let c2 = t.add(cs, &mut v1);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut other = self.clone();
+        self.mul(cs, &mut other)
+    }
+
+    /// Multiplies by an element of Fq2
+    pub fn mul_by_fq2<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut BN254Fq2<F, NN>,
+    ) -> Self {
+        let c0 = self.c0.mul(cs, other);
+        let c1 = self.c1.mul(cs, other);
+        let c2 = self.c2.mul(cs, other);
+
+        Self::new(c0, c1, c2)
+    }
+
+    /// Multiplies by the sparse element `b0 + b1 * v`
+    pub fn mul_by_01<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        b0: &mut BN254Fq2<F, NN>,
+        b1: &mut BN254Fq2<F, NN>,
+    ) -> Self {
+        let mut a_a = self.c0.mul(cs, b0);
+        let mut b_b = self.c1.mul(cs, b1);
+
+        // c0 = ((a1 + a2) * b1 - a1 * b1) * xi + a0 * b0
+        let mut t = self.c1.add(cs, &mut self.c2);
+        let mut t = t.mul(cs, b1);
+        let mut t = t.sub(cs, &mut b_b);
+        let mut t = t.mul_by_nonresidue(cs);
+        let c0 = t.add(cs, &mut a_a);
+
+        // c1 = (b0 + b1) * (a0 + a1) - a0 * b0 - a1 * b1
+        let mut a = self.c0.add(cs, &mut self.c1);
+        let mut b = b0.add(cs, b1);
+        let mut t = a.mul(cs, &mut b);
+        let mut t = t.sub(cs, &mut a_a);
+        let c1 = t.sub(cs, &mut b_b);
+
+        // c2 = (a0 + a2) * b0 - a0 * b0 + a1 * b1
+        let mut t = self.c0.add(cs, &mut self.c2);
+        let mut t = t.mul(cs, b0);
+        let mut t = t.sub(cs, &mut a_a);
+        let c2 = t.add(cs, &mut b_b);
+
+        Self::new(c0, c1, c2)
+    }
+
+    /// Multiplies by `v`
+    pub fn mul_by_nonresidue<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let c0 = self.c2.mul_by_nonresidue(cs);
+
+        Self::new(c0, self.c0.clone(), self.c1.clone())
+    }
+
+    /// Computes the inverse. Unsatisfiable if the element is zero
+    pub fn inverse<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        // t0 = a0^2 - xi * a1 * a2
+        let mut t = self.c1.mul(cs, &mut self.c2);
+        let mut t = t.mul_by_nonresidue(cs);
+        let mut t0 = self.c0.square(cs);
+        let mut t0 = t0.sub(cs, &mut t);
+
+        // t1 = xi * a2^2 - a0 * a1
+        let mut t = self.c2.square(cs);
+        let mut t = t.mul_by_nonresidue(cs);
+        let mut a0_a1 = self.c0.mul(cs, &mut self.c1);
+        let mut t1 = t.sub(cs, &mut a0_a1);
+
+        // t2 = a1^2 - a0 * a2
+        let mut t = self.c1.square(cs);
+        let mut a0_a2 = self.c0.mul(cs, &mut self.c2);
+        let mut t2 = t.sub(cs, &mut a0_a2);
+
+        // norm = a0 * t0 + xi * (a2 * t1 + a1 * t2)
+        let mut a2_t1 = self.c2.mul(cs, &mut t1);
+        let mut a1_t2 = self.c1.mul(cs, &mut t2);
+        let mut t = a2_t1.add(cs, &mut a1_t2);
+        let mut t = t.mul_by_nonresidue(cs);
+        let mut a0_t0 = self.c0.mul(cs, &mut t0);
+        let mut norm = a0_t0.add(cs, &mut t);
+        let mut norm_inverse = norm.inverse(cs);
+
+        let c0 = t0.mul(cs, &mut norm_inverse);
+        let c1 = t1.mul(cs, &mut norm_inverse);
+        let c2 = t2.mul(cs, &mut norm_inverse);
+
+        Self::new(c0, c1, c2)
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let c0_is_zero = self.c0.is_zero(cs);
+        let c1_is_zero = self.c1.is_zero(cs);
+        let c2_is_zero = self.c2.is_zero(cs);
+
+        Boolean::multi_and(cs, &[c0_is_zero, c1_is_zero, c2_is_zero])
+    }
+
+    pub fn equals<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Boolean<F> {
+        let c0_is_equal = self.c0.equals(cs, &mut other.c0);
+        let c1_is_equal = self.c1.equals(cs, &mut other.c1);
+        let c2_is_equal = self.c2.equals(cs, &mut other.c2);
+
+        Boolean::multi_and(cs, &[c0_is_equal, c1_is_equal, c2_is_equal])
+    }
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> Selectable<F> for BN254Fq6<F, NN> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let c0 = BN254Fq2::conditionally_select(cs, flag, &a.c0, &b.c0);
+        let c1 = BN254Fq2::conditionally_select(cs, flag, &a.c1, &b.c1);
+        let c2 = BN254Fq2::conditionally_select(cs, flag, &a.c2, &b.c2);
+
+        Self::new(c0, c1, c2)
+    }
+}
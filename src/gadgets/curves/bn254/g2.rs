@@ -0,0 +1,236 @@
+use super::*;
+
+use pairing::bn256::G2Affine;
+use pairing::GenericCurveAffine;
+
+/// Affine point of BN254 G2, that is defined over Fq2 by `y^2 = x^3 + 3 / xi`.
+/// Point at infinity is encoded as `(0, 0)`, as in EIP-197
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct BN254G2AffinePoint<F: SmallField, NN: NonNativeField<F, Fq>> {
+    pub x: BN254Fq2<F, NN>,
+    pub y: BN254Fq2<F, NN>,
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> BN254G2AffinePoint<F, NN> {
+    pub fn from_xy_unchecked(x: BN254Fq2<F, NN>, y: BN254Fq2<F, NN>) -> Self {
+        Self { x, y }
+    }
+
+    /// Allocates coordinates of the point without checking that it's on the curve
+    pub fn allocate_unchecked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: G2Affine,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        let (x, y) = if value.is_zero() {
+            (Fq2::zero(), Fq2::zero())
+        } else {
+            value.into_xy_unchecked()
+        };
+        let x = BN254Fq2::allocate_checked(cs, x, params);
+        let y = BN254Fq2::allocate_checked(cs, y, params);
+
+        Self::from_xy_unchecked(x, y)
+    }
+
+    pub fn constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: G2Affine,
+        params: &Arc<NN::Params>,
+    ) -> Self {
+        assert!(value.is_zero() == false);
+        let (x, y) = value.into_xy_unchecked();
+        let x = BN254Fq2::constant(cs, x, params);
+        let y = BN254Fq2::constant(cs, y, params);
+
+        Self::from_xy_unchecked(x, y)
+    }
+
+    /// Twisted curve coefficient `b = 3 / xi`
+    pub fn b_coeff() -> Fq2 {
+        let mut result = Fq2 {
+            c0: Fq::from_str("3").unwrap(),
+            c1: Fq::zero(),
+        };
+        result.mul_assign(&xi().inverse().unwrap());
+
+        result
+    }
+
+    pub fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let x_is_zero = self.x.is_zero(cs);
+        let y_is_zero = self.y.is_zero(cs);
+
+        x_is_zero.and(cs, y_is_zero)
+    }
+
+    /// Checks that the point satisfies the curve equation. Point at infinity is not on the curve.
+    /// Note that membership in the prime order subgroup is not checked, see `is_in_subgroup`
+    pub fn is_on_curve<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.x.get_params().clone();
+        let mut b = BN254Fq2::constant(cs, Self::b_coeff(), &params);
+
+        let mut lhs = self.y.square(cs);
+        let mut x_squared = self.x.square(cs);
+        let mut x_cubed = x_squared.mul(cs, &mut self.x);
+        let mut rhs = x_cubed.add(cs, &mut b);
+
+        lhs.equals(cs, &mut rhs)
+    }
+
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let y = self.y.negated(cs);
+
+        Self::from_xy_unchecked(self.x.clone(), y)
+    }
+
+    /// Untwist-Frobenius-twist endomorphism
+    /// `psi(x, y) = (x^p * xi^((p - 1) / 3), y^p * xi^((p - 1) / 2))`
+    pub fn psi<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let [gamma, _, _] = frobenius_gammas();
+        let mut gamma_squared = gamma;
+        gamma_squared.square();
+        let mut gamma_cubed = gamma_squared;
+        gamma_cubed.mul_assign(&gamma);
+
+        let mut x = self.x.conjugate(cs);
+        let x = x.mul_by_constant(cs, gamma_squared);
+        let mut y = self.y.conjugate(cs);
+        let y = y.mul_by_constant(cs, gamma_cubed);
+
+        Self::from_xy_unchecked(x, y)
+    }
+
+    /// Checks that the point is in the prime order subgroup, assuming that it's on the curve.
+    /// Point at infinity is in the subgroup. Uses the criterion `psi(Q) == [6x^2] Q` from
+    /// "Co-factor clearing and subgroup membership testing on pairing-friendly curves" by
+    /// El Housni, Guillevic and Piellard. The twist has odd order, so complete formulas of Renes,
+    /// Costello and Batina have no exceptional cases, and points outside of the subgroup give
+    /// `false` instead of an unsatisfiable circuit
+    pub fn is_in_subgroup<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        let params = self.x.get_params().clone();
+
+        // `(0, 0)` is not on the curve, so it's replaced by the generator
+        let is_zero = self.is_zero(cs);
+        let generator = Self::constant(cs, G2Affine::one(), &params);
+        let mut point = Self::conditionally_select(cs, is_zero, &generator, self);
+
+        let mut b3 = Self::b_coeff();
+        b3.mul_assign(&Fq2 {
+            c0: Fq::from_str("3").unwrap(),
+            c1: Fq::zero(),
+        });
+
+        // 6x^2 == p mod r, and the most significant bit is processed by the initialization
+        let scalar = U256::from(BN254_X) * U256::from(BN254_X) * U256::from(6u64);
+        let mut accumulator = (point.x.clone(), point.y.clone(), BN254Fq2::one(cs, &params));
+        for bit in (0..(scalar.bits() - 1)).rev() {
+            accumulator = projective_double(cs, &mut accumulator, b3);
+            if scalar.bit(bit) {
+                accumulator = projective_add_mixed(cs, &mut accumulator, &mut point, b3);
+            }
+        }
+
+        let (mut x, mut y, mut z) = accumulator;
+        let mut expected = point.psi(cs);
+        let mut x_expected = expected.x.mul(cs, &mut z);
+        let mut y_expected = expected.y.mul(cs, &mut z);
+        let x_matches = x.equals(cs, &mut x_expected);
+        let y_matches = y.equals(cs, &mut y_expected);
+        let z_is_zero = z.is_zero(cs);
+        let is_finite = z_is_zero.negated(cs);
+        let in_subgroup = Boolean::multi_and(cs, &[x_matches, y_matches, is_finite]);
+
+        in_subgroup.or(cs, is_zero)
+    }
+}
+
+// Homogeneous projective coordinates `(X : Y : Z)` of the point `(X / Z, Y / Z)`,
+// point at infinity is `(0 : 1 : 0)`
+type ProjectiveCoordinates<F, NN> = (BN254Fq2<F, NN>, BN254Fq2<F, NN>, BN254Fq2<F, NN>);
+
+/// Doubling for `a = 0`, algorithm 9 of https://eprint.iacr.org/2015/1060.pdf
+fn projective_double<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    point: &mut ProjectiveCoordinates<F, NN>,
+    b3: Fq2,
+) -> ProjectiveCoordinates<F, NN> {
+    let (x, y, z) = point;
+
+    let mut t0 = y.square(cs);
+    let mut z3 = t0.double(cs);
+    let mut z3 = z3.double(cs);
+    let mut z3 = z3.double(cs);
+    let mut t1 = y.mul(cs, z);
+    let mut t2 = z.square(cs);
+    let mut t2 = t2.mul_by_constant(cs, b3);
+    let mut x3 = t2.mul(cs, &mut z3);
+    let mut y3 = t0.add(cs, &mut t2);
+    let z3 = t1.mul(cs, &mut z3);
+    let mut t1 = t2.double(cs);
+    let mut t2 = t1.add(cs, &mut t2);
+    let mut t0 = t0.sub(cs, &mut t2);
+    let mut y3 = t0.mul(cs, &mut y3);
+    let y3 = x3.add(cs, &mut y3);
+    let mut t1 = x.mul(cs, y);
+    let mut x3 = t0.mul(cs, &mut t1);
+    let x3 = x3.double(cs);
+
+    (x3, y3, z3)
+}
+
+/// Mixed addition for `a = 0`, algorithm 8 of https://eprint.iacr.org/2015/1060.pdf.
+/// The affine point must not be the point at infinity
+fn projective_add_mixed<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    point: &mut ProjectiveCoordinates<F, NN>,
+    other: &mut BN254G2AffinePoint<F, NN>,
+    b3: Fq2,
+) -> ProjectiveCoordinates<F, NN> {
+    let (x1, y1, z1) = point;
+    let (x2, y2) = (&mut other.x, &mut other.y);
+
+    let mut t0 = x1.mul(cs, x2);
+    let mut t1 = y1.mul(cs, y2);
+    let mut t3 = x2.add(cs, y2);
+    let mut t4 = x1.add(cs, y1);
+    let mut t3 = t3.mul(cs, &mut t4);
+    let mut t4 = t0.add(cs, &mut t1);
+    let mut t3 = t3.sub(cs, &mut t4);
+    let mut t4 = y2.mul(cs, z1);
+    let mut t4 = t4.add(cs, y1);
+    let mut y3 = x2.mul(cs, z1);
+    let mut y3 = y3.add(cs, x1);
+    let mut x3 = t0.double(cs);
+    let mut t0 = x3.add(cs, &mut t0);
+    let mut t2 = z1.mul_by_constant(cs, b3);
+    let mut z3 = t1.add(cs, &mut t2);
+    let mut t1 = t1.sub(cs, &mut t2);
+    let mut y3 = y3.mul_by_constant(cs, b3);
+    let mut x3 = t4.mul(cs, &mut y3);
+    let mut t2 = t3.mul(cs, &mut t1);
+    let x3 = t2.sub(cs, &mut x3);
+    let mut y3 = y3.mul(cs, &mut t0);
+    let mut t1 = t1.mul(cs, &mut z3);
+    let y3 = t1.add(cs, &mut y3);
+    let mut t0 = t0.mul(cs, &mut t3);
+    let mut z3 = z3.mul(cs, &mut t4);
+    let z3 = z3.add(cs, &mut t0);
+
+    (x3, y3, z3)
+}
+
+impl<F: SmallField, NN: NonNativeField<F, Fq>> Selectable<F> for BN254G2AffinePoint<F, NN> {
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let x = BN254Fq2::conditionally_select(cs, flag, &a.x, &b.x);
+        let y = BN254Fq2::conditionally_select(cs, flag, &a.y, &b.y);
+
+        Self::from_xy_unchecked(x, y)
+    }
+}
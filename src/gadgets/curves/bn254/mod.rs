@@ -0,0 +1,80 @@
+use super::*;
+
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::non_native_field::traits::NonNativeField;
+use crate::gadgets::traits::selectable::Selectable;
+use ethereum_types::U256;
+use pairing::bn256::{Fq, Fq12, Fq2, Fq6};
+use pairing::ff::{Field, PrimeField};
+use std::sync::Arc;
+
+pub mod ate_pairing;
+pub mod fq12;
+pub mod fq2;
+pub mod fq6;
+pub mod g2;
+pub mod precompile;
+
+pub use self::ate_pairing::{final_exponentiation, miller_loop, multi_pairing_is_one};
+pub use self::fq12::BN254Fq12;
+pub use self::fq2::BN254Fq2;
+pub use self::fq6::BN254Fq6;
+pub use self::g2::BN254G2AffinePoint;
+pub use self::precompile::ec_pairing_precompile;
+
+// BN254 extension tower, same as the one used by `pairing::bn256`:
+// - Fq2 = Fq[u] / (u^2 + 1)
+// - Fq6 = Fq2[v] / (v^3 - xi), xi = 9 + u
+// - Fq12 = Fq6[w] / (w^2 - v)
+// Every tower operation returns an element with normalized coefficients, so results can be
+// freely combined (and negated) further. Reductions inside of a single operation are postponed
+// over sums of up to 11 moduli, so for `NonNativeFieldOverU16` one more limb than the modulus
+// needs is required (`N = 17`)
+
+/// BN254 curve parameter, the one that defines field characteristic and group order
+pub const BN254_X: u64 = 4965661367192848881;
+
+/// Non-residue `xi = 9 + u` defining Fq6 over Fq2
+pub(crate) fn xi() -> Fq2 {
+    Fq2 {
+        c0: Fq::from_str("9").unwrap(),
+        c1: Fq::one(),
+    }
+}
+
+/// `xi^((p^k - 1) / 6)` for `k = 1, 2, 3`, used for Frobenius maps
+pub(crate) fn frobenius_gammas() -> [Fq2; 3] {
+    let mut modulus = U256::zero();
+    modulus.0.copy_from_slice(Fq::char().as_ref());
+    let exponent = (modulus - U256::one()) / U256::from(6u64);
+
+    let gamma_1 = xi().pow(exponent.0);
+    // gamma_k = gamma_{k - 1} * frobenius^{k - 1}(gamma_1)
+    let mut gamma_2 = gamma_1;
+    gamma_2.frobenius_map(1);
+    gamma_2.mul_assign(&gamma_1);
+    let mut gamma_3 = gamma_1;
+    gamma_3.frobenius_map(2);
+    gamma_3.mul_assign(&gamma_2);
+
+    [gamma_1, gamma_2, gamma_3]
+}
+
+/// Checks that `(x, y)` is on BN254 G1 (`y^2 = x^3 + 3`). Point at infinity is not on the curve
+pub fn g1_is_on_curve<F: SmallField, CS: ConstraintSystem<F>, NN: NonNativeField<F, Fq>>(
+    cs: &mut CS,
+    x: &mut NN,
+    y: &mut NN,
+) -> Boolean<F> {
+    let params = x.get_params().clone();
+    let mut b = NN::allocated_constant(cs, Fq::from_str("3").unwrap(), &params);
+    b.normalize(cs);
+
+    let mut lhs = y.square(cs);
+    let mut x_squared = x.square(cs);
+    let mut x_cubed = x_squared.mul(cs, x);
+    let mut rhs = x_cubed.add(cs, &mut b);
+
+    lhs.equals(cs, &mut rhs)
+}
@@ -0,0 +1,271 @@
+use super::*;
+
+use crate::gadgets::ecdsa::{is_reduced, uint256_into_field_element};
+use crate::gadgets::non_native_field::implementations::{
+    NonNativeFieldOverU16, NonNativeFieldOverU16Params,
+};
+use crate::gadgets::u256::UInt256;
+
+use super::ate_pairing::multi_pairing_is_one_impl;
+
+/// Input of `ecPairing` precompile for a single pair, in the order of EIP-197 encoding:
+/// `x` and `y` of the G1 point, then `x` and `y` of the G2 point, where every Fq2 coordinate
+/// goes as the imaginary part followed by the real one
+pub type EcPairingPairInput<F> = [UInt256<F>; 6];
+
+/// `ecPairing` precompile (EIP-197). Returns the flag of the input being valid, and the result of
+/// the pairing check, that is `false` for invalid inputs. The input is invalid if any coordinate is
+/// not reduced modulo `p`, if any point (except the point at infinity, encoded as zeroes) is not on
+/// the curve, or if any G2 point is not in the prime order subgroup. The precompile fails in such
+/// a case, and the circuit stays satisfiable for any input
+pub fn ec_pairing_precompile<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+    cs: &mut CS,
+    inputs: &[EcPairingPairInput<F>],
+    params: &Arc<NonNativeFieldOverU16Params<Fq, N>>,
+) -> (Boolean<F>, Boolean<F>)
+where
+    [(); N + 1]:,
+{
+    let mut pairs = Vec::with_capacity(inputs.len());
+    let mut validity_flags = Vec::with_capacity(inputs.len());
+    for input in inputs.iter() {
+        // unreduced values are masked, so there are no unreduced field elements below
+        let mut reduced_flags = Vec::with_capacity(6);
+        let [mut p_x, mut p_y, q_x_c1, q_x_c0, q_y_c1, q_y_c0] = input.each_ref().map(|value| {
+            let value_is_reduced = is_reduced::<_, _, Fq>(cs, value);
+            reduced_flags.push(value_is_reduced);
+            let value = value.mask(cs, value_is_reduced);
+
+            uint256_into_field_element::<_, _, Fq, N>(cs, &value, params)
+        });
+
+        let p_x_is_zero = p_x.is_zero(cs);
+        let p_y_is_zero = p_y.is_zero(cs);
+        let p_is_zero = p_x_is_zero.and(cs, p_y_is_zero);
+        let p_is_on_curve = g1_is_on_curve(cs, &mut p_x, &mut p_y);
+        let p_is_valid = p_is_zero.or(cs, p_is_on_curve);
+
+        let q_x = BN254Fq2::new(q_x_c0, q_x_c1);
+        let q_y = BN254Fq2::new(q_y_c0, q_y_c1);
+        let mut q =
+            BN254G2AffinePoint::<F, NonNativeFieldOverU16<F, Fq, N>>::from_xy_unchecked(q_x, q_y);
+        let q_is_zero = q.is_zero(cs);
+        let q_is_on_curve = q.is_on_curve(cs);
+        let q_is_in_subgroup = q.is_in_subgroup(cs);
+        let q_is_valid = q_is_on_curve.and(cs, q_is_in_subgroup);
+        let q_is_valid = q_is_zero.or(cs, q_is_valid);
+
+        reduced_flags.extend([p_is_valid, q_is_valid]);
+        let is_valid = Boolean::multi_and(cs, &reduced_flags);
+        validity_flags.push(is_valid);
+        pairs.push(((p_x, p_y), q));
+    }
+
+    let pairing_is_one = multi_pairing_is_one_impl(cs, &mut pairs, &validity_flags);
+    let is_valid = if validity_flags.is_empty() {
+        Boolean::allocated_constant(cs, true)
+    } else {
+        Boolean::multi_and(cs, &validity_flags)
+    };
+    let result = pairing_is_one.and(cs, is_valid);
+
+    (is_valid, result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::gates::testing_cs::create_test_cs;
+    use crate::cs::gates::*;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::tables::and8::{create_and8_table, And8Table};
+    use crate::gadgets::tables::byte_split::{create_byte_split_table, ByteSplitTable};
+    use crate::gadgets::tables::xor8::{create_xor8_table, Xor8Table};
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::{Bn256, Fr, G1Affine, G2Affine};
+    use pairing::ff::SqrtField;
+    use pairing::{Engine, GenericCurveAffine, GenericCurveProjective};
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+
+    fn into_u256(value: Fq) -> U256 {
+        let mut result = U256::zero();
+        result.0.copy_from_slice(value.into_repr().as_ref());
+
+        result
+    }
+
+    fn encode_coordinates(p: (Fq, Fq), q: (Fq2, Fq2)) -> [U256; 6] {
+        let ((p_x, p_y), (q_x, q_y)) = (p, q);
+
+        [p_x, p_y, q_x.c1, q_x.c0, q_y.c1, q_y.c0].map(into_u256)
+    }
+
+    fn encode_pair(p: G1Affine, q: G2Affine) -> [U256; 6] {
+        let p = if p.is_zero() {
+            (Fq::zero(), Fq::zero())
+        } else {
+            p.into_xy_unchecked()
+        };
+        let q = if q.is_zero() {
+            (Fq2::zero(), Fq2::zero())
+        } else {
+            q.into_xy_unchecked()
+        };
+
+        encode_coordinates(p, q)
+    }
+
+    // point on the twist, but not in the prime order subgroup
+    fn g2_point_outside_of_subgroup() -> G2Affine {
+        let mut x = Fq2::one();
+        loop {
+            x.add_assign(&Fq2::one());
+            let mut rhs = x;
+            rhs.square();
+            rhs.mul_assign(&x);
+            rhs.add_assign(&G2Affine::b_coeff());
+            if let Some(y) = rhs.sqrt() {
+                let point = G2Affine::from_xy_unchecked(x, y);
+                if point.mul(Fr::char()).is_zero() == false {
+                    return point;
+                }
+            }
+        }
+    }
+
+    fn run_precompile(inputs: &[[U256; 6]]) -> (bool, bool) {
+        let mut owned_cs =
+            create_test_cs::<F, (UIntXAddGate<32>, UIntXAddGate<16>)>(1 << 26, 3, 8, 1 << 30);
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+        let table = create_and8_table();
+        owned_cs.add_lookup_table::<And8Table, 3>(table);
+        let table = create_byte_split_table::<F, 1>();
+        owned_cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        owned_cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        owned_cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        owned_cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let inputs: Vec<_> = inputs
+            .iter()
+            .map(|input| input.map(|value| UInt256::allocate(cs, value)))
+            .collect();
+        let (is_valid, result) = ec_pairing_precompile(cs, &inputs, &params);
+        let is_valid = is_valid.witness_hook(&*cs)().unwrap();
+        let result = result.witness_hook(&*cs)().unwrap();
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+
+        (is_valid, result)
+    }
+
+    #[test]
+    fn test_g2_subgroup_check() {
+        let mut owned_cs = create_cs(1 << 22);
+        let cs = &mut owned_cs;
+
+        let params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let q_outside = g2_point_outside_of_subgroup();
+        let q_inside = G2Affine::one()
+            .mul(Fr::from_str("1234567").unwrap().into_repr())
+            .into_affine();
+        let cases = [
+            (G2Affine::one(), true),
+            (q_inside, true),
+            (G2Affine::zero(), true),
+            (q_outside, false),
+        ];
+        for (value, expected) in cases {
+            let mut point =
+                BN254G2AffinePoint::<F, NonNativeFieldOverU16<F, Fq, 17>>::allocate_unchecked(
+                    cs, value, &params,
+                );
+            let in_subgroup = point.is_in_subgroup(cs);
+            assert_eq!(in_subgroup.witness_hook(&*cs)().unwrap(), expected);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    #[ignore = "pairing check is a large circuit, run explicitly"]
+    fn test_ec_pairing_precompile_valid_inputs() {
+        let a = Fr::from_str("1234567").unwrap();
+        let b = Fr::from_str("7654321").unwrap();
+        let mut ab = a;
+        ab.mul_assign(&b);
+        ab.negate();
+        let p_a = G1Affine::one().mul(a.into_repr()).into_affine();
+        let q_b = G2Affine::one().mul(b.into_repr()).into_affine();
+        let p_ab = G1Affine::one().mul(ab.into_repr()).into_affine();
+
+        // e(a * P, b * Q) * e(-ab * P, Q) * e(0, Q) * e(P, 0) == 1
+        let mut expected = Bn256::pairing(p_a, q_b);
+        expected.mul_assign(&Bn256::pairing(p_ab, G2Affine::one()));
+        assert_eq!(expected, Fq12::one());
+        let inputs = [
+            encode_pair(p_a, q_b),
+            encode_pair(p_ab, G2Affine::one()),
+            encode_pair(G1Affine::zero(), G2Affine::one()),
+            encode_pair(G1Affine::one(), G2Affine::zero()),
+        ];
+        assert_eq!(run_precompile(&inputs), (true, true));
+
+        // e(a * P, b * Q) != 1
+        assert!(Bn256::pairing(p_a, q_b) != Fq12::one());
+        assert_eq!(run_precompile(&[encode_pair(p_a, q_b)]), (true, false));
+
+        // empty input
+        assert_eq!(run_precompile(&[]), (true, true));
+    }
+
+    #[test]
+    #[ignore = "pairing check is a large circuit, run explicitly"]
+    fn test_ec_pairing_precompile_invalid_inputs() {
+        let p = G1Affine::one();
+        let q = G2Affine::one();
+        let (p_x, p_y) = p.into_xy_unchecked();
+        let (q_x, q_y) = q.into_xy_unchecked();
+
+        // G1 point outside of the curve
+        let mut p_y_shifted = p_y;
+        p_y_shifted.add_assign(&Fq::one());
+        let input = encode_coordinates((p_x, p_y_shifted), (q_x, q_y));
+        assert_eq!(run_precompile(&[input]), (false, false));
+
+        // G2 point outside of the curve
+        let mut q_y_shifted = q_y;
+        q_y_shifted.add_assign(&Fq2::one());
+        let input = encode_coordinates((p_x, p_y), (q_x, q_y_shifted));
+        assert_eq!(run_precompile(&[input]), (false, false));
+
+        // G2 point on the curve, but outside of the subgroup, together with a valid pair
+        let q_outside = g2_point_outside_of_subgroup();
+        let inputs = [encode_pair(p, q_outside), encode_pair(p, q)];
+        assert_eq!(run_precompile(&inputs), (false, false));
+
+        // unreduced coordinate, `p + x` encodes the same field element as `x`
+        let mut input = encode_pair(p, q);
+        let mut modulus = U256::zero();
+        modulus.0.copy_from_slice(Fq::char().as_ref());
+        input[0] = input[0] + modulus;
+        assert_eq!(run_precompile(&[input]), (false, false));
+    }
+}
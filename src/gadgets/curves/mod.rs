@@ -1,4 +1,6 @@
 use super::*;
 
+pub mod bn254;
+pub mod secp256k1;
 pub mod sw_projective;
 pub mod zeroable_affine;
@@ -0,0 +1,7 @@
+use pairing::ff::*;
+
+// base field, q = 2^256 - 2^32 - 977
+#[derive(PrimeField)]
+#[PrimeFieldModulus = "115792089237316195423570985008687907853269984665640564039457584007908834671663"]
+#[PrimeFieldGenerator = "3"]
+pub struct Fq(FqRepr);
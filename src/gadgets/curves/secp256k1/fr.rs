@@ -0,0 +1,7 @@
+use pairing::ff::*;
+
+// scalar field, n = 2^256 - 432420386565659656852420866394968145599
+#[derive(PrimeField)]
+#[PrimeFieldModulus = "115792089237316195423570985008687907852837564279074904382605163141518161494337"]
+#[PrimeFieldGenerator = "7"]
+pub struct Fr(FrRepr);
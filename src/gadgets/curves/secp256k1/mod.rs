@@ -0,0 +1,496 @@
+use pairing::ff::rand::{Rand, Rng};
+use pairing::ff::{BitIterator, Field, PrimeField, PrimeFieldRepr, SqrtField};
+use pairing::{GenericCurveAffine, GenericCurveProjective, GroupDecodingError};
+use std::fmt;
+
+pub mod fq;
+pub mod fr;
+
+pub use self::fq::Fq;
+pub use self::fr::Fr;
+
+// secp256k1 is `y^2 = x^3 + 7` over Fq. It has prime order, so every point on the curve
+// (except infinity) generates the whole group and no subgroup checks are needed.
+// Projective points use Jacobian coordinates: (X, Y, Z) represents (X / Z^2, Y / Z^3)
+
+const GENERATOR_X: &str =
+    "55066263022277343669578718895168534326250603453777594175500187360389116729240";
+const GENERATOR_Y: &str =
+    "32670510020758816978083085130507043184471273380659243275938904335757337482424";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PointAffine {
+    x: Fq,
+    y: Fq,
+    infinity: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PointProjective {
+    x: Fq,
+    y: Fq,
+    z: Fq,
+}
+
+impl fmt::Display for PointAffine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.infinity {
+            write!(f, "secp256k1(Infinity)")
+        } else {
+            write!(f, "secp256k1(x={}, y={})", self.x, self.y)
+        }
+    }
+}
+
+impl fmt::Display for PointProjective {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.into_affine())
+    }
+}
+
+impl PartialEq for PointProjective {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_zero() {
+            return other.is_zero();
+        }
+        if other.is_zero() {
+            return false;
+        }
+
+        // X1 * Z2^2 == X2 * Z1^2 and Y1 * Z2^3 == Y2 * Z1^3
+        let mut z1_squared = self.z;
+        z1_squared.square();
+        let mut z2_squared = other.z;
+        z2_squared.square();
+
+        let mut lhs = self.x;
+        lhs.mul_assign(&z2_squared);
+        let mut rhs = other.x;
+        rhs.mul_assign(&z1_squared);
+        if lhs != rhs {
+            return false;
+        }
+
+        z1_squared.mul_assign(&self.z);
+        z2_squared.mul_assign(&other.z);
+        let mut lhs = self.y;
+        lhs.mul_assign(&z2_squared);
+        let mut rhs = other.y;
+        rhs.mul_assign(&z1_squared);
+
+        lhs == rhs
+    }
+}
+
+impl Eq for PointProjective {}
+
+impl PointAffine {
+    fn curve_equation_rhs(x: &Fq) -> Fq {
+        let mut rhs = *x;
+        rhs.square();
+        rhs.mul_assign(x);
+        rhs.add_assign(&Self::b_coeff());
+
+        rhs
+    }
+
+    fn is_on_curve(&self) -> bool {
+        if self.infinity {
+            return true;
+        }
+        let mut lhs = self.y;
+        lhs.square();
+
+        lhs == Self::curve_equation_rhs(&self.x)
+    }
+
+    /// Returns a point with the given `x` and the larger (if `greatest`) of two possible `y`,
+    /// or `None` if `x` is not a coordinate of any point
+    pub fn get_point_from_x(x: Fq, greatest: bool) -> Option<Self> {
+        let y = Self::curve_equation_rhs(&x).sqrt()?;
+        let mut negated_y = y;
+        negated_y.negate();
+        let y_is_greatest = y.into_repr() > negated_y.into_repr();
+        let y = if y_is_greatest == greatest {
+            y
+        } else {
+            negated_y
+        };
+
+        Some(Self {
+            x,
+            y,
+            infinity: false,
+        })
+    }
+}
+
+impl GenericCurveAffine for PointAffine {
+    type Scalar = Fr;
+    type Base = Fq;
+    type Projective = PointProjective;
+
+    fn zero() -> Self {
+        Self {
+            x: Fq::zero(),
+            y: Fq::one(),
+            infinity: true,
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            x: Fq::from_str(GENERATOR_X).unwrap(),
+            y: Fq::from_str(GENERATOR_Y).unwrap(),
+            infinity: false,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.infinity
+    }
+
+    fn negate(&mut self) {
+        if !self.infinity {
+            self.y.negate();
+        }
+    }
+
+    fn mul<S: Into<<Self::Scalar as PrimeField>::Repr>>(&self, other: S) -> Self::Projective {
+        let mut result = self.into_projective();
+        result.mul_assign(other);
+
+        result
+    }
+
+    fn into_projective(&self) -> Self::Projective {
+        if self.infinity {
+            PointProjective::zero()
+        } else {
+            PointProjective {
+                x: self.x,
+                y: self.y,
+                z: Fq::one(),
+            }
+        }
+    }
+
+    fn as_xy(&self) -> (&Self::Base, &Self::Base) {
+        (&self.x, &self.y)
+    }
+
+    fn into_xy_unchecked(self) -> (Self::Base, Self::Base) {
+        (self.x, self.y)
+    }
+
+    fn from_xy_unchecked(x: Self::Base, y: Self::Base) -> Self {
+        Self {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    fn from_xy_checked(x: Self::Base, y: Self::Base) -> Result<Self, GroupDecodingError> {
+        let point = Self::from_xy_unchecked(x, y);
+        if point.is_on_curve() {
+            Ok(point)
+        } else {
+            Err(GroupDecodingError::NotOnCurve)
+        }
+    }
+
+    fn a_coeff() -> Self::Base {
+        Fq::zero()
+    }
+
+    fn b_coeff() -> Self::Base {
+        Fq::from_str("7").unwrap()
+    }
+}
+
+impl Rand for PointProjective {
+    fn rand<R: Rng>(rng: &mut R) -> Self {
+        loop {
+            let x = Fq::rand(rng);
+            let greatest = rng.gen();
+            if let Some(point) = PointAffine::get_point_from_x(x, greatest) {
+                return point.into_projective();
+            }
+        }
+    }
+}
+
+impl GenericCurveProjective for PointProjective {
+    type Scalar = Fr;
+    type Base = Fq;
+    type Affine = PointAffine;
+
+    fn zero() -> Self {
+        Self {
+            x: Fq::zero(),
+            y: Fq::one(),
+            z: Fq::zero(),
+        }
+    }
+
+    fn one() -> Self {
+        PointAffine::one().into_projective()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    fn batch_normalization(v: &mut [Self]) {
+        for point in v.iter_mut() {
+            *point = point.into_affine().into_projective();
+        }
+    }
+
+    fn is_normalized(&self) -> bool {
+        self.is_zero() || self.z == Fq::one()
+    }
+
+    fn double(&mut self) {
+        if self.is_zero() {
+            return;
+        }
+
+        // dbl-2009-l, a = 0
+        let mut a = self.x;
+        a.square();
+        let mut b = self.y;
+        b.square();
+        let mut c = b;
+        c.square();
+
+        // D = 2 * ((X1 + B)^2 - A - C)
+        let mut d = self.x;
+        d.add_assign(&b);
+        d.square();
+        d.sub_assign(&a);
+        d.sub_assign(&c);
+        d.double();
+
+        // E = 3 * A, F = E^2
+        let mut e = a;
+        e.double();
+        e.add_assign(&a);
+        let mut f = e;
+        f.square();
+
+        // Z3 = 2 * Y1 * Z1
+        self.z.mul_assign(&self.y);
+        self.z.double();
+
+        // X3 = F - 2 * D
+        self.x = f;
+        self.x.sub_assign(&d);
+        self.x.sub_assign(&d);
+
+        // Y3 = E * (D - X3) - 8 * C
+        self.y = d;
+        self.y.sub_assign(&self.x);
+        self.y.mul_assign(&e);
+        c.double();
+        c.double();
+        c.double();
+        self.y.sub_assign(&c);
+    }
+
+    fn add_assign(&mut self, other: &Self) {
+        if self.is_zero() {
+            *self = *other;
+            return;
+        }
+        if other.is_zero() {
+            return;
+        }
+
+        // add-2007-bl
+        let mut z1z1 = self.z;
+        z1z1.square();
+        let mut z2z2 = other.z;
+        z2z2.square();
+
+        let mut u1 = self.x;
+        u1.mul_assign(&z2z2);
+        let mut u2 = other.x;
+        u2.mul_assign(&z1z1);
+
+        let mut s1 = self.y;
+        s1.mul_assign(&other.z);
+        s1.mul_assign(&z2z2);
+        let mut s2 = other.y;
+        s2.mul_assign(&self.z);
+        s2.mul_assign(&z1z1);
+
+        if u1 == u2 && s1 == s2 {
+            self.double();
+            return;
+        }
+
+        // H = U2 - U1, I = (2 * H)^2, J = H * I
+        let mut h = u2;
+        h.sub_assign(&u1);
+        let mut i = h;
+        i.double();
+        i.square();
+        let mut j = h;
+        j.mul_assign(&i);
+
+        // r = 2 * (S2 - S1), V = U1 * I
+        let mut r = s2;
+        r.sub_assign(&s1);
+        r.double();
+        let mut v = u1;
+        v.mul_assign(&i);
+
+        // X3 = r^2 - J - 2 * V
+        self.x = r;
+        self.x.square();
+        self.x.sub_assign(&j);
+        self.x.sub_assign(&v);
+        self.x.sub_assign(&v);
+
+        // Y3 = r * (V - X3) - 2 * S1 * J
+        self.y = v;
+        self.y.sub_assign(&self.x);
+        self.y.mul_assign(&r);
+        s1.mul_assign(&j);
+        s1.double();
+        self.y.sub_assign(&s1);
+
+        // Z3 = ((Z1 + Z2)^2 - Z1Z1 - Z2Z2) * H, that is zero for P + (-P)
+        self.z.add_assign(&other.z);
+        self.z.square();
+        self.z.sub_assign(&z1z1);
+        self.z.sub_assign(&z2z2);
+        self.z.mul_assign(&h);
+    }
+
+    fn add_assign_mixed(&mut self, other: &Self::Affine) {
+        self.add_assign(&other.into_projective());
+    }
+
+    fn negate(&mut self) {
+        if !self.is_zero() {
+            self.y.negate();
+        }
+    }
+
+    fn mul_assign<S: Into<<Self::Scalar as PrimeField>::Repr>>(&mut self, other: S) {
+        let mut result = Self::zero();
+        for bit in BitIterator::new(other.into()) {
+            result.double();
+            if bit {
+                result.add_assign(self);
+            }
+        }
+
+        *self = result;
+    }
+
+    fn into_affine(&self) -> Self::Affine {
+        if self.is_zero() {
+            return PointAffine::zero();
+        }
+
+        let z_inversed = self.z.inverse().unwrap();
+        let mut z_inversed_squared = z_inversed;
+        z_inversed_squared.square();
+
+        let mut x = self.x;
+        x.mul_assign(&z_inversed_squared);
+        let mut y = self.y;
+        y.mul_assign(&z_inversed_squared);
+        y.mul_assign(&z_inversed);
+
+        PointAffine::from_xy_unchecked(x, y)
+    }
+
+    fn recommended_wnaf_for_scalar(scalar: <Self::Scalar as PrimeField>::Repr) -> usize {
+        let num_bits = scalar.num_bits() as usize;
+        if num_bits >= 130 {
+            4
+        } else if num_bits >= 34 {
+            3
+        } else {
+            2
+        }
+    }
+
+    fn recommended_wnaf_for_num_scalars(num_scalars: usize) -> usize {
+        const RECOMMENDATIONS: [usize; 12] =
+            [1, 3, 7, 20, 43, 120, 273, 563, 1630, 3128, 7933, 62569];
+
+        let mut result = 4;
+        for threshold in RECOMMENDATIONS.iter() {
+            if num_scalars > *threshold {
+                result += 1;
+            } else {
+                break;
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1_group_law() {
+        let generator = PointAffine::one();
+        assert!(generator.is_on_curve());
+        assert!(
+            PointAffine::from_xy_checked(generator.x, Fq::one()).is_err(),
+            "point off the curve must be rejected"
+        );
+
+        // n * G = O, (n - 1) * G = -G
+        let mut order_minus_one = Fr::one();
+        order_minus_one.negate();
+        let mut negated_generator = generator;
+        negated_generator.negate();
+        assert_eq!(
+            generator.mul(order_minus_one.into_repr()).into_affine(),
+            negated_generator
+        );
+        let mut sum = generator.mul(order_minus_one.into_repr());
+        sum.add_assign_mixed(&generator);
+        assert!(sum.is_zero());
+
+        // (a + b) * G = a * G + b * G, including doubling inside of the addition
+        let a = Fr::from_str("123456789").unwrap();
+        let b = Fr::from_str("987654321").unwrap();
+        let mut a_plus_b = a;
+        a_plus_b.add_assign(&b);
+        let mut sum = generator.mul(a.into_repr());
+        sum.add_assign(&generator.mul(b.into_repr()));
+        assert_eq!(sum, generator.mul(a_plus_b.into_repr()));
+        let mut doubled = generator.mul(a.into_repr());
+        doubled.add_assign(&generator.mul(a.into_repr()));
+        let mut two_a = a;
+        two_a.double();
+        assert_eq!(
+            doubled.into_affine(),
+            generator.mul(two_a.into_repr()).into_affine()
+        );
+
+        // 2 * G of the standard test vectors
+        let mut two_g = generator.into_projective();
+        two_g.double();
+        let (x, _) = two_g.into_affine().into_xy_unchecked();
+        assert_eq!(
+            x,
+            Fq::from_str(
+                "89565891926547004231252920425935692360644145829622209833684329913297188986597"
+            )
+            .unwrap()
+        );
+    }
+}
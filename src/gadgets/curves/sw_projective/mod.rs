@@ -7,6 +7,8 @@ use crate::{
 };
 use pairing::GenericCurveAffine;
 
+pub mod scalar_mul;
+
 // https://eprint.iacr.org/2015/1060.pdf
 
 #[derive(Derivative)]
@@ -451,6 +453,229 @@ where
         new
     }
 
+    fn add_sub_impl<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut Self,
+        is_subtraction: bool,
+    ) -> Self {
+        use pairing::ff::Field;
+        if C::a_coeff().is_zero() == false {
+            return self.generic_add_sub_impl(cs, other, is_subtraction);
+        }
+
+        let params = self.x.get_params().clone();
+
+        let curve_b = C::b_coeff();
+        let mut curve_b3 = curve_b;
+        curve_b3.double();
+        curve_b3.add_assign(&curve_b);
+
+        let mut curve_b3 = NN::allocated_constant(cs, curve_b3, &params);
+
+        let x1 = &mut self.x;
+        let y1 = &mut self.y;
+        let z1 = &mut self.z;
+
+        let mut y2_local: NN = other.y.clone();
+        let x2 = &mut other.x;
+        let z2 = &mut other.z;
+        if is_subtraction {
+            y2_local = y2_local.negated(cs);
+        }
+        let y2 = &mut y2_local;
+
+        // t0 = x1 * x2
+        let mut t0 = x1.mul(cs, x2);
+        // t1 = y1 * y2
+        let mut t1 = y1.mul(cs, y2);
+        // t2 = z1 * z2
+        let mut t2 = z1.mul(cs, z2);
+
+        // t3 = (x1 + y1) * (x2 + y2) - t0 - t1
+        let mut a = x1.add(cs, y1);
+        let mut b = x2.add(cs, y2);
+        let mut t3 = a.mul(cs, &mut b);
+        let mut t4 = t0.add(cs, &mut t1);
+        let mut t3 = t3.sub(cs, &mut t4);
+
+        // t4 = (y1 + z1) * (y2 + z2) - t1 - t2
+        let mut a = y1.add(cs, z1);
+        let mut b = y2.add(cs, z2);
+        let mut t4 = a.mul(cs, &mut b);
+        let mut tmp = t1.add(cs, &mut t2);
+        let mut t4 = t4.sub(cs, &mut tmp);
+
+        // y3 = (x1 + z1) * (x2 + z2) - t0 - t2
+        let mut a = x1.add(cs, z1);
+        let mut b = x2.add(cs, z2);
+        let mut y3 = a.mul(cs, &mut b);
+        let mut tmp = t0.add(cs, &mut t2);
+        let mut y3 = y3.sub(cs, &mut tmp);
+
+        // t0 = 3 * t0
+        let mut t0_mul_2 = t0.double(cs);
+        let mut t0 = t0_mul_2.add(cs, &mut t0);
+        // t2 = b3 * t2
+        let mut t2 = t2.mul(cs, &mut curve_b3);
+        // z3 = t1 + t2
+        let mut z3 = t1.add(cs, &mut t2);
+        // t1 = t1 - t2
+        let mut t1 = t1.sub(cs, &mut t2);
+        // y3 = b3 * y3
+        let mut y3 = y3.mul(cs, &mut curve_b3);
+
+        // x3 = t3 * t1 - t4 * y3
+        let mut x3 = t4.mul(cs, &mut y3);
+        let mut t2 = t3.mul(cs, &mut t1);
+        let x3 = t2.sub(cs, &mut x3);
+
+        // y3 = t1 * z3 + y3 * t0
+        let mut y3 = y3.mul(cs, &mut t0);
+        let mut t1 = t1.mul(cs, &mut z3);
+        let y3 = t1.add(cs, &mut y3);
+
+        // z3 = z3 * t4 + t0 * t3
+        let mut t0 = t0.mul(cs, &mut t3);
+        let mut z3 = z3.mul(cs, &mut t4);
+        let z3 = z3.add(cs, &mut t0);
+
+        let new = Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            _marker: std::marker::PhantomData,
+        };
+
+        new
+    }
+
+    fn generic_add_sub_impl<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut Self,
+        is_subtraction: bool,
+    ) -> Self {
+        use pairing::ff::Field;
+        let params = self.x.get_params().clone();
+
+        let curve_b = C::b_coeff();
+        let mut curve_b3 = curve_b;
+        curve_b3.double();
+        curve_b3.add_assign(&curve_b);
+
+        let mut curve_a = NN::allocated_constant(cs, C::a_coeff(), &params);
+        let mut curve_b3 = NN::allocated_constant(cs, curve_b3, &params);
+
+        let x1 = &mut self.x;
+        let y1 = &mut self.y;
+        let z1 = &mut self.z;
+
+        let mut y2_local: NN = other.y.clone();
+        let x2 = &mut other.x;
+        let z2 = &mut other.z;
+        if is_subtraction {
+            y2_local = y2_local.negated(cs);
+        }
+        let y2 = &mut y2_local;
+
+        // t0 = x1 * x2
+        let mut t0 = x1.mul(cs, x2);
+        // t1 = y1 * y2
+        let mut t1 = y1.mul(cs, y2);
+        // t2 = z1 * z2
+        let mut t2 = z1.mul(cs, z2);
+
+        // t3 = (x1 + y1) * (x2 + y2) - t0 - t1
+        let mut a = x1.add(cs, y1);
+        let mut b = x2.add(cs, y2);
+        let mut t3 = a.mul(cs, &mut b);
+        let mut t4 = t0.add(cs, &mut t1);
+        let mut t3 = t3.sub(cs, &mut t4);
+
+        // t4 = (x1 + z1) * (x2 + z2) - t0 - t2
+        let mut a = x1.add(cs, z1);
+        let mut b = x2.add(cs, z2);
+        let mut t4 = a.mul(cs, &mut b);
+        let mut t5 = t0.add(cs, &mut t2);
+        let mut t4 = t4.sub(cs, &mut t5);
+
+        // t5 = (y1 + z1) * (y2 + z2) - t1 - t2
+        let mut a = y1.add(cs, z1);
+        let mut b = y2.add(cs, z2);
+        let mut t5 = a.mul(cs, &mut b);
+        let mut x3 = t1.add(cs, &mut t2);
+        let mut t5 = t5.sub(cs, &mut x3);
+
+        // z3 = a * t4
+        let mut z3 = curve_a.mul(cs, &mut t4);
+        // x3 = b3 * t2
+        let mut x3 = curve_b3.mul(cs, &mut t2);
+        // z3 = x3 + z3
+        let mut z3 = x3.add(cs, &mut z3);
+        // x3 = t1 - z3
+        let mut x3 = t1.sub(cs, &mut z3);
+        // z3 = t1 + z3
+        let mut z3 = t1.add(cs, &mut z3);
+        // y3 = x3 * z3
+        let mut y3 = x3.mul(cs, &mut z3);
+
+        // t1 = t0 + t0 + t0
+        let mut t1 = t0.double(cs);
+        let mut t1 = t1.add(cs, &mut t0);
+        // t2 = a * t2
+        let mut t2 = curve_a.mul(cs, &mut t2);
+        // t4 = b3 * t4
+        let mut t4 = curve_b3.mul(cs, &mut t4);
+
+        // t1 = t1 + t2
+        let mut t1 = t1.add(cs, &mut t2);
+        // t2 = t0 - t2
+        let mut t2 = t0.sub(cs, &mut t2);
+        // t2 = a * t2
+        let mut t2 = curve_a.mul(cs, &mut t2);
+
+        // t4 = t4 + t2
+        let mut t4 = t4.add(cs, &mut t2);
+        // t0 = t1 * t4
+        let mut t0 = t1.mul(cs, &mut t4);
+        // y3 = y3 + t0
+        let y3 = y3.add(cs, &mut t0);
+
+        // t0 = t5 * t4
+        let mut t0 = t5.mul(cs, &mut t4);
+        // x3 = t3 * x3
+        let mut x3 = t3.mul(cs, &mut x3);
+        // x3 = x3 - t0
+        let x3 = x3.sub(cs, &mut t0);
+
+        // t0 = t3 * t1
+        let mut t0 = t3.mul(cs, &mut t1);
+        // z3 = t5 * z3
+        let mut z3 = t5.mul(cs, &mut z3);
+        // z3 = z3 + t0
+        let z3 = z3.add(cs, &mut t0);
+
+        let new = Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            _marker: std::marker::PhantomData,
+        };
+
+        new
+    }
+
+    /// Complete addition of two points in projective form
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        self.add_sub_impl(cs, other, false)
+    }
+
+    /// Complete subtraction of two points in projective form
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        self.add_sub_impl(cs, other, true)
+    }
+
     pub fn add_mixed<CS: ConstraintSystem<F>>(
         &mut self,
         cs: &mut CS,
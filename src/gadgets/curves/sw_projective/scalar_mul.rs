@@ -0,0 +1,504 @@
+use super::*;
+
+use pairing::GenericCurveProjective;
+use std::sync::Arc;
+
+// All multiplications below recode the scalar `k` into signed odd digits `d_i` from [-(2^w - 1), 2^w - 1]
+// such that `k = sum d_i * 2^{w * i}`. It requires `k` to be odd, so for even `k` we multiply by `k + 1`
+// (it's just a lowest bit set) and subtract the base in the end. Odd digits never select the point at infinity
+// from the table of multiples, and both the sign and magnitude of a digit are just bits of the scalar
+// (see `signed_odd_digits`), so together with complete addition formulas there are no exceptional cases
+
+pub const MAX_WINDOW_WIDTH: usize = 8;
+
+struct SignedOddDigit<F: SmallField> {
+    is_positive: Boolean<F>,
+    // bits of `u` such that absolute value of the digit is `2 * u + 1`
+    index_bits: Vec<Boolean<F>>,
+}
+
+// Recodes the scalar given by it's little-endian bits. Digits are returned from the least significant one
+fn signed_odd_digits<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    bits: &[Boolean<F>],
+    window_width: usize,
+) -> Vec<SignedOddDigit<F>> {
+    assert!(window_width > 0 && window_width <= MAX_WINDOW_WIDTH);
+    assert!(bits.is_empty() == false);
+
+    // for odd `k` we have `k = sum (2 * b_{i+1} - 1) * 2^i` over all `i` from 0 to the (padded) bit length,
+    // except the top coefficient that is always `+1`. Grouping it by `w` terms gives a digit
+    // which sign is determined by its top coefficient, and absolute value is `2 * u + 1` where `u` is made of
+    // the lower `w - 1` coefficients, that are inverted if the digit is negative
+    let num_windows = (bits.len() + window_width - 1) / window_width;
+    let boolean_false = Boolean::allocated_constant(cs, false);
+    let boolean_true = Boolean::allocated_constant(cs, true);
+    let bit = |idx: usize| bits.get(idx).copied().unwrap_or(boolean_false);
+
+    let mut digits = Vec::with_capacity(num_windows);
+    for window_idx in 0..num_windows {
+        let offset = window_idx * window_width;
+        let is_positive = if window_idx == num_windows - 1 {
+            boolean_true
+        } else {
+            bit(offset + window_width)
+        };
+        let is_negative = is_positive.negated(cs);
+
+        let mut index_bits = Vec::with_capacity(window_width - 1);
+        for idx in 1..window_width {
+            let index_bit = bit(offset + idx).xor(cs, is_negative);
+            index_bits.push(index_bit);
+        }
+
+        digits.push(SignedOddDigit {
+            is_positive,
+            index_bits,
+        });
+    }
+
+    digits
+}
+
+// Selects `table[index]` where index is given by little-endian bits
+fn select_by_bits<F: SmallField, CS: ConstraintSystem<F>, T: Clone>(
+    cs: &mut CS,
+    table: &[T],
+    bits: &[Boolean<F>],
+    select_fn: impl Fn(&mut CS, Boolean<F>, &T, &T) -> T,
+) -> T {
+    assert_eq!(table.len(), 1 << bits.len());
+
+    let mut layer = table.to_vec();
+    for bit in bits.iter() {
+        let mut next_layer = Vec::with_capacity(layer.len() / 2);
+        for pair in layer.array_chunks::<2>() {
+            next_layer.push(select_fn(cs, *bit, &pair[1], &pair[0]));
+        }
+        layer = next_layer;
+    }
+    debug_assert_eq!(layer.len(), 1);
+
+    layer.pop().unwrap()
+}
+
+impl<F: SmallField, C: GenericCurveAffine, NN: NonNativeField<F, C::Base>>
+    SWProjectivePoint<F, C, NN>
+where
+    C::Base: pairing::ff::PrimeField,
+{
+    // [P, 3P, 5P, ..., (2^w - 1)P]
+    fn odd_multiples_table<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        window_width: usize,
+    ) -> Vec<Self> {
+        let table_size = 1 << (window_width - 1);
+        let mut doubled = self.double(cs);
+
+        let mut table = Vec::with_capacity(table_size);
+        table.push(self.clone());
+        for _ in 1..table_size {
+            let mut previous = table.last().unwrap().clone();
+            let next = previous.add(cs, &mut doubled);
+            table.push(next);
+        }
+
+        table
+    }
+
+    fn select_signed<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        table: &[Self],
+        digit: &SignedOddDigit<F>,
+    ) -> Self {
+        let mut point = select_by_bits(cs, table, &digit.index_bits, |cs, flag, a, b| {
+            Self::conditionally_select(cs, flag, a, b)
+        });
+        let y_negated = point.y.negated(cs);
+        point.y = NN::conditionally_select(cs, digit.is_positive, &point.y, &y_negated);
+
+        point
+    }
+
+    /// Variable base scalar multiplication with signed windows of `window_width` bits.
+    /// Works for any point (including the point at infinity) and any scalar
+    pub fn mul<CS: ConstraintSystem<F>, NNS: NonNativeField<F, C::Scalar>>(
+        &mut self,
+        cs: &mut CS,
+        scalar: &mut NNS,
+        window_width: usize,
+    ) -> Self {
+        let bits = scalar.decompose_into_le_bits(cs);
+        let digits = signed_odd_digits(cs, &bits, window_width);
+        let table = self.odd_multiples_table(cs, window_width);
+
+        let mut digits = digits.into_iter().rev();
+        let top_digit = digits.next().unwrap();
+        let mut acc = Self::select_signed(cs, &table, &top_digit);
+        for digit in digits {
+            for _ in 0..window_width {
+                acc = acc.double(cs);
+            }
+            let mut selected = Self::select_signed(cs, &table, &digit);
+            acc = acc.add(cs, &mut selected);
+        }
+
+        // we have computed (k + 1) * P for even k
+        let corrected = acc.sub(cs, self);
+        Self::conditionally_select(cs, bits[0], &acc, &corrected)
+    }
+
+    /// Fixed base scalar multiplication. Multiples of the base are precomputed out of circuit for every window,
+    /// so there are no doublings, and the cost is one mixed addition and a table selection per window
+    pub fn mul_by_fixed_base<CS: ConstraintSystem<F>, NNS: NonNativeField<F, C::Scalar>>(
+        cs: &mut CS,
+        base: C,
+        scalar: &mut NNS,
+        params: &Arc<NN::Params>,
+        window_width: usize,
+    ) -> Self {
+        assert!(base.is_zero() == false);
+
+        let bits = scalar.decompose_into_le_bits(cs);
+        let digits = signed_odd_digits(cs, &bits, window_width);
+
+        let table_size = 1 << (window_width - 1);
+        let mut window_base = base.into_projective();
+        let mut acc = Self::zero(cs, params);
+        for digit in digits.iter() {
+            // [B, 3B, 5B, ..., (2^w - 1)B] for B = 2^{w * i} * base. Those are never zero
+            // as the group order is a large prime
+            let mut doubled = window_base;
+            doubled.double();
+            let mut multiple = window_base;
+            let mut table = Vec::with_capacity(table_size);
+            for _ in 0..table_size {
+                let (x, y) = multiple.into_affine().into_xy_unchecked();
+                let x = NN::allocated_constant(cs, x, params);
+                let y = NN::allocated_constant(cs, y, params);
+                table.push((x, y));
+                multiple.add_assign(&doubled);
+            }
+
+            let (x, mut y) = select_by_bits(cs, &table, &digit.index_bits, |cs, flag, a, b| {
+                let x = NN::conditionally_select(cs, flag, &a.0, &b.0);
+                let y = NN::conditionally_select(cs, flag, &a.1, &b.1);
+                (x, y)
+            });
+            let y_negated = y.negated(cs);
+            let y = NN::conditionally_select(cs, digit.is_positive, &y, &y_negated);
+            acc = acc.add_mixed(cs, &mut (x, y));
+
+            for _ in 0..window_width {
+                window_base.double();
+            }
+        }
+
+        // we have computed (k + 1) * base for even k
+        let (x, y) = base.into_xy_unchecked();
+        let x = NN::allocated_constant(cs, x, params);
+        let y = NN::allocated_constant(cs, y, params);
+        let corrected = acc.sub_mixed(cs, &mut (x, y));
+        Self::conditionally_select(cs, bits[0], &acc, &corrected)
+    }
+
+    /// Multiplication of the curve generator, see `mul_by_fixed_base`
+    pub fn mul_by_generator<CS: ConstraintSystem<F>, NNS: NonNativeField<F, C::Scalar>>(
+        cs: &mut CS,
+        scalar: &mut NNS,
+        params: &Arc<NN::Params>,
+        window_width: usize,
+    ) -> Self {
+        Self::mul_by_fixed_base(cs, C::one(), scalar, params, window_width)
+    }
+
+    /// Computes `sum k_i * P_i` using Straus' method, so doublings are shared between all the terms.
+    /// For small batches we are interested in it's cheaper than Pippenger's buckets, that would require
+    /// variable index access to the buckets
+    pub fn multi_scalar_mul<CS: ConstraintSystem<F>, NNS: NonNativeField<F, C::Scalar>>(
+        cs: &mut CS,
+        points: &mut [Self],
+        scalars: &mut [NNS],
+        window_width: usize,
+    ) -> Self {
+        assert_eq!(points.len(), scalars.len());
+
+        let mut all_bits = Vec::with_capacity(points.len());
+        for scalar in scalars.iter_mut() {
+            all_bits.push(scalar.decompose_into_le_bits(cs));
+        }
+
+        Self::multi_scalar_mul_by_bits(cs, points, &all_bits, window_width)
+    }
+
+    /// Same as `multi_scalar_mul`, but scalars are given by their little-endian bits, that don't
+    /// have to be of the same length or to represent elements of the scalar field
+    pub fn multi_scalar_mul_by_bits<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        points: &mut [Self],
+        scalars_bits: &[Vec<Boolean<F>>],
+        window_width: usize,
+    ) -> Self {
+        assert_eq!(points.len(), scalars_bits.len());
+        assert!(points.is_empty() == false);
+
+        let mut all_digits = Vec::with_capacity(points.len());
+        let mut tables = Vec::with_capacity(points.len());
+        for (point, bits) in points.iter_mut().zip(scalars_bits.iter()) {
+            let digits = signed_odd_digits(cs, bits, window_width);
+            let table = point.odd_multiples_table(cs, window_width);
+            all_digits.push(digits);
+            tables.push(table);
+        }
+
+        let num_windows = all_digits.iter().map(|el| el.len()).max().unwrap();
+        let mut acc: Option<Self> = None;
+        for window_idx in (0..num_windows).rev() {
+            if let Some(acc) = acc.as_mut() {
+                for _ in 0..window_width {
+                    *acc = acc.double(cs);
+                }
+            }
+            for (table, digits) in tables.iter().zip(all_digits.iter()) {
+                // shorter scalars just start later
+                if window_idx >= digits.len() {
+                    continue;
+                }
+                let mut selected = Self::select_signed(cs, table, &digits[window_idx]);
+                let new_acc = match acc.take() {
+                    Some(mut acc) => acc.add(cs, &mut selected),
+                    None => selected,
+                };
+                acc = Some(new_acc);
+            }
+        }
+        let mut acc = acc.unwrap();
+
+        // we have computed (k_i + 1) * P_i for even k_i
+        for (point, bits) in points.iter_mut().zip(scalars_bits.iter()) {
+            let corrected = acc.sub(cs, point);
+            acc = Self::conditionally_select(cs, bits[0], &acc, &corrected);
+        }
+
+        acc
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::gates::testing_cs::create_test_cs;
+    use crate::cs::gates::*;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::non_native_field::implementations::*;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use pairing::bn256::{Fq, Fr, G1Affine};
+    use pairing::ff::{Field, PrimeField};
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+    type NN = NonNativeFieldOverU16<F, Fq, 17>;
+    type NNS = NonNativeFieldOverU16<F, Fr, 17>;
+    type Point = SWProjectivePoint<F, G1Affine, NN>;
+
+    fn assert_point_value<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        point: &mut Point,
+        expected: G1Affine,
+    ) {
+        let ((x, y), is_infinity) = point.convert_to_affine_or_default(cs, G1Affine::one());
+        let is_infinity = is_infinity.witness_hook(&*cs)().unwrap();
+        assert_eq!(is_infinity, expected.is_zero());
+        if is_infinity == false {
+            let x = x.witness_hook(&*cs)().unwrap().get();
+            let y = y.witness_hook(&*cs)().unwrap().get();
+            assert_eq!((x, y), expected.into_xy_unchecked());
+        }
+    }
+
+    fn allocate_point<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: G1Affine,
+        params: &Arc<NonNativeFieldOverU16Params<Fq, 17>>,
+    ) -> Point {
+        let (x, y) = value.into_xy_unchecked();
+        let x = NN::allocate_checked(cs, x, params);
+        let y = NN::allocate_checked(cs, y, params);
+        Point::from_xy_unchecked(cs, x, y)
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 22, 1, 10, 1 << 26);
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<Fr, 17>::create());
+
+        let p_value = G1Affine::one()
+            .mul(Fr::from_str("7").unwrap().into_repr())
+            .into_affine();
+        let q_value = G1Affine::one()
+            .mul(Fr::from_str("11").unwrap().into_repr())
+            .into_affine();
+        let k_value = Fr::from_str(
+            "10944121435919637611123202872628637544274182200208017171849102093287904247808",
+        )
+        .unwrap();
+        let l_value = Fr::from_str("1234567890123456789012345678901234567891").unwrap();
+
+        // variable base
+        for scalar_value in [k_value, l_value] {
+            let mut point = allocate_point(cs, p_value, &base_params);
+            let mut scalar = NNS::allocate_checked(cs, scalar_value, &scalar_params);
+            let mut result = point.mul(cs, &mut scalar, 4);
+            let expected = p_value.mul(scalar_value.into_repr()).into_affine();
+            assert_point_value(cs, &mut result, expected);
+        }
+
+        // fixed base
+        for scalar_value in [k_value, l_value] {
+            let mut scalar = NNS::allocate_checked(cs, scalar_value, &scalar_params);
+            let mut result = Point::mul_by_generator(cs, &mut scalar, &base_params, 4);
+            let expected = G1Affine::one().mul(scalar_value.into_repr()).into_affine();
+            assert_point_value(cs, &mut result, expected);
+        }
+
+        // multi scalar multiplication
+        let mut points = [
+            allocate_point(cs, p_value, &base_params),
+            allocate_point(cs, q_value, &base_params),
+        ];
+        let mut scalars = [
+            NNS::allocate_checked(cs, k_value, &scalar_params),
+            NNS::allocate_checked(cs, l_value, &scalar_params),
+        ];
+        let mut result = Point::multi_scalar_mul(cs, &mut points, &mut scalars, 4);
+        let mut expected = p_value.mul(k_value.into_repr());
+        expected.add_assign(&q_value.mul(l_value.into_repr()));
+        assert_point_value(cs, &mut result, expected.into_affine());
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_group_law_edge_cases() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 20, 1, 10, 1 << 24);
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let p_value = G1Affine::one()
+            .mul(Fr::from_str("7").unwrap().into_repr())
+            .into_affine();
+        let mut doubled_value = p_value.into_projective();
+        doubled_value.double();
+        let doubled_value = doubled_value.into_affine();
+        let mut negated_value = p_value;
+        negated_value.negate();
+
+        // P + P is the same as doubling, both for projective and mixed addition
+        let mut p = allocate_point(cs, p_value, &base_params);
+        let mut other = allocate_point(cs, p_value, &base_params);
+        let mut result = p.add(cs, &mut other);
+        assert_point_value(cs, &mut result, doubled_value);
+        let mut result = p.double(cs);
+        assert_point_value(cs, &mut result, doubled_value);
+        let mut other_xy = (other.x.clone(), other.y.clone());
+        let mut result = p.add_mixed(cs, &mut other_xy);
+        assert_point_value(cs, &mut result, doubled_value);
+
+        // P + (-P) and P - P are the point at infinity
+        let mut negated = p.negated(cs);
+        let mut result = p.add(cs, &mut negated);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+        let mut result = p.sub(cs, &mut other);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+        let mut result = p.sub_mixed(cs, &mut other_xy);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+        assert_point_value(cs, &mut negated, negated_value);
+
+        // point at infinity is the identity
+        let mut zero = Point::zero(cs, &base_params);
+        let mut result = zero.add(cs, &mut p);
+        assert_point_value(cs, &mut result, p_value);
+        let mut result = p.add(cs, &mut zero);
+        assert_point_value(cs, &mut result, p_value);
+        let mut result = zero.add_mixed(cs, &mut other_xy);
+        assert_point_value(cs, &mut result, p_value);
+        let mut other_zero = Point::zero(cs, &base_params);
+        let mut result = zero.add(cs, &mut other_zero);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+        let mut result = zero.double(cs);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+        let mut result = zero.negated(cs);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_scalar_mul_edge_cases() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 22, 1, 10, 1 << 26);
+        let cs = &mut owned_cs;
+
+        let base_params = Arc::new(NonNativeFieldOverU16Params::<Fq, 17>::create());
+        let scalar_params = Arc::new(NonNativeFieldOverU16Params::<Fr, 17>::create());
+
+        let p_value = G1Affine::one()
+            .mul(Fr::from_str("7").unwrap().into_repr())
+            .into_affine();
+        let mut minus_one = Fr::one();
+        minus_one.negate();
+        let scalars = [
+            Fr::zero(),
+            Fr::one(),
+            Fr::from_str("2").unwrap(),
+            Fr::from_str("15").unwrap(),
+            minus_one,
+        ];
+
+        // every result is compared with native multiplication, including the point at infinity
+        for scalar_value in scalars {
+            let expected = p_value.mul(scalar_value.into_repr()).into_affine();
+            let mut point = allocate_point(cs, p_value, &base_params);
+            let mut scalar = NNS::allocate_checked(cs, scalar_value, &scalar_params);
+            let mut result = point.mul(cs, &mut scalar, 4);
+            assert_point_value(cs, &mut result, expected);
+
+            let expected = G1Affine::one().mul(scalar_value.into_repr()).into_affine();
+            let mut scalar = NNS::allocate_checked(cs, scalar_value, &scalar_params);
+            let mut result = Point::mul_by_generator(cs, &mut scalar, &base_params, 4);
+            assert_point_value(cs, &mut result, expected);
+        }
+
+        // zero scalar in MSM, and two terms that cancel each other
+        let mut points = [
+            allocate_point(cs, p_value, &base_params),
+            allocate_point(cs, p_value, &base_params),
+            allocate_point(cs, G1Affine::one(), &base_params),
+        ];
+        let mut scalars = [
+            NNS::allocate_checked(cs, Fr::one(), &scalar_params),
+            NNS::allocate_checked(cs, minus_one, &scalar_params),
+            NNS::allocate_checked(cs, Fr::zero(), &scalar_params),
+        ];
+        let mut result = Point::multi_scalar_mul(cs, &mut points, &mut scalars, 4);
+        assert_point_value(cs, &mut result, G1Affine::zero());
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
@@ -0,0 +1,243 @@
+use super::*;
+
+use crate::gadgets::non_native_field::implementations::utils::u1024_to_u16_words;
+use crypto_bigint::U1024;
+use pairing::GenericCurveProjective;
+
+// GLV method: for curves with an efficient endomorphism (x, y) -> (beta * x, y), that acts as
+// multiplication by `lambda`, `k * P = k1 * P + k2 * (lambda * P)` where `k = k1 + k2 * lambda mod n`
+// and `k1`, `k2` are about half of the bit length of the group order. The decomposition is found
+// using the short basis (a1, b1), (a2, b2) of the lattice {(a, b): a + b * lambda = 0 mod n}, see
+// "Guide to Elliptic Curve Cryptography", algorithm 3.74
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct GlvParameters<C: GenericCurveAffine>
+where
+    C::Base: PrimeField,
+{
+    pub beta: C::Base,
+    pub lambda: C::Scalar,
+    // basis vectors are (a1, -minus_b1) and (a2, b2) with all the numbers below being positive
+    pub a1: U256,
+    pub minus_b1: U256,
+    pub a2: U256,
+    pub b2: U256,
+    // upper bound on the bit length of |k1| and |k2|
+    pub decomposition_bits: usize,
+}
+
+impl<C: GenericCurveAffine> GlvParameters<C>
+where
+    C::Base: PrimeField,
+{
+    pub fn from_decimal_strings(
+        beta: &str,
+        lambda: &str,
+        a1: &str,
+        minus_b1: &str,
+        a2: &str,
+        b2: &str,
+        decomposition_bits: usize,
+    ) -> Self {
+        let beta = C::Base::from_str(beta).unwrap();
+        let lambda = C::Scalar::from_str(lambda).unwrap();
+
+        // check that the endomorphism indeed acts as multiplication by lambda
+        let (x, y) = C::one().into_xy_unchecked();
+        let mut endomorphism_x = x;
+        endomorphism_x.mul_assign(&beta);
+        let expected = C::from_xy_unchecked(endomorphism_x, y);
+        assert!(C::one().mul(lambda.into_repr()).into_affine() == expected);
+
+        Self {
+            beta,
+            lambda,
+            a1: U256::from_dec_str(a1).unwrap(),
+            minus_b1: U256::from_dec_str(minus_b1).unwrap(),
+            a2: U256::from_dec_str(a2).unwrap(),
+            b2: U256::from_dec_str(b2).unwrap(),
+            decomposition_bits,
+        }
+    }
+
+    /// Parameters for secp256k1, with constants from libsecp256k1. `C` must be secp256k1 curve,
+    /// e.g. `crate::gadgets::curves::secp256k1::PointAffine`
+    pub fn secp256k1() -> Self {
+        Self::from_decimal_strings(
+            "55594575648329892869085402983802832744385952214688224221778511981742606582254",
+            "37718080363155996902926221483475020450927657555482586988616620542887997980018",
+            "64502973549206556628585045361533709077",
+            "303414439467246543595250775667605759171",
+            "367917413016453100223835821029139468248",
+            "64502973549206556628585045361533709077",
+            129,
+        )
+    }
+
+    /// Parameters for BN254 G1. `C` must be BN254 G1 curve
+    pub fn bn254() -> Self {
+        Self::from_decimal_strings(
+            "2203960485148121921418603742825762020974279258880205651966",
+            "4407920970296243842393367215006156084916469457145843978461",
+            "9931322734385697763",
+            "147946756881789319000765030803803410728",
+            "147946756881789319010696353538189108491",
+            "9931322734385697763",
+            128,
+        )
+    }
+
+    /// Decomposes `k` into `k1 + k2 * lambda`. Returns little-endian bits of |k1| and |k2|
+    /// (`decomposition_bits` of each) and flags of those being negative
+    pub fn decompose_scalar<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
+        &self,
+        cs: &mut CS,
+        scalar: &mut NonNativeFieldOverU16<F, C::Scalar, N>,
+    ) -> [(Vec<Boolean<F>>, Boolean<F>); 2]
+    where
+        [(); N + 1]:,
+    {
+        scalar.normalize(cs);
+        let params = scalar.params.clone();
+
+        let mut k1_abs = NonNativeFieldOverU16::allocate_checked_without_value(cs, &params);
+        let mut k2_abs = NonNativeFieldOverU16::allocate_checked_without_value(cs, &params);
+        let k1_is_negative = Boolean::allocate_without_value(cs);
+        let k2_is_negative = Boolean::allocate_without_value(cs);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+            let modulus_limbs = params.modulus_limbs;
+            let modulus = params.modulus_u1024;
+            let half_modulus = modulus.shr_vartime(1);
+            let coeffs = [self.a1, self.minus_b1, self.a2, self.b2].map(u256_to_u1024);
+            let decomposition_bits = self.decomposition_bits;
+
+            let value_fn = move |inputs: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let k = u16_field_words_to_u1024(inputs);
+                let [a1, minus_b1, a2, b2] = coeffs;
+
+                // c1 = round(b2 * k / n), c2 = round(-b1 * k / n)
+                let (c1, _) = b2
+                    .wrapping_mul(&k)
+                    .wrapping_add(&half_modulus)
+                    .div_rem(&modulus);
+                let (c2, _) = minus_b1
+                    .wrapping_mul(&k)
+                    .wrapping_add(&half_modulus)
+                    .div_rem(&modulus);
+
+                // k1 = k - c1 * a1 - c2 * a2, k2 = -c1 * b1 - c2 * b2
+                let subtrahend = c1.wrapping_mul(&a1).wrapping_add(&c2.wrapping_mul(&a2));
+                let (k1_abs, k1_is_negative) = signed_difference(&k, &subtrahend);
+                let (k2_abs, k2_is_negative) =
+                    signed_difference(&c1.wrapping_mul(&minus_b1), &c2.wrapping_mul(&b2));
+                assert!(k1_abs.bits() <= decomposition_bits);
+                assert!(k2_abs.bits() <= decomposition_bits);
+
+                for value in [k1_abs, k2_abs] {
+                    let limbs = u1024_to_u16_words::<N>(&value);
+                    dst.extend(
+                        limbs
+                            .into_iter()
+                            .take(modulus_limbs)
+                            .map(|el| F::from_u64_unchecked(el as u64)),
+                    );
+                }
+                dst.push(F::from_u64_unchecked(k1_is_negative as u64));
+                dst.push(F::from_u64_unchecked(k2_is_negative as u64));
+            };
+
+            let dependencies: Vec<_> = Place::from_variables(scalar.limbs)
+                .into_iter()
+                .take(modulus_limbs)
+                .collect();
+            let mut outputs = Vec::with_capacity(2 * modulus_limbs + 2);
+            for value in [&k1_abs, &k2_abs] {
+                outputs.extend(
+                    Place::from_variables(value.limbs)
+                        .into_iter()
+                        .take(modulus_limbs),
+                );
+            }
+            outputs.push(Place::from_variable(k1_is_negative.get_variable()));
+            outputs.push(Place::from_variable(k2_is_negative.get_variable()));
+
+            cs.set_values_with_dependencies_vararg(&dependencies, &outputs, value_fn);
+        }
+
+        // k == +-k1 +- k2 * lambda
+        let k1_negated = k1_abs.negated(cs);
+        let mut k1 =
+            NonNativeFieldOverU16::conditionally_select(cs, k1_is_negative, &k1_negated, &k1_abs);
+        let k2_negated = k2_abs.negated(cs);
+        let mut k2 =
+            NonNativeFieldOverU16::conditionally_select(cs, k2_is_negative, &k2_negated, &k2_abs);
+        let mut lambda = NonNativeFieldOverU16::allocated_constant(cs, self.lambda, &params);
+        let mut k2_mul_lambda = k2.mul(cs, &mut lambda);
+        let mut recomposed = k1.add(cs, &mut k2_mul_lambda);
+        let is_valid = NonNativeFieldOverU16::equals(cs, &mut recomposed, scalar);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &is_valid, &boolean_true);
+
+        // and both parts are short
+        let boolean_false = Boolean::allocated_constant(cs, false);
+        let mut result = [(vec![], k1_is_negative), (vec![], k2_is_negative)];
+        for ((bits, _), value) in result.iter_mut().zip([&mut k1_abs, &mut k2_abs]) {
+            let mut all_bits = value.decompose_into_le_bits(cs);
+            for bit in all_bits[self.decomposition_bits..].iter() {
+                Boolean::enforce_equal(cs, bit, &boolean_false);
+            }
+            all_bits.truncate(self.decomposition_bits);
+            *bits = all_bits;
+        }
+
+        result
+    }
+}
+
+fn u256_to_u1024(value: U256) -> U1024 {
+    let mut result = U1024::ZERO;
+    result.as_words_mut()[..4].copy_from_slice(&value.0);
+
+    result
+}
+
+fn signed_difference(a: &U1024, b: &U1024) -> (U1024, bool) {
+    if a >= b {
+        (a.wrapping_sub(b), false)
+    } else {
+        (b.wrapping_sub(a), true)
+    }
+}
+
+/// Computes `k * P` using the GLV decomposition of the scalar, so the number of doublings is halved
+pub fn glv_mul<F: SmallField, CS: ConstraintSystem<F>, C: GenericCurveAffine, const N: usize>(
+    cs: &mut CS,
+    point: &mut SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, N>>,
+    scalar: &mut NonNativeFieldOverU16<F, C::Scalar, N>,
+    glv_params: &GlvParameters<C>,
+    window_width: usize,
+) -> SWProjectivePoint<F, C, NonNativeFieldOverU16<F, C::Base, N>>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let [(k1_bits, k1_is_negative), (k2_bits, k2_is_negative)] =
+        glv_params.decompose_scalar(cs, scalar);
+
+    let params = point.x.params.clone();
+    let mut beta = NonNativeFieldOverU16::allocated_constant(cs, glv_params.beta, &params);
+    let mut endomorphism = point.clone();
+    endomorphism.x = point.x.mul(cs, &mut beta);
+
+    // multiplication by negative scalar is multiplication of the negated point
+    let mut points = [point.clone(), endomorphism];
+    for (point, is_negative) in points.iter_mut().zip([k1_is_negative, k2_is_negative]) {
+        let y_negated = point.y.negated(cs);
+        point.y =
+            NonNativeFieldOverU16::conditionally_select(cs, is_negative, &y_negated, &point.y);
+    }
+
+    SWProjectivePoint::multi_scalar_mul_by_bits(cs, &mut points, &[k1_bits, k2_bits], window_width)
+}
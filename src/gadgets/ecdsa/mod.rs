@@ -0,0 +1,757 @@
+use super::*;
+
+use crate::config::*;
+use crate::cs::gates::ConstantAllocatableCS;
+use crate::cs::traits::cs::{ConstraintSystem, DstBuffer};
+use crate::cs::Variable;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::curves::sw_projective::SWProjectivePoint;
+use crate::gadgets::keccak256::keccak256;
+use crate::gadgets::non_native_field::implementations::utils::{
+    fe_to_u16_words, u1024_to_fe, u16_field_words_to_u1024,
+};
+use crate::gadgets::non_native_field::implementations::{
+    range_check_u16, NonNativeFieldOverU16, NonNativeFieldOverU16Params, OverflowTracker,
+    RepresentationForm,
+};
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::u160::UInt160;
+use crate::gadgets::u256::UInt256;
+use crate::gadgets::u32::UInt32;
+use crate::gadgets::u8::UInt8;
+use ethereum_types::U256;
+use pairing::ff::{Field, PrimeField, PrimeFieldRepr, SqrtField};
+use pairing::GenericCurveAffine;
+use std::sync::Arc;
+
+pub mod glv;
+
+use self::glv::{glv_mul, GlvParameters};
+
+// Signatures are verified over any short Weierstrass curve with an efficient endomorphism, so the
+// curve itself (e.g. secp256k1) is a type parameter and only it's GLV constants are provided here.
+// Both fields are represented with 16-bit limbs, and all the values come in as `UInt256`, so those
+// are expected to be 256-bit wide at most. One more limb than the modulus needs is required, as
+// otherwise there is no room for lazy additions in curve arithmetic (e.g. `N = 17` for secp256k1)
+
+pub const WINDOW_WIDTH: usize = 4;
+
+type BaseField<F, C, const N: usize> = NonNativeFieldOverU16<F, <C as GenericCurveAffine>::Base, N>;
+type ScalarField<F, C, const N: usize> =
+    NonNativeFieldOverU16<F, <C as GenericCurveAffine>::Scalar, N>;
+type Point<F, C, const N: usize> = SWProjectivePoint<F, C, BaseField<F, C, N>>;
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct EcdsaParams<C: GenericCurveAffine, const N: usize>
+where
+    C::Base: PrimeField,
+{
+    pub base_field_params: Arc<NonNativeFieldOverU16Params<C::Base, N>>,
+    pub scalar_field_params: Arc<NonNativeFieldOverU16Params<C::Scalar, N>>,
+    pub glv_params: GlvParameters<C>,
+}
+
+impl<C: GenericCurveAffine, const N: usize> EcdsaParams<C, N>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    pub fn new(glv_params: GlvParameters<C>) -> Self {
+        assert!(N >= 17);
+        let base_field_params = NonNativeFieldOverU16Params::<C::Base, N>::create();
+        let scalar_field_params = NonNativeFieldOverU16Params::<C::Scalar, N>::create();
+        assert_eq!(base_field_params.modulus_limbs, 16);
+        assert_eq!(scalar_field_params.modulus_limbs, 16);
+
+        // we use that n < q < 2n, so `r` is always a valid x-coordinate
+        // and affine x-coordinates are reduced modulo `n` by a single subtraction
+        let q = modulus_as_u256::<C::Base>();
+        let n = modulus_as_u256::<C::Scalar>();
+        assert!(n < q);
+        assert!(q - n < n);
+
+        Self {
+            base_field_params: Arc::new(base_field_params),
+            scalar_field_params: Arc::new(scalar_field_params),
+            glv_params,
+        }
+    }
+}
+
+pub(crate) fn modulus_as_u256<T: PrimeField>() -> U256 {
+    let mut result = U256::zero();
+    for (dst, src) in result.0.iter_mut().zip(T::char().as_ref().iter()) {
+        *dst = *src;
+    }
+
+    result
+}
+
+pub(crate) fn is_reduced<F: SmallField, CS: ConstraintSystem<F>, T: PrimeField>(
+    cs: &mut CS,
+    value: &UInt256<F>,
+) -> Boolean<F> {
+    let modulus = UInt256::allocated_constant(cs, modulus_as_u256::<T>());
+
+    UInt256::less_than(cs, value, &modulus)
+}
+
+// `0 < value < modulus` of `T`
+fn is_nonzero_and_reduced<F: SmallField, CS: ConstraintSystem<F>, T: PrimeField>(
+    cs: &mut CS,
+    value: &UInt256<F>,
+) -> Boolean<F> {
+    let is_reduced = is_reduced::<_, _, T>(cs, value);
+    let is_zero = value.is_zero(cs);
+    let is_nonzero = is_zero.negated(cs);
+
+    is_reduced.and(cs, is_nonzero)
+}
+
+fn split_into_u16_limbs<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    value: UInt32<F>,
+) -> [Variable; 2] {
+    let [low, high] = Num::allocate_multiple_from_closure_and_dependencies(
+        cs,
+        |inputs: &[F]| {
+            let value = inputs[0].as_u64_reduced();
+            [
+                F::from_u64_unchecked(value & (u16::MAX as u64)),
+                F::from_u64_unchecked(value >> 16),
+            ]
+        },
+        &[value.get_variable().into()],
+    );
+    range_check_u16(cs, low.variable);
+    range_check_u16(cs, high.variable);
+    Num::enforce_zero_for_linear_combination(
+        cs,
+        &[
+            (low.variable, F::ONE),
+            (high.variable, F::from_u64_unchecked(1u64 << 16)),
+            (value.get_variable(), F::MINUS_ONE),
+        ],
+    );
+
+    [low.variable, high.variable]
+}
+
+// The result is not reduced, but it's limbs are range checked, so it's good for any arithmetic
+pub(crate) fn uint256_into_field_element<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: PrimeField,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &UInt256<F>,
+    params: &Arc<NonNativeFieldOverU16Params<T, N>>,
+) -> NonNativeFieldOverU16<F, T, N> {
+    let zero = cs.allocate_constant(F::ZERO);
+    let mut limbs = [zero; N];
+    for (dst, src) in limbs.array_chunks_mut::<2>().zip(value.inner.iter()) {
+        *dst = split_into_u16_limbs(cs, *src);
+    }
+
+    NonNativeFieldOverU16 {
+        limbs,
+        non_zero_limbs: params.modulus_limbs,
+        tracker: OverflowTracker {
+            max_moduluses: params.max_mods_in_allocation,
+        },
+        form: RepresentationForm::Normalized,
+        params: params.clone(),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+fn field_element_into_uint256<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: PrimeField,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &mut NonNativeFieldOverU16<F, T, N>,
+) -> UInt256<F>
+where
+    [(); N + 1]:,
+{
+    // normalized limbs are range checked, so pairs of them are valid u32 words
+    value.normalize(cs);
+    let shift = F::from_u64_unchecked(1u64 << 16);
+    let inner = std::array::from_fn(|i| {
+        let word = Num::linear_combination(
+            cs,
+            &[
+                (value.limbs[2 * i], F::ONE),
+                (value.limbs[2 * i + 1], shift),
+            ],
+        );
+        unsafe { UInt32::from_variable_unchecked(word.variable) }
+    });
+
+    UInt256 { inner }
+}
+
+// x^3 + a * x + b
+fn curve_equation_rhs<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    const N: usize,
+>(
+    cs: &mut CS,
+    x: &mut BaseField<F, C, N>,
+) -> BaseField<F, C, N>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let params = x.params.clone();
+    let mut a = BaseField::<F, C, N>::allocated_constant(cs, C::a_coeff(), &params);
+    let mut b = BaseField::<F, C, N>::allocated_constant(cs, C::b_coeff(), &params);
+    let mut x_squared = x.square(cs);
+    let mut tmp = x_squared.add(cs, &mut a);
+    let mut tmp = tmp.mul(cs, x);
+
+    tmp.add(cs, &mut b)
+}
+
+// Complete addition formulas only work for points on the curve, so invalid points are
+// replaced by the generator, and it's up to the caller to discard the result
+fn point_or_generator<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    const N: usize,
+>(
+    cs: &mut CS,
+    is_valid: Boolean<F>,
+    x: &BaseField<F, C, N>,
+    y: &BaseField<F, C, N>,
+) -> Point<F, C, N>
+where
+    C::Base: PrimeField,
+{
+    let params = x.params.clone();
+    let (generator_x, generator_y) = C::one().into_xy_unchecked();
+    let generator_x = BaseField::<F, C, N>::allocated_constant(cs, generator_x, &params);
+    let generator_y = BaseField::<F, C, N>::allocated_constant(cs, generator_y, &params);
+    let x = BaseField::<F, C, N>::conditionally_select(cs, is_valid, x, &generator_x);
+    let y = BaseField::<F, C, N>::conditionally_select(cs, is_valid, y, &generator_y);
+
+    Point::<F, C, N>::from_xy_unchecked(cs, x, y)
+}
+
+fn find_non_residue<T: PrimeField + SqrtField>() -> T {
+    let mut candidate = T::one();
+    loop {
+        candidate.add_assign(&T::one());
+        if candidate.sqrt().is_none() {
+            return candidate;
+        }
+    }
+}
+
+// Returns `y` such that `y^2 = value` and `y` has the requested parity, and a flag if it exists.
+// If `value` is not a square, we instead prove that `y^2 = value * non_residue`, so the prover
+// can not claim that there is no square root
+fn square_root_with_parity<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    T: PrimeField + SqrtField,
+    const N: usize,
+>(
+    cs: &mut CS,
+    value: &mut NonNativeFieldOverU16<F, T, N>,
+    is_odd: Boolean<F>,
+) -> (NonNativeFieldOverU16<F, T, N>, Boolean<F>)
+where
+    [(); N + 1]:,
+{
+    value.normalize(cs);
+    let params = value.params.clone();
+    let non_residue = find_non_residue::<T>();
+
+    let mut root = NonNativeFieldOverU16::allocate_checked_without_value(cs, &params);
+    let is_square = Boolean::allocate_without_value(cs);
+
+    if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+        let modulus_limbs = params.modulus_limbs;
+        let value_fn = move |inputs: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+            let value: T = u1024_to_fe(&u16_field_words_to_u1024(&inputs[..modulus_limbs]));
+            let is_odd = inputs[modulus_limbs].as_u64_reduced() == 1;
+
+            let (mut root, is_square) = match value.sqrt() {
+                Some(root) => (root, true),
+                None => {
+                    let mut tmp = value;
+                    tmp.mul_assign(&non_residue);
+                    (tmp.sqrt().expect("must be a square"), false)
+                }
+            };
+            if root.into_repr().is_odd() != is_odd {
+                root.negate();
+            }
+
+            let limbs = fe_to_u16_words::<_, N>(&root);
+            dst.extend(
+                limbs
+                    .into_iter()
+                    .take(modulus_limbs)
+                    .map(|el| F::from_u64_unchecked(el as u64)),
+            );
+            dst.push(F::from_u64_unchecked(is_square as u64));
+        };
+
+        let mut dependencies = Vec::with_capacity(modulus_limbs + 1);
+        dependencies.extend(
+            Place::from_variables(value.limbs)
+                .into_iter()
+                .take(modulus_limbs),
+        );
+        dependencies.push(Place::from_variable(is_odd.get_variable()));
+        let mut outputs = Vec::with_capacity(modulus_limbs + 1);
+        outputs.extend(
+            Place::from_variables(root.limbs)
+                .into_iter()
+                .take(modulus_limbs),
+        );
+        outputs.push(Place::from_variable(is_square.get_variable()));
+
+        cs.set_values_with_dependencies_vararg(&dependencies, &outputs, value_fn);
+    }
+
+    let mut non_residue = NonNativeFieldOverU16::allocated_constant(cs, non_residue, &params);
+    let value_mul_non_residue = value.mul(cs, &mut non_residue);
+    let mut expected =
+        NonNativeFieldOverU16::conditionally_select(cs, is_square, value, &value_mul_non_residue);
+    let mut root_squared = root.square(cs);
+    let root_is_valid = NonNativeFieldOverU16::equals(cs, &mut root_squared, &mut expected);
+    let boolean_true = Boolean::allocated_constant(cs, true);
+    Boolean::enforce_equal(cs, &root_is_valid, &boolean_true);
+
+    // the only case when we can't choose the parity is zero root
+    let root_bits = Num::from_variable(root.limbs[0]).spread_into_bits::<_, 16>(cs);
+    let parity_matches = Boolean::equals(cs, &root_bits[0], &is_odd);
+    let root_is_zero = root.is_zero(cs);
+    let root_is_nonzero = root_is_zero.negated(cs);
+    let should_enforce = is_square.and(cs, root_is_nonzero);
+    parity_matches.conditionally_enforce_true(cs, should_enforce);
+
+    let exists = is_square.and(cs, parity_matches);
+
+    (root, exists)
+}
+
+fn affine_x_mod_group_order<
+    F: SmallField,
+    CS: ConstraintSystem<F>,
+    C: GenericCurveAffine,
+    const N: usize,
+>(
+    cs: &mut CS,
+    x: &mut BaseField<F, C, N>,
+) -> UInt256<F>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let x = field_element_into_uint256(cs, x);
+    let modulus = UInt256::allocated_constant(cs, modulus_as_u256::<C::Scalar>());
+    let (x_minus_modulus, x_is_reduced) = x.overflowing_sub(cs, &modulus);
+
+    UInt256::conditionally_select(cs, x_is_reduced, &x, &x_minus_modulus)
+}
+
+/// Verifies the signature `(r, s)` of the message hash for the public key given by it's affine
+/// coordinates. The hash is taken modulo the group order, that is the same as the truncation
+/// of the standard for 256-bit groups. Malformed inputs (including public keys that are not on
+/// the curve) just make the signature invalid, so the circuit is satisfiable for any of them
+pub fn ecdsa_verify<F: SmallField, CS: ConstraintSystem<F>, C: GenericCurveAffine, const N: usize>(
+    cs: &mut CS,
+    public_key_x: &UInt256<F>,
+    public_key_y: &UInt256<F>,
+    r: &UInt256<F>,
+    s: &UInt256<F>,
+    message_hash: &UInt256<F>,
+    params: &EcdsaParams<C, N>,
+) -> Boolean<F>
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let base_params = &params.base_field_params;
+    let scalar_params = &params.scalar_field_params;
+
+    let r_is_valid = is_nonzero_and_reduced::<_, _, C::Scalar>(cs, r);
+    let s_is_valid = is_nonzero_and_reduced::<_, _, C::Scalar>(cs, s);
+
+    let x_is_valid = is_reduced::<_, _, C::Base>(cs, public_key_x);
+    let y_is_valid = is_reduced::<_, _, C::Base>(cs, public_key_y);
+    let mut x = uint256_into_field_element(cs, public_key_x, base_params);
+    let mut y = uint256_into_field_element(cs, public_key_y, base_params);
+    let mut rhs = curve_equation_rhs::<_, _, C, N>(cs, &mut x);
+    let mut y_squared = y.square(cs);
+    let is_on_curve = BaseField::<F, C, N>::equals(cs, &mut y_squared, &mut rhs);
+    let public_key_is_valid = Boolean::multi_and(cs, &[x_is_valid, y_is_valid, is_on_curve]);
+    let mut public_key = point_or_generator::<_, _, C, N>(cs, public_key_is_valid, &x, &y);
+
+    // u1 = z / s, u2 = r / s, and we invert 1 instead of invalid `s`
+    let mut r_scalar = uint256_into_field_element(cs, r, scalar_params);
+    let s_scalar = uint256_into_field_element(cs, s, scalar_params);
+    let mut z = uint256_into_field_element(cs, message_hash, scalar_params);
+    let one = ScalarField::<F, C, N>::allocated_constant(cs, C::Scalar::one(), scalar_params);
+    let mut s_scalar =
+        ScalarField::<F, C, N>::conditionally_select(cs, s_is_valid, &s_scalar, &one);
+    let mut s_inversed = s_scalar.inverse_unchecked(cs);
+    let mut u1 = z.mul(cs, &mut s_inversed);
+    let mut u2 = r_scalar.mul(cs, &mut s_inversed);
+
+    // R = u1 * G + u2 * Q
+    let mut generator_part =
+        Point::<F, C, N>::mul_by_generator(cs, &mut u1, base_params, WINDOW_WIDTH);
+    let mut public_key_part = glv_mul(
+        cs,
+        &mut public_key,
+        &mut u2,
+        &params.glv_params,
+        WINDOW_WIDTH,
+    );
+    let mut point = generator_part.add(cs, &mut public_key_part);
+    let ((mut x, _), is_infinity) = point.convert_to_affine_or_default(cs, C::one());
+    let is_finite = is_infinity.negated(cs);
+
+    let x = affine_x_mod_group_order::<_, _, C, N>(cs, &mut x);
+    let r_matches = UInt256::equals(cs, &x, r);
+
+    Boolean::multi_and(
+        cs,
+        &[
+            r_is_valid,
+            s_is_valid,
+            public_key_is_valid,
+            is_finite,
+            r_matches,
+        ],
+    )
+}
+
+/// Recovers the public key from the signature `(r, s)` of the message hash and returns it's
+/// Ethereum address, that is the last 20 bytes of keccak256 of big-endian coordinates of the key.
+/// Recovery id is the parity of `y` of the point `R`, and same as in Ethereum only ids 0 and 1
+/// are supported. For invalid inputs the returned flag is `false` and the address is zero
+pub fn ecrecover<F: SmallField, CS: ConstraintSystem<F>, C: GenericCurveAffine, const N: usize>(
+    cs: &mut CS,
+    recovery_id: &UInt8<F>,
+    r: &UInt256<F>,
+    s: &UInt256<F>,
+    message_hash: &UInt256<F>,
+    params: &EcdsaParams<C, N>,
+) -> (Boolean<F>, UInt160<F>)
+where
+    C::Base: PrimeField,
+    [(); N + 1]:,
+{
+    let base_params = &params.base_field_params;
+    let scalar_params = &params.scalar_field_params;
+
+    let recovery_id_bits =
+        Num::from_variable(recovery_id.get_variable()).spread_into_bits::<_, 8>(cs);
+    let y_is_odd = recovery_id_bits[0];
+    let upper_bits_are_zero: Vec<_> = recovery_id_bits[1..]
+        .iter()
+        .map(|el| el.negated(cs))
+        .collect();
+    let recovery_id_is_valid = Boolean::multi_and(cs, &upper_bits_are_zero);
+
+    let r_is_valid = is_nonzero_and_reduced::<_, _, C::Scalar>(cs, r);
+    let s_is_valid = is_nonzero_and_reduced::<_, _, C::Scalar>(cs, s);
+
+    // R = (r, y), and `r` is less than the base field modulus if it's valid
+    let mut x = uint256_into_field_element(cs, r, base_params);
+    let mut rhs = curve_equation_rhs::<_, _, C, N>(cs, &mut x);
+    let (y, y_exists) = square_root_with_parity(cs, &mut rhs, y_is_odd);
+    let mut point = point_or_generator::<_, _, C, N>(cs, y_exists, &x, &y);
+
+    // Q = (-z / r) * G + (s / r) * R, and we invert 1 instead of invalid `r`
+    let r_scalar = uint256_into_field_element(cs, r, scalar_params);
+    let mut s_scalar = uint256_into_field_element(cs, s, scalar_params);
+    let mut z = uint256_into_field_element(cs, message_hash, scalar_params);
+    let one = ScalarField::<F, C, N>::allocated_constant(cs, C::Scalar::one(), scalar_params);
+    let mut r_scalar =
+        ScalarField::<F, C, N>::conditionally_select(cs, r_is_valid, &r_scalar, &one);
+    let mut r_inversed = r_scalar.inverse_unchecked(cs);
+    let mut u1 = z.mul(cs, &mut r_inversed);
+    let mut u1 = u1.negated(cs);
+    let mut u2 = s_scalar.mul(cs, &mut r_inversed);
+
+    let mut generator_part =
+        Point::<F, C, N>::mul_by_generator(cs, &mut u1, base_params, WINDOW_WIDTH);
+    let mut point_part = glv_mul(cs, &mut point, &mut u2, &params.glv_params, WINDOW_WIDTH);
+    let mut public_key = generator_part.add(cs, &mut point_part);
+    let ((mut x, mut y), is_infinity) = public_key.convert_to_affine_or_default(cs, C::one());
+    let is_finite = is_infinity.negated(cs);
+
+    let x = field_element_into_uint256(cs, &mut x);
+    let y = field_element_into_uint256(cs, &mut y);
+    let mut preimage = Vec::with_capacity(64);
+    preimage.extend(x.to_be_bytes(cs));
+    preimage.extend(y.to_be_bytes(cs));
+    let digest = keccak256(cs, &preimage);
+
+    // address words are little-endian, while bytes of every word are big-endian
+    let inner = std::array::from_fn(|i| {
+        let end = digest.len() - 4 * i;
+        let bytes: [UInt8<F>; 4] = digest[(end - 4)..end].try_into().unwrap();
+        UInt32::from_be_bytes(cs, bytes)
+    });
+    let address = UInt160 { inner };
+
+    let is_valid = Boolean::multi_and(
+        cs,
+        &[
+            recovery_id_is_valid,
+            r_is_valid,
+            s_is_valid,
+            y_exists,
+            is_finite,
+        ],
+    );
+    let address = address.mask(cs, is_valid);
+
+    (is_valid, address)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cs::gates::testing_cs::create_test_cs;
+    use crate::cs::gates::*;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::gadgets::curves::secp256k1;
+    use crate::gadgets::tables::and8::{create_and8_table, And8Table};
+    use crate::gadgets::tables::byte_split::{create_byte_split_table, ByteSplitTable};
+    use crate::gadgets::tables::xor8::{create_xor8_table, Xor8Table};
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use ethereum_types::Address;
+    use pairing::bn256::{Fq, Fr, G1Affine};
+    use pairing::GenericCurveProjective;
+    use sha3::Digest;
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+
+    fn into_u256<T: PrimeField>(value: T) -> U256 {
+        let mut result = U256::zero();
+        result.0.copy_from_slice(value.into_repr().as_ref());
+
+        result
+    }
+
+    fn from_u256<T: PrimeField>(value: U256) -> T {
+        let mut repr = T::Repr::default();
+        repr.as_mut().copy_from_slice(&value.0);
+
+        T::from_repr(repr).unwrap()
+    }
+
+    fn be_bytes(value: U256) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        value.to_big_endian(&mut result);
+
+        result
+    }
+
+    fn address_of<C: GenericCurveAffine>(public_key: C) -> Address
+    where
+        C::Base: PrimeField,
+    {
+        let (x, y) = public_key.into_xy_unchecked();
+        let mut preimage = be_bytes(into_u256(x)).to_vec();
+        preimage.extend(be_bytes(into_u256(y)));
+
+        Address::from_slice(&sha3::Keccak256::digest(&preimage)[12..])
+    }
+
+    // signs the hash natively, returns `(recovery_id, r, s)`
+    fn sign<C: GenericCurveAffine>(
+        private_key: C::Scalar,
+        nonce: C::Scalar,
+        message_hash: U256,
+    ) -> (u8, U256, U256)
+    where
+        C::Base: PrimeField,
+    {
+        let r_point = C::one().mul(nonce.into_repr()).into_affine();
+        let (r_x, r_y) = r_point.into_xy_unchecked();
+        let group_order = modulus_as_u256::<C::Scalar>();
+        assert!(into_u256(r_x) < group_order);
+        let r_value = from_u256::<C::Scalar>(into_u256(r_x));
+        let recovery_id = r_y.into_repr().is_odd() as u8;
+
+        let z_value = from_u256::<C::Scalar>(message_hash % group_order);
+        let mut s_value = r_value;
+        s_value.mul_assign(&private_key);
+        s_value.add_assign(&z_value);
+        s_value.mul_assign(&nonce.inverse().unwrap());
+
+        (recovery_id, into_u256(r_value), into_u256(s_value))
+    }
+
+    // tables used by UInt256 and keccak, on top of the 16 bit range check
+    fn add_byte_tables<CS: ConstraintSystem<F>>(cs: &mut CS) {
+        let table = create_xor8_table();
+        cs.add_lookup_table::<Xor8Table, 3>(table);
+        let table = create_and8_table();
+        cs.add_lookup_table::<And8Table, 3>(table);
+        let table = create_byte_split_table::<F, 1>();
+        cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+        let table = create_byte_split_table::<F, 2>();
+        cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+        let table = create_byte_split_table::<F, 3>();
+        cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+        let table = create_byte_split_table::<F, 4>();
+        cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+    }
+
+    #[test]
+    fn test_ecdsa_verify_and_ecrecover() {
+        let private_key = Fr::from_str("123456789012345678901234567890").unwrap();
+        let public_key = G1Affine::one().mul(private_key.into_repr()).into_affine();
+        let nonce = Fr::from_str("98765432109876543210987654321").unwrap();
+        let message_hash = U256::from_big_endian(&sha3::Keccak256::digest(b"boojum"));
+        let (recovery_id, r_value, s_value) = sign::<G1Affine>(private_key, nonce, message_hash);
+        let (public_key_x, public_key_y) = public_key.into_xy_unchecked();
+        let expected_address = address_of(public_key);
+
+        let mut owned_cs =
+            create_test_cs::<F, (UIntXAddGate<32>, UIntXAddGate<16>)>(1 << 23, 3, 8, 1 << 27);
+        add_byte_tables(&mut owned_cs);
+        let cs = &mut owned_cs;
+
+        let params = EcdsaParams::<G1Affine, 17>::new(GlvParameters::bn254());
+
+        let public_key_x = UInt256::allocate(cs, into_u256::<Fq>(public_key_x));
+        let public_key_y = UInt256::allocate(cs, into_u256::<Fq>(public_key_y));
+        let r = UInt256::allocate(cs, r_value);
+        let s = UInt256::allocate(cs, s_value);
+        let hash = UInt256::allocate(cs, message_hash);
+        let other_hash = UInt256::allocate(cs, message_hash + U256::one());
+
+        let is_valid = ecdsa_verify(cs, &public_key_x, &public_key_y, &r, &s, &hash, &params);
+        assert!(is_valid.witness_hook(&*cs)().unwrap());
+
+        let is_valid = ecdsa_verify(
+            cs,
+            &public_key_x,
+            &public_key_y,
+            &r,
+            &s,
+            &other_hash,
+            &params,
+        );
+        assert!(is_valid.witness_hook(&*cs)().unwrap() == false);
+
+        let recovery_id = UInt8::allocate_checked(cs, recovery_id);
+        let (is_valid, address) = ecrecover(cs, &recovery_id, &r, &s, &hash, &params);
+        assert!(is_valid.witness_hook(&*cs)().unwrap());
+        assert_eq!(address.witness_hook(&*cs)().unwrap(), expected_address);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    fn check_secp256k1_ecrecover(
+        recovery_id: u8,
+        r: U256,
+        s: U256,
+        message_hash: U256,
+        expected_address: Option<Address>,
+    ) {
+        let mut owned_cs =
+            create_test_cs::<F, (UIntXAddGate<32>, UIntXAddGate<16>)>(1 << 22, 3, 8, 1 << 26);
+        add_byte_tables(&mut owned_cs);
+        let cs = &mut owned_cs;
+
+        let params = EcdsaParams::<secp256k1::PointAffine, 17>::new(GlvParameters::secp256k1());
+
+        let recovery_id = UInt8::allocate_checked(cs, recovery_id);
+        let r = UInt256::allocate(cs, r);
+        let s = UInt256::allocate(cs, s);
+        let hash = UInt256::allocate(cs, message_hash);
+        let (is_valid, address) = ecrecover(cs, &recovery_id, &r, &s, &hash, &params);
+
+        // invalid inputs give zero address
+        assert_eq!(
+            is_valid.witness_hook(&*cs)().unwrap(),
+            expected_address.is_some()
+        );
+        assert_eq!(
+            address.witness_hook(&*cs)().unwrap(),
+            expected_address.unwrap_or(Address::zero())
+        );
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_secp256k1_ecrecover() {
+        use secp256k1::{Fr, PointAffine};
+
+        // well-known address of the private key 1
+        let address_of_one =
+            Address::from_slice(&hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap());
+        assert_eq!(address_of(PointAffine::one()), address_of_one);
+
+        let private_key = Fr::from_str("123456789012345678901234567890").unwrap();
+        let public_key = PointAffine::one()
+            .mul(private_key.into_repr())
+            .into_affine();
+        let expected_address = address_of(public_key);
+        let nonce = Fr::from_str("98765432109876543210987654321").unwrap();
+        let message_hash = U256::from_big_endian(&sha3::Keccak256::digest(b"boojum"));
+        let (recovery_id, r, s) = sign::<PointAffine>(private_key, nonce, message_hash);
+        let group_order = modulus_as_u256::<Fr>();
+
+        // valid signature
+        check_secp256k1_ecrecover(recovery_id, r, s, message_hash, Some(expected_address));
+
+        // high `s` together with the other recovery id gives the same key, and same as
+        // the precompile, that doesn't enforce EIP-2, such signature is accepted
+        let high_s = group_order - s;
+        check_secp256k1_ecrecover(
+            1 - recovery_id,
+            r,
+            high_s,
+            message_hash,
+            Some(expected_address),
+        );
+
+        // r = 0
+        check_secp256k1_ecrecover(recovery_id, U256::zero(), s, message_hash, None);
+
+        // only 0 and 1 are valid recovery ids, in particular Ethereum's 27 is not
+        check_secp256k1_ecrecover(2, r, s, message_hash, None);
+        check_secp256k1_ecrecover(27, r, s, message_hash, None);
+
+        // `n + 2` is an x-coordinate of some point, but `r` must be reduced modulo the group order
+        let overflowing_r = group_order + U256::from(2u64);
+        assert!(overflowing_r < modulus_as_u256::<secp256k1::Fq>());
+        check_secp256k1_ecrecover(recovery_id, overflowing_r, s, message_hash, None);
+    }
+}
@@ -2,6 +2,7 @@ use crate::cs::gates::{ReductionByPowersGate, ReductionGate};
 use crate::cs::Variable;
 use crate::{cs::traits::cs::ConstraintSystem, field::SmallField};
 
+#[track_caller]
 pub fn decompose_into_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
     cs: &mut CS,
     limb_size: F,
@@ -16,6 +17,7 @@ pub fn decompose_into_limbs<F: SmallField, CS: ConstraintSystem<F>, const N: usi
     }
 }
 
+#[track_caller]
 pub fn decompose_into_limbs_limited<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
     cs: &mut CS,
     limb_size: F,
@@ -34,6 +36,7 @@ pub fn decompose_into_limbs_limited<F: SmallField, CS: ConstraintSystem<F>, cons
     }
 }
 
+#[track_caller]
 pub fn reduce_terms<F: SmallField, CS: ConstraintSystem<F>, const N: usize>(
     cs: &mut CS,
     reduction_constant: F,
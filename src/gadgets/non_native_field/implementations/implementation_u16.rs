@@ -1014,6 +1014,25 @@ where
 
         Boolean::multi_and(cs, &equalities)
     }
+
+    #[must_use]
+    pub fn decompose_into_le_bits<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+    ) -> Vec<Boolean<F>> {
+        // normalized limbs are range checked and the value is < modulus, so
+        // bits above the modulus bit length are zeroes and can be dropped
+        self.normalize(cs);
+
+        let mut bits = Vec::with_capacity(self.params.modulus_limbs * 16);
+        for limb in self.limbs.iter().take(self.params.modulus_limbs) {
+            let limb_bits = Num::from_variable(*limb).spread_into_bits::<_, 16>(cs);
+            bits.extend(limb_bits);
+        }
+        bits.truncate(self.params.modulus_bits as usize);
+
+        bits
+    }
 }
 
 impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> CSAllocatable<F>
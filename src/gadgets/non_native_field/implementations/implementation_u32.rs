@@ -0,0 +1,1226 @@
+use crypto_bigint::CheckedMul;
+
+use crate::cs::gates::ConstantAllocatableCS;
+use crate::cs::traits::cs::DstBuffer;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::gadgets::traits::castable::WitnessCastable;
+use crate::gadgets::traits::selectable::Selectable;
+use crate::gadgets::traits::witnessable::{CSWitnessable, WitnessHookable};
+
+use super::utils::*;
+use super::*;
+
+// Lazy additions and negations can grow limbs up to this width, and after that operands are normalized first.
+// It leaves enough room to multiply such limbs by normalized ones
+const MAX_LAZY_LIMB_BITS: u32 = 40;
+
+// Shape of the relation a * b = q * P + r that is enforced in multiplication
+#[derive(Clone, Copy, Debug)]
+struct MultiplicationLayout {
+    num_q_limbs: usize,
+    num_columns: usize,
+    carry_bits: usize,
+    residue_carry_bits: usize,
+}
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> NonNativeFieldOverU32<F, T, N> {
+    #[must_use]
+    pub fn allocated_constant<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        value: T,
+        params: &Arc<NonNativeFieldOverU32Params<T, N>>,
+    ) -> Self {
+        let words = fe_to_u32_words::<_, N>(&value);
+
+        let limbs = words.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64)));
+        let residues =
+            words.map(|el| cs.allocate_constant(F::from_u64_unchecked(el as u64 & 0xffff)));
+        Self {
+            limbs,
+            residues,
+            max_limb_value: u32::MAX as u64,
+            max_residue_value: u16::MAX as u64,
+            // constant is always canonical
+            tracker: OverflowTracker { max_moduluses: 1 },
+            form: RepresentationForm::Normalized,
+            params: params.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn allocate_checked_without_value<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        params: &Arc<NonNativeFieldOverU32Params<T, N>>,
+    ) -> Self {
+        let zero_var = cs.allocate_constant(F::ZERO);
+        let mut limbs = [zero_var; N];
+        let mut residues = [zero_var; N];
+        for (limb, residue) in limbs
+            .iter_mut()
+            .zip(residues.iter_mut())
+            .take(params.modulus_limbs)
+        {
+            *limb = cs.alloc_variable_without_value();
+            // range check, and lowest chunk is a residue
+            *residue = decompose_into_range_checked_u16_chunks(cs, *limb, 32)[0];
+        }
+
+        let mut new = Self {
+            limbs,
+            residues,
+            max_limb_value: u32::MAX as u64,
+            max_residue_value: u16::MAX as u64,
+            tracker: OverflowTracker {
+                max_moduluses: params.max_mods_in_allocation,
+            },
+            form: RepresentationForm::Normalized,
+            params: params.clone(),
+            _marker: std::marker::PhantomData,
+        };
+
+        // for now we use explicit normalized form for allocated values
+        new.enforce_reduced(cs);
+
+        new
+    }
+
+    #[must_use]
+    pub fn allocate_checked<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        witness: T,
+        params: &Arc<NonNativeFieldOverU32Params<T, N>>,
+    ) -> Self {
+        let new = Self::allocate_checked_without_value(cs, params);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS == true {
+            let modulus_limbs = params.modulus_limbs;
+            let value_fn = move |_input: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let limbs = fe_to_u32_words::<_, N>(&witness);
+                for (idx, el) in limbs.into_iter().enumerate() {
+                    if idx < modulus_limbs {
+                        dst.push(F::from_u64_unchecked(el as u64));
+                    } else {
+                        assert_eq!(el, 0);
+                    }
+                }
+            };
+
+            let mut outputs = Vec::with_capacity(params.modulus_limbs);
+            outputs.extend(
+                Place::from_variables(new.limbs)
+                    .into_iter()
+                    .take(params.modulus_limbs),
+            );
+
+            cs.set_values_with_dependencies_vararg(&[], &outputs, value_fn);
+        }
+
+        new
+    }
+
+    pub fn enforce_reduced<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        assert_eq!(self.form, RepresentationForm::Normalized);
+        if self.tracker.max_moduluses == 1 {
+            return;
+        }
+
+        // we show that (P - 1) - self doesn't underflow using long subtraction
+        let modulus_limbs = self.params.modulus_limbs;
+        let modulus_minus_one = self.params.modulus_u1024.wrapping_sub(&U1024::ONE);
+        let modulus_minus_one = u1024_to_u32_words::<N>(&modulus_minus_one);
+
+        let differences: Vec<_> = (0..modulus_limbs)
+            .map(|_| cs.alloc_variable_without_value())
+            .collect();
+        let borrows: Vec<_> = (0..(modulus_limbs - 1))
+            .map(|_| cs.alloc_variable_without_value())
+            .collect();
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS == true {
+            let value_fn = move |inputs: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let mut borrow = 0u64;
+                for (idx, (limb, modulus_word)) in
+                    inputs.iter().zip(modulus_minus_one.iter()).enumerate()
+                {
+                    let minuend = *modulus_word as u64;
+                    let subtrahend = limb.as_u64_reduced() + borrow;
+                    let (difference, new_borrow) = if minuend >= subtrahend {
+                        (minuend - subtrahend, 0)
+                    } else {
+                        (minuend + (1u64 << 32) - subtrahend, 1)
+                    };
+
+                    dst.push(F::from_u64_unchecked(difference));
+                    if idx != modulus_limbs - 1 {
+                        dst.push(F::from_u64_unchecked(new_borrow));
+                    }
+                    borrow = new_borrow;
+                }
+
+                assert_eq!(borrow, 0, "element is not reduced");
+            };
+
+            let mut outputs = Vec::with_capacity(modulus_limbs * 2);
+            for (idx, difference) in differences.iter().enumerate() {
+                outputs.push(Place::from_variable(*difference));
+                if let Some(borrow) = borrows.get(idx) {
+                    outputs.push(Place::from_variable(*borrow));
+                }
+            }
+
+            let dependencies: Vec<_> = Place::from_variables(self.limbs)
+                .into_iter()
+                .take(modulus_limbs)
+                .collect();
+
+            cs.set_values_with_dependencies_vararg(&dependencies, &outputs, value_fn);
+        }
+
+        // difference + limb + borrow_in - 2^32 * borrow_out = modulus word
+        let one = cs.allocate_constant(F::ONE);
+        let mut minus_shift = F::from_u64_unchecked(1u64 << 32);
+        minus_shift.negate();
+        for (idx, (difference, limb)) in differences.iter().zip(self.limbs.iter()).enumerate() {
+            let mut modulus_word = F::from_u64_unchecked(modulus_minus_one[idx] as u64);
+            modulus_word.negate();
+
+            let mut terms = Vec::with_capacity(5);
+            terms.push((*difference, F::ONE));
+            terms.push((*limb, F::ONE));
+            if idx != 0 {
+                terms.push((borrows[idx - 1], F::ONE));
+            }
+            if let Some(borrow) = borrows.get(idx) {
+                terms.push((*borrow, minus_shift));
+            }
+            terms.push((one, modulus_word));
+
+            Num::enforce_zero_for_linear_combination(cs, &terms);
+
+            let _ = decompose_into_range_checked_u16_chunks(cs, *difference, 32);
+        }
+
+        for borrow in borrows.into_iter() {
+            let _ = Boolean::from_variable_checked(cs, borrow);
+        }
+
+        self.tracker.max_moduluses = 1;
+    }
+
+    pub fn normalize<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        if self.tracker.max_moduluses == 1 && self.form == RepresentationForm::Normalized {
+            return;
+        }
+
+        if self.form == RepresentationForm::Lazy {
+            // well, we just mul by 1
+            let mut one = Self::allocated_constant(cs, T::one(), &self.params);
+            *self = self.mul(cs, &mut one);
+        }
+
+        // normalized elements are only produced by allocation or multiplication, so for an honest prover
+        // they are already below the modulus, and we only need to prove it
+        self.enforce_reduced(cs);
+    }
+
+    // upper bound of the value from the upper bound of limbs
+    fn limbs_upper_bound(&self) -> U1024 {
+        let mut result = U1024::ZERO;
+        for idx in 0..self.params.modulus_limbs {
+            let limb_max = U1024::from_word(self.max_limb_value).shl_vartime(32 * idx);
+            result = result.wrapping_add(&limb_max);
+        }
+
+        result
+    }
+
+    fn max_value(&self) -> U1024 {
+        let from_tracker = self
+            .tracker
+            .into_max_value(self.params.modulus_u1024.as_ref());
+
+        std::cmp::min(from_tracker, self.limbs_upper_bound())
+    }
+
+    #[must_use]
+    pub fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        // all additions are lazy
+        self.lazy_add(cs, other)
+    }
+
+    #[must_use]
+    pub fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut tmp = self.clone();
+        self.lazy_add(cs, &mut tmp)
+    }
+
+    #[must_use]
+    pub fn lazy_double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut tmp = self.clone();
+        self.lazy_add(cs, &mut tmp)
+    }
+
+    // we add limbs and residues only without range checks
+    #[must_use]
+    pub fn lazy_add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        if self.max_limb_value + other.max_limb_value >= 1u64 << MAX_LAZY_LIMB_BITS {
+            self.normalize(cs);
+            other.normalize(cs);
+        }
+
+        let used_words = self.params.modulus_limbs;
+        let mut limbs = self.limbs;
+        let mut residues = self.residues;
+        for (dst, src) in limbs[..used_words]
+            .iter_mut()
+            .zip(other.limbs[..used_words].iter())
+        {
+            *dst = Num::from_variable(*dst)
+                .add(cs, &Num::from_variable(*src))
+                .variable;
+        }
+        for (dst, src) in residues[..used_words]
+            .iter_mut()
+            .zip(other.residues[..used_words].iter())
+        {
+            *dst = Num::from_variable(*dst)
+                .add(cs, &Num::from_variable(*src))
+                .variable;
+        }
+
+        Self {
+            limbs,
+            residues,
+            max_limb_value: self.max_limb_value + other.max_limb_value,
+            max_residue_value: self.max_residue_value + other.max_residue_value,
+            tracker: self.tracker.add(&other.tracker),
+            form: RepresentationForm::Lazy,
+            params: self.params.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn add_many_lazy<CS: ConstraintSystem<F>, const M: usize>(
+        cs: &mut CS,
+        mut inputs: [&mut Self; M],
+    ) -> Self {
+        assert!(inputs.len() > 1);
+        let [a, b] = inputs.array_chunks_mut::<2>().next().unwrap();
+        let mut result = a.lazy_add(cs, b);
+
+        for el in inputs.into_iter().skip(2) {
+            result = result.lazy_add(cs, el);
+        }
+
+        result
+    }
+
+    #[must_use]
+    pub fn negated<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        if self.max_limb_value + (u32::MAX as u64) >= 1u64 << MAX_LAZY_LIMB_BITS {
+            self.normalize(cs);
+        }
+
+        // we compute k * P - self, where k * P is represented by limbs that are not smaller
+        // than the upper bound of corresponding limbs of self, so no limb underflows. For this
+        // we take the smallest k * P that is not smaller than the upper bound of self, and
+        // distribute the difference (that is smaller than P) over the limbs
+        let used_words = self.params.modulus_limbs;
+        let upper_bound = self.limbs_upper_bound();
+        let (k, rem) = upper_bound.div_rem(&self.params.modulus_u1024);
+        let k = if rem.is_zero().unwrap_u8() == 1 {
+            k
+        } else {
+            k.wrapping_add(&U1024::ONE)
+        };
+        assert!(k.bits_vartime() < 32);
+        let modulus_multiple = self.params.modulus_u1024.wrapping_mul(&k);
+        let extra = modulus_multiple.wrapping_sub(&upper_bound);
+        let extra = u1024_to_u32_words::<N>(&extra);
+
+        let mut limbs = self.limbs;
+        let mut residues = self.residues;
+        for ((limb, residue), extra) in limbs[..used_words]
+            .iter_mut()
+            .zip(residues[..used_words].iter_mut())
+            .zip(extra.iter())
+        {
+            let limb_constant = self.max_limb_value + (*extra as u64);
+            // smallest value that is not smaller than the residue bound and is equal to the limb constant modulo 2^16
+            let residue_constant = self.max_residue_value
+                + (limb_constant.wrapping_sub(self.max_residue_value) & (u16::MAX as u64));
+
+            *limb = Num::allocated_constant(cs, F::from_u64_unchecked(limb_constant))
+                .sub(cs, &Num::from_variable(*limb))
+                .variable;
+            *residue = Num::allocated_constant(cs, F::from_u64_unchecked(residue_constant))
+                .sub(cs, &Num::from_variable(*residue))
+                .variable;
+        }
+
+        Self {
+            limbs,
+            residues,
+            max_limb_value: self.max_limb_value + (u32::MAX as u64),
+            max_residue_value: self.max_residue_value + (u16::MAX as u64),
+            // NOTE: if self == 0, then the value is exactly k * P, so use k + 1
+            tracker: OverflowTracker {
+                max_moduluses: k.as_words()[0] as u32 + 1,
+            },
+            form: RepresentationForm::Lazy,
+            params: self.params.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let mut other_negated = other.negated(cs);
+        self.lazy_add(cs, &mut other_negated)
+    }
+
+    #[must_use]
+    pub fn lazy_sub<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        let mut other_negated = other.negated(cs);
+        self.lazy_add(cs, &mut other_negated)
+    }
+
+    // Returns `None` if limbs are too large to enforce the relation
+    fn multiplication_layout(a: &Self, b: &Self) -> Option<MultiplicationLayout> {
+        let modulus_limbs = a.params.modulus_limbs;
+
+        let lhs_max = a.max_value().checked_mul(&b.max_value()).unwrap();
+        let q_max_value = lhs_max
+            .checked_div(a.params.modulus_u1024.as_ref())
+            .unwrap();
+        let q_max_value_bits = q_max_value.bits_vartime();
+        let num_q_limbs = std::cmp::max((q_max_value_bits + 31) / 32, 1);
+        let num_columns = std::cmp::max(2 * modulus_limbs - 1, num_q_limbs + modulus_limbs - 1);
+
+        // bound absolute values of every column of a * b - q * P - r and of the same column for residues
+        let max_modulus_word = *a.params.modulus.iter().max().unwrap() as u128;
+        let mut max_column = 0u128;
+        let mut max_residue_column = 0u128;
+        for column_idx in 0..num_columns {
+            let words_in_column = |num_words: usize| {
+                (0..num_words)
+                    .filter(|i| column_idx >= *i && column_idx - *i < modulus_limbs)
+                    .count() as u128
+            };
+            let num_products = words_in_column(modulus_limbs);
+            let num_q_products = words_in_column(num_q_limbs);
+
+            let positive = num_products * (a.max_limb_value as u128) * (b.max_limb_value as u128);
+            let negative =
+                num_q_products * (u32::MAX as u128) * max_modulus_word + u32::MAX as u128;
+            max_column = std::cmp::max(max_column, std::cmp::max(positive, negative));
+
+            let positive =
+                num_products * (a.max_residue_value as u128) * (b.max_residue_value as u128);
+            let negative =
+                num_q_products * (u16::MAX as u128) * (u16::MAX as u128) + u16::MAX as u128;
+            max_residue_column =
+                std::cmp::max(max_residue_column, std::cmp::max(positive, negative));
+        }
+
+        // carries are signed, so we range check them with offset
+        let max_carry = max_column / (u32::MAX as u128) + 1;
+        let carry_bits = (u128::BITS - max_carry.leading_zeros()) as usize + 1;
+        let carry_offset = 1u128 << (carry_bits - 1);
+
+        // column relation holds modulo F::CHAR * 2^16, so to hold over integers it should be smaller
+        if max_column + carry_offset * ((1u128 << 32) + 1) >= (F::CHAR as u128) << 16 {
+            return None;
+        }
+
+        let max_residue_carry = (max_residue_column + carry_offset) / (1u128 << 16) + 1;
+        let residue_carry_bits = (u128::BITS - max_residue_carry.leading_zeros()) as usize + 1;
+        let residue_carry_offset = 1u128 << (residue_carry_bits - 1);
+
+        // and relation for residues must not overflow the native field at all
+        if max_residue_column + carry_offset + (residue_carry_offset << 16) >= F::CHAR as u128 {
+            return None;
+        }
+
+        Some(MultiplicationLayout {
+            num_q_limbs,
+            num_columns,
+            carry_bits,
+            residue_carry_bits,
+        })
+    }
+
+    #[must_use]
+    pub fn mul<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self {
+        // we prove that a * b = q * P + r by columns: for every column k of a * b - q * P - r
+        // we enforce column_k + carry_{k - 1} - carry_k * 2^32 = 0 modulo the native field and
+        // modulo 2^16, so it's zero over integers as long as it's small enough
+
+        let mut layout = Self::multiplication_layout(self, other);
+        if layout.is_none() {
+            // limbs are too large, so normalize the largest one first
+            if self.max_limb_value >= other.max_limb_value {
+                self.normalize(cs);
+            } else {
+                other.normalize(cs);
+            }
+            layout = Self::multiplication_layout(self, other);
+        }
+        if layout.is_none() {
+            self.normalize(cs);
+            other.normalize(cs);
+            layout = Self::multiplication_layout(self, other);
+        }
+        let MultiplicationLayout {
+            num_q_limbs,
+            num_columns,
+            carry_bits,
+            residue_carry_bits,
+        } = layout.expect("normalized elements can always be multiplied");
+
+        let modulus_limbs = self.params.modulus_limbs;
+        let modulus = self.params.modulus;
+
+        // then allocate q and r
+        let zero_var = cs.allocate_constant(F::ZERO);
+        let q: Vec<_> = (0..num_q_limbs)
+            .map(|_| cs.alloc_variable_without_value())
+            .collect();
+        let mut r = [zero_var; N];
+        for dst in r.iter_mut().take(modulus_limbs) {
+            *dst = cs.alloc_variable_without_value();
+        }
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS == true {
+            let modulus_u1024 = self.params.modulus_u1024;
+
+            let value_fn = move |inputs: &[F], buffer: &mut DstBuffer<'_, '_, F>| {
+                debug_assert_eq!(inputs.len(), modulus_limbs * 2);
+
+                let a = unnormalized_u32_field_words_to_u1024(&inputs[..modulus_limbs]);
+                let b = unnormalized_u32_field_words_to_u1024(&inputs[modulus_limbs..]);
+
+                let product = a.checked_mul(&b).unwrap();
+                let (q, r) = product.div_rem(&modulus_u1024);
+                assert!(q.bits_vartime() <= num_q_limbs * 32);
+
+                // first q, then r
+                for idx in 0..num_q_limbs {
+                    let word = q.shr_vartime(32 * idx).as_words()[0] as u32;
+                    buffer.push(F::from_u64_unchecked(word as u64));
+                }
+                for idx in 0..modulus_limbs {
+                    let word = r.shr_vartime(32 * idx).as_words()[0] as u32;
+                    buffer.push(F::from_u64_unchecked(word as u64));
+                }
+            };
+
+            let mut dependencies = Vec::with_capacity(modulus_limbs * 2);
+            dependencies.extend(
+                self.limbs[..modulus_limbs]
+                    .iter()
+                    .map(|el| Place::from_variable(*el)),
+            );
+            dependencies.extend(
+                other.limbs[..modulus_limbs]
+                    .iter()
+                    .map(|el| Place::from_variable(*el)),
+            );
+
+            let mut outputs = Vec::with_capacity(num_q_limbs + modulus_limbs);
+            outputs.extend(q.iter().map(|el| Place::from_variable(*el)));
+            outputs.extend(
+                r[..modulus_limbs]
+                    .iter()
+                    .map(|el| Place::from_variable(*el)),
+            );
+
+            cs.set_values_with_dependencies_vararg(&dependencies, &outputs, value_fn);
+        }
+
+        // range check, and get residues for free
+        let q_residues: Vec<_> = q
+            .iter()
+            .map(|el| decompose_into_range_checked_u16_chunks(cs, *el, 32)[0])
+            .collect();
+        let mut r_residues = [zero_var; N];
+        for (src, dst) in r.iter().zip(r_residues.iter_mut()).take(modulus_limbs) {
+            *dst = decompose_into_range_checked_u16_chunks(cs, *src, 32)[0];
+        }
+
+        // now carries. Carry from the last column is zero
+        let carries: Vec<_> = (0..(num_columns - 1))
+            .map(|_| cs.alloc_variable_without_value())
+            .collect();
+        let residue_carries: Vec<_> = (0..num_columns)
+            .map(|_| cs.alloc_variable_without_value())
+            .collect();
+        let carry_offset = 1u64 << (carry_bits - 1);
+        let residue_carry_offset = 1u64 << (residue_carry_bits - 1);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS == true {
+            let value_fn = move |inputs: &[F], buffer: &mut DstBuffer<'_, '_, F>| {
+                debug_assert_eq!(inputs.len(), modulus_limbs * 5 + num_q_limbs);
+                let inputs: Vec<i128> = inputs
+                    .iter()
+                    .map(|el| el.as_u64_reduced() as i128)
+                    .collect();
+                let (a, rest) = inputs.split_at(modulus_limbs);
+                let (a_residues, rest) = rest.split_at(modulus_limbs);
+                let (b, rest) = rest.split_at(modulus_limbs);
+                let (b_residues, rest) = rest.split_at(modulus_limbs);
+                let (q, r) = rest.split_at(num_q_limbs);
+
+                let mut carry = 0i128;
+                for column_idx in 0..num_columns {
+                    let mut column = carry;
+                    let mut residue_column = carry;
+                    for (i, (a, a_residue)) in a.iter().zip(a_residues.iter()).enumerate() {
+                        if column_idx >= i && column_idx - i < modulus_limbs {
+                            column += a * b[column_idx - i];
+                            residue_column += a_residue * b_residues[column_idx - i];
+                        }
+                    }
+                    for (i, q) in q.iter().enumerate() {
+                        if column_idx >= i && column_idx - i < modulus_limbs {
+                            let p = modulus[column_idx - i] as i128;
+                            column -= q * p;
+                            residue_column -= (q & 0xffff) * (p & 0xffff);
+                        }
+                    }
+                    if let Some(r) = r.get(column_idx) {
+                        column -= r;
+                        residue_column -= r & 0xffff;
+                    }
+
+                    assert!(residue_column % (1i128 << 16) == 0);
+                    let residue_carry = (residue_column >> 16) + residue_carry_offset as i128;
+                    buffer.push(F::from_u64_unchecked(residue_carry as u64));
+
+                    assert!(column % (1i128 << 32) == 0);
+                    carry = column >> 32;
+                    if column_idx != num_columns - 1 {
+                        buffer.push(F::from_u64_unchecked((carry + carry_offset as i128) as u64));
+                    } else {
+                        assert_eq!(carry, 0);
+                    }
+                }
+            };
+
+            let mut dependencies = Vec::with_capacity(modulus_limbs * 5 + num_q_limbs);
+            for el in [self, &*other].into_iter() {
+                dependencies.extend(
+                    el.limbs[..modulus_limbs]
+                        .iter()
+                        .map(|el| Place::from_variable(*el)),
+                );
+                dependencies.extend(
+                    el.residues[..modulus_limbs]
+                        .iter()
+                        .map(|el| Place::from_variable(*el)),
+                );
+            }
+            dependencies.extend(q.iter().map(|el| Place::from_variable(*el)));
+            dependencies.extend(
+                r[..modulus_limbs]
+                    .iter()
+                    .map(|el| Place::from_variable(*el)),
+            );
+
+            let mut outputs = Vec::with_capacity(num_columns * 2);
+            for (idx, residue_carry) in residue_carries.iter().enumerate() {
+                outputs.push(Place::from_variable(*residue_carry));
+                if let Some(carry) = carries.get(idx) {
+                    outputs.push(Place::from_variable(*carry));
+                }
+            }
+
+            cs.set_values_with_dependencies_vararg(&dependencies, &outputs, value_fn);
+        }
+
+        for carry in carries.iter() {
+            let _ = decompose_into_range_checked_u16_chunks(cs, *carry, carry_bits);
+        }
+        for carry in residue_carries.iter() {
+            let _ = decompose_into_range_checked_u16_chunks(cs, *carry, residue_carry_bits);
+        }
+
+        // and enforce relations for every column
+        let one = cs.allocate_constant(F::ONE);
+        let minus_one = cs.allocate_constant(F::MINUS_ONE);
+        let shift_16 = F::from_u64_unchecked(1u64 << 16);
+        let shift_32 = F::from_u64_unchecked(1u64 << 32);
+        let mut minus_shift_16 = shift_16;
+        minus_shift_16.negate();
+        let minus_shift_16 = cs.allocate_constant(minus_shift_16);
+        let mut minus_shift_32 = shift_32;
+        minus_shift_32.negate();
+        let minus_shift_32 = cs.allocate_constant(minus_shift_32);
+
+        let mut terms = Vec::with_capacity(modulus_limbs + num_q_limbs + 4);
+        for column_idx in 0..num_columns {
+            let first_round = column_idx == 0;
+            let last_round = column_idx == num_columns - 1;
+
+            // column itself
+            terms.clear();
+            for (i, a_word) in self.limbs[..modulus_limbs].iter().enumerate() {
+                if column_idx >= i && column_idx - i < modulus_limbs {
+                    terms.push((*a_word, other.limbs[column_idx - i]));
+                }
+            }
+            for (i, q_word) in q.iter().enumerate() {
+                if column_idx >= i && column_idx - i < modulus_limbs {
+                    let p_word = modulus[column_idx - i];
+                    if p_word == 0 {
+                        continue;
+                    }
+                    let mut coeff = F::from_u64_unchecked(p_word as u64);
+                    coeff.negate();
+                    terms.push((*q_word, cs.allocate_constant(coeff)));
+                }
+            }
+            if column_idx < modulus_limbs {
+                terms.push((r[column_idx], minus_one));
+            }
+            let mut constant_term = F::ZERO;
+            if first_round == false {
+                terms.push((carries[column_idx - 1], one));
+                constant_term.sub_assign(&F::from_u64_unchecked(carry_offset));
+            }
+            if last_round == false {
+                terms.push((carries[column_idx], minus_shift_32));
+                let mut tmp = F::from_u64_unchecked(carry_offset);
+                tmp.mul_assign(&shift_32);
+                constant_term.add_assign(&tmp);
+            }
+            if constant_term.is_zero() == false {
+                terms.push((one, cs.allocate_constant(constant_term)));
+            }
+            enforce_dot_product_is_zero(cs, &terms);
+
+            // same column over residues, and carry to the next column is divisible by 2^16
+            terms.clear();
+            for (i, a_residue) in self.residues[..modulus_limbs].iter().enumerate() {
+                if column_idx >= i && column_idx - i < modulus_limbs {
+                    terms.push((*a_residue, other.residues[column_idx - i]));
+                }
+            }
+            for (i, q_residue) in q_residues.iter().enumerate() {
+                if column_idx >= i && column_idx - i < modulus_limbs {
+                    let p_residue = modulus[column_idx - i] & 0xffff;
+                    if p_residue == 0 {
+                        continue;
+                    }
+                    let mut coeff = F::from_u64_unchecked(p_residue as u64);
+                    coeff.negate();
+                    terms.push((*q_residue, cs.allocate_constant(coeff)));
+                }
+            }
+            if column_idx < modulus_limbs {
+                terms.push((r_residues[column_idx], minus_one));
+            }
+            let mut constant_term = F::from_u64_unchecked(residue_carry_offset);
+            constant_term.mul_assign(&shift_16);
+            if first_round == false {
+                terms.push((carries[column_idx - 1], one));
+                constant_term.sub_assign(&F::from_u64_unchecked(carry_offset));
+            }
+            terms.push((residue_carries[column_idx], minus_shift_16));
+            if constant_term.is_zero() == false {
+                terms.push((one, cs.allocate_constant(constant_term)));
+            }
+            enforce_dot_product_is_zero(cs, &terms);
+        }
+
+        Self {
+            limbs: r,
+            residues: r_residues,
+            max_limb_value: u32::MAX as u64,
+            max_residue_value: u16::MAX as u64,
+            // r is not reduced, but fits into the allocation range
+            tracker: OverflowTracker {
+                max_moduluses: self.params.max_mods_in_allocation,
+            },
+            form: RepresentationForm::Normalized,
+            params: self.params.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub fn square<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut other = self.clone();
+        self.mul(cs, &mut other)
+    }
+
+    #[must_use]
+    pub fn allocate_inverse_or_zero<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Self {
+        let new = Self::allocate_checked_without_value(cs, &self.params);
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS == true {
+            let modulus_u1024 = self.params.modulus_u1024;
+            let modulus_limbs = self.params.modulus_limbs;
+            let value_fn = move |input: &[F], dst: &mut DstBuffer<'_, '_, F>| {
+                let mut value = unnormalized_u32_field_words_to_u1024(input);
+                value = value.checked_rem(&modulus_u1024).unwrap();
+
+                let inner = u1024_to_fe::<T>(&value);
+                match inner.inverse() {
+                    Some(inversed) => {
+                        let words = fe_to_u32_words::<_, N>(&inversed);
+                        for (idx, word) in words.into_iter().enumerate() {
+                            if idx < modulus_limbs {
+                                dst.push(F::from_u64_unchecked(word as u64))
+                            } else {
+                                assert_eq!(word, 0);
+                            }
+                        }
+                    }
+                    None => {
+                        for _ in 0..modulus_limbs {
+                            dst.push(F::ZERO);
+                        }
+                    }
+                }
+            };
+
+            let mut outputs = Vec::with_capacity(modulus_limbs);
+            outputs.extend(
+                Place::from_variables(new.limbs)
+                    .into_iter()
+                    .take(modulus_limbs),
+            );
+
+            cs.set_values_with_dependencies_vararg(
+                &Place::from_variables(self.limbs),
+                &outputs,
+                value_fn,
+            );
+        }
+
+        new
+    }
+
+    #[must_use]
+    pub fn inverse_unchecked<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
+        let mut inverse = self.allocate_inverse_or_zero(cs);
+        let mut product = self.mul(cs, &mut inverse);
+        // enforce that product is normalized 1
+        product.enforce_reduced(cs);
+        let zero = cs.allocate_constant(F::ZERO);
+        let one = cs.allocate_constant(F::ONE);
+        for (idx, el) in product.limbs.into_iter().enumerate() {
+            if idx == 0 {
+                Num::enforce_equal(cs, &Num::from_variable(el), &Num::from_variable(one));
+            } else {
+                Num::enforce_equal(cs, &Num::from_variable(el), &Num::from_variable(zero));
+            }
+        }
+
+        inverse
+    }
+
+    #[must_use]
+    pub fn div_unchecked<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        other: &mut Self,
+    ) -> Self {
+        let mut inversed = other.inverse_unchecked(cs);
+        self.mul(cs, &mut inversed)
+    }
+
+    #[must_use]
+    pub fn is_zero<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Boolean<F> {
+        self.normalize(cs);
+        let zeroes: Vec<_> = self.limbs[..self.params.modulus_limbs]
+            .iter()
+            .map(|el| Num::from_variable(*el).is_zero(cs))
+            .collect();
+
+        Boolean::multi_and(cs, &zeroes)
+    }
+
+    #[must_use]
+    pub fn mask<CS: ConstraintSystem<F>>(&self, cs: &mut CS, masking_bit: Boolean<F>) -> Self {
+        let mut new = self.clone();
+        for dst in new.limbs.iter_mut().chain(new.residues.iter_mut()) {
+            *dst = Num::from_variable(*dst).mask(cs, masking_bit).variable;
+        }
+
+        new
+    }
+
+    #[must_use]
+    pub fn mask_negated<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        masking_bit: Boolean<F>,
+    ) -> Self {
+        let mut new = self.clone();
+        for dst in new.limbs.iter_mut().chain(new.residues.iter_mut()) {
+            *dst = Num::from_variable(*dst)
+                .mask_negated(cs, masking_bit)
+                .variable;
+        }
+
+        new
+    }
+
+    #[must_use]
+    pub fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &mut Self, b: &mut Self) -> Boolean<F> {
+        // difference is lazy, so we only normalize once
+        let mut diff = a.sub(cs, b);
+
+        diff.is_zero(cs)
+    }
+
+    #[must_use]
+    pub fn decompose_into_le_bits<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+    ) -> Vec<Boolean<F>> {
+        // normalized limbs are range checked and the value is < modulus, so
+        // bits above the modulus bit length are zeroes and can be dropped
+        self.normalize(cs);
+
+        let mut bits = Vec::with_capacity(self.params.modulus_limbs * 32);
+        for limb in self.limbs.iter().take(self.params.modulus_limbs) {
+            let limb_bits = Num::from_variable(*limb).spread_into_bits::<_, 32>(cs);
+            bits.extend(limb_bits);
+        }
+        bits.truncate(self.params.modulus_bits as usize);
+
+        bits
+    }
+}
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> CSAllocatable<F>
+    for NonNativeFieldOverU32<F, T, N>
+{
+    type Witness = FFProxyValueOverU32<T, N>;
+
+    fn placeholder_witness() -> Self::Witness {
+        FFProxyValueOverU32 { value: T::zero() }
+    }
+    fn allocate_without_value<CS: ConstraintSystem<F>>(_cs: &mut CS) -> Self {
+        unimplemented!("we need parameters to do it")
+    }
+    fn allocate<CS: ConstraintSystem<F>>(_cs: &mut CS, _witness: Self::Witness) -> Self {
+        unimplemented!("we need parameters to do it")
+    }
+}
+
+// Same as `FFProxyValue`, but for 32 bit limbs
+
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Hash)]
+pub struct FFProxyValueOverU32<T: pairing::ff::PrimeField, const N: usize> {
+    value: T,
+}
+
+impl<T: pairing::ff::PrimeField, const N: usize> FFProxyValueOverU32<T, N> {
+    pub const fn get(&self) -> T {
+        self.value
+    }
+}
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> WitnessCastable<F, [F; N]>
+    for FFProxyValueOverU32<T, N>
+{
+    fn cast_from_source(witness: [F; N]) -> Self {
+        let mut modulus_u1024 = U1024::ZERO;
+        for (dst, src) in modulus_u1024
+            .as_words_mut()
+            .iter_mut()
+            .zip(T::char().as_ref())
+        {
+            *dst = *src;
+        }
+        let modulus_u1024 = NonZero::<U1024>::new(modulus_u1024).unwrap();
+
+        let value = unnormalized_u32_field_words_to_u1024(&witness);
+        let (_, rem) = value.div_rem(&modulus_u1024);
+
+        let inner = u1024_to_fe::<T>(&rem);
+
+        Self { value: inner }
+    }
+
+    fn cast_into_source(self) -> [F; N] {
+        unimplemented!("we allow non-reduced representations, so we should not use this function")
+    }
+}
+
+use crate::gadgets::traits::castable::Convertor;
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> CSWitnessable<F, N>
+    for NonNativeFieldOverU32<F, T, N>
+{
+    type ConversionFunction = Convertor<F, [F; N], FFProxyValueOverU32<T, N>>;
+
+    fn witness_from_set_of_values(values: [F; N]) -> Self::Witness {
+        <FFProxyValueOverU32<T, N> as WitnessCastable<F, [F; N]>>::cast_from_source(values)
+    }
+
+    fn as_variables_set(&self) -> [Variable; N] {
+        self.limbs
+    }
+}
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> WitnessHookable<F>
+    for NonNativeFieldOverU32<F, T, N>
+{
+    fn witness_hook<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &CS,
+    ) -> Box<dyn FnOnce() -> Option<Self::Witness>> {
+        let raw_witness = self.get_witness(cs);
+        Box::new(move || raw_witness.wait())
+    }
+}
+
+impl<F: SmallField, T: pairing::ff::PrimeField, const N: usize> Selectable<F>
+    for NonNativeFieldOverU32<F, T, N>
+{
+    const SUPPORTS_PARALLEL_SELECT: bool = false;
+
+    #[must_use]
+    fn conditionally_select<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        flag: Boolean<F>,
+        a: &Self,
+        b: &Self,
+    ) -> Self {
+        let a_limbs = a.limbs.map(|el| Num::from_variable(el));
+        let b_limbs = b.limbs.map(|el| Num::from_variable(el));
+        let selected_limbs = Num::parallel_select(cs, flag, &a_limbs, &b_limbs);
+
+        let a_residues = a.residues.map(|el| Num::from_variable(el));
+        let b_residues = b.residues.map(|el| Num::from_variable(el));
+        let selected_residues = Num::parallel_select(cs, flag, &a_residues, &b_residues);
+
+        let max_moduluses = std::cmp::max(a.tracker.max_moduluses, b.tracker.max_moduluses);
+        let new_tracker = OverflowTracker { max_moduluses };
+
+        let new_form = match (a.form, b.form) {
+            (RepresentationForm::Normalized, RepresentationForm::Normalized) => {
+                RepresentationForm::Normalized
+            }
+            _ => RepresentationForm::Lazy,
+        };
+
+        Self {
+            limbs: selected_limbs.map(|el| el.get_variable()),
+            residues: selected_residues.map(|el| el.get_variable()),
+            max_limb_value: std::cmp::max(a.max_limb_value, b.max_limb_value),
+            max_residue_value: std::cmp::max(a.max_residue_value, b.max_residue_value),
+            tracker: new_tracker,
+            form: new_form,
+            params: a.params.clone(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::alloc::Global;
+
+    use super::*;
+    use crate::cs::gates::testing_cs::create_test_cs;
+    use crate::cs::gates::*;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::worker::Worker;
+    use pairing::ff::{Field, PrimeField};
+
+    type F = GoldilocksField;
+    type Ext = pairing::bn256::Fq;
+    type NN = NonNativeFieldOverU32<F, Ext, 8>;
+    type Params = NonNativeFieldOverU32Params<Ext, 8>;
+
+    #[test]
+    fn test_mul() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 18, 1, 10, 1 << 20);
+        let cs = &mut owned_cs;
+
+        let a_value = Ext::from_str("123").unwrap();
+        let b_value = Ext::from_str("456").unwrap();
+
+        let params = Params::create();
+        let params = std::sync::Arc::new(params);
+
+        let mut a = NN::allocate_checked(cs, a_value, &params);
+        let mut b = NN::allocate_checked(cs, b_value, &params);
+
+        let c = a.mul(cs, &mut b);
+
+        let mut c_value = a_value;
+        c_value.mul_assign(&b_value);
+
+        let witness = c.witness_hook(&*cs)().unwrap().get();
+
+        assert_eq!(c_value, witness);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    fn test_lazy_arithmetic() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 18, 1, 10, 1 << 20);
+        let cs = &mut owned_cs;
+
+        let a_value = Ext::from_str(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208582",
+        )
+        .unwrap();
+        let b_value = Ext::from_str("1234567890123456789012345678901234567890").unwrap();
+
+        let params = Params::create();
+        let params = std::sync::Arc::new(params);
+
+        let mut a = NN::allocate_checked(cs, a_value, &params);
+        let mut b = NN::allocate_checked(cs, b_value, &params);
+
+        // long chain of lazy operations forces intermediate normalization
+        let mut acc = a.clone();
+        let mut acc_value = a_value;
+        for _ in 0..20 {
+            let mut a_negated = a.negated(cs);
+            acc = acc.lazy_add(cs, &mut a_negated);
+            acc = acc.sub(cs, &mut b);
+            acc_value.sub_assign(&a_value);
+            acc_value.sub_assign(&b_value);
+        }
+
+        // multiplication of lazy values
+        let mut sum = a.lazy_add(cs, &mut b);
+        let mut product = acc.mul(cs, &mut sum);
+        let mut sum_value = a_value;
+        sum_value.add_assign(&b_value);
+        let mut product_value = acc_value;
+        product_value.mul_assign(&sum_value);
+
+        let witness = product.witness_hook(&*cs)().unwrap().get();
+        assert_eq!(product_value, witness);
+
+        let mut expected = NN::allocate_checked(cs, product_value, &params);
+        let is_equal = NN::equals(cs, &mut product, &mut expected);
+        assert!(is_equal.witness_hook(&*cs)().unwrap());
+        let is_equal = NN::equals(cs, &mut product, &mut a);
+        assert!(is_equal.witness_hook(&*cs)().unwrap() == false);
+
+        let mut difference = product.sub(cs, &mut expected);
+        let is_zero = difference.is_zero(cs);
+        assert!(is_zero.witness_hook(&*cs)().unwrap());
+
+        let mut quotient = product.div_unchecked(cs, &mut sum);
+        let witness = quotient.witness_hook(&*cs)().unwrap().get();
+        assert_eq!(acc_value, witness);
+
+        let bits = quotient.decompose_into_le_bits(cs);
+        assert_eq!(bits.len(), Ext::NUM_BITS as usize);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    // the largest upper bound on limbs of `a` for which `a * b` still fits into the CRT bound
+    fn max_multipliable_limb_value(a: &NN, b: &NN) -> u64 {
+        let fits = |max_limb_value: u64| {
+            let mut a = a.clone();
+            a.max_limb_value = max_limb_value;
+            NN::multiplication_layout(&a, b).is_some()
+        };
+
+        let (mut low, mut high) = (b.max_limb_value, 1u64 << 63);
+        assert!(fits(low));
+        assert!(fits(high) == false);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if fits(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        low
+    }
+
+    #[test]
+    fn test_mul_at_crt_bound() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 18, 1, 10, 1 << 20);
+        let cs = &mut owned_cs;
+
+        let a_value = Ext::from_str(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208582",
+        )
+        .unwrap();
+        let b_value = Ext::from_str("1234567890123456789012345678901234567890").unwrap();
+
+        let params = Params::create();
+        let params = std::sync::Arc::new(params);
+
+        let mut a = NN::allocate_checked(cs, a_value, &params);
+        let mut b = NN::allocate_checked(cs, b_value, &params);
+
+        // limbs at the lazy limit can be multiplied by normalized ones as is,
+        // but not by each other
+        let mut lazy = a.clone();
+        lazy.max_limb_value = (1u64 << MAX_LAZY_LIMB_BITS) - 1;
+        assert!(NN::multiplication_layout(&lazy, &b).is_some());
+        assert!(NN::multiplication_layout(&lazy, &lazy).is_none());
+
+        // only the bound is loosened, so the relation is enforced with the widest carries
+        // that still fit, and the product is still correct
+        let max_limb_value = max_multipliable_limb_value(&a, &b);
+        assert!(max_limb_value >= 1u64 << MAX_LAZY_LIMB_BITS);
+        a.max_limb_value = max_limb_value;
+        let c = a.mul(cs, &mut b);
+
+        let mut c_value = a_value;
+        c_value.mul_assign(&b_value);
+        let witness = c.witness_hook(&*cs)().unwrap().get();
+        assert_eq!(c_value, witness);
+
+        let worker = Worker::new_with_num_threads(8);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+
+    #[test]
+    #[should_panic(expected = "normalized elements can always be multiplied")]
+    fn test_mul_past_crt_bound() {
+        let mut owned_cs = create_test_cs::<F, UIntXAddGate<16>>(1 << 18, 1, 10, 1 << 20);
+        let cs = &mut owned_cs;
+
+        let params = Params::create();
+        let params = std::sync::Arc::new(params);
+
+        let mut a = NN::allocate_checked(cs, Ext::from_str("123").unwrap(), &params);
+        let mut b = NN::allocate_checked(cs, Ext::from_str("456").unwrap(), &params);
+
+        // `a` is still marked as normalized, so normalization doesn't reset the bound
+        // and there is no way to fit the relation
+        a.max_limb_value = max_multipliable_limb_value(&a, &b) + 1;
+        let _ = a.mul(cs, &mut b);
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use self::utils::u16_words_to_u1024;
+use self::utils::{u1024_to_u32_words, u16_words_to_u1024};
 
 use super::*;
 use crate::config::*;
@@ -12,6 +12,7 @@ use crypto_bigint::{CheckedMul, NonZero, Zero, U1024};
 
 pub mod impl_traits;
 pub mod implementation_u16;
+pub mod implementation_u32;
 pub mod utils;
 
 // Small note on the strategy - because we are quite flexible in what range check tables we use we have a few options:
@@ -24,6 +25,15 @@ pub mod utils;
 // a * b = q * P + r, and depending on what we need afterwards to ensure range of `q` and `r` in certain bounds.
 // Note that we rarely need `r` to be strictly `< P` and usually are fine to have it `< 2^log2(P)` or even `< 2^next_range_check_multiple(log2(P))`
 
+// Alternative representation (`NonNativeFieldOverU32`) uses wider limbs. Limbs can not be 64 bits wide as they would
+// not fit into the native field, so those are 32 bits, and 64 bit wide partial products are accumulated
+// in columns of a * b - q * P - r without any intermediate reduction. Such column overflows the native field, so the relation
+// `column_k + carry_{k - 1} - carry_k * 2^32 = 0` is enforced modulo the native field and modulo 2^16 (using small
+// residues of the limbs that are carried along with them), and by CRT it holds over integers as long as everything is much smaller
+// than F::CHAR * 2^16. Residues come for free from range checks, and while partial products are computed twice (for limbs and
+// for residues) there are still 2x less of them than for 16 bit limbs. Additions and negations are lazy and only touch limbs and residues,
+// and results of multiplication are not reduced below P unless one explicitly asks for it.
+
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug)]
 pub struct OverflowTracker {
@@ -177,6 +187,69 @@ impl<T: pairing::ff::PrimeField, const N: usize> NonNativeFieldOverU16Params<T,
     }
 }
 
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug)]
+pub struct NonNativeFieldOverU32Params<T: pairing::ff::PrimeField, const N: usize> {
+    pub modulus: [u32; N], // modulus padded with zeroes to number of representation words if necessary
+    pub modulus_bits: u32, // number of bits in modulus
+    pub modulus_limbs: usize, // number of u32 limbs required to represent a modulus
+    pub modulus_u1024: crypto_bigint::NonZero<U1024>,
+    pub max_mods_in_allocation: u32, // how many moduluses fit into 2^{32 * modulus_limbs}
+    pub _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: pairing::ff::PrimeField, const N: usize> NonNativeFieldOverU32Params<T, N> {
+    pub const fn repr_bits(&self) -> u32 {
+        (N as u32) * 32
+    }
+
+    pub fn create() -> Self {
+        let modulus_bits = T::NUM_BITS;
+        let mut modulus_limbs = modulus_bits / 32;
+        if modulus_bits % 32 != 0 {
+            modulus_limbs += 1;
+        }
+        assert!(modulus_limbs as usize <= N);
+
+        let mut modulus_u1024 = U1024::ZERO;
+        for (dst, src) in modulus_u1024
+            .as_words_mut()
+            .iter_mut()
+            .zip(T::char().as_ref())
+        {
+            *dst = *src;
+        }
+
+        let modulus_non_zero = NonZero::new(modulus_u1024).unwrap();
+
+        let max_in_allocation = U1024::ONE
+            .shl_vartime(32 * modulus_limbs as usize)
+            .wrapping_sub(&U1024::ONE);
+        let (q, r) = max_in_allocation.div_rem(&modulus_non_zero);
+        for el in q.as_words().iter().skip(1) {
+            assert_eq!(*el, 0);
+        }
+        let mut max_mods_in_allocation = q.as_words()[0];
+        if r.is_zero().unwrap_u8() == 0 {
+            max_mods_in_allocation += 1;
+        }
+        assert!(max_mods_in_allocation <= u32::MAX as u64);
+
+        let max_mods_in_allocation = max_mods_in_allocation as u32;
+
+        let modulus = u1024_to_u32_words::<N>(&modulus_u1024);
+
+        Self {
+            modulus,
+            modulus_bits,
+            modulus_limbs: modulus_limbs as usize,
+            modulus_u1024: modulus_non_zero,
+            max_mods_in_allocation,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RepresentationForm {
@@ -195,6 +268,22 @@ pub struct NonNativeFieldOverU16<F: SmallField, T: pairing::ff::PrimeField, cons
     pub _marker: std::marker::PhantomData<F>,
 }
 
+// Only lowest `modulus_limbs` limbs are used, and the rest are zeroes
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct NonNativeFieldOverU32<F: SmallField, T: pairing::ff::PrimeField, const N: usize> {
+    pub limbs: [Variable; N],
+    // integers that are equal to corresponding limbs modulo 2^16
+    pub residues: [Variable; N],
+    // upper bounds (inclusive) for every limb and every residue
+    pub max_limb_value: u64,
+    pub max_residue_value: u64,
+    pub tracker: OverflowTracker,
+    pub form: RepresentationForm,
+    pub params: Arc<NonNativeFieldOverU32Params<T, N>>,
+    pub _marker: std::marker::PhantomData<F>,
+}
+
 pub fn get_16_bits_range_check_table<F: SmallField, CS: ConstraintSystem<F>>(
     cs: &CS,
 ) -> Option<u32> {
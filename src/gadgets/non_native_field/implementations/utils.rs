@@ -1,6 +1,6 @@
 use super::*;
 use crate::{
-    cs::gates::{ReductionGate, ReductionGateParams, UIntXAddGate},
+    cs::gates::{DotProductGate, ReductionGate, ReductionGateParams, UIntXAddGate},
     gadgets::{boolean::Boolean, num::Num, traits::selectable::Selectable},
 };
 use crypto_bigint::U1024;
@@ -43,6 +43,28 @@ pub(crate) fn fe_to_u16_words<T: pairing::ff::PrimeField, const N: usize>(src: &
     result
 }
 
+pub(crate) fn fe_to_u32_words<T: pairing::ff::PrimeField, const N: usize>(src: &T) -> [u32; N] {
+    let mut result = [0u32; N];
+    let repr = src.into_repr();
+    for (idx, el) in repr.as_ref().iter().enumerate() {
+        let low = *el as u32;
+        let high = (*el >> 32) as u32;
+
+        if 2 * idx < N {
+            result[2 * idx] = low;
+        } else {
+            debug_assert_eq!(low, 0);
+        }
+        if 2 * idx + 1 < N {
+            result[2 * idx + 1] = high;
+        } else {
+            debug_assert_eq!(high, 0);
+        }
+    }
+
+    result
+}
+
 pub fn u16_words_to_u1024(words: &[u16]) -> U1024 {
     let mut result = U1024::ZERO;
     let mut i = 0;
@@ -87,6 +109,19 @@ pub fn unnormalized_u16_field_words_to_u1024<F: SmallField>(words: &[F]) -> U102
     result
 }
 
+pub fn unnormalized_u32_field_words_to_u1024<F: SmallField>(words: &[F]) -> U1024 {
+    let mut result = U1024::ZERO;
+    let mut shift = 0;
+    for el in words.iter() {
+        let as_u1024 = U1024::from_word(el.as_u64_reduced());
+        let as_u1024 = as_u1024.shl_vartime(shift);
+        result = result.saturating_add(&as_u1024);
+        shift += 32;
+    }
+
+    result
+}
+
 pub fn u16_field_words_to_u1024<F: SmallField>(words: &[F]) -> U1024 {
     let mut result = U1024::ZERO;
     let mut i = 0;
@@ -333,6 +368,82 @@ pub fn split_out_u32_carry_from_zero_low<F: SmallField, CS: ConstraintSystem<F>>
     (swap, (low, high))
 }
 
+/// Decomposes `variable` into 16 bit chunks, least significant first, and range checks them,
+/// so that `variable < 2^num_bits`. Unused chunks are zeroes
+pub fn decompose_into_range_checked_u16_chunks<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    variable: Variable,
+    num_bits: usize,
+) -> [Variable; 4] {
+    debug_assert!(num_bits > 0);
+    debug_assert!(num_bits <= F::CAPACITY_BITS);
+    let num_chunks = (num_bits + 15) / 16;
+
+    let zero = cs.allocate_constant(F::ZERO);
+    let chunks = ReductionGate::<F, 4>::decompose_into_limbs_limited(
+        cs,
+        F::from_u64_unchecked(1u64 << 16),
+        variable,
+        num_chunks,
+        zero,
+    );
+    for chunk in chunks[..num_chunks].iter() {
+        range_check_u16(cs, *chunk);
+    }
+
+    // top chunk is shifted to the top of 16 bits and checked once more
+    if num_bits % 16 != 0 {
+        let shift = F::from_u64_unchecked(1u64 << (16 - num_bits % 16));
+        let shifted = Num::linear_combination(cs, &[(chunks[num_chunks - 1], shift)]).variable;
+        range_check_u16(cs, shifted);
+    }
+
+    chunks
+}
+
+/// Enforces that `sum a_i * b_i = 0` for given pairs of variables, using chain of dot product gates
+pub fn enforce_dot_product_is_zero<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    terms: &[(Variable, Variable)],
+) {
+    let zero = cs.allocate_constant(F::ZERO);
+    let one = cs.allocate_constant(F::ONE);
+
+    let mut terms_it = terms.iter().copied().peekable();
+    let mut previous = None;
+    loop {
+        let mut gate_terms = [(zero, zero); 4];
+        for dst in gate_terms.iter_mut() {
+            // accumulator from the previous gate goes first
+            if let Some(previous) = previous.take() {
+                *dst = (previous, one);
+            } else if let Some(next) = terms_it.next() {
+                *dst = next;
+            }
+        }
+
+        if terms_it.peek().is_none() {
+            let mut terms_flattened = [zero; 8];
+            for ((a, b), dst) in gate_terms
+                .into_iter()
+                .zip(terms_flattened.array_chunks_mut::<2>())
+            {
+                dst[0] = a;
+                dst[1] = b;
+            }
+            let gate = DotProductGate::<4> {
+                terms: terms_flattened,
+                result: zero,
+            };
+            gate.add_to_cs(cs);
+
+            break;
+        }
+
+        previous = Some(DotProductGate::<4>::compute_dot_product(cs, gate_terms));
+    }
+}
+
 #[track_caller]
 pub fn u1024_to_fe<T: pairing::ff::PrimeField>(input: &U1024) -> T {
     let mut modulus_u1024 = U1024::ZERO;
@@ -369,3 +480,19 @@ pub fn u1024_to_u16_words<const N: usize>(input: &U1024) -> [u16; N] {
 
     result
 }
+
+#[track_caller]
+pub fn u1024_to_u32_words<const N: usize>(input: &U1024) -> [u32; N] {
+    let mut result = [0u32; N];
+    let mut tmp = *input;
+    for dst in result.iter_mut() {
+        let low = tmp.as_words()[0];
+        *dst = low as u32;
+
+        tmp = tmp.shr_vartime(32);
+    }
+
+    assert!(tmp.is_zero().unwrap_u8() == 1);
+
+    result
+}
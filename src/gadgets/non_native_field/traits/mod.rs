@@ -28,6 +28,11 @@ pub trait NonNativeField<F: SmallField, T: pairing::ff::PrimeField>:
     fn enforce_reduced<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS);
     fn normalize<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS);
 
+    /// Normalizes the element and returns `T::NUM_BITS` bits of its canonical representation,
+    /// least significant first. There is no default implementation, as bits can't be witnessed
+    /// through the rest of this trait, so implementations outside of this crate have to add it
+    fn decompose_into_le_bits<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Vec<Boolean<F>>;
+
     fn add<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, other: &mut Self) -> Self;
 
     fn double<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Self {
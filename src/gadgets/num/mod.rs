@@ -37,6 +37,7 @@ impl<F: SmallField> CSAllocatable<F> for Num<F> {
         F::ZERO
     }
 
+    #[track_caller]
     #[inline(always)]
     #[must_use]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
@@ -48,6 +49,7 @@ impl<F: SmallField> CSAllocatable<F> for Num<F> {
         }
     }
 
+    #[track_caller]
     #[inline(always)]
     #[must_use]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
@@ -59,6 +61,7 @@ impl<F: SmallField> CSAllocatable<F> for Num<F> {
         }
     }
 
+    #[track_caller]
     #[inline(always)]
     #[must_use]
     fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
@@ -206,6 +209,7 @@ impl<F: SmallField> Num<F> {
         }
     }
 
+    #[track_caller]
     #[inline]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: F) -> Self {
@@ -217,6 +221,7 @@ impl<F: SmallField> Num<F> {
         }
     }
 
+    #[track_caller]
     #[inline]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
@@ -617,6 +622,31 @@ impl<F: SmallField> Num<F> {
         self.decompose_into_bytes_inner(cs, length / 8)
     }
 
+    /// Decomposes the field element into 8 little-endian bytes, and enforces that decomposition
+    /// is canonical (smaller than the modulus), so it matches `as_u64_reduced().to_le_bytes()`
+    /// out of circuit
+    #[must_use]
+    pub fn into_canonical_le_bytes<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> [UInt8<F>; 8] {
+        use crate::gadgets::u32::UInt32;
+
+        assert!(F::CHAR_BITS <= 64);
+
+        let bytes = self.constraint_bit_length_as_bytes(cs, 64);
+        let bytes: [UInt8<F>; 8] = bytes.into_inner().expect("must have 8 bytes");
+
+        // value - modulus must underflow
+        let low = UInt32::from_le_bytes(cs, bytes[..4].try_into().unwrap());
+        let high = UInt32::from_le_bytes(cs, bytes[4..].try_into().unwrap());
+        let modulus_low = UInt32::allocated_constant(cs, F::CHAR as u32);
+        let modulus_high = UInt32::allocated_constant(cs, (F::CHAR >> 32) as u32);
+        let (_, borrow) = low.overflowing_sub(cs, modulus_low);
+        let (_, borrow) = high.overflowing_sub_with_borrow_in(cs, modulus_high, borrow);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &borrow, &boolean_true);
+
+        bytes
+    }
+
     #[must_use]
     pub fn linear_combination<CS: ConstraintSystem<F>>(
         cs: &mut CS,
@@ -939,6 +969,7 @@ impl<F: SmallField> Num<F> {
         diff.is_zero(cs)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_multiple_from_closure_and_dependencies<
         CS: ConstraintSystem<F>,
@@ -1236,6 +1267,7 @@ impl<F: SmallField> CSAllocatableExt<F> for Num<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -1252,3 +1284,37 @@ impl<F: SmallField> CSAllocatableExt<F> for Num<F> {
         dst.push(witness);
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for Num<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    #[inline]
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Self::enforce_equal(cs, a, b)
+    }
+}
+
+// Field elements are compared as integers in `[0, modulus)`. Both elements are decomposed
+// into canonical 32-bit limbs, so it's not cheap
+impl<F: SmallField> CircuitOrd<F> for Num<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        use crate::gadgets::u32::UInt32;
+
+        let a = a.into_canonical_le_bytes(cs);
+        let b = b.into_canonical_le_bytes(cs);
+        let mut borrow = Boolean::allocated_constant(cs, false);
+        for (a, b) in a.array_chunks::<4>().zip(b.array_chunks::<4>()) {
+            let a = UInt32::from_le_bytes(cs, *a);
+            let b = UInt32::from_le_bytes(cs, *b);
+            (_, borrow) = a.overflowing_sub_with_borrow_in(cs, b, borrow);
+        }
+
+        borrow
+    }
+}
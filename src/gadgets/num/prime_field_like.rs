@@ -157,162 +157,172 @@ where
 #[derivative(Debug(bound = ""), Hash(bound = ""))]
 pub struct NumExtAsFieldWrapper<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F>,
+    const N: usize = 2,
 > {
-    c0: NumAsFieldWrapper<F, CS>,
-    c1: NumAsFieldWrapper<F, CS>,
+    coeffs: [NumAsFieldWrapper<F, CS>; N],
     #[derivative(Debug = "ignore", Hash = "ignore")]
     _marker: std::marker::PhantomData<EXT>,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>>
-    std::fmt::Display for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > std::fmt::Display for NumExtAsFieldWrapper<F, EXT, CS, N>
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "NumExt as PrimeFieldLike{{")?;
-        writeln!(f, "c0: {:?},", self.c0.inner.variable)?;
-        writeln!(f, "c1: {:?},", self.c1.inner.variable)?;
+        for (idx, coeff) in self.coeffs.iter().enumerate() {
+            writeln!(f, "c{}: {:?},", idx, coeff.inner.variable)?;
+        }
         writeln!(f, "}}")
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>> Clone
-    for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > Clone for NumExtAsFieldWrapper<F, EXT, CS, N>
 {
     #[must_use]
     fn clone(&self) -> Self {
         Self {
-            c0: self.c0,
-            c1: self.c1,
+            coeffs: self.coeffs,
             _marker: std::marker::PhantomData,
         }
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>> Copy
-    for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > Copy for NumExtAsFieldWrapper<F, EXT, CS, N>
 {
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CSO: ConstraintSystem<F> + 'static>
-    CSAllocatable<F> for NumExtAsFieldWrapper<F, EXT, CSO>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CSO: ConstraintSystem<F> + 'static,
+    > CSAllocatable<F> for NumExtAsFieldWrapper<F, EXT, CSO, N>
 {
-    type Witness = ExtensionField<F, 2, EXT>;
+    type Witness = ExtensionField<F, N, EXT>;
 
     #[must_use]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
-        let [c0, c1] = witness.into_coeffs_in_base();
-        let c0 = Num::allocate(cs, c0).into();
-        let c1 = Num::allocate(cs, c1).into();
+        let coeffs = witness
+            .into_coeffs_in_base()
+            .map(|el| Num::allocate(cs, el).into());
 
-        Self::from_coeffs_in_base([c0, c1])
+        Self::from_coeffs_in_base(coeffs)
     }
 
     #[must_use]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
-        let c0 = Num::allocate_without_value(cs).into();
-        let c1 = Num::allocate_without_value(cs).into();
+        let coeffs = [(); N].map(|_| Num::allocate_without_value(cs).into());
 
-        Self::from_coeffs_in_base([c0, c1])
+        Self::from_coeffs_in_base(coeffs)
     }
 
     fn placeholder_witness() -> Self::Witness {
-        <ExtensionField<F, 2, EXT> as crate::field::traits::field::Field>::ZERO
+        <ExtensionField<F, N, EXT> as crate::field::traits::field::Field>::ZERO
     }
 }
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CSO: ConstraintSystem<F> + 'static>
-    WitnessHookable<F> for NumExtAsFieldWrapper<F, EXT, CSO>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CSO: ConstraintSystem<F> + 'static,
+    > WitnessHookable<F> for NumExtAsFieldWrapper<F, EXT, CSO, N>
 {
     fn witness_hook<CS: ConstraintSystem<F>>(
         &self,
         cs: &CS,
     ) -> Box<dyn FnOnce() -> Option<Self::Witness> + 'static> {
-        let c0_hook = self.c0.witness_hook(cs);
-        let c1_hook = self.c1.witness_hook(cs);
+        let hooks = self.coeffs.map(|el| el.witness_hook(cs));
         Box::new(|| {
-            let c0 = c0_hook()?;
-            let c1 = c1_hook()?;
+            let mut coeffs = [F::ZERO; N];
+            for (dst, hook) in coeffs.iter_mut().zip(hooks.into_iter()) {
+                *dst = hook()?;
+            }
 
-            Some(ExtensionField::<F, 2, EXT>::from_coeff_in_base([c0, c1]))
+            Some(ExtensionField::<F, N, EXT>::from_coeff_in_base(coeffs))
         })
     }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F> + 'static>
-    NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F> + 'static,
+    > NumExtAsFieldWrapper<F, EXT, CS, N>
 {
     #[must_use]
-    pub const fn from_coeffs_in_base(coeffs: [NumAsFieldWrapper<F, CS>; 2]) -> Self {
-        let [c0, c1] = coeffs;
-
+    pub const fn from_coeffs_in_base(coeffs: [NumAsFieldWrapper<F, CS>; N]) -> Self {
         Self {
-            c0,
-            c1,
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
 
     #[must_use]
-    pub fn from_num_coeffs_in_base(coeffs: [Num<F>; 2]) -> Self {
-        let [c0, c1] = coeffs;
-
+    pub fn from_num_coeffs_in_base(coeffs: [Num<F>; N]) -> Self {
         Self {
-            c0: c0.into(),
-            c1: c1.into(),
+            coeffs: coeffs.map(|el| el.into()),
             _marker: std::marker::PhantomData,
         }
     }
 
     #[must_use]
-    pub const fn into_coeffs_in_base(self) -> [NumAsFieldWrapper<F, CS>; 2] {
-        [self.c0, self.c1]
+    pub const fn into_coeffs_in_base(self) -> [NumAsFieldWrapper<F, CS>; N] {
+        self.coeffs
     }
 
     #[must_use]
-    pub const fn into_num_coeffs_in_base(self) -> [Num<F>; 2] {
-        [self.c0.into_num(), self.c1.into_num()]
+    pub fn into_num_coeffs_in_base(self) -> [Num<F>; N] {
+        self.coeffs.map(|el| el.into_num())
     }
 
-    pub fn mul_assign_by_base(&mut self, base: &NumAsFieldWrapper<F, CS>, cs: &mut CS) {
-        // // There is no win here
-        // if cs.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
-        //     // we can use FMA
-        //     let a0 = self.c0.inner.get_variable();
-        //     let a1 = self.c1.inner.get_variable();
-
-        //     let zero_var = cs.allocate_constant(F::ZERO);
-
-        //     let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
-        //         cs,
-        //         ExtensionField::<F, 2, EXT>::ONE,
-        //         ([a0, a1], [base.inner.get_variable(), zero_var]),
-        //         ExtensionField::<F, 2, EXT>::ZERO,
-        //         [a0, a1],
-        //     );
-
-        //     let c0 = Num::from_variable(c0);
-        //     let c1 = Num::from_variable(c1);
-
-        //     let result = Self::from_num_coeffs_in_base([c0, c1]);
+    #[must_use]
+    fn variables(&self) -> [Variable; N] {
+        self.coeffs.map(|el| el.inner.get_variable())
+    }
 
-        //     *self = result;
+    // element of the base field as the first coefficient
+    #[must_use]
+    fn from_base(value: NumAsFieldWrapper<F, CS>, cs: &mut CS) -> Self {
+        let zero = Num::allocated_constant(cs, F::ZERO).into();
+        let mut coeffs = [zero; N];
+        coeffs[0] = value;
 
-        //     return;
-        // }
+        Self::from_coeffs_in_base(coeffs)
+    }
 
-        self.c0.mul_assign(base, cs);
-        self.c1.mul_assign(base, cs);
+    pub fn mul_assign_by_base(&mut self, base: &NumAsFieldWrapper<F, CS>, cs: &mut CS) {
+        // There is no win from the FMA gate here
+        for coeff in self.coeffs.iter_mut() {
+            coeff.mul_assign(base, cs);
+        }
     }
 
     #[must_use]
     pub fn conditionally_select(cs: &mut CS, flag: Boolean<F>, a: &Self, b: &Self) -> Self {
-        let c0 = NumAsFieldWrapper::conditionally_select(cs, flag, &a.c0, &b.c0);
-        let c1 = NumAsFieldWrapper::conditionally_select(cs, flag, &a.c1, &b.c1);
+        let mut coeffs = a.coeffs;
+        for (dst, b) in coeffs.iter_mut().zip(b.coeffs.iter()) {
+            *dst = NumAsFieldWrapper::conditionally_select(cs, flag, dst, b);
+        }
 
         Self {
-            c0,
-            c1,
+            coeffs,
             _marker: std::marker::PhantomData,
         }
     }
@@ -323,30 +333,19 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         other: &Self,
         cs: &mut CS,
     ) {
-        if cs.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if cs.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT, N>>() {
             // we can use FMA gate
-            let a0 = other.c0.inner.get_variable();
-            let a1 = other.c1.inner.get_variable();
-
-            let c0 = dst.c0.inner.get_variable();
-            let c1 = dst.c1.inner.get_variable();
+            let base = Self::from_base(*base, cs);
 
-            let zero_var = cs.allocate_constant(F::ZERO);
-
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
+            let result = FmaGateInExtensionWithoutConstant::<F, EXT, N>::compute_fma_in_extension(
                 cs,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([a0, a1], [base.inner.get_variable(), zero_var]),
-                ExtensionField::<F, 2, EXT>::ONE,
-                [c0, c1],
+                ExtensionField::<F, N, EXT>::ONE,
+                (other.variables(), base.variables()),
+                ExtensionField::<F, N, EXT>::ONE,
+                dst.variables(),
             );
 
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
-
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
-
-            *dst = result;
+            *dst = Self::from_num_coeffs_in_base(result.map(Num::from_variable));
         } else {
             // baseline
             let mut tmp = *other;
@@ -355,10 +354,68 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             dst.add_assign(&tmp, cs);
         }
     }
+
+    // schoolbook multiplication with reduction by the non-residue, for extensions
+    // where there is no specialized formula
+    fn mul_assign_generic(&mut self, other: &Self, cs: &mut CS) {
+        let zero = NumAsFieldWrapper::zero(cs);
+        let mut result = [zero; N];
+        let mut to_reduce = [zero; N];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                if i + j < N {
+                    NumAsFieldWrapper::mul_and_accumulate_into(&mut result[i + j], a, b, cs);
+                } else {
+                    NumAsFieldWrapper::mul_and_accumulate_into(&mut to_reduce[i + j - N], a, b, cs);
+                }
+            }
+        }
+        let non_residue = NumAsFieldWrapper::constant(EXT::non_residue(), cs);
+        for (dst, src) in result.iter_mut().zip(to_reduce.iter()) {
+            NumAsFieldWrapper::mul_and_accumulate_into(dst, src, &non_residue, cs);
+        }
+
+        self.coeffs = result;
+    }
+
+    // inverse is given as a witness, and its product with the element is constrained to be one
+    fn inverse_generic(&self, cs: &mut CS) -> Self {
+        let inverse = cs.alloc_multiple_variables_without_values::<N>();
+
+        if <CS::Config as CSConfig>::WitnessConfig::EVALUATE_WITNESS {
+            let value_fn = move |inputs: [F; N]| {
+                let value = ExtensionField::<F, N, EXT>::from_coeff_in_base(inputs);
+                let inverse = value.inverse().expect("element must be invertible");
+
+                inverse.into_coeffs_in_base()
+            };
+
+            cs.set_values_with_dependencies(
+                &Place::from_variables(self.variables()),
+                &Place::from_variables(inverse),
+                value_fn,
+            );
+        }
+
+        let inverse = Self::from_num_coeffs_in_base(inverse.map(Num::from_variable));
+        let mut product = *self;
+        product.mul_assign_generic(&inverse, cs);
+
+        let one = Self::one(cs);
+        for (a, b) in product.coeffs.iter().zip(one.coeffs.iter()) {
+            Num::enforce_equal(cs, &a.inner, &b.inner);
+        }
+
+        inverse
+    }
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F>> PrimeFieldLike
-    for NumExtAsFieldWrapper<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F>,
+    > PrimeFieldLike for NumExtAsFieldWrapper<F, EXT, CS, N>
 where
     CS: 'static,
 {
@@ -370,175 +427,175 @@ where
     fn zero(ctx: &mut Self::Context) -> Self {
         let zero = Num::allocated_constant(ctx, F::ZERO).into();
         Self {
-            c0: zero,
-            c1: zero,
+            coeffs: [zero; N],
             _marker: std::marker::PhantomData,
         }
     }
     #[must_use]
     fn one(ctx: &mut Self::Context) -> Self {
-        let zero = Num::allocated_constant(ctx, F::ZERO).into();
         let one = Num::allocated_constant(ctx, F::ONE).into();
-        Self {
-            c0: one,
-            c1: zero,
-            _marker: std::marker::PhantomData,
-        }
+
+        Self::from_base(one, ctx)
     }
     #[must_use]
     fn minus_one(ctx: &mut Self::Context) -> Self {
-        let zero = Num::allocated_constant(ctx, F::ZERO).into();
         let minus_one = Num::allocated_constant(ctx, F::MINUS_ONE).into();
-        Self {
-            c0: minus_one,
-            c1: zero,
-            _marker: std::marker::PhantomData,
-        }
+
+        Self::from_base(minus_one, ctx)
     }
     fn add_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.add_assign(&other.c0, ctx);
-        self.c1.add_assign(&other.c1, ctx);
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.add_assign(b, ctx);
+        }
 
         self
     }
     fn sub_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.sub_assign(&other.c0, ctx);
-        self.c1.sub_assign(&other.c1, ctx);
+        for (a, b) in self.coeffs.iter_mut().zip(other.coeffs.iter()) {
+            a.sub_assign(b, ctx);
+        }
 
         self
     }
     fn mul_assign(&'_ mut self, other: &Self, ctx: &mut Self::Context) -> &'_ mut Self {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT, N>>() {
             // we can use FMA
-            let a0 = self.c0.inner.get_variable();
-            let a1 = self.c1.inner.get_variable();
+            let a = self.variables();
 
-            let b0 = other.c0.inner.get_variable();
-            let b1 = other.c1.inner.get_variable();
-
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
+            let result = FmaGateInExtensionWithoutConstant::<F, EXT, N>::compute_fma_in_extension(
                 ctx,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([a0, a1], [b0, b1]),
-                ExtensionField::<F, 2, EXT>::ZERO,
-                [a0, a1],
+                ExtensionField::<F, N, EXT>::ONE,
+                (a, other.variables()),
+                ExtensionField::<F, N, EXT>::ZERO,
+                a,
             );
 
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
+            *self = Self::from_num_coeffs_in_base(result.map(Num::from_variable));
 
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            return self;
+        }
 
-            *self = result;
+        if N != 2 {
+            self.mul_assign_generic(other, ctx);
 
             return self;
         }
 
-        let mut v0 = self.c0;
-        v0.mul_assign(&other.c0, ctx);
-        let mut v1 = self.c1;
-        v1.mul_assign(&other.c1, ctx);
+        let [mut c0, mut c1] = [self.coeffs[0], self.coeffs[1]];
+        let [other_c0, other_c1] = [other.coeffs[0], other.coeffs[1]];
+
+        let mut v0 = c0;
+        v0.mul_assign(&other_c0, ctx);
+        let mut v1 = c1;
+        v1.mul_assign(&other_c1, ctx);
 
-        let t = self.c0;
-        self.c1.add_assign(&t, ctx);
+        c1.add_assign(&c0, ctx);
 
-        let mut t0 = other.c0;
-        t0.add_assign(&other.c1, ctx);
-        self.c1.mul_assign(&t0, ctx);
-        self.c1.sub_assign(&v0, ctx);
-        self.c1.sub_assign(&v1, ctx);
-        self.c0 = v0;
+        let mut t0 = other_c0;
+        t0.add_assign(&other_c1, ctx);
+        c1.mul_assign(&t0, ctx);
+        c1.sub_assign(&v0, ctx);
+        c1.sub_assign(&v1, ctx);
+        c0 = v0;
         let non_residue = NumAsFieldWrapper::constant(EXT::non_residue(), ctx);
         v1.mul_assign(&non_residue, ctx);
-        self.c0.add_assign(&v1, ctx);
+        c0.add_assign(&v1, ctx);
+
+        self.coeffs[0] = c0;
+        self.coeffs[1] = c1;
 
         self
     }
     fn square(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT, N>>() {
             // we can use FMA
-            let c0 = self.c0.inner.get_variable();
-            let c1 = self.c1.inner.get_variable();
+            let a = self.variables();
 
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
+            let result = FmaGateInExtensionWithoutConstant::<F, EXT, N>::compute_fma_in_extension(
                 ctx,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([c0, c1], [c0, c1]),
-                ExtensionField::<F, 2, EXT>::ZERO,
-                [c0, c1],
+                ExtensionField::<F, N, EXT>::ONE,
+                (a, a),
+                ExtensionField::<F, N, EXT>::ZERO,
+                a,
             );
 
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
+            *self = Self::from_num_coeffs_in_base(result.map(Num::from_variable));
 
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            return self;
+        }
 
-            *self = result;
+        if N != 2 {
+            let this = *self;
+            self.mul_assign_generic(&this, ctx);
 
             return self;
         }
 
-        let mut v0 = self.c0;
-        v0.sub_assign(&self.c1, ctx);
-        let mut v3 = self.c0;
-        let mut t0 = self.c1;
+        let [c0, c1] = [self.coeffs[0], self.coeffs[1]];
+
+        let mut v0 = c0;
+        v0.sub_assign(&c1, ctx);
+        let mut v3 = c0;
+        let mut t0 = c1;
         let non_residue = NumAsFieldWrapper::constant(EXT::non_residue(), ctx);
         t0.mul_assign(&non_residue, ctx);
         v3.sub_assign(&t0, ctx);
-        let mut v2 = self.c0;
-        v2.mul_assign(&self.c1, ctx);
+        let mut v2 = c0;
+        v2.mul_assign(&c1, ctx);
         v0.mul_assign(&v3, ctx);
         v0.add_assign(&v2, ctx);
 
-        self.c1 = v2;
-        self.c1.double(ctx);
-        self.c0 = v0;
+        self.coeffs[1] = v2;
+        self.coeffs[1].double(ctx);
+        self.coeffs[0] = v0;
 
         v2.mul_assign(&non_residue, ctx);
-        self.c0.add_assign(&v2, ctx);
+        self.coeffs[0].add_assign(&v2, ctx);
 
         self
     }
     fn negate(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.negate(ctx);
-        self.c1.negate(ctx);
+        for coeff in self.coeffs.iter_mut() {
+            coeff.negate(ctx);
+        }
 
         self
     }
     fn double(&'_ mut self, ctx: &mut Self::Context) -> &'_ mut Self {
-        self.c0.double(ctx);
-        self.c1.double(ctx);
+        for coeff in self.coeffs.iter_mut() {
+            coeff.double(ctx);
+        }
 
         self
     }
     // infallible inverse
     #[must_use]
     fn inverse(&self, ctx: &mut Self::Context) -> Self {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT, N>>() {
             // we can use FMA
-            let c0 = self.c0.inner.get_variable();
-            let c1 = self.c1.inner.get_variable();
             let zero_var = ctx.allocate_constant(F::ZERO);
             let one_var = ctx.allocate_constant(F::ONE);
 
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::create_inversion_constraint(
-                ctx,
-                [c0, c1],
-                zero_var,
-                one_var,
-            );
-
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
+            let result =
+                FmaGateInExtensionWithoutConstant::<F, EXT, N>::create_inversion_constraint(
+                    ctx,
+                    self.variables(),
+                    zero_var,
+                    one_var,
+                );
 
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
+            return Self::from_num_coeffs_in_base(result.map(Num::from_variable));
+        }
 
-            return result;
+        if N != 2 {
+            return self.inverse_generic(ctx);
         }
 
-        let mut v0 = self.c0;
+        let [c0, c1] = [self.coeffs[0], self.coeffs[1]];
+
+        let mut v0 = c0;
         v0.square(ctx);
-        let mut v1 = self.c1;
+        let mut v1 = c1;
         v1.square(ctx);
         // v0 = v0 - beta * v1
         let mut v1_by_nonresidue = v1;
@@ -546,56 +603,33 @@ where
         v1_by_nonresidue.mul_assign(&non_residue, ctx);
         v0.sub_assign(&v1_by_nonresidue, ctx);
         let inversed = v0.inverse(ctx);
-        let mut c0 = self.c0;
-        c0.mul_assign(&inversed, ctx);
-        let mut c1 = self.c1;
-        c1.mul_assign(&inversed, ctx);
-        c1.negate(ctx);
-
-        let new = Self {
-            c0,
-            c1,
-            _marker: std::marker::PhantomData,
-        };
+        let mut new = *self;
+        new.coeffs[0].mul_assign(&inversed, ctx);
+        new.coeffs[1].mul_assign(&inversed, ctx);
+        new.coeffs[1].negate(ctx);
 
         new
     }
     // constant creation
     #[must_use]
     fn constant(value: Self::Base, ctx: &mut Self::Context) -> Self {
-        let zero = Num::allocated_constant(ctx, F::ZERO).into();
         let constant = Num::allocated_constant(ctx, value).into();
-        Self {
-            c0: constant,
-            c1: zero,
-            _marker: std::marker::PhantomData,
-        }
+
+        Self::from_base(constant, ctx)
     }
     #[must_use]
     fn mul_and_accumulate_into(acc: &mut Self, a: &Self, b: &Self, ctx: &mut Self::Context) {
-        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT>>() {
+        if ctx.gate_is_allowed::<FmaGateInExtensionWithoutConstant<F, EXT, N>>() {
             // we can use FMA
-            let a_c0 = a.c0.inner.get_variable();
-            let a_c1 = a.c1.inner.get_variable();
-            let b_c0 = b.c0.inner.get_variable();
-            let b_c1 = b.c1.inner.get_variable();
-            let acc_c0 = acc.c0.inner.get_variable();
-            let acc_c1 = acc.c1.inner.get_variable();
-
-            let [c0, c1] = FmaGateInExtensionWithoutConstant::<F, EXT>::compute_fma_in_extension(
+            let result = FmaGateInExtensionWithoutConstant::<F, EXT, N>::compute_fma_in_extension(
                 ctx,
-                ExtensionField::<F, 2, EXT>::ONE,
-                ([a_c0, a_c1], [b_c0, b_c1]),
-                ExtensionField::<F, 2, EXT>::ONE,
-                [acc_c0, acc_c1],
+                ExtensionField::<F, N, EXT>::ONE,
+                (a.variables(), b.variables()),
+                ExtensionField::<F, N, EXT>::ONE,
+                acc.variables(),
             );
 
-            let c0 = Num::from_variable(c0);
-            let c1 = Num::from_variable(c1);
-
-            let result = Self::from_num_coeffs_in_base([c0, c1]);
-
-            *acc = result;
+            *acc = Self::from_num_coeffs_in_base(result.map(Num::from_variable));
         } else {
             // degrade to default implementation
             let mut tmp = *a;
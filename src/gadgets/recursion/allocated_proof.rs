@@ -134,16 +134,22 @@ pub struct AllocatedSingleRoundQueries<F: SmallField, H: RecursiveTreeHasher<F,
 }
 
 impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>> AllocatedSingleRoundQueries<F, H> {
-    pub fn allocate_from_witness<CS: ConstraintSystem<F>, EXT: FieldExtension<2, BaseField = F>>(
+    pub fn allocate_from_witness<
+        CS: ConstraintSystem<F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    >(
         cs: &mut CS,
         witness: Option<SingleRoundQueries<F, H::NonCircuitSimulator>>,
-        verifier: &RecursiveVerifier<F, EXT, CS>,
+        verifier: &RecursiveVerifier<F, EXT, CS, N>,
         fixed_parameters: &VerificationKeyCircuitGeometry,
         proof_config: &ProofConfig,
     ) -> Self {
         let base_oracle_depth = fixed_parameters.base_oracles_depth();
 
-        let witness_leaf_size = verifier.witness_leaf_size(fixed_parameters);
+        // every leaf ends with salt in zero-knowledge mode
+        let salt_size = proof_config.salt_size();
+        let witness_leaf_size = verifier.witness_leaf_size(fixed_parameters) + salt_size;
         let witness_query = AllocatedOracleQuery::allocate_from_witness(
             cs,
             witness.as_ref().map(|el| el.witness_query.clone()),
@@ -151,7 +157,7 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>> AllocatedSingleRoundQueri
             base_oracle_depth,
         );
 
-        let stage_2_leaf_size = verifier.stage_2_leaf_size(fixed_parameters);
+        let stage_2_leaf_size = verifier.stage_2_leaf_size(fixed_parameters) + salt_size;
         let stage_2_query = AllocatedOracleQuery::allocate_from_witness(
             cs,
             witness.as_ref().map(|el| el.stage_2_query.clone()),
@@ -159,7 +165,9 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>> AllocatedSingleRoundQueri
             base_oracle_depth,
         );
 
-        let quotient_leaf_size = verifier.quotient_leaf_size(fixed_parameters);
+        let quotient_leaf_size = verifier.quotient_leaf_size(fixed_parameters)
+            + proof_config.num_masking_polys() * N
+            + salt_size;
         let quotient_query = AllocatedOracleQuery::allocate_from_witness(
             cs,
             witness.as_ref().map(|el| el.quotient_query.clone()),
@@ -181,7 +189,7 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>> AllocatedSingleRoundQueri
         let mut fri_queries = Vec::with_capacity(interpolation_schedule.len());
         for (idx, interpolation_log_2) in interpolation_schedule.into_iter().enumerate() {
             expected_fri_query_len -= interpolation_log_2;
-            let leaf_size = (1 << interpolation_log_2) * 2; // in extension
+            let leaf_size = (1 << interpolation_log_2) * N; // in extension
             let wit = witness.as_ref().map(|el| el.fri_queries[idx].clone());
             let query = AllocatedOracleQuery::allocate_from_witness(
                 cs,
@@ -251,18 +259,19 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>> AllocatedSingleRoundQueri
 pub struct AllocatedProof<
     F: SmallField,
     H: RecursiveTreeHasher<F, Num<F>>,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
+    const N: usize = 2,
 > {
     pub public_inputs: Vec<Num<F>>,
 
     pub witness_oracle_cap: Vec<H::CircuitOutput>,
     pub stage_2_oracle_cap: Vec<H::CircuitOutput>,
     pub quotient_oracle_cap: Vec<H::CircuitOutput>,
-    pub final_fri_monomials: [Vec<Num<F>>; 2],
+    pub final_fri_monomials: [Vec<Num<F>>; N],
 
-    pub values_at_z: Vec<[Num<F>; 2]>,
-    pub values_at_z_omega: Vec<[Num<F>; 2]>,
-    pub values_at_0: Vec<[Num<F>; 2]>,
+    pub values_at_z: Vec<[Num<F>; N]>,
+    pub values_at_z_omega: Vec<[Num<F>; N]>,
+    pub values_at_0: Vec<[Num<F>; N]>,
 
     pub fri_base_oracle_cap: Vec<H::CircuitOutput>,
     pub fri_intermediate_oracles_caps: Vec<Vec<H::CircuitOutput>>,
@@ -274,13 +283,17 @@ pub struct AllocatedProof<
     _marker: std::marker::PhantomData<EXT>,
 }
 
-impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>, EXT: FieldExtension<2, BaseField = F>>
-    AllocatedProof<F, H, EXT>
+impl<
+        F: SmallField,
+        H: RecursiveTreeHasher<F, Num<F>>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+    > AllocatedProof<F, H, EXT, N>
 {
     pub fn allocate_from_witness<CS: ConstraintSystem<F>>(
         cs: &mut CS,
-        witness: Option<Proof<F, H::NonCircuitSimulator, EXT>>,
-        verifier: &RecursiveVerifier<F, EXT, CS>,
+        witness: Option<Proof<F, H::NonCircuitSimulator, EXT, N>>,
+        verifier: &RecursiveVerifier<F, EXT, CS, N>,
         fixed_parameters: &VerificationKeyCircuitGeometry,
         proof_config: &ProofConfig,
     ) -> Self {
@@ -314,25 +327,19 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>, EXT: FieldExtension<2, Ba
             allocate_num_elements::<F, CS, H::CircuitOutput>(cs, num_elements, quotient_oracle_cap);
 
         let num_elements = verifier.final_expected_degree(fixed_parameters, proof_config);
-        let final_fri_monomials_c0 = witness
-            .as_ref()
-            .map(|el| el.final_fri_monomials[0].iter().cloned());
-        let final_fri_monomials_c0 =
-            allocate_num_elements::<F, CS, Num<F>>(cs, num_elements, final_fri_monomials_c0);
-
-        let num_elements = verifier.final_expected_degree(fixed_parameters, proof_config);
-        let final_fri_monomials_c1 = witness
-            .as_ref()
-            .map(|el| el.final_fri_monomials[1].iter().cloned());
-        let final_fri_monomials_c1 =
-            allocate_num_elements::<F, CS, Num<F>>(cs, num_elements, final_fri_monomials_c1);
+        let final_fri_monomials: [_; N] = std::array::from_fn(|idx| {
+            let final_fri_monomials = witness
+                .as_ref()
+                .map(|el| el.final_fri_monomials[idx].iter().cloned());
+            allocate_num_elements::<F, CS, Num<F>>(cs, num_elements, final_fri_monomials)
+        });
 
-        let num_elements = verifier.num_poly_values_at_z(fixed_parameters);
+        let num_elements = verifier.num_poly_values_at_z(fixed_parameters, proof_config);
         let values_at_z = witness
             .as_ref()
             .map(|el| el.values_at_z.iter().map(|el| el.into_coeffs_in_base()));
         let values_at_z =
-            allocate_num_elements::<F, CS, [Num<F>; 2]>(cs, num_elements, values_at_z);
+            allocate_num_elements::<F, CS, [Num<F>; N]>(cs, num_elements, values_at_z);
 
         let num_elements = verifier.num_poly_values_at_z_omega();
         let values_at_z_omega = witness.as_ref().map(|el| {
@@ -341,14 +348,14 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>, EXT: FieldExtension<2, Ba
                 .map(|el| el.into_coeffs_in_base())
         });
         let values_at_z_omega =
-            allocate_num_elements::<F, CS, [Num<F>; 2]>(cs, num_elements, values_at_z_omega);
+            allocate_num_elements::<F, CS, [Num<F>; N]>(cs, num_elements, values_at_z_omega);
 
         let num_elements = verifier.num_poly_values_at_zero(fixed_parameters);
         let values_at_0 = witness
             .as_ref()
             .map(|el| el.values_at_0.iter().map(|el| el.into_coeffs_in_base()));
         let values_at_0 =
-            allocate_num_elements::<F, CS, [Num<F>; 2]>(cs, num_elements, values_at_0);
+            allocate_num_elements::<F, CS, [Num<F>; N]>(cs, num_elements, values_at_0);
 
         let num_elements = fixed_parameters.cap_size;
         let fri_base_oracle_cap = witness
@@ -397,8 +404,6 @@ impl<F: SmallField, H: RecursiveTreeHasher<F, Num<F>>, EXT: FieldExtension<2, Ba
             Boolean::allocate(cs, witness)
         });
 
-        let final_fri_monomials = [final_fri_monomials_c0, final_fri_monomials_c1];
-
         Self {
             public_inputs,
 
@@ -1,7 +1,24 @@
 use super::*;
+use crate::algebraic_props::round_function::{AbsorptionModeOverwrite, AlgebraicRoundFunction};
+use crate::algebraic_props::sponge::SimpleAlgebraicSponge;
 use crate::cs::implementations::pow::{NoPow, PoWRunner};
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::goldilocks::GoldilocksField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::num::Num;
+use crate::gadgets::traits::round_function::CircuitRoundFunction;
+use crate::implementations::poseidon2::Poseidon2Goldilocks;
 
-pub trait CircuitPowRunner<F: SmallField> {}
+pub trait CircuitPowRunner<F: SmallField>: 'static + Send + Sync {
+    /// Returns a flag whether the challenge (given as little-endian bits) is a valid
+    /// PoW for the seed pulled from the transcript
+    fn verify_from_field_elements<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        seed: Vec<Num<F>>,
+        pow_bits: u32,
+        challenge: &[Boolean<F>; 64],
+    ) -> Boolean<F>;
+}
 
 pub trait RecursivePoWRunner<F: SmallField>: PoWRunner {
     type CircuitReflection: CircuitPowRunner<F>;
@@ -13,8 +30,207 @@ pub struct CircuitNoPow<F: SmallField> {
     _marker: std::marker::PhantomData<F>,
 }
 
-impl<F: SmallField> CircuitPowRunner<F> for CircuitNoPow<F> {}
+impl<F: SmallField> CircuitPowRunner<F> for CircuitNoPow<F> {
+    fn verify_from_field_elements<CS: ConstraintSystem<F>>(
+        _cs: &mut CS,
+        _seed: Vec<Num<F>>,
+        pow_bits: u32,
+        _challenge: &[Boolean<F>; 64],
+    ) -> Boolean<F> {
+        assert_eq!(pow_bits, 0);
+        unreachable!()
+    }
+}
 
 impl<F: SmallField> RecursivePoWRunner<F> for NoPow {
     type CircuitReflection = CircuitNoPow<F>;
 }
+
+/// Splits the PoW challenge into two 32-bit limbs, in the same way as the prover
+/// commits it into the transcript
+pub fn pow_challenge_into_limbs<F: SmallField, CS: ConstraintSystem<F>>(
+    cs: &mut CS,
+    challenge: &[Boolean<F>; 64],
+) -> [Num<F>; 2] {
+    assert!(F::CAPACITY_BITS >= 32);
+
+    std::array::from_fn(|limb_idx| {
+        let input: Vec<_> = challenge[limb_idx * 32..(limb_idx + 1) * 32]
+            .iter()
+            .zip(F::SHIFTS.iter())
+            .map(|(bit, shift)| (bit.get_variable(), *shift))
+            .collect();
+
+        Num::linear_combination(cs, &input)
+    })
+}
+
+/// Circuit counterpart of the PoW over the `SimpleAlgebraicSponge` in the overwrite absorption mode
+#[derive(Derivative)]
+#[derivative(Clone, Copy, Debug, Default(bound = ""))]
+pub struct CircuitAlgebraicSpongePoW<
+    F: SmallField,
+    const AW: usize,
+    const SW: usize,
+    const CW: usize,
+    R: CircuitRoundFunction<F, AW, SW, CW>,
+> {
+    _marker: std::marker::PhantomData<(F, R)>,
+}
+
+// The commitment is decomposed into 64 bits, so it's only implemented for Goldilocks
+impl<
+        const AW: usize,
+        const SW: usize,
+        const CW: usize,
+        R: CircuitRoundFunction<GoldilocksField, AW, SW, CW>,
+    > CircuitPowRunner<GoldilocksField>
+    for CircuitAlgebraicSpongePoW<GoldilocksField, AW, SW, CW, R>
+{
+    fn verify_from_field_elements<CS: ConstraintSystem<GoldilocksField>>(
+        cs: &mut CS,
+        seed: Vec<Num<GoldilocksField>>,
+        pow_bits: u32,
+        challenge: &[Boolean<GoldilocksField>; 64],
+    ) -> Boolean<GoldilocksField> {
+        assert!(pow_bits <= 32);
+
+        let [low, high] = pow_challenge_into_limbs(cs, challenge);
+
+        let mut to_absorb = seed;
+        to_absorb.push(low);
+        to_absorb.push(high);
+        // same as finalization of the sponge: pad the last chunk with zeroes
+        let mut multiple = to_absorb.len() / AW;
+        if to_absorb.len() % AW != 0 {
+            multiple += 1;
+        }
+        let zero_num = Num::zero(cs);
+        to_absorb.resize(multiple * AW, zero_num);
+
+        let mut state = R::create_empty_state(cs).map(|el| Num::from_variable(el));
+        for chunk in to_absorb.array_chunks::<AW>() {
+            let els_to_keep = R::split_capacity_elements(&state.map(|el| el.get_variable()))
+                .map(|el| Num::from_variable(el));
+            state = R::absorb_with_replacement_over_nums(cs, *chunk, els_to_keep);
+            state = R::compute_round_function_over_nums(cs, state);
+        }
+
+        if pow_bits == 0 {
+            return Boolean::allocated_constant(cs, true);
+        }
+
+        let [result] = R::state_into_commitment::<1>(&state.map(|el| el.get_variable()));
+        // decomposition is not necessarily canonical, but a non-canonical one only exists for
+        // values below 2^32 for Goldilocks, that is even harder to grind for than the PoW itself
+        let result_bits = Num::from_variable(result).spread_into_bits::<CS, 64>(cs);
+        let any_low_bit_is_set = Boolean::multi_or(cs, &result_bits[..(pow_bits as usize)]);
+
+        any_low_bit_is_set.negated(cs)
+    }
+}
+
+impl<
+        R: AlgebraicRoundFunction<GoldilocksField, AW, SW, CW>
+            + CircuitRoundFunction<GoldilocksField, AW, SW, CW>,
+        const AW: usize,
+        const SW: usize,
+        const CW: usize,
+    > RecursivePoWRunner<GoldilocksField>
+    for SimpleAlgebraicSponge<GoldilocksField, AW, SW, CW, R, AbsorptionModeOverwrite>
+{
+    type CircuitReflection = CircuitAlgebraicSpongePoW<GoldilocksField, AW, SW, CW, R>;
+}
+
+pub type CircuitGoldilocksPoseidon2PoW =
+    CircuitAlgebraicSpongePoW<GoldilocksField, 8, 12, 4, Poseidon2Goldilocks>;
+
+#[cfg(test)]
+mod test {
+    use std::alloc::Global;
+
+    use super::*;
+    use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::dag::CircuitResolverOpts;
+    use crate::field::U64Representable;
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+
+    type F = GoldilocksField;
+    type Pow = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+    #[test]
+    fn circuit_pow_matches_native() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 80,
+            num_witness_columns: 0,
+            num_constant_columns: 8,
+            max_allowed_constraint_degree: 8,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 18);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(CircuitResolverOpts::new(1 << 20));
+        let cs = &mut owned_cs;
+
+        let worker = Worker::new_with_num_threads(4);
+        let seed: Vec<F> = (0..5).map(|el| F::from_u64_unchecked(el + 1)).collect();
+        let pow_bits = 8;
+        let challenge = Pow::run_from_field_elements(seed.clone(), pow_bits, &worker);
+        // serial search returns the first suitable challenge, so the previous one is invalid
+        assert!(challenge > 0);
+
+        for (challenge, expected) in [(challenge, true), (challenge - 1, false)] {
+            assert_eq!(
+                Pow::verify_from_field_elements(seed.clone(), pow_bits, challenge),
+                expected
+            );
+
+            let seed: Vec<_> = seed.iter().map(|el| Num::allocate(cs, *el)).collect();
+            let challenge: [_; 64] =
+                std::array::from_fn(|idx| Boolean::allocate(cs, (challenge >> idx) & 1 == 1));
+            let is_valid = CircuitGoldilocksPoseidon2PoW::verify_from_field_elements(
+                cs, seed, pow_bits, &challenge,
+            );
+            assert_eq!(is_valid.witness_hook(&*cs)().unwrap(), expected);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
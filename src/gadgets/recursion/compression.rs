@@ -0,0 +1,794 @@
+use super::*;
+
+use crate::algebraic_props::round_function::AbsorptionModeOverwrite;
+use crate::algebraic_props::sponge::GoldilocksPoseidon2Sponge;
+use crate::config::{CSConfig, ProvingCSConfig, SetupCSConfig};
+use crate::cs::cs_builder::*;
+use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+use crate::cs::gates::*;
+use crate::cs::implementations::hints::{DenseVariablesCopyHint, DenseWitnessCopyHint};
+use crate::cs::implementations::polynomial_storage::{SetupBaseStorage, SetupStorage};
+use crate::cs::implementations::pow::PoWRunner;
+use crate::cs::implementations::proof::Proof;
+use crate::cs::implementations::prover::ProofConfig;
+use crate::cs::implementations::reference_cs::CSReferenceImplementation;
+use crate::cs::implementations::setup::FinalizationHintsForProver;
+use crate::cs::implementations::transcript::{GoldilocksPoisedon2Transcript, Transcript};
+use crate::cs::implementations::verifier::{VerificationError, VerificationKey};
+use crate::cs::oracle::merkle_tree::MerkleTreeWithCap;
+use crate::cs::oracle::TreeHasher;
+use crate::cs::traits::circuit::*;
+use crate::cs::traits::gate::GatePlacementStrategy;
+use crate::cs::{CSGeometry, GateConfigurationHolder, LookupParameters, StaticToolboxHolder};
+use crate::dag::CircuitResolverOpts;
+use crate::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::recursion::allocated_proof::AllocatedProof;
+use crate::gadgets::recursion::allocated_vk::AllocatedVerificationKey;
+use crate::gadgets::recursion::circuit_pow::RecursivePoWRunner;
+use crate::gadgets::recursion::recursive_transcript::CircuitAlgebraicSpongeBasedTranscript;
+use crate::gadgets::recursion::recursive_tree_hasher::CircuitGoldilocksPoseidon2Sponge;
+use crate::gadgets::traits::allocatable::CSAllocatable;
+use crate::implementations::poseidon2::Poseidon2Goldilocks;
+use crate::worker::Worker;
+use std::alloc::Global;
+
+// Compression re-proves an existing proof to make it smaller. A circuit that verifies
+// the original proof with the `RecursiveVerifier` is proven with a configuration tuned
+// for the proof size: a high LDE factor (so few queries are needed), a large cap (so
+// Merkle paths are short and FRI folds down to a single element), maximum PoW and a
+// byte oriented hash (Keccak256 or Blake2s) that is cheap for the final verifier.
+//
+// Verification key of the original circuit is placed into the compression circuit as
+// constants, so the verification key of the compression circuit commits to it, and
+// public inputs of the original proof are the public inputs of the compressed one.
+// The original proof must use Poseidon2 over Goldilocks for both commitments and
+// the transcript, as this is what is cheap to verify in the circuit.
+
+type F = GoldilocksField;
+type Ext = GoldilocksExt2;
+
+pub type CompressionInputTreeHasher = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+pub type CompressionInputTranscript = GoldilocksPoisedon2Transcript;
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CompressionConfig {
+    pub proof_config: ProofConfig,
+    pub max_trace_len: usize,
+    pub max_variables: usize,
+}
+
+impl std::default::Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            proof_config: ProofConfig {
+                fri_lde_factor: 64,
+                // equal to the LDE factor, so FRI folds down to a single element
+                merkle_tree_cap_size: 64,
+                fri_folding_schedule: None,
+                security_level: 100,
+                pow_bits: 28,
+                zero_knowledge: false,
+            },
+            max_trace_len: 1 << 20,
+            max_variables: 1 << 26,
+        }
+    }
+}
+
+/// Gates of the compression circuit. They are enough for the recursive verifier
+/// over Poseidon2, whatever gates the original circuit uses
+pub struct CompressionCircuitBuilder;
+
+impl CircuitBuilder<F> for CompressionCircuitBuilder {
+    fn geometry() -> CSGeometry {
+        CSGeometry {
+            num_columns_under_copy_permutation: 132,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 8,
+        }
+    }
+
+    fn lookup_parameters() -> LookupParameters {
+        LookupParameters::NoLookup
+    }
+
+    fn configure_builder<
+        T: CsBuilderImpl<F, T>,
+        GC: GateConfigurationHolder<F>,
+        TB: StaticToolboxHolder,
+    >(
+        builder: CsBuilder<T, F, GC, TB>,
+    ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+        type Poseidon2Gate = Poseidon2FlattenedGate<F, 8, 12, 4, Poseidon2Goldilocks>;
+
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = Poseidon2Gate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder = PublicInputGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+
+        NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns)
+    }
+}
+
+/// Setup of the compression circuit. It only depends on the verification key and the
+/// proof config of the original circuit, so it's created once and used for all the proofs
+pub struct CompressionSetup<H: TreeHasher<F>> {
+    inner_proof_config: ProofConfig,
+    finalization_hint: FinalizationHintsForProver,
+    base_setup: SetupBaseStorage<F, F>,
+    setup: SetupStorage<F, F>,
+    setup_tree: MerkleTreeWithCap<F, H>,
+    vars_hint: DenseVariablesCopyHint,
+    wits_hint: DenseWitnessCopyHint,
+    vk: VerificationKey<F, H>,
+}
+
+impl<H: TreeHasher<F>> CompressionSetup<H> {
+    /// Verification key of the compression circuit
+    pub fn vk(&self) -> &VerificationKey<F, H> {
+        &self.vk
+    }
+}
+
+// Synthesizes the compression circuit, that verifies the proof of the circuit described by `IC`.
+// Witness is only needed for proving, so `proof` may be `None` during the setup
+fn synthesize_compression_circuit<
+    IC: CircuitBuilder<F> + 'static,
+    IPOW: RecursivePoWRunner<F>,
+    CFG: CSConfig,
+>(
+    proof: Option<Proof<F, CompressionInputTreeHasher, Ext>>,
+    vk: &VerificationKey<F, CompressionInputTreeHasher>,
+    inner_proof_config: &ProofConfig,
+    config: &CompressionConfig,
+) -> CSReferenceImplementation<F, F, CFG, impl GateConfigurationHolder<F>, impl StaticToolboxHolder>
+{
+    let builder_impl = CsReferenceImplementationBuilder::<F, F, CFG>::new(
+        CompressionCircuitBuilder::geometry(),
+        config.max_trace_len,
+    );
+    let builder = new_builder::<_, F>(builder_impl);
+    let builder = CompressionCircuitBuilder::configure_builder(builder);
+    let mut cs = builder.build(CircuitResolverOpts::new(config.max_variables));
+
+    let recursive_verifier =
+        CircuitBuilderProxy::<F, IC>::dyn_recursive_verifier_builder::<2, Ext, _>()
+            .create_recursive_verifier(&mut cs);
+
+    let allocated_vk =
+        AllocatedVerificationKey::<F, CircuitGoldilocksPoseidon2Sponge>::allocate_constant(
+            &mut cs,
+            vk.clone(),
+        );
+    let allocated_proof =
+        AllocatedProof::<F, CircuitGoldilocksPoseidon2Sponge, Ext>::allocate_from_witness(
+            &mut cs,
+            proof,
+            &recursive_verifier,
+            &vk.fixed_parameters,
+            inner_proof_config,
+        );
+
+    let (is_valid, public_inputs) = recursive_verifier.verify::<
+        CircuitGoldilocksPoseidon2Sponge,
+        CompressionInputTranscript,
+        CircuitAlgebraicSpongeBasedTranscript<F, 8, 12, 4, Poseidon2Goldilocks>,
+        IPOW,
+    >(
+        &mut cs,
+        (),
+        &allocated_proof,
+        &vk.fixed_parameters,
+        inner_proof_config,
+        &allocated_vk,
+    );
+
+    let boolean_true = Boolean::allocated_constant(&mut cs, true);
+    Boolean::enforce_equal(&mut cs, &is_valid, &boolean_true);
+
+    for input in public_inputs.into_iter() {
+        PublicInputGate::new(input.get_variable()).add_to_cs(&mut cs);
+    }
+
+    cs
+}
+
+/// Creates the setup of the compression circuit for proofs of the circuit described by `IC`,
+/// that are made with `inner_proof_config`. Witness is not evaluated
+pub fn create_compression_setup<
+    IC: CircuitBuilder<F> + 'static,
+    IPOW: RecursivePoWRunner<F>,
+    H: TreeHasher<F>,
+>(
+    worker: &Worker,
+    vk: &VerificationKey<F, CompressionInputTreeHasher>,
+    inner_proof_config: &ProofConfig,
+    config: &CompressionConfig,
+) -> CompressionSetup<H> {
+    let mut cs = synthesize_compression_circuit::<IC, IPOW, SetupCSConfig>(
+        None,
+        vk,
+        inner_proof_config,
+        config,
+    );
+    let (_, finalization_hint) = cs.pad_and_shrink();
+    let cs = cs.into_assembly::<Global>();
+
+    let (base_setup, setup, vk, setup_tree, vars_hint, wits_hint) = cs.get_full_setup::<H>(
+        worker,
+        config.proof_config.fri_lde_factor,
+        config.proof_config.merkle_tree_cap_size,
+    );
+
+    CompressionSetup {
+        inner_proof_config: inner_proof_config.clone(),
+        finalization_hint,
+        base_setup,
+        setup,
+        setup_tree,
+        vars_hint,
+        wits_hint,
+        vk,
+    }
+}
+
+/// Re-proves the proof of the circuit described by `IC` in the compression circuit, using the
+/// setup from `create_compression_setup`. The original proof is checked first, and an error is
+/// returned if it's invalid or was made with another config than the setup
+pub fn prove_compression<
+    IC: CircuitBuilder<F> + 'static,
+    IPOW: RecursivePoWRunner<F>,
+    H: TreeHasher<F>,
+    TR: Transcript<F, CompatibleCap = H::Output>,
+    POW: PoWRunner,
+>(
+    worker: &Worker,
+    compression_setup: &CompressionSetup<H>,
+    proof: Proof<F, CompressionInputTreeHasher, Ext>,
+    vk: &VerificationKey<F, CompressionInputTreeHasher>,
+    config: &CompressionConfig,
+    transcript_params: TR::TransciptParameters,
+) -> Result<Proof<F, H, Ext>, VerificationError> {
+    if proof.proof_config != compression_setup.inner_proof_config {
+        return Err(VerificationError::ProofConfigMismatch);
+    }
+    let verifier = CircuitBuilderProxy::<F, IC>::dyn_verifier_builder::<2, Ext>().create_verifier();
+    verifier.verify_detailed::<CompressionInputTreeHasher, CompressionInputTranscript, IPOW>(
+        (),
+        vk,
+        &proof,
+    )?;
+
+    let mut cs = synthesize_compression_circuit::<IC, IPOW, ProvingCSConfig>(
+        Some(proof),
+        vk,
+        &compression_setup.inner_proof_config,
+        config,
+    );
+    cs.pad_and_shrink_using_hint(&compression_setup.finalization_hint);
+    let mut cs = cs.into_assembly::<Global>();
+
+    let witness_set = cs.take_witness_using_hints(
+        worker,
+        &compression_setup.vars_hint,
+        &compression_setup.wits_hint,
+    );
+
+    Ok(cs.prove_cpu_basic::<2, Ext, TR, H, POW>(
+        worker,
+        witness_set,
+        &compression_setup.base_setup,
+        &compression_setup.setup,
+        &compression_setup.setup_tree,
+        &compression_setup.vk,
+        config.proof_config.clone(),
+        transcript_params,
+    ))
+}
+
+/// Re-proves the proof of the circuit described by `IC` in the compression circuit.
+/// The original proof is checked first, and an error is returned if it's invalid.
+/// Returns the compressed proof and the verification key of the compression circuit,
+/// the latter only depends on `vk`, the config of the original proof and `config`.
+/// Creates the setup every time, so `create_compression_setup` and `prove_compression`
+/// should be used to compress many proofs of the same circuit
+pub fn compress_proof<
+    IC: CircuitBuilder<F> + 'static,
+    IPOW: RecursivePoWRunner<F>,
+    H: TreeHasher<F>,
+    TR: Transcript<F, CompatibleCap = H::Output>,
+    POW: PoWRunner,
+>(
+    worker: &Worker,
+    proof: Proof<F, CompressionInputTreeHasher, Ext>,
+    vk: &VerificationKey<F, CompressionInputTreeHasher>,
+    config: CompressionConfig,
+    transcript_params: TR::TransciptParameters,
+) -> Result<(Proof<F, H, Ext>, VerificationKey<F, H>), VerificationError> {
+    let compression_setup =
+        create_compression_setup::<IC, IPOW, H>(worker, vk, &proof.proof_config, &config);
+    let compressed_proof = prove_compression::<IC, IPOW, H, TR, POW>(
+        worker,
+        &compression_setup,
+        proof,
+        vk,
+        &config,
+        transcript_params,
+    )?;
+
+    Ok((compressed_proof, compression_setup.vk))
+}
+
+/// Verifies the proof produced by `compress_proof` against the verification key of the
+/// compression circuit. Public inputs of the proof are the ones of the original proof
+pub fn verify_compressed_proof<
+    H: TreeHasher<F>,
+    TR: Transcript<F, CompatibleCap = H::Output>,
+    POW: PoWRunner,
+>(
+    transcript_params: TR::TransciptParameters,
+    vk: &VerificationKey<F, H>,
+    proof: &Proof<F, H, Ext>,
+) -> Result<(), VerificationError> {
+    let verifier =
+        CircuitBuilderProxy::<F, CompressionCircuitBuilder>::dyn_verifier_builder::<2, Ext>()
+            .create_verifier();
+
+    verifier.verify_detailed::<H, TR, POW>(transcript_params, vk, proof)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::gates::fma_gate_without_constant::FmaGateInBaseFieldWithoutConstant;
+    use crate::cs::implementations::pow::NoPow;
+    use crate::cs::implementations::transcript::Blake2sTranscript;
+    use crate::cs::traits::cs::ConstraintSystem;
+    use crate::field::{Field, U64Representable};
+
+    struct TestCircuit;
+
+    impl CircuitBuilder<F> for TestCircuit {
+        fn geometry() -> CSGeometry {
+            CSGeometry {
+                num_columns_under_copy_permutation: 8,
+                num_witness_columns: 0,
+                num_constant_columns: 2,
+                max_allowed_constraint_degree: 8,
+            }
+        }
+
+        fn lookup_parameters() -> LookupParameters {
+            LookupParameters::NoLookup
+        }
+
+        fn configure_builder<
+            T: CsBuilderImpl<F, T>,
+            GC: GateConfigurationHolder<F>,
+            TB: StaticToolboxHolder,
+        >(
+            builder: CsBuilder<T, F, GC, TB>,
+        ) -> CsBuilder<T, F, impl GateConfigurationHolder<F>, impl StaticToolboxHolder> {
+            let builder = ConstantsAllocatorGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+            let builder = PublicInputGate::configure_builder(
+                builder,
+                GatePlacementStrategy::UseGeneralPurposeColumns,
+            );
+
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns)
+        }
+    }
+
+    fn prove_test_circuit(
+        worker: &Worker,
+        num_steps: usize,
+        proof_config: ProofConfig,
+    ) -> (
+        Proof<F, CompressionInputTreeHasher, Ext>,
+        VerificationKey<F, CompressionInputTreeHasher>,
+    ) {
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+            TestCircuit::geometry(),
+            num_steps.next_power_of_two(),
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = TestCircuit::configure_builder(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(8 * num_steps));
+
+        let mut previous = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(1));
+        for _ in 0..num_steps {
+            let b = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(2));
+            let c = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(3));
+
+            previous = FmaGateInBaseFieldWithoutConstant::compute_fma(
+                &mut cs,
+                F::TWO,
+                (previous, b),
+                F::MINUS_ONE,
+                c,
+            );
+        }
+        PublicInputGate::new(previous).add_to_cs(&mut cs);
+        cs.allocate_constant(F::from_u64_unchecked(3));
+
+        if proof_config.zero_knowledge {
+            cs.add_blinding_rows(proof_config.num_blinding_rows(num_steps.next_power_of_two()));
+        }
+        cs.pad_and_shrink();
+
+        let cs = cs.into_assembly::<Global>();
+
+        cs.prove_one_shot::<2, Ext, CompressionInputTranscript, CompressionInputTreeHasher, NoPow>(
+            worker,
+            proof_config,
+            (),
+        )
+    }
+
+    // Verifies the proof in the compression circuit, and returns the verification result
+    // together with the satisfiability of the circuit
+    fn verify_recursively(
+        proof: &Proof<F, CompressionInputTreeHasher, Ext>,
+        vk: &VerificationKey<F, CompressionInputTreeHasher>,
+    ) -> (bool, bool) {
+        use crate::gadgets::traits::witnessable::WitnessHookable;
+
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+            CompressionCircuitBuilder::geometry(),
+            1 << 16,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = CompressionCircuitBuilder::configure_builder(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(1 << 22));
+
+        let recursive_verifier =
+            CircuitBuilderProxy::<F, TestCircuit>::dyn_recursive_verifier_builder::<2, Ext, _>()
+                .create_recursive_verifier(&mut cs);
+
+        let allocated_vk =
+            AllocatedVerificationKey::<F, CircuitGoldilocksPoseidon2Sponge>::allocate_constant(
+                &mut cs,
+                vk.clone(),
+            );
+        let allocated_proof =
+            AllocatedProof::<F, CircuitGoldilocksPoseidon2Sponge, Ext>::allocate_from_witness(
+                &mut cs,
+                Some(proof.clone()),
+                &recursive_verifier,
+                &vk.fixed_parameters,
+                &proof.proof_config,
+            );
+
+        let (is_valid, public_inputs) = recursive_verifier.verify::<
+            CircuitGoldilocksPoseidon2Sponge,
+            CompressionInputTranscript,
+            CircuitAlgebraicSpongeBasedTranscript<F, 8, 12, 4, Poseidon2Goldilocks>,
+            NoPow,
+        >(
+            &mut cs,
+            (),
+            &allocated_proof,
+            &vk.fixed_parameters,
+            &proof.proof_config,
+            &allocated_vk,
+        );
+        let is_valid = is_valid.witness_hook(&cs)().unwrap();
+        assert_eq!(public_inputs.len(), proof.public_inputs.len());
+
+        cs.pad_and_shrink();
+        let worker = Worker::new();
+        let mut cs = cs.into_assembly::<Global>();
+
+        (is_valid, cs.check_if_satisfied(&worker))
+    }
+
+    #[test]
+    fn compress_simple_proof() {
+        let worker = Worker::new();
+
+        let inner_proof_config = ProofConfig {
+            fri_lde_factor: 8,
+            merkle_tree_cap_size: 4,
+            pow_bits: 0,
+            ..Default::default()
+        };
+        let (proof, vk) = prove_test_circuit(&worker, 16, inner_proof_config);
+
+        // keep the test fast, while still folding FRI down to a single element
+        let config = CompressionConfig {
+            proof_config: ProofConfig {
+                fri_lde_factor: 8,
+                merkle_tree_cap_size: 8,
+                pow_bits: 4,
+                ..Default::default()
+            },
+            max_trace_len: 1 << 16,
+            max_variables: 1 << 22,
+        };
+
+        let (compressed_proof, compressed_vk) =
+            compress_proof::<
+                TestCircuit,
+                NoPow,
+                blake2::Blake2s256,
+                Blake2sTranscript,
+                blake2::Blake2s256,
+            >(&worker, proof.clone(), &vk, config.clone(), ())
+            .unwrap();
+
+        assert_eq!(compressed_proof.public_inputs, proof.public_inputs);
+        assert_eq!(compressed_proof.final_fri_monomials[0].len(), 1);
+
+        let result = verify_compressed_proof::<
+            blake2::Blake2s256,
+            Blake2sTranscript,
+            blake2::Blake2s256,
+        >((), &compressed_vk, &compressed_proof);
+        assert_eq!(result, Ok(()));
+
+        let mut malformed_proof = compressed_proof;
+        malformed_proof.public_inputs[0].add_assign(&F::ONE);
+        let result = verify_compressed_proof::<
+            blake2::Blake2s256,
+            Blake2sTranscript,
+            blake2::Blake2s256,
+        >((), &compressed_vk, &malformed_proof);
+        assert!(result.is_err());
+
+        // setup is independent of the proof, so the same one is used for other proofs
+        let compression_setup = create_compression_setup::<TestCircuit, NoPow, blake2::Blake2s256>(
+            &worker,
+            &vk,
+            &proof.proof_config,
+            &config,
+        );
+        assert_eq!(compression_setup.vk(), &compressed_vk);
+
+        // invalid original proofs are not compressed
+        let mut malformed_proof = proof.clone();
+        malformed_proof.public_inputs[0].add_assign(&F::ONE);
+        let result = prove_compression::<
+            TestCircuit,
+            NoPow,
+            blake2::Blake2s256,
+            Blake2sTranscript,
+            blake2::Blake2s256,
+        >(
+            &worker,
+            &compression_setup,
+            malformed_proof,
+            &vk,
+            &config,
+            (),
+        );
+        assert!(result.is_err());
+
+        let mut malformed_proof = proof;
+        malformed_proof.proof_config.pow_bits += 1;
+        let result = prove_compression::<
+            TestCircuit,
+            NoPow,
+            blake2::Blake2s256,
+            Blake2sTranscript,
+            blake2::Blake2s256,
+        >(
+            &worker,
+            &compression_setup,
+            malformed_proof,
+            &vk,
+            &config,
+            (),
+        );
+        assert_eq!(result.err(), Some(VerificationError::ProofConfigMismatch));
+    }
+
+    #[test]
+    fn recursive_verifier_with_custom_fri_schedule() {
+        use crate::cs::implementations::prover::{FriScheduleError, MAX_FRI_FOLDING_DEGREE_LOG2};
+        use crate::gadgets::traits::witnessable::WitnessHookable;
+
+        let worker = Worker::new();
+
+        // 120 FMA steps fit into 64 rows, so the first step folds by the largest supported
+        // arity of 32 and the second one down to a single element
+        let proof_config = ProofConfig {
+            fri_lde_factor: 8,
+            merkle_tree_cap_size: 4,
+            fri_folding_schedule: Some(vec![MAX_FRI_FOLDING_DEGREE_LOG2, 1]),
+            pow_bits: 0,
+            ..Default::default()
+        };
+        let (proof, vk) = prove_test_circuit(&worker, 120, proof_config);
+        assert_eq!(vk.fixed_parameters.domain_size, 64);
+        assert_eq!(proof.fri_intermediate_oracles_caps.len(), 1);
+        assert_eq!(proof.final_fri_monomials[0].len(), 1);
+        assert_eq!(
+            proof.queries_per_fri_repetition[0].fri_queries[0]
+                .leaf_elements
+                .len(),
+            2 << MAX_FRI_FOLDING_DEGREE_LOG2
+        );
+
+        let verifier = CircuitBuilderProxy::<F, TestCircuit>::dyn_verifier_builder::<2, Ext>()
+            .create_verifier();
+        let result = verifier
+            .verify_detailed::<CompressionInputTreeHasher, CompressionInputTranscript, NoPow>(
+                (),
+                &vk,
+                &proof,
+            );
+        assert_eq!(result, Ok(()));
+
+        let builder_impl = CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(
+            CompressionCircuitBuilder::geometry(),
+            1 << 16,
+        );
+        let builder = new_builder::<_, F>(builder_impl);
+        let builder = CompressionCircuitBuilder::configure_builder(builder);
+        let mut cs = builder.build(CircuitResolverOpts::new(1 << 22));
+
+        let recursive_verifier =
+            CircuitBuilderProxy::<F, TestCircuit>::dyn_recursive_verifier_builder::<2, Ext, _>()
+                .create_recursive_verifier(&mut cs);
+
+        let allocated_vk =
+            AllocatedVerificationKey::<F, CircuitGoldilocksPoseidon2Sponge>::allocate_constant(
+                &mut cs,
+                vk.clone(),
+            );
+        let allocated_proof =
+            AllocatedProof::<F, CircuitGoldilocksPoseidon2Sponge, Ext>::allocate_from_witness(
+                &mut cs,
+                Some(proof.clone()),
+                &recursive_verifier,
+                &vk.fixed_parameters,
+                &proof.proof_config,
+            );
+
+        let (is_valid, public_inputs) = recursive_verifier.verify::<
+            CircuitGoldilocksPoseidon2Sponge,
+            CompressionInputTranscript,
+            CircuitAlgebraicSpongeBasedTranscript<F, 8, 12, 4, Poseidon2Goldilocks>,
+            NoPow,
+        >(
+            &mut cs,
+            (),
+            &allocated_proof,
+            &vk.fixed_parameters,
+            &proof.proof_config,
+            &allocated_vk,
+        );
+
+        assert!(is_valid.witness_hook(&cs)().unwrap());
+        assert_eq!(public_inputs.len(), proof.public_inputs.len());
+        for (input, expected) in public_inputs.iter().zip(proof.public_inputs.iter()) {
+            assert_eq!(input.witness_hook(&cs)().unwrap(), *expected);
+        }
+
+        let mut malformed_proof = proof;
+        malformed_proof.proof_config.fri_folding_schedule =
+            Some(vec![MAX_FRI_FOLDING_DEGREE_LOG2 + 1]);
+        let result = verifier
+            .verify_detailed::<CompressionInputTreeHasher, CompressionInputTranscript, NoPow>(
+                (),
+                &vk,
+                &malformed_proof,
+            );
+        assert_eq!(
+            result,
+            Err(VerificationError::InvalidFriSchedule(
+                FriScheduleError::InvalidFoldingStep {
+                    step: 0,
+                    interpolation_degree_log2: MAX_FRI_FOLDING_DEGREE_LOG2 + 1,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn recursive_verifier_with_zero_knowledge() {
+        use crate::cs::implementations::prover::SALT_SIZE;
+        use crate::field::ExtensionField;
+
+        let worker = Worker::new();
+
+        let proof_config = ProofConfig {
+            fri_lde_factor: 8,
+            merkle_tree_cap_size: 4,
+            security_level: 20,
+            pow_bits: 0,
+            zero_knowledge: true,
+            ..Default::default()
+        };
+        let (proof, vk) = prove_test_circuit(&worker, 64, proof_config);
+        assert_eq!(proof.proof_config.num_masking_polys(), 1);
+
+        // every leaf of the witness oracle is salted
+        let geometry = TestCircuit::geometry();
+        assert_eq!(
+            proof.queries_per_fri_repetition[0]
+                .witness_query
+                .leaf_elements
+                .len(),
+            geometry.num_columns_under_copy_permutation + geometry.num_witness_columns + SALT_SIZE
+        );
+
+        let verifier = CircuitBuilderProxy::<F, TestCircuit>::dyn_verifier_builder::<2, Ext>()
+            .create_verifier();
+        let result = verifier
+            .verify_detailed::<CompressionInputTreeHasher, CompressionInputTranscript, NoPow>(
+                (),
+                &vk,
+                &proof,
+            );
+        assert_eq!(result, Ok(()));
+
+        let (is_valid, is_satisfied) = verify_recursively(&proof, &vk);
+        assert!(is_valid);
+        assert!(is_satisfied);
+
+        // salt is hashed into the leaf
+        let mut malformed_proof = proof.clone();
+        malformed_proof.queries_per_fri_repetition[0]
+            .witness_query
+            .leaf_elements
+            .last_mut()
+            .unwrap()
+            .add_assign(&F::ONE);
+        let (is_valid, _) = verify_recursively(&malformed_proof, &vk);
+        assert!(!is_valid);
+
+        // masking polynomial is opened last, and it only takes part in the DEEP batch
+        let mut malformed_proof = proof;
+        malformed_proof
+            .values_at_z
+            .last_mut()
+            .unwrap()
+            .add_assign(&ExtensionField::ONE);
+        let (is_valid, _) = verify_recursively(&malformed_proof, &vk);
+        assert!(!is_valid);
+    }
+}
@@ -3,6 +3,7 @@ use super::*;
 pub mod allocated_proof;
 pub mod allocated_vk;
 pub mod circuit_pow;
+pub mod compression;
 pub mod recursive_transcript;
 pub mod recursive_tree_hasher;
 pub mod recursive_verifier;
@@ -48,7 +48,7 @@ pub trait CircuitTranscript<F: SmallField>: Clone + Send + Sync + std::fmt::Debu
         &mut self,
         _cs: &mut CS,
         _num_bytes: usize,
-    ) -> Vec<u8> {
+    ) -> Vec<UInt8<F>> {
         if Self::IS_ALGEBRAIC {
             unimplemented!("Should not be called on algebraic transcripts")
         } else {
@@ -66,6 +66,7 @@ pub trait RecursiveTranscript<F: SmallField>: Transcript<F> {
 
 use crate::gadgets::boolean::Boolean;
 use crate::gadgets::traits::round_function::CircuitRoundFunction;
+use crate::gadgets::u8::UInt8;
 
 pub(crate) struct BoolsBuffer<F: SmallField> {
     pub(crate) available: Vec<Boolean<F>>,
@@ -84,16 +85,27 @@ impl<F: SmallField> BoolsBuffer<F> {
 
             give
         } else {
-            let bits_avaiable = F::CHAR_BITS - self.max_needed;
+            if T::IS_ALGEBRAIC {
+                let bits_avaiable = F::CHAR_BITS - self.max_needed;
 
-            // get 1 field element form transcript
-            let field_el = transcript.get_challenge(cs);
-            let el_bits = field_el.spread_into_bits::<CS, 64>(cs);
-            let mut lsb_iterator = el_bits.iter();
+                // get 1 field element form transcript
+                let field_el = transcript.get_challenge(cs);
+                let el_bits = field_el.spread_into_bits::<CS, 64>(cs);
+                let mut lsb_iterator = el_bits.iter();
 
-            for _ in 0..bits_avaiable {
-                let bit = lsb_iterator.next().unwrap();
-                self.available.push(*bit);
+                for _ in 0..bits_avaiable {
+                    let bit = lsb_iterator.next().unwrap();
+                    self.available.push(*bit);
+                }
+            } else {
+                // same as out of circuit, we assume that bytes are uniform
+                let bytes = transcript.get_challenge_bytes(cs, 8);
+                assert_eq!(bytes.len(), 8);
+                for byte in bytes.into_iter() {
+                    let bits =
+                        Num::from_variable(byte.get_variable()).spread_into_bits::<CS, 8>(cs);
+                    self.available.extend(bits);
+                }
             }
 
             self.get_bits(cs, transcript, num_bits)
@@ -243,3 +255,125 @@ pub type GoldilocksPoisedon2CircuitTranscript =
 impl RecursiveTranscript<GoldilocksField> for GoldilocksPoisedon2Transcript {
     type CircuitReflection = GoldilocksPoisedon2CircuitTranscript;
 }
+
+use crate::cs::implementations::transcript::{Blake2sTranscript, Keccak256Transcript};
+use crate::gadgets::recursion::recursive_tree_hasher::{
+    Blake2sGadget, CircuitByteOrientedHashFunction, Keccak256Gadget,
+};
+
+/// Circuit counterpart of the byte-oriented transcripts. Every time new data is committed,
+/// or challenge bytes are exhausted, the transcript hashes the previous digest together with
+/// pending bytes, and the new digest is used as a source of challenges
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct CircuitByteOrientedTranscript<F: SmallField, H: CircuitByteOrientedHashFunction<F>> {
+    buffer: Vec<UInt8<F>>,
+    last_digest: Option<[UInt8<F>; 32]>,
+    available_challenge_bytes: Vec<UInt8<F>>,
+    _marker: std::marker::PhantomData<H>,
+}
+
+impl<F: SmallField, H: CircuitByteOrientedHashFunction<F>> CircuitByteOrientedTranscript<F, H> {
+    fn reseed<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        let mut to_hash = Vec::with_capacity(32 + self.buffer.len());
+        if let Some(last_digest) = self.last_digest.as_ref() {
+            to_hash.extend_from_slice(&last_digest[..]);
+        }
+        to_hash.append(&mut self.buffer);
+
+        let digest = H::hash(cs, &to_hash);
+        self.last_digest = Some(digest);
+        self.available_challenge_bytes.extend(digest);
+    }
+
+    fn absorb_pending<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) {
+        if self.buffer.is_empty() == false {
+            self.available_challenge_bytes.clear();
+            self.reseed(cs);
+        }
+    }
+}
+
+impl<F: SmallField, H: CircuitByteOrientedHashFunction<F>> CircuitTranscript<F>
+    for CircuitByteOrientedTranscript<F, H>
+{
+    type CircuitCompatibleCap = [UInt8<F>; 32];
+    type TransciptParameters = ();
+
+    const IS_ALGEBRAIC: bool = false;
+
+    fn new<CS: ConstraintSystem<F>>(_cs: &mut CS, _params: Self::TransciptParameters) -> Self {
+        Self {
+            buffer: Vec::with_capacity(64),
+            last_digest: None,
+            available_challenge_bytes: Vec::with_capacity(32),
+            _marker: std::marker::PhantomData,
+        }
+    }
+    fn witness_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        field_els: &[Num<F>],
+    ) {
+        for el in field_els.iter() {
+            let bytes = el.into_canonical_le_bytes(cs);
+            self.buffer.extend(bytes);
+        }
+    }
+    fn witness_merkle_tree_cap<CS: ConstraintSystem<F>>(
+        &mut self,
+        _cs: &mut CS,
+        cap: &[Self::CircuitCompatibleCap],
+    ) {
+        for el in cap.iter() {
+            self.buffer.extend_from_slice(&el[..]);
+        }
+    }
+    fn get_challenge<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS) -> Num<F> {
+        self.absorb_pending(cs);
+
+        if self.available_challenge_bytes.is_empty() {
+            self.reseed(cs);
+        }
+
+        assert!(self.available_challenge_bytes.len() % 8 == 0);
+        // reduction happens naturally when we form a linear combination
+        let input: Vec<_> = self
+            .available_challenge_bytes
+            .drain(..8)
+            .enumerate()
+            .map(|(idx, byte)| {
+                (
+                    byte.get_variable(),
+                    F::from_u64_with_reduction(1u64 << (8 * idx)),
+                )
+            })
+            .collect();
+
+        Num::linear_combination(cs, &input)
+    }
+    fn get_challenge_bytes<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+        num_bytes: usize,
+    ) -> Vec<UInt8<F>> {
+        self.absorb_pending(cs);
+
+        while self.available_challenge_bytes.len() < num_bytes {
+            self.reseed(cs);
+        }
+
+        self.available_challenge_bytes.drain(..num_bytes).collect()
+    }
+}
+
+pub type Blake2sCircuitTranscript<F> = CircuitByteOrientedTranscript<F, Blake2sGadget>;
+pub type Keccak256CircuitTranscript<F> = CircuitByteOrientedTranscript<F, Keccak256Gadget>;
+
+impl<F: SmallField> RecursiveTranscript<F> for Blake2sTranscript {
+    type CircuitReflection = Blake2sCircuitTranscript<F>;
+}
+
+impl<F: SmallField> RecursiveTranscript<F> for Keccak256Transcript {
+    type CircuitReflection = Keccak256CircuitTranscript<F>;
+}
@@ -11,6 +11,7 @@ use crate::gadgets::traits::allocatable::CSAllocatable;
 use crate::gadgets::traits::encodable::CircuitVarLengthEncodable;
 use crate::gadgets::traits::round_function::CircuitRoundFunction;
 use crate::implementations::poseidon2::Poseidon2Goldilocks;
+use derivative::*;
 
 pub trait CircuitTreeHasher<F: SmallField, B: Sized + CSAllocatable<F>>:
     'static + Clone + Send + Sync
@@ -254,3 +255,336 @@ impl RecursiveTreeHasher<GoldilocksField, Num<GoldilocksField>>
 {
     type NonCircuitSimulator = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
 }
+
+use crate::gadgets::u8::UInt8;
+
+/// Byte-oriented hash function gadget with 32 byte digest, that can be used as a tree hasher
+/// or transcript in the recursive verifier
+pub trait CircuitByteOrientedHashFunction<F: SmallField>:
+    'static + Clone + Copy + Send + Sync + std::fmt::Debug
+{
+    fn hash<CS: ConstraintSystem<F>>(cs: &mut CS, input: &[UInt8<F>]) -> [UInt8<F>; 32];
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Blake2sGadget;
+
+impl<F: SmallField> CircuitByteOrientedHashFunction<F> for Blake2sGadget {
+    fn hash<CS: ConstraintSystem<F>>(cs: &mut CS, input: &[UInt8<F>]) -> [UInt8<F>; 32] {
+        crate::gadgets::blake2s::blake2s(cs, input)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Keccak256Gadget;
+
+impl<F: SmallField> CircuitByteOrientedHashFunction<F> for Keccak256Gadget {
+    fn hash<CS: ConstraintSystem<F>>(cs: &mut CS, input: &[UInt8<F>]) -> [UInt8<F>; 32] {
+        crate::gadgets::keccak256::keccak256(cs, input)
+    }
+}
+
+/// Circuit counterpart of the `TreeHasher` implementations over byte-oriented hash functions.
+/// Field elements are absorbed as 8 bytes in little-endian form, same as out of circuit
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct CircuitByteOrientedTreeHasher<F: SmallField, H: CircuitByteOrientedHashFunction<F>> {
+    buffer: Vec<UInt8<F>>,
+    _marker: std::marker::PhantomData<H>,
+}
+
+impl<F: SmallField, H: CircuitByteOrientedHashFunction<F>> CircuitTreeHasher<F, Num<F>>
+    for CircuitByteOrientedTreeHasher<F, H>
+{
+    type CircuitOutput = [UInt8<F>; 32];
+
+    fn new<CS: ConstraintSystem<F>>(_cs: &mut CS) -> Self {
+        Self {
+            buffer: Vec::with_capacity(64),
+            _marker: std::marker::PhantomData,
+        }
+    }
+    fn placeholder_output<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self::CircuitOutput {
+        let zero_u8 = UInt8::zero(cs);
+
+        [zero_u8; 32]
+    }
+    fn accumulate_into_leaf<CS: ConstraintSystem<F>>(&mut self, cs: &mut CS, value: &Num<F>) {
+        let bytes = value.into_canonical_le_bytes(cs);
+        self.buffer.extend(bytes);
+    }
+    fn finalize_into_leaf_hash_and_reset<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: &mut CS,
+    ) -> Self::CircuitOutput {
+        let input = std::mem::take(&mut self.buffer);
+
+        H::hash(cs, &input)
+    }
+    fn hash_into_leaf<'a, S: IntoIterator<Item = &'a Num<F>>, CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        source: S,
+    ) -> Self::CircuitOutput
+    where
+        Num<F>: 'a,
+    {
+        let mut hasher = <Self as CircuitTreeHasher<F, Num<F>>>::new(cs);
+
+        for el in source.into_iter() {
+            <Self as CircuitTreeHasher<F, Num<F>>>::accumulate_into_leaf(&mut hasher, cs, el);
+        }
+
+        <Self as CircuitTreeHasher<F, Num<F>>>::finalize_into_leaf_hash_and_reset(&mut hasher, cs)
+    }
+    fn hash_into_leaf_owned<S: IntoIterator<Item = Num<F>>, CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        source: S,
+    ) -> Self::CircuitOutput {
+        let mut hasher = <Self as CircuitTreeHasher<F, Num<F>>>::new(cs);
+
+        for el in source.into_iter() {
+            <Self as CircuitTreeHasher<F, Num<F>>>::accumulate_into_leaf(&mut hasher, cs, &el);
+        }
+
+        <Self as CircuitTreeHasher<F, Num<F>>>::finalize_into_leaf_hash_and_reset(&mut hasher, cs)
+    }
+    fn swap_nodes<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        should_swap: Boolean<F>,
+        left: &Self::CircuitOutput,
+        right: &Self::CircuitOutput,
+        _depth: usize,
+    ) -> (Self::CircuitOutput, Self::CircuitOutput) {
+        use crate::gadgets::traits::selectable::Selectable;
+
+        let new_left = Selectable::conditionally_select(cs, should_swap, right, left);
+        let new_right = Selectable::conditionally_select(cs, should_swap, left, right);
+
+        (new_left, new_right)
+    }
+    fn hash_into_node<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        left: &Self::CircuitOutput,
+        right: &Self::CircuitOutput,
+        _depth: usize,
+    ) -> Self::CircuitOutput {
+        let mut input = Vec::with_capacity(64);
+        input.extend_from_slice(&left[..]);
+        input.extend_from_slice(&right[..]);
+
+        H::hash(cs, &input)
+    }
+    fn select_cap_node<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        cap_bits: &[Boolean<F>],
+        cap: &[Self::CircuitOutput],
+    ) -> Self::CircuitOutput {
+        use crate::gadgets::recursion::recursive_verifier::binary_select;
+
+        binary_select(cs, cap, cap_bits)
+    }
+    fn compare_output<CS: ConstraintSystem<F>>(
+        cs: &mut CS,
+        a: &Self::CircuitOutput,
+        b: &Self::CircuitOutput,
+    ) -> Boolean<F> {
+        let equalities: [_; 32] = std::array::from_fn(|idx| UInt8::equals(cs, &a[idx], &b[idx]));
+
+        Boolean::multi_and(cs, &equalities)
+    }
+}
+
+pub type CircuitBlake2sTreeHasher<F> = CircuitByteOrientedTreeHasher<F, Blake2sGadget>;
+pub type CircuitKeccak256TreeHasher<F> = CircuitByteOrientedTreeHasher<F, Keccak256Gadget>;
+
+impl<F: SmallField> RecursiveTreeHasher<F, Num<F>> for CircuitBlake2sTreeHasher<F> {
+    type NonCircuitSimulator = blake2::Blake2s256;
+}
+
+impl<F: SmallField> RecursiveTreeHasher<F, Num<F>> for CircuitKeccak256TreeHasher<F> {
+    type NonCircuitSimulator = sha3::Keccak256;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::implementations::transcript::{
+        Blake2sTranscript, Keccak256Transcript, Transcript,
+    };
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::dag::CircuitResolverOpts;
+    use crate::field::{Field, U64Representable};
+    use crate::gadgets::recursion::recursive_transcript::{
+        CircuitByteOrientedTranscript, CircuitTranscript,
+    };
+    use crate::gadgets::tables::*;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::worker::Worker;
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+
+    #[test]
+    fn keccak_hasher_and_transcript_match_native() {
+        hasher_and_transcript_match_native::<Keccak256Gadget, sha3::Keccak256, Keccak256Transcript>(
+        );
+    }
+
+    #[test]
+    fn blake2s_hasher_and_transcript_match_native() {
+        hasher_and_transcript_match_native::<Blake2sGadget, blake2::Blake2s256, Blake2sTranscript>(
+        );
+    }
+
+    // every table has to be used at least once to pad the CS
+    trait AddTables {
+        fn add_tables<CS: ConstraintSystem<F>>(cs: &mut CS);
+    }
+
+    impl AddTables for Keccak256Gadget {
+        fn add_tables<CS: ConstraintSystem<F>>(cs: &mut CS) {
+            let table = create_xor8_table();
+            cs.add_lookup_table::<Xor8Table, 3>(table);
+            let table = create_and8_table();
+            cs.add_lookup_table::<And8Table, 3>(table);
+            let table = create_byte_split_table::<F, 1>();
+            cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+            let table = create_byte_split_table::<F, 2>();
+            cs.add_lookup_table::<ByteSplitTable<2>, 3>(table);
+            let table = create_byte_split_table::<F, 3>();
+            cs.add_lookup_table::<ByteSplitTable<3>, 3>(table);
+            let table = create_byte_split_table::<F, 4>();
+            cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+        }
+    }
+
+    impl AddTables for Blake2sGadget {
+        fn add_tables<CS: ConstraintSystem<F>>(cs: &mut CS) {
+            let table = create_xor8_table();
+            cs.add_lookup_table::<Xor8Table, 3>(table);
+            let table = create_byte_split_table::<F, 1>();
+            cs.add_lookup_table::<ByteSplitTable<1>, 3>(table);
+            let table = create_byte_split_table::<F, 4>();
+            cs.add_lookup_table::<ByteSplitTable<4>, 3>(table);
+            let table = create_byte_split_table::<F, 7>();
+            cs.add_lookup_table::<ByteSplitTable<7>, 3>(table);
+        }
+    }
+
+    fn hasher_and_transcript_match_native<
+        H: CircuitByteOrientedHashFunction<F> + AddTables,
+        N: TreeHasher<F, Output = [u8; 32]>,
+        T: Transcript<F, CompatibleCap = [u8; 32], TransciptParameters = ()>,
+    >() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 40,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 20);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = U32TriAddCarryAsChunkGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(CircuitResolverOpts::new(1 << 22));
+
+        H::add_tables(&mut owned_cs);
+
+        let cs = &mut owned_cs;
+
+        let values = vec![
+            F::ZERO,
+            F::ONE,
+            F::MINUS_ONE,
+            F::from_u64_unchecked(0xffff_ffff),
+            F::from_u64_unchecked(0x1234_5678_9abc_def0),
+        ];
+        let nums: Vec<_> = values.iter().map(|el| Num::allocate(cs, *el)).collect();
+
+        let leaf = N::hash_into_leaf(&values);
+        let circuit_leaf = CircuitByteOrientedTreeHasher::<F, H>::hash_into_leaf(cs, &nums);
+        assert_eq!((circuit_leaf.witness_hook(&*cs))().unwrap(), leaf);
+
+        let node = N::hash_into_node(&leaf, &leaf, 0);
+        let circuit_node = CircuitByteOrientedTreeHasher::<F, H>::hash_into_node(
+            cs,
+            &circuit_leaf,
+            &circuit_leaf,
+            0,
+        );
+        assert_eq!((circuit_node.witness_hook(&*cs))().unwrap(), node);
+
+        let mut transcript = T::new(());
+        let mut circuit_transcript = CircuitByteOrientedTranscript::<F, H>::new(cs, ());
+
+        Transcript::<F>::witness_field_elements(&mut transcript, &values);
+        Transcript::<F>::witness_merkle_tree_cap(&mut transcript, &[leaf, node]);
+        circuit_transcript.witness_field_elements(cs, &nums);
+        circuit_transcript.witness_merkle_tree_cap(cs, &[circuit_leaf, circuit_node]);
+
+        // enough to exhaust the first digest
+        for _ in 0..6 {
+            let challenge = Transcript::<F>::get_challenge(&mut transcript);
+            let circuit_challenge = circuit_transcript.get_challenge(cs);
+            assert_eq!((circuit_challenge.witness_hook(&*cs))().unwrap(), challenge);
+        }
+
+        let bytes = Transcript::<F>::get_challenge_bytes(&mut transcript, 8);
+        let circuit_bytes = circuit_transcript.get_challenge_bytes(cs, 8);
+        let circuit_bytes: Vec<u8> = circuit_bytes
+            .iter()
+            .map(|el| (el.witness_hook(&*cs))().unwrap())
+            .collect();
+        assert_eq!(circuit_bytes, bytes);
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
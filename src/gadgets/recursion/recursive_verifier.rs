@@ -30,22 +30,25 @@ use crate::gadgets::recursion::recursive_tree_hasher::*;
 use crate::gadgets::recursion::recursive_verifier_builder::TypeErasedGateEvaluationRecursiveVerificationFunction;
 use std::alloc::Global;
 
-use crate::gadgets::recursion::circuit_pow::RecursivePoWRunner;
+use crate::gadgets::recursion::circuit_pow::{
+    pow_challenge_into_limbs, CircuitPowRunner, RecursivePoWRunner,
+};
 
 fn materialize_powers_serial<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 >(
     cs: &mut CS,
-    base: NumExtAsFieldWrapper<F, EXT, CS>,
+    base: NumExtAsFieldWrapper<F, EXT, CS, N>,
     size: usize,
-) -> Vec<NumExtAsFieldWrapper<F, EXT, CS>> {
+) -> Vec<NumExtAsFieldWrapper<F, EXT, CS, N>> {
     if size == 0 {
         return Vec::new();
     }
     let mut storage = Vec::with_capacity(size);
-    let mut current = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+    let mut current = NumExtAsFieldWrapper::<F, EXT, CS, N>::one(cs);
     storage.push(current);
     for idx in 1..size {
         if idx == 1 {
@@ -61,10 +64,11 @@ fn materialize_powers_serial<
 
 pub struct RecursiveVerifierProxy<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
     GC: GateConfigurationHolder<F>,
     T: StaticToolboxHolder,
+    const N: usize = 2,
 > {
     // when we init we get the following from VK
     pub parameters: CSGeometry,
@@ -73,11 +77,11 @@ pub struct RecursiveVerifierProxy<
     pub gates_configuration: GC,
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>>,
 
     pub(crate) total_num_variables_for_specialized_columns: usize,
     pub(crate) total_num_witnesses_for_specialized_columns: usize,
@@ -88,13 +92,14 @@ pub struct RecursiveVerifierProxy<
 
 impl<
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
         GC: GateConfigurationHolder<F>,
         T: StaticToolboxHolder,
-    > RecursiveVerifierProxy<F, EXT, CS, GC, T>
+    > RecursiveVerifierProxy<F, EXT, CS, GC, T, N>
 {
-    pub fn into_verifier(self) -> RecursiveVerifier<F, EXT, CS> {
+    pub fn into_verifier(self) -> RecursiveVerifier<F, EXT, CS, N> {
         let Self {
             parameters,
             lookup_parameters,
@@ -125,7 +130,7 @@ impl<
             placement_strategies.insert(*gate_type_id, placement_strategy);
         }
 
-        RecursiveVerifier::<F, EXT, CS> {
+        RecursiveVerifier::<F, EXT, CS, N> {
             parameters,
             lookup_parameters,
             gate_type_ids_for_specialized_columns,
@@ -142,8 +147,9 @@ impl<
 
 pub struct RecursiveVerifier<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
+    const N: usize = 2,
 > {
     // when we init we get the following from VK
     pub parameters: CSGeometry,
@@ -151,11 +157,11 @@ pub struct RecursiveVerifier<
 
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>>,
 
     pub(crate) total_num_variables_for_specialized_columns: usize,
     pub(crate) total_num_witnesses_for_specialized_columns: usize,
@@ -164,12 +170,16 @@ pub struct RecursiveVerifier<
     pub(crate) placement_strategies: HashMap<TypeId, GatePlacementStrategy>,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F> + 'static>
-    RecursiveVerifier<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F> + 'static,
+    > RecursiveVerifier<F, EXT, CS, N>
 {
     #[inline]
     fn num_sublookup_arguments(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_sublookup_arguments(
+        SizeCalculator::<F, N, EXT>::num_sublookup_arguments(
             &self.parameters,
             &self.lookup_parameters,
         )
@@ -177,7 +187,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
     #[inline]
     pub fn num_multipicities_polys(&self, total_tables_len: usize, domain_size: u64) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_multipicities_polys(
+        SizeCalculator::<F, N, EXT>::num_multipicities_polys(
             &self.lookup_parameters,
             total_tables_len,
             domain_size,
@@ -185,21 +195,21 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn num_variable_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_variable_polys(
+        SizeCalculator::<F, N, EXT>::num_variable_polys(
             &self.parameters,
             self.total_num_variables_for_specialized_columns,
         )
     }
 
     pub fn num_witness_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_witness_polys(
+        SizeCalculator::<F, N, EXT>::num_witness_polys(
             &self.parameters,
             self.total_num_witnesses_for_specialized_columns,
         )
     }
 
     pub fn num_constant_polys(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_constant_polys(
+        SizeCalculator::<F, N, EXT>::num_constant_polys(
             &self.parameters,
             vk_fixed_params,
             self.total_num_constants_for_specialized_columns,
@@ -207,7 +217,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn quotient_degree(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_degree(vk_fixed_params)
+        SizeCalculator::<F, N, EXT>::quotient_degree(vk_fixed_params)
     }
 
     pub fn num_copy_permutation_polys(&self) -> usize {
@@ -215,11 +225,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn num_lookup_table_setup_polys(&self) -> usize {
-        SizeCalculator::<F, 2, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
+        SizeCalculator::<F, N, EXT>::num_lookup_table_setup_polys(&self.lookup_parameters)
     }
 
     pub fn witness_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::witness_leaf_size(
+        SizeCalculator::<F, N, EXT>::witness_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -229,7 +239,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn stage_2_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::stage_2_leaf_size(
+        SizeCalculator::<F, N, EXT>::stage_2_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -238,11 +248,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
     }
 
     pub fn quotient_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::quotient_leaf_size(vk_fixed_params)
+        SizeCalculator::<F, N, EXT>::quotient_leaf_size(vk_fixed_params)
     }
 
     pub fn setup_leaf_size(&self, vk_fixed_params: &VerificationKeyCircuitGeometry) -> usize {
-        SizeCalculator::<F, 2, EXT>::setup_leaf_size(
+        SizeCalculator::<F, N, EXT>::setup_leaf_size(
             &self.parameters,
             &self.lookup_parameters,
             vk_fixed_params,
@@ -261,13 +271,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             _num_queries,                 // num queries
             interpolation_log2s_schedule, // folding schedule
             _final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
 
         interpolation_log2s_schedule
     }
@@ -282,13 +291,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             _num_queries,                 // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
 
         let mut expected_degree = fixed_parameters.domain_size;
 
@@ -311,18 +319,21 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             num_queries,                   // num queries
             _interpolation_log2s_schedule, // folding schedule
             _final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
 
         num_queries
     }
 
-    pub fn num_poly_values_at_z(&self, fixed_parameters: &VerificationKeyCircuitGeometry) -> usize {
+    pub fn num_poly_values_at_z(
+        &self,
+        fixed_parameters: &VerificationKeyCircuitGeometry,
+        proof_config: &ProofConfig,
+    ) -> usize {
         let num_lookup_subarguments = self.num_sublookup_arguments();
         let num_multiplicities_polys = self.num_multipicities_polys(
             fixed_parameters.total_tables_len as usize,
@@ -355,7 +366,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             1 + // z_poly
             num_intermediate_partial_product_relations + // partial products in copy-permutation
             expected_lookup_polys_total + // everything from lookup
-            quotient_degree; // chunks of quotient poly
+            quotient_degree + // chunks of quotient poly
+            proof_config.num_masking_polys(); // committed with the quotient
 
         num_poly_values_at_z
     }
@@ -396,14 +408,13 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         &self,
         cs: &mut CS,
         transcript_params: <TR as Transcript<F>>::TransciptParameters,
-        proof: &AllocatedProof<F, H, EXT>,
+        proof: &AllocatedProof<F, H, EXT, N>,
         fixed_parameters: &VerificationKeyCircuitGeometry,
         proof_config: &ProofConfig,
         vk: &AllocatedVerificationKey<F, H>,
     ) -> (Boolean<F>, Vec<Num<F>>) {
         assert_eq!(self.parameters, fixed_parameters.parameters);
         assert_eq!(self.lookup_parameters, fixed_parameters.lookup_parameters);
-        assert!(proof_config.fri_folding_schedule.is_none());
         assert_eq!(fixed_parameters.cap_size, proof_config.merkle_tree_cap_size);
         assert_eq!(fixed_parameters.fri_lde_factor, proof_config.fri_lde_factor,);
         assert_eq!(fixed_parameters.cap_size, vk.setup_merkle_tree_cap.len());
@@ -414,8 +425,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
         let zero_base = NumAsFieldWrapper::<F, CS>::zero(cs);
 
-        let zero_ext = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
-        let one_ext = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+        let zero_ext = NumExtAsFieldWrapper::<F, EXT, CS, N>::zero(cs);
+        let one_ext = NumExtAsFieldWrapper::<F, EXT, CS, N>::one(cs);
 
         let multiplicative_generator =
             NumAsFieldWrapper::constant(F::multiplicative_generator(), cs);
@@ -457,20 +468,20 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
         // draw challenges for stage 2
 
-        let beta = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let beta = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(beta);
+        let beta = transcript.get_multiple_challenges_fixed::<_, N>(cs);
+        let beta = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(beta);
 
-        let gamma = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let gamma = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(gamma);
+        let gamma = transcript.get_multiple_challenges_fixed::<_, N>(cs);
+        let gamma = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(gamma);
 
         let (lookup_beta, lookup_gamma) = if self.lookup_parameters != LookupParameters::NoLookup {
             // lookup argument related parts
-            let lookup_beta = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
+            let lookup_beta = transcript.get_multiple_challenges_fixed::<_, N>(cs);
             let lookup_beta =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(lookup_beta);
-            let lookup_gamma = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(lookup_beta);
+            let lookup_gamma = transcript.get_multiple_challenges_fixed::<_, N>(cs);
             let lookup_gamma =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(lookup_gamma);
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(lookup_gamma);
 
             (lookup_beta, lookup_gamma)
         } else {
@@ -481,8 +492,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         transcript.witness_merkle_tree_cap(cs, &proof.stage_2_oracle_cap);
 
         // draw challenges for quotient
-        let alpha = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let alpha = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(alpha);
+        let alpha = transcript.get_multiple_challenges_fixed::<_, N>(cs);
+        let alpha = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(alpha);
 
         // two extra relations per lookup subargument - for A and B polys
         let num_lookup_subarguments = self.num_sublookup_arguments();
@@ -560,23 +571,23 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         transcript.witness_merkle_tree_cap(cs, &proof.quotient_oracle_cap);
 
         // get z
-        let z = transcript.get_multiple_challenges_fixed::<_, 2>(cs);
-        let z = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(z);
+        let z = transcript.get_multiple_challenges_fixed::<_, N>(cs);
+        let z = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(z);
 
         let all_values_at_z: Vec<_> = proof
             .values_at_z
             .iter()
-            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(*el))
+            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(*el))
             .collect();
         let all_values_at_z_omega: Vec<_> = proof
             .values_at_z_omega
             .iter()
-            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(*el))
+            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(*el))
             .collect();
         let all_values_at_0: Vec<_> = proof
             .values_at_0
             .iter()
-            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base(*el))
+            .map(|el| NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(*el))
             .collect();
 
         // commit claimed values at z, and form our poly storage
@@ -612,7 +623,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             }
         }
 
-        let num_poly_values_at_z = self.num_poly_values_at_z(fixed_parameters);
+        let num_poly_values_at_z = self.num_poly_values_at_z(fixed_parameters, proof_config);
         let num_poly_values_at_z_omega = self.num_poly_values_at_z_omega();
         let num_poly_values_at_zero = self.num_poly_values_at_zero(fixed_parameters);
 
@@ -679,15 +690,16 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 .take(num_lookup_table_setup_polys)
                 .copied()
                 .collect();
-            // quotient
-            let quotient_chunks: Vec<_> = source_it.copied().collect();
+            // quotient, masking polynomials are only used for DEEP
+            let quotient_chunks: Vec<_> = (&mut source_it).take(quotient_degree).copied().collect();
 
             assert_eq!(quotient_chunks.len(), quotient_degree);
+            assert_eq!(source_it.len(), proof_config.num_masking_polys());
 
             let mut source_it = all_values_at_z_omega.iter();
             let copy_permutation_z_at_z_omega = *source_it.next().unwrap();
 
-            let mut t_accumulator = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+            let mut t_accumulator = NumExtAsFieldWrapper::<F, EXT, CS, N>::zero(cs);
             // precompute selectors at z
 
             let mut selectors_buffer = HashMap::new();
@@ -723,12 +735,12 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let multiplicities_encoding_polys_at_0 =
                     &all_values_at_0[num_lookup_subarguments..];
 
-                let mut witness_subsum = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+                let mut witness_subsum = NumExtAsFieldWrapper::<F, EXT, CS, N>::zero(cs);
                 for a in lookup_witness_encoding_polys_polys_at_0.iter() {
                     witness_subsum.add_assign(a, cs);
                 }
 
-                let mut multiplicities_subsum = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+                let mut multiplicities_subsum = NumExtAsFieldWrapper::<F, EXT, CS, N>::zero(cs);
                 for b in multiplicities_encoding_polys_at_0.iter() {
                     multiplicities_subsum.add_assign(b, cs);
                 }
@@ -736,11 +748,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let witness_subsum = witness_subsum.into_num_coeffs_in_base();
                 let multiplicities_subsum = multiplicities_subsum.into_num_coeffs_in_base();
 
-                let c0_is_valid = Num::equals(cs, &witness_subsum[0], &multiplicities_subsum[0]);
-                let c1_is_valid = Num::equals(cs, &witness_subsum[1], &multiplicities_subsum[1]);
-
-                validity_flags.push(c0_is_valid);
-                validity_flags.push(c1_is_valid);
+                for (a, b) in witness_subsum.iter().zip(multiplicities_subsum.iter()) {
+                    let is_valid = Num::equals(cs, a, b);
+                    validity_flags.push(is_valid);
+                }
 
                 // lookup argument related parts
                 match self.lookup_parameters {
@@ -777,7 +788,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         let capacity = column_elements_per_subargument
                             + ((fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+                        let mut tmp = NumExtAsFieldWrapper::<F, EXT, CS, N>::one(cs);
                         powers_of_gamma.push(tmp);
                         for _idx in 1..capacity {
                             if _idx == 1 {
@@ -795,7 +806,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -838,7 +849,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                                NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -852,7 +863,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(&selector, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -882,7 +893,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(multiplicities_poly, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -918,7 +929,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         let capacity = column_elements_per_subargument
                             + ((fixed_parameters.table_ids_column_idxes.len() == 1) as usize);
                         let mut powers_of_gamma = Vec::with_capacity(capacity);
-                        let mut tmp = NumExtAsFieldWrapper::<F, EXT, CS>::one(cs);
+                        let mut tmp = NumExtAsFieldWrapper::<F, EXT, CS, N>::one(cs);
                         powers_of_gamma.push(tmp);
                         for _idx in 1..capacity {
                             if _idx == 1 {
@@ -936,7 +947,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         for (gamma, column) in
                             powers_of_gamma.iter().zip(lookup_tables_columns.iter())
                         {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                 &mut lookup_table_columns_aggregated,
                                 gamma,
                                 column,
@@ -982,7 +993,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                                 .iter()
                                 .zip(witness_columns.iter().chain(table_id.iter()))
                             {
-                                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                                NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                     &mut contribution,
                                     gamma,
                                     column,
@@ -996,7 +1007,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(&one_ext, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -1026,7 +1037,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             contribution.sub_assign(multiplicities_poly, cs);
 
                             // mul by power of challenge and accumulate
-                            NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                                 &mut t_accumulator,
                                 &alpha,
                                 &contribution,
@@ -1266,7 +1277,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 contribution.mul_assign(&unnormalized_l1_inverse_at_z, cs);
 
                 // mul by power of challenge and accumulate
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                     &mut t_accumulator,
                     &alpha,
                     &contribution,
@@ -1319,7 +1330,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 contribution.sub_assign(&rhs, cs);
 
                 // mul by power of challenge and accumulate
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                     &mut t_accumulator,
                     alpha,
                     &contribution,
@@ -1335,7 +1346,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let mut t_from_chunks = zero_ext;
             let mut pow = one_ext;
             for el in quotient_chunks.into_iter() {
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
                     &mut t_from_chunks,
                     &el,
                     &pow,
@@ -1354,16 +1365,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let t_accumulator = t_accumulator.into_num_coeffs_in_base();
             let t_from_chunks = t_from_chunks.into_num_coeffs_in_base();
 
-            let c0_is_valid = Num::equals(cs, &t_accumulator[0], &t_from_chunks[0]);
-            let c1_is_valid = Num::equals(cs, &t_accumulator[1], &t_from_chunks[1]);
-
-            validity_flags.push(c0_is_valid);
-            validity_flags.push(c1_is_valid);
+            for (a, b) in t_accumulator.iter().zip(t_from_chunks.iter()) {
+                let is_valid = Num::equals(cs, a, b);
+                validity_flags.push(is_valid);
+            }
         }
 
         // get challenges
-        let c0 = transcript.get_challenge(cs);
-        let c1 = transcript.get_challenge(cs);
+        let challenge = transcript.get_multiple_challenges_fixed::<_, N>(cs);
 
         let expected_lookup_polys_total = if self.lookup_parameters.lookup_is_allowed() {
             num_lookup_subarguments + // lookup witness encoding polys
@@ -1379,7 +1388,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             1 + // z_poly
             num_intermediate_partial_product_relations + // partial products in copy-permutation
             expected_lookup_polys_total + // everything from lookup
-            quotient_degree; // chunks of quotient poly
+            quotient_degree + // chunks of quotient poly
+            proof_config.num_masking_polys(); // committed with the quotient
 
         let mut total_num_challenges = 0;
         total_num_challenges += num_poly_values_at_z;
@@ -1393,22 +1403,29 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         assert_eq!(all_values_at_z_omega.len(), 1);
         assert_eq!(all_values_at_0.len(), total_num_lookup_argument_terms);
 
-        let challenge = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, c1]);
+        let challenge = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(challenge);
         let challenges_for_fri_quotiening =
-            materialize_powers_serial::<F, EXT, CS>(cs, challenge, total_num_challenges);
+            materialize_powers_serial::<F, N, EXT, CS>(cs, challenge, total_num_challenges);
 
         let (
             new_pow_bits,                 // updated POW bits if needed
             num_queries,                  // num queries
             interpolation_log2s_schedule, // folding schedule
             final_expected_degree,
-        ) = crate::cs::implementations::prover::compute_fri_schedule(
-            proof_config.security_level as u32,
-            proof_config.merkle_tree_cap_size,
-            proof_config.pow_bits,
+        ) = crate::cs::implementations::prover::compute_fri_schedule_for_config(
+            proof_config,
             fixed_parameters.fri_lde_factor.trailing_zeros(),
             fixed_parameters.domain_size.trailing_zeros(),
-        );
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+        // default schedule is not validated, and may be empty for very small domains
+        crate::cs::implementations::prover::validate_fri_schedule(
+            &interpolation_log2s_schedule,
+            fixed_parameters.cap_size,
+            fixed_parameters.fri_lde_factor.trailing_zeros(),
+            fixed_parameters.domain_size.trailing_zeros(),
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
 
         let mut expected_degree = fixed_parameters.domain_size;
 
@@ -1423,12 +1440,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
             let reduction_degree_log_2 = interpolation_log2s_schedule[0];
 
-            let c0 = transcript.get_challenge(cs);
-            let c1 = transcript.get_challenge(cs);
+            let challenge = transcript.get_multiple_challenges_fixed::<_, N>(cs);
 
             let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
             let as_extension =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, c1]);
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(challenge);
             challenge_powers.push(as_extension);
 
             let mut current = as_extension;
@@ -1457,12 +1473,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
             // get challenge
             let reduction_degree_log_2 = *interpolation_degree_log2;
-            let c0 = transcript.get_challenge(cs);
-            let c1 = transcript.get_challenge(cs);
+            let challenge = transcript.get_multiple_challenges_fixed::<_, N>(cs);
 
             let mut challenge_powers = Vec::with_capacity(reduction_degree_log_2);
             let as_extension =
-                NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, c1]);
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(challenge);
             challenge_powers.push(as_extension);
 
             let mut current = as_extension;
@@ -1477,17 +1492,15 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         }
 
         assert_eq!(final_expected_degree, expected_degree as usize);
-        assert_eq!(
-            proof.final_fri_monomials[0].len(),
-            proof.final_fri_monomials[1].len()
-        );
-        assert_eq!(expected_degree as usize, proof.final_fri_monomials[0].len());
-        assert_eq!(expected_degree as usize, proof.final_fri_monomials[1].len());
+        for monomials in proof.final_fri_monomials.iter() {
+            assert_eq!(expected_degree as usize, monomials.len());
+        }
         assert!(proof.final_fri_monomials[0].len() > 0);
 
         // witness monomial coeffs
-        transcript.witness_field_elements(cs, &proof.final_fri_monomials[0]);
-        transcript.witness_field_elements(cs, &proof.final_fri_monomials[1]);
+        for monomials in proof.final_fri_monomials.iter() {
+            transcript.witness_field_elements(cs, monomials);
+        }
 
         if new_pow_bits != 0 {
             log!("Doing PoW verification for {} bits", new_pow_bits);
@@ -1498,9 +1511,18 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             if num_challenges % F::CHAR_BITS != 0 {
                 num_challenges += 1;
             }
-            let _challenges: Vec<_> = transcript.get_multiple_challenges(cs, num_challenges);
+            let challenges: Vec<_> = transcript.get_multiple_challenges(cs, num_challenges);
+
+            let pow_is_valid = POW::CircuitReflection::verify_from_field_elements(
+                cs,
+                challenges,
+                new_pow_bits,
+                &proof.pow_challenge,
+            );
+            validity_flags.push(pow_is_valid);
 
-            todo!()
+            let [low, high] = pow_challenge_into_limbs(cs, &proof.pow_challenge);
+            transcript.witness_field_elements(cs, &[low, high]);
         }
 
         let max_needed_bits = (fixed_parameters.domain_size
@@ -1531,25 +1553,15 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
         let omega_cs_constant = NumAsFieldWrapper::constant(omega, cs);
 
         // we also want to precompute "steps" for different interpolation degrees
-        // e.g. if we interpolate 8 elements,
-        // then those will be ordered as bitreverses of [0..=7], namely
-        // [0, 4, 2, 6, 1, 5, 3, 7]
-
-        // so we want to have exactly half of it, because separation by 4
-        // is exactly -1, so we need [1, sqrt4(1), sqrt8(1), sqrt4(1)*sqrt8(1)]
-
-        let mut interpolation_steps = vec![F::ONE; 4]; // max size
-
-        for idx in [1, 3].into_iter() {
-            interpolation_steps[idx].mul_assign(&precomputed_powers_inversed[2]);
-        }
-        for idx in [2, 3].into_iter() {
-            interpolation_steps[idx].mul_assign(&precomputed_powers_inversed[3]);
-        }
-
-        assert_eq!(interpolation_steps[0], F::ONE);
-        assert_eq!(interpolation_steps[1].pow_u64(4), F::ONE);
-        assert_eq!(interpolation_steps[2].pow_u64(8), F::ONE);
+        let max_interpolation_degree_log2 = interpolation_log2s_schedule
+            .iter()
+            .copied()
+            .max()
+            .expect("schedule is validated");
+        let interpolation_steps = crate::cs::implementations::fri::precompute_interpolation_steps(
+            max_interpolation_degree_log2,
+            &precomputed_powers_inversed,
+        );
 
         let precomputed_powers: Vec<_> = precomputed_powers
             .into_iter()
@@ -1568,10 +1580,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
         let base_oracle_depth = fixed_parameters.base_oracles_depth();
 
-        let witness_leaf_size = self.witness_leaf_size(fixed_parameters);
+        // salt is at the end of every leaf, and is only hashed
+        let salt_size = proof_config.salt_size();
+        let witness_leaf_size = self.witness_leaf_size(fixed_parameters) + salt_size;
 
-        let stage_2_leaf_size = self.stage_2_leaf_size(fixed_parameters);
-        let quotient_leaf_size = self.quotient_leaf_size(fixed_parameters);
+        let stage_2_leaf_size = self.stage_2_leaf_size(fixed_parameters) + salt_size;
+        let quotient_leaf_size = self.quotient_leaf_size(fixed_parameters)
+            + proof_config.num_masking_polys() * N
+            + salt_size;
 
         let setup_leaf_size = self.setup_leaf_size(fixed_parameters);
 
@@ -1691,11 +1707,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let mut challenge_offset = 0;
 
             let z_polys_offset = 0;
-            let intermediate_polys_offset = 2;
+            let intermediate_polys_offset = N;
             let lookup_witness_encoding_polys_offset =
-                intermediate_polys_offset + num_intermediate_partial_product_relations * 2;
+                intermediate_polys_offset + num_intermediate_partial_product_relations * N;
             let lookup_multiplicities_encoding_polys_offset =
-                lookup_witness_encoding_polys_offset + num_lookup_subarguments * 2;
+                lookup_witness_encoding_polys_offset + num_lookup_subarguments * N;
             let copy_permutation_polys_offset = 0;
             let constants_offset = 0 + num_copy_permutation_polys;
             let lookup_tables_values_offset = 0 + num_copy_permutation_polys + num_constant_polys;
@@ -1711,19 +1727,19 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let cast_from_base = move |el: &[Num<F>]| {
                     el.iter()
                         .map(|el| {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([
-                                *el, zero_num,
-                            ])
+                            let mut coeffs = [zero_num; N];
+                            coeffs[0] = *el;
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(coeffs)
                         })
                         .collect::<Vec<_>>()
                 };
 
                 let cast_from_extension = move |el: &[Num<F>]| {
-                    assert_eq!(el.len() % 2, 0);
+                    assert_eq!(el.len() % N, 0);
 
-                    el.array_chunks::<2>()
-                        .map(|[c0, c1]| {
-                            NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([*c0, *c1])
+                    el.array_chunks::<N>()
+                        .map(|coeffs| {
+                            NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(*coeffs)
                         })
                         .collect::<Vec<_>>()
                 };
@@ -1765,8 +1781,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         ..lookup_multiplicities_encoding_polys_offset],
                 ));
                 sources.extend(cast_from_extension(
-                    &queries.stage_2_query.leaf_elements
-                        [lookup_multiplicities_encoding_polys_offset..],
+                    &queries.stage_2_query.leaf_elements[lookup_multiplicities_encoding_polys_offset
+                        ..(stage_2_leaf_size - salt_size)],
                 ));
                 // lookup setup
                 if self.lookup_parameters.lookup_is_allowed() {
@@ -1776,8 +1792,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             ..(lookup_tables_values_offset + num_lookup_setups)],
                     ));
                 }
-                // quotient
-                sources.extend(cast_from_extension(&queries.quotient_query.leaf_elements));
+                // quotient and masking polynomials
+                sources.extend(cast_from_extension(
+                    &queries.quotient_query.leaf_elements[..(quotient_leaf_size - salt_size)],
+                ));
 
                 let values_at_z = &all_values_at_z;
                 assert_eq!(sources.len(), values_at_z.len());
@@ -1826,7 +1844,8 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                     // multiplicities encoding
                     sources.extend(cast_from_extension(
                         &queries.stage_2_query.leaf_elements
-                            [lookup_multiplicities_encoding_polys_offset..],
+                            [lookup_multiplicities_encoding_polys_offset
+                                ..(stage_2_leaf_size - salt_size)],
                     ));
 
                     let values_at_0 = &all_values_at_0;
@@ -1852,15 +1871,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let mut sources = Vec::with_capacity(set.len());
                 let mut values = Vec::with_capacity(set.len());
                 for (column, expected_value) in set {
-                    let c0 = queries.witness_query.leaf_elements[*column];
-                    let el =
-                        NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([c0, zero_num]);
+                    let mut coeffs = [zero_num; N];
+                    coeffs[0] = queries.witness_query.leaf_elements[*column];
+                    let el = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(coeffs);
                     sources.push(el);
 
-                    let value = NumExtAsFieldWrapper::<F, EXT, CS>::from_coeffs_in_base([
-                        *expected_value,
-                        zero_base,
-                    ]);
+                    let mut coeffs = [zero_base; N];
+                    coeffs[0] = *expected_value;
+                    let value = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_coeffs_in_base(coeffs);
                     values.push(value);
                 }
                 let num_challenges_required = sources.len();
@@ -1868,9 +1886,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
                 // log!("Making quotiening at {} for public inputs", open_at);
 
-                let open_at = NumAsFieldWrapper::constant(*open_at, cs);
-                let open_at =
-                    NumExtAsFieldWrapper::<F, EXT, CS>::from_coeffs_in_base([open_at, zero_base]);
+                let mut coeffs = [zero_base; N];
+                coeffs[0] = NumAsFieldWrapper::constant(*open_at, cs);
+                let open_at = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_coeffs_in_base(coeffs);
 
                 quotening_operation(
                     cs,
@@ -1904,26 +1922,20 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                 let subidx_in_leaf = &subidx[..*interpolation_degree_log2];
                 let tree_idx = &subidx[*interpolation_degree_log2..];
 
-                assert_eq!(fri_query.leaf_elements.len(), interpolation_degree * 2);
+                assert_eq!(fri_query.leaf_elements.len(), interpolation_degree * N);
 
-                let [c0, c1] = current_folded_value.into_num_coeffs_in_base();
+                // leaf is split into columns, one per coefficient of the extension
+                let leaf_columns: Vec<_> = fri_query
+                    .leaf_elements
+                    .chunks_exact(interpolation_degree)
+                    .collect();
 
-                let c0_from_leaf = binary_select(
-                    cs,
-                    &fri_query.leaf_elements[..interpolation_degree],
-                    subidx_in_leaf,
-                );
-                let c1_from_leaf = binary_select(
-                    cs,
-                    &fri_query.leaf_elements[interpolation_degree..],
-                    subidx_in_leaf,
-                );
-
-                let c0_is_valid = Num::equals(cs, &c0, &c0_from_leaf);
-                let c1_is_valid = Num::equals(cs, &c1, &c1_from_leaf);
-
-                validity_flags.push(c0_is_valid);
-                validity_flags.push(c1_is_valid);
+                let coeffs = current_folded_value.into_num_coeffs_in_base();
+                for (coeff, column) in coeffs.iter().zip(leaf_columns.iter()) {
+                    let coeff_from_leaf = binary_select(cs, column, subidx_in_leaf);
+                    let is_valid = Num::equals(cs, coeff, &coeff_from_leaf);
+                    validity_flags.push(is_valid);
+                }
 
                 // verify query itself
                 let cap = if idx == 0 {
@@ -1947,12 +1959,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
                 // interpolate
                 let mut elements_to_interpolate = Vec::with_capacity(interpolation_degree);
-                for (c0, c1) in fri_query.leaf_elements[..interpolation_degree]
-                    .iter()
-                    .zip(fri_query.leaf_elements[interpolation_degree..].iter())
-                {
+                for i in 0..interpolation_degree {
+                    let coeffs = std::array::from_fn(|j| leaf_columns[j][i]);
                     let as_ext =
-                        NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([*c0, *c1]);
+                        NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(coeffs);
                     elements_to_interpolate.push(as_ext);
                 }
 
@@ -1976,7 +1986,7 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                         let coset_inverse = NumAsFieldWrapper::constant(coset_inverse, cs);
                         pow.mul_assign(&coset_inverse, cs);
 
-                        NumExtAsFieldWrapper::<F, EXT, CS>::mul_by_base_and_accumulate_into(
+                        NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_by_base_and_accumulate_into(
                             &mut result,
                             &pow,
                             &diff,
@@ -2008,17 +2018,14 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
             let mut result_from_monomial = zero_ext;
             // horner rule
-            for (c0, c1) in proof.final_fri_monomials[0]
-                .iter()
-                .zip(proof.final_fri_monomials[1].iter())
-                .rev()
-            {
-                let coeff = NumExtAsFieldWrapper::<F, EXT, CS>::from_num_coeffs_in_base([*c0, *c1]);
+            for i in (0..proof.final_fri_monomials[0].len()).rev() {
+                let coeffs = std::array::from_fn(|j| proof.final_fri_monomials[j][i]);
+                let coeff = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_num_coeffs_in_base(coeffs);
 
                 // result_from_monomial = result_from_monomial * z + coeff
 
                 let mut tmp = coeff;
-                NumExtAsFieldWrapper::<F, EXT, CS>::mul_by_base_and_accumulate_into(
+                NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_by_base_and_accumulate_into(
                     &mut tmp,
                     &domain_element_for_interpolation,
                     &result_from_monomial,
@@ -2034,11 +2041,10 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
             let result_from_monomial = result_from_monomial.into_num_coeffs_in_base();
             let current_folded_value = current_folded_value.into_num_coeffs_in_base();
 
-            let c0_is_valid = Num::equals(cs, &result_from_monomial[0], &current_folded_value[0]);
-            let c1_is_valid = Num::equals(cs, &result_from_monomial[1], &current_folded_value[1]);
-
-            validity_flags.push(c0_is_valid);
-            validity_flags.push(c1_is_valid);
+            for (a, b) in result_from_monomial.iter().zip(current_folded_value.iter()) {
+                let is_valid = Num::equals(cs, a, b);
+                validity_flags.push(is_valid);
+            }
         }
 
         let is_valid = Boolean::multi_and(cs, &validity_flags);
@@ -2049,16 +2055,17 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 
 fn quotening_operation<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    const N: usize,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
 >(
     cs: &mut CS,
-    dst: &mut NumExtAsFieldWrapper<F, EXT, CS>,
-    polynomial_values: &Vec<NumExtAsFieldWrapper<F, EXT, CS>>,
-    values_at: &Vec<NumExtAsFieldWrapper<F, EXT, CS>>,
+    dst: &mut NumExtAsFieldWrapper<F, EXT, CS, N>,
+    polynomial_values: &Vec<NumExtAsFieldWrapper<F, EXT, CS, N>>,
+    values_at: &Vec<NumExtAsFieldWrapper<F, EXT, CS, N>>,
     domain_element: NumAsFieldWrapper<F, CS>,
-    at: NumExtAsFieldWrapper<F, EXT, CS>,
-    challenges: &[NumExtAsFieldWrapper<F, EXT, CS>],
+    at: NumExtAsFieldWrapper<F, EXT, CS, N>,
+    challenges: &[NumExtAsFieldWrapper<F, EXT, CS, N>],
 ) {
     // we precompute challenges outside to avoid any manual extension ops here
     assert_eq!(polynomial_values.len(), values_at.len());
@@ -2066,12 +2073,13 @@ fn quotening_operation<
 
     let zero_base = NumAsFieldWrapper::zero(cs);
 
-    let mut denom =
-        NumExtAsFieldWrapper::<F, EXT, CS>::from_coeffs_in_base([domain_element, zero_base]);
+    let mut coeffs = [zero_base; N];
+    coeffs[0] = domain_element;
+    let mut denom = NumExtAsFieldWrapper::<F, EXT, CS, N>::from_coeffs_in_base(coeffs);
     denom.sub_assign(&at, cs);
     denom = denom.inverse(cs);
 
-    let mut acc = NumExtAsFieldWrapper::<F, EXT, CS>::zero(cs);
+    let mut acc = NumExtAsFieldWrapper::<F, EXT, CS, N>::zero(cs);
 
     for ((poly_value, value_at), challenge) in polynomial_values
         .iter()
@@ -2082,14 +2090,16 @@ fn quotening_operation<
         let mut tmp = *poly_value;
         tmp.sub_assign(value_at, cs);
 
-        NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(&mut acc, &tmp, challenge, cs);
+        NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(
+            &mut acc, &tmp, challenge, cs,
+        );
 
         // let mut as_ext = *challenge;
         // as_ext.mul_assign(&tmp, cs);
         // acc.add_assign(&as_ext, cs);
     }
 
-    NumExtAsFieldWrapper::<F, EXT, CS>::mul_and_accumulate_into(dst, &acc, &denom, cs);
+    NumExtAsFieldWrapper::<F, EXT, CS, N>::mul_and_accumulate_into(dst, &acc, &denom, cs);
 
     // acc.mul_assign(&denom, cs);
     // dst.add_assign(&acc, cs);
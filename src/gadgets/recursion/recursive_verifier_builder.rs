@@ -31,8 +31,9 @@ use std::{any::TypeId, collections::HashMap};
 #[derivative(Debug)]
 pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
+    const N: usize = 2,
 > {
     pub(crate) debug_name: String,
     pub(crate) evaluator_type_id: TypeId,
@@ -48,9 +49,9 @@ pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverSpecializedColumns<
                     F,
-                    NumExtAsFieldWrapper<F, EXT, CS>,
-                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
-                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                    NumExtAsFieldWrapper<F, EXT, CS, N>,
+                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS, N>>,
+                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, EXT, CS, N>>,
                 >
                 + 'static
                 + Send
@@ -62,9 +63,9 @@ pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
         Box<
             dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                     F,
-                    NumExtAsFieldWrapper<F, EXT, CS>,
-                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
-                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                    NumExtAsFieldWrapper<F, EXT, CS, N>,
+                    VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS, N>>,
+                    VerifierRelationDestination<F, NumExtAsFieldWrapper<F, EXT, CS, N>>,
                 >
                 + 'static
                 + Send
@@ -73,8 +74,12 @@ pub(crate) struct TypeErasedGateEvaluationRecursiveVerificationFunction<
     >,
 }
 
-impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<F> + 'static>
-    TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>
+impl<
+        F: SmallField,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
+        CS: ConstraintSystem<F> + 'static,
+    > TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>
 {
     pub fn from_evaluator<E: GateConstraintEvaluator<F>>(
         cs: &mut CS,
@@ -145,11 +150,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             as Box<
                                 dyn GenericDynamicEvaluatorOverSpecializedColumns<
                                         F,
-                                        NumExtAsFieldWrapper<F, EXT, CS>,
-                                        VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                                        NumExtAsFieldWrapper<F, EXT, CS, N>,
+                                        VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS, N>>,
                                         VerifierRelationDestination<
                                             F,
-                                            NumExtAsFieldWrapper<F, EXT, CS>,
+                                            NumExtAsFieldWrapper<F, EXT, CS, N>,
                                         >,
                                     >
                                     + 'static
@@ -175,11 +180,11 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
                             as Box<
                                 dyn GenericDynamicEvaluatorOverGeneralPurposeColumns<
                                         F,
-                                        NumExtAsFieldWrapper<F, EXT, CS>,
-                                        VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS>>,
+                                        NumExtAsFieldWrapper<F, EXT, CS, N>,
+                                        VerifierPolyStorage<F, NumExtAsFieldWrapper<F, EXT, CS, N>>,
                                         VerifierRelationDestination<
                                             F,
-                                            NumExtAsFieldWrapper<F, EXT, CS>,
+                                            NumExtAsFieldWrapper<F, EXT, CS, N>,
                                         >,
                                     >
                                     + 'static
@@ -226,8 +231,9 @@ impl<F: SmallField, EXT: FieldExtension<2, BaseField = F>, CS: ConstraintSystem<
 pub struct CsRecursiveVerifierBuilder<
     'a,
     F: SmallField,
-    EXT: FieldExtension<2, BaseField = F>,
+    EXT: FieldExtension<N, BaseField = F>,
     CS: ConstraintSystem<F> + 'static,
+    const N: usize = 2,
 > {
     pub(crate) cs: &'a mut CS,
 
@@ -236,11 +242,11 @@ pub struct CsRecursiveVerifierBuilder<
 
     pub(crate) gate_type_ids_for_specialized_columns: Vec<TypeId>,
     pub(crate) evaluators_over_specialized_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>>,
     pub(crate) offsets_for_specialized_evaluators: Vec<(PerChunkOffset, PerChunkOffset, usize)>,
 
     pub(crate) evaluators_over_general_purpose_columns:
-        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS>>,
+        Vec<TypeErasedGateEvaluationRecursiveVerificationFunction<F, EXT, CS, N>>,
     pub(crate) general_purpose_evaluators_comparison_functions:
         HashMap<TypeId, Vec<(GateBatchEvaluationComparisonFunction, usize)>>,
 
@@ -252,9 +258,10 @@ pub struct CsRecursiveVerifierBuilder<
 impl<
         'a,
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
-    > CsRecursiveVerifierBuilder<'a, F, EXT, CS>
+    > CsRecursiveVerifierBuilder<'a, F, EXT, CS, N>
 {
     pub fn new_from_parameters(cs: &'a mut CS, parameters: CSGeometry) -> Self {
         Self {
@@ -282,13 +289,14 @@ use super::recursive_verifier::*;
 impl<
         'a,
         F: SmallField,
-        EXT: FieldExtension<2, BaseField = F>,
+        const N: usize,
+        EXT: FieldExtension<N, BaseField = F>,
         CS: ConstraintSystem<F> + 'static,
-    > CsBuilderImpl<F, CsRecursiveVerifierBuilder<'a, F, EXT, CS>>
-    for CsRecursiveVerifierBuilder<'a, F, EXT, CS>
+    > CsBuilderImpl<F, CsRecursiveVerifierBuilder<'a, F, EXT, CS, N>>
+    for CsRecursiveVerifierBuilder<'a, F, EXT, CS, N>
 {
     type Final<GC: GateConfigurationHolder<F>, TB: StaticToolboxHolder> =
-        RecursiveVerifier<F, EXT, CS>;
+        RecursiveVerifier<F, EXT, CS, N>;
 
     type BuildParams<'b> = ();
 
@@ -638,9 +646,9 @@ impl<
         builder: CsBuilder<Self, F, GC, TB>,
         _params: ARG,
     ) -> Self::Final<GC, TB> {
-        let this: CsRecursiveVerifierBuilder<F, EXT, CS> = builder.implementation;
+        let this: CsRecursiveVerifierBuilder<F, EXT, CS, N> = builder.implementation;
 
-        let new = RecursiveVerifierProxy::<F, EXT, CS, GC, TB> {
+        let new = RecursiveVerifierProxy::<F, EXT, CS, GC, TB, N> {
             parameters: this.parameters,
             lookup_parameters: this.lookup_parameters,
 
@@ -513,7 +513,7 @@ mod test {
 
         let now = std::time::Instant::now();
 
-        let proof = owned_cs.prove_cpu_basic::<GoldilocksExt2, TR, T, NoPow>(
+        let proof = owned_cs.prove_cpu_basic::<2, GoldilocksExt2, TR, T, NoPow>(
             &worker,
             witness_set,
             &base_setup,
@@ -0,0 +1,53 @@
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+
+pub trait CircuitEq<F: SmallField>: Sized {
+    /// Returns `true` if `a` and `b` are equal
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F>;
+
+    /// Enforces that `a` and `b` are equal. Default implementation goes through `equals`,
+    /// types made of variables should enforce equality of those directly, as it's cheaper
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        let equal = Self::equals(cs, a, b);
+        let boolean_true = Boolean::allocated_constant(cs, true);
+        Boolean::enforce_equal(cs, &equal, &boolean_true);
+    }
+}
+
+impl<F: SmallField> CircuitEq<F> for () {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, _a: &Self, _b: &Self) -> Boolean<F> {
+        Boolean::allocated_constant(cs, true)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(_cs: &mut CS, _a: &Self, _b: &Self) {}
+}
+
+impl<F: SmallField, T: Sized> CircuitEq<F> for std::marker::PhantomData<T> {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, _a: &Self, _b: &Self) -> Boolean<F> {
+        Boolean::allocated_constant(cs, true)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(_cs: &mut CS, _a: &Self, _b: &Self) {}
+}
+
+impl<F: SmallField, T: CircuitEq<F>, const N: usize> CircuitEq<F> for [T; N] {
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        if N == 0 {
+            return Boolean::allocated_constant(cs, true);
+        }
+
+        let mut equalities = Vec::with_capacity(N);
+        for (a, b) in a.iter().zip(b.iter()) {
+            equalities.push(T::equals(cs, a, b));
+        }
+
+        Boolean::multi_and(cs, &equalities)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        for (a, b) in a.iter().zip(b.iter()) {
+            T::enforce_equal(cs, a, b);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::cs::traits::cs::ConstraintSystem;
+use crate::field::SmallField;
+use crate::gadgets::boolean::Boolean;
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::selectable::Selectable;
+
+/// Total order over the values of the type. Unsigned integers are ordered by value, field
+/// elements by their canonical representation, and arrays and derived structs lexicographically
+/// (first element or field is the most significant one), same as `Ord` of Rust does
+pub trait CircuitOrd<F: SmallField>: CircuitEq<F> + Selectable<F> {
+    /// Returns `true` if `a < b`
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F>;
+
+    /// Returns `true` if `a <= b`
+    fn less_than_or_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::less_than(cs, b, a).negated(cs)
+    }
+
+    /// Returns the smallest of `a` and `b`, or `a` if those are equal
+    fn min<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Self {
+        let b_is_smaller = Self::less_than(cs, b, a);
+        Self::conditionally_select(cs, b_is_smaller, b, a)
+    }
+
+    /// Returns the largest of `a` and `b`, or `b` if those are equal
+    fn max<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Self {
+        let b_is_smaller = Self::less_than(cs, b, a);
+        Self::conditionally_select(cs, b_is_smaller, a, b)
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for () {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, _a: &Self, _b: &Self) -> Boolean<F> {
+        Boolean::allocated_constant(cs, false)
+    }
+}
+
+impl<F: SmallField, T: Sized> CircuitOrd<F> for std::marker::PhantomData<T> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, _a: &Self, _b: &Self) -> Boolean<F> {
+        Boolean::allocated_constant(cs, false)
+    }
+}
+
+impl<F: SmallField, T: CircuitOrd<F>, const N: usize> CircuitOrd<F> for [T; N] {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        if N == 0 {
+            return Boolean::allocated_constant(cs, false);
+        }
+
+        // from the least significant element: a < b if it's smaller in the current
+        // element, or the current elements are equal and the rest is smaller
+        let mut result = T::less_than(cs, &a[N - 1], &b[N - 1]);
+        for (a, b) in a[..N - 1].iter().zip(b[..N - 1].iter()).rev() {
+            let less = T::less_than(cs, a, b);
+            let equal = T::equals(cs, a, b);
+            let rest_is_less = equal.and(cs, result);
+            result = less.or(cs, rest_is_less);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::DevCSConfig;
+    use crate::cs::cs_builder::new_builder;
+    use crate::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+    use crate::cs::gates::*;
+    use crate::cs::traits::gate::GatePlacementStrategy;
+    use crate::cs::CSGeometry;
+    use crate::dag::CircuitResolverOpts;
+    use crate::field::goldilocks::GoldilocksField;
+    use crate::field::{Field, U64Representable};
+    use crate::gadgets::num::Num;
+    use crate::gadgets::tables::*;
+    use crate::gadgets::traits::allocatable::CSAllocatable;
+    use crate::gadgets::traits::witnessable::WitnessHookable;
+    use crate::gadgets::u16::UInt16;
+    use crate::gadgets::u160::UInt160;
+    use crate::gadgets::u256::UInt256;
+    use crate::gadgets::u32::UInt32;
+    use crate::gadgets::u8::UInt8;
+    use crate::serde_utils::BigArraySerde;
+    use crate::worker::Worker;
+    use cs_derive::*;
+    use derivative::Derivative;
+    use ethereum_types::{Address, U256};
+    use std::alloc::Global;
+
+    type F = GoldilocksField;
+
+    #[derive(Derivative, CSAllocatable, CSSelectable, CSCircuitEq, CSCircuitOrd)]
+    #[derivative(Clone, Copy, Debug)]
+    struct Key<F: SmallField> {
+        pub high: UInt32<F>,
+        pub low: [UInt8<F>; 2],
+    }
+
+    fn check<T: CircuitOrd<F>, CS: ConstraintSystem<F>>(cs: &mut CS, a: &T, b: &T, less: bool) {
+        let eval = |cs: &mut CS, el: Boolean<F>| (el.witness_hook(&*cs))().unwrap();
+
+        let lt = T::less_than(cs, a, b);
+        assert_eq!(eval(cs, lt), less);
+        let gt = T::less_than(cs, b, a);
+        let equal = T::equals(cs, a, b);
+        assert_eq!(eval(cs, equal), !less && !eval(cs, gt));
+        let le = T::less_than_or_equal(cs, a, b);
+        assert_eq!(eval(cs, le), less || eval(cs, equal));
+
+        // min and max agree with the comparison
+        let min = T::min(cs, a, b);
+        let expected_min = if less { a } else { b };
+        let min_is_correct = T::equals(cs, &min, expected_min);
+        assert!(eval(cs, min_is_correct));
+        let max = T::max(cs, a, b);
+        let expected_max = if less { b } else { a };
+        let max_is_correct = T::equals(cs, &max, expected_max);
+        assert!(eval(cs, max_is_correct));
+        T::enforce_equal(cs, &max, expected_max);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let geometry = CSGeometry {
+            num_columns_under_copy_permutation: 40,
+            num_witness_columns: 0,
+            num_constant_columns: 4,
+            max_allowed_constraint_degree: 4,
+        };
+
+        let builder_impl =
+            CsReferenceImplementationBuilder::<F, F, DevCSConfig>::new(geometry, 1 << 16);
+        let builder = new_builder::<_, F>(builder_impl);
+
+        let builder = builder.allow_lookup(
+            crate::cs::LookupParameters::UseSpecializedColumnsWithTableIdAsConstant {
+                width: 3,
+                num_repetitions: 5,
+                share_table_id: true,
+            },
+        );
+        let builder = ConstantsAllocatorGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = BooleanConstraintGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ReductionGate::<F, 4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<32>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<16>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = UIntXAddGate::<8>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = SelectionGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ParallelSelectionGate::<4>::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+        );
+        let builder = ZeroCheckGate::configure_builder(
+            builder,
+            GatePlacementStrategy::UseGeneralPurposeColumns,
+            false,
+        );
+        let builder =
+            NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+
+        let mut owned_cs = builder.build(CircuitResolverOpts::new(1 << 18));
+
+        let table = create_xor8_table();
+        owned_cs.add_lookup_table::<Xor8Table, 3>(table);
+
+        let cs = &mut owned_cs;
+
+        for (a, b) in [(3u8, 200u8), (200, 3), (7, 7), (0, 255)] {
+            let (x, y) = (UInt8::allocate(cs, a), UInt8::allocate(cs, b));
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [(300u16, 65535u16), (65535, 300), (1, 1)] {
+            let (x, y) = (UInt16::allocate(cs, a), UInt16::allocate(cs, b));
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [(1u32 << 31, u32::MAX), (u32::MAX, 1 << 31), (42, 42)] {
+            let (x, y) = (UInt32::allocate(cs, a), UInt32::allocate(cs, b));
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [
+            (Address::from_low_u64_be(5), Address::repeat_byte(1)),
+            (Address::repeat_byte(1), Address::from_low_u64_be(5)),
+        ] {
+            let (x, y) = (UInt160::allocate(cs, a), UInt160::allocate(cs, b));
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [
+            (U256::from(5u64), U256::MAX),
+            (U256::MAX - U256::one(), U256::from(5u64) << 200),
+            (U256::MAX, U256::MAX),
+        ] {
+            let (x, y) = (UInt256::allocate(cs, a), UInt256::allocate(cs, b));
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [
+            (F::ONE, F::MINUS_ONE),
+            (F::MINUS_ONE, F::from_u64_unchecked(1 << 32)),
+            (
+                F::from_u64_unchecked(0xffff_ffff),
+                F::from_u64_unchecked(1 << 32),
+            ),
+            (F::ZERO, F::ZERO),
+        ] {
+            let (x, y) = (Num::allocate(cs, a), Num::allocate(cs, b));
+            check(cs, &x, &y, a.as_u64_reduced() < b.as_u64_reduced());
+        }
+        for (a, b) in [(false, true), (true, false), (true, true)] {
+            let (x, y) = (Boolean::allocate(cs, a), Boolean::allocate(cs, b));
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [([1u32, 5], [2u32, 0]), ([2, 0], [2, 1]), ([2, 1], [2, 1])] {
+            let x = <[UInt32<F>; 2]>::allocate(cs, a);
+            let y = <[UInt32<F>; 2]>::allocate(cs, b);
+            check(cs, &x, &y, a < b);
+        }
+        for (a, b) in [
+            ((1u32, [9u8, 9]), (2u32, [0u8, 0])),
+            ((2, [0, 5]), (2, [1, 0])),
+            ((2, [1, 3]), (2, [1, 2])),
+            ((2, [1, 3]), (2, [1, 3])),
+        ] {
+            let x = Key::allocate(
+                cs,
+                KeyWitness {
+                    high: a.0,
+                    low: a.1,
+                },
+            );
+            let y = Key::allocate(
+                cs,
+                KeyWitness {
+                    high: b.0,
+                    low: b.1,
+                },
+            );
+            check(cs, &x, &y, a < b);
+        }
+
+        drop(cs);
+        owned_cs.pad_and_shrink();
+        let mut owned_cs = owned_cs.into_assembly::<Global>();
+        let worker = Worker::new_with_num_threads(8);
+        assert!(owned_cs.check_if_satisfied(&worker));
+    }
+}
@@ -3,6 +3,8 @@ use super::*;
 pub mod allocatable;
 pub mod auxiliary;
 pub mod castable;
+pub mod circuit_eq;
+pub mod circuit_ord;
 pub mod configuration;
 pub mod encodable;
 pub mod round_function;
@@ -29,6 +29,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt16<F> {
         0u16
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         let var = cs.alloc_variable_without_value();
@@ -36,6 +37,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt16<F> {
         Self::from_variable_checked(cs, var)
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let var = Self::allocate_checked(cs, witness);
@@ -43,6 +45,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt16<F> {
         var
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         Self::allocated_constant(cs, witness)
@@ -57,6 +60,7 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt16<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -193,6 +197,7 @@ impl<F: SmallField> UInt16<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: u16) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 16);
@@ -205,11 +210,13 @@ impl<F: SmallField> UInt16<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocated_constant(cs, 0)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_checked<CS: ConstraintSystem<F>>(cs: &mut CS, witness: u16) -> Self {
         let result_var =
@@ -220,6 +227,7 @@ impl<F: SmallField> UInt16<F> {
         result
     }
 
+    #[track_caller]
     #[inline]
     #[must_use]
     pub fn from_variable_checked<CS: ConstraintSystem<F>>(cs: &mut CS, variable: Variable) -> Self {
@@ -570,3 +578,30 @@ impl<F: SmallField> CircuitVarLengthEncodable<F> for UInt16<F> {
         dst.push(self.variable);
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for UInt16<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    #[inline]
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Num::enforce_equal(
+            cs,
+            &Num::from_variable(a.variable),
+            &Num::from_variable(b.variable),
+        )
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for UInt16<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, b);
+
+        borrow
+    }
+}
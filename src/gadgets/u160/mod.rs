@@ -49,6 +49,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt160<F> {
         Address::zero()
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         let vars = cs.alloc_multiple_variables_without_values::<5>();
@@ -58,17 +59,32 @@ impl<F: SmallField> CSAllocatable<F> for UInt160<F> {
         Self { inner: as_u32 }
     }
 
+    #[track_caller]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let chunks = decompose_address_as_u32x5(witness);
-        let chunks = chunks.map(|el| UInt32::allocate_checked(cs, el));
-        Self { inner: chunks }
+        // allocate in a loop rather than a closure, so call sites are the ones of the caller
+        let mut inner = [std::mem::MaybeUninit::uninit(); 5];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocate_checked(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let chunks = decompose_address_as_u32x5(witness);
-        let chunks = chunks.map(|el| UInt32::allocate_constant(cs, el));
-        Self { inner: chunks }
+        let mut inner = [std::mem::MaybeUninit::uninit(); 5];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocate_constant(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 }
 
@@ -82,6 +98,7 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt160<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -100,15 +117,23 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt160<F> {
 }
 
 impl<F: SmallField> UInt160<F> {
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: Address) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 32);
 
         let chunks = decompose_address_as_u32x5(constant);
-        let chunks = chunks.map(|el| UInt32::allocated_constant(cs, el));
-        Self { inner: chunks }
+        let mut inner = [std::mem::MaybeUninit::uninit(); 5];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocated_constant(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocated_constant(cs, Address::zero())
@@ -264,3 +289,31 @@ impl<F: SmallField> CSPlaceholder<F> for UInt160<F> {
         Self::zero(cs)
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for UInt160<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        for (a, b) in a.inner.iter().zip(b.inner.iter()) {
+            CircuitEq::enforce_equal(cs, a, b);
+        }
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for UInt160<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        // there is no arithmetic over 160 bits, so subtract limb by limb
+        let mut borrow = Boolean::allocated_constant(cs, false);
+        for (a, b) in a.inner.iter().zip(b.inner.iter()) {
+            (_, borrow) = a.overflowing_sub_with_borrow_in(cs, *b, borrow);
+        }
+
+        borrow
+    }
+}
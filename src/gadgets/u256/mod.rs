@@ -51,6 +51,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt256<F> {
         U256::zero()
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         let vars = cs.alloc_multiple_variables_without_values::<8>();
@@ -60,16 +61,31 @@ impl<F: SmallField> CSAllocatable<F> for UInt256<F> {
         Self { inner: as_u32 }
     }
 
+    #[track_caller]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let chunks = decompose_u256_as_u32x8(witness);
-        let chunks = chunks.map(|el| UInt32::allocate_checked(cs, el));
-        Self { inner: chunks }
+        // allocate in a loop rather than a closure, so call sites are the ones of the caller
+        let mut inner = [std::mem::MaybeUninit::uninit(); 8];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocate_checked(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 
+    #[track_caller]
     fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let chunks = decompose_u256_as_u32x8(witness);
-        let chunks = chunks.map(|el| UInt32::allocate_constant(cs, el));
-        Self { inner: chunks }
+        let mut inner = [std::mem::MaybeUninit::uninit(); 8];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocate_constant(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 }
 
@@ -84,6 +100,7 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt256<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -117,15 +134,23 @@ impl<F: SmallField> Selectable<F> for UInt256<F> {
 }
 
 impl<F: SmallField> UInt256<F> {
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: U256) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 32);
 
         let chunks = decompose_u256_as_u32x8(constant);
-        let chunks = chunks.map(|el| UInt32::allocated_constant(cs, el));
-        Self { inner: chunks }
+        let mut inner = [std::mem::MaybeUninit::uninit(); 8];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocated_constant(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_from_closure_and_dependencies<
         CS: ConstraintSystem<F>,
@@ -157,6 +182,7 @@ impl<F: SmallField> UInt256<F> {
         Self { inner: chunks }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocated_constant(cs, U256::zero())
@@ -273,6 +299,7 @@ impl<F: SmallField> UInt256<F> {
         Boolean::multi_and(cs, &equals)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn from_le_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 32]) -> Self {
         let mut inner = [std::mem::MaybeUninit::uninit(); 8];
@@ -285,6 +312,7 @@ impl<F: SmallField> UInt256<F> {
         Self { inner }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn from_be_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 32]) -> Self {
         let mut inner = [std::mem::MaybeUninit::uninit(); 8];
@@ -422,3 +450,27 @@ impl<F: SmallField> CSPlaceholder<F> for UInt256<F> {
         Self::zero(cs)
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for UInt256<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        for (a, b) in a.inner.iter().zip(b.inner.iter()) {
+            CircuitEq::enforce_equal(cs, a, b);
+        }
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for UInt256<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, b);
+
+        borrow
+    }
+}
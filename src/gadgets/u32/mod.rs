@@ -38,6 +38,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt32<F> {
         0u32
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         let var = cs.alloc_variable_without_value();
@@ -45,6 +46,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt32<F> {
         Self::from_variable_checked(cs, var)
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let var = Self::allocate_checked(cs, witness);
@@ -52,6 +54,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt32<F> {
         var
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         Self::allocated_constant(cs, witness)
@@ -66,6 +69,7 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt32<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -202,6 +206,7 @@ impl<F: SmallField> UInt32<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: u32) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 32);
@@ -214,11 +219,13 @@ impl<F: SmallField> UInt32<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocated_constant(cs, 0)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_checked<CS: ConstraintSystem<F>>(cs: &mut CS, witness: u32) -> Self {
         let result_var =
@@ -229,6 +236,7 @@ impl<F: SmallField> UInt32<F> {
         result
     }
 
+    #[track_caller]
     #[inline]
     #[must_use]
     pub fn from_variable_checked<CS: ConstraintSystem<F>>(cs: &mut CS, variable: Variable) -> Self {
@@ -505,6 +513,7 @@ impl<F: SmallField> UInt32<F> {
         bytes.map(|el| unsafe { UInt8::from_variable_unchecked(el) })
     }
 
+    #[track_caller]
     #[must_use]
     pub fn from_le_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 4]) -> Self {
         let bytes = bytes.map(|el| el.get_variable());
@@ -531,6 +540,7 @@ impl<F: SmallField> UInt32<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn from_be_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 4]) -> Self {
         let mut le_bytes = bytes;
@@ -736,6 +746,7 @@ impl<F: SmallField> UInt32<F> {
         Self::from_variable_checked(cs, var)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_from_closure_and_dependencies<
         CS: ConstraintSystem<F>,
@@ -896,6 +907,33 @@ impl<F: SmallField> CSPlaceholder<F> for UInt32<F> {
     }
 }
 
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for UInt32<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    #[inline]
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Num::enforce_equal(
+            cs,
+            &Num::from_variable(a.variable),
+            &Num::from_variable(b.variable),
+        )
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for UInt32<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, *b);
+
+        borrow
+    }
+}
+
 // #[cfg(test)]
 // mod test {
 //     use crate::cs::traits::cs::{EmptyGatesConfiguration, GatesConfigulation};
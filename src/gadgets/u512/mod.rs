@@ -62,6 +62,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt512<F> {
         (U256::zero(), U256::zero())
     }
 
+    #[track_caller]
     #[inline(always)]
     #[must_use]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
@@ -72,11 +73,19 @@ impl<F: SmallField> CSAllocatable<F> for UInt512<F> {
         Self { inner: as_u32 }
     }
 
+    #[track_caller]
     #[must_use]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         let chunks = decompose_u512_as_u32x16(witness);
-        let chunks = chunks.map(|el| UInt32::allocate_checked(cs, el));
-        Self { inner: chunks }
+        // allocate in a loop rather than a closure, so call sites are the ones of the caller
+        let mut inner = [std::mem::MaybeUninit::uninit(); 16];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocate_checked(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 }
 
@@ -91,6 +100,7 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt512<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -124,6 +134,7 @@ impl<F: SmallField> Selectable<F> for UInt512<F> {
 }
 
 impl<F: SmallField> UInt512<F> {
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(
         cs: &mut CS,
@@ -132,10 +143,17 @@ impl<F: SmallField> UInt512<F> {
         debug_assert!(F::CAPACITY_BITS >= 32);
 
         let chunks = decompose_u512_as_u32x16(constant);
-        let chunks = chunks.map(|el| UInt32::allocated_constant(cs, el));
-        Self { inner: chunks }
+        let mut inner = [std::mem::MaybeUninit::uninit(); 16];
+        for (dst, el) in inner.iter_mut().zip(chunks) {
+            dst.write(UInt32::allocated_constant(cs, el));
+        }
+
+        let inner = unsafe { inner.map(|el| el.assume_init()) };
+
+        Self { inner }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_from_closure_and_dependencies<
         CS: ConstraintSystem<F>,
@@ -167,6 +185,7 @@ impl<F: SmallField> UInt512<F> {
         Self { inner: chunks }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocated_constant(cs, (U256::zero(), U256::zero()))
@@ -242,6 +261,7 @@ impl<F: SmallField> UInt512<F> {
         Boolean::multi_and(cs, &equals)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn from_le_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 64]) -> Self {
         let mut inner = [std::mem::MaybeUninit::uninit(); 16];
@@ -254,6 +274,7 @@ impl<F: SmallField> UInt512<F> {
         Self { inner }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn from_be_bytes<CS: ConstraintSystem<F>>(cs: &mut CS, bytes: [UInt8<F>; 64]) -> Self {
         let mut inner = [std::mem::MaybeUninit::uninit(); 16];
@@ -395,3 +416,27 @@ impl<F: SmallField> CSPlaceholder<F> for UInt512<F> {
         Self::zero(cs)
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for UInt512<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        for (a, b) in a.inner.iter().zip(b.inner.iter()) {
+            CircuitEq::enforce_equal(cs, a, b);
+        }
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for UInt512<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, b);
+
+        borrow
+    }
+}
@@ -118,7 +118,7 @@ fn uint8_into_4bit_chunks_unchecked<F: SmallField, CS: ConstraintSystem<F>>(
 }
 
 #[derive(Derivative)]
-#[derivative(Clone, Copy, Debug, Hash)]
+#[derivative(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct UInt8<F: SmallField> {
     pub(crate) variable: Variable,
     pub(crate) _marker: std::marker::PhantomData<F>,
@@ -132,6 +132,7 @@ impl<F: SmallField> CSAllocatable<F> for UInt8<F> {
         0u8
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         let var = cs.alloc_variable_without_value();
@@ -139,11 +140,13 @@ impl<F: SmallField> CSAllocatable<F> for UInt8<F> {
         Self::from_variable_checked(cs, var)
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         Self::allocate_checked(cs, witness)
     }
 
+    #[track_caller]
     #[inline(always)]
     fn allocate_constant<CS: ConstraintSystem<F>>(cs: &mut CS, witness: Self::Witness) -> Self {
         Self::allocated_constant(cs, witness)
@@ -158,6 +161,7 @@ impl<F: SmallField> CSAllocatableExt<F> for UInt8<F> {
     }
 
     // we should be able to allocate without knowing values yet
+    #[track_caller]
     fn create_without_value<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocate_without_value(cs)
     }
@@ -281,6 +285,7 @@ impl<F: SmallField> UInt8<F> {
     }
 
     // this routine is not too efficient because we can allocate more per lookup, so use carefully
+    #[track_caller]
     pub fn from_variable_checked<CS: ConstraintSystem<F>>(cs: &mut CS, variable: Variable) -> Self {
         range_check_u8(cs, variable);
 
@@ -304,6 +309,7 @@ impl<F: SmallField> UInt8<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocated_constant<CS: ConstraintSystem<F>>(cs: &mut CS, constant: u8) -> Self {
         debug_assert!(F::CAPACITY_BITS >= 8);
@@ -316,11 +322,13 @@ impl<F: SmallField> UInt8<F> {
         }
     }
 
+    #[track_caller]
     #[must_use]
     pub fn zero<CS: ConstraintSystem<F>>(cs: &mut CS) -> Self {
         Self::allocated_constant(cs, 0)
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_checked<CS: ConstraintSystem<F>>(cs: &mut CS, witness: u8) -> Self {
         let a = cs.alloc_single_variable_from_witness(F::from_u64_with_reduction(witness as u64));
@@ -334,6 +342,7 @@ impl<F: SmallField> UInt8<F> {
         a
     }
 
+    #[track_caller]
     #[must_use]
     pub fn allocate_pair<CS: ConstraintSystem<F>>(cs: &mut CS, pair: [u8; 2]) -> [Self; 2] {
         let a = cs.alloc_single_variable_from_witness(F::from_u64_with_reduction(pair[0] as u64));
@@ -586,3 +595,30 @@ impl<F: SmallField> CSPlaceholder<F> for UInt8<F> {
         Self::zero(cs)
     }
 }
+
+use crate::gadgets::traits::circuit_eq::CircuitEq;
+use crate::gadgets::traits::circuit_ord::CircuitOrd;
+
+impl<F: SmallField> CircuitEq<F> for UInt8<F> {
+    #[inline]
+    fn equals<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        Self::equals(cs, a, b)
+    }
+
+    #[inline]
+    fn enforce_equal<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) {
+        Num::enforce_equal(
+            cs,
+            &Num::from_variable(a.variable),
+            &Num::from_variable(b.variable),
+        )
+    }
+}
+
+impl<F: SmallField> CircuitOrd<F> for UInt8<F> {
+    fn less_than<CS: ConstraintSystem<F>>(cs: &mut CS, a: &Self, b: &Self) -> Boolean<F> {
+        let (_, borrow) = a.overflowing_sub(cs, b);
+
+        borrow
+    }
+}
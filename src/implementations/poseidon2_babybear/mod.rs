@@ -0,0 +1,244 @@
+//! Poseidon2 over BabyBear with the state width of 16, rate of 8 and capacity of 8.
+//!
+//! The permutation is a straightforward scalar implementation that uses the same round structure
+//! as the Goldilocks one. Packed `MixedBabyBear` is only used by the prover for polynomial
+//! arithmetic, the sponge state is not vectorized.
+use crate::algebraic_props::round_function::*;
+use crate::field::babybear::BabyBearField;
+use crate::field::traits::field::Field;
+use derivative::*;
+use unroll::unroll_for_loops;
+
+pub mod params;
+
+use self::params::*;
+
+#[derive(Derivative, serde::Serialize, serde::Deserialize)]
+#[derivative(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Poseidon2BabyBear;
+
+#[inline(always)]
+fn apply_non_linearity(el: &mut BabyBearField) {
+    // x^7
+    let mut t = *el;
+    el.square();
+    t.mul_assign(&*el);
+    el.square();
+    el.mul_assign(&t);
+}
+
+#[inline(always)]
+#[unroll_for_loops]
+fn external_matrix_mul(state: &mut [BabyBearField; STATE_WIDTH]) {
+    // apply M4 to every block of 4 elements, and then add a sum of the same
+    // positions over all the blocks, that gives circ(2 * M4, M4, M4, M4)
+    let mut blocks = [[BabyBearField::ZERO; 4]; 4];
+    for block in 0..4 {
+        for row in 0..4 {
+            for column in 0..4 {
+                let mut tmp = state[block * 4 + column];
+                tmp.mul_assign(&EXTERNAL_MDS_MATRIX_BLOCK[row][column]);
+                blocks[block][row].add_assign(&tmp);
+            }
+        }
+    }
+
+    let mut sums = [BabyBearField::ZERO; 4];
+    for block in 0..4 {
+        for row in 0..4 {
+            sums[row].add_assign(&blocks[block][row]);
+        }
+    }
+
+    for block in 0..4 {
+        for row in 0..4 {
+            state[block * 4 + row] = blocks[block][row];
+            state[block * 4 + row].add_assign(&sums[row]);
+        }
+    }
+}
+
+#[inline(always)]
+#[unroll_for_loops]
+fn inner_matrix_mul(state: &mut [BabyBearField; STATE_WIDTH]) {
+    let mut rowwise_sum = BabyBearField::ZERO;
+    for i in 0..16 {
+        rowwise_sum.add_assign(&state[i]);
+    }
+
+    for i in 0..16 {
+        state[i].mul_assign(&INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE[i]);
+        state[i].add_assign(&rowwise_sum);
+    }
+}
+
+#[inline(always)]
+#[unroll_for_loops]
+fn full_round(state: &mut [BabyBearField; STATE_WIDTH], round_constants: &[BabyBearField; 16]) {
+    for i in 0..16 {
+        state[i].add_assign(&round_constants[i]);
+        apply_non_linearity(&mut state[i]);
+    }
+    external_matrix_mul(state);
+}
+
+pub fn poseidon2_permutation(state: &mut [BabyBearField; STATE_WIDTH]) {
+    external_matrix_mul(state);
+
+    for round_constants in FULL_ROUND_CONSTANTS[..HALF_NUM_FULL_ROUNDS].iter() {
+        full_round(state, round_constants);
+    }
+
+    for round_constant in PARTIAL_ROUND_CONSTANTS.iter() {
+        state[0].add_assign(round_constant);
+        apply_non_linearity(&mut state[0]);
+        inner_matrix_mul(state);
+    }
+
+    for round_constants in FULL_ROUND_CONSTANTS[HALF_NUM_FULL_ROUNDS..].iter() {
+        full_round(state, round_constants);
+    }
+}
+
+impl AlgebraicRoundFunctionWithParams<BabyBearField, 8, 16, 8> for Poseidon2BabyBear {
+    #[inline(always)]
+    fn round_function(&self, state: &mut [BabyBearField; 16]) {
+        poseidon2_permutation(state);
+    }
+    #[inline(always)]
+    fn initial_state(&self) -> [BabyBearField; 16] {
+        [BabyBearField::ZERO; STATE_WIDTH]
+    }
+    #[inline(always)]
+    fn specialize_for_len(&self, len: u32, state: &mut [BabyBearField; 16]) {
+        // as described in the original Poseidon paper we use
+        // the last element of the state
+        state[15] = BabyBearField::from_u64_with_reduction(len as u64);
+    }
+    #[unroll_for_loops]
+    #[inline(always)]
+    fn absorb_into_state(
+        &self,
+        state: &mut [BabyBearField; 16],
+        to_absorb: &[BabyBearField; 8],
+        mode: AbsorptionMode,
+    ) {
+        match mode {
+            AbsorptionMode::Overwrite => {
+                let mut i = 0;
+                while i < 8 {
+                    state[i] = to_absorb[i];
+                    i += 1;
+                }
+            }
+            AbsorptionMode::Addition => {
+                let mut i = 0;
+                while i < 8 {
+                    state[i].add_assign(&to_absorb[i]);
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn state_get_commitment<'a>(&self, state: &'a [BabyBearField; 16]) -> &'a [BabyBearField] {
+        &state[0..8]
+    }
+
+    #[inline(always)]
+    fn state_into_commitment_fixed<const N: usize>(
+        &self,
+        state: &[BabyBearField; 16],
+    ) -> [BabyBearField; N] {
+        debug_assert!(N <= 8);
+        let mut result = [BabyBearField::ZERO; N];
+        result.copy_from_slice(&state[..N]);
+
+        result
+    }
+}
+
+impl AlgebraicRoundFunction<BabyBearField, 8, 16, 8> for Poseidon2BabyBear {
+    #[inline(always)]
+    fn round_function(state: &mut [BabyBearField; 16]) {
+        poseidon2_permutation(state);
+    }
+    #[inline(always)]
+    fn initial_state() -> [BabyBearField; 16] {
+        [BabyBearField::ZERO; STATE_WIDTH]
+    }
+    #[inline(always)]
+    fn specialize_for_len(len: u32, state: &mut [BabyBearField; 16]) {
+        // as described in the original Poseidon paper we use
+        // the last element of the state
+        state[15] = BabyBearField::from_u64_with_reduction(len as u64);
+    }
+    #[inline(always)]
+    #[unroll_for_loops]
+    fn absorb_into_state<M: AbsorptionModeTrait<BabyBearField>>(
+        state: &mut [BabyBearField; 16],
+        to_absorb: &[BabyBearField; 8],
+    ) {
+        for i in 0..8 {
+            M::absorb(&mut state[i], &to_absorb[i]);
+        }
+    }
+
+    #[inline(always)]
+    fn state_into_commitment<const N: usize>(state: &[BabyBearField; 16]) -> [BabyBearField; N] {
+        debug_assert!(N <= 8);
+        let mut result = [BabyBearField::ZERO; N];
+        result.copy_from_slice(&state[..N]);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn permutation_by_definition(state: &mut [BabyBearField; STATE_WIDTH]) {
+        fn matrix_mul(
+            state: &mut [BabyBearField; STATE_WIDTH],
+            matrix: &[[BabyBearField; STATE_WIDTH]; STATE_WIDTH],
+        ) {
+            let input = *state;
+            for (dst, row) in state.iter_mut().zip(matrix.iter()) {
+                *dst = BabyBearField::ZERO;
+                for (coeff, el) in row.iter().zip(input.iter()) {
+                    let mut tmp = *coeff;
+                    tmp.mul_assign(el);
+                    dst.add_assign(&tmp);
+                }
+            }
+        }
+
+        matrix_mul(state, &EXTERNAL_MDS_MATRIX);
+        for round in 0..NUM_FULL_ROUNDS_TOTAL {
+            if round == HALF_NUM_FULL_ROUNDS {
+                for constant in PARTIAL_ROUND_CONSTANTS.iter() {
+                    state[0].add_assign(constant);
+                    state[0] = state[0].pow_u64(NONLINEARITY_DEGREE as u64);
+                    matrix_mul(state, &INNER_ROUNDS_MATRIX);
+                }
+            }
+            for (el, constant) in state.iter_mut().zip(FULL_ROUND_CONSTANTS[round].iter()) {
+                el.add_assign(constant);
+                *el = el.pow_u64(NONLINEARITY_DEGREE as u64);
+            }
+            matrix_mul(state, &EXTERNAL_MDS_MATRIX);
+        }
+    }
+
+    #[test]
+    fn test_poseidon2_babybear_permutation() {
+        let mut state: [BabyBearField; 16] =
+            std::array::from_fn(|i| BabyBearField::from_u64_with_reduction(i as u64));
+        let mut expected = state;
+        poseidon2_permutation(&mut state);
+        permutation_by_definition(&mut expected);
+        assert_eq!(state, expected);
+    }
+}
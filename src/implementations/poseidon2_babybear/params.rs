@@ -0,0 +1,288 @@
+//! Parameters of the Poseidon2 permutation over BabyBear with the state width of 16.
+//!
+//! Round constants are produced by the Grain LFSR exactly as in the reference
+//! implementation (field = 1, sbox = 0, n = 31, t = 16, R_F = 8, R_P = 13), and
+//! partial rounds only use the first constant of the corresponding row.
+use crate::field::babybear::BabyBearField;
+use crate::field::traits::field::*;
+
+pub const RATE: usize = 8;
+pub const CAPACITY: usize = 8;
+pub const STATE_WIDTH: usize = RATE + CAPACITY;
+
+pub const HALF_NUM_FULL_ROUNDS: usize = 4;
+pub const NUM_FULL_ROUNDS_TOTAL: usize = HALF_NUM_FULL_ROUNDS * 2;
+pub const NUM_PARTIAL_ROUNDS: usize = 13;
+pub const TOTAL_NUM_ROUNDS: usize = NUM_FULL_ROUNDS_TOTAL + NUM_PARTIAL_ROUNDS;
+
+pub const NONLINEARITY_DEGREE: usize = 7;
+
+pub const EXTERNAL_MDS_MATRIX_BLOCK: [[BabyBearField; 4]; 4] = [
+    [
+        BabyBearField(5),
+        BabyBearField(7),
+        BabyBearField(1),
+        BabyBearField(3),
+    ],
+    [
+        BabyBearField(4),
+        BabyBearField(6),
+        BabyBearField(1),
+        BabyBearField(1),
+    ],
+    [
+        BabyBearField(1),
+        BabyBearField(3),
+        BabyBearField(5),
+        BabyBearField(7),
+    ],
+    [
+        BabyBearField(1),
+        BabyBearField(1),
+        BabyBearField(4),
+        BabyBearField(6),
+    ],
+];
+
+// -2, 1, 2, 1/2, 3, 4, -1/2, -3, -4, 1/2^8, 1/4, 1/8, 1/2^27, -1/2^8, -1/16, -1/2^27.
+// Characteristic polynomials of M_I^k are irreducible for k up to 2 * STATE_WIDTH, so
+// there are no invariant subspaces over the partial rounds
+pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE: [BabyBearField; STATE_WIDTH] = [
+    BabyBearField(2013265919),
+    BabyBearField(1),
+    BabyBearField(2),
+    BabyBearField(1006632961),
+    BabyBearField(3),
+    BabyBearField(4),
+    BabyBearField(1006632960),
+    BabyBearField(2013265918),
+    BabyBearField(2013265917),
+    BabyBearField(2005401601),
+    BabyBearField(1509949441),
+    BabyBearField(1761607681),
+    BabyBearField(2013265906),
+    BabyBearField(7864320),
+    BabyBearField(125829120),
+    BabyBearField(15),
+];
+
+pub const INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS: [BabyBearField; STATE_WIDTH] = const {
+    let mut result = [BabyBearField::ZERO; STATE_WIDTH];
+    let mut i = 0;
+    while i < STATE_WIDTH {
+        result[i] = BabyBearField(
+            (INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS_MINUS_ONE[i].0 + 1) % BabyBearField::ORDER,
+        );
+        i += 1;
+    }
+
+    result
+};
+
+pub const EXTERNAL_MDS_MATRIX: [[BabyBearField; STATE_WIDTH]; STATE_WIDTH] = const {
+    let mut result = [[BabyBearField::ZERO; STATE_WIDTH]; STATE_WIDTH];
+    let mut block_row = 0;
+    while block_row < 4 {
+        let mut block_column = 0;
+        while block_column < 4 {
+            let mut inner_row = 0;
+            while inner_row < 4 {
+                let mut inner_column = 0;
+                while inner_column < 4 {
+                    // so it's block circulant
+                    let should_double = block_row == block_column;
+
+                    let row = block_row * 4 + inner_row;
+                    let column = block_column * 4 + inner_column;
+
+                    let mut coeff = EXTERNAL_MDS_MATRIX_BLOCK[inner_row][inner_column].0;
+                    if should_double {
+                        coeff *= 2;
+                    }
+                    result[row][column] = BabyBearField(coeff);
+                    inner_column += 1;
+                }
+                inner_row += 1;
+            }
+
+            block_column += 1;
+        }
+        block_row += 1;
+    }
+
+    result
+};
+
+pub const INNER_ROUNDS_MATRIX: [[BabyBearField; STATE_WIDTH]; STATE_WIDTH] = const {
+    let mut result = [[BabyBearField::ONE; STATE_WIDTH]; STATE_WIDTH];
+    let mut i = 0;
+    while i < STATE_WIDTH {
+        result[i][i] = INNER_ROUNDS_MATRIX_DIAGONAL_ELEMENTS[i];
+        i += 1;
+    }
+
+    result
+};
+
+pub const FULL_ROUND_CONSTANTS: [[BabyBearField; STATE_WIDTH]; NUM_FULL_ROUNDS_TOTAL] = [
+    [
+        BabyBearField(0x69cbb6af),
+        BabyBearField(0x46ad93f9),
+        BabyBearField(0x60a00f4e),
+        BabyBearField(0x6b1297cd),
+        BabyBearField(0x23189afe),
+        BabyBearField(0x732e7bef),
+        BabyBearField(0x72c246de),
+        BabyBearField(0x2c941900),
+        BabyBearField(0x0557eede),
+        BabyBearField(0x1580496f),
+        BabyBearField(0x3a3ea77b),
+        BabyBearField(0x54f3f271),
+        BabyBearField(0x0f49b029),
+        BabyBearField(0x47872fe1),
+        BabyBearField(0x221e2e36),
+        BabyBearField(0x1ab7202e),
+    ],
+    [
+        BabyBearField(0x487779a6),
+        BabyBearField(0x3851c9d8),
+        BabyBearField(0x38dc17c0),
+        BabyBearField(0x209f8849),
+        BabyBearField(0x268dcee8),
+        BabyBearField(0x350c48da),
+        BabyBearField(0x5b9ad32e),
+        BabyBearField(0x0523272b),
+        BabyBearField(0x3f89055b),
+        BabyBearField(0x01e894b2),
+        BabyBearField(0x13ddedde),
+        BabyBearField(0x1b2ef334),
+        BabyBearField(0x7507d8b4),
+        BabyBearField(0x6ceeb94e),
+        BabyBearField(0x52eb6ba2),
+        BabyBearField(0x50642905),
+    ],
+    [
+        BabyBearField(0x05453f3f),
+        BabyBearField(0x06349efc),
+        BabyBearField(0x6922787c),
+        BabyBearField(0x04bfff9c),
+        BabyBearField(0x768c714a),
+        BabyBearField(0x3e9ff21a),
+        BabyBearField(0x15737c9c),
+        BabyBearField(0x2229c807),
+        BabyBearField(0x0d47f88c),
+        BabyBearField(0x097e0ecc),
+        BabyBearField(0x27eadba0),
+        BabyBearField(0x2d7d29e4),
+        BabyBearField(0x3502aaa0),
+        BabyBearField(0x0f475fd7),
+        BabyBearField(0x29fbda49),
+        BabyBearField(0x018afffd),
+    ],
+    [
+        BabyBearField(0x0315b618),
+        BabyBearField(0x6d4497d1),
+        BabyBearField(0x1b171d9e),
+        BabyBearField(0x52861abd),
+        BabyBearField(0x2e5d0501),
+        BabyBearField(0x3ec8646c),
+        BabyBearField(0x6e5f250a),
+        BabyBearField(0x148ae8e6),
+        BabyBearField(0x17f5fa4a),
+        BabyBearField(0x3e66d284),
+        BabyBearField(0x0051aa3b),
+        BabyBearField(0x483f7913),
+        BabyBearField(0x2cfe5f15),
+        BabyBearField(0x023427ca),
+        BabyBearField(0x2cc78315),
+        BabyBearField(0x1e36ea47),
+    ],
+    [
+        BabyBearField(0x366cb7ec),
+        BabyBearField(0x0e6335de),
+        BabyBearField(0x5e1374ca),
+        BabyBearField(0x493cf6d2),
+        BabyBearField(0x2ffe3703),
+        BabyBearField(0x19dd3b51),
+        BabyBearField(0x3d64878f),
+        BabyBearField(0x3ef43ee8),
+        BabyBearField(0x64723e7c),
+        BabyBearField(0x4fe5418a),
+        BabyBearField(0x0f7b671d),
+        BabyBearField(0x3f3adb8c),
+        BabyBearField(0x1830fd89),
+        BabyBearField(0x5b15366e),
+        BabyBearField(0x3ca9204d),
+        BabyBearField(0x149cee3c),
+    ],
+    [
+        BabyBearField(0x547bb959),
+        BabyBearField(0x4d6a44a0),
+        BabyBearField(0x771612ca),
+        BabyBearField(0x3f5bdd26),
+        BabyBearField(0x23a3d984),
+        BabyBearField(0x170b07bd),
+        BabyBearField(0x5a2a5094),
+        BabyBearField(0x6e7e68b4),
+        BabyBearField(0x1f3c8320),
+        BabyBearField(0x0ffbb8b6),
+        BabyBearField(0x5ebe7442),
+        BabyBearField(0x45ffc700),
+        BabyBearField(0x64d1f7b6),
+        BabyBearField(0x1b30b661),
+        BabyBearField(0x586ea500),
+        BabyBearField(0x503111fd),
+    ],
+    [
+        BabyBearField(0x72b41cf7),
+        BabyBearField(0x6468ad65),
+        BabyBearField(0x64c713b1),
+        BabyBearField(0x450b1ccd),
+        BabyBearField(0x211e6028),
+        BabyBearField(0x300b11ac),
+        BabyBearField(0x74226654),
+        BabyBearField(0x56308a44),
+        BabyBearField(0x5aa55b4a),
+        BabyBearField(0x52f2bc9a),
+        BabyBearField(0x1a076e50),
+        BabyBearField(0x5eb92894),
+        BabyBearField(0x13baaf6f),
+        BabyBearField(0x4d19b625),
+        BabyBearField(0x30d25297),
+        BabyBearField(0x52f00c13),
+    ],
+    [
+        BabyBearField(0x2a6753d7),
+        BabyBearField(0x40bdd8de),
+        BabyBearField(0x22acbb98),
+        BabyBearField(0x77e41654),
+        BabyBearField(0x23ab6b0f),
+        BabyBearField(0x0629e7d6),
+        BabyBearField(0x000eadff),
+        BabyBearField(0x64cc8e81),
+        BabyBearField(0x364fc012),
+        BabyBearField(0x43cc48cd),
+        BabyBearField(0x611baf29),
+        BabyBearField(0x48bdf828),
+        BabyBearField(0x1a8ab06f),
+        BabyBearField(0x112ee5e0),
+        BabyBearField(0x036e01dc),
+        BabyBearField(0x18106634),
+    ],
+];
+
+pub const PARTIAL_ROUND_CONSTANTS: [BabyBearField; NUM_PARTIAL_ROUNDS] = [
+    BabyBearField(0x5a8053c0),
+    BabyBearField(0x76a859a0),
+    BabyBearField(0x1448bc54),
+    BabyBearField(0x0eba33ba),
+    BabyBearField(0x1d7c2824),
+    BabyBearField(0x1cb929e6),
+    BabyBearField(0x16dd2e49),
+    BabyBearField(0x0d8eacbc),
+    BabyBearField(0x27c99e66),
+    BabyBearField(0x4b1392b6),
+    BabyBearField(0x02d04b6d),
+    BabyBearField(0x1d7cd264),
+    BabyBearField(0x0f8b2954),
+];
@@ -51,6 +51,7 @@
 #![feature(return_position_impl_trait_in_trait)]
 #![feature(type_changing_struct_update)]
 #![feature(slice_flatten)]
+#![feature(array_methods)]
 
 pub mod algebraic_props;
 pub mod config;
@@ -99,3 +99,76 @@ impl<'de, B: BigArraySerde<'de>> serde::Deserialize<'de> for BigArrayWrapper<'de
         Ok(Self(new, std::marker::PhantomData))
     }
 }
+
+// Same as `crate::utils::serialize_vec_with_allocator`, for vectors of arrays of any length,
+// e.g. of polynomials in extension, where every coefficient is a separate column
+pub fn serialize_vec_of_arrays_with_allocator<
+    T: serde::Serialize + serde::de::DeserializeOwned,
+    S,
+    A: crate::cs::traits::GoodAllocator,
+    const N: usize,
+>(
+    t: &Vec<[T; N], A>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut seq = serializer.serialize_seq(Some(t.len()))?;
+    use serde::ser::SerializeSeq;
+
+    for el in t.iter() {
+        seq.serialize_element(&BigArrayRefWrapper(el))?;
+    }
+    seq.end()
+}
+
+pub fn deserialize_vec_of_arrays_with_allocator<
+    'de,
+    D,
+    T: serde::Serialize + serde::Deserialize<'de>,
+    A: crate::cs::traits::GoodAllocator,
+    const N: usize,
+>(
+    deserializer: D,
+) -> Result<Vec<[T; N], A>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct VecVisitor<'de, T, A, const M: usize> {
+        element: std::marker::PhantomData<(&'de (), T, A)>,
+    }
+
+    impl<'de, T, A: crate::cs::traits::GoodAllocator, const M: usize> serde::de::Visitor<'de>
+        for VecVisitor<'de, T, A, M>
+    where
+        T: serde::Serialize + serde::Deserialize<'de>,
+    {
+        type Value = Vec<[T; M], A>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a vector of arrays")
+        }
+
+        fn visit_seq<B>(self, mut seq: B) -> Result<Self::Value, B::Error>
+        where
+            B: serde::de::SeqAccess<'de>,
+        {
+            let expected_len = seq.size_hint().unwrap_or(0);
+            let mut vector = Vec::with_capacity_in(expected_len, A::default());
+            for i in 0..expected_len {
+                let el: BigArrayWrapper<'de, [T; M]> = seq
+                    .next_element()?
+                    .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                vector.push(el.0);
+            }
+
+            Ok(vector)
+        }
+    }
+
+    let visitor = VecVisitor::<'de, T, A, N> {
+        element: std::marker::PhantomData,
+    };
+    deserializer.deserialize_seq(visitor)
+}
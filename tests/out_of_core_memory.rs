@@ -0,0 +1,194 @@
+#![feature(allocator_api)]
+
+// Out-of-core proving must lower peak heap usage, so we count allocations of the whole test binary.
+// This lives in its own test binary, as the allocator is global and other tests running
+// in parallel would be counted too.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use boojum::algebraic_props::round_function::AbsorptionModeOverwrite;
+use boojum::algebraic_props::sponge::GoldilocksPoseidonSponge;
+use boojum::config::DevCSConfig;
+use boojum::cs::cs_builder::new_builder;
+use boojum::cs::cs_builder_reference::CsReferenceImplementationBuilder;
+use boojum::cs::gates::*;
+use boojum::cs::implementations::out_of_core::OutOfCoreConfig;
+use boojum::cs::implementations::pow::NoPow;
+use boojum::cs::implementations::prover::ProofConfig;
+use boojum::cs::implementations::reference_cs::CSReferenceAssembly;
+use boojum::cs::implementations::transcript::GoldilocksPoisedonTranscript;
+use boojum::cs::traits::cs::ConstraintSystem;
+use boojum::cs::traits::gate::GatePlacementStrategy;
+use boojum::cs::CSGeometry;
+use boojum::dag::CircuitResolverOpts;
+use boojum::field::goldilocks::{GoldilocksExt2, GoldilocksField};
+use boojum::field::{Field, U64Representable};
+use boojum::worker::Worker;
+
+type F = GoldilocksField;
+type P = GoldilocksField;
+type TR = GoldilocksPoisedonTranscript;
+type H = GoldilocksPoseidonSponge<AbsorptionModeOverwrite>;
+
+struct PeakTrackingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakTrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if ptr.is_null() == false {
+            let current = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if new_ptr.is_null() == false {
+            if new_size > layout.size() {
+                let current = ALLOCATED.fetch_add(new_size - layout.size(), Ordering::SeqCst)
+                    + (new_size - layout.size());
+                PEAK.fetch_max(current, Ordering::SeqCst);
+            } else {
+                ALLOCATED.fetch_sub(layout.size() - new_size, Ordering::SeqCst);
+            }
+        }
+
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakTrackingAllocator = PeakTrackingAllocator;
+
+// Returns peak heap usage during `f` on top of what was allocated before it
+fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    let result = f();
+    let peak = PEAK.load(Ordering::SeqCst);
+
+    (result, peak - baseline)
+}
+
+// Wide trace, so LDEs of the witness and second stage dominate everything else
+fn synthesize() -> CSReferenceAssembly<F, P, DevCSConfig> {
+    let geometry = CSGeometry {
+        num_columns_under_copy_permutation: 64,
+        num_witness_columns: 0,
+        num_constant_columns: 2,
+        max_allowed_constraint_degree: 4,
+    };
+    let num_fmas = 20_000;
+    let max_variables = 3 * num_fmas + 16;
+    let max_trace_len = 1 << 12;
+
+    let builder_impl =
+        CsReferenceImplementationBuilder::<F, P, DevCSConfig>::new(geometry, max_trace_len);
+    let builder = new_builder::<_, F>(builder_impl);
+    let builder = ConstantsAllocatorGate::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder = FmaGateInBaseFieldWithoutConstant::configure_builder(
+        builder,
+        GatePlacementStrategy::UseGeneralPurposeColumns,
+    );
+    let builder =
+        NopGate::configure_builder(builder, GatePlacementStrategy::UseGeneralPurposeColumns);
+    let mut cs = builder.build(CircuitResolverOpts::new(max_variables));
+
+    let mut previous = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(1));
+    for _ in 0..num_fmas {
+        let b = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(2));
+        let c = cs.alloc_single_variable_from_witness(F::from_u64_unchecked(3));
+
+        previous = FmaGateInBaseFieldWithoutConstant::compute_fma(
+            &mut cs,
+            F::TWO,
+            (previous, b),
+            F::MINUS_ONE,
+            c,
+        );
+    }
+    cs.allocate_constant(F::from_u64_unchecked(3));
+    cs.pad_and_shrink();
+
+    cs.into_assembly::<std::alloc::Global>()
+}
+
+#[test]
+fn out_of_core_lowers_peak_allocation() {
+    let worker = Worker::new_with_num_threads(4);
+    let proof_config = ProofConfig {
+        fri_lde_factor: 8,
+        pow_bits: 0,
+        ..Default::default()
+    };
+
+    let spill_directory = std::env::temp_dir().join("boojum_out_of_core_lowers_peak_allocation");
+    let out_of_core = OutOfCoreConfig::new(spill_directory);
+
+    let mut peaks = vec![];
+    let mut proofs = vec![];
+    for out_of_core in [None, Some(&out_of_core)] {
+        // setup and witness are the same in both modes, so those are not measured
+        let mut assembly = synthesize();
+        let setup_base = assembly.create_base_setup(&worker, &mut ());
+        let (setup, vk, setup_tree) = assembly.materialize_setup_storage_and_vk::<H>(
+            proof_config.fri_lde_factor,
+            proof_config.merkle_tree_cap_size,
+            &worker,
+            &mut (),
+        );
+        let witness_set = assembly.take_witness(&worker);
+
+        let (proof, peak) = measure_peak(|| match out_of_core {
+            None => assembly.prove_cpu_basic::<2, GoldilocksExt2, TR, H, NoPow>(
+                &worker,
+                witness_set,
+                &setup_base,
+                &setup,
+                &setup_tree,
+                &vk,
+                proof_config.clone(),
+                (),
+            ),
+            Some(out_of_core) => assembly.prove_cpu_out_of_core::<2, GoldilocksExt2, TR, H, NoPow>(
+                &worker,
+                witness_set,
+                &setup_base,
+                &setup,
+                &setup_tree,
+                &vk,
+                proof_config.clone(),
+                (),
+                out_of_core,
+            ),
+        });
+
+        peaks.push(peak);
+        proofs.push(proof);
+    }
+
+    let [in_memory_peak, spilled_peak] = [peaks[0], peaks[1]];
+    assert_eq!(proofs[0].to_bytes(), proofs[1].to_bytes());
+    // all three oracles are in memory together without spilling, while with spilling at most
+    // one full LDE is in memory at a time
+    assert!(
+        spilled_peak * 2 < in_memory_peak,
+        "spilling does not lower peak allocation: {} bytes in memory, {} bytes with spilling",
+        in_memory_peak,
+        spilled_peak
+    );
+}